@@ -0,0 +1,501 @@
+//! Population baselines: GTO frequencies for one position pair, aggregated
+//! over the batch-solved flops, for diffing against tracker pool stats
+//! ("BB folds 52% to a BTN c-bet").
+//!
+//! Each stat is the frequency of an action at a decision, conditional on
+//! reaching it. A flop contributes in proportion to its flop weight (the raw
+//! flops it stands for) times how often its solution reaches the decision, so
+//! a flop where the solver rarely bets a size barely moves that size's
+//! numbers. Rows show the conditional frequency next to that reach, and count
+//! flops whose reach is under `RARE_REACH`.
+//!
+//! Flop decisions use the per-combo strategies, with the defender's combos
+//! weighted by their reach to the decision. Turn probes and delayed c-bets
+//! come from the turn template, whose bucket strategies are shared by every
+//! flop line that gets to the turn; they're read after a flop check-through
+//! and averaged over buckets.
+
+use colored::Colorize;
+
+use crate::batch::batch_boards;
+use crate::config::BoardSet;
+use crate::flop_enumerator::flop_weight;
+use crate::flop_solver::{FlopNodeStrategy, FlopSolution, TemplateBucketStrategy, TreeEdge};
+use crate::notes::parse_pot_type;
+use crate::preflop_solver::Position;
+use crate::strategy::PotType;
+
+/// Decisions reached less often than this on a flop are flagged as rare.
+pub const RARE_REACH: f64 = 0.02;
+
+/// Bet sizes within this fraction of the pot count as the requested size.
+const SIZE_TOLERANCE: f64 = 0.05;
+
+/// Which stats `gto report baseline` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineStat {
+    FoldVsCbet,
+    TurnProbe,
+    DelayedCbet,
+    All,
+}
+
+impl BaselineStat {
+    pub fn parse(s: &str) -> Result<BaselineStat, String> {
+        match s.trim().to_lowercase().as_str() {
+            "fold-vs-cbet" => Ok(BaselineStat::FoldVsCbet),
+            "turn-probe" | "probe" => Ok(BaselineStat::TurnProbe),
+            "delayed-cbet" => Ok(BaselineStat::DelayedCbet),
+            "all" => Ok(BaselineStat::All),
+            _ => Err(format!(
+                "Invalid stat '{}'. Valid: fold-vs-cbet, turn-probe, delayed-cbet, all",
+                s
+            )),
+        }
+    }
+
+    fn includes(&self, stat: BaselineStat) -> bool {
+        *self == BaselineStat::All || *self == stat
+    }
+}
+
+/// Parse `--sizes` like "33,75" into pot fractions.
+pub fn parse_sizes(s: &str) -> Result<Vec<f64>, String> {
+    s.split(',')
+        .map(|t| {
+            let t = t.trim().trim_end_matches('%');
+            match t.parse::<f64>() {
+                Ok(v) if v > 0.0 => Ok(v / 100.0),
+                _ => Err(format!("Invalid bet size '{}'. Use percent of pot, e.g. 33,75", t)),
+            }
+        })
+        .collect()
+}
+
+/// Reach-weighted aggregate of one stat across flops.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatAccumulator {
+    total_weight: f64,
+    reach_weight: f64,
+    freq_weight: f64,
+    pub flops: usize,
+    pub rare: usize,
+}
+
+impl StatAccumulator {
+    /// Add one flop: its flop weight, how often its solution reaches the
+    /// decision, and the action's frequency there. Flops that never reach it
+    /// (e.g. the size isn't in the tree) pass `reach` 0.
+    pub fn add(&mut self, flop_weight: f64, reach: f64, freq: f64) {
+        self.total_weight += flop_weight;
+        if reach <= 0.0 {
+            return;
+        }
+        self.reach_weight += flop_weight * reach;
+        self.freq_weight += flop_weight * reach * freq;
+        self.flops += 1;
+        if reach < RARE_REACH {
+            self.rare += 1;
+        }
+    }
+
+    /// Frequency conditional on reaching the decision, or `None` when no
+    /// flop reaches it.
+    pub fn frequency(&self) -> Option<f64> {
+        (self.reach_weight > 0.0).then(|| self.freq_weight / self.reach_weight)
+    }
+
+    /// How often the decision is reached, averaged over flops by weight.
+    pub fn reach(&self) -> f64 {
+        if self.total_weight > 0.0 {
+            self.reach_weight / self.total_weight
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Rows in print order, keyed by label.
+#[derive(Default)]
+struct Rows(Vec<(String, StatAccumulator)>);
+
+impl Rows {
+    fn add(&mut self, label: String, flop_weight: f64, reach: f64, freq: f64) {
+        match self.0.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, acc)) => acc.add(flop_weight, reach, freq),
+            None => {
+                let mut acc = StatAccumulator::default();
+                acc.add(flop_weight, reach, freq);
+                self.0.push((label, acc));
+            }
+        }
+    }
+}
+
+/// True when the in-position seat was the preflop aggressor: the opener in
+/// single-raised and 4-bet pots, the 3-bettor in 3-bet pots.
+pub fn aggressor_is_ip(oop: Position, ip: Position, pot_type: PotType) -> bool {
+    // Earlier seats act first preflop, so the earlier seat opened
+    let ip_opened = (ip as u8) < (oop as u8);
+    match pot_type {
+        PotType::Srp | PotType::FourBet => ip_opened,
+        PotType::ThreeBet => !ip_opened,
+    }
+}
+
+fn child(edges: &[TreeEdge], from: u16, action: &str) -> Option<u16> {
+    edges.iter().find(|e| e.from == from && e.action == action).map(|e| e.to)
+}
+
+/// The decision with no parent edge.
+fn root_id(mut ids: impl Iterator<Item = u16>, edges: &[TreeEdge]) -> Option<u16> {
+    ids.find(|id| !edges.iter().any(|e| e.to == *id))
+}
+
+fn flop_strategy(solution: &FlopSolution, node: u16) -> Option<&FlopNodeStrategy> {
+    solution.strategies.iter().find(|s| s.node_id == node)
+}
+
+fn action_index(actions: &[String], action: &str) -> Option<usize> {
+    actions.iter().position(|a| a == action)
+}
+
+/// Mean frequency of `action` over the rows (combos or buckets).
+fn mean_frequency(frequencies: &[Vec<f64>], action: usize) -> f64 {
+    if frequencies.is_empty() {
+        return 0.0;
+    }
+    frequencies.iter().map(|f| f[action]).sum::<f64>() / frequencies.len() as f64
+}
+
+/// Label of the bet closest to `size` × pot, within `SIZE_TOLERANCE`.
+fn bet_action(actions: &[String], pot: f64, size: f64) -> Option<&str> {
+    actions
+        .iter()
+        .filter_map(|a| {
+            let amount: f64 = a.strip_prefix("Bet ")?.parse().ok()?;
+            Some((a.as_str(), (amount / pot - size).abs()))
+        })
+        .filter(|(_, diff)| *diff <= SIZE_TOLERANCE)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(a, _)| a)
+}
+
+/// Share of the defender's reach that folds, calls and raises.
+fn defense(strategy: &FlopNodeStrategy, reach: &[f64]) -> Option<[f64; 3]> {
+    let mut out = [0.0; 3];
+    let mut total = 0.0;
+    for (freqs, &r) in strategy.frequencies.iter().zip(reach) {
+        for (action, &f) in strategy.actions.iter().zip(freqs) {
+            let slot = if action == "Fold" {
+                0
+            } else if action.starts_with("Call") {
+                1
+            } else {
+                2
+            };
+            out[slot] += r * f;
+        }
+        total += r;
+    }
+    (total > 0.0).then(|| out.map(|x| x / total))
+}
+
+/// The flop c-bet decision: the bettor's strategy, how often play gets there,
+/// and the defender's per-combo reach to it.
+struct CbetNode<'a> {
+    bettor: &'a FlopNodeStrategy,
+    prior: f64,
+    defender_reach: Vec<f64>,
+}
+
+/// Labels for one pair, e.g. "BB fold vs BTN c-bet 33%".
+struct Seats {
+    aggressor: String,
+    defender: String,
+    aggressor_ip: bool,
+}
+
+/// Add one flop's stats to `rows`.
+fn add_flop(rows: &mut Rows, solution: &FlopSolution, weight: f64, seats: &Seats, stat: BaselineStat, sizes: &[f64]) {
+    let edges = &solution.flop_tree_edges;
+    let Some(root) = root_id(solution.strategies.iter().map(|s| s.node_id), edges) else {
+        return;
+    };
+    let Some(root_strategy) = flop_strategy(solution, root) else {
+        return;
+    };
+    let check = action_index(&root_strategy.actions, "Check");
+    let ip_after_check = child(edges, root, "Check").and_then(|id| flop_strategy(solution, id).map(|s| (id, s)));
+
+    let cbet = if seats.aggressor_ip {
+        match (check, ip_after_check) {
+            (Some(check), Some((node, bettor))) => {
+                let reach: Vec<f64> = root_strategy.frequencies.iter().map(|f| f[check]).collect();
+                Some((
+                    node,
+                    CbetNode { bettor, prior: mean_frequency(&root_strategy.frequencies, check), defender_reach: reach },
+                ))
+            }
+            _ => None,
+        }
+    } else {
+        let defenders = solution.ip_combos.len();
+        Some((root, CbetNode { bettor: root_strategy, prior: 1.0, defender_reach: vec![1.0; defenders] }))
+    };
+
+    if stat.includes(BaselineStat::FoldVsCbet) {
+        for &size in sizes {
+            let pct = (size * 100.0).round();
+            let cbet_label = format!("{} c-bet {}%", seats.aggressor, pct);
+            let outcome_labels = ["fold", "call", "raise"].map(|a| format!("{} {} vs c-bet {}%", seats.defender, a, pct));
+            let found = cbet.as_ref().and_then(|(node, c)| {
+                let label = bet_action(&c.bettor.actions, solution.starting_pot, size)?;
+                let bet = action_index(&c.bettor.actions, label)?;
+                let freq = mean_frequency(&c.bettor.frequencies, bet);
+                let defense = child(edges, *node, label)
+                    .and_then(|id| flop_strategy(solution, id))
+                    .and_then(|s| defense(s, &c.defender_reach));
+                Some((c.prior, freq, defense))
+            });
+            // A size missing from this flop's tree is never bet there
+            let prior = cbet.as_ref().map_or(0.0, |(_, c)| c.prior);
+            let (prior, freq, defense) = found.unwrap_or((prior, 0.0, None));
+            rows.add(cbet_label, weight, prior, freq);
+            let reach = if defense.is_some() { prior * freq } else { 0.0 };
+            for (label, f) in outcome_labels.into_iter().zip(defense.unwrap_or_default()) {
+                rows.add(label, weight, reach, f);
+            }
+        }
+    }
+
+    if !stat.includes(BaselineStat::TurnProbe) && !stat.includes(BaselineStat::DelayedCbet) {
+        return;
+    }
+    // Flop checks through: OOP checks, then IP checks behind
+    let check_through = match (check, ip_after_check) {
+        (Some(check), Some((_, ip))) => action_index(&ip.actions, "Check")
+            .map(|c| mean_frequency(&root_strategy.frequencies, check) * mean_frequency(&ip.frequencies, c))
+            .unwrap_or(0.0),
+        _ => 0.0,
+    };
+    let turn = TurnTemplate::new(solution);
+    let turn_root = turn.as_ref().map(|t| (t.bet_share(t.root), t.check_share(t.root)));
+    let turn_ip = turn.as_ref().and_then(|t| t.after_check().map(|ip| t.bet_share(ip)));
+
+    if stat.includes(BaselineStat::TurnProbe) && seats.aggressor_ip {
+        let label = format!("{} turn probe", seats.defender);
+        match turn_root {
+            Some((bet, _)) => rows.add(label, weight, check_through, bet),
+            None => rows.add(label, weight, 0.0, 0.0),
+        }
+    }
+    if stat.includes(BaselineStat::DelayedCbet) {
+        let label = format!("{} delayed c-bet", seats.aggressor);
+        let delayed = if seats.aggressor_ip {
+            turn_root.zip(turn_ip).map(|((_, check), bet)| (check_through * check, bet))
+        } else {
+            turn_root.map(|(bet, _)| (check_through, bet))
+        };
+        match delayed {
+            Some((reach, bet)) => rows.add(label, weight, reach, bet),
+            None => rows.add(label, weight, 0.0, 0.0),
+        }
+    }
+}
+
+/// The turn template's first two decisions.
+struct TurnTemplate<'a> {
+    strategies: &'a [TemplateBucketStrategy],
+    edges: &'a [TreeEdge],
+    root: &'a TemplateBucketStrategy,
+}
+
+impl<'a> TurnTemplate<'a> {
+    fn new(solution: &'a FlopSolution) -> Option<TurnTemplate<'a>> {
+        let strategies = &solution.turn_strategies;
+        let edges = &solution.turn_tree_edges;
+        let root = root_id(strategies.iter().map(|s| s.node_id), edges)?;
+        let root = strategies.iter().find(|s| s.node_id == root && s.player == "OOP")?;
+        Some(TurnTemplate { strategies, edges, root })
+    }
+
+    /// IP's decision after OOP checks the turn.
+    fn after_check(&self) -> Option<&'a TemplateBucketStrategy> {
+        let id = child(self.edges, self.root.node_id, "Check")?;
+        self.strategies.iter().find(|s| s.node_id == id)
+    }
+
+    fn bet_share(&self, strategy: &TemplateBucketStrategy) -> f64 {
+        strategy
+            .actions
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.starts_with("Bet"))
+            .map(|(i, _)| mean_frequency(&strategy.frequencies, i))
+            .sum()
+    }
+
+    fn check_share(&self, strategy: &TemplateBucketStrategy) -> f64 {
+        action_index(&strategy.actions, "Check").map_or(0.0, |i| mean_frequency(&strategy.frequencies, i))
+    }
+}
+
+/// `gto report baseline`: aggregate GTO frequencies for one position pair
+/// over the cached batch flops.
+#[allow(clippy::too_many_arguments)]
+pub fn run_baseline_report(
+    stat: &str,
+    oop: &str,
+    ip: &str,
+    sizes: &str,
+    pot_type: &str,
+    stack: f64,
+    all_flops: bool,
+    boardset: Option<&BoardSet>,
+) -> Result<(), String> {
+    let stat = BaselineStat::parse(stat)?;
+    let oop = Position::from_str(oop).ok_or_else(|| format!("Invalid position '{}'", oop))?;
+    let ip = Position::from_str(ip).ok_or_else(|| format!("Invalid position '{}'", ip))?;
+    let sizes = parse_sizes(sizes)?;
+    let pot_type =
+        parse_pot_type(pot_type).ok_or_else(|| format!("Invalid pot type '{}'. Valid: srp, 3bp, 4bp", pot_type))?;
+    if !ip.is_ip_vs(&oop) {
+        return Err(format!("{} is out of position against {}; swap --oop and --ip", ip, oop));
+    }
+    let aggressor_ip = aggressor_is_ip(oop, ip, pot_type);
+    let (aggressor, defender) = if aggressor_ip { (ip, oop) } else { (oop, ip) };
+    let seats = Seats {
+        aggressor: aggressor.as_str().to_string(),
+        defender: defender.as_str().to_string(),
+        aggressor_ip,
+    };
+    let (pot, eff_stack) = pot_type.pot_and_stack_at(stack);
+    let (oop_key, ip_key) = (pot_type.seat_key(oop.as_str()), pot_type.seat_key(ip.as_str()));
+
+    let boards = batch_boards(all_flops, boardset);
+    let total_weight: usize = boards.iter().filter_map(|b| flop_weight(b).ok()).sum();
+    let mut rows = Rows::default();
+    let (mut solved, mut solved_weight, mut no_tree) = (0usize, 0usize, 0usize);
+    for board in &boards {
+        let Some(solution) = FlopSolution::load_cache(board, &oop_key, &ip_key, pot, eff_stack, None) else {
+            continue;
+        };
+        // Solutions cached before tree edges were stored can't be navigated
+        if solution.flop_tree_edges.is_empty() {
+            no_tree += 1;
+            continue;
+        }
+        let weight = flop_weight(board)?;
+        solved += 1;
+        solved_weight += weight;
+        add_flop(&mut rows, &solution, weight as f64, &seats, stat, &sizes);
+    }
+
+    println!();
+    println!(
+        "  {} baseline  |  {} (OOP) vs {} (IP)  |  {}  |  {}bb  |  {} of {} flops ({:.0}% of flop weight)",
+        "GTO".bold(),
+        oop,
+        ip,
+        pot_type.as_str(),
+        stack,
+        solved.to_string().bold(),
+        boards.len(),
+        solved_weight as f64 / total_weight.max(1) as f64 * 100.0,
+    );
+    if let Some(set) = boardset {
+        println!("  Board set: {}", set.label());
+    }
+    if no_tree > 0 {
+        println!("  {}", format!("{} cached flops predate tree edges; re-solve to include them", no_tree).dimmed());
+    }
+    if solved == 0 {
+        println!();
+        println!("  No cached flops for this pair. Run `gto solve batch` first.");
+        println!();
+        return Ok(());
+    }
+    if stat.includes(BaselineStat::TurnProbe) && aggressor_ip {
+        println!("  {}", "Turn stats use the flop solve's turn template, after a flop check-through.".dimmed());
+    } else if stat == BaselineStat::TurnProbe {
+        println!("  {}", format!("No turn probe: the defender ({}) is in position.", defender).dimmed());
+    }
+    print_rows(&rows);
+    Ok(())
+}
+
+fn print_rows(rows: &Rows) {
+    use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Stat", "GTO", "Reached", "Flops"]);
+    for (label, acc) in &rows.0 {
+        let freq = acc.frequency().map_or("-".to_string(), |f| format!("{:.1}%", f * 100.0));
+        let flops = if acc.rare > 0 {
+            format!("{} ({} rare)", acc.flops, acc.rare)
+        } else {
+            acc.flops.to_string()
+        };
+        table.add_row(vec![
+            Cell::new(label),
+            Cell::new(freq).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.1}%", acc.reach() * 100.0)).set_alignment(CellAlignment::Right),
+            Cell::new(flops).set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!();
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    println!(
+        "  {}",
+        format!(
+            "GTO is conditional on reaching the decision; Reached is how often it comes up. Rare: reached under {:.0}% on that flop.",
+            RARE_REACH * 100.0
+        )
+        .dimmed()
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulator_weights_by_flop_and_reach() {
+        let mut acc = StatAccumulator::default();
+        acc.add(24.0, 0.5, 0.4);
+        // Rarely reached: counted, flagged, and barely moves the frequency
+        acc.add(4.0, 0.01, 1.0);
+        // Size not in this flop's tree
+        acc.add(12.0, 0.0, 0.0);
+        let expected = (24.0 * 0.5 * 0.4 + 4.0 * 0.01) / (24.0 * 0.5 + 4.0 * 0.01);
+        assert!((acc.frequency().unwrap() - expected).abs() < 1e-12);
+        assert!((acc.reach() - 12.04 / 40.0).abs() < 1e-12);
+        assert_eq!((acc.flops, acc.rare), (2, 1));
+        assert_eq!(StatAccumulator::default().frequency(), None);
+    }
+
+    #[test]
+    fn test_aggressor_by_pot_type() {
+        use Position::*;
+        assert!(aggressor_is_ip(BB, BTN, PotType::Srp));
+        assert!(!aggressor_is_ip(CO, BTN, PotType::Srp));
+        assert!(aggressor_is_ip(CO, BTN, PotType::ThreeBet));
+        assert!(!aggressor_is_ip(BB, BTN, PotType::ThreeBet));
+        assert!(aggressor_is_ip(BB, BTN, PotType::FourBet));
+    }
+
+    #[test]
+    fn test_bet_action_matches_nearest_size() {
+        let actions: Vec<String> = ["Check", "Bet 2.0", "Bet 4.5", "Bet 97.0"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(bet_action(&actions, 6.0, 0.33), Some("Bet 2.0"));
+        assert_eq!(bet_action(&actions, 6.0, 0.75), Some("Bet 4.5"));
+        assert_eq!(bet_action(&actions, 6.0, 0.5), None);
+        assert_eq!(parse_sizes("33, 75%").unwrap(), vec![0.33, 0.75]);
+        assert!(parse_sizes("33,big").is_err());
+    }
+}
@@ -1,15 +1,29 @@
 //! Batch pre-solve: generates a manifest of position × board × pot-type spots
-//! and solves them sequentially with resumability (skips existing cache files).
+//! and solves them one at a time or, with `--jobs`, several side by side, with
+//! resumability: progress is recorded in a manifest file after every spot, and
+//! spots done or already cached are skipped.
 
+use std::collections::HashMap;
 use std::io::Write;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use colored::Colorize;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::cache::format_size;
+use crate::cache_index::{solver_cache_dir, write_atomic, CacheIndex, SolveLock};
+use crate::cache_key::range_key_of;
+use crate::config::BoardSet;
 use crate::flop_enumerator::generate_canonical_flops;
 use crate::flop_solver::{FlopSolverConfig, FlopSolution, solve_flop};
-use crate::preflop_solver::{Position, PreflopSolution};
-use crate::strategy::{derive_defending_range, derive_opening_range, PotType};
+use crate::prefetch::format_duration;
+use crate::preflop_solver::{Position, PreflopSizing, PreflopSolution};
+use crate::quality::pct_of_pot;
+use crate::strategy::{derive_pot_ranges, parse_matchup, PotType};
 
 // ---------------------------------------------------------------------------
 // Representative flop boards (~50 covering major textures)
@@ -59,38 +73,149 @@ fn position_pairs() -> Vec<(Position, Position)> {
     ]
 }
 
+/// Parse `--positions`: comma-separated "BTNvsBB"-style matchups, as
+/// `parse_matchup` reads them, in the order to solve them.
+pub fn parse_position_pairs(s: &str) -> Result<Vec<(Position, Position)>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(parse_matchup)
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|pairs| {
+            if pairs.is_empty() {
+                Err("No matchups given (expected e.g. BTNvsBB,COvsBB)".to_string())
+            } else {
+                Ok(pairs)
+            }
+        })
+}
+
 // ---------------------------------------------------------------------------
 // Spot manifest
 // ---------------------------------------------------------------------------
 
-struct BatchSpot {
+pub(crate) struct BatchSpot {
+    pub(crate) opener: Position,
+    pub(crate) responder: Position,
+    pub(crate) board: String,
+    pub(crate) pot_type: PotType,
+    pub(crate) oop_range: String,
+    pub(crate) ip_range: String,
+    pub(crate) pot: f64,
+    pub(crate) stack: f64,
+    pub(crate) oop_pos: String,
+    pub(crate) ip_pos: String,
+}
+
+impl BatchSpot {
+    /// The batch spot for one matchup, board and pot type at `stack` bb, or
+    /// `None` when the preflop solution has no ranges for the matchup.
+    pub(crate) fn new(
+        solution: &PreflopSolution,
+        opener: Position,
+        responder: Position,
+        board: &str,
+        pot_type: PotType,
+        stack: f64,
+    ) -> Option<BatchSpot> {
+        let (oop_range, ip_range, oop_pos, ip_pos) = pair_ranges(solution, opener, responder, pot_type)?;
+        let (pot, eff_stack) = pot_type.pot_and_stack_at(stack);
+        Some(BatchSpot {
+            opener,
+            responder,
+            board: board.to_string(),
+            pot_type,
+            oop_range,
+            ip_range,
+            pot,
+            stack: eff_stack,
+            oop_pos,
+            ip_pos,
+        })
+    }
+
+    pub(crate) fn cache_file_name(&self) -> String {
+        FlopSolution::cache_file_name(&self.board, &self.oop_pos, &self.ip_pos, self.pot, self.stack, &self.range_key())
+    }
+
+    pub(crate) fn range_key(&self) -> String {
+        range_key_of(&self.oop_range, &self.ip_range)
+    }
+}
+
+/// (OOP range, IP range, OOP seat key, IP seat key) for a matchup in
+/// `pot_type`, from the opener's and responder's preflop frequencies.
+fn pair_ranges(
+    solution: &PreflopSolution,
     opener: Position,
     responder: Position,
-    board: String,
     pot_type: PotType,
-    oop_range: String,
-    ip_range: String,
-    pot: f64,
-    stack: f64,
-    oop_pos: String,
-    ip_pos: String,
-}
+) -> Option<(String, String, String, String)> {
+    let spot = solution.find_spot(opener, responder)?;
+    let (opener_range, responder_range) = derive_pot_ranges(spot, pot_type, 0.05);
+    if opener_range.is_empty() || responder_range.is_empty() {
+        return None;
+    }
 
-fn generate_manifest(
-    solution: &PreflopSolution,
-    stack: f64,
-    srp_only: bool,
-    all_flops: bool,
-) -> Vec<BatchSpot> {
-    let pairs = position_pairs();
-    let pot_types = if srp_only {
-        vec![PotType::Srp]
+    Some(if opener.is_ip_vs(&responder) {
+        (responder_range.join(","), opener_range.join(","), pot_type.seat_key(responder.as_str()), pot_type.seat_key(opener.as_str()))
     } else {
-        vec![PotType::Srp, PotType::ThreeBet]
-    };
+        (opener_range.join(","), responder_range.join(","), pot_type.seat_key(opener.as_str()), pot_type.seat_key(responder.as_str()))
+    })
+}
 
-    // Choose boards: all 1,755 canonical flops or 50 representative
-    let boards: Vec<String> = if all_flops {
+/// Solve one spot and save it to the cache.
+pub(crate) fn solve_batch_spot(spot: &BatchSpot, iterations: usize) -> Result<FlopSolution, String> {
+    let config = FlopSolverConfig::new(&spot.board, &spot.oop_range, &spot.ip_range, spot.pot, spot.stack, iterations)
+        .map_err(|e| e.to_string())?;
+    Ok(solve_and_cache(&config, &spot.oop_pos, &spot.ip_pos))
+}
+
+fn solve_and_cache(config: &FlopSolverConfig, oop_pos: &str, ip_pos: &str) -> FlopSolution {
+    let mut result = solve_flop(config);
+    result.oop_pos = oop_pos.to_string();
+    result.ip_pos = ip_pos.to_string();
+    result.save_cache();
+    result
+}
+
+/// A spot's cached solve for `ranges` (a `range_key`). A quick query solve
+/// doesn't count as the spot's solve.
+pub(crate) fn load_full_solve(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64, ranges: &str) -> Option<FlopSolution> {
+    FlopSolution::load_cache(board, oop_pos, ip_pos, pot, stack, Some(ranges)).filter(|s| !s.low_fidelity)
+}
+
+/// Each `(label, board, config)` spot loaded from the cache or else solved
+/// and cached, side by side, with a progress line per spot under its label.
+/// The spots share the seats and the ranges of `ranges`.
+pub(crate) fn load_or_solve_all(
+    spots: &[(String, &str, FlopSolverConfig)],
+    oop_pos: &str,
+    ip_pos: &str,
+    ranges: &str,
+) -> Vec<FlopSolution> {
+    spots
+        .par_iter()
+        .map(|(label, board, config)| {
+            let cached = load_full_solve(board, oop_pos, ip_pos, config.starting_pot, config.effective_stack, ranges);
+            if let Some(s) = cached {
+                println!("  {}: {}", label, "cached".dimmed());
+                return s;
+            }
+            println!("  {}: solving {} iterations...", label, config.iterations);
+            let s = solve_and_cache(config, oop_pos, ip_pos);
+            println!("  {}: done (exploit: {:.4})", label, s.exploitability);
+            s
+        })
+        .collect()
+}
+
+/// Boards the batch solves: a board set, all 1,755 canonical flops (highest
+/// strategic priority first), or the 50 representative ones.
+pub(crate) fn batch_boards(all_flops: bool, boardset: Option<&BoardSet>) -> Vec<String> {
+    if let Some(set) = boardset {
+        set.boards.clone()
+    } else if all_flops {
         use crate::flop_enumerator::strategic_priority;
         let mut flops = generate_canonical_flops();
         // Sort by strategic priority descending (A-high first, low boards last)
@@ -98,50 +223,55 @@ fn generate_manifest(
         flops
     } else {
         REPRESENTATIVE_FLOPS.iter().map(|s| s.to_string()).collect()
-    };
-
-    // Pre-compute ranges for each position pair
-    let mut pair_data: Vec<(Position, Position, String, String, String, String)> = Vec::new();
-    for &(opener, responder) in &pairs {
-        let spot = match solution.find_spot(opener, responder) {
-            Some(s) => s,
-            None => continue,
-        };
+    }
+}
 
-        let opener_range = derive_opening_range(spot, 0.05);
-        let responder_range = derive_defending_range(spot, 0.05);
+/// (opener, responder, OOP range, IP range, OOP seat key, IP seat key)
+type PairData = (Position, Position, String, String, String, String);
 
-        if opener_range.is_empty() || responder_range.is_empty() {
-            continue;
-        }
-
-        let (oop_range, ip_range, oop_pos, ip_pos) = if opener.is_ip_vs(&responder) {
-            (responder_range.join(","), opener_range.join(","), responder.as_str().to_string(), opener.as_str().to_string())
-        } else {
-            (opener_range.join(","), responder_range.join(","), opener.as_str().to_string(), responder.as_str().to_string())
-        };
+fn generate_manifest(
+    solution: &PreflopSolution,
+    stack: f64,
+    srp_only: bool,
+    boards: &[String],
+    pairs: &[(Position, Position)],
+) -> Vec<BatchSpot> {
+    let pot_types = if srp_only {
+        vec![PotType::Srp]
+    } else {
+        vec![PotType::Srp, PotType::ThreeBet]
+    };
 
-        pair_data.push((opener, responder, oop_range, ip_range, oop_pos, ip_pos));
-    }
+    // Pre-compute ranges for each pot type and position pair
+    let pair_data: Vec<(PotType, Vec<PairData>)> = pot_types
+        .iter()
+        .map(|&pot_type| {
+            let pairs = pairs
+                .iter()
+                .filter_map(|&(opener, responder)| {
+                    let (oop_range, ip_range, oop_pos, ip_pos) = pair_ranges(solution, opener, responder, pot_type)?;
+                    Some((opener, responder, oop_range, ip_range, oop_pos, ip_pos))
+                })
+                .collect();
+            (pot_type, pairs)
+        })
+        .collect();
 
     let mut spots = Vec::new();
 
     // Board-first iteration: for each board, solve all position pairs and pot
     // types before moving to the next board. This ensures the highest-priority
     // boards get full position coverage first.
-    for board in &boards {
+    for board in boards {
         let board_clean: String = board.chars().filter(|c| !c.is_whitespace()).collect();
         if board_clean.len() != 6 {
             continue;
         }
 
-        for pot_type in &pot_types {
-            let (pot, eff_stack) = pot_type.pot_and_stack();
-            let scale = stack / 100.0;
-            let pot_scaled = pot * scale;
-            let stack_scaled = eff_stack * scale;
+        for (pot_type, pairs) in &pair_data {
+            let (pot_scaled, stack_scaled) = pot_type.pot_and_stack_at(stack);
 
-            for (opener, responder, oop_range, ip_range, oop_pos, ip_pos) in &pair_data {
+            for (opener, responder, oop_range, ip_range, oop_pos, ip_pos) in pairs {
                 spots.push(BatchSpot {
                     opener: *opener,
                     responder: *responder,
@@ -161,13 +291,177 @@ fn generate_manifest(
     spots
 }
 
+// ---------------------------------------------------------------------------
+// Progress manifest
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpotStatus {
+    Pending,
+    Done,
+    Failed,
+}
+
+/// One spot's progress in a `BatchManifest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub board: String,
+    pub opener: String,
+    pub responder: String,
+    pub pot_type: String,
+    pub status: SpotStatus,
+    /// Seconds the solve took; `None` until solved here (a spot found in the
+    /// cache is done without one).
+    #[serde(default)]
+    pub solve_secs: Option<f64>,
+    /// Exploitability in % of the pot, once done.
+    #[serde(default)]
+    pub exploitability_pct_pot: Option<f64>,
+    /// Why the last attempt failed.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl ManifestEntry {
+    fn matches(&self, spot: &BatchSpot) -> bool {
+        self.board == spot.board
+            && self.opener == spot.opener.as_str()
+            && self.responder == spot.responder.as_str()
+            && self.pot_type == spot.pot_type.as_str()
+    }
+}
+
+/// What the batch at one stack depth has got through, rewritten after every
+/// spot so a killed run can pick up where it stopped. Spots of earlier runs
+/// over other boards or matchups are kept.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BatchManifest {
+    pub spots: Vec<ManifestEntry>,
+}
+
+impl BatchManifest {
+    /// `batch_manifest_<stack>.json` in the solver cache directory.
+    pub fn path(stack: f64) -> PathBuf {
+        solver_cache_dir().join(format!("batch_manifest_{}.json", stack))
+    }
+
+    /// Load the manifest at `path`; a missing file is an empty manifest.
+    pub fn load_from(path: &Path) -> Result<BatchManifest, String> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Cannot parse {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BatchManifest::default()),
+            Err(e) => Err(format!("Cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Save to `path`; a run killed mid-write leaves the previous manifest.
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        write_atomic(path, json.as_bytes()).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    fn entry(&self, spot: &BatchSpot) -> Option<&ManifestEntry> {
+        self.spots.iter().find(|e| e.matches(spot))
+    }
+
+    /// `spot`'s entry, added as pending if it has none.
+    fn entry_mut(&mut self, spot: &BatchSpot) -> &mut ManifestEntry {
+        match self.spots.iter().position(|e| e.matches(spot)) {
+            Some(i) => &mut self.spots[i],
+            None => {
+                self.spots.push(ManifestEntry {
+                    board: spot.board.clone(),
+                    opener: spot.opener.as_str().to_string(),
+                    responder: spot.responder.as_str().to_string(),
+                    pot_type: spot.pot_type.as_str().to_string(),
+                    status: SpotStatus::Pending,
+                    solve_secs: None,
+                    exploitability_pct_pot: None,
+                    error: None,
+                });
+                self.spots.last_mut().unwrap()
+            }
+        }
+    }
+
+    fn mark_done(&mut self, spot: &BatchSpot, solve_secs: Option<f64>, exploitability_pct_pot: f64) {
+        let entry = self.entry_mut(spot);
+        entry.status = SpotStatus::Done;
+        entry.solve_secs = solve_secs.or(entry.solve_secs);
+        entry.exploitability_pct_pot = Some(exploitability_pct_pot);
+        entry.error = None;
+    }
+
+    fn mark_failed(&mut self, spot: &BatchSpot, error: &str) {
+        let entry = self.entry_mut(spot);
+        entry.status = SpotStatus::Failed;
+        entry.error = Some(error.to_string());
+    }
+
+    /// Mean time of the solves done so far.
+    pub fn mean_solve_secs(&self) -> Option<f64> {
+        let times: Vec<f64> = self
+            .spots
+            .iter()
+            .filter(|e| e.status == SpotStatus::Done)
+            .filter_map(|e| e.solve_secs)
+            .collect();
+        (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64)
+    }
+}
+
+/// The batch's spots by manifest status: (done, failed, pending).
+fn progress_counts(manifest: &BatchManifest, spots: &[BatchSpot]) -> (usize, usize, usize) {
+    let status = |spot| manifest.entry(spot).map_or(SpotStatus::Pending, |e| e.status);
+    let count = |s: SpotStatus| spots.iter().filter(|spot| status(spot) == s).count();
+    (count(SpotStatus::Done), count(SpotStatus::Failed), count(SpotStatus::Pending))
+}
+
+/// "~3.2 h left" for `remaining` spots at the mean solve time so far, over
+/// `jobs` workers.
+fn eta(manifest: &BatchManifest, remaining: usize, jobs: usize) -> Option<String> {
+    if remaining == 0 {
+        return None;
+    }
+    let mean = manifest.mean_solve_secs()?;
+    let secs = mean * remaining.div_ceil(jobs.max(1)) as f64;
+    Some(format!("~{} left", format_duration(Duration::from_secs_f64(secs))))
+}
+
+/// Save `manifest`, warning instead of stopping the batch if it can't be.
+fn save_manifest(manifest: &BatchManifest, path: &Path) {
+    if let Err(e) = manifest.save_to(path) {
+        eprintln!("  {}", format!("Warning: {}", e).yellow());
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Batch solver
 // ---------------------------------------------------------------------------
 
-pub fn run_batch_solve(stack: f64, srp_only: bool, limit: Option<usize>, iterations: usize, all_flops: bool) {
+/// Solve the batch's spots, recording progress in the stack's
+/// `BatchManifest`. Spots the manifest has done are skipped unless `force`
+/// (which also re-solves cached spots); failed ones are retried. `jobs`
+/// workers solve spots side by side, as many as `worker_count` allows.
+#[allow(clippy::too_many_arguments)]
+pub fn run_batch_solve(
+    stack: f64,
+    srp_only: bool,
+    limit: Option<usize>,
+    iterations: usize,
+    all_flops: bool,
+    boardset: Option<&BoardSet>,
+    positions: Option<&[(Position, Position)]>,
+    force: bool,
+    jobs: usize,
+    max_memory: Option<u64>,
+) {
     // 1. Load preflop solution
-    let solution = match PreflopSolution::load("6max", stack, 0.0) {
+    let solution = match PreflopSolution::load("6max", stack, 0.0, &PreflopSizing::default()) {
         Ok(s) => s,
         Err(_) => {
             eprintln!(
@@ -179,13 +473,28 @@ pub fn run_batch_solve(stack: f64, srp_only: bool, limit: Option<usize>, iterati
     };
 
     // 2. Generate manifest
-    let mut manifest = generate_manifest(&solution, stack, srp_only, all_flops);
+    let pairs = positions.map_or_else(position_pairs, <[_]>::to_vec);
+    let mut manifest = generate_manifest(&solution, stack, srp_only, &batch_boards(all_flops, boardset), &pairs);
 
     // Apply limit
     if let Some(max) = limit {
         manifest.truncate(max);
     }
 
+    let progress_path = BatchManifest::path(stack);
+    let mut progress = match BatchManifest::load_from(&progress_path) {
+        Ok(p) => p,
+        Err(e) => {
+            let msg = format!("{} (delete it to start the batch over)", e);
+            crate::display::print_failure(crate::status::ExitCode::CacheIo, &msg);
+            return;
+        }
+    };
+    for spot in &manifest {
+        progress.entry_mut(spot);
+    }
+    save_manifest(&progress, &progress_path);
+
     let total = manifest.len();
     println!();
     println!(
@@ -194,90 +503,375 @@ pub fn run_batch_solve(stack: f64, srp_only: bool, limit: Option<usize>, iterati
         total.to_string().bold(),
     );
     println!(
-        "  Stack: {}bb | Iterations: {} | {} | {} flops",
+        "  Stack: {}bb | Iterations: {} | {} | {}",
         stack,
         iterations,
         if srp_only { "SRP only" } else { "SRP + 3-bet pots" },
-        if all_flops { "1,755" } else { "50 representative" },
+        match boardset {
+            Some(set) => format!("board set {}", set.label()),
+            None if all_flops => "1,755 flops".to_string(),
+            None => "50 representative flops".to_string(),
+        },
     );
+
+    let to_solve: Vec<(usize, &BatchSpot)> = manifest
+        .iter()
+        .enumerate()
+        .filter(|(_, spot)| force || progress.entry(spot).is_none_or(|e| e.status != SpotStatus::Done))
+        .collect();
+    let spots: Vec<&BatchSpot> = to_solve.iter().map(|&(_, spot)| spot).collect();
+    let jobs = match worker_count(jobs, &spots, iterations, max_memory) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            crate::display::print_error(&e);
+            return;
+        }
+    };
+    let threads_per_job = (rayon::current_num_threads() / jobs).max(1);
+    if jobs > 1 {
+        println!("  Workers: {} × {} threads", jobs, threads_per_job);
+    }
+
+    let (done, failed, pending) = progress_counts(&progress, &manifest);
+    let remaining = if force { total } else { failed + pending };
+    println!(
+        "  Progress: {} done, {} failed (retrying), {} pending{}",
+        done,
+        failed,
+        pending,
+        eta(&progress, remaining, jobs).map(|eta| format!(" | {}", eta)).unwrap_or_default(),
+    );
+    if force && done > 0 {
+        println!("  {}", "--force: re-solving spots already done".yellow());
+    }
+    println!("  {}", format!("Manifest: {}", progress_path.display()).dimmed());
     println!();
 
-    let mut solved = 0;
-    let mut skipped = 0;
+    let progress = Mutex::new(progress);
+    let next = AtomicUsize::new(0);
+    let solved = AtomicUsize::new(0);
+    let skipped = AtomicUsize::new(total - to_solve.len());
+    let failures = AtomicUsize::new(0);
+    let remaining = AtomicUsize::new(remaining);
     let batch_start = Instant::now();
 
-    for (i, spot) in manifest.iter().enumerate() {
-        // 3. Check if already cached
-        if FlopSolution::load_cache(&spot.board, &spot.oop_pos, &spot.ip_pos, spot.pot, spot.stack).is_some() {
-            skipped += 1;
-            println!(
-                "  [{}/{}] {} {} vs {} ({}) ... {}",
+    // Each worker takes the next spot until none are left
+    let work = || {
+        while let Some(&(i, spot)) = to_solve.get(next.fetch_add(1, Ordering::Relaxed)) {
+            let label = format!(
+                "[{}/{}] {} {} vs {} ({})",
                 i + 1,
                 total,
                 spot.board,
                 spot.opener.as_str(),
                 spot.responder.as_str(),
                 spot.pot_type.as_str(),
-                "cached".dimmed(),
             );
-            continue;
-        }
-
-        // 4. Solve
-        print!(
-            "  [{}/{}] Solving {} {} vs {} ({}) ... ",
-            i + 1,
-            total,
-            spot.board,
-            spot.opener.as_str(),
-            spot.responder.as_str(),
-            spot.pot_type.as_str(),
-        );
-        let _ = std::io::stdout().flush();
-
-        let spot_start = Instant::now();
-
-        let config = match FlopSolverConfig::new(
-            &spot.board,
-            &spot.oop_range,
-            &spot.ip_range,
-            spot.pot,
-            spot.stack,
-            iterations,
-        ) {
-            Ok(c) => c,
-            Err(e) => {
-                println!("{}", format!("error: {}", e).red());
+            let left = remaining.fetch_sub(1, Ordering::Relaxed).saturating_sub(1);
+
+            // 3. Check if already cached
+            let load_cached = || {
+                (!force)
+                    .then(|| load_full_solve(&spot.board, &spot.oop_pos, &spot.ip_pos, spot.pot, spot.stack, &spot.range_key()))
+                    .flatten()
+            };
+            if let Some(cached) = load_cached() {
+                skipped.fetch_add(1, Ordering::Relaxed);
+                let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                progress.mark_done(spot, None, cached.exploitability_pct_pot);
+                save_manifest(&progress, &progress_path);
+                println!("  {} ... {}", label, format!("cached, grade {}", cached.solve_quality().grade).dimmed());
                 continue;
             }
-        };
 
-        let mut result = solve_flop(&config);
-        result.oop_pos = spot.oop_pos.clone();
-        result.ip_pos = spot.ip_pos.clone();
-        result.save_cache();
-        solved += 1;
+            // 4. Solve. One worker reports on one line; several report the
+            // start and the end of each spot on lines of their own.
+            if jobs == 1 {
+                print!("  {} ... ", label.replacen("] ", "] Solving ", 1));
+                let _ = std::io::stdout().flush();
+            } else {
+                println!("  {} ... {}", label, "solving".dimmed());
+            }
+            let outcome = |text: String| if jobs == 1 { text } else { format!("  {} ... {}", label, text) };
 
-        let elapsed = spot_start.elapsed();
-        println!(
-            "done ({:.1}s, exploit: {:.4})",
-            elapsed.as_secs_f64(),
-            result.exploitability,
-        );
+            let spot_start = Instant::now();
+
+            let Some(_lock) = SolveLock::acquire(&solver_cache_dir(), &spot.cache_file_name()) else {
+                println!("{}", outcome("solving in another process".dimmed().to_string()));
+                continue;
+            };
+            // Another process may have solved the spot and released its lock
+            // since the check above
+            if let Some(cached) = load_cached() {
+                skipped.fetch_add(1, Ordering::Relaxed);
+                let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                progress.mark_done(spot, None, cached.exploitability_pct_pot);
+                save_manifest(&progress, &progress_path);
+                let text = format!("solved in another process, grade {}", cached.solve_quality().grade);
+                println!("{}", outcome(text.dimmed().to_string()));
+                continue;
+            }
+            let result = match solve_batch_spot(spot, iterations) {
+                Ok(r) => r,
+                Err(e) => {
+                    println!("{}", outcome(format!("error: {}", e).red().to_string()));
+                    crate::status::fail(crate::status::ExitCode::SolveFailed, &e);
+                    failures.fetch_add(1, Ordering::Relaxed);
+                    let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                    progress.mark_failed(spot, &e);
+                    save_manifest(&progress, &progress_path);
+                    continue;
+                }
+            };
+            solved.fetch_add(1, Ordering::Relaxed);
+
+            let elapsed = spot_start.elapsed();
+            let eta = {
+                let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+                progress.mark_done(spot, Some(elapsed.as_secs_f64()), result.exploitability_pct_pot);
+                save_manifest(&progress, &progress_path);
+                eta(&progress, left, jobs)
+            };
+            println!(
+                "{}",
+                outcome(format!(
+                    "done ({:.1}s, grade {}, exploit: {:.1}% ± {:.1}% of pot, OOP eq {:.1}% / EV {:.1}%){}",
+                    elapsed.as_secs_f64(),
+                    result.solve_quality().grade,
+                    result.exploitability_pct_pot,
+                    pct_of_pot(result.exploitability_std_err, result.starting_pot),
+                    result.oop_equity * 100.0,
+                    result.oop_ev_share * 100.0,
+                    eta.map(|eta| format!(" {}", eta.dimmed())).unwrap_or_default(),
+                ))
+            );
+        }
+    };
+
+    if jobs == 1 {
+        work();
+    } else {
+        // Each worker's solves share its own slice of the threads
+        std::thread::scope(|scope| {
+            for _ in 0..jobs {
+                scope.spawn(|| match rayon::ThreadPoolBuilder::new().num_threads(threads_per_job).build() {
+                    Ok(pool) => pool.install(work),
+                    Err(e) => crate::display::print_failure(
+                        crate::status::ExitCode::SolveFailed,
+                        &format!("Cannot start a batch worker: {}", e),
+                    ),
+                });
+            }
+        });
     }
 
     let total_elapsed = batch_start.elapsed();
     println!();
     println!(
-        "  {} Complete: {} solved, {} cached, {:.1} min total",
+        "  {} Complete: {} solved, {} cached or done, {} failed, {:.1} min total",
         "Batch".bold(),
-        solved.to_string().bold(),
-        skipped.to_string().bold(),
+        solved.into_inner().to_string().bold(),
+        skipped.into_inner().to_string().bold(),
+        failures.into_inner().to_string().bold(),
         total_elapsed.as_secs_f64() / 60.0,
     );
     println!();
 }
 
+/// `jobs` capped by memory: the largest of the spots' estimated peaks
+/// (`FlopSolverConfig::estimated_memory_bytes`) must fit `jobs` times in
+/// `max_memory`, or in the memory available when that isn't given. Errors
+/// when `max_memory` can't hold even one spot.
+fn worker_count(jobs: usize, spots: &[&BatchSpot], iterations: usize, max_memory: Option<u64>) -> Result<usize, String> {
+    let jobs = jobs.clamp(1, spots.len().max(1));
+    if jobs == 1 && max_memory.is_none() {
+        return Ok(1);
+    }
+    let Some(budget) = max_memory.or_else(available_memory) else {
+        println!("  {}", "Available memory unknown: running the requested workers uncapped".yellow());
+        return Ok(jobs);
+    };
+
+    // Spots with the same ranges differ only by board blockers
+    let mut by_ranges: HashMap<String, u64> = HashMap::new();
+    for spot in spots {
+        if let std::collections::hash_map::Entry::Vacant(entry) = by_ranges.entry(spot.range_key()) {
+            let config = FlopSolverConfig::new(&spot.board, &spot.oop_range, &spot.ip_range, spot.pot, spot.stack, iterations);
+            entry.insert(config.map_or(0, |c| c.estimated_memory_bytes()));
+        }
+    }
+    let per_spot = by_ranges.values().copied().max().unwrap_or(0).max(1);
+    let fits = (budget / per_spot) as usize;
+    if fits == 0 && max_memory.is_some() {
+        return Err(format!(
+            "A spot needs about {} but --max-memory is {}",
+            format_size(per_spot),
+            format_size(budget)
+        ));
+    }
+    let capped = jobs.min(fits.max(1));
+    if capped < jobs {
+        println!(
+            "  {}",
+            format!(
+                "Capping --jobs {} to {}: about {} per spot, {} memory",
+                jobs,
+                capped,
+                format_size(per_spot),
+                format_size(budget)
+            )
+            .yellow()
+        );
+    }
+    Ok(capped)
+}
+
+/// `MemAvailable` from /proc/meminfo, where there is one.
+fn available_memory() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemAvailable:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+// ---------------------------------------------------------------------------
+// Edge report: EV share vs equity share
+// ---------------------------------------------------------------------------
+
+/// A solved batch spot with its equity and EV split (OOP's perspective).
+pub struct EdgeRow {
+    pub board: String,
+    pub opener: Position,
+    pub responder: Position,
+    pub pot_type: PotType,
+    pub oop_pos: String,
+    pub ip_pos: String,
+    pub oop_equity: f64,
+    pub oop_ev_share: f64,
+}
+
+impl EdgeRow {
+    /// OOP's EV share minus equity share. Positive = OOP realizes more than its
+    /// equity (nut/range advantage); negative = IP over-realizes (position).
+    pub fn edge(&self) -> f64 {
+        self.oop_ev_share - self.oop_equity
+    }
+}
+
+/// Split rows into (OOP over-realizes, IP over-realizes), each sorted by
+/// |EV share − equity share| descending.
+pub fn rank_edges(rows: Vec<EdgeRow>) -> (Vec<EdgeRow>, Vec<EdgeRow>) {
+    let (mut oop, mut ip): (Vec<EdgeRow>, Vec<EdgeRow>) =
+        rows.into_iter().partition(|r| r.edge() >= 0.0);
+    let by_size = |a: &EdgeRow, b: &EdgeRow| {
+        b.edge()
+            .abs()
+            .partial_cmp(&a.edge().abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    };
+    oop.sort_by(by_size);
+    ip.sort_by(by_size);
+    (oop, ip)
+}
+
+/// `gto report edges`: list cached batch spots where the EV split diverges
+/// most from the raw equity split.
+pub fn run_edges_report(stack: f64, srp_only: bool, all_flops: bool, top: usize, boardset: Option<&BoardSet>) {
+    let solution = match PreflopSolution::load("6max", stack, 0.0, &PreflopSizing::default()) {
+        Ok(s) => s,
+        Err(_) => {
+            eprintln!(
+                "{}",
+                "Error: No preflop solution found. Run `gto solve preflop` first.".red()
+            );
+            return;
+        }
+    };
+
+    let manifest = generate_manifest(&solution, stack, srp_only, &batch_boards(all_flops, boardset), &position_pairs());
+    // Headline numbers come from the index; only unindexed or changed files are opened
+    let mut index = CacheIndex::load(&solver_cache_dir());
+    let mut rows = Vec::new();
+    for spot in &manifest {
+        let file = spot.cache_file_name();
+        let sol = match index.summary(&file) {
+            Some(s) => s,
+            None => continue,
+        };
+        // Solutions cached before equity tracking have no split to compare
+        if sol.oop_equity <= 0.0 {
+            continue;
+        }
+        rows.push(EdgeRow {
+            board: spot.board.clone(),
+            opener: spot.opener,
+            responder: spot.responder,
+            pot_type: spot.pot_type,
+            oop_pos: spot.oop_pos.clone(),
+            ip_pos: spot.ip_pos.clone(),
+            oop_equity: sol.oop_equity,
+            oop_ev_share: sol.oop_ev_share,
+        });
+    }
+
+    println!();
+    if rows.is_empty() {
+        println!(
+            "  No solved spots with equity data. Run `gto solve batch` first."
+        );
+        println!();
+        return;
+    }
+    println!(
+        "  {} EV vs equity edges across {} solved spots",
+        "GTO".bold(),
+        rows.len().to_string().bold(),
+    );
+    if let Some(set) = boardset {
+        println!("  Board set: {}", set.label());
+    }
+
+    let (oop_edges, ip_edges) = rank_edges(rows);
+    print_edge_table("OOP realizes more than its equity", &oop_edges, top);
+    print_edge_table("IP realizes more than its equity", &ip_edges, top);
+}
+
+fn print_edge_table(title: &str, rows: &[EdgeRow], top: usize) {
+    use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+    use crate::cards::parse_board;
+    use crate::postflop::analyze_board;
+
+    println!();
+    println!("  {}", title.bold());
+    if rows.is_empty() {
+        println!("    (none)");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Board", "Spot", "Pot", "OOP eq", "OOP EV", "Edge", "Texture"]);
+    for row in rows.iter().take(top) {
+        let texture = parse_board(&row.board)
+            .ok()
+            .and_then(|cards| analyze_board(&cards).ok())
+            .map(|t| t.category)
+            .unwrap_or_default();
+        table.add_row(vec![
+            Cell::new(&row.board),
+            Cell::new(format!("{} vs {} ({}/{})", row.opener, row.responder, row.oop_pos, row.ip_pos)),
+            Cell::new(row.pot_type.as_str()),
+            Cell::new(format!("{:.1}%", row.oop_equity * 100.0)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.1}%", row.oop_ev_share * 100.0)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:+.1}%", row.edge() * 100.0)).set_alignment(CellAlignment::Right),
+            Cell::new(texture),
+        ]);
+    }
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -309,6 +903,99 @@ mod tests {
         assert_eq!(pairs[0], (Position::BTN, Position::BB));
     }
 
+    #[test]
+    fn test_parse_position_pairs() {
+        assert_eq!(
+            parse_position_pairs("BTNvsBB, covsbb").unwrap(),
+            vec![(Position::BTN, Position::BB), (Position::CO, Position::BB)]
+        );
+        // Opener first, whichever order the seats come in
+        assert_eq!(parse_position_pairs("BBvsSB").unwrap(), vec![(Position::SB, Position::BB)]);
+        assert!(parse_position_pairs("BTN-BB").is_err());
+        assert!(parse_position_pairs("BTNvsXX").is_err());
+        assert!(parse_position_pairs("BBvsBB").is_err());
+        assert!(parse_position_pairs(",").is_err());
+    }
+
+    fn spot(board: &str) -> BatchSpot {
+        BatchSpot {
+            opener: Position::BTN,
+            responder: Position::BB,
+            board: board.to_string(),
+            pot_type: PotType::Srp,
+            oop_range: "AA".to_string(),
+            ip_range: "KK".to_string(),
+            pot: 5.5,
+            stack: 97.5,
+            oop_pos: "BB".to_string(),
+            ip_pos: "BTN".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_manifest_tracks_progress_across_runs() {
+        let spots = [spot("As7d2c"), spot("Kh8d3c"), spot("Qd6s2h")];
+        let mut manifest = BatchManifest::default();
+        manifest.mark_done(&spots[0], Some(30.0), 1.5);
+        manifest.mark_failed(&spots[1], "out of memory");
+        manifest.mark_done(&spots[2], None, 2.0);
+        assert_eq!(progress_counts(&manifest, &spots), (2, 1, 0));
+        // Only solves timed here count toward the ETA
+        assert_eq!(manifest.mean_solve_secs(), Some(30.0));
+        assert_eq!(eta(&manifest, 4, 1).as_deref(), Some("~2.0 min left"));
+        assert_eq!(eta(&manifest, 4, 2).as_deref(), Some("~1.0 min left"));
+        assert_eq!(eta(&manifest, 0, 1), None);
+
+        let path = std::env::temp_dir().join(format!("gto_batch_manifest_{}.json", std::process::id()));
+        manifest.save_to(&path).unwrap();
+        let loaded = BatchManifest::load_from(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(progress_counts(&loaded, &spots), (2, 1, 0));
+        assert_eq!(loaded.entry(&spots[1]).unwrap().error.as_deref(), Some("out of memory"));
+        assert!(BatchManifest::load_from(&path).unwrap().spots.is_empty());
+    }
+
+    #[test]
+    fn test_worker_count_fits_memory() {
+        let spots = [spot("As7d2c"), spot("Kh8d3c"), spot("Qd6s2h")];
+        let spots: Vec<&BatchSpot> = spots.iter().collect();
+        let per_spot = FlopSolverConfig::new("As7d2c", "AA", "KK", 5.5, 97.5, 1000)
+            .unwrap()
+            .estimated_memory_bytes();
+        assert_eq!(worker_count(4, &spots, 1000, Some(per_spot * 2 + 1)), Ok(2));
+        // No more workers than spots
+        assert_eq!(worker_count(8, &spots, 1000, Some(per_spot * 10)), Ok(3));
+        assert!(worker_count(1, &spots, 1000, Some(per_spot / 2)).is_err());
+    }
+
+    fn edge_row(board: &str, oop_equity: f64, oop_ev_share: f64) -> EdgeRow {
+        EdgeRow {
+            board: board.to_string(),
+            opener: Position::BTN,
+            responder: Position::BB,
+            pot_type: PotType::Srp,
+            oop_pos: "BB".to_string(),
+            ip_pos: "BTN".to_string(),
+            oop_equity,
+            oop_ev_share,
+        }
+    }
+
+    #[test]
+    fn test_rank_edges_splits_and_sorts() {
+        let rows = vec![
+            edge_row("As7d2c", 0.45, 0.35),
+            edge_row("9h7d5c", 0.50, 0.56),
+            edge_row("Kh8d3c", 0.44, 0.30),
+            edge_row("6d4c2s", 0.52, 0.53),
+        ];
+        let (oop, ip) = rank_edges(rows);
+        let oop_boards: Vec<&str> = oop.iter().map(|r| r.board.as_str()).collect();
+        let ip_boards: Vec<&str> = ip.iter().map(|r| r.board.as_str()).collect();
+        assert_eq!(oop_boards, vec!["9h7d5c", "6d4c2s"]);
+        assert_eq!(ip_boards, vec!["Kh8d3c", "As7d2c"]);
+    }
+
     #[test]
     fn test_pot_type_scaling() {
         let (pot, stack) = PotType::Srp.pot_and_stack();
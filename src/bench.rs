@@ -0,0 +1,258 @@
+//! `gto bench`: a fixed suite of small solves for tracking solver performance.
+//!
+//! Every spot is solved cold (no cache) with the library API, so the suite
+//! stays in step with the solver configs. The flop spot uses a fixed seed,
+//! which keeps its buckets and sampled runouts, and so its exploitability,
+//! comparable from run to run. `--record` appends one JSON line per spot, so
+//! a file of records tracks performance across commits.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::error::SolverResult;
+use crate::flop_solver::{solve_flop_timed, FlopSolverConfig};
+use crate::progress::SolveStats;
+use crate::quality::pct_of_pot;
+use crate::river_solver::{solve_river_timed, RiverSolverConfig};
+use crate::turn_solver::{solve_turn_timed, TurnSolverConfig};
+
+/// Seed for the flop spot's sampling.
+pub const BENCH_SEED: u64 = 42;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchStreet {
+    River,
+    Turn,
+    Flop,
+}
+
+impl BenchStreet {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchStreet::River => "river",
+            BenchStreet::Turn => "turn",
+            BenchStreet::Flop => "flop",
+        }
+    }
+}
+
+/// One spot of the suite.
+#[derive(Debug, Clone)]
+pub struct BenchSpot {
+    pub name: &'static str,
+    pub street: BenchStreet,
+    pub board: &'static str,
+    pub oop: &'static str,
+    pub ip: &'static str,
+    pub pot: f64,
+    pub stack: f64,
+    pub iterations: usize,
+}
+
+/// The suite, smallest solve first: peak RSS is the process high-water mark,
+/// so each spot's reading covers it and every spot before it.
+pub const SUITE: [BenchSpot; 3] = [
+    BenchSpot {
+        name: "river-srp",
+        street: BenchStreet::River,
+        board: "Ks9d4c7h2s",
+        oop: "AA,KK,99,44,AK,KQ,T8s,65s",
+        ip: "QQ,JJ,TT,AQs,AJs,KJs,QJs",
+        pot: 10.0,
+        stack: 50.0,
+        iterations: 1_000,
+    },
+    BenchSpot {
+        name: "turn-srp",
+        street: BenchStreet::Turn,
+        board: "Ks9d4c7h",
+        oop: "AA,KK,99,AK,KQs,T8s",
+        ip: "QQ,JJ,TT,AQs,KJs,QJs",
+        pot: 10.0,
+        stack: 50.0,
+        iterations: 100,
+    },
+    BenchSpot {
+        name: "flop-srp",
+        street: BenchStreet::Flop,
+        board: "Ks9d4c",
+        oop: "TT+,AQs+,AKo,KQs",
+        ip: "88+,AJs+,KQs,QJs,JTs",
+        pot: 6.0,
+        stack: 97.0,
+        iterations: 50_000,
+    },
+];
+
+/// Measurements for one spot; also the JSON record `--record` appends.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    pub name: String,
+    pub street: String,
+    pub board: String,
+    pub iterations: usize,
+    pub wall_ms: f64,
+    pub iterations_per_sec: f64,
+    /// Process peak resident set size after the solve, when the OS reports it.
+    pub peak_rss_kb: Option<u64>,
+    pub exploitability: f64,
+    pub exploitability_pct_pot: f64,
+    pub stats: SolveStats,
+}
+
+/// Solve `spot` cold and measure it.
+pub fn run_spot(spot: &BenchSpot) -> SolverResult<BenchResult> {
+    let BenchSpot { board, oop, ip, pot, stack, iterations, .. } = *spot;
+    let start = Instant::now();
+    let (iterations, exploitability, stats) = match spot.street {
+        BenchStreet::River => {
+            let config = RiverSolverConfig::new(board, oop, ip, pot, stack, iterations)?;
+            let (solution, stats) = solve_river_timed(&config);
+            (solution.iterations, solution.exploitability, stats)
+        }
+        BenchStreet::Turn => {
+            let config = TurnSolverConfig::new(board, oop, ip, pot, stack, iterations)?;
+            let (solution, stats) = solve_turn_timed(&config);
+            (solution.iterations, solution.exploitability, stats)
+        }
+        BenchStreet::Flop => {
+            let mut config = FlopSolverConfig::new(board, oop, ip, pot, stack, iterations)?;
+            config.seed = Some(BENCH_SEED);
+            let (solution, stats) = solve_flop_timed(&config);
+            (solution.iterations, solution.exploitability, stats)
+        }
+    };
+    let wall_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let iterations_per_sec = if stats.iterate_ms > 0.0 {
+        iterations as f64 / (stats.iterate_ms / 1000.0)
+    } else {
+        0.0
+    };
+    Ok(BenchResult {
+        name: spot.name.to_string(),
+        street: spot.street.as_str().to_string(),
+        board: spot.board.to_string(),
+        iterations,
+        wall_ms,
+        iterations_per_sec,
+        peak_rss_kb: peak_rss_kb(),
+        exploitability,
+        exploitability_pct_pot: pct_of_pot(exploitability, spot.pot),
+        stats,
+    })
+}
+
+/// Peak resident set size of this process in KiB (`VmHWM`), on Linux only.
+pub fn peak_rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+#[derive(Serialize)]
+struct BenchRecord<'a> {
+    timestamp: u64,
+    version: &'static str,
+    #[serde(flatten)]
+    result: &'a BenchResult,
+}
+
+/// Append one JSON line per result to `path`, stamped with the time and
+/// crate version.
+pub fn record_results(path: &Path, results: &[BenchResult]) -> std::io::Result<()> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut out = String::new();
+    for result in results {
+        let record = BenchRecord { timestamp, version: env!("CARGO_PKG_VERSION"), result };
+        out.push_str(&serde_json::to_string(&record).map_err(std::io::Error::other)?);
+        out.push('\n');
+    }
+    let mut f = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    f.write_all(out.as_bytes())
+}
+
+/// Print `results` as a table, with the phase breakdown of each solve.
+pub fn print_results(results: &[BenchResult]) {
+    use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+    let right = |s: String| Cell::new(s).set_alignment(CellAlignment::Right);
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        "Spot", "Board", "Iters", "Wall", "It/s", "Peak RSS", "Exploit", "Setup", "Precomp", "Iterate", "Extract",
+    ]);
+    for r in results {
+        let rss = r.peak_rss_kb.map(|kb| format!("{:.1} MB", kb as f64 / 1024.0)).unwrap_or_else(|| "-".to_string());
+        table.add_row(vec![
+            Cell::new(&r.name),
+            Cell::new(&r.board),
+            right(r.iterations.to_string()),
+            right(format_ms(r.wall_ms)),
+            right(format!("{:.0}", r.iterations_per_sec)),
+            right(rss),
+            right(format!("{:.2}% pot", r.exploitability_pct_pot)),
+            right(format_ms(r.stats.setup_ms)),
+            right(format_ms(r.stats.precompute_ms)),
+            right(format_ms(r.stats.iterate_ms)),
+            right(format_ms(r.stats.extract_ms)),
+        ]);
+    }
+    println!();
+    println!("  {}", "Solver benchmark".bold());
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+}
+
+fn format_ms(ms: f64) -> String {
+    if ms >= 1000.0 {
+        format!("{:.2}s", ms / 1000.0)
+    } else {
+        format!("{:.0}ms", ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suite_spots_build() {
+        for spot in &SUITE {
+            let BenchSpot { board, oop, ip, pot, stack, iterations, .. } = *spot;
+            let built = match spot.street {
+                BenchStreet::River => RiverSolverConfig::new(board, oop, ip, pot, stack, iterations).map(|_| ()),
+                BenchStreet::Turn => TurnSolverConfig::new(board, oop, ip, pot, stack, iterations).map(|_| ()),
+                BenchStreet::Flop => FlopSolverConfig::new(board, oop, ip, pot, stack, iterations).map(|_| ()),
+            };
+            assert!(built.is_ok(), "{} doesn't build: {:?}", spot.name, built.err());
+        }
+        assert_eq!(SUITE.iter().find(|s| s.street == BenchStreet::Flop).map(|s| s.iterations), Some(50_000));
+    }
+
+    #[test]
+    fn test_river_spot_reports_phases_and_records_json_lines() {
+        let spot = BenchSpot { iterations: 50, ..SUITE[0].clone() };
+        let result = run_spot(&spot).unwrap();
+        assert_eq!(result.iterations, 50);
+        assert!(result.stats.iterate_ms > 0.0);
+        assert!(result.stats.total_ms() <= result.wall_ms + 1.0);
+        assert!(result.exploitability.is_finite());
+
+        let path = std::env::temp_dir().join(format!("gto-bench-{}.jsonl", std::process::id()));
+        record_results(&path, std::slice::from_ref(&result)).unwrap();
+        record_results(&path, std::slice::from_ref(&result)).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let lines: Vec<serde_json::Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["name"], "river-srp");
+        assert!(lines[0]["stats"]["iterate_ms"].as_f64().is_some());
+    }
+}
@@ -0,0 +1,408 @@
+//! Hand-class composition of a solved street: for the decisions
+//! `range_summary` covers, how many combos of each made hand or draw take
+//! each action, grouped into value, marginal and bluff hands, so a betting
+//! range's bluff:value ratio can be checked against the one its size
+//! supports (`math_engine::bluff_to_value_ratio`).
+//!
+//! Combos count at their reach of the decision (see `range_summary`) times
+//! their frequency for the action. On a complete board, draws are the
+//! draws a hand had on the turn and missed.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{parse_board, Card};
+use crate::hand_evaluator::{evaluate_hand, HandCategory};
+use crate::math_engine::bluff_to_value_ratio;
+use crate::postflop_tree::{player_label, Action, Player, TreeNode};
+use crate::range_summary::{reached_decisions, WeightedCombos};
+
+/// What a combo holds on the board, most valuable first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandClass {
+    /// Quads and straight flushes.
+    QuadsPlus,
+    FullHouse,
+    Flush,
+    Straight,
+    /// A pocket pair matching a board card.
+    Set,
+    /// One hole card matching a paired board card.
+    Trips,
+    /// Both hole cards matching board cards.
+    TwoPair,
+    Overpair,
+    TopPair,
+    /// A pair below one higher board card.
+    SecondPair,
+    /// Any lower pair.
+    WeakPair,
+    FlushDraw,
+    OpenEnder,
+    Gutshot,
+    /// No pair and no draw.
+    Air,
+}
+
+/// How a class plays when it bets: for value, as a bluff catcher, or as a
+/// bluff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandGroup {
+    Value,
+    Marginal,
+    Bluff,
+}
+
+impl HandClass {
+    pub const ALL: [HandClass; 15] = [
+        HandClass::QuadsPlus,
+        HandClass::FullHouse,
+        HandClass::Flush,
+        HandClass::Straight,
+        HandClass::Set,
+        HandClass::Trips,
+        HandClass::TwoPair,
+        HandClass::Overpair,
+        HandClass::TopPair,
+        HandClass::SecondPair,
+        HandClass::WeakPair,
+        HandClass::FlushDraw,
+        HandClass::OpenEnder,
+        HandClass::Gutshot,
+        HandClass::Air,
+    ];
+
+    pub fn group(&self) -> HandGroup {
+        match self {
+            HandClass::SecondPair | HandClass::WeakPair => HandGroup::Marginal,
+            HandClass::FlushDraw | HandClass::OpenEnder | HandClass::Gutshot | HandClass::Air => HandGroup::Bluff,
+            _ => HandGroup::Value,
+        }
+    }
+
+    /// Plural label; draws read as missed on a complete board.
+    pub fn label(&self, complete_board: bool) -> &'static str {
+        match (self, complete_board) {
+            (HandClass::QuadsPlus, _) => "quads+",
+            (HandClass::FullHouse, _) => "full houses",
+            (HandClass::Flush, _) => "flushes",
+            (HandClass::Straight, _) => "straights",
+            (HandClass::Set, _) => "sets",
+            (HandClass::Trips, _) => "trips",
+            (HandClass::TwoPair, _) => "two pair",
+            (HandClass::Overpair, _) => "overpairs",
+            (HandClass::TopPair, _) => "top pair",
+            (HandClass::SecondPair, _) => "second pair",
+            (HandClass::WeakPair, _) => "weak pairs",
+            (HandClass::FlushDraw, false) => "FD",
+            (HandClass::FlushDraw, true) => "missed FD",
+            (HandClass::OpenEnder, false) => "OESD",
+            (HandClass::OpenEnder, true) => "missed OESD",
+            (HandClass::Gutshot, false) => "gutshots",
+            (HandClass::Gutshot, true) => "missed gutshots",
+            (HandClass::Air, _) => "air",
+        }
+    }
+}
+
+impl HandGroup {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HandGroup::Value => "value",
+            HandGroup::Marginal => "marginal",
+            HandGroup::Bluff => "bluff",
+        }
+    }
+}
+
+/// Classify `hole` on `board` (3 to 5 cards).
+pub fn classify(hole: &[Card], board: &[Card]) -> HandClass {
+    match evaluate_hand(hole, board).map(|r| r.category) {
+        Ok(HandCategory::RoyalFlush | HandCategory::StraightFlush | HandCategory::FourOfAKind) => {
+            return HandClass::QuadsPlus;
+        }
+        Ok(HandCategory::FullHouse) => return HandClass::FullHouse,
+        Ok(HandCategory::Flush) => return HandClass::Flush,
+        Ok(HandCategory::Straight) => return HandClass::Straight,
+        _ => {}
+    }
+
+    let on_board = |value: u8| board.iter().filter(|c| c.value() == value).count();
+    // Distinct board ranks above `value`: 0 for top pair, 1 for second pair
+    let higher = |value: u8| {
+        let mut above: Vec<u8> = board.iter().map(|c| c.value()).filter(|&v| v > value).collect();
+        above.sort_unstable();
+        above.dedup();
+        above.len()
+    };
+    let pair_class = |value: u8| match higher(value) {
+        0 => HandClass::TopPair,
+        1 => HandClass::SecondPair,
+        _ => HandClass::WeakPair,
+    };
+
+    let (a, b) = (hole[0].value(), hole[1].value());
+    if a == b {
+        return match (on_board(a), higher(a)) {
+            (1, _) => HandClass::Set,
+            (_, 0) => HandClass::Overpair,
+            (_, 1) => HandClass::SecondPair,
+            _ => HandClass::WeakPair,
+        };
+    }
+    if on_board(a) >= 2 || on_board(b) >= 2 {
+        return HandClass::Trips;
+    }
+    match (on_board(a) == 1, on_board(b) == 1) {
+        (true, true) => HandClass::TwoPair,
+        (true, false) => pair_class(a),
+        (false, true) => pair_class(b),
+        (false, false) => draw_class(hole, if board.len() == 5 { &board[..4] } else { board }),
+    }
+}
+
+/// The best draw `hole` has on `board`, or `Air`.
+fn draw_class(hole: &[Card], board: &[Card]) -> HandClass {
+    let flush_draw = hole.iter().any(|h| {
+        hole.iter().chain(board).filter(|c| c.suit == h.suit).count() == 4
+    });
+    if flush_draw {
+        return HandClass::FlushDraw;
+    }
+
+    // Rank presence, the ace counting low as well
+    let mut present = [false; 15];
+    for c in hole.iter().chain(board) {
+        present[c.value() as usize] = true;
+    }
+    present[1] = present[14];
+    let hero = |v: usize| hole.iter().any(|c| c.value() as usize == v || (v == 1 && c.value() == 14));
+
+    // Four in a row with a card free at both ends
+    let open_ended = (2..=10).any(|low| {
+        (low..low + 4).all(|v| present[v]) && (low..low + 4).any(hero) && !present[low - 1] && !present[low + 4]
+    });
+    if open_ended {
+        return HandClass::OpenEnder;
+    }
+    // Four of five in a row
+    let gutshot = (1..=10).any(|low| {
+        (low..low + 5).filter(|&v| present[v]).count() == 4 && (low..low + 5).any(|v| present[v] && hero(v))
+    });
+    if gutshot { HandClass::Gutshot } else { HandClass::Air }
+}
+
+/// Combos of one class taking an action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassCount {
+    pub class: HandClass,
+    pub group: HandGroup,
+    pub combos: f64,
+}
+
+/// What takes one action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionBreakdown {
+    pub action: String,
+    /// Combos taking the action by class, most valuable first; classes with
+    /// none are left out.
+    pub classes: Vec<ClassCount>,
+    /// Bluffs per value combo a bet of this size supports
+    /// (`bluff_to_value_ratio`); `None` for actions other than bets.
+    pub balanced_bluff_to_value: Option<f64>,
+}
+
+impl ActionBreakdown {
+    /// Combos taking the action in `group`, or in all groups.
+    pub fn combos(&self, group: Option<HandGroup>) -> f64 {
+        self.classes.iter().filter(|c| group.is_none_or(|g| c.group == g)).fold(0.0, |sum, c| sum + c.combos)
+    }
+
+    /// Bluff combos per value combo taking the action; `None` without value.
+    pub fn bluff_to_value(&self) -> Option<f64> {
+        let value = self.combos(Some(HandGroup::Value));
+        (value > 0.0).then(|| self.combos(Some(HandGroup::Bluff)) / value)
+    }
+}
+
+/// One decision's composition by action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionBreakdown {
+    pub node_id: u16,
+    /// "OOP" or "IP".
+    pub player: String,
+    /// Actions leading here from the root: empty at the root.
+    pub line: Vec<String>,
+    pub actions: Vec<ActionBreakdown>,
+}
+
+/// The root decision and the responses to each root action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Breakdown {
+    pub board: String,
+    pub decisions: Vec<DecisionBreakdown>,
+}
+
+impl Breakdown {
+    /// Whether `board` is a river, where draws have missed.
+    pub fn complete_board(&self) -> bool {
+        self.board.len() >= 10
+    }
+}
+
+/// Break down the decisions `range_summary::summarize` covers. `strategy`
+/// gives the actions and per-combo frequencies solved at a node.
+pub fn breakdown<'a>(
+    root: &TreeNode,
+    strategy: impl Fn(u16) -> Option<(&'a [String], &'a [Vec<f64>])>,
+    oop: WeightedCombos,
+    ip: WeightedCombos,
+    board: &str,
+) -> Breakdown {
+    let board_cards = parse_board(board).unwrap_or_default();
+    let mut classes: HashMap<&str, HandClass> = HashMap::new();
+    for combo in oop.combos.iter().chain(ip.combos) {
+        if let Ok(hole) = parse_board(combo) {
+            if hole.len() == 2 && board_cards.len() >= 3 {
+                classes.insert(combo, classify(&hole, &board_cards));
+            }
+        }
+    }
+
+    let decisions = reached_decisions(root, strategy, &oop, &ip)
+        .into_iter()
+        .map(|d| {
+            let combos = if d.player == Player::OOP { oop.combos } else { ip.combos };
+            let TreeNode::Action { pot, actions: tree_actions, .. } = d.node else {
+                unreachable!("decisions are action nodes")
+            };
+            let actions = d
+                .actions
+                .iter()
+                .enumerate()
+                .map(|(a, action)| {
+                    let mut counts: Vec<ClassCount> = Vec::new();
+                    for ((combo, freqs), reach) in combos.iter().zip(d.frequencies).zip(&d.reach) {
+                        let (Some(&class), Some(f)) = (classes.get(combo.as_str()), freqs.get(a)) else {
+                            continue;
+                        };
+                        let n = reach * f;
+                        if n <= 0.0 {
+                            continue;
+                        }
+                        match counts.iter_mut().find(|c| c.class == class) {
+                            Some(count) => count.combos += n,
+                            None => counts.push(ClassCount { class, group: class.group(), combos: n }),
+                        }
+                    }
+                    counts.sort_by_key(|c| c.class);
+                    let balanced_bluff_to_value = match tree_actions.get(a) {
+                        Some(Action::Bet(amount)) => bluff_to_value_ratio(*amount, *pot).ok(),
+                        _ => None,
+                    };
+                    ActionBreakdown { action: action.clone(), classes: counts, balanced_bluff_to_value }
+                })
+                .collect();
+            DecisionBreakdown { node_id: d.node_id, player: player_label(d.player).to_string(), line: d.line, actions }
+        })
+        .collect();
+    Breakdown { board: board.to_string(), decisions }
+}
+
+/// "Bet 6.6: 14.2 combos value (sets 3.0, two pair 4.1), 6.8 combos bluff
+/// (missed FD 4.2, gutshots 2.6)"; `None` when no combo takes the action.
+pub fn describe_action(action: &ActionBreakdown, complete_board: bool) -> Option<String> {
+    let groups: Vec<String> = [HandGroup::Value, HandGroup::Marginal, HandGroup::Bluff]
+        .iter()
+        .filter_map(|&group| {
+            let classes: Vec<String> = action
+                .classes
+                .iter()
+                .filter(|c| c.group == group && c.combos >= 0.05)
+                .map(|c| format!("{} {:.1}", c.class.label(complete_board), c.combos))
+                .collect();
+            (!classes.is_empty()).then(|| {
+                format!("{:.1} combos {} ({})", action.combos(Some(group)), group.label(), classes.join(", "))
+            })
+        })
+        .collect();
+    (!groups.is_empty()).then(|| format!("{}: {}", action.action, groups.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::postflop_tree::{build_tree, TreeConfig};
+
+    fn class(hole: &str, board: &str) -> HandClass {
+        classify(&parse_board(hole).unwrap(), &parse_board(board).unwrap())
+    }
+
+    #[test]
+    fn test_classify_made_hands() {
+        assert_eq!(class("9h9c", "Ks9d4c"), HandClass::Set);
+        assert_eq!(class("Kh9h", "Ks9d4c"), HandClass::TwoPair);
+        assert_eq!(class("AhAc", "Ks9d4c"), HandClass::Overpair);
+        assert_eq!(class("AhKh", "Ks9d4c"), HandClass::TopPair);
+        assert_eq!(class("Th9h", "Ks9d4c"), HandClass::SecondPair);
+        assert_eq!(class("QhQc", "Ks9d4c"), HandClass::SecondPair);
+        assert_eq!(class("5h4h", "Ks9d4c"), HandClass::WeakPair);
+        assert_eq!(class("Ah9h", "Ks9d9c"), HandClass::Trips);
+        assert_eq!(class("AhKh", "Qh9h4h"), HandClass::Flush);
+        assert_eq!(class("8h7h", "Ts9d6c"), HandClass::Straight);
+        // A paired board alone isn't the hero's pair
+        assert_eq!(class("AhQh", "Ks9d9c"), HandClass::Air);
+    }
+
+    #[test]
+    fn test_classify_draws_and_missed_draws() {
+        assert_eq!(class("AhQh", "Kh9h4c"), HandClass::FlushDraw);
+        assert_eq!(class("8h7c", "Ts9d2c"), HandClass::OpenEnder);
+        assert_eq!(class("QhJc", "9s8d2c"), HandClass::Gutshot);
+        assert_eq!(class("Ah5c", "4s3d9c"), HandClass::Gutshot);
+        assert_eq!(class("AhJc", "7s4d2c"), HandClass::Air);
+        // On the river, the turn's draw that didn't come in
+        assert_eq!(class("AhQh", "Kh9h4c2s3d"), HandClass::FlushDraw);
+        assert_eq!(HandClass::FlushDraw.label(true), "missed FD");
+    }
+
+    #[test]
+    fn test_breakdown_counts_combos_per_class_and_action() {
+        let (tree, _) = build_tree(&TreeConfig::default_river(10.0, 100.0));
+        let TreeNode::Action { node_id, actions, .. } = &tree else { panic!("root is an action node") };
+        let labels: Vec<String> = actions.iter().map(|a| a.label()).collect();
+        // Sets always bet the first size, flush draws half the time
+        let frequencies: Vec<Vec<f64>> = [vec![0.0, 1.0], vec![0.0, 1.0], vec![0.5, 0.5]]
+            .into_iter()
+            .map(|f| {
+                let mut row = vec![0.0; labels.len()];
+                row[..2].copy_from_slice(&f);
+                row
+            })
+            .collect();
+        let oop = ["9h9c".to_string(), "4h4d".to_string(), "AhQh".to_string()];
+        let ip = ["KhKd".to_string()];
+        let result = breakdown(
+            &tree,
+            |id| (id == *node_id).then_some((labels.as_slice(), frequencies.as_slice())),
+            WeightedCombos { combos: &oop, weights: &[1.0, 1.0, 1.0] },
+            WeightedCombos { combos: &ip, weights: &[1.0] },
+            "Kh9h4c2s3d",
+        );
+        let root = &result.decisions[0];
+        let bet = &root.actions[1];
+        assert_eq!(bet.combos(Some(HandGroup::Value)), 2.0);
+        assert_eq!(bet.combos(Some(HandGroup::Bluff)), 0.5);
+        assert_eq!(bet.bluff_to_value(), Some(0.25));
+        assert!(bet.balanced_bluff_to_value.is_some());
+        assert_eq!(root.actions[0].balanced_bluff_to_value, None);
+        assert_eq!(
+            describe_action(bet, result.complete_board()).unwrap(),
+            format!("{}: 2.0 combos value (sets 2.0), 0.5 combos bluff (missed FD 0.5)", labels[1])
+        );
+        assert_eq!(describe_action(&root.actions[2], true), None);
+    }
+}
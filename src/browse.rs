@@ -0,0 +1,325 @@
+//! `gto browse`: a terminal UI over a cached flop solve. Arrow keys walk the
+//! flop tree (`tree_nav`); each decision shows the acting range's overall
+//! action mix and a 13x13 grid of one action's frequency by hand, which a
+//! filter can narrow to one hand class (`breakdown::HandClass`).
+//!
+//! `node_view` and `ClassFilter` hold what's shown and are independent of
+//! the terminal; `run` draws them with crossterm. The terminal is restored
+//! when `run` returns, on error, and on panic.
+
+use std::io::Write;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Stylize;
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::breakdown::{classify, HandClass, HandGroup};
+use crate::cards::{hand_to_canonical, parse_board};
+use crate::flop_solver::FlopSolution;
+use crate::game_tree::{hand_to_bucket, NUM_HANDS};
+use crate::postflop_tree::{build_tree, player_label};
+use crate::tree_nav::TreeNav;
+
+/// Restricts the grid to one hand class or to a whole group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassFilter {
+    Class(HandClass),
+    Group(HandGroup),
+}
+
+impl ClassFilter {
+    /// A class by its label ("top pair", "fd", "air") or a group ("value",
+    /// "marginal", "bluff"), in any case; a prefix naming one is enough
+    /// ("top").
+    pub fn parse(text: &str) -> Result<ClassFilter, String> {
+        let text = text.trim().to_lowercase();
+        let candidates: Vec<ClassFilter> = HandClass::ALL
+            .iter()
+            .map(|&c| ClassFilter::Class(c))
+            .chain([HandGroup::Value, HandGroup::Marginal, HandGroup::Bluff].map(ClassFilter::Group))
+            .collect();
+        let label = |f: &ClassFilter| f.label().to_lowercase();
+        if let Some(exact) = candidates.iter().find(|f| label(f) == text) {
+            return Ok(*exact);
+        }
+        let prefixed: Vec<&ClassFilter> = candidates.iter().filter(|f| label(f).starts_with(&text)).collect();
+        match prefixed.as_slice() {
+            [one] if !text.is_empty() => Ok(**one),
+            [] | [_] => Err(format!(
+                "Unknown hand class '{}'; try {}",
+                text,
+                candidates.iter().map(|f| f.label()).collect::<Vec<_>>().join(", ")
+            )),
+            many => Err(format!(
+                "'{}' could be {}",
+                text,
+                many.iter().map(|f| f.label()).collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClassFilter::Class(class) => class.label(false),
+            ClassFilter::Group(group) => group.label(),
+        }
+    }
+
+    pub fn matches(&self, class: HandClass) -> bool {
+        match self {
+            ClassFilter::Class(c) => *c == class,
+            ClassFilter::Group(g) => class.group() == *g,
+        }
+    }
+}
+
+/// What `gto browse` shows at one decision.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeView {
+    /// "OOP" or "IP".
+    pub player: &'static str,
+    /// Actions from the root; empty at the root.
+    pub line: Vec<String>,
+    pub actions: Vec<String>,
+    /// The acting range's frequency for each action, combos weighted by
+    /// their reach of the decision.
+    pub frequencies: Vec<f64>,
+    /// Combos reaching the decision (reach-weighted).
+    pub combos: f64,
+    /// The selected action's frequency by hand, indexed like
+    /// `display::strategy_grid`; `None` for hands with no combos reaching
+    /// the decision or none passing the filter.
+    pub grid: Vec<Option<f64>>,
+}
+
+/// The decision `nav` is at, with the grid for its selected action.
+pub fn node_view(solution: &FlopSolution, nav: &TreeNav, filter: Option<ClassFilter>) -> Result<NodeView, String> {
+    let node_id = nav.node_id();
+    let player = player_label(nav.player());
+    let strategy = solution
+        .strategies
+        .iter()
+        .find(|s| s.node_id == node_id)
+        .ok_or_else(|| format!("Solution has no strategy for node {}", node_id))?;
+    let reach = solution.decision_reach(node_id)?;
+    let combos = if player == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+    let board = parse_board(&solution.board).map_err(|e| e.to_string())?;
+
+    let mut totals = vec![0.0; strategy.actions.len()];
+    // Reach and reach taking the selected action, by hand
+    let mut by_hand = vec![(0.0, 0.0); NUM_HANDS];
+    let selected = nav.selected();
+    for ((combo, &r), freqs) in combos.iter().zip(&reach).zip(&strategy.frequencies) {
+        for (t, f) in totals.iter_mut().zip(freqs) {
+            *t += r * f;
+        }
+        if let Some(filter) = filter {
+            let hole = parse_board(combo).map_err(|e| e.to_string())?;
+            if !filter.matches(classify(&hole, &board)) {
+                continue;
+            }
+        }
+        if let Some(bucket) = hand_to_bucket(&hand_to_canonical(combo)) {
+            by_hand[bucket].0 += r;
+            by_hand[bucket].1 += r * freqs[selected];
+        }
+    }
+    let reached: f64 = reach.iter().sum();
+    Ok(NodeView {
+        player,
+        line: nav.line(),
+        actions: strategy.actions.clone(),
+        frequencies: totals.iter().map(|t| if reached > 0.0 { t / reached } else { 0.0 }).collect(),
+        combos: reached,
+        grid: by_hand.iter().map(|&(r, taken)| (r > 1e-9).then(|| taken / r)).collect(),
+    })
+}
+
+/// Browse `solution` until the user quits.
+pub fn run(solution: &FlopSolution, title: &str) -> Result<(), String> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        return Err("gto browse needs an interactive terminal".to_string());
+    }
+    let (tree, _) = build_tree(&solution.tree_config());
+    let mut app = App { nav: TreeNav::new(tree)?, filter: None, input: None, message: None };
+
+    let _terminal = RawTerminal::enter().map_err(|e| format!("Cannot set up the terminal: {}", e))?;
+    let mut out = std::io::stdout();
+    loop {
+        let view = node_view(solution, &app.nav, app.filter)?;
+        draw(&mut out, title, &app, &view).map_err(|e| e.to_string())?;
+        let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Press && !app.handle_key(key) {
+            return Ok(());
+        }
+    }
+}
+
+/// Browser state between key presses.
+struct App {
+    nav: TreeNav,
+    filter: Option<ClassFilter>,
+    /// Text typed in the filter box while it's open.
+    input: Option<String>,
+    /// Shown under the grid until the next key.
+    message: Option<String>,
+}
+
+impl App {
+    /// Apply a key; false to quit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.message = None;
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        if let Some(input) = &mut self.input {
+            match key.code {
+                KeyCode::Enter => {
+                    let text = self.input.take().unwrap_or_default();
+                    if text.trim().is_empty() {
+                        self.filter = None;
+                    } else {
+                        match ClassFilter::parse(&text) {
+                            Ok(filter) => self.filter = Some(filter),
+                            Err(e) => self.message = Some(e),
+                        }
+                    }
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.nav.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => self.nav.select_next(),
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => {
+                if let Err(e) = self.nav.descend() {
+                    self.message = Some(e);
+                }
+            }
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => {
+                self.message = (!self.nav.ascend()).then(|| "Already at the root".to_string());
+            }
+            KeyCode::Char('/') | KeyCode::Char('f') => self.input = Some(String::new()),
+            KeyCode::Char('c') => self.filter = None,
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Raw mode on the alternate screen, left when dropped or on panic.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> std::io::Result<RawTerminal> {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            previous(info);
+        }));
+        terminal::enable_raw_mode()?;
+        // From here on, dropping the guard undoes whatever succeeded
+        let guard = RawTerminal;
+        execute!(std::io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    execute!(std::io::stdout(), cursor::Show, terminal::LeaveAlternateScreen).ok();
+    terminal::disable_raw_mode().ok();
+}
+
+const GRID_RANKS: [char; 13] = ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'];
+
+fn draw(out: &mut impl Write, title: &str, app: &App, view: &NodeView) -> std::io::Result<()> {
+    queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+    let mut line = |text: String| write!(out, "{}\r\n", text);
+
+    line(format!(" {}", title.bold()))?;
+    let path = if view.line.is_empty() { "root".to_string() } else { view.line.join(" → ") };
+    line(format!(" Line: {}", path))?;
+    line(format!(" {} to act, {:.1} combos", view.player.bold(), view.combos))?;
+    line(String::new())?;
+
+    for (a, (action, freq)) in view.actions.iter().zip(&view.frequencies).enumerate() {
+        let text = format!("{:<12} {:>5.1}%", action, freq * 100.0);
+        if a == app.nav.selected() {
+            line(format!(" > {}", text.reverse()))?;
+        } else {
+            line(format!("   {}", text))?;
+        }
+    }
+    line(String::new())?;
+
+    let filter = app.filter.map(|f| format!(", {} only", f.label())).unwrap_or_default();
+    line(format!(" {} % by hand{}", view.actions[app.nav.selected()].clone().bold(), filter))?;
+    let header: String = GRID_RANKS.iter().map(|r| format!("{:>4}", r)).collect();
+    line(format!("   {}", header))?;
+    for (i, r) in GRID_RANKS.iter().enumerate() {
+        let cells: String = (0..13)
+            .map(|j| match view.grid[i * 13 + j] {
+                None => format!("{:>4}", "·").dark_grey().to_string(),
+                Some(freq) => {
+                    let pct = format!("{:>4}", (freq * 100.0).round() as u32);
+                    if freq > 0.70 {
+                        pct.green().bold().to_string()
+                    } else if freq > 0.30 {
+                        pct.yellow().to_string()
+                    } else {
+                        pct.dark_grey().to_string()
+                    }
+                }
+            })
+            .collect();
+        line(format!(" {}{}", r.to_string().bold(), cells))?;
+    }
+    line(String::new())?;
+
+    match &app.input {
+        Some(input) => line(format!(" Filter (hand class, e.g. top pair, fd, value): {}_", input))?,
+        None => line(
+            " ↑/↓ action  →/Enter take it  ←/Backspace back  / filter  c clear filter  q quit"
+                .dark_grey()
+                .to_string(),
+        )?,
+    }
+    if let Some(message) = &app.message {
+        line(format!(" {}", message.as_str().yellow()))?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_filters_parse_labels_groups_and_prefixes() {
+        assert_eq!(ClassFilter::parse("Top Pair"), Ok(ClassFilter::Class(HandClass::TopPair)));
+        assert_eq!(ClassFilter::parse("fd"), Ok(ClassFilter::Class(HandClass::FlushDraw)));
+        assert_eq!(ClassFilter::parse("sec"), Ok(ClassFilter::Class(HandClass::SecondPair)));
+        assert_eq!(ClassFilter::parse("value"), Ok(ClassFilter::Group(HandGroup::Value)));
+        assert!(ClassFilter::parse("f").unwrap_err().contains("could be"));
+        assert!(ClassFilter::parse("kings").unwrap_err().starts_with("Unknown hand class"));
+        assert!(ClassFilter::Group(HandGroup::Bluff).matches(HandClass::Gutshot));
+        assert!(!ClassFilter::Class(HandClass::Set).matches(HandClass::Trips));
+    }
+}
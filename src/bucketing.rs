@@ -1,19 +1,24 @@
 //! Equity-based hand bucketing for flop solver.
 //!
-//! Groups hand combos into equal-frequency equity buckets to reduce the
-//! information set count from ~1000 combos to ~200 buckets. Equity is
-//! computed via Monte Carlo sampling against a uniform random opponent.
+//! Groups hand combos into buckets to reduce the information set count from
+//! ~1000 combos to ~200 buckets. Equity is computed against a uniform random
+//! opponent. `BucketingStrategy::EquityOnly` slices combos into
+//! equal-frequency equity buckets; `BucketingStrategy::EHS2` also looks at how
+//! that equity spreads over the runouts (potential) and whether the combo is
+//! drawing, and clusters the features with k-means, so a pair and a combo
+//! draw of the same equity land in different buckets.
 
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::lookup_eval::evaluate_fast;
 
 /// Compute equity of a specific combo (c0, c1) against a uniformly random
-/// opponent hand on the given board, using Monte Carlo sampling.
+/// opponent hand on the given board, using Monte Carlo sampling from `rng`.
 ///
 /// For river (5 cards): exhaustive enumeration over all possible opponent hands.
 /// For flop/turn (3-4 cards): Monte Carlo sampling of runouts + opponents.
-pub fn combo_equity_vs_random(c0: u8, c1: u8, board: &[u8], num_samples: usize) -> f64 {
+pub fn combo_equity_vs_random<R: Rng>(c0: u8, c1: u8, board: &[u8], num_samples: usize, rng: &mut R) -> f64 {
     let board_len = board.len();
 
     // Build dead card set
@@ -29,7 +34,7 @@ pub fn combo_equity_vs_random(c0: u8, c1: u8, board: &[u8], num_samples: usize)
         exhaustive_river_equity(c0, c1, board, &dead)
     } else {
         // Flop or turn: Monte Carlo
-        monte_carlo_equity(c0, c1, board, &dead, num_samples)
+        monte_carlo_equity(c0, c1, board, &dead, num_samples, rng)
     }
 }
 
@@ -70,16 +75,16 @@ fn exhaustive_river_equity(c0: u8, c1: u8, board: &[u8], dead: &[bool; 52]) -> f
 }
 
 /// Monte Carlo equity for flop/turn boards.
-fn monte_carlo_equity(
+fn monte_carlo_equity<R: Rng>(
     c0: u8,
     c1: u8,
     board: &[u8],
     dead: &[bool; 52],
     num_samples: usize,
+    rng: &mut R,
 ) -> f64 {
     let live: Vec<u8> = (0..52u8).filter(|&c| !dead[c as usize]).collect();
     let cards_needed = 5 - board.len(); // cards to complete the board
-    let mut rng = rand::thread_rng();
 
     let mut wins = 0.0;
     let mut total = 0.0;
@@ -145,6 +150,53 @@ fn monte_carlo_equity(
     }
 }
 
+/// How combos are grouped into buckets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BucketingStrategy {
+    /// Equal-frequency slices of equity.
+    #[default]
+    EquityOnly,
+    /// k-means over equity, its spread over the runouts and a draw flag.
+    /// On a river board nothing is left to come, so this is `EquityOnly`.
+    #[allow(clippy::upper_case_acronyms)]
+    EHS2,
+}
+
+impl BucketingStrategy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BucketingStrategy::EquityOnly => "equity",
+            BucketingStrategy::EHS2 => "ehs2",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<BucketingStrategy> {
+        match s.to_lowercase().as_str() {
+            "equity" | "equity-only" => Some(BucketingStrategy::EquityOnly),
+            "ehs2" => Some(BucketingStrategy::EHS2),
+            _ => None,
+        }
+    }
+
+    /// `assign_buckets_with_rng` under this strategy.
+    pub fn assign<R: Rng>(
+        self,
+        combos: &[(u8, u8)],
+        board: &[u8],
+        num_buckets: usize,
+        num_samples: usize,
+        rng: &mut R,
+    ) -> Vec<u16> {
+        match self {
+            BucketingStrategy::EHS2 if board.len() < 5 => {
+                let features = combo_features(combos, board, num_samples, rng);
+                kmeans_buckets(&features, num_buckets)
+            }
+            _ => assign_buckets_with_rng(combos, board, num_buckets, num_samples, rng),
+        }
+    }
+}
+
 /// Assign combos to equity buckets using equal-frequency binning.
 ///
 /// Returns a Vec<u16> of the same length as `combos`, where each element
@@ -157,6 +209,17 @@ pub fn assign_buckets(
     board: &[u8],
     num_buckets: usize,
     num_samples: usize,
+) -> Vec<u16> {
+    assign_buckets_with_rng(combos, board, num_buckets, num_samples, &mut rand::thread_rng())
+}
+
+/// `assign_buckets`, sampling equities from `rng` (for reproducible solves).
+pub fn assign_buckets_with_rng<R: Rng>(
+    combos: &[(u8, u8)],
+    board: &[u8],
+    num_buckets: usize,
+    num_samples: usize,
+    rng: &mut R,
 ) -> Vec<u16> {
     let n = combos.len();
     if n == 0 {
@@ -166,25 +229,249 @@ pub fn assign_buckets(
     // Compute equity for each combo
     let equities: Vec<f64> = combos
         .iter()
-        .map(|&(c0, c1)| combo_equity_vs_random(c0, c1, board, num_samples))
+        .map(|&(c0, c1)| combo_equity_vs_random(c0, c1, board, num_samples, rng))
         .collect();
 
-    // Sort by equity, keeping track of original indices
-    let mut indexed: Vec<(usize, f64)> = equities.iter().enumerate().map(|(i, &e)| (i, e)).collect();
+    equal_frequency_buckets(&equities, num_buckets)
+}
+
+/// Sort `values` and cut them into `num_buckets` equally-sized groups
+/// (one per value if there are fewer values than buckets).
+fn equal_frequency_buckets(values: &[f64], num_buckets: usize) -> Vec<u16> {
+    let n = values.len();
+    if n == 0 {
+        return vec![];
+    }
+    // Sort by value, keeping track of original indices
+    let mut indexed: Vec<(usize, f64)> = values.iter().enumerate().map(|(i, &e)| (i, e)).collect();
     indexed.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
 
-    // Equal-frequency binning
     let actual_buckets = num_buckets.min(n);
     let mut result = vec![0u16; n];
-
     for (rank, &(orig_idx, _)) in indexed.iter().enumerate() {
         let bucket = (rank * actual_buckets / n).min(actual_buckets - 1);
         result[orig_idx] = bucket as u16;
     }
-
     result
 }
 
+// ---------------------------------------------------------------------------
+// EHS2 features
+// ---------------------------------------------------------------------------
+
+/// Scale of the potential (standard deviation of equity over the runouts)
+/// against equity in the k-means distance.
+const POTENTIAL_WEIGHT: f64 = 1.0;
+/// Distance a draw flag adds between otherwise equal combos.
+const DRAW_WEIGHT: f64 = 0.1;
+/// Runouts sampled per flop: one per this many `num_samples`. A turn
+/// enumerates every river instead.
+const SAMPLES_PER_RUNOUT: usize = 10;
+/// Lloyd iterations cap; k-means usually settles well before.
+const KMEANS_MAX_ITERATIONS: usize = 25;
+
+/// Hand scores of every two-card hand on one complete board.
+struct RunoutScores {
+    /// Score of hand (a, b) at `a * 52 + b` (and `b * 52 + a`).
+    scores: Vec<u32>,
+    /// Every live hand's score, sorted.
+    all: Vec<u32>,
+    /// Sorted scores of the live hands holding each card.
+    by_card: Vec<Vec<u32>>,
+}
+
+impl RunoutScores {
+    fn new(board: &[u8; 5]) -> Self {
+        let live: Vec<u8> = (0..52u8).filter(|c| !board.contains(c)).collect();
+        let mut scores = vec![0u32; 52 * 52];
+        let mut all = Vec::with_capacity(live.len() * (live.len() - 1) / 2);
+        let mut by_card = vec![Vec::new(); 52];
+        for (i, &a) in live.iter().enumerate() {
+            for &b in &live[i + 1..] {
+                let score = evaluate_fast(&[a, b, board[0], board[1], board[2], board[3], board[4]]);
+                scores[a as usize * 52 + b as usize] = score;
+                scores[b as usize * 52 + a as usize] = score;
+                all.push(score);
+                by_card[a as usize].push(score);
+                by_card[b as usize].push(score);
+            }
+        }
+        all.sort_unstable();
+        for row in &mut by_card {
+            row.sort_unstable();
+        }
+        RunoutScores { scores, all, by_card }
+    }
+
+    /// Share of the opponent hands (none holding c0 or c1) that (c0, c1)
+    /// beats, ties counting half.
+    fn strength(&self, c0: u8, c1: u8) -> f64 {
+        let mine = self.scores[c0 as usize * 52 + c1 as usize];
+        let beaten = |sorted: &[u32]| {
+            let below = sorted.partition_point(|&s| s < mine);
+            let tied = sorted.partition_point(|&s| s <= mine) - below;
+            below as f64 + 0.5 * tied as f64
+        };
+        let (row0, row1) = (&self.by_card[c0 as usize], &self.by_card[c1 as usize]);
+        // Both card rows hold (c0, c1) itself, a tie, so add it back once
+        let wins = beaten(&self.all) - beaten(row0) - beaten(row1) + 0.5;
+        let total = self.all.len() + 1 - row0.len() - row1.len();
+        if total > 0 { wins / total as f64 } else { 0.5 }
+    }
+}
+
+/// Complete boards to measure a flop or turn on: every river of a turn, or
+/// `num_samples / SAMPLES_PER_RUNOUT` sampled turn-river pairs of a flop.
+fn sample_runouts<R: Rng>(board: &[u8], num_samples: usize, rng: &mut R) -> Vec<[u8; 5]> {
+    let live: Vec<u8> = (0..52u8).filter(|c| !board.contains(c)).collect();
+    let mut full = [0u8; 5];
+    full[..board.len()].copy_from_slice(board);
+    if board.len() == 4 {
+        return live.iter().map(|&river| { full[4] = river; full }).collect();
+    }
+    (0..(num_samples / SAMPLES_PER_RUNOUT).max(1))
+        .map(|_| {
+            let turn = rng.gen_range(0..live.len());
+            let mut river = rng.gen_range(0..live.len() - 1);
+            if river >= turn {
+                river += 1;
+            }
+            full[3] = live[turn];
+            full[4] = live[river];
+            full
+        })
+        .collect()
+}
+
+/// [equity, potential, draw] of each combo on a flop or turn, scaled for
+/// k-means: equity and its standard deviation over the runouts the combo's
+/// cards allow, and whether it holds a flush or straight draw.
+fn combo_features<R: Rng>(combos: &[(u8, u8)], board: &[u8], num_samples: usize, rng: &mut R) -> Vec<[f64; 3]> {
+    let runouts = sample_runouts(board, num_samples, rng);
+    let mut sums = vec![(0.0f64, 0.0f64, 0usize); combos.len()];
+    for runout in &runouts {
+        let scores = RunoutScores::new(runout);
+        for (&(c0, c1), sum) in combos.iter().zip(&mut sums) {
+            if runout.contains(&c0) || runout.contains(&c1) {
+                continue;
+            }
+            let strength = scores.strength(c0, c1);
+            sum.0 += strength;
+            sum.1 += strength * strength;
+            sum.2 += 1;
+        }
+    }
+    combos
+        .iter()
+        .zip(sums)
+        .map(|(&(c0, c1), (total, squares, count))| {
+            let (equity, potential) = if count > 0 {
+                let mean = total / count as f64;
+                (mean, (squares / count as f64 - mean * mean).max(0.0).sqrt())
+            } else {
+                (0.5, 0.0)
+            };
+            let draw = if has_draw(c0, c1, board) { DRAW_WEIGHT } else { 0.0 };
+            [equity, POTENTIAL_WEIGHT * potential, draw]
+        })
+        .collect()
+}
+
+/// Four to a flush, or four ranks of a straight, using at least one hole
+/// card, without the flush or straight already made.
+fn has_draw(c0: u8, c1: u8, board: &[u8]) -> bool {
+    let cards: Vec<u8> = [c0, c1].iter().chain(board).copied().collect();
+    let flush_draw = [c0 % 4, c1 % 4].iter().any(|&suit| cards.iter().filter(|&&c| c % 4 == suit).count() == 4)
+        && !(0..4).any(|suit| cards.iter().filter(|&&c| c % 4 == suit).count() >= 5);
+
+    // Rank bits with the ace also below the two
+    let rank_bits = |cards: &[u8]| {
+        cards.iter().fold(0u16, |bits, &c| {
+            let rank = c / 4;
+            let bits = bits | 1 << (rank + 1);
+            if rank == 12 { bits | 1 } else { bits }
+        })
+    };
+    let all = rank_bits(&cards);
+    let board_only = rank_bits(board);
+    let windows = || (0..10).map(|low| 0b11111u16 << low);
+    let straight_made = windows().any(|w| all & w == w);
+    let straight_draw = windows().any(|w| (all & w).count_ones() == 4 && (board_only & w).count_ones() < 4);
+    flush_draw || (straight_draw && !straight_made)
+}
+
+/// Cluster `features` into at most `num_buckets` buckets with k-means,
+/// starting from equity quantiles. Buckets are numbered by centroid equity,
+/// with empty clusters dropped.
+fn kmeans_buckets(features: &[[f64; 3]], num_buckets: usize) -> Vec<u16> {
+    let n = features.len();
+    let equities: Vec<f64> = features.iter().map(|f| f[0]).collect();
+    if n <= num_buckets {
+        return equal_frequency_buckets(&equities, num_buckets);
+    }
+
+    let mut by_equity: Vec<usize> = (0..n).collect();
+    by_equity.sort_by(|&a, &b| equities[a].partial_cmp(&equities[b]).unwrap());
+    let mut centroids: Vec<[f64; 3]> =
+        (0..num_buckets).map(|k| features[by_equity[(2 * k + 1) * n / (2 * num_buckets)]]).collect();
+
+    let mut assignment = balanced_assignment(features, &centroids);
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let mut sums = vec![([0.0f64; 3], 0usize); num_buckets];
+        for (f, &k) in features.iter().zip(&assignment) {
+            for (sum, x) in sums[k].0.iter_mut().zip(f) {
+                *sum += x;
+            }
+            sums[k].1 += 1;
+        }
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            if count > 0 {
+                *centroid = sum.map(|x| x / count as f64);
+            }
+        }
+        let next = balanced_assignment(features, &centroids);
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+    }
+
+    // Number the used clusters by centroid equity
+    let mut used: Vec<usize> = (0..num_buckets).filter(|k| assignment.contains(k)).collect();
+    used.sort_by(|&a, &b| centroids[a][0].partial_cmp(&centroids[b][0]).unwrap());
+    let mut bucket_of = vec![0u16; num_buckets];
+    for (bucket, &k) in used.iter().enumerate() {
+        bucket_of[k] = bucket as u16;
+    }
+    assignment.iter().map(|&k| bucket_of[k]).collect()
+}
+
+/// Assign each feature vector to the nearest centroid that still has room,
+/// closest pairs first, with room for `ceil(n / k)` per centroid. Plain
+/// nearest-centroid assignment lets a few clusters swallow most of the
+/// range, which costs more than the extra features gain.
+fn balanced_assignment(features: &[[f64; 3]], centroids: &[[f64; 3]]) -> Vec<usize> {
+    let n = features.len();
+    let capacity = n.div_ceil(centroids.len());
+    let distance = |a: &[f64; 3], b: &[f64; 3]| a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f64>();
+    let mut pairs: Vec<(f64, usize, usize)> = features
+        .iter()
+        .enumerate()
+        .flat_map(|(i, f)| centroids.iter().enumerate().map(move |(k, c)| (distance(f, c), i, k)))
+        .collect();
+    pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let mut assignment = vec![usize::MAX; n];
+    let mut sizes = vec![0usize; centroids.len()];
+    for (_, i, k) in pairs {
+        if assignment[i] == usize::MAX && sizes[k] < capacity {
+            assignment[i] = k;
+            sizes[k] += 1;
+        }
+    }
+    assignment
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,7 +494,7 @@ mod tests {
         let c0 = card_to_index(&crate::cards::parse_card("As").unwrap());
         let c1 = card_to_index(&crate::cards::parse_card("Ac").unwrap());
 
-        let eq = combo_equity_vs_random(c0, c1, &board, 0);
+        let eq = combo_equity_vs_random(c0, c1, &board, 0, &mut rand::thread_rng());
         assert!(eq > 0.7, "AA should have high equity on low board, got {:.3}", eq);
     }
 
@@ -218,7 +505,7 @@ mod tests {
         let c0 = card_to_index(&crate::cards::parse_card("7h").unwrap());
         let c1 = card_to_index(&crate::cards::parse_card("2c").unwrap());
 
-        let eq = combo_equity_vs_random(c0, c1, &board, 0);
+        let eq = combo_equity_vs_random(c0, c1, &board, 0, &mut rand::thread_rng());
         assert!(eq < 0.5, "72o should have low equity on AKQ96 board, got {:.3}", eq);
     }
 
@@ -228,7 +515,7 @@ mod tests {
         let c0 = card_to_index(&crate::cards::parse_card("As").unwrap());
         let c1 = card_to_index(&crate::cards::parse_card("Ah").unwrap());
 
-        let eq = combo_equity_vs_random(c0, c1, &board, 500);
+        let eq = combo_equity_vs_random(c0, c1, &board, 500, &mut rand::thread_rng());
         assert!(eq > 0.5, "AA should have >50% equity on flop, got {:.3}", eq);
         assert!(eq < 1.0, "Equity should be <1.0, got {:.3}", eq);
     }
@@ -256,6 +543,76 @@ mod tests {
         assert!(buckets[0] != buckets[1] || combos.len() <= 1);
     }
 
+    fn card(s: &str) -> u8 {
+        card_to_index(&crate::cards::parse_card(s).unwrap())
+    }
+
+    #[test]
+    fn runout_strength_matches_exhaustive_equity() {
+        let board = board_indices("Ks9d4c7h2s");
+        let scores = RunoutScores::new(&[board[0], board[1], board[2], board[3], board[4]]);
+        let mut dead = [false; 52];
+        for (c0, c1) in [("As", "Ah"), ("Kd", "Qh"), ("8c", "6c")] {
+            let (c0, c1) = (card(c0), card(c1));
+            dead.fill(false);
+            for &c in board.iter().chain([&c0, &c1]) {
+                dead[c as usize] = true;
+            }
+            let exact = exhaustive_river_equity(c0, c1, &board, &dead);
+            assert!((scores.strength(c0, c1) - exact).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn draws_are_flagged_but_made_hands_and_board_draws_are_not() {
+        let board = board_indices("Ts9s2d");
+        assert!(has_draw(card("As"), card("3s"), &board), "nut flush draw");
+        assert!(has_draw(card("Qh"), card("Jc"), &board), "open-ended straight draw");
+        assert!(!has_draw(card("Td"), card("Tc"), &board), "a set is not a draw");
+        assert!(!has_draw(card("Kc"), card("Kh"), &board));
+        // The board's own four-straight isn't the hand's draw
+        assert!(!has_draw(card("Ac"), card("2h"), &board_indices("8h7c6d5s")));
+    }
+
+    #[test]
+    fn ehs2_separates_a_draw_from_a_made_hand_of_similar_equity() {
+        let board = board_indices("Ts9s2d");
+        // A weak made pair and a flush draw with two overcards
+        let combos = vec![(card("2h"), card("2c")), (card("Ks"), card("Qs")), (card("3h"), card("3c"))];
+        let mut rng = rand::thread_rng();
+        let features = combo_features(&combos, &board, 1000, &mut rng);
+        assert!(features[1][1] > features[0][1], "the draw's equity should swing more: {:?}", features);
+        assert_eq!(features[1][2], DRAW_WEIGHT);
+
+        let buckets = BucketingStrategy::EHS2.assign(&combos, &board, 2, 1000, &mut rng);
+        assert_eq!(buckets.len(), 3);
+        assert!(buckets.iter().all(|&b| b < 2));
+        // On the river there's nothing left to come
+        let river = board_indices("Ts9s2d5h8c");
+        assert_eq!(
+            BucketingStrategy::EHS2.assign(&combos, &river, 2, 0, &mut rng),
+            assign_buckets(&combos, &river, 2, 0)
+        );
+    }
+
+    #[test]
+    fn kmeans_buckets_are_numbered_by_equity_and_dense() {
+        let features = vec![
+            [0.9, 0.0, 0.0],
+            [0.1, 0.0, 0.0],
+            [0.5, 0.3, DRAW_WEIGHT],
+            [0.5, 0.0, 0.0],
+            [0.11, 0.0, 0.0],
+            [0.91, 0.0, 0.0],
+        ];
+        let buckets = kmeans_buckets(&features, 4);
+        assert_eq!(buckets[1], 0);
+        assert_eq!(buckets[1], buckets[4]);
+        assert_eq!(buckets[0], buckets[5]);
+        assert_ne!(buckets[2], buckets[3], "same equity, different potential");
+        assert_eq!(*buckets.iter().max().unwrap(), 3);
+    }
+
     #[test]
     fn bucket_empty_combos() {
         let board = board_indices("2s3h4d5c8h");
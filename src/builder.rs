@@ -0,0 +1,161 @@
+//! Builders for the flop, turn and river solver configs.
+//!
+//! Board and both ranges are required; pot, stack and iterations default to a
+//! 10-chip pot, 100 behind and the street's recommended iterations. Anything
+//! else (buckets, seed, bet sizes, ...) is a public field of the built config.
+//!
+//! ```
+//! use gto_cli::{solve_river, RiverSolverConfig};
+//!
+//! let config = RiverSolverConfig::builder()
+//!     .board("Ks9d4c7h2s")
+//!     .oop("AA,KK,T8s")
+//!     .ip("QQ,JJ,AQs")
+//!     .pot(10.0)
+//!     .stack(50.0)
+//!     .iterations(200)
+//!     .build()?;
+//! let solution = solve_river(&config);
+//! assert_eq!(solution.strategies[0].player, "OOP");
+//! # Ok::<(), gto_cli::SolverError>(())
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::error::{SolverError, SolverResult};
+use crate::flop_solver::FlopSolverConfig;
+use crate::quality::{RECOMMENDED_FLOP_ITERATIONS, RECOMMENDED_RIVER_ITERATIONS, RECOMMENDED_TURN_ITERATIONS};
+use crate::river_solver::RiverSolverConfig;
+use crate::turn_solver::TurnSolverConfig;
+
+const DEFAULT_POT: f64 = 10.0;
+const DEFAULT_STACK: f64 = 100.0;
+
+/// A solver config built from a board, two ranges, pot, stack and iterations.
+pub trait SpotConfig: Sized {
+    const DEFAULT_ITERATIONS: usize;
+
+    fn from_spot(board: &str, oop: &str, ip: &str, pot: f64, stack: f64, iterations: usize) -> SolverResult<Self>;
+}
+
+impl SpotConfig for FlopSolverConfig {
+    const DEFAULT_ITERATIONS: usize = RECOMMENDED_FLOP_ITERATIONS;
+
+    fn from_spot(board: &str, oop: &str, ip: &str, pot: f64, stack: f64, iterations: usize) -> SolverResult<Self> {
+        FlopSolverConfig::new(board, oop, ip, pot, stack, iterations)
+    }
+}
+
+impl SpotConfig for TurnSolverConfig {
+    const DEFAULT_ITERATIONS: usize = RECOMMENDED_TURN_ITERATIONS;
+
+    fn from_spot(board: &str, oop: &str, ip: &str, pot: f64, stack: f64, iterations: usize) -> SolverResult<Self> {
+        TurnSolverConfig::new(board, oop, ip, pot, stack, iterations)
+    }
+}
+
+impl SpotConfig for RiverSolverConfig {
+    const DEFAULT_ITERATIONS: usize = RECOMMENDED_RIVER_ITERATIONS;
+
+    fn from_spot(board: &str, oop: &str, ip: &str, pot: f64, stack: f64, iterations: usize) -> SolverResult<Self> {
+        RiverSolverConfig::new(board, oop, ip, pot, stack, iterations)
+    }
+}
+
+/// Builder for a `SpotConfig`; see the module docs.
+#[derive(Debug, Clone)]
+pub struct SpotBuilder<C> {
+    board: Option<String>,
+    oop: Option<String>,
+    ip: Option<String>,
+    pot: f64,
+    stack: f64,
+    iterations: Option<usize>,
+    config: PhantomData<C>,
+}
+
+pub type FlopSolverConfigBuilder = SpotBuilder<FlopSolverConfig>;
+pub type TurnSolverConfigBuilder = SpotBuilder<TurnSolverConfig>;
+pub type RiverSolverConfigBuilder = SpotBuilder<RiverSolverConfig>;
+
+impl<C: SpotConfig> Default for SpotBuilder<C> {
+    fn default() -> Self {
+        SpotBuilder {
+            board: None,
+            oop: None,
+            ip: None,
+            pot: DEFAULT_POT,
+            stack: DEFAULT_STACK,
+            iterations: None,
+            config: PhantomData,
+        }
+    }
+}
+
+impl<C: SpotConfig> SpotBuilder<C> {
+    /// Board cards, e.g. "Ks9d4c".
+    pub fn board(mut self, board: &str) -> Self {
+        self.board = Some(board.to_string());
+        self
+    }
+
+    /// OOP range, e.g. "TT+,AKs,AQo:0.5".
+    pub fn oop(mut self, range: &str) -> Self {
+        self.oop = Some(range.to_string());
+        self
+    }
+
+    /// IP range.
+    pub fn ip(mut self, range: &str) -> Self {
+        self.ip = Some(range.to_string());
+        self
+    }
+
+    pub fn pot(mut self, pot: f64) -> Self {
+        self.pot = pot;
+        self
+    }
+
+    /// Effective stack behind.
+    pub fn stack(mut self, stack: f64) -> Self {
+        self.stack = stack;
+        self
+    }
+
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    pub fn build(self) -> SolverResult<C> {
+        let board = self.board.ok_or(SolverError::MissingField { field: "board" })?;
+        let oop = self.oop.ok_or(SolverError::MissingField { field: "oop range" })?;
+        let ip = self.ip.ok_or(SolverError::MissingField { field: "ip range" })?;
+        if !(self.pot > 0.0 && self.pot.is_finite()) {
+            return Err(SolverError::InvalidConfig { field: "pot", reason: "must be positive".to_string() });
+        }
+        if !(self.stack >= 0.0 && self.stack.is_finite()) {
+            return Err(SolverError::InvalidConfig { field: "stack", reason: "can't be negative".to_string() });
+        }
+        let iterations = self.iterations.unwrap_or(C::DEFAULT_ITERATIONS);
+        C::from_spot(&board, &oop, &ip, self.pot, self.stack, iterations)
+    }
+}
+
+impl FlopSolverConfig {
+    pub fn builder() -> FlopSolverConfigBuilder {
+        SpotBuilder::default()
+    }
+}
+
+impl TurnSolverConfig {
+    pub fn builder() -> TurnSolverConfigBuilder {
+        SpotBuilder::default()
+    }
+}
+
+impl RiverSolverConfig {
+    pub fn builder() -> RiverSolverConfigBuilder {
+        SpotBuilder::default()
+    }
+}
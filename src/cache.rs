@@ -0,0 +1,496 @@
+//! `gto cache list|info|prune`: what is in the solver cache directory.
+//!
+//! Every solver writes into `solver_cache_dir()` (`$GTO_CACHE_DIR` when set,
+//! otherwise `~/.gto-cli/solver`), and the file name says what a file holds:
+//!
+//! - `preflop_{table}_{stack}bb_{rake}pct.json` — a `PreflopSolution` as JSON
+//! - `pushfold_{pos}_{stack}bb_{ante}ante_{rake}pct.json` — a
+//!   `MultiwayPushFoldResult` as JSON
+//! - `flop_{board}_{oop}_{ip}_{pot}_{stack}.bin` — a `FlopSolution` (bincode)
+//! - `turn_{board}_{oop}_{ip}_{pot}_{stack}.bin` — a `TurnSolution` (bincode)
+//! - `river_{board}_{oop}_{ip}_{pot}_{stack}[_locked].bin` — a `RiverSolution`
+//!
+//! Flop checkpoints (`.ckpt`), the flop summary index and solve locks live in
+//! the same directory; they aren't solutions and are left alone here.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use colored::Colorize;
+
+use crate::cache_index::{solver_cache_dir, CacheIndex};
+use crate::flop_solver::FlopSolution;
+use crate::game_tree::MultiwayPushFoldResult;
+use crate::preflop_solver::PreflopSolution;
+use crate::quality::{pct_of_pot, Grade};
+use crate::river_solver::RiverSolution;
+use crate::status::io_error;
+use crate::turn_solver::TurnSolution;
+
+/// Which solver wrote a cache file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CacheKind {
+    Preflop,
+    PushFold,
+    Flop,
+    Turn,
+    River,
+}
+
+impl CacheKind {
+    pub const ALL: [CacheKind; 5] =
+        [CacheKind::Preflop, CacheKind::PushFold, CacheKind::Flop, CacheKind::Turn, CacheKind::River];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheKind::Preflop => "preflop",
+            CacheKind::PushFold => "pushfold",
+            CacheKind::Flop => "flop",
+            CacheKind::Turn => "turn",
+            CacheKind::River => "river",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<CacheKind> {
+        let s = s.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|k| k.as_str() == s)
+    }
+
+    /// The kind of solution a cache file name holds, or `None` for anything
+    /// else in the directory.
+    pub fn of_file(name: &str) -> Option<CacheKind> {
+        let kind = Self::ALL.into_iter().find(|k| name.starts_with(&format!("{}_", k.as_str())))?;
+        let ext = if matches!(kind, CacheKind::Preflop | CacheKind::PushFold) { ".json" } else { ".bin" };
+        name.ends_with(ext).then_some(kind)
+    }
+}
+
+/// One solution file in the cache directory.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub file: String,
+    pub kind: CacheKind,
+    /// File size in bytes.
+    pub len: u64,
+    /// Time since the file was last written.
+    pub age: Duration,
+}
+
+/// Every solution file in `dir`, sorted by kind then file name. A missing
+/// directory is empty.
+pub fn scan(dir: &Path) -> Vec<CacheEntry> {
+    let now = SystemTime::now();
+    let mut entries: Vec<CacheEntry> = std::fs::read_dir(dir)
+        .map(|rd| {
+            rd.filter_map(|e| {
+                let e = e.ok()?;
+                let file = e.file_name().into_string().ok()?;
+                let kind = CacheKind::of_file(&file)?;
+                let meta = e.metadata().ok()?;
+                let age = meta.modified().ok().and_then(|t| now.duration_since(t).ok()).unwrap_or_default();
+                Some(CacheEntry { file, kind, len: meta.len(), age })
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.file.cmp(&b.file)));
+    entries
+}
+
+/// A deserialized cache file.
+pub enum CachedSolution {
+    Preflop(PreflopSolution),
+    PushFold(MultiwayPushFoldResult),
+    Flop(Box<FlopSolution>),
+    Turn(TurnSolution),
+    River(RiverSolution),
+}
+
+/// Read a cache file: JSON for preflop and push/fold solutions, bincode for
+/// the rest.
+pub fn load(path: &Path) -> Result<CachedSolution, String> {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let kind = CacheKind::of_file(name).ok_or_else(|| format!("{} is not a solver cache file", path.display()))?;
+    if kind == CacheKind::Flop {
+        return FlopSolution::load_file(path).map(|s| CachedSolution::Flop(Box::new(s)));
+    }
+    let data = std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let not_a = |e: &dyn std::fmt::Display| format!("{} is not a {} solution: {}", path.display(), kind.as_str(), e);
+    Ok(match kind {
+        CacheKind::Preflop => CachedSolution::Preflop(serde_json::from_slice(&data).map_err(|e| not_a(&e))?),
+        CacheKind::PushFold => CachedSolution::PushFold(serde_json::from_slice(&data).map_err(|e| not_a(&e))?),
+        CacheKind::Turn => {
+            let mut solution: TurnSolution = bincode::deserialize(&data).map_err(|e| not_a(&e))?;
+            solution.fill_exploitability_pct();
+            CachedSolution::Turn(solution)
+        }
+        CacheKind::River => CachedSolution::River(bincode::deserialize(&data).map_err(|e| not_a(&e))?),
+        CacheKind::Flop => unreachable!(),
+    })
+}
+
+/// The headline numbers of a cached solution. Preflop and push/fold
+/// solutions have no board, pot or grade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheHeader {
+    pub kind: CacheKind,
+    pub board: String,
+    /// "BBvBTN", the table size for preflop solutions, or the shover for
+    /// push/fold.
+    pub seats: String,
+    pub pot: Option<f64>,
+    pub stack: f64,
+    pub iterations: usize,
+    pub exploitability: Option<f64>,
+    pub grade: Option<Grade>,
+}
+
+fn seats(oop_pos: &str, ip_pos: &str) -> String {
+    if oop_pos.is_empty() {
+        "-".to_string()
+    } else {
+        format!("{}v{}", oop_pos, ip_pos)
+    }
+}
+
+impl CachedSolution {
+    pub fn header(&self) -> CacheHeader {
+        macro_rules! postflop {
+            ($kind:expr, $s:expr) => {
+                CacheHeader {
+                    kind: $kind,
+                    board: $s.board.clone(),
+                    seats: seats(&$s.oop_pos, &$s.ip_pos),
+                    pot: Some($s.starting_pot),
+                    stack: $s.effective_stack,
+                    iterations: $s.iterations,
+                    exploitability: Some($s.exploitability),
+                    grade: Some($s.solve_quality().grade),
+                }
+            };
+        }
+        match self {
+            CachedSolution::Preflop(s) => CacheHeader {
+                kind: CacheKind::Preflop,
+                board: "-".to_string(),
+                seats: s.table_size.clone(),
+                pot: None,
+                stack: s.stack_bb,
+                iterations: s.iterations,
+                exploitability: None,
+                grade: None,
+            },
+            CachedSolution::PushFold(s) => CacheHeader {
+                kind: CacheKind::PushFold,
+                board: "-".to_string(),
+                seats: s.shover.to_string(),
+                pot: None,
+                stack: s.stack_bb,
+                iterations: s.iterations,
+                exploitability: None,
+                grade: None,
+            },
+            CachedSolution::Flop(s) => postflop!(CacheKind::Flop, s),
+            CachedSolution::Turn(s) => postflop!(CacheKind::Turn, s),
+            CachedSolution::River(s) => postflop!(CacheKind::River, s),
+        }
+    }
+
+    /// One line per root decision: OOP's first action averaged over its
+    /// combos (flop solutions weight combos by range frequency), the preflop
+    /// sizing and each preflop spot's open, 3-bet and flat percentages, or
+    /// the push/fold shove and call-off ranges.
+    pub fn root_summary(&self) -> Vec<String> {
+        fn root_line(actions: &[String], frequencies: &[Vec<f64>], weights: &[f64]) -> String {
+            let mut avg = vec![0.0; actions.len()];
+            let mut total = 0.0;
+            for (i, freqs) in frequencies.iter().enumerate() {
+                let w = weights.get(i).copied().unwrap_or(1.0);
+                total += w;
+                for (a, f) in avg.iter_mut().zip(freqs) {
+                    *a += f * w;
+                }
+            }
+            let parts: Vec<String> = actions
+                .iter()
+                .zip(&avg)
+                .map(|(a, f)| format!("{} {:.1}%", a, f / total.max(1e-12) * 100.0))
+                .collect();
+            format!("OOP: {}", parts.join("  "))
+        }
+        let line = match self {
+            CachedSolution::Preflop(s) => {
+                return std::iter::once(format!("Sizing: {}", s.sizing.label()))
+                    .chain(s.spots.iter().map(|spot| {
+                        format!(
+                            "{} vs {}: open {:.1}%, 3-bet {:.1}%, flat {:.1}%",
+                            spot.opener,
+                            spot.responder,
+                            spot.open_pct(),
+                            spot.three_bet_pct(),
+                            spot.flat_call_pct()
+                        )
+                    }))
+                    .collect();
+            }
+            CachedSolution::PushFold(s) => {
+                let mut lines = vec![format!("{} shove {:.1}%", s.shover, s.shove_pct())];
+                lines.extend(s.call_offs().map(|c| format!("{} call {:.1}%", c.position, c.call_pct())));
+                return lines;
+            }
+            CachedSolution::Flop(s) => s
+                .strategies
+                .iter()
+                .find(|n| n.player == "OOP")
+                .map(|n| root_line(&n.actions, &n.frequencies, &s.oop_combo_weights())),
+            CachedSolution::Turn(s) => s
+                .strategies
+                .iter()
+                .find(|n| n.player == "OOP")
+                .map(|n| root_line(&n.actions, &n.frequencies, &[])),
+            CachedSolution::River(s) => s
+                .strategies
+                .iter()
+                .find(|n| n.player == "OOP")
+                .map(|n| root_line(&n.actions, &n.frequencies, &[])),
+        };
+        line.into_iter().collect()
+    }
+}
+
+/// Header for one entry: flop solutions come from the summary index when it
+/// is current, everything else from the file.
+fn entry_header(dir: &Path, entry: &CacheEntry, index: &mut CacheIndex) -> Result<CacheHeader, String> {
+    if entry.kind == CacheKind::Flop {
+        let r = index.load_summary(&entry.file)?;
+        return Ok(CacheHeader {
+            kind: CacheKind::Flop,
+            board: r.board,
+            seats: seats(&r.oop_pos, &r.ip_pos),
+            pot: Some(r.pot),
+            stack: r.stack,
+            iterations: r.iterations,
+            exploitability: Some(r.exploitability),
+            grade: Some(r.grade),
+        });
+    }
+    load(&dir.join(&entry.file)).map(|s| s.header())
+}
+
+/// Parse an age such as "30d", "12h", "90m", "2w" or "45s".
+pub fn parse_age(s: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid age '{}'. Use a number and a unit: 45s, 90m, 12h, 30d, 2w", s);
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let n: u64 = s[..split].parse().map_err(|_| invalid())?;
+    let unit = match &s[split..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => return Err(invalid()),
+    };
+    let secs = n.checked_mul(unit).ok_or_else(|| format!("Age '{}' is too large", s))?;
+    Ok(Duration::from_secs(secs))
+}
+
+/// Delete every solution file in `dir` at least `older_than` old and of
+/// `kind`, when given, returning what was removed.
+pub fn prune(dir: &Path, older_than: Option<Duration>, kind: Option<CacheKind>) -> Result<Vec<CacheEntry>, String> {
+    let mut removed = Vec::new();
+    for entry in scan(dir) {
+        if older_than.is_some_and(|min| entry.age < min) || kind.is_some_and(|k| entry.kind != k) {
+            continue;
+        }
+        let path = dir.join(&entry.file);
+        std::fs::remove_file(&path).map_err(|e| format!("Cannot remove {}: {}", path.display(), e))?;
+        removed.push(entry);
+    }
+    Ok(removed)
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    let b = bytes as f64;
+    if b >= 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.1} GB", b / (1024.0 * 1024.0 * 1024.0))
+    } else if b >= 1024.0 * 1024.0 {
+        format!("{:.1} MB", b / (1024.0 * 1024.0))
+    } else if b >= 1024.0 {
+        format!("{:.1} KB", b / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn colored_grade(grade: Option<Grade>) -> colored::ColoredString {
+    match grade {
+        Some(g @ (Grade::A | Grade::B)) => g.as_str().green(),
+        Some(g @ Grade::C) => g.as_str().yellow(),
+        Some(g @ Grade::D) => g.as_str().red(),
+        None => "-".normal(),
+    }
+}
+
+/// `gto cache list`: every cached solution, optionally only one kind or
+/// only those graded below `below` (which leaves out preflop solutions).
+pub fn run_cache_list(kind: Option<CacheKind>, below: Option<Grade>) {
+    let dir = solver_cache_dir();
+    let mut index = CacheIndex::load(&dir);
+    let mut unreadable = 0;
+    let rows: Vec<(CacheEntry, Option<CacheHeader>)> = scan(&dir)
+        .into_iter()
+        .filter(|e| kind.is_none_or(|k| e.kind == k))
+        .filter_map(|e| match entry_header(&dir, &e, &mut index) {
+            Ok(h) => below
+                .is_none_or(|min| h.grade.is_some_and(|g| !g.meets(min)))
+                .then_some((e, Some(h))),
+            Err(_) => {
+                unreadable += 1;
+                below.is_none().then_some((e, None))
+            }
+        })
+        .collect();
+
+    println!();
+    println!(
+        "  {} {} cached solutions{} in {}",
+        "GTO".bold(),
+        rows.len().to_string().bold(),
+        below.map(|g| format!(" graded below {}", g)).unwrap_or_default(),
+        dir.display()
+    );
+    if !rows.is_empty() {
+        println!();
+        println!(
+            "  {:<8} {:<12} {:<10} {:>6} {:>6} {:>10} {:>9} {:<5} {:>9} {:>5}",
+            "Type", "Board", "Seats", "Pot", "Stack", "Iters", "Exploit", "Grade", "Size", "Age"
+        );
+    }
+    for (entry, header) in &rows {
+        let Some(h) = header else {
+            println!(
+                "  {:<8} {:<12} {:<10} {:>6} {:>6} {:>10} {:>9} {:<5} {:>9} {:>5}",
+                entry.kind.as_str(),
+                "unreadable".red(),
+                "-",
+                "-",
+                "-",
+                "-",
+                "-",
+                "-",
+                format_size(entry.len),
+                format_age(entry.age)
+            );
+            continue;
+        };
+        let exploit = match (h.exploitability, h.pot) {
+            (Some(e), Some(pot)) => format!("{:.2}%", pct_of_pot(e, pot)),
+            _ => "-".to_string(),
+        };
+        println!(
+            "  {:<8} {:<12} {:<10} {:>6} {:>6} {:>10} {:>9} {:<5} {:>9} {:>5}",
+            h.kind.as_str(),
+            h.board,
+            h.seats,
+            h.pot.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+            h.stack,
+            h.iterations,
+            exploit,
+            colored_grade(h.grade),
+            format_size(entry.len),
+            format_age(entry.age)
+        );
+    }
+    if unreadable > 0 {
+        println!();
+        println!(
+            "  {}",
+            format!("{} files could not be read; `gto cache info FILE` says why", unreadable).dimmed()
+        );
+    }
+    println!();
+}
+
+/// `gto cache info`: the header and root strategy of one cache file, given
+/// by name within the cache directory or by path.
+pub fn run_cache_info(file: &str) -> Result<(), String> {
+    let given = PathBuf::from(file);
+    let path = if given.exists() { given } else { solver_cache_dir().join(file) };
+    let solution = load(&path).map_err(io_error)?;
+    let h = solution.header();
+    let meta = std::fs::metadata(&path).map_err(|e| io_error(format!("Cannot stat {}: {}", path.display(), e)))?;
+    let age = meta.modified().ok().and_then(|t| t.elapsed().ok()).unwrap_or_default();
+
+    println!();
+    println!(
+        "  {} {} solution  {}",
+        "GTO".bold(),
+        h.kind.as_str().bold(),
+        path.display().to_string().dimmed()
+    );
+    println!();
+    match h.pot {
+        Some(pot) => println!("  Board: {}  Seats: {}  Pot: {}  Stack: {}", h.board.bold(), h.seats, pot, h.stack),
+        None if h.kind == CacheKind::PushFold => println!("  Shover: {}  Stack: {}bb", h.seats, h.stack),
+        None => println!("  Table: {}  Stack: {}bb", h.seats, h.stack),
+    }
+    print!("  Iterations: {}", h.iterations);
+    if let (Some(e), Some(pot)) = (h.exploitability, h.pot) {
+        print!("  Exploitability: {:.4} ({:.1}% of pot)", e, pct_of_pot(e, pot));
+    }
+    if h.grade.is_some() {
+        print!("  Grade: {}", colored_grade(h.grade));
+    }
+    println!();
+    println!("  Size: {}  Age: {}", format_size(meta.len()), format_age(age));
+    let summary = solution.root_summary();
+    if !summary.is_empty() {
+        println!();
+        println!("  {}", "Root strategy".bold());
+        for line in summary {
+            println!("    {}", line);
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// `gto cache prune`: delete solutions by age and kind, listing each one.
+pub fn run_cache_prune(older_than: Option<&str>, kind: Option<CacheKind>) -> Result<(), String> {
+    let older_than = older_than.map(parse_age).transpose()?;
+    if older_than.is_none() && kind.is_none() {
+        return Err("Nothing to prune by: pass --older-than, --type, or both".to_string());
+    }
+    let removed = prune(&solver_cache_dir(), older_than, kind).map_err(io_error)?;
+    println!();
+    for entry in &removed {
+        println!(
+            "  removed {}  {}",
+            entry.file,
+            format!("({}, {} old)", format_size(entry.len), format_age(entry.age)).dimmed()
+        );
+    }
+    if !removed.is_empty() {
+        println!();
+    }
+    let freed: u64 = removed.iter().map(|e| e.len).sum();
+    println!(
+        "  {} Removed {} cached solutions ({})",
+        "GTO".bold(),
+        removed.len().to_string().bold(),
+        format_size(freed)
+    );
+    println!();
+    Ok(())
+}
@@ -0,0 +1,555 @@
+//! Sidecar index of cached flop solutions.
+//!
+//! Reports over many spots need only a few numbers per solution, but a
+//! `FlopSolution` file holds every node's per-combo strategy and template
+//! buckets, so deserializing hundreds of them dominates report time. Every
+//! `save_cache` appends a compact `IndexRecord` to `index.bin` in the solver
+//! directory (u32 little-endian length + bincode record) after an
+//! `INDEX_MAGIC` + `INDEX_VERSION` header. Readers keep the last record per
+//! file and trust it only while the file's mtime and size match; anything else
+//! falls back to the full file. An index with another version is discarded
+//! whole, since its records may mean something different.
+//!
+//! `gto cache index --rebuild` compacts the log into one sorted record per
+//! file. Files whose mtime and size are unchanged are skipped; files that were
+//! touched but hash the same keep their summary; only new or changed contents
+//! are deserialized.
+
+use std::collections::HashMap;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::UNIX_EPOCH;
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::flop_solver::FlopSolution;
+use crate::quality::Grade;
+
+pub const INDEX_FILE: &str = "index.bin";
+
+const INDEX_MAGIC: &[u8; 4] = b"GIDX";
+
+/// Bumped whenever an `IndexRecord` field changes meaning. Version 1 had no
+/// header, no grade and root frequencies averaged with every combo equal.
+pub const INDEX_VERSION: u32 = 2;
+
+fn index_header() -> [u8; 8] {
+    let mut header = [0u8; 8];
+    header[..4].copy_from_slice(INDEX_MAGIC);
+    header[4..].copy_from_slice(&INDEX_VERSION.to_le_bytes());
+    header
+}
+
+/// Root-node summary and headline numbers for one cached flop solution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexRecord {
+    /// Cache file name within the solver directory.
+    pub file: String,
+    /// File size in bytes when summarized.
+    pub len: u64,
+    /// File mtime (ns since the epoch) when summarized.
+    pub mtime: u64,
+    /// FNV-1a hash of the file contents.
+    pub hash: u64,
+    pub board: String,
+    pub oop_pos: String,
+    pub ip_pos: String,
+    pub pot: f64,
+    pub stack: f64,
+    pub iterations: usize,
+    pub exploitability: f64,
+    pub oop_equity: f64,
+    pub oop_ev_share: f64,
+    pub ip_ev_share: f64,
+    /// Player to act at the flop root ("OOP").
+    pub root_player: String,
+    pub root_actions: Vec<String>,
+    /// Root frequencies averaged over the acting range, each combo weighted
+    /// by its range frequency.
+    pub root_frequencies: Vec<f64>,
+    /// Solve quality grade (see `quality`).
+    pub grade: Grade,
+}
+
+impl IndexRecord {
+    pub fn summarize(file: &str, len: u64, mtime: u64, hash: u64, solution: &FlopSolution) -> Self {
+        let root = solution.strategies.iter().find(|s| s.player == "OOP");
+        let (root_player, root_actions, root_frequencies) = match root {
+            Some(strat) => {
+                let weights = solution.oop_combo_weights();
+                let total: f64 = weights.iter().take(strat.frequencies.len()).sum();
+                let mut avg = vec![0.0; strat.actions.len()];
+                for (freqs, w) in strat.frequencies.iter().zip(&weights) {
+                    for (a, f) in avg.iter_mut().zip(freqs) {
+                        *a += f * w / total.max(1e-12);
+                    }
+                }
+                (strat.player.clone(), strat.actions.clone(), avg)
+            }
+            None => (String::new(), vec![], vec![]),
+        };
+        IndexRecord {
+            file: file.to_string(),
+            len,
+            mtime,
+            hash,
+            board: solution.board.clone(),
+            oop_pos: solution.oop_pos.clone(),
+            ip_pos: solution.ip_pos.clone(),
+            pot: solution.starting_pot,
+            stack: solution.effective_stack,
+            iterations: solution.iterations,
+            exploitability: solution.exploitability,
+            oop_equity: solution.oop_equity,
+            oop_ev_share: solution.oop_ev_share,
+            ip_ev_share: solution.ip_ev_share,
+            root_player,
+            root_actions,
+            root_frequencies,
+            grade: solution.solve_quality().grade,
+        }
+    }
+}
+
+/// Counts from `CacheIndex::rebuild`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RebuildStats {
+    /// Records in the rebuilt index.
+    pub indexed: usize,
+    /// Files skipped because mtime and size were unchanged.
+    pub unchanged: usize,
+    /// Files re-stamped because their contents hashed the same.
+    pub rehashed: usize,
+    /// Files deserialized and summarized.
+    pub summarized: usize,
+    /// Records dropped for files that no longer exist or no longer load.
+    pub removed: usize,
+}
+
+/// The index for one solver directory.
+pub struct CacheIndex {
+    dir: PathBuf,
+    records: HashMap<String, IndexRecord>,
+}
+
+/// Overrides the solver cache directory, e.g. to keep tests out of `$HOME`.
+pub const CACHE_DIR_ENV: &str = "GTO_CACHE_DIR";
+
+/// Where every solver cache lives: `$GTO_CACHE_DIR` when set, otherwise
+/// `~/.gto-cli/solver`.
+pub fn solver_cache_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(CACHE_DIR_ENV).filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".gto-cli").join("solver")
+}
+
+/// Write `data` to `path` through a temp file beside it and a rename, so
+/// readers and concurrent writers see the old file or the new one, never a
+/// partial write.
+pub fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("cache");
+    let tmp = path.with_file_name(format!(
+        "{}.{}-{}.tmp",
+        name,
+        std::process::id(),
+        NEXT_TMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    let written = std::fs::write(&tmp, data).and_then(|_| std::fs::rename(&tmp, path));
+    if written.is_err() {
+        std::fs::remove_file(&tmp).ok();
+    }
+    written
+}
+
+/// FNV-1a (64-bit) over raw bytes.
+pub fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// (len, mtime in ns) of a file, or `None` if it can't be stat'ed.
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64;
+    Some((meta.len(), mtime))
+}
+
+fn encode_record(record: &IndexRecord) -> Option<Vec<u8>> {
+    let body = bincode::serialize(record).ok()?;
+    let mut out = Vec::with_capacity(body.len() + 4);
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(&body);
+    Some(out)
+}
+
+/// Record a freshly written solution file. Errors are ignored: a missing
+/// record only means the next report opens the full file.
+pub fn record_saved(path: &Path, data: &[u8], solution: &FlopSolution) {
+    let (Some(dir), Some(file)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return;
+    };
+    let Some((len, mtime)) = file_stamp(path) else {
+        return;
+    };
+    let record = IndexRecord::summarize(file, len, mtime, fnv1a64(data), solution);
+    append_record(dir, &record);
+}
+
+fn append_record(dir: &Path, record: &IndexRecord) {
+    let Some(bytes) = encode_record(record) else {
+        return;
+    };
+    // Only the process that creates the index writes its header
+    if let Ok(mut f) = std::fs::OpenOptions::new().write(true).create_new(true).open(dir.join(INDEX_FILE)) {
+        f.write_all(&index_header()).ok();
+    }
+    if let Ok(mut f) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(INDEX_FILE))
+    {
+        f.write_all(&bytes).ok();
+    }
+}
+
+impl CacheIndex {
+    /// Read the index for `dir`; later records for a file replace earlier ones.
+    /// A missing index is empty, and a torn trailing record is ignored. An
+    /// index from another `INDEX_VERSION` is removed, so every file is
+    /// summarized again from its solution.
+    pub fn load(dir: &Path) -> Self {
+        let mut records = HashMap::new();
+        if let Ok(f) = std::fs::File::open(dir.join(INDEX_FILE)) {
+            let mut reader = BufReader::new(f);
+            let mut header = [0u8; 8];
+            if reader.read_exact(&mut header).is_err() || header != index_header() {
+                std::fs::remove_file(dir.join(INDEX_FILE)).ok();
+                return CacheIndex { dir: dir.to_path_buf(), records };
+            }
+            let mut len_buf = [0u8; 4];
+            let mut body = Vec::new();
+            while reader.read_exact(&mut len_buf).is_ok() {
+                body.resize(u32::from_le_bytes(len_buf) as usize, 0);
+                if reader.read_exact(&mut body).is_err() {
+                    break;
+                }
+                match bincode::deserialize::<IndexRecord>(&body) {
+                    Ok(r) => {
+                        records.insert(r.file.clone(), r);
+                    }
+                    Err(_) => break,
+                }
+            }
+        }
+        CacheIndex { dir: dir.to_path_buf(), records }
+    }
+
+    /// The record for `file` if the file still matches it.
+    pub fn get(&self, file: &str) -> Option<&IndexRecord> {
+        let record = self.records.get(file)?;
+        let (len, mtime) = file_stamp(&self.dir.join(file))?;
+        (record.len == len && record.mtime == mtime).then_some(record)
+    }
+
+    /// Summary for `file`: the indexed record when fresh, otherwise read from
+    /// the full solution (and appended so the next scan is fast).
+    pub fn summary(&mut self, file: &str) -> Option<IndexRecord> {
+        self.load_summary(file).ok()
+    }
+
+    /// `summary`, with the reason when the file can't be summarized (missing,
+    /// corrupt, or written with an older combo ordering).
+    pub fn load_summary(&mut self, file: &str) -> Result<IndexRecord, String> {
+        if let Some(r) = self.get(file) {
+            return Ok(r.clone());
+        }
+        let record = self.summarize_file(file, None)?;
+        append_record(&self.dir, &record);
+        self.records.insert(file.to_string(), record.clone());
+        Ok(record)
+    }
+
+    /// Summarize `file` from disk. With `previous`, contents that hash the same
+    /// reuse its summary under the new stamp instead of deserializing.
+    fn summarize_file(&self, file: &str, previous: Option<&IndexRecord>) -> Result<IndexRecord, String> {
+        let path = self.dir.join(file);
+        let (len, mtime) = file_stamp(&path).ok_or_else(|| format!("Cannot stat {}", path.display()))?;
+        let data = std::fs::read(&path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let hash = fnv1a64(&data);
+        if let Some(prev) = previous.filter(|p| p.hash == hash) {
+            return Ok(IndexRecord { len, mtime, ..prev.clone() });
+        }
+        let solution = FlopSolution::load_file(&path)?;
+        Ok(IndexRecord::summarize(file, len, mtime, hash, &solution))
+    }
+
+    /// Re-index every cached flop solution in `dir` and rewrite the index as
+    /// one record per file, sorted by file name.
+    pub fn rebuild(dir: &Path) -> Result<RebuildStats, String> {
+        let old = CacheIndex::load(dir);
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?
+            .filter_map(|e| e.ok()?.file_name().into_string().ok())
+            .filter(|n| n.starts_with("flop_") && n.ends_with(".bin"))
+            .collect();
+        files.sort();
+
+        let mut stats = RebuildStats::default();
+        let mut fresh = Vec::with_capacity(files.len());
+        for file in &files {
+            if let Some(r) = old.get(file) {
+                stats.unchanged += 1;
+                fresh.push(r.clone());
+                continue;
+            }
+            let previous = old.records.get(file);
+            match old.summarize_file(file, previous) {
+                Ok(r) => {
+                    if previous.is_some_and(|p| p.hash == r.hash) {
+                        stats.rehashed += 1;
+                    } else {
+                        stats.summarized += 1;
+                    }
+                    fresh.push(r);
+                }
+                Err(_) => {
+                    if previous.is_some() {
+                        stats.removed += 1;
+                    }
+                }
+            }
+        }
+        stats.removed += old.records.keys().filter(|f| !files.contains(f)).count();
+        stats.indexed = fresh.len();
+
+        // Write the compacted index beside the old one, then swap it in
+        let tmp = dir.join(format!("{}.tmp", INDEX_FILE));
+        let mut out = index_header().to_vec();
+        for record in &fresh {
+            out.extend(encode_record(record).ok_or("Failed to encode index record")?);
+        }
+        std::fs::write(&tmp, &out).map_err(|e| format!("Cannot write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, dir.join(INDEX_FILE))
+            .map_err(|e| format!("Cannot replace {}: {}", INDEX_FILE, e))?;
+        Ok(stats)
+    }
+}
+
+/// `gto cache index`: show index coverage, or rebuild it.
+pub fn run_cache_index(rebuild: bool) {
+    let dir = solver_cache_dir();
+    println!();
+    if rebuild {
+        let start = std::time::Instant::now();
+        match CacheIndex::rebuild(&dir) {
+            Ok(stats) => {
+                println!(
+                    "  {} Indexed {} solutions in {:.2}s",
+                    "GTO".bold(),
+                    stats.indexed.to_string().bold(),
+                    start.elapsed().as_secs_f64()
+                );
+                println!(
+                    "  Unchanged: {}  Re-stamped: {}  Summarized: {}  Removed: {}",
+                    stats.unchanged, stats.rehashed, stats.summarized, stats.removed
+                );
+            }
+            Err(e) => eprintln!("{}", format!("Error: {}", e).red()),
+        }
+        println!();
+        return;
+    }
+
+    let index = CacheIndex::load(&dir);
+    let files: Vec<String> = std::fs::read_dir(&dir)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok()?.file_name().into_string().ok())
+                .filter(|n| n.starts_with("flop_") && n.ends_with(".bin"))
+                .collect()
+        })
+        .unwrap_or_default();
+    let fresh = files.iter().filter(|f| index.get(f).is_some()).count();
+    println!(
+        "  {} {} cached flop solutions, {} indexed and current",
+        "GTO".bold(),
+        files.len().to_string().bold(),
+        fresh
+    );
+    if fresh < files.len() {
+        println!("  Run `gto cache index --rebuild` to index the rest.");
+    }
+    println!();
+}
+
+/// Locks older than this are left over from a crashed solve and are taken over.
+const STALE_LOCK_SECS: u64 = 6 * 3600;
+
+/// Cross-process claim on one cache file while it is being solved: a
+/// `<file>.lock` next to it, created exclusively and removed on drop, so
+/// concurrent `batch`/`prefetch` runs don't solve the same spot twice.
+pub struct SolveLock {
+    path: PathBuf,
+}
+
+impl SolveLock {
+    /// Claim `file` in `dir`, or `None` if another process holds it.
+    pub fn acquire(dir: &Path, file: &str) -> Option<SolveLock> {
+        std::fs::create_dir_all(dir).ok();
+        let path = dir.join(format!("{}.lock", file));
+        let stale = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age.as_secs() > STALE_LOCK_SECS);
+        if stale {
+            std::fs::remove_file(&path).ok();
+        }
+        let mut f = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .ok()?;
+        write!(f, "{}", std::process::id()).ok();
+        Some(SolveLock { path })
+    }
+}
+
+impl Drop for SolveLock {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flop_solver::{solve_flop, FlopSolverConfig};
+
+    #[test]
+    fn test_solve_lock_is_exclusive_until_dropped() {
+        let dir = scratch_dir("lock");
+        let lock = SolveLock::acquire(&dir, "flop_a.bin").expect("first claim");
+        assert!(SolveLock::acquire(&dir, "flop_a.bin").is_none());
+        assert!(SolveLock::acquire(&dir, "flop_b.bin").is_some());
+        drop(lock);
+        assert!(SolveLock::acquire(&dir, "flop_a.bin").is_some());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_without_leftovers() {
+        let dir = scratch_dir("atomic");
+        let path = dir.join("flop_a.bin");
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        let files: Vec<_> = std::fs::read_dir(&dir).unwrap().filter_map(|e| e.ok()).collect();
+        assert_eq!(files.len(), 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gto-index-{}-{}", name, std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_solution(dir: &Path, file: &str, solution: &FlopSolution) {
+        let data = bincode::serialize(solution).unwrap();
+        let path = dir.join(file);
+        std::fs::write(&path, &data).unwrap();
+        record_saved(&path, &data, solution);
+    }
+
+    #[test]
+    fn test_saved_solution_is_indexed() {
+        let dir = scratch_dir("saved");
+        let config = FlopSolverConfig::new("Ks9d4c", "AA,KK", "QQ,JJ", 10.0, 50.0, 200).unwrap();
+        let solution = solve_flop(&config);
+        write_solution(&dir, "flop_a.bin", &solution);
+
+        let index = CacheIndex::load(&dir);
+        let r = index.get("flop_a.bin").expect("record written at save time");
+        assert_eq!(r.board, "Ks9d4c");
+        assert_eq!(r.root_player, "OOP");
+        assert!((r.root_frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!((r.oop_equity - solution.oop_equity).abs() < 1e-12);
+        assert_eq!(Some(r.grade), solution.quality.as_ref().map(|q| q.grade));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rebuild_is_idempotent_and_skips_unchanged() {
+        let dir = scratch_dir("rebuild");
+        let config = FlopSolverConfig::new("Ks9d4c", "AA,KK", "QQ,JJ", 10.0, 50.0, 200).unwrap();
+        let solution = solve_flop(&config);
+        write_solution(&dir, "flop_a.bin", &solution);
+        write_solution(&dir, "flop_b.bin", &solution);
+        // A file written without going through save_cache
+        std::fs::write(dir.join("flop_c.bin"), bincode::serialize(&solution).unwrap()).unwrap();
+
+        let first = CacheIndex::rebuild(&dir).unwrap();
+        assert_eq!(first.indexed, 3);
+        assert_eq!(first.unchanged, 2);
+        assert_eq!(first.summarized, 1);
+        let bytes = std::fs::read(dir.join(INDEX_FILE)).unwrap();
+
+        let second = CacheIndex::rebuild(&dir).unwrap();
+        assert_eq!(second, RebuildStats { indexed: 3, unchanged: 3, ..Default::default() });
+        assert_eq!(std::fs::read(dir.join(INDEX_FILE)).unwrap(), bytes);
+
+        std::fs::remove_file(dir.join("flop_b.bin")).unwrap();
+        let third = CacheIndex::rebuild(&dir).unwrap();
+        assert_eq!(third.indexed, 2);
+        assert_eq!(third.removed, 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_stale_record_is_not_trusted() {
+        let dir = scratch_dir("stale");
+        let config = FlopSolverConfig::new("Ks9d4c", "AA,KK", "QQ,JJ", 10.0, 50.0, 200).unwrap();
+        let mut solution = solve_flop(&config);
+        write_solution(&dir, "flop_a.bin", &solution);
+
+        // Overwrite behind the index's back with different contents
+        solution.oop_pos = "BB".to_string();
+        std::fs::write(dir.join("flop_a.bin"), bincode::serialize(&solution).unwrap()).unwrap();
+
+        let mut index = CacheIndex::load(&dir);
+        assert!(index.get("flop_a.bin").is_none());
+        assert_eq!(index.summary("flop_a.bin").unwrap().oop_pos, "BB");
+        assert_eq!(CacheIndex::load(&dir).get("flop_a.bin").unwrap().oop_pos, "BB");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_index_from_another_version_is_discarded() {
+        let dir = scratch_dir("version");
+        let config = FlopSolverConfig::new("Ks9d4c", "AA,KK", "QQ,JJ", 10.0, 50.0, 200).unwrap();
+        let solution = solve_flop(&config);
+        write_solution(&dir, "flop_a.bin", &solution);
+
+        // A version 1 index: the same record with no header
+        let bytes = std::fs::read(dir.join(INDEX_FILE)).unwrap();
+        assert_eq!(bytes[..8], index_header());
+        std::fs::write(dir.join(INDEX_FILE), &bytes[8..]).unwrap();
+
+        let mut index = CacheIndex::load(&dir);
+        assert!(index.get("flop_a.bin").is_none());
+        assert!(!dir.join(INDEX_FILE).exists());
+        assert!(index.summary("flop_a.bin").is_some());
+        assert_eq!(std::fs::read(dir.join(INDEX_FILE)).unwrap(), bytes);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
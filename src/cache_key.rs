@@ -0,0 +1,158 @@
+//! Range keys in postflop cache file names.
+//!
+//! A flop, turn or river solve is cached as
+//! `<street>_<board>_<oop seat>_<ip seat>_<pot>_<stack>_<range key>.bin`
+//! (plus any sizing suffix before `.bin`). The range key tells apart solves
+//! of the same seats with different ranges, which used to share a file.
+//! Files from before the key are still read when their solve's ranges match,
+//! and renamed to the new name on the way.
+//!
+//! A drill-down turn solve (`gto query --drill`) is cached as
+//! `turn_drill_<board>_<oop seat>_<ip seat>_<drill key>.bin`, the drill key
+//! standing for the flop solve it started from and the flop line.
+
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+use crate::cache_index::{fnv1a64, solver_cache_dir};
+use crate::ranges::parse_range_weights;
+
+/// Tag for a solve's ranges in cache file names: "r" and 8 hex digits of a
+/// hash of both ranges as parsed (hands in `parse_range` order, each with its
+/// frequency), so "QQ+" and "AA,KK,QQ" share a file. `*_weights` are indexed
+/// like their range; hands past the end count in full.
+pub fn range_key(oop_range: &[String], oop_weights: &[f64], ip_range: &[String], ip_weights: &[f64]) -> String {
+    let mut text = String::new();
+    for (hands, weights) in [(oop_range, oop_weights), (ip_range, ip_weights)] {
+        for (i, hand) in hands.iter().enumerate() {
+            text.push_str(&format!("{}:{},", hand, weights.get(i).copied().unwrap_or(1.0)));
+        }
+        text.push('|');
+    }
+    let hash = fnv1a64(text.as_bytes());
+    format!("r{:08x}", (hash ^ (hash >> 32)) as u32)
+}
+
+/// Suffix for a river solve given per-combo starting weights: "_w" and 8
+/// hex digits of a hash of them, or "" for none, so such a solve is cached
+/// apart from its ranges' own.
+pub fn combo_weights_key(oop_weights: &[f64], ip_weights: &[f64]) -> String {
+    if oop_weights.is_empty() && ip_weights.is_empty() {
+        return String::new();
+    }
+    let text = format!("{:?}|{:?}", oop_weights, ip_weights);
+    let hash = fnv1a64(text.as_bytes());
+    format!("_w{:08x}", (hash ^ (hash >> 32)) as u32)
+}
+
+/// `range_key` of two ranges as the solvers take them ("AA,AKs:0.5"). A
+/// range that doesn't parse is hashed as written.
+pub fn range_key_of(oop_range: &str, ip_range: &str) -> String {
+    let parse = |range: &str| {
+        parse_range_weights(range).unwrap_or_else(|_| (vec![range.trim().to_string()], Vec::new()))
+    };
+    let (oop, oop_weights) = parse(oop_range);
+    let (ip, ip_weights) = parse(ip_range);
+    range_key(&oop, &oop_weights, &ip, &ip_weights)
+}
+
+/// Tag for a drill-down solve in cache file names: "d" and 8 hex digits of
+/// a hash of the flop solve's cache file name, iterations and
+/// exploitability, so a re-solved flop gets fresh drills, and the flop
+/// line's action labels.
+pub fn drill_key(flop_file: &str, flop_iterations: usize, flop_exploitability: f64, line: &[String]) -> String {
+    let text = format!("{}|{}|{}|{}", flop_file, flop_iterations, flop_exploitability, line.join(","));
+    let hash = fnv1a64(text.as_bytes());
+    format!("d{:08x}", (hash ^ (hash >> 32)) as u32)
+}
+
+/// Whether `rest` (a file name past its spot's stem) is a range key and
+/// `.bin`, or `.bin` alone for a file from before range keys.
+fn is_spot_file_rest(rest: &str) -> bool {
+    let Some(key) = rest.strip_suffix(".bin") else {
+        return false;
+    };
+    key.is_empty()
+        || key
+            .strip_prefix("_r")
+            .is_some_and(|hex| hex.len() == 8 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// Read a spot's cached solve with `read`. `stems` are the spot's file names
+/// up to the range key, the preferred (canonical board) one first.
+///
+/// With `ranges`, the file for that range key, or else a file from before
+/// range keys whose solve has the same key (`key_of`), which is renamed to
+/// the key's name. Without, for callers that know only the seats, the most
+/// recently written file of the spot, whatever its ranges.
+pub fn read_spot_cache<T>(
+    stems: &[String],
+    ranges: Option<&str>,
+    read: impl Fn(&Path) -> Option<T>,
+    key_of: impl Fn(&T) -> String,
+) -> Option<T> {
+    let dir = solver_cache_dir();
+    let Some(ranges) = ranges else {
+        return newest_spot_file(&dir, stems).and_then(|path| read(&path));
+    };
+    let keyed = |stem: &String| dir.join(format!("{}_{}.bin", stem, ranges));
+    if let Some(solution) = stems.iter().find_map(|stem| read(&keyed(stem))) {
+        return Some(solution);
+    }
+    let (legacy, solution) = stems.iter().find_map(|stem| {
+        let path = dir.join(format!("{}.bin", stem));
+        let solution = read(&path)?;
+        Some((path, solution))
+    })?;
+    if key_of(&solution) != ranges {
+        return None;
+    }
+    let renamed = keyed(&stems[0]);
+    if std::fs::rename(&legacy, &renamed).is_ok() {
+        static NOTE: Once = Once::new();
+        NOTE.call_once(|| {
+            eprintln!("  Note: cache file names now carry a range key; renaming older files as they're read");
+        });
+    }
+    Some(solution)
+}
+
+/// The most recently written file of any of `stems` in `dir`.
+fn newest_spot_file(dir: &Path, stems: &[String]) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            stems.iter().any(|stem| name.strip_prefix(stem.as_str()).is_some_and(is_spot_file_rest))
+        })
+        .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+        .map(|e| e.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_key_normalizes_ranges() {
+        assert_eq!(range_key_of("QQ+", "AKs"), range_key_of("AA, KK,QQ", "AKs"));
+        assert_eq!(range_key_of("AA,KK", "QQ"), range_key_of("KK,AA", "QQ:1"));
+        assert_ne!(range_key_of("AA,KK", "QQ"), range_key_of("QQ", "AA,KK"));
+        assert_ne!(range_key_of("AA,KK", "QQ"), range_key_of("AA,KK", "QQ:0.5"));
+        let key = range_key_of("AA", "KK");
+        assert!(key.starts_with('r') && key.len() == 9);
+        let parsed = parse_range_weights("AA,KK:0.5").unwrap();
+        assert_eq!(range_key(&parsed.0, &parsed.1, &["QQ".to_string()], &[]), range_key_of("AA,KK:0.5", "QQ"));
+    }
+
+    #[test]
+    fn test_spot_file_rest() {
+        assert!(is_spot_file_rest(".bin"));
+        assert!(is_spot_file_rest("_r0badf00d.bin"));
+        assert!(!is_spot_file_rest("_r0badf00d_river50.bin"));
+        assert!(!is_spot_file_rest("_river50.bin"));
+        assert!(!is_spot_file_rest("0_r0badf00d.bin"));
+    }
+}
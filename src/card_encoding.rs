@@ -27,8 +27,19 @@ pub fn cards_to_indices(cards: &[Card]) -> Vec<u8> {
 
 /// Build a full deck (0-51) excluding the given dead cards.
 pub fn remaining_deck(dead: &[u8]) -> Vec<u8> {
+    debug_assert!(
+        dead.iter().enumerate().all(|(i, c)| !dead[..i].contains(c)),
+        "dead cards {:?} repeat a card",
+        dead
+    );
+    remaining_deck_excluding(dead, &[])
+}
+
+/// `remaining_deck` without the `excluded` cards as well, e.g. a spot's
+/// known dead cards (`--dead`) on top of its board.
+pub fn remaining_deck_excluding(dead: &[u8], excluded: &[u8]) -> Vec<u8> {
     let mut dead_set = [false; 52];
-    for &d in dead {
+    for &d in dead.iter().chain(excluded) {
         dead_set[d as usize] = true;
     }
     (0..52u8).filter(|&c| !dead_set[c as usize]).collect()
@@ -64,5 +75,9 @@ mod tests {
         assert!(!deck.contains(&0));
         assert!(!deck.contains(&3));
         assert!(deck.contains(&4));
+
+        let deck = remaining_deck_excluding(&dead, &[4, 51]);
+        assert_eq!(deck.len(), 46);
+        assert!(!deck.contains(&4) && !deck.contains(&51));
     }
 }
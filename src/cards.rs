@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -94,12 +96,14 @@ pub enum Suit {
 }
 
 impl Suit {
+    /// Accepts the ASCII letter (any case) or a Unicode suit symbol, filled or
+    /// outline (♠♥♦♣ / ♤♡♢♧), so rendered output can be pasted back in.
     pub fn from_char(c: char) -> GtoResult<Suit> {
         match c.to_ascii_lowercase() {
-            's' => Ok(Suit::Spades),
-            'h' => Ok(Suit::Hearts),
-            'd' => Ok(Suit::Diamonds),
-            'c' => Ok(Suit::Clubs),
+            's' | '\u{2660}' | '\u{2664}' => Ok(Suit::Spades),
+            'h' | '\u{2665}' | '\u{2661}' => Ok(Suit::Hearts),
+            'd' | '\u{2666}' | '\u{2662}' => Ok(Suit::Diamonds),
+            'c' | '\u{2663}' | '\u{2667}' => Ok(Suit::Clubs),
             _ => Err(GtoError::InvalidSuit(c)),
         }
     }
@@ -121,6 +125,42 @@ impl Suit {
             Suit::Clubs => "\u{2663}",
         }
     }
+
+    /// Suit as shown in output under the given style.
+    pub fn render(self, style: SuitStyle) -> &'static str {
+        match style {
+            SuitStyle::Unicode => self.symbol(),
+            SuitStyle::Ascii => match self {
+                Suit::Spades => "s",
+                Suit::Hearts => "h",
+                Suit::Diamonds => "d",
+                Suit::Clubs => "c",
+            },
+        }
+    }
+}
+
+/// How suits are rendered in pretty output. ASCII avoids symbol-width issues
+/// that break table alignment on some terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuitStyle {
+    Unicode,
+    Ascii,
+}
+
+static ASCII_SUITS: AtomicBool = AtomicBool::new(false);
+
+/// Set the process-wide suit style (from `--ascii` or `GTO_ASCII`).
+pub fn set_suit_style(style: SuitStyle) {
+    ASCII_SUITS.store(style == SuitStyle::Ascii, AtomicOrdering::Relaxed);
+}
+
+pub fn suit_style() -> SuitStyle {
+    if ASCII_SUITS.load(AtomicOrdering::Relaxed) {
+        SuitStyle::Ascii
+    } else {
+        SuitStyle::Unicode
+    }
 }
 
 pub const ALL_SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
@@ -141,7 +181,7 @@ impl Card {
     }
 
     pub fn pretty(&self) -> String {
-        format!("{}{}", self.rank.to_char(), self.suit.symbol())
+        format!("{}{}", self.rank.to_char(), self.suit.render(suit_style()))
     }
 }
 
@@ -215,8 +255,18 @@ impl Deck {
     }
 }
 
+/// Strip presentation noise from card notation: whitespace, commas, emoji
+/// variation selectors, and "10" written for a ten.
+fn normalize_notation(notation: &str) -> String {
+    notation
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',' && *c != '\u{FE0F}' && *c != '\u{FE0E}')
+        .collect::<String>()
+        .replace("10", "T")
+}
+
 pub fn parse_card(notation: &str) -> GtoResult<Card> {
-    let notation = notation.trim();
+    let notation = normalize_notation(notation);
     let chars: Vec<char> = notation.chars().collect();
     if chars.len() != 2 {
         return Err(GtoError::InvalidCardNotation(notation.to_string()));
@@ -226,9 +276,21 @@ pub fn parse_card(notation: &str) -> GtoResult<Card> {
     Ok(Card::new(rank, suit))
 }
 
+/// Parse a board: at most 5 cards, none of them repeated.
 pub fn parse_board(notation: &str) -> GtoResult<Vec<Card>> {
-    let notation = notation.trim().replace(' ', "").replace(',', "");
-    if notation.len() % 2 != 0 {
+    let cards = parse_cards(notation)?;
+    if cards.len() > 5 {
+        return Err(GtoError::BoardTooLong(cards.len()));
+    }
+    check_distinct(&cards)?;
+    Ok(cards)
+}
+
+/// Parse any run of cards (a hand, dead cards) with no board rules; callers
+/// check repeats themselves so they can say where a card is dealt twice.
+pub fn parse_cards(notation: &str) -> GtoResult<Vec<Card>> {
+    let notation = normalize_notation(notation);
+    if notation.chars().count() % 2 != 0 {
         return Err(GtoError::InvalidBoardNotation(notation.to_string()));
     }
     let mut cards = Vec::new();
@@ -240,6 +302,56 @@ pub fn parse_board(notation: &str) -> GtoResult<Vec<Card>> {
     Ok(cards)
 }
 
+/// Cards that appear more than once in `cards`, each listed once.
+pub fn duplicate_cards(cards: &[Card]) -> Vec<Card> {
+    let mut seen = HashSet::new();
+    let mut dups: Vec<Card> = Vec::new();
+    for &card in cards {
+        if !seen.insert(card) && !dups.contains(&card) {
+            dups.push(card);
+        }
+    }
+    dups
+}
+
+/// Check that no card is dealt twice across a hand, board and villain hand
+/// (pass them all in one slice); the error names the repeated cards.
+pub fn check_distinct(cards: &[Card]) -> GtoResult<()> {
+    let dups = duplicate_cards(cards);
+    if dups.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<String> = dups.iter().map(|c| c.to_string()).collect();
+    Err(GtoError::ConflictingCards(names.join(", ")))
+}
+
+/// Check dead cards (known to be out of the deck: a shown fold, an exposed
+/// card) against the cards in play. `hands` are the players' hole cards,
+/// hero's first; the error says where a dead card is already dealt.
+pub fn check_dead_cards(dead: &[Card], hands: &[&[Card]], board: &[Card]) -> GtoResult<()> {
+    for (i, card) in dead.iter().enumerate() {
+        let place = if dead[..i].contains(card) {
+            "listed twice"
+        } else if hands.first().is_some_and(|hand| hand.contains(card)) {
+            "in your hand"
+        } else if hands.iter().skip(1).any(|hand| hand.contains(card)) {
+            "in villain's hand"
+        } else if board.contains(card) {
+            "on the board"
+        } else {
+            continue;
+        };
+        return Err(GtoError::DeadCardDealt { card: card.to_string(), place });
+    }
+    Ok(())
+}
+
+/// Parse cards in any accepted notation and return them in canonical ASCII
+/// form ("K♠ 9♦ 4♣" -> "Ks9d4c").
+pub fn normalize_cards(notation: &str) -> GtoResult<String> {
+    Ok(parse_cards(notation)?.iter().map(|c| c.to_string()).collect())
+}
+
 pub fn simplify_hand(cards: &[Card]) -> GtoResult<String> {
     if cards.len() != 2 {
         return Err(GtoError::InvalidHandSize);
@@ -259,6 +371,56 @@ pub fn simplify_hand(cards: &[Card]) -> GtoResult<String> {
     Ok(format!("{}{}{}", r1.to_char(), r2.to_char(), suffix))
 }
 
+/// Convert specific cards "AhKs" to canonical notation "AKo".
+/// Anything that isn't four characters is returned unchanged.
+pub fn hand_to_canonical(hand: &str) -> String {
+    if hand.len() != 4 {
+        return hand.to_string();
+    }
+    let chars: Vec<char> = hand.chars().collect();
+    let r1 = chars[0];
+    let s1 = chars[1];
+    let r2 = chars[2];
+    let s2 = chars[3];
+
+    // Higher rank first
+    let (h, l) = if rank_index(r1) >= rank_index(r2) { (r1, r2) } else { (r2, r1) };
+    if r1 == r2 {
+        format!("{}{}", r1, r2)
+    } else if s1 == s2 {
+        format!("{}{}s", h, l)
+    } else {
+        format!("{}{}o", h, l)
+    }
+}
+
+/// A hand class typed in any case ("qq", "kas", "T9O") in canonical
+/// notation ("QQ", "AKs", "T9o"), or `None` if `s` isn't one.
+pub fn canonical_hand_class(s: &str) -> Option<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let rank = |c: char| Rank::from_char(c.to_ascii_uppercase()).ok();
+    match *chars.as_slice() {
+        [a, b] => {
+            let r = rank(a)?;
+            (rank(b)? == r).then(|| format!("{}{}", r.to_char(), r.to_char()))
+        }
+        [a, b, kind] => {
+            let (r1, r2) = (rank(a)?, rank(b)?);
+            let suffix = match kind.to_ascii_lowercase() {
+                's' => 's',
+                'o' => 'o',
+                _ => return None,
+            };
+            if r1 == r2 {
+                return None;
+            }
+            let (high, low) = if r1 > r2 { (r1, r2) } else { (r2, r1) };
+            Some(format!("{}{}{}", high.to_char(), low.to_char(), suffix))
+        }
+        _ => None,
+    }
+}
+
 pub fn hand_combos(notation: &str) -> GtoResult<Vec<(Card, Card)>> {
     let notation = notation.trim();
     let chars: Vec<char> = notation.chars().collect();
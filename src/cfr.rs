@@ -3,9 +3,88 @@
 //! Each information set tracks cumulative regret per action and cumulative
 //! strategy weights. The average strategy over all iterations converges to
 //! a Nash equilibrium.
+//!
+//! Solvers can also run Linear CFR or Discounted CFR (Brown & Sandholm, 2019),
+//! which down-weight early iterations; see [`CfrVariant`].
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Regret-minimization variant a solver runs.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum CfrVariant {
+    /// Regrets floored at zero, every iteration weighted equally in the average.
+    #[default]
+    CfrPlus,
+    /// Linear CFR: iteration t's regrets and strategy are weighted by t.
+    LinearCfr,
+    /// Discounted CFR: after iteration t, positive regrets are scaled by
+    /// t^α/(t^α+1), negative regrets by t^β/(t^β+1) and the strategy sum by
+    /// (t/(t+1))^γ.
+    Dcfr { alpha: f64, beta: f64, gamma: f64 },
+}
+
+impl CfrVariant {
+    /// The parameters recommended by Brown & Sandholm: α = 1.5, β = 0, γ = 2.
+    pub const DCFR: CfrVariant = CfrVariant::Dcfr { alpha: 1.5, beta: 0.0, gamma: 2.0 };
+
+    /// Short name for display.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CfrVariant::CfrPlus => "CFR+",
+            CfrVariant::LinearCfr => "Linear CFR",
+            CfrVariant::Dcfr { .. } => "DCFR",
+        }
+    }
+
+    /// Log of the discounts applied after 1-based iteration `t`, as
+    /// [positive regret, negative regret, strategy sum].
+    fn log_discounts(&self, t: usize) -> [f64; 3] {
+        let (alpha, beta, gamma) = match *self {
+            CfrVariant::CfrPlus => return [0.0; 3],
+            CfrVariant::LinearCfr => (1.0, 1.0, 1.0),
+            CfrVariant::Dcfr { alpha, beta, gamma } => (alpha, beta, gamma),
+        };
+        let t = t as f64;
+        let ratio = |exp: f64| {
+            let p = t.powf(exp);
+            (p / (p + 1.0)).ln()
+        };
+        [ratio(alpha), ratio(beta), gamma * (t / (t + 1.0)).ln()]
+    }
+}
+
+/// Running sums of log discounts, so an info set that sat out several
+/// iterations can catch up on all of them in one multiplication.
+///
+/// Values written during 0-based iteration `from` are discounted by the
+/// factors for iterations `from + 1 ..= to` before iteration `to` adds to them.
+/// A table is only valid for the variant it was first grown with.
+#[derive(Debug, Clone, Default)]
+pub struct Discounts {
+    prefix: Vec<[f64; 3]>,
+}
+
+impl Discounts {
+    /// Multipliers for [positive regret, negative regret, strategy sum]
+    /// carrying a value from iteration `from` to iteration `to`.
+    pub fn between(&mut self, variant: CfrVariant, from: usize, to: usize) -> [f64; 3] {
+        if from >= to {
+            return [1.0; 3];
+        }
+        if self.prefix.is_empty() {
+            self.prefix.push([0.0; 3]);
+        }
+        while self.prefix.len() <= to {
+            let last = self.prefix[self.prefix.len() - 1];
+            let d = variant.log_discounts(self.prefix.len());
+            self.prefix.push([last[0] + d[0], last[1] + d[1], last[2] + d[2]]);
+        }
+        let (a, b) = (self.prefix[from], self.prefix[to]);
+        [(b[0] - a[0]).exp(), (b[1] - a[1]).exp(), (b[2] - a[2]).exp()]
+    }
+}
+
 /// One information set's accumulated data.
 #[derive(Debug, Clone)]
 pub struct InfoSetData {
@@ -15,6 +94,8 @@ pub struct InfoSetData {
     pub cumulative_regret: Vec<f64>,
     /// Cumulative strategy weight for each action (for computing average strategy).
     pub cumulative_strategy: Vec<f64>,
+    /// Iteration of the last discounted update (Linear CFR / DCFR only).
+    pub last_iteration: usize,
 }
 
 impl InfoSetData {
@@ -23,6 +104,7 @@ impl InfoSetData {
             num_actions,
             cumulative_regret: vec![0.0; num_actions],
             cumulative_strategy: vec![0.0; num_actions],
+            last_iteration: 0,
         }
     }
 
@@ -73,6 +155,34 @@ impl InfoSetData {
             self.cumulative_strategy[a] += reach_prob * strategy[a];
         }
     }
+
+    /// Linear CFR / DCFR update during 0-based `iteration`: discount what has
+    /// accumulated since the last update, then add this iteration's regrets
+    /// (not floored) and the updated strategy's weight.
+    ///
+    /// Accumulating the strategy after the regret update keeps the first
+    /// iteration's uniform play out of nodes that soon stop being reached;
+    /// negative regrets aren't floored, so those nodes may never be revisited.
+    pub fn update_discounted(
+        &mut self,
+        action_utilities: &[f64],
+        node_utility: f64,
+        reach_prob: f64,
+        factors: [f64; 3],
+        iteration: usize,
+    ) {
+        let [positive, negative, strat] = factors;
+        for (r, &u) in self.cumulative_regret.iter_mut().zip(action_utilities) {
+            *r *= if *r > 0.0 { positive } else { negative };
+            *r += u - node_utility;
+        }
+
+        let strategy = self.current_strategy();
+        for (s, p) in self.cumulative_strategy.iter_mut().zip(strategy) {
+            *s = *s * strat + reach_prob * p;
+        }
+        self.last_iteration = iteration;
+    }
 }
 
 /// Key for an information set: encodes what the player knows.
@@ -88,12 +198,42 @@ pub struct InfoSetKey {
 /// The CFR trainer holds all information set data.
 pub struct CfrTrainer {
     pub info_sets: HashMap<InfoSetKey, InfoSetData>,
+    variant: CfrVariant,
+    discounts: Discounts,
 }
 
 impl CfrTrainer {
     pub fn new() -> Self {
+        CfrTrainer::with_variant(CfrVariant::CfrPlus)
+    }
+
+    pub fn with_variant(variant: CfrVariant) -> Self {
         CfrTrainer {
             info_sets: HashMap::new(),
+            variant,
+            discounts: Discounts::default(),
+        }
+    }
+
+    /// Update one info set during 0-based `iteration` under the trainer's variant.
+    pub fn update(
+        &mut self,
+        key: &InfoSetKey,
+        action_utilities: &[f64],
+        node_utility: f64,
+        reach_prob: f64,
+        iteration: usize,
+    ) {
+        let variant = self.variant;
+        let data = self
+            .info_sets
+            .entry(key.clone())
+            .or_insert_with(|| InfoSetData::new(action_utilities.len()));
+        if variant == CfrVariant::CfrPlus {
+            data.update(action_utilities, node_utility, reach_prob);
+        } else {
+            let factors = self.discounts.between(variant, data.last_iteration, iteration);
+            data.update_discounted(action_utilities, node_utility, reach_prob, factors, iteration);
         }
     }
 
@@ -119,6 +259,12 @@ impl CfrTrainer {
             None => vec![1.0 / num_actions as f64; num_actions],
         }
     }
+
+    /// Overwrite an info set's average strategy (e.g. after post-processing).
+    pub fn set_average_strategy(&mut self, key: &InfoSetKey, strategy: &[f64]) {
+        let data = self.get_or_create(key, strategy.len());
+        data.cumulative_strategy = strategy.to_vec();
+    }
 }
 
 #[cfg(test)]
@@ -176,6 +322,34 @@ mod tests {
         assert!((data.cumulative_regret[1] - 6.0).abs() < 1e-9);
     }
 
+    #[test]
+    fn discounts_compose_across_skipped_iterations() {
+        let mut discounts = Discounts::default();
+        let step = |d: &mut Discounts, t: usize| d.between(CfrVariant::DCFR, t - 1, t);
+        let one_by_one: Vec<[f64; 3]> = (1..=4).map(|t| step(&mut discounts, t)).collect();
+        let jump = discounts.between(CfrVariant::DCFR, 0, 4);
+        for k in 0..3 {
+            let product: f64 = one_by_one.iter().map(|f| f[k]).product();
+            assert!((product - jump[k]).abs() < 1e-12);
+        }
+        // β = 0 halves negative regrets every iteration; γ = 2 after t = 1 is (1/2)^2
+        assert!((one_by_one[0][1] - 0.5).abs() < 1e-12);
+        assert!((one_by_one[0][2] - 0.25).abs() < 1e-12);
+        assert_eq!(Discounts::default().between(CfrVariant::CfrPlus, 0, 10), [1.0; 3]);
+    }
+
+    #[test]
+    fn linear_cfr_keeps_negative_regret() {
+        let mut trainer = CfrTrainer::with_variant(CfrVariant::LinearCfr);
+        let key = InfoSetKey { hand_bucket: 0, node_id: 0 };
+        trainer.update(&key, &[-10.0, 5.0], 0.0, 1.0, 0);
+        // Iteration 0's regrets are scaled by 1/2 before iteration 1 adds its own
+        trainer.update(&key, &[4.0, 0.0], 0.0, 1.0, 1);
+        let data = &trainer.info_sets[&key];
+        assert!((data.cumulative_regret[0] - (-5.0 + 4.0)).abs() < 1e-9);
+        assert!((data.cumulative_regret[1] - 2.5).abs() < 1e-9);
+    }
+
     #[test]
     fn trainer_get_or_create() {
         let mut trainer = CfrTrainer::new();
@@ -1,11 +1,12 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
 
-use crate::cards::parse_board;
+use crate::cards::{hand_to_canonical, normalize_cards, parse_board};
 use crate::display::{
-    board_display, equity_bar, print_error, range_grid, styled_action,
+    board_display, equity_bar, print_error, print_failure, print_solver_error, range_grid, styled_action,
 };
+use crate::status::{io_error, ExitCode};
 
 const POSITIONS_6MAX: &[&str] = &["UTG", "HJ", "CO", "BTN", "SB", "BB"];
 const POSITIONS_9MAX: &[&str] = &["UTG", "UTG1", "UTG2", "MP", "HJ", "CO", "BTN", "SB", "BB"];
@@ -13,6 +14,12 @@ const POSITIONS_9MAX: &[&str] = &["UTG", "UTG1", "UTG2", "MP", "HJ", "CO", "BTN"
 #[derive(Parser)]
 #[command(name = "gto", version = "1.0.0", about = "GTO Poker Toolkit — preflop ranges, equity, odds, and strategy.")]
 struct Cli {
+    /// Render suits as letters instead of symbols (also set by GTO_ASCII=1)
+    #[arg(long, global = true)]
+    ascii: bool,
+    /// End with one JSON status line on stderr: command, ok, exit_code, duration_ms, cache_path, error
+    #[arg(long, global = true)]
+    status_json: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -77,6 +84,375 @@ impl ActionSituation {
     }
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum CfrVariantArg {
+    #[value(name = "cfr-plus")]
+    CfrPlus,
+    Linear,
+    Dcfr,
+}
+
+impl CfrVariantArg {
+    fn variant(self) -> crate::cfr::CfrVariant {
+        match self {
+            CfrVariantArg::CfrPlus => crate::cfr::CfrVariant::CfrPlus,
+            CfrVariantArg::Linear => crate::cfr::CfrVariant::LinearCfr,
+            CfrVariantArg::Dcfr => crate::cfr::CfrVariant::DCFR,
+        }
+    }
+}
+
+/// Parse a `--bucketing` value: "equity" (or "equity-only") or "ehs2".
+fn parse_bucketing(s: &str) -> Result<crate::bucketing::BucketingStrategy, String> {
+    crate::bucketing::BucketingStrategy::parse(s)
+        .ok_or_else(|| format!("Unknown bucketing '{}'. Valid: equity, ehs2", s))
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DrawArg {
+    /// Flush draw: 9 outs
+    Flush,
+    /// Open-ended straight draw: 8 outs
+    Oesd,
+    /// Gutshot: 4 outs
+    Gutshot,
+    /// Flush draw and open-ender: 15 outs
+    Combo,
+    /// Two overcards: 6 outs
+    Overcards,
+}
+
+impl DrawArg {
+    fn draw(self) -> crate::math_engine::DrawType {
+        use crate::math_engine::DrawType;
+
+        match self {
+            DrawArg::Flush => DrawType::Flush,
+            DrawArg::Oesd => DrawType::OpenEnded,
+            DrawArg::Gutshot => DrawType::Gutshot,
+            DrawArg::Combo => DrawType::Combo,
+            DrawArg::Overcards => DrawType::Overcards,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, ValueEnum)]
+enum Facing {
+    Bet,
+    Raise,
+}
+
+/// Strategy clean-up flags shared by the postflop solve commands.
+#[derive(Args)]
+struct PostProcessArgs {
+    /// Shrink each combo's strategy toward its hand-class mean (0 = off, 1 = full)
+    #[arg(long, default_value = "0")]
+    smooth: f64,
+    /// Zero out action frequencies below this percent and renormalize
+    #[arg(long, default_value = "0")]
+    purify_threshold: f64,
+    /// Skip smoothing for combos whose equity is more than this percent off the class mean
+    #[arg(long, default_value = "3")]
+    blocker_tolerance: f64,
+}
+
+impl PostProcessArgs {
+    fn build(&self) -> Result<Option<crate::postprocess::PostProcess>, String> {
+        let pp = crate::postprocess::PostProcess::new(
+            self.purify_threshold / 100.0,
+            self.smooth,
+            self.blocker_tolerance / 100.0,
+        )?;
+        Ok(pp.is_active().then_some(pp))
+    }
+}
+
+/// Display rounding shared by the postflop solve commands and `gto query`;
+/// cached solutions keep their exact frequencies.
+#[derive(Args)]
+struct SimplifyArgs {
+    /// Show strategies with action frequencies below this percent rounded to 0 (and within it of 100% to 100%), with the estimated EV loss
+    #[arg(long)]
+    simplify: Option<f64>,
+    /// Show each combo's most frequent action only, with the estimated EV loss
+    #[arg(long)]
+    purify: bool,
+}
+
+impl SimplifyArgs {
+    fn build(&self) -> Result<Option<crate::postprocess::Simplify>, String> {
+        if self.simplify.is_none() && !self.purify {
+            return Ok(None);
+        }
+        crate::postprocess::Simplify::new(self.simplify.unwrap_or(0.0) / 100.0, self.purify).map(Some)
+    }
+}
+
+/// Early-stopping flags shared by the postflop solve commands.
+#[derive(Args)]
+struct TargetArgs {
+    /// Stop once exploitability is at or below this percent of the pot
+    #[arg(long)]
+    target_exploitability: Option<f64>,
+    /// Iteration cap with --target-exploitability [default: the --iterations default]
+    #[arg(long, requires = "target_exploitability", conflicts_with = "iterations")]
+    max_iterations: Option<usize>,
+    /// Solve for this long instead of a set number of iterations, e.g. 90s, 2m or 1h
+    #[arg(long, value_parser = crate::progress::parse_time_budget, conflicts_with_all = ["iterations", "max_iterations"])]
+    time: Option<std::time::Duration>,
+}
+
+impl TargetArgs {
+    /// The iteration count (a cap with a target or --time) and the target.
+    /// A timed solve's cap is the most iterations `bounds` allow.
+    fn resolve(&self, iterations: usize, bounds: crate::limits::Bounds) -> Result<(usize, Option<f64>), String> {
+        match self.target_exploitability {
+            Some(t) if t.is_nan() || t <= 0.0 => Err("--target-exploitability must be positive".to_string()),
+            target if self.time.is_some() => Ok((bounds.max, target)),
+            target => Ok((self.max_iterations.unwrap_or(iterations), target)),
+        }
+    }
+
+    /// How long the solve runs, for the "Solving ..." line.
+    fn run_length(&self, iterations: usize) -> String {
+        match self.time {
+            Some(time) => format!("{:.0}s budget", time.as_secs_f64()),
+            None => format!("{} iterations", iterations),
+        }
+    }
+
+    /// Say whether the solve reached the target.
+    fn report(&self, iterations_run: usize, cap: usize, exploitability: f64, pot: f64) {
+        let Some(target) = self.target_exploitability else {
+            return;
+        };
+        let pct = exploitability.max(0.0) / pot * 100.0;
+        // A timed solve can stop short of the cap without reaching the target
+        let reached = if self.time.is_some() { pct <= target } else { iterations_run < cap };
+        let budget = match self.time {
+            Some(time) => format!("{:.0}s", time.as_secs_f64()),
+            None => format!("{} iterations", cap),
+        };
+        if reached {
+            println!("  Reached {:.2}% of pot (target {}%) after {} iterations", pct, target, iterations_run);
+        } else {
+            let msg = format!("Target {}% not reached in {}: {:.2}% of pot", target, budget, pct);
+            // Out of time, the solution saved is a partial one
+            if self.time.is_some() {
+                crate::status::fail(ExitCode::Interrupted, &msg);
+            }
+            println!("  {}", msg.yellow());
+        }
+        println!();
+    }
+}
+
+/// Raise tree flags shared by the turn and river solve commands.
+#[derive(Args)]
+struct RaiseArgs {
+    /// Raise sizes as fractions of the pot after calling, e.g. 1.0,2.5 [default: 1.0]; a turn solve raises
+    /// this way on both streets
+    #[arg(long, value_delimiter = ',')]
+    raise_sizes: Option<Vec<f64>>,
+    /// Most raises in a betting round [default: 2 on the turn, 3 on the river]
+    #[arg(long)]
+    max_raises: Option<usize>,
+    /// Refuse to solve a tree with more decision nodes than this
+    #[arg(long, default_value_t = crate::postflop_tree::DEFAULT_MAX_TREE_NODES)]
+    max_nodes: usize,
+}
+
+impl RaiseArgs {
+    /// The raise sizes given, checked.
+    fn sizes(&self) -> Result<Option<Vec<f64>>, String> {
+        match &self.raise_sizes {
+            Some(sizes) if sizes.is_empty() || sizes.iter().any(|&f| !(f > 0.0 && f.is_finite())) => {
+                Err("Raise sizes must be positive fractions of the pot".to_string())
+            }
+            sizes => Ok(sizes.clone()),
+        }
+    }
+}
+
+/// (OOP range, IP range, seats) for a postflop solve.
+type SeatedRanges = (Option<String>, Option<String>, Option<(String, String)>);
+
+/// Hero/villain flags shared by the postflop solve commands: an alternative
+/// to --oop/--ip and --oop-pos/--ip-pos that works out who is out of
+/// position from the seats.
+#[derive(Args)]
+struct HeroSeatArgs {
+    /// Hero's seat (UTG, HJ, CO, BTN, SB, BB); with --villain-pos, sets which player is OOP
+    #[arg(long, requires = "villain_pos")]
+    hero_pos: Option<String>,
+    /// Villain's seat (UTG, HJ, CO, BTN, SB, BB)
+    #[arg(long, requires = "hero_pos")]
+    villain_pos: Option<String>,
+    /// Hero's range, for hero's side of the solve (instead of --oop/--ip)
+    #[arg(long, requires = "hero_pos", conflicts_with_all = ["oop", "ip"])]
+    hero: Option<String>,
+    /// Villain's range, for villain's side of the solve (instead of --oop/--ip)
+    #[arg(long, requires = "hero_pos", conflicts_with_all = ["oop", "ip"])]
+    villain: Option<String>,
+}
+
+impl HeroSeatArgs {
+    /// The (OOP, IP) ranges and seats: hero's and villain's put on their
+    /// sides when the seats are given, or `oop`, `ip` and `seats` unchanged.
+    /// Unless `derived` (a preflop matchup or pot type fills in ranges not
+    /// given), the seats need both ranges.
+    fn resolve(
+        self,
+        oop: Option<String>,
+        ip: Option<String>,
+        seats: Option<(String, String)>,
+        derived: bool,
+    ) -> Result<SeatedRanges, String> {
+        let (Some(hero_pos), Some(villain_pos)) = (&self.hero_pos, &self.villain_pos) else {
+            return Ok((oop, ip, seats));
+        };
+        let given = seats.as_ref().map(|(o, i)| (o.as_str(), i.as_str()));
+        let ((oop_pos, ip_pos), hero_is_oop) = crate::strategy::hero_villain_seats(hero_pos, villain_pos, given)?;
+        let (oop, ip) = if hero_is_oop {
+            (self.hero.or(oop), self.villain.or(ip))
+        } else {
+            (self.villain.or(oop), self.hero.or(ip))
+        };
+        let (hero_range, villain_range) = if hero_is_oop { (&oop, &ip) } else { (&ip, &oop) };
+        if !derived && hero_range.is_none() {
+            return Err("--hero range required".to_string());
+        }
+        if !derived && villain_range.is_none() {
+            return Err("--villain range required".to_string());
+        }
+        let hero_seat = if hero_is_oop { format!("{} (OOP)", oop_pos) } else { format!("{} (IP)", ip_pos) };
+        eprintln!("  {}", format!("Hero is {}", hero_seat).dimmed());
+        Ok((oop, ip, Some((oop_pos.as_str().to_string(), ip_pos.as_str().to_string()))))
+    }
+}
+
+/// Raise-size flags for the solved preflop tree, shared by `gto solve
+/// preflop` and the commands that read its solutions.
+#[derive(Args)]
+struct PreflopSizingArgs {
+    /// Open size in big blinds [default: 2.5, or 2.5x a straddle]
+    #[arg(long)]
+    open_size: Option<f64>,
+    /// 3-bet size as a multiple of the open
+    #[arg(long = "threebet-mult", default_value = "3.0")]
+    three_bet_mult: f64,
+    /// 4-bet size as a multiple of the 3-bet
+    #[arg(long = "fourbet-mult", default_value = "2.5")]
+    four_bet_mult: f64,
+}
+
+impl PreflopSizingArgs {
+    fn build(&self, stack_bb: f64) -> Result<crate::preflop_solver::PreflopSizing, String> {
+        self.build_with(stack_bb, &crate::preflop_solver::ForcedBets::default())
+    }
+
+    /// Sizing for a game with antes or a straddle; the default open scales
+    /// with the straddle, and any open has to raise it.
+    fn build_with(
+        &self,
+        stack_bb: f64,
+        forced: &crate::preflop_solver::ForcedBets,
+    ) -> Result<crate::preflop_solver::PreflopSizing, String> {
+        let default_open = crate::preflop_solver::PreflopSizing::default_for(forced).open_size_bb;
+        let open_size = self.open_size.unwrap_or(default_open);
+        if forced.is_straddled() && open_size <= forced.straddle_bb {
+            return Err(format!("Open size must be more than the {}bb straddle, got {}", forced.straddle_bb, open_size));
+        }
+        crate::preflop_solver::PreflopSizing::new(open_size, self.three_bet_mult, self.four_bet_mult, stack_bb)
+    }
+}
+
+/// Antes and straddle of the game a preflop solve is for.
+#[derive(Args)]
+struct ForcedBetArgs {
+    /// Ante every player posts, in big blinds (e.g. 0.125)
+    #[arg(long, default_value = "0")]
+    ante: f64,
+    /// UTG straddle in big blinds (e.g. 2): UTG posts it and acts last preflop
+    #[arg(long)]
+    straddle: Option<f64>,
+}
+
+impl ForcedBetArgs {
+    fn build(&self, stack_bb: f64) -> Result<crate::preflop_solver::ForcedBets, String> {
+        crate::preflop_solver::ForcedBets::new(self.ante, self.straddle.unwrap_or(0.0), stack_bb)
+    }
+}
+
+/// Output flags shared by the postflop solve commands.
+#[derive(Args)]
+struct SolutionOutputArgs {
+    /// Report progress and exploitability every N iterations (0 = off) [default: every 10%, of --time if given]
+    #[arg(long)]
+    progress_every: Option<usize>,
+    /// Print the full solution as JSON (see `solution_json`) instead of the summary
+    #[arg(long)]
+    json: bool,
+    /// Write the JSON to this file instead of stdout
+    #[arg(long, requires = "json")]
+    output: Option<String>,
+    /// List every root combo instead of grouping the ones a hand plays alike
+    #[arg(short, long)]
+    verbose: bool,
+    /// Also break each action down by hand class (sets, top pair, draws, ...)
+    #[arg(long)]
+    breakdown: bool,
+    /// Also write every node's per-combo strategy to this CSV file (see `strategy_csv`)
+    #[arg(long)]
+    export_csv: Option<String>,
+}
+
+impl SolutionOutputArgs {
+    /// Iterations between snapshots. A timed solve's iterations are only a
+    /// cap, so by default it takes its snapshots by the clock (see `progress`).
+    fn progress_interval(&self, iterations: usize, timed: bool) -> Option<usize> {
+        let every = self.progress_every.unwrap_or(if timed { 0 } else { iterations / 10 });
+        (every > 0).then_some(every)
+    }
+
+    /// The progress line printer, unless --progress-every 0 turned it off.
+    fn progress_callback(&self) -> Option<crate::progress::ProgressCallback> {
+        (self.progress_every != Some(0)).then_some(crate::progress::print_progress)
+    }
+
+    /// Show `solution`: the summary, with the breakdown on `--breakdown`, or
+    /// JSON with `--json`; `--export-csv` also writes the strategies as CSV.
+    fn emit<T>(&self, solution: &T, display: impl FnOnce(&T))
+    where
+        T: crate::solution_json::JsonSolution + crate::strategy_csv::CsvSolution,
+    {
+        if !self.json {
+            display(solution);
+            if let Some(breakdown) = solution.breakdown().filter(|_| self.breakdown) {
+                crate::display::print_breakdown(&breakdown);
+            }
+        } else {
+            let output = self.output.as_deref().map(std::path::Path::new);
+            match crate::solution_json::write_json(solution, output) {
+                Ok(()) => {
+                    if let Some(path) = output {
+                        println!("  Solution JSON written to {}", path.display());
+                    }
+                }
+                Err(ref e) => print_failure(ExitCode::CacheIo, e),
+            }
+        }
+        if let Some(path) = &self.export_csv {
+            match crate::strategy_csv::write_csv(solution, std::path::Path::new(path)) {
+                // Keep stdout clean for JSON
+                Ok(()) if self.json && self.output.is_none() => {}
+                Ok(()) => println!("  Strategy CSV written to {}", path),
+                Err(ref e) => print_failure(ExitCode::CacheIo, e),
+            }
+        }
+    }
+}
+
 #[derive(Clone, ValueEnum)]
 enum Street {
     Flop,
@@ -119,12 +495,18 @@ impl Strength {
     }
 }
 
+// Parsed once per run, so the biggest variant's size doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Show preflop opening range for a position
+    #[command(args_conflicts_with_subcommands = true)]
     Range {
+        #[command(subcommand)]
+        command: Option<RangeCommands>,
         /// Position (e.g., UTG, HJ, CO, BTN, SB, BB)
-        position: String,
+        #[arg(required = true)]
+        position: Option<String>,
         /// Table format
         #[arg(short = 't', long = "table", default_value = "6max")]
         table_size: TableSize,
@@ -143,21 +525,41 @@ enum Commands {
         /// Rake percentage for solved ranges
         #[arg(long, default_value = "0")]
         rake: f64,
+        #[command(flatten)]
+        sizing: PreflopSizingArgs,
     },
-    /// Calculate equity between two hands or hand vs range
+    /// Calculate equity between two hands, hand vs range, or range vs range
     Equity {
-        /// Your hand (e.g., AhAs)
+        /// Your hand (e.g., AhAs), or a range (e.g., "TT+,AQs+") for a per-hand range vs range breakdown
         hand1: String,
         /// "vs" keyword (optional)
         versus: Option<String>,
-        /// Opponent hand or range (e.g., KsKd or KK)
+        /// Opponent hand or range (e.g., KsKd, KK, or "AA:1.0,KK:0.5" with frequencies)
         hand2: Option<String>,
         /// Board cards (e.g., AsKd5c)
         #[arg(short, long)]
         board: Option<String>,
-        /// Number of simulations
+        /// Number of simulations (preflop; from the flop on every runout is enumerated)
         #[arg(short = 'n', long, default_value = "30000")]
         sims: usize,
+        /// Enumerate every preflop runout instead of sampling (1.7M boards per villain combo)
+        #[arg(long)]
+        exact: bool,
+        /// Stop sampling once the 95% confidence interval is within this much equity (e.g. 0.005 for ±0.5%); --sims becomes a cap
+        #[arg(long, conflicts_with = "exact")]
+        precision: Option<f64>,
+        /// Seed the sampled runouts: the same seed gives the same result
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Cards known to be out of the deck, e.g. a shown fold (AhKd); no runout deals them
+        #[arg(long)]
+        dead: Option<String>,
+        /// On a flop or turn, also enumerate hero's equity after each next card: best and worst cards and a histogram
+        #[arg(long, requires = "board")]
+        distribution: bool,
+        /// Allow a simulation count outside the recommended bounds
+        #[arg(long)]
+        force: bool,
     },
     /// Calculate pot odds, EV, and implied odds
     Odds {
@@ -165,12 +567,27 @@ enum Commands {
         pot: f64,
         /// Bet size to call
         bet: f64,
-        /// Your equity (0-1) to calculate EV
-        #[arg(short, long = "equity")]
-        equity_val: Option<f64>,
+        /// Your equity to calculate EV: a fraction (0.35) or a percentage (35, 35%)
+        #[arg(short, long = "equity", conflicts_with_all = ["hand", "draw"])]
+        equity_val: Option<String>,
         /// Expected future winnings for implied odds
         #[arg(short = 'i', long = "implied")]
         future: Option<f64>,
+        /// Your hand (e.g., AhKs): computes your equity against --villain
+        #[arg(long, requires = "villain", conflicts_with = "draw")]
+        hand: Option<String>,
+        /// Villain's hand or range for --hand (e.g., KsKd, "TT+,AQs+", or "AA:1.0,KK:0.5" with frequencies)
+        #[arg(long, requires = "hand")]
+        villain: Option<String>,
+        /// Board cards (e.g., Ks9d4c)
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Your draw, when no equity is given: its chance to hit on the next card (flop unless --board says otherwise)
+        #[arg(long, value_enum)]
+        draw: Option<DrawArg>,
+        /// Number of simulations for --hand (preflop; from the flop on every runout is enumerated)
+        #[arg(short = 'n', long, default_value = "30000")]
+        sims: usize,
     },
     /// Analyze board texture
     Board {
@@ -210,16 +627,44 @@ enum Commands {
         /// Hand strength category (for postflop)
         #[arg(long)]
         strength: Option<Strength>,
+        #[command(flatten)]
+        sizing: PreflopSizingArgs,
     },
     /// Calculate minimum defense frequency
     Mdf {
-        /// Current pot size
-        pot: f64,
+        /// Current pot size (before the bet)
+        #[arg(required_unless_present = "pot_flag")]
+        pot: Option<f64>,
         /// Bet size
-        bet: f64,
+        #[arg(required_unless_present = "bet_flag")]
+        bet: Option<f64>,
+        /// Pot size, as a flag
+        #[arg(long = "pot", id = "pot_flag", conflicts_with = "pot")]
+        pot_flag: Option<f64>,
+        /// Bet size, as a flag
+        #[arg(long = "bet", id = "bet_flag", conflicts_with = "bet")]
+        bet_flag: Option<f64>,
         /// Number of players
         #[arg(short = 'n', long, default_value = "2")]
         players: usize,
+        /// Whether you face the bet or, having bet, face a raise
+        #[arg(long, value_enum, default_value = "bet")]
+        facing: Facing,
+        /// Raiser's total for the street (raise-to), with --facing raise
+        #[arg(long, required_if_eq("facing", "raise"))]
+        raise: Option<f64>,
+        /// Flop of a cached solve to compare against (with --facing raise); the bet and raise must be sizes its tree has
+        #[arg(long)]
+        board: Option<String>,
+        /// OOP position of the cached solve, the check-raiser (omit for `gto solve flop` caches)
+        #[arg(long, requires = "board")]
+        oop: Option<String>,
+        /// IP position of the cached solve, the bettor (omit for `gto solve flop` caches)
+        #[arg(long, requires = "board")]
+        ip: Option<String>,
+        /// Effective stack of the cached solve
+        #[arg(short, long, default_value = "100")]
+        stack: f64,
     },
     /// Analyze stack-to-pot ratio
     Spr {
@@ -230,7 +675,7 @@ enum Commands {
     },
     /// Count combos in a range
     Combos {
-        /// Range expression (e.g., "AA,KK,QQ,AKs" or "TT+")
+        /// Range expression (e.g., "AA,KK,QQ,AKs", "TT+", or "TT+,AKo:0.5" with frequencies)
         range_str: String,
     },
     /// Calculate bluff-to-value ratio and fold equity needed
@@ -240,9 +685,19 @@ enum Commands {
         /// Bet size
         bet: f64,
     },
+    /// Plan a multi-street bluff with geometric sizing — `gto geometric 6 94 3`
+    Geometric {
+        /// Current pot size in bb
+        pot: f64,
+        /// Effective stack in bb
+        stack: f64,
+        /// Streets to get the stack in over (1-3, ending on the river)
+        #[arg(default_value = "3")]
+        streets: usize,
+    },
     /// Query GTO strategy for a hand — `gto query AhKs BTN [Ks9d4c] [--pot 6] [--stack 97]`
     Query {
-        /// Your hole cards (e.g., AhKs, QdQc, Td9c)
+        /// Your hole cards (e.g., AhKs, QdQc, Td9c), or postflop a hand class (AKo, QQ) for its average
         hand: String,
         /// Your position (UTG, HJ, CO, BTN, SB, BB)
         position: String,
@@ -254,113 +709,701 @@ enum Commands {
         /// Pot size in bb (auto-derived from spot if omitted)
         #[arg(long)]
         pot: Option<f64>,
-        /// Effective stack in bb
+        /// Effective stack in bb; with --pot-type, the stack depth before the preflop action
         #[arg(short, long, default_value = "100")]
         stack: f64,
-        /// MCCFR iterations for on-demand solving
+        /// Preflop pot type (srp, 3bet, 4bet): derives the pot, effective stack and ranges
+        #[arg(long, conflicts_with = "pot")]
+        pot_type: Option<String>,
+        /// Iterations for on-demand solving instead of a time budget (flops without a cache get a quick solve
+        /// unless --refine)
+        #[arg(short, long)]
+        iterations: Option<usize>,
+        /// Time for each on-demand solve, e.g. 90s or 2m [default: 20s]
+        #[arg(long, value_parser = crate::progress::parse_time_budget, conflicts_with = "iterations")]
+        time: Option<std::time::Duration>,
+        /// Override villain's range for an exploitative solve (postflop only, e.g. "JJ+,AQs+,J4o")
+        #[arg(long)]
+        villain_range: Option<String>,
+        /// Override hero's range the same way (postflop only)
+        #[arg(long)]
+        hero_range: Option<String>,
+        /// Allow on-demand solves outside the recommended iteration bounds
+        #[arg(long)]
+        force: bool,
+        /// Refuse postflop answers graded below this (A-D) and re-solve instead
+        #[arg(long)]
+        min_grade: Option<String>,
+        /// Actions so far on this street, e.g. "check,bet33": show hero's strategy at the node they lead to
+        #[arg(long, requires = "board")]
+        line: Option<String>,
+        /// Solve the flop at full fidelity (--time or --iterations) instead of a quick approximate solve, replacing a
+        /// cached quick solve
+        #[arg(long, requires = "board")]
+        refine: bool,
+        /// Cards known to be out of the deck, e.g. a shown fold (AhKd): solves fresh without them, uncached
+        #[arg(long, requires = "board")]
+        dead: Option<String>,
+        /// On a turn board, answer on this river (e.g. 7s) from the turn solve; --line closes the turn and carries on
+        #[arg(long, requires = "line")]
+        river_card: Option<String>,
+        /// On a turn board, solve the turn and rivers combo by combo from the cached flop solve's reach along --flop-line
+        #[arg(long, requires = "board")]
+        drill: bool,
+        /// Flop actions leading to the turn for --drill, e.g. "bet33,call" [default: check,check]
+        #[arg(long, requires = "drill")]
+        flop_line: Option<String>,
+        /// On a flop board, also show villain's range and how often it folds, calls or raises against each of your options
+        #[arg(long, requires = "board")]
+        show_villain: bool,
+        #[command(flatten)]
+        simplify: SimplifyArgs,
+        #[command(flatten)]
+        sizing: PreflopSizingArgs,
+        #[command(flatten)]
+        forced_bets: ForcedBetArgs,
+    },
+    /// Compare hero's decisions in a hand history file with solver strategies
+    Analyze {
+        /// Hand history file: per hand "Hand: 1", "Seats: BTN 100, BB 100", "Hero: BTN AhKd", then street lines like "Preflop: BTN raise 2.5, BB call" and "Flop: Ks9d4c | BB check, BTN bet 2"
+        file: String,
+        /// MCCFR iterations for on-demand postflop solving
         #[arg(short, long, default_value = "500000")]
         iterations: usize,
+        /// Allow on-demand solves outside the recommended iteration bounds
+        #[arg(long)]
+        force: bool,
     },
     /// Interactive hand advisor — walk through a poker hand step-by-step
     Play,
+    /// Replay a day's logged `gto play` hands against the solver caches and show how often you played its top action
+    Review {
+        /// Day to review (YYYY-MM-DD, UTC) [default: today]
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Walk a cached flop solve's tree in the terminal — `gto browse --board Ks9d4c --oop-pos BB --ip-pos BTN`
+    Browse {
+        /// Flop of the cached solve (e.g., Ks9d4c)
+        #[arg(short, long)]
+        board: String,
+        /// OOP seat of the cached solve (omit for `gto solve flop` caches without seats)
+        #[arg(long, requires = "ip_pos")]
+        oop_pos: Option<String>,
+        /// IP seat of the cached solve
+        #[arg(long, requires = "oop_pos")]
+        ip_pos: Option<String>,
+        /// Starting pot of the cached solve
+        #[arg(short, long, default_value = "10")]
+        pot: f64,
+        /// Effective stack of the cached solve
+        #[arg(short, long, default_value = "50")]
+        stack: f64,
+    },
     /// Solve GTO strategies using CFR+
     Solve {
         #[command(subcommand)]
-        solver: SolverCommands,
+        solver: Box<SolverCommands>,
+    },
+    /// Export sampled (state, strategy) pairs from cached flop solutions as NDJSON
+    ExportTraining {
+        /// JSON array of spots ({board, oop_pos, ip_pos, pot, stack}); default: every cached flop
+        #[arg(long)]
+        spots: Option<String>,
+        /// Output file (newline-delimited JSON)
+        #[arg(long)]
+        out: String,
+        /// Samples drawn per spot
+        #[arg(long, default_value = "5000")]
+        samples_per_spot: usize,
+        /// RNG seed (same seed and spots give identical output)
+        #[arg(long, default_value = "0")]
+        seed: u64,
+    },
+    /// Reports over batch-solved spots
+    Report {
+        #[command(subcommand)]
+        report: ReportCommands,
+    },
+    /// Study notes attached to spots, shown by `query` and `play`
+    Note {
+        #[command(subcommand)]
+        note: NoteCommands,
+    },
+    /// Solve a file of spots ahead of a study session, cheapest first
+    Prefetch {
+        /// Spots file: one descriptor (srp:BTNvsBB:100bb:Ks9d4c) or query-style spot (BTN BB Ks9d4c) per line
+        #[arg(long)]
+        file: String,
+        /// Stop starting new solves once this is spent, e.g. 30m, 1h30m or 90s
+        #[arg(long)]
+        time_budget: Option<String>,
+        /// Number of MCCFR iterations per spot
+        #[arg(short, long, default_value = "500000")]
+        iterations: usize,
+        /// Skip spots already cached at this grade or better (A-D)
+        #[arg(long, default_value = "B")]
+        min_grade: String,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Named board sets for --boardset, stored in ~/.gto-cli/config.json
+    Boardset {
+        #[command(subcommand)]
+        boardset: BoardsetCommands,
+    },
+    /// Manage the solver cache
+    Cache {
+        #[command(subcommand)]
+        cache: CacheCommands,
+    },
+    /// Solve one spot across a parameter range and compare the strategies
+    Sweep {
+        #[command(subcommand)]
+        sweep: SweepCommands,
+    },
+    /// Import solved ranges from portable JSON into the cache
+    Import {
+        #[command(subcommand)]
+        import: ImportCommands,
+    },
+    /// Run a fixed suite of small river, turn and flop solves and report timings
+    Bench {
+        /// Append one JSON line per spot to this file (e.g., bench.jsonl)
+        #[arg(long)]
+        record: Option<String>,
+    },
+    /// Compare rainbow, two-tone and monotone flops of the same ranks
+    CompareTextures {
+        /// Flop ranks in any order (e.g., K94, KK4)
+        #[arg(long, required_unless_present = "boardset")]
+        ranks: Option<String>,
+        /// Not supported: textures are built from one rank pattern (--ranks)
+        #[arg(long)]
+        boardset: Option<String>,
+        /// OOP seat (UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long)]
+        oop_pos: String,
+        /// IP seat (UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long)]
+        ip_pos: String,
+        /// Starting pot size
+        #[arg(short, long, default_value = "6")]
+        pot: f64,
+        /// Effective stack in bb
+        #[arg(short, long, default_value = "100")]
+        stack: f64,
+        /// MCCFR iterations per texture
+        #[arg(short, long, default_value = "200000")]
+        iterations: usize,
+        /// OOP range (default: derived from the 100bb preflop solution)
+        #[arg(long, requires = "ip")]
+        oop: Option<String>,
+        /// IP range (default: derived from the 100bb preflop solution)
+        #[arg(long, requires = "oop")]
+        ip: Option<String>,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
+        #[arg(long)]
+        force: bool,
     },
 }
 
 #[derive(Subcommand)]
-enum SolverCommands {
-    /// Solve push/fold ranges for a given stack depth
-    Pushfold {
-        /// Stack depth in big blinds
-        #[arg(short, long, default_value = "10")]
+enum RangeCommands {
+    /// Compare a solved range across two stack depths, or against the static chart
+    Diff {
+        /// Position (e.g., UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long)]
+        position: String,
+        /// Villain position (for vs_RFI / vs_3bet)
+        #[arg(long)]
+        vs: Option<String>,
+        /// Preflop situation
+        #[arg(short, long, default_value = "RFI")]
+        situation: Situation,
+        /// Table format
+        #[arg(short = 't', long = "table", default_value = "6max")]
+        table_size: TableSize,
+        /// Stack depth of the solve to compare from (in bb)
+        #[arg(long, default_value = "100")]
         stack: f64,
-        /// Rake percentage (0-100)
-        #[arg(short, long, default_value = "0")]
+        /// Stack depth of the solve to compare to (in bb)
+        #[arg(long, required_unless_present = "vs_chart", conflicts_with = "vs_chart")]
+        stack2: Option<f64>,
+        /// Compare the static chart range to the solve at --stack instead
+        #[arg(long)]
+        vs_chart: bool,
+        /// Rake percentage of the solves
+        #[arg(long, default_value = "0")]
         rake: f64,
-        /// Number of CFR+ iterations (more = more accurate)
-        #[arg(short, long, default_value = "10000")]
-        iterations: usize,
+        /// Number of hands to list by largest change
+        #[arg(long, default_value = "10")]
+        top: usize,
+        #[command(flatten)]
+        sizing: PreflopSizingArgs,
     },
-    /// Solve full preflop decision tree (open/3-bet/4-bet)
+}
+
+#[derive(Subcommand)]
+enum ImportCommands {
+    /// Import preflop ranges written by `gto solve preflop --export`
     Preflop {
-        /// Table format
-        #[arg(short = 't', long = "table", default_value = "6max")]
-        table_size: TableSize,
-        /// Stack depth in big blinds
-        #[arg(short, long, default_value = "100")]
+        /// Portable JSON ranges file, as `gto solve preflop --export` writes
+        file: String,
+        /// Stack depth in big blinds the ranges are for
+        #[arg(short, long)]
         stack: f64,
         /// Rake percentage (0-100)
         #[arg(short, long, default_value = "0")]
         rake: f64,
-        /// Number of CFR+ iterations (more = more accurate)
-        #[arg(short, long, default_value = "50000")]
-        iterations: usize,
-    },
-    /// Solve a river spot using CFR+
-    River {
-        /// Board cards (exactly 5 for river, e.g., Ks9d4c7hQc)
-        #[arg(short, long)]
-        board: String,
-        /// OOP player range (e.g., "AA,AKs,KQs")
-        #[arg(long)]
-        oop: String,
-        /// IP player range (e.g., "QQ,JJ,TT")
+        /// Replace a cached solution for the same stack, rake and sizing
         #[arg(long)]
-        ip: String,
-        /// Starting pot size
-        #[arg(short, long, default_value = "10")]
-        pot: f64,
-        /// Effective stack remaining
-        #[arg(short, long, default_value = "20")]
-        stack: f64,
-        /// Number of CFR+ iterations
-        #[arg(short, long, default_value = "10000")]
-        iterations: usize,
+        force: bool,
     },
-    /// Solve a turn spot using CFR+ (turn + river)
-    Turn {
-        /// Board cards (exactly 4 for turn, e.g., Ks9d4c7h)
-        #[arg(short, long)]
-        board: String,
-        /// OOP player range (e.g., "AA,AKs,KQs")
+}
+
+#[derive(Subcommand)]
+enum SweepCommands {
+    /// Solve a flop spot at several stack depths and show how strategy changes with SPR
+    Stacks {
+        /// Board cards (exactly 3, e.g., Ks9d4c)
+        #[arg(short, long, required_unless_present = "boardset", conflicts_with = "boardset")]
+        board: Option<String>,
+        /// Sweep every flop of a board set from config instead of one board
         #[arg(long)]
-        oop: String,
-        /// IP player range (e.g., "QQ,JJ,TT")
+        boardset: Option<String>,
+        /// OOP seat (UTG, HJ, CO, BTN, SB, BB)
         #[arg(long)]
-        ip: String,
+        oop_pos: String,
+        /// IP seat (UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long)]
+        ip_pos: String,
         /// Starting pot size
-        #[arg(short, long, default_value = "10")]
+        #[arg(short, long, default_value = "6")]
         pot: f64,
-        /// Effective stack remaining
-        #[arg(short, long, default_value = "20")]
-        stack: f64,
-        /// Number of CFR+ iterations
-        #[arg(short, long, default_value = "5000")]
+        /// Comma-separated effective stacks (e.g., 20,35,50,75,100,150)
+        #[arg(long)]
+        stacks: String,
+        /// MCCFR iterations per depth
+        #[arg(short, long, default_value = "200000")]
         iterations: usize,
-    },
-    /// Solve a flop spot using MCCFR (flop + turn + river)
-    Flop {
-        /// Board cards (exactly 3 for flop, e.g., Ks9d4c)
-        #[arg(short, long)]
-        board: String,
-        /// OOP player range (e.g., "AA,AKs,KQs")
+        /// OOP range (default: derived from the 100bb preflop solution)
+        #[arg(long, requires = "ip")]
+        oop: Option<String>,
+        /// IP range (default: derived from the 100bb preflop solution)
+        #[arg(long, requires = "oop")]
+        ip: Option<String>,
+        /// Also write per-depth frequencies as CSV for plotting
         #[arg(long)]
-        oop: String,
-        /// IP player range (e.g., "QQ,JJ,TT")
+        csv: Option<String>,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
         #[arg(long)]
-        ip: String,
-        /// Starting pot size
-        #[arg(short, long, default_value = "10")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BoardsetCommands {
+    /// Create or replace a board set
+    Add {
+        /// Set name (e.g. my25)
+        name: String,
+        /// Flops, space- or comma-separated (e.g. Ks9d4c As7d2c)
+        #[arg(required = true)]
+        boards: Vec<String>,
+    },
+    /// List board sets with their coverage of all flops
+    List,
+    /// Delete a board set
+    Remove {
+        /// Set name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum NoteCommands {
+    /// Attach a note to a spot
+    Add {
+        /// Spot as pot:matchup:stack:board[:line] (e.g. srp:BTNvsBB:100bb:Ks9d4c)
+        #[arg(long)]
+        spot: String,
+        /// Note text
+        text: String,
+        /// Tag the note (repeatable, e.g. --tag exploit)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// List notes, optionally for one spot or tag
+    List {
+        /// Only notes that show for this spot
+        #[arg(long)]
+        spot: Option<String>,
+        /// Only notes with this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Search note text, tags and spots
+    Search {
+        /// Text to look for (case-insensitive)
+        query: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Show or rebuild the summary index that reports scan instead of full solutions
+    Index {
+        /// Re-index every cached flop solution (skips unchanged files)
+        #[arg(long)]
+        rebuild: bool,
+    },
+    /// List cached solutions: type, spot, iterations, grade, size and age
+    List {
+        /// Only show solutions graded below this (A-D), e.g. to pick re-solves
+        #[arg(long)]
+        below: Option<String>,
+        /// Only show one solver's solutions (preflop, pushfold, flop, turn, river)
+        #[arg(long = "type")]
+        kind: Option<String>,
+    },
+    /// Show one cached solution's header and root strategy
+    Info {
+        /// File name in the cache directory, or a path
+        file: String,
+    },
+    /// Delete cached solutions by age and/or type
+    Prune {
+        /// Only solutions at least this old (e.g., 30d, 12h, 2w)
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Only one solver's solutions (preflop, pushfold, flop, turn, river)
+        #[arg(long = "type")]
+        kind: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Spots where the EV split diverges most from the equity split
+    Edges {
+        /// Stack depth in big blinds (must match the batch solve)
+        #[arg(short, long, default_value = "100")]
+        stack: f64,
+        /// Only include single raised pots
+        #[arg(long)]
+        srp_only: bool,
+        /// Include all 1,755 canonical flops instead of 50 representative
+        #[arg(long)]
+        all_flops: bool,
+        /// Only include the flops of a board set from config
+        #[arg(long, conflicts_with = "all_flops")]
+        boardset: Option<String>,
+        /// Number of boards to show for each direction
+        #[arg(long, default_value = "10")]
+        top: usize,
+    },
+    /// GTO frequencies for one position pair across batch flops, to compare with pool stats
+    Baseline {
+        /// Stat to report: fold-vs-cbet, turn-probe, delayed-cbet, or all
+        #[arg(long, default_value = "all")]
+        stat: String,
+        /// OOP seat (e.g., BB)
+        #[arg(long)]
+        oop: String,
+        /// IP seat (e.g., BTN)
+        #[arg(long)]
+        ip: String,
+        /// C-bet sizes in percent of pot
+        #[arg(long, default_value = "33,75")]
+        sizes: String,
+        /// Pot type: srp, 3bp, 4bp
+        #[arg(long, default_value = "srp")]
+        pot_type: String,
+        /// Stack depth in big blinds (must match the batch solve)
+        #[arg(short, long, default_value = "100")]
+        stack: f64,
+        /// Include all 1,755 canonical flops instead of 50 representative
+        #[arg(long)]
+        all_flops: bool,
+        /// Aggregate over the flops of a board set from config
+        #[arg(long, conflicts_with = "all_flops")]
+        boardset: Option<String>,
+    },
+    /// OOP root frequencies and bet sizing per board texture across cached flops
+    Flops {
+        /// Stack depth in big blinds (must match the batch solve)
+        #[arg(short, long, default_value = "100")]
+        stack: f64,
+    },
+}
+
+#[derive(Subcommand)]
+enum SolverCommands {
+    /// Solve push/fold ranges for a given stack depth
+    Pushfold {
+        /// Stack depth in big blinds
+        #[arg(short, long, default_value = "10")]
+        stack: f64,
+        /// Rake percentage (0-100)
+        #[arg(short, long, default_value = "0")]
+        rake: f64,
+        /// Shoving seat with folds ahead (UTG, HJ, CO, BTN, SB); everyone behind calls or folds in turn
+        #[arg(short, long)]
+        position: Option<String>,
+        /// Ante every player posts, in big blinds
+        #[arg(short, long, default_value = "0")]
+        ante: f64,
+        /// Number of CFR+ iterations (more = more accurate)
+        #[arg(short, long, default_value = "10000")]
+        iterations: usize,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Solve full preflop decision tree (open/3-bet/4-bet)
+    Preflop {
+        /// Table format
+        #[arg(short = 't', long = "table", default_value = "6max")]
+        table_size: TableSize,
+        /// Stack depth in big blinds
+        #[arg(short, long, default_value = "100")]
+        stack: f64,
+        /// Rake percentage (0-100)
+        #[arg(short, long, default_value = "0")]
+        rake: f64,
+        /// Number of CFR+ iterations per spot (more = more accurate); a cap with --target
+        #[arg(short, long, default_value = "50000")]
+        iterations: usize,
+        /// Stop each spot once its exploitability is at or below this many bb (e.g. 0.2)
+        #[arg(long)]
+        target: Option<f64>,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        sizing: PreflopSizingArgs,
+        #[command(flatten)]
+        forced_bets: ForcedBetArgs,
+        /// Also write the solved ranges as portable JSON (see `gto import preflop`)
+        #[arg(long)]
+        export: Option<String>,
+    },
+    /// Solve a river spot using CFR+
+    River {
+        /// Board cards (exactly 5 for river, e.g., Ks9d4c7hQc)
+        #[arg(short, long)]
+        board: String,
+        /// OOP player range (e.g., "AA,AKs,KQs", or "AA,AKs:0.5" with frequencies)
+        #[arg(long, required_unless_present_any = ["from_preflop", "hero", "villain"])]
+        oop: Option<String>,
+        /// IP player range (e.g., "QQ,JJ,TT", or "QQ,JJ:0.25" with frequencies)
+        #[arg(long, required_unless_present_any = ["from_preflop", "hero", "villain"])]
+        ip: Option<String>,
+        /// Middle player's range, for a 3-way solve (acts after OOP, before IP; a CFR approximation, not cached)
+        #[arg(long, conflicts_with_all = ["oop_pos", "hero_pos", "from_preflop", "lock", "target_exploitability", "smooth", "purify_threshold", "cfr_variant", "bet_sizes"])]
+        mp: Option<String>,
+        /// Starting pot size
+        #[arg(short, long, default_value = "10")]
         pot: f64,
         /// Effective stack remaining
-        #[arg(short, long, default_value = "50")]
+        #[arg(short, long, default_value = "20")]
         stack: f64,
-        /// Number of MCCFR iterations
-        #[arg(short, long, default_value = "500000")]
+        /// Number of CFR+ iterations
+        #[arg(short, long, default_value = "10000")]
+        iterations: usize,
+        /// Bet sizes as fractions of the pot, e.g. 0.5,1.0,2.0 (default 0.33,0.67,1.0 plus all-in); sizes
+        /// past the stack become an all-in
+        #[arg(long, value_delimiter = ',')]
+        bet_sizes: Option<Vec<f64>>,
+        /// Also offer an all-in with --bet-sizes
+        #[arg(long, requires = "bet_sizes")]
+        allin: bool,
+        /// OOP seat (UTG, HJ, CO, BTN, SB, BB); lets `gto query` find this solve
+        #[arg(long, requires = "ip_pos")]
+        oop_pos: Option<String>,
+        /// IP seat (UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long, requires = "oop_pos")]
+        ip_pos: Option<String>,
+        /// Preflop matchup, e.g. BTNvsBB: sets the seats, and any range not given is the single raised pot
+        /// range from the cached preflop solve at --preflop-stack (the static charts if there is none)
+        #[arg(long, conflicts_with_all = ["oop_pos", "hero_pos"])]
+        from_preflop: Option<String>,
+        /// Stack depth in big blinds of the preflop solve --from-preflop reads
+        #[arg(long, default_value = "100", requires = "from_preflop")]
+        preflop_stack: f64,
+        /// Fix a player's strategy at a node, e.g. "node=3,player=OOP,strategy=check:1.0"; separate actions
+        /// with | ("check:0.5|bet:0.5") and add combo=AhKd to lock one combo. Repeatable
+        #[arg(long)]
+        lock: Vec<String>,
+        /// CSV of `combo,weight` lines giving each OOP combo's starting reach (e.g. how often it gets here in a
+        /// turn solve); combos of the range not listed start at 0
+        #[arg(long, conflicts_with = "mp")]
+        oop_weights_file: Option<String>,
+        /// CSV of `combo,weight` lines for the IP combos, like --oop-weights-file
+        #[arg(long, conflicts_with = "mp")]
+        ip_weights_file: Option<String>,
+        /// Regret-minimization variant: CFR+, linear CFR, or discounted CFR (usually converges in fewer iterations)
+        #[arg(long, value_enum, default_value = "cfr-plus")]
+        cfr_variant: CfrVariantArg,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        raises: RaiseArgs,
+        #[command(flatten)]
+        roles: HeroSeatArgs,
+        #[command(flatten)]
+        postprocess: PostProcessArgs,
+        #[command(flatten)]
+        simplify: SimplifyArgs,
+        #[command(flatten)]
+        output: SolutionOutputArgs,
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+    /// Solve a turn spot using CFR+ (turn + river)
+    Turn {
+        /// Board cards (exactly 4 for turn, e.g., Ks9d4c7h)
+        #[arg(short, long)]
+        board: String,
+        /// OOP player range (e.g., "AA,AKs,KQs", or "AA,AKs:0.5" with frequencies), or the range taking a line of a cached flop solve: "@flop:Ks9d4c:bet75"
+        #[arg(long, required_unless_present_any = ["from_preflop", "hero", "villain"])]
+        oop: Option<String>,
+        /// IP player range (e.g., "QQ,JJ,TT", or "QQ,JJ:0.25" with frequencies), or "@flop:Ks9d4c:check,bet75"
+        #[arg(long, required_unless_present_any = ["from_preflop", "hero", "villain"])]
+        ip: Option<String>,
+        /// Starting pot size
+        #[arg(short, long, default_value = "10")]
+        pot: f64,
+        /// Effective stack remaining
+        #[arg(short, long, default_value = "20")]
+        stack: f64,
+        /// Number of CFR+ iterations
+        #[arg(short, long, default_value = "5000")]
         iterations: usize,
+        /// OOP seat (UTG, HJ, CO, BTN, SB, BB); lets `gto query` find this solve
+        #[arg(long, requires = "ip_pos")]
+        oop_pos: Option<String>,
+        /// IP seat (UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long, requires = "oop_pos")]
+        ip_pos: Option<String>,
+        /// Preflop matchup, e.g. BTNvsBB: sets the seats, and any range not given is the single raised pot
+        /// range from the cached preflop solve at --preflop-stack (the static charts if there is none)
+        #[arg(long, conflicts_with_all = ["oop_pos", "hero_pos"])]
+        from_preflop: Option<String>,
+        /// Stack depth in big blinds of the preflop solve --from-preflop reads
+        #[arg(long, default_value = "100", requires = "from_preflop")]
+        preflop_stack: f64,
+        /// Regret-minimization variant: CFR+, linear CFR, or discounted CFR (usually converges in fewer iterations)
+        #[arg(long, value_enum, default_value = "cfr-plus")]
+        cfr_variant: CfrVariantArg,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`), and re-solve a spot already cached
+        #[arg(long)]
+        force: bool,
+        /// Also show the river strategies after this river card (e.g. 7s), for every turn line that reaches it
+        #[arg(long)]
+        river_card: Option<String>,
+        /// Don't keep the solved river strategies (a smaller cache file; turn queries can't then take --river-card)
+        #[arg(long, conflicts_with = "river_card")]
+        no_rivers: bool,
+        #[command(flatten)]
+        raises: RaiseArgs,
+        #[command(flatten)]
+        roles: HeroSeatArgs,
+        #[command(flatten)]
+        postprocess: PostProcessArgs,
+        #[command(flatten)]
+        simplify: SimplifyArgs,
+        #[command(flatten)]
+        output: SolutionOutputArgs,
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+    /// Solve a flop spot using MCCFR (flop + turn + river)
+    Flop {
+        /// Board cards (exactly 3 for flop, e.g., Ks9d4c)
+        #[arg(short, long, required_unless_present = "edit_from")]
+        board: Option<String>,
+        /// OOP player range (e.g., "AA,AKs,KQs", or "AA,AKs:0.5" with frequencies); with --edit-from, an edit like "+A5o,-K7s"
+        #[arg(long, required_unless_present_any = ["edit_from", "pot_type", "from_preflop", "hero", "villain"])]
+        oop: Option<String>,
+        /// IP player range (e.g., "QQ,JJ,TT", or "QQ,JJ:0.25" with frequencies); with --edit-from, an edit like "+A5o,-K7s"
+        #[arg(long, required_unless_present_any = ["edit_from", "pot_type", "from_preflop", "hero", "villain"])]
+        ip: Option<String>,
+        /// Starting pot size
+        #[arg(short, long, default_value = "10")]
+        pot: f64,
+        /// Effective stack remaining; with --pot-type, the stack depth before the preflop action [default: 50, or 100 with --pot-type]
+        #[arg(short, long)]
+        stack: Option<f64>,
+        /// Preflop pot type (srp, 3bet, 4bet): derives the pot and effective stack, and any range not given from the cached preflop solve;
+        /// needs the seats (--oop-pos/--ip-pos or --from-preflop)
+        #[arg(long, conflicts_with_all = ["pot", "edit_from"])]
+        pot_type: Option<String>,
+        /// Number of MCCFR iterations [default: 500000, or 20% of the cached run with --edit-from]
+        #[arg(short, long)]
+        iterations: Option<usize>,
+        /// Re-solve a cached flop solution (.bin) after editing its ranges
+        #[arg(long, conflicts_with_all = ["board", "pot", "stack", "hero_pos"])]
+        edit_from: Option<String>,
+        /// Continue this spot's last solve, if it saved a checkpoint; --iterations is then the new total
+        #[arg(long, conflicts_with = "edit_from")]
+        resume: bool,
+        /// Save a checkpoint for --resume every this many iterations (one is also saved when --time or
+        /// --target stops the solve early)
+        #[arg(long, conflicts_with = "edit_from")]
+        checkpoint_every: Option<usize>,
+        /// OOP seat (UTG, HJ, CO, BTN, SB, BB); lets `gto query` find this solve
+        #[arg(long, requires = "ip_pos", conflicts_with = "edit_from")]
+        oop_pos: Option<String>,
+        /// IP seat (UTG, HJ, CO, BTN, SB, BB)
+        #[arg(long, requires = "oop_pos", conflicts_with = "edit_from")]
+        ip_pos: Option<String>,
+        /// Preflop matchup, e.g. BTNvsBB: sets the seats and, like --pot-type (default srp), the spot and any
+        /// range not given, from the cached preflop solve at --stack (the static charts if there is none)
+        #[arg(long, conflicts_with_all = ["oop_pos", "hero_pos", "pot", "edit_from"])]
+        from_preflop: Option<String>,
+        /// Seed the solver's sampling: the same seed and iterations give an identical solution
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Regret-minimization variant: CFR+, linear CFR, or discounted CFR (usually converges in fewer iterations)
+        #[arg(long, value_enum, default_value = "cfr-plus")]
+        cfr_variant: CfrVariantArg,
+        /// How combos are bucketed for the turn and river: "equity" slices, or "ehs2", k-means over equity, its
+        /// spread over the runouts and draws (separates made hands from draws of the same equity)
+        #[arg(long, value_parser = parse_bucketing, default_value = "equity", conflicts_with = "edit_from")]
+        bucketing: crate::bucketing::BucketingStrategy,
+        /// Also show each player's first decision as a 13x13 grid by canonical hand
+        #[arg(long)]
+        grid: bool,
+        /// Print the range taking an action as a weighted range string, e.g. "node=0,action=bet 75%" (add ",threshold=0.05" to drop rarer combos)
+        #[arg(long)]
+        export_range: Option<String>,
+        /// Memory the per-runout river tables may take (e.g., 512M, 2G); past it, runouts are built as sampled
+        #[arg(long, default_value = "2G")]
+        max_precompute_mem: String,
+        /// After the solve, refine flop strategies per combo (a tenth more iterations) so combos sharing a bucket can differ by blockers
+        #[arg(long)]
+        refine_combos: bool,
+        /// Also solve a flop tree with each of these bet sizes alone (pot fractions, e.g. 0.33,0.75) and compare
+        /// OOP's EV against a best response with the full tree's
+        #[arg(long, conflicts_with_all = ["edit_from", "resume", "grid", "export_range", "json"])]
+        compare_sizes: Option<String>,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`)
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        roles: HeroSeatArgs,
+        #[command(flatten)]
+        postprocess: PostProcessArgs,
+        #[command(flatten)]
+        simplify: SimplifyArgs,
+        #[command(flatten)]
+        output: SolutionOutputArgs,
+        #[command(flatten)]
+        target: TargetArgs,
     },
     /// Batch pre-solve flop spots across positions and boards
     Batch {
@@ -379,6 +1422,22 @@ enum SolverCommands {
         /// Use all 1,755 canonical flops instead of 50 representative
         #[arg(long)]
         all_flops: bool,
+        /// Solve the flops of a board set from config
+        #[arg(long, conflicts_with = "all_flops")]
+        boardset: Option<String>,
+        /// Only these matchups, in this order (e.g. BTNvsBB,COvsBB)
+        #[arg(long)]
+        positions: Option<String>,
+        /// Spots to solve at once, each on its share of the threads; capped so the spots' estimated memory fits
+        #[arg(short, long, default_value = "1")]
+        jobs: usize,
+        /// Memory the workers may use together (e.g. 24G); defaults to the memory available
+        #[arg(long)]
+        max_memory: Option<String>,
+        /// Allow iterations outside the recommended bounds (below-floor solves aren't served to `gto query`),
+        /// and re-solve spots the batch manifest marks done
+        #[arg(long)]
+        force: bool,
     },
 }
 
@@ -405,14 +1464,74 @@ pub fn run() {
     dispatch(cli);
 }
 
+/// Parse and run one command, then exit with its status code (see `status`).
 pub fn run_with_args(args: Vec<String>) {
-    let cli = Cli::parse_from(args);
+    use clap::{CommandFactory, FromArgMatches};
+    use crate::status::{self, Status};
+
+    let start = std::time::Instant::now();
+    let status_json = args.iter().any(|a| a == "--status-json");
+    let matches = Cli::command().try_get_matches_from(&args).and_then(|m| Cli::from_arg_matches(&m).map(|cli| (m, cli)));
+    let (matches, cli) = match matches {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let _ = e.print();
+            let failed = e.use_stderr();
+            if status_json {
+                let status = Status {
+                    code: if failed { ExitCode::InvalidInput } else { ExitCode::Success },
+                    error: failed.then(|| {
+                        let rendered = e.to_string();
+                        rendered.lines().next().unwrap_or_default().trim_start_matches("error: ").to_string()
+                    }),
+                    cache_path: None,
+                };
+                let command = args.get(1).filter(|a| !a.starts_with('-')).map(String::as_str).unwrap_or_default();
+                eprintln!("{}", status::status_line(command, &status, start.elapsed().as_millis()));
+            }
+            std::process::exit(if failed { ExitCode::InvalidInput.code() } else { 0 });
+        }
+    };
+
     dispatch(cli);
+
+    let status = status::current();
+    if status_json {
+        let mut names = Vec::new();
+        let mut sub = matches.subcommand();
+        while let Some((name, m)) = sub {
+            names.push(name);
+            sub = m.subcommand();
+        }
+        eprintln!("{}", status::status_line(&names.join(" "), &status, start.elapsed().as_millis()));
+    }
+    if status.code != ExitCode::Success {
+        std::process::exit(status.code.code());
+    }
 }
 
 fn dispatch(cli: Cli) {
+    if cli.ascii || std::env::var_os("GTO_ASCII").is_some() {
+        crate::cards::set_suit_style(crate::cards::SuitStyle::Ascii);
+    }
     match cli.command {
         Commands::Range {
+            command: Some(RangeCommands::Diff {
+                position,
+                vs,
+                situation,
+                table_size,
+                stack,
+                stack2,
+                vs_chart: _,
+                rake,
+                top,
+                sizing,
+            }),
+            ..
+        } => cmd_range_diff(position, table_size.as_str(), vs, situation, stack, stack2, rake, top, &sizing),
+        Commands::Range {
+            command: None,
             position,
             table_size,
             vs,
@@ -420,9 +1539,11 @@ fn dispatch(cli: Cli) {
             solved,
             stack,
             rake,
+            sizing,
         } => {
+            let position = position.unwrap_or_default();
             if solved {
-                cmd_range_solved(position, table_size.as_str(), vs, situation, stack, rake);
+                cmd_range_solved(position, table_size.as_str(), vs, situation, stack, rake, &sizing);
             } else {
                 cmd_range(position, table_size.as_str(), vs, situation);
             }
@@ -433,13 +1554,35 @@ fn dispatch(cli: Cli) {
             hand2,
             board,
             sims,
-        } => cmd_equity(hand1, versus, hand2, board, sims),
+            exact,
+            precision,
+            seed,
+            dead,
+            distribution,
+            force,
+        } => cmd_equity(
+            hand1,
+            versus,
+            hand2,
+            board,
+            dead,
+            sims,
+            exact,
+            crate::equity::Sampling { precision, seed },
+            distribution,
+            force,
+        ),
         Commands::Odds {
             pot,
             bet,
             equity_val,
             future,
-        } => cmd_odds(pot, bet, equity_val, future),
+            hand,
+            villain,
+            board,
+            draw,
+            sims,
+        } => cmd_odds(pot, bet, equity_val, future, hand.zip(villain), board, draw, sims),
         Commands::Board { cards } => cmd_board(cards),
         Commands::Action {
             hand,
@@ -453,9 +1596,10 @@ fn dispatch(cli: Cli) {
             street,
             strength,
             rake,
+            sizing,
         } => {
             if board.is_none() {
-                cmd_action_preflop(hand, position, vs, table_size.as_str(), stack, rake);
+                cmd_action_preflop(hand, position, vs, table_size.as_str(), stack, rake, &sizing);
             } else {
                 // Infer situation for postflop static advisor
                 let situation = if vs.is_some() {
@@ -478,13 +1622,37 @@ fn dispatch(cli: Cli) {
                 );
             }
         }
-        Commands::Mdf { pot, bet, players } => cmd_mdf(pot, bet, players),
+        Commands::Mdf {
+            pot,
+            bet,
+            pot_flag,
+            bet_flag,
+            players,
+            facing,
+            raise,
+            board,
+            oop,
+            ip,
+            stack,
+        } => {
+            let (pot, bet) = (pot.or(pot_flag).unwrap_or_default(), bet.or(bet_flag).unwrap_or_default());
+            match facing {
+                Facing::Bet => cmd_mdf(pot, bet, players),
+                Facing::Raise => cmd_mdf_vs_raise(
+                    pot,
+                    bet,
+                    raise.unwrap_or_default(),
+                    board.map(|b| (b, oop.unwrap_or_default(), ip.unwrap_or_default(), stack)),
+                ),
+            }
+        }
         Commands::Spr {
             stack_size,
             pot_size,
         } => cmd_spr(stack_size, pot_size),
         Commands::Combos { range_str } => cmd_combos(range_str),
         Commands::Bluff { pot, bet } => cmd_bluff(pot, bet),
+        Commands::Geometric { pot, stack, streets } => cmd_geometric(pot, stack, streets),
         Commands::Query {
             hand,
             position,
@@ -492,29 +1660,110 @@ fn dispatch(cli: Cli) {
             board,
             pot,
             stack,
+            pot_type,
             iterations,
-        } => cmd_query(hand, position, vs, board, pot, stack, iterations),
+            time,
+            villain_range,
+            hero_range,
+            force,
+            min_grade,
+            line,
+            refine,
+            dead,
+            river_card,
+            drill,
+            flop_line,
+            show_villain,
+            simplify,
+            sizing,
+            forced_bets,
+        } => cmd_query(
+            hand, position, vs, board, pot, stack, pot_type, iterations, time, villain_range, hero_range, force,
+            min_grade, line, refine, dead, river_card,
+            drill.then(|| flop_line.unwrap_or_else(|| "check,check".to_string())), show_villain, simplify, &sizing,
+            forced_bets,
+        ),
+        Commands::Analyze { file, iterations, force } => {
+            if let Err(e) = crate::hand_history::run_analyze(&file, iterations, force) {
+                print_error(&e);
+            }
+        }
         Commands::Play => crate::play::play_command(),
-        Commands::Solve { solver } => match solver {
+        Commands::Review { date } => {
+            if let Err(e) = crate::play::run_review(date.as_deref()) {
+                print_error(&e);
+            }
+        }
+        Commands::Browse { board, oop_pos, ip_pos, pot, stack } => cmd_browse(board, oop_pos, ip_pos, pot, stack),
+        Commands::Solve { solver } => match *solver {
             SolverCommands::Pushfold {
                 stack,
                 rake,
+                position,
+                ante,
                 iterations,
-            } => cmd_solve_pushfold(stack, rake, iterations),
+                force,
+            } => cmd_solve_pushfold(stack, rake, position, ante, iterations, force),
             SolverCommands::Preflop {
                 table_size,
                 stack,
                 rake,
                 iterations,
-            } => cmd_solve_preflop(table_size, stack, rake, iterations),
+                target,
+                force,
+                sizing,
+                forced_bets,
+                export,
+            } => cmd_solve_preflop(table_size, stack, rake, iterations, target, force, &sizing, &forced_bets, export),
             SolverCommands::River {
                 board,
                 oop,
                 ip,
+                mp: Some(mp),
                 pot,
                 stack,
                 iterations,
-            } => cmd_solve_river(board, oop, ip, pot, stack, iterations),
+                force,
+                output,
+                ..
+            } => cmd_solve_river_multiway(
+                board, [oop.unwrap_or_default(), mp, ip.unwrap_or_default()], pot, stack, iterations, force, output,
+            ),
+            SolverCommands::River {
+                board,
+                oop,
+                ip,
+                mp: None,
+                pot,
+                stack,
+                iterations,
+                bet_sizes,
+                allin,
+                oop_pos,
+                ip_pos,
+                from_preflop,
+                preflop_stack,
+                lock,
+                oop_weights_file,
+                ip_weights_file,
+                cfr_variant,
+                force,
+                raises,
+                roles,
+                postprocess,
+                simplify,
+                output,
+                target,
+            } => match roles
+                .resolve(oop, ip, oop_pos.zip(ip_pos), from_preflop.is_some())
+                .and_then(|(oop, ip, seats)| preflop_spot(from_preflop.as_deref(), preflop_stack, oop, ip, seats))
+            {
+                Ok((oop, ip, seats)) => cmd_solve_river(
+                    board, oop, ip, pot, stack, iterations, bet_sizes.map(|sizes| (sizes, allin)), raises, seats, lock,
+                    [oop_weights_file, ip_weights_file], cfr_variant, force, postprocess, simplify, output, target,
+                ),
+                Err(ref e) => print_error(e),
+            },
             SolverCommands::Turn {
                 board,
                 oop,
@@ -522,51 +1771,423 @@ fn dispatch(cli: Cli) {
                 pot,
                 stack,
                 iterations,
-            } => cmd_solve_turn(board, oop, ip, pot, stack, iterations),
+                oop_pos,
+                ip_pos,
+                from_preflop,
+                preflop_stack,
+                cfr_variant,
+                force,
+                river_card,
+                no_rivers,
+                raises,
+                roles,
+                postprocess,
+                simplify,
+                output,
+                target,
+            } => match roles
+                .resolve(oop, ip, oop_pos.zip(ip_pos), from_preflop.is_some())
+                .and_then(|(oop, ip, seats)| preflop_spot(from_preflop.as_deref(), preflop_stack, oop, ip, seats))
+            {
+                Ok((oop, ip, seats)) => cmd_solve_turn(
+                    board, oop, ip, pot, stack, iterations, seats, cfr_variant, force, river_card, no_rivers, raises,
+                    postprocess, simplify, output, target,
+                ),
+                Err(ref e) => print_error(e),
+            },
             SolverCommands::Flop {
                 board,
                 oop,
                 ip,
                 pot,
                 stack,
+                pot_type,
                 iterations,
-            } => cmd_solve_flop(board, oop, ip, pot, stack, iterations),
+                edit_from,
+                resume,
+                checkpoint_every,
+                oop_pos,
+                ip_pos,
+                from_preflop,
+                seed,
+                cfr_variant,
+                bucketing,
+                grid,
+                export_range,
+                max_precompute_mem,
+                refine_combos,
+                compare_sizes,
+                force,
+                roles,
+                postprocess,
+                simplify,
+                output,
+                target,
+            } => match crate::runout_table::parse_mem_size(&max_precompute_mem) {
+                Err(ref e) => print_error(e),
+                Ok(max_precompute) => match edit_from {
+                    Some(path) => cmd_solve_flop_edit(
+                        path, oop, ip, iterations, seed, cfr_variant, grid, export_range, max_precompute,
+                        refine_combos, force, postprocess, simplify, output, target,
+                    ),
+                    None => match roles.resolve(
+                        oop,
+                        ip,
+                        oop_pos.zip(ip_pos),
+                        pot_type.is_some() || from_preflop.is_some(),
+                    ) {
+                        Err(ref e) => print_error(e),
+                        Ok((oop, ip, seats)) => cmd_solve_flop(
+                            board.unwrap_or_default(),
+                            oop,
+                            ip,
+                            pot,
+                            stack,
+                            pot_type,
+                            iterations.unwrap_or(500_000),
+                            seats,
+                            from_preflop,
+                            resume,
+                            checkpoint_every,
+                            seed,
+                            cfr_variant,
+                            bucketing,
+                            grid,
+                            export_range,
+                            max_precompute,
+                            refine_combos,
+                            compare_sizes,
+                            force,
+                            postprocess,
+                            simplify,
+                            output,
+                            target,
+                        ),
+                    },
+                },
+            },
             SolverCommands::Batch {
                 stack,
                 srp_only,
                 iterations,
                 limit,
                 all_flops,
-            } => crate::batch::run_batch_solve(stack, srp_only, limit, iterations, all_flops),
+                boardset,
+                positions,
+                jobs,
+                max_memory,
+                force,
+            } => {
+                if check_bounds(crate::limits::FLOP_ITERATIONS, iterations, force) {
+                    let positions = positions.as_deref().map(crate::batch::parse_position_pairs).transpose();
+                    let max_memory = max_memory.as_deref().map(crate::runout_table::parse_mem_size).transpose();
+                    match resolve_boardset_arg(boardset).and_then(|set| Ok((set, positions?, max_memory?))) {
+                        Ok((set, positions, max_memory)) => crate::batch::run_batch_solve(
+                            stack,
+                            srp_only,
+                            limit,
+                            iterations,
+                            all_flops,
+                            set.as_ref(),
+                            positions.as_deref(),
+                            force,
+                            jobs,
+                            max_memory,
+                        ),
+                        Err(ref e) => print_error(e),
+                    }
+                }
+            }
+        },
+        Commands::ExportTraining {
+            spots,
+            out,
+            samples_per_spot,
+            seed,
+        } => crate::training_export::run_export(spots, out, samples_per_spot, seed),
+        Commands::Report { report } => match report {
+            ReportCommands::Edges {
+                stack,
+                srp_only,
+                all_flops,
+                boardset,
+                top,
+            } => match resolve_boardset_arg(boardset) {
+                Ok(set) => crate::batch::run_edges_report(stack, srp_only, all_flops, top, set.as_ref()),
+                Err(ref e) => print_error(e),
+            },
+            ReportCommands::Baseline {
+                stat,
+                oop,
+                ip,
+                sizes,
+                pot_type,
+                stack,
+                all_flops,
+                boardset,
+            } => {
+                let result = resolve_boardset_arg(boardset).and_then(|set| {
+                    crate::baseline::run_baseline_report(&stat, &oop, &ip, &sizes, &pot_type, stack, all_flops, set.as_ref())
+                });
+                if let Err(e) = result {
+                    print_error(&e);
+                }
+            }
+            ReportCommands::Flops { stack } => crate::texture_report::run_flop_texture_report(stack),
+        },
+        Commands::Note { note } => {
+            let result = match note {
+                NoteCommands::Add { spot, text, tags } => crate::notes::run_note_add(&spot, &text, tags),
+                NoteCommands::List { spot, tag } => crate::notes::run_note_list(spot.as_deref(), tag.as_deref()),
+                NoteCommands::Search { query } => crate::notes::run_note_search(&query),
+            };
+            if let Err(e) = result {
+                print_error(&e);
+            }
+        }
+        Commands::Prefetch {
+            file,
+            time_budget,
+            iterations,
+            min_grade,
+            force,
+        } => {
+            if check_bounds(crate::limits::FLOP_ITERATIONS, iterations, force) {
+                let result = parse_grade(Some(&min_grade)).and_then(|min_grade| {
+                    let budget = time_budget.as_deref().map(crate::progress::parse_time_budget).transpose()?;
+                    let min_grade = min_grade.unwrap_or(crate::quality::Grade::B);
+                    crate::prefetch::run_prefetch(&file, budget, iterations, min_grade)
+                });
+                if let Err(e) = result {
+                    print_error(&e);
+                }
+            }
+        }
+        Commands::Boardset { boardset } => {
+            let result = match boardset {
+                BoardsetCommands::Add { name, boards } => crate::config::run_boardset_add(&name, &boards),
+                BoardsetCommands::List => crate::config::run_boardset_list(),
+                BoardsetCommands::Remove { name } => crate::config::run_boardset_remove(&name),
+            };
+            if let Err(e) = result {
+                print_error(&e);
+            }
+        }
+        Commands::Cache { cache } => match cache {
+            CacheCommands::Index { rebuild } => crate::cache_index::run_cache_index(rebuild),
+            CacheCommands::List { below, kind } => {
+                match parse_grade(below.as_deref()).and_then(|below| Ok((parse_cache_kind(kind.as_deref())?, below))) {
+                    Ok((kind, below)) => crate::cache::run_cache_list(kind, below),
+                    Err(ref e) => print_error(e),
+                }
+            }
+            CacheCommands::Info { file } => {
+                if let Err(e) = crate::cache::run_cache_info(&file) {
+                    print_error(&e);
+                }
+            }
+            CacheCommands::Prune { older_than, kind } => {
+                let result = parse_cache_kind(kind.as_deref())
+                    .and_then(|kind| crate::cache::run_cache_prune(older_than.as_deref(), kind));
+                if let Err(e) = result {
+                    print_error(&e);
+                }
+            }
+        },
+        Commands::Sweep { sweep } => match sweep {
+            SweepCommands::Stacks {
+                board,
+                boardset,
+                oop_pos,
+                ip_pos,
+                pot,
+                stacks,
+                iterations,
+                oop,
+                ip,
+                csv,
+                force,
+            } => {
+                if check_bounds(crate::limits::FLOP_ITERATIONS, iterations, force) {
+                    match boards_arg(board, boardset) {
+                        Ok(boards) => cmd_sweep_stacks(boards, oop_pos, ip_pos, pot, stacks, iterations, oop.zip(ip), csv),
+                        Err(ref e) => print_error(e),
+                    }
+                }
+            }
         },
+        Commands::Import { import } => match import {
+            ImportCommands::Preflop { file, stack, rake, force } => cmd_import_preflop(file, stack, rake, force),
+        },
+        Commands::Bench { record } => cmd_bench(record),
+        Commands::CompareTextures {
+            ranks,
+            boardset,
+            oop_pos,
+            ip_pos,
+            pot,
+            stack,
+            iterations,
+            oop,
+            ip,
+            force,
+        } => {
+            if let Some(name) = boardset {
+                print_error(&format!(
+                    "compare-textures needs a rank pattern (--ranks), not a board set; try `gto report baseline --boardset {}`",
+                    name
+                ));
+            } else if check_bounds(crate::limits::FLOP_ITERATIONS, iterations, force) {
+                cmd_compare_textures(ranks.unwrap_or_default(), oop_pos, ip_pos, pot, stack, iterations, oop.zip(ip))
+            }
+        }
     }
 }
 
-fn cmd_range(position: String, table_size: &str, vs: Option<String>, situation: Situation) {
-    use crate::preflop::{
-        get_bb_defense, get_rfi_pct, get_rfi_range, get_vs_3bet_range, get_vs_rfi_range,
-    };
-    use crate::ranges::{range_pct, total_combos};
+fn cmd_bench(record: Option<String>) {
+    use crate::bench::{print_results, record_results, run_spot, SUITE};
 
-    let position = match validate_position(&position, table_size) {
-        Ok(p) => p,
-        Err(e) => {
-            print_error(&e);
-            return;
+    let mut results = Vec::new();
+    for spot in &SUITE {
+        let _spinner = crate::progress::Spinner::start(&format!("Solving {} ({})", spot.name, spot.board));
+        match run_spot(spot) {
+            Ok(result) => results.push(result),
+            Err(ref e) => {
+                print_solver_error(e);
+                return;
+            }
         }
-    };
+    }
+    print_results(&results);
 
-    match situation {
-        Situation::RFI => {
-            let hands = get_rfi_range(&position, table_size);
-            let pct = get_rfi_pct(&position, table_size);
-            let title = format!("{} RFI Range ({})", position, table_size);
+    if let Some(path) = record {
+        match record_results(std::path::Path::new(&path), &results) {
+            Ok(()) => println!("\n  Recorded {} results to {}", results.len(), path),
+            Err(e) => print_failure(ExitCode::CacheIo, &format!("Could not write {}: {}", path, e)),
+        }
+    }
+}
 
-            println!();
-            println!("{}", range_grid(&hands, &title));
-            println!();
-            println!(
-                "  {} hands | {} combos | {}% of hands",
+/// `gto range diff`: per-hand frequency changes at one spot between two
+/// solved stack depths (`stack2`), or from the static chart to the solve.
+#[allow(clippy::too_many_arguments)]
+fn cmd_range_diff(
+    position: String,
+    table_size: &str,
+    vs: Option<String>,
+    situation: Situation,
+    stack_bb: f64,
+    stack2: Option<f64>,
+    rake_pct: f64,
+    top: usize,
+    sizing: &PreflopSizingArgs,
+) {
+    use crate::display::diff_grid;
+    use crate::preflop_solver::Position;
+    use crate::range_diff::{DiffSpot, RangeDiff};
+
+    let parse_position = |pos: &str| -> Result<Position, String> {
+        let pos = validate_position(pos, table_size)?;
+        Position::from_str(&pos).ok_or_else(|| format!("Invalid position: {}", pos))
+    };
+    let hero = match parse_position(&position) {
+        Ok(p) => p,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let villain = match vs.as_deref().map(parse_position).transpose() {
+        Ok(v) => v,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let spot = match (situation, villain) {
+        (Situation::RFI, _) => DiffSpot::Open,
+        (Situation::VsRFI | Situation::BbDefense, Some(v)) => DiffSpot::VsOpen(v),
+        (Situation::Vs3Bet, Some(v)) => DiffSpot::Vs3Bet(v),
+        (situation, None) => {
+            print_error(&format!("--vs required for {} situation", situation.as_str()));
+            return;
+        }
+    };
+
+    let solved = |stack_bb: f64| -> Result<Vec<f64>, String> {
+        let sizing = sizing.build(stack_bb)?;
+        let solution = load_preflop_solution(table_size, stack_bb, rake_pct, &sizing)?;
+        spot.solved(&solution, hero)
+    };
+    let ranges = match stack2 {
+        Some(stack2) => solved(stack_bb)
+            .and_then(|before| Ok((before, solved(stack2)?)))
+            .map(|r| (r, format!("{}bb", stack_bb), format!("{}bb", stack2))),
+        None => spot
+            .chart(hero, table_size)
+            .and_then(|before| Ok((before, solved(stack_bb)?)))
+            .map(|r| (r, "chart".to_string(), format!("{}bb solved", stack_bb))),
+    };
+    let ((before, after), from, to) = match ranges {
+        Ok(r) => r,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let diff = RangeDiff::new(before, after);
+    let (pct_before, pct_after) = diff.range_pcts();
+
+    println!();
+    println!("  {} Range Diff  |  {}  |  {} → {}", "GTO".bold(), spot.label(hero), from, to);
+    println!("  Plays {:.1}% → {:.1}% of hands", pct_before, pct_after);
+    println!();
+    println!("{}", diff_grid(&diff.deltas(), "Change in frequency (points)"));
+    println!();
+    let shifts = diff.largest_shifts(top);
+    if shifts.is_empty() {
+        println!("  {}", "No hand changed by half a point or more.".dimmed());
+    } else {
+        println!("  Largest changes:");
+        for shift in &shifts {
+            let delta = format!("{:+.0}", shift.delta() * 100.0);
+            println!(
+                "    {:<4} {:>4.0}% → {:>3.0}%  {}",
+                shift.hand,
+                shift.before * 100.0,
+                shift.after * 100.0,
+                if shift.delta() > 0.0 { delta.green() } else { delta.red() },
+            );
+        }
+    }
+    println!();
+}
+
+fn cmd_range(position: String, table_size: &str, vs: Option<String>, situation: Situation) {
+    use crate::preflop::{
+        get_bb_defense, get_rfi_pct, get_rfi_range, get_vs_3bet_range, get_vs_rfi_range,
+    };
+    use crate::ranges::{range_pct, total_combos};
+
+    let position = match validate_position(&position, table_size) {
+        Ok(p) => p,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    };
+
+    match situation {
+        Situation::RFI => {
+            let hands = get_rfi_range(&position, table_size);
+            let pct = get_rfi_pct(&position, table_size);
+            let title = format!("{} RFI Range ({})", position, table_size);
+
+            println!();
+            println!("{}", range_grid(&hands, &title));
+            println!();
+            println!(
+                "  {} hands | {} combos | {}% of hands",
                 hands.len().to_string().bold(),
                 total_combos(&hands).to_string().bold(),
                 pct.to_string().bold(),
@@ -724,9 +2345,10 @@ fn cmd_range_solved(
     situation: Situation,
     stack_bb: f64,
     rake_pct: f64,
+    sizing: &PreflopSizingArgs,
 ) {
     use crate::display::strategy_grid;
-    use crate::preflop_solver::{Position, PreflopSolution};
+    use crate::preflop_solver::Position;
 
     let position = match validate_position(&position, table_size) {
         Ok(p) => p,
@@ -736,13 +2358,17 @@ fn cmd_range_solved(
         }
     };
 
-    let solution = match PreflopSolution::load(table_size, stack_bb, rake_pct) {
+    let sizing = match sizing.build(stack_bb) {
         Ok(s) => s,
-        Err(_) => {
-            print_error(&format!(
-                "No cached solution found for {} {}bb {}% rake. Run 'gto solve preflop --stack {} --rake {}' first.",
-                table_size, stack_bb, rake_pct, stack_bb, rake_pct,
-            ));
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    };
+    let solution = match load_preflop_solution(table_size, stack_bb, rake_pct, &sizing) {
+        Ok(s) => s,
+        Err(e) => {
+            print_error(&e);
             return;
         }
     };
@@ -764,11 +2390,12 @@ fn cmd_range_solved(
                 Some(spot) => {
                     println!();
                     println!(
-                        "  {} {} Open Range ({:.1}% of hands) | {}bb | Solved",
+                        "  {} {} Open Range ({:.1}% of hands) | {}bb | {} | Solved",
                         "GTO".bold(),
                         position,
                         spot.open_pct(),
                         stack_bb,
+                        solution.sizing.label(),
                     );
                     println!();
                     println!("{}", strategy_grid(&spot.open_strategy, &format!(
@@ -804,11 +2431,12 @@ fn cmd_range_solved(
                 Some(spot) => {
                     println!();
                     println!(
-                        "  {} {} vs {} Open | {}bb | Solved",
+                        "  {} {} vs {} Open | {}bb | {} | Solved",
                         "GTO".bold(),
                         position,
                         vs_str,
                         stack_bb,
+                        solution.sizing.label(),
                     );
                     println!();
                     println!("{}", strategy_grid(&spot.vs_open_3bet, &format!(
@@ -854,11 +2482,12 @@ fn cmd_range_solved(
                 Some(spot) => {
                     println!();
                     println!(
-                        "  {} {} vs {} 3-Bet | {}bb | Solved",
+                        "  {} {} vs {} 3-Bet | {}bb | {} | Solved",
                         "GTO".bold(),
                         position,
                         vs_str,
                         stack_bb,
+                        solution.sizing.label(),
                     );
                     println!();
                     println!("{}", strategy_grid(&spot.vs_3bet_4bet, &format!(
@@ -878,16 +2507,58 @@ fn cmd_range_solved(
     }
 }
 
+/// Validate an iteration or simulation count, printing the rejection.
+/// A forced run below the floor gets a warning instead.
+fn check_bounds(bounds: crate::limits::Bounds, value: usize, force: bool) -> bool {
+    if let Err(ref e) = bounds.check(value, force) {
+        print_error(e);
+        return false;
+    }
+    if !bounds.meets_floor(value) {
+        let note = if bounds == crate::limits::EQUITY_SIMS {
+            "expect noisy results"
+        } else {
+            "expect noisy results; `gto query` won't use this solution"
+        };
+        println!(
+            "  {} {} below the quality floor of {}: {}",
+            "Warning:".yellow().bold(),
+            bounds.name,
+            bounds.min,
+            note
+        );
+    }
+    true
+}
+
 fn cmd_equity(
     hand1: String,
     versus: Option<String>,
     hand2: Option<String>,
     board: Option<String>,
+    dead: Option<String>,
     sims: usize,
+    exact: bool,
+    sampling: crate::equity::Sampling,
+    distribution: bool,
+    force: bool,
 ) {
-    use crate::cards::parse_card;
-    use crate::equity::{equity_vs_hand, equity_vs_range};
-    use crate::ranges::parse_range;
+    use crate::cards::{check_dead_cards, check_distinct};
+    use crate::equity::{
+        equity_vs_hand_sampled, equity_vs_weighted_range_sampled, exact_equity_vs_hand,
+        exact_equity_vs_weighted_range, EXACT_MIN_BOARD,
+    };
+    use crate::ranges::parse_weighted_range;
+
+    if !check_bounds(crate::limits::EQUITY_SIMS, sims, force) {
+        return;
+    }
+    if sampling.precision.is_some_and(|p| !(p > 0.0 && p < 1.0)) {
+        print_error("--precision must be between 0 and 1 (e.g. 0.005 for ±0.5%)");
+        return;
+    }
+
+    let hand1 = normalize_cards(&hand1).unwrap_or(hand1);
 
     // Handle "gto equity AhAs vs KsKd" or "gto equity AhAs KsKd"
     let hand2 = match (hand2, &versus) {
@@ -898,6 +2569,8 @@ fn cmd_equity(
     };
 
     let hand2 = match hand2 {
+        // A single hand may be pasted in rendered form; ranges pass through
+        Some(h) if !h.contains(',') => normalize_cards(&h).unwrap_or(h),
         Some(h) => h,
         None => {
             print_error("Usage: gto equity <hand1> vs <hand2|range>");
@@ -905,78 +2578,134 @@ fn cmd_equity(
         }
     };
 
-    let board_cards = match &board {
-        Some(b) => match parse_board(b) {
-            Ok(cards) => Some(cards),
-            Err(e) => {
-                print_error(&e.to_string());
-                return;
-            }
-        },
-        None => None,
+    let board_cards = match parse_board_arg(board.as_deref()) {
+        Ok(cards) => cards,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
     };
-
-    let h1: Vec<crate::cards::Card> = {
-        let mut cards = Vec::new();
-        let chars: Vec<char> = hand1.chars().collect();
-        for i in (0..chars.len()).step_by(2) {
-            if i + 1 >= chars.len() {
-                print_error(&format!("Invalid hand: {}", hand1));
-                return;
-            }
-            let s: String = chars[i..=i + 1].iter().collect();
-            match parse_card(&s) {
-                Ok(c) => cards.push(c),
-                Err(_) => {
-                    print_error(&format!("Invalid hand: {}", hand1));
-                    return;
-                }
-            }
+    let dead_cards = match dead.as_deref().map(crate::cards::parse_cards).transpose() {
+        Ok(cards) => cards.unwrap_or_default(),
+        Err(e) => {
+            print_error(&format!("Invalid --dead cards: {}", e));
+            return;
         }
-        cards
     };
 
-    // Try parsing hand2 as specific cards first
-    let is_range = hand2.len() != 4 || {
-        let chars: Vec<char> = hand2.chars().collect();
-        let mut bad = false;
-        for i in (0..chars.len()).step_by(2) {
-            if i + 1 >= chars.len() {
-                bad = true;
-                break;
-            }
-            let s: String = chars[i..=i + 1].iter().collect();
-            if parse_card(&s).is_err() {
-                bad = true;
-                break;
-            }
+    if !is_exact_hand(&hand1) {
+        if !dead_cards.is_empty() {
+            print_error("--dead needs a single hand first");
+            return;
+        }
+        if exact {
+            print_error("--exact needs a single hand first; range vs range already enumerates when it can");
+            return;
+        }
+        if sampling.precision.is_some() {
+            print_error("--precision needs a single hand first");
+            return;
+        }
+        if distribution {
+            print_error("--distribution needs a single hand first");
+            return;
+        }
+        cmd_equity_ranges(&hand1, &hand2, board_cards.as_deref(), sims);
+        return;
+    }
+
+    let h1 = match parse_hole_cards(&hand1) {
+        Ok(cards) => cards,
+        Err(ref e) => {
+            print_error(e);
+            return;
         }
-        bad
     };
 
+    // Try parsing hand2 as specific cards first
+    let is_range = !is_exact_hand(&hand2);
+    let h2 = if is_range { vec![] } else { parse_board(&hand2).unwrap_or_default() };
+
+    // Villain combos a range shares with the board are just left out
+    let dealt = [&h1[..], &h2, board_cards.as_deref().unwrap_or_default()].concat();
+    if let Err(e) = check_distinct(&dealt) {
+        print_error(&e.to_string());
+        return;
+    }
+    if let Err(e) = check_dead_cards(&dead_cards, &[&h1, &h2], board_cards.as_deref().unwrap_or_default()) {
+        print_error(&e.to_string());
+        return;
+    }
+
     println!();
-    let board_str = if let Some(ref bc) = board_cards {
+    let mut board_str = if let Some(ref bc) = board_cards {
         format!(" on {}", board_display(bc))
     } else {
         String::new()
     };
+    if !dead_cards.is_empty() {
+        board_str.push_str(&format!(" (dead: {})", board_display(&dead_cards)));
+    }
+    let board_cards = board_cards.unwrap_or_default();
+    if distribution && !(3..=4).contains(&board_cards.len()) {
+        print_error("--distribution needs a flop or turn board (3 or 4 cards)");
+        return;
+    }
+    let exact = exact || board_cards.len() >= EXACT_MIN_BOARD;
+    let running = if exact {
+        format!("  {} every runout...\n", "Exact:".bold())
+    } else if let Some(precision) = sampling.precision {
+        format!(
+            "  Running up to {} simulations (until ±{:.1}%)...\n",
+            format!("{}", sims).bold(),
+            precision * 100.0
+        )
+    } else {
+        format!("  Running {} simulations...\n", format!("{}", sims).bold())
+    };
+    let sims_row = |result: &crate::equity::EquityResult| {
+        if result.exact {
+            vec![Cell::new("Runouts".bold().to_string()), Cell::new(format!("{} (exact)", result.simulations))]
+        } else if sampling.precision.is_some() {
+            let stop = if result.converged { "converged" } else { "cap reached" };
+            vec![Cell::new("Sims".bold().to_string()), Cell::new(format!("{} ({})", result.simulations, stop))]
+        } else {
+            vec![Cell::new("Sims".bold().to_string()), Cell::new(format!("{}", result.simulations))]
+        }
+    };
+    // Sampled equity gets its 95% confidence interval under the estimate
+    let ci_row = |table: &mut Table, result: &crate::equity::EquityResult| {
+        if !result.exact {
+            let (lo, hi) = (result.equity() - result.ci_half_width(), result.equity() + result.ci_half_width());
+            table.add_row(vec![
+                Cell::new("95% CI".bold().to_string()),
+                Cell::new(format!("{:.1}% – {:.1}% (±{:.2}%)", lo * 100.0, hi * 100.0, result.ci_half_width() * 100.0)),
+            ]);
+        }
+    };
 
     if is_range {
-        let villain_range = parse_range(&hand2);
+        let villain_range = match parse_weighted_range(&hand2) {
+            Ok(r) => r,
+            Err(e) => {
+                print_error(&e.to_string());
+                return;
+            }
+        };
         println!(
             "  {} vs {}{}",
             hand1.bold(),
             hand2.bold(),
             board_str
         );
-        println!("  Running {} simulations...\n", format!("{}", sims).bold());
+        println!("{}", running);
 
-        match equity_vs_range(
-            &h1,
-            &villain_range,
-            board_cards.as_deref(),
-            sims,
-        ) {
+        let result = if exact {
+            exact_equity_vs_weighted_range(&h1, &villain_range, &board_cards, &dead_cards)
+        } else {
+            equity_vs_weighted_range_sampled(&h1, &villain_range, Some(&board_cards), &dead_cards, sims, sampling)
+        };
+        match result {
             Ok(result) => {
                 println!("  Hero:    {}", equity_bar(result.equity(), 30));
                 println!("  Villain: {}", equity_bar(1.0 - result.equity(), 30));
@@ -1001,35 +2730,35 @@ fn cmd_equity(
                     Cell::new("Equity".bold().to_string()),
                     Cell::new(format!("{:.1}%", result.equity() * 100.0).bold().to_string()),
                 ]);
+                ci_row(&mut table, &result);
                 table.add_row(vec![
-                    Cell::new("Sims".bold().to_string()),
-                    Cell::new(format!("{}", result.simulations)),
+                    Cell::new("Villain combos".bold().to_string()),
+                    Cell::new(format!("{:.1}", result.villain_combos)),
                 ]);
+                table.add_row(sims_row(&result));
                 println!("{}", table);
                 println!();
             }
-            Err(e) => print_error(&e.to_string()),
+            Err(e) => {
+                print_error(&e.to_string());
+                return;
+            }
         }
     } else {
-        let h2: Vec<crate::cards::Card> = {
-            let chars: Vec<char> = hand2.chars().collect();
-            let mut cards = Vec::new();
-            for i in (0..chars.len()).step_by(2) {
-                let s: String = chars[i..=i + 1].iter().collect();
-                cards.push(parse_card(&s).unwrap());
-            }
-            cards
-        };
-
         println!(
             "  {} vs {}{}",
             hand1.bold(),
             hand2.bold(),
             board_str
         );
-        println!("  Running {} simulations...\n", format!("{}", sims).bold());
+        println!("{}", running);
 
-        match equity_vs_hand(&h1, &h2, board_cards.as_deref(), sims) {
+        let result = if exact {
+            exact_equity_vs_hand(&h1, &h2, &board_cards, &dead_cards)
+        } else {
+            equity_vs_hand_sampled(&h1, &h2, Some(&board_cards), &dead_cards, sims, sampling)
+        };
+        match result {
             Ok(result) => {
                 println!("  Hero:    {}", equity_bar(result.equity(), 30));
                 println!("  Villain: {}", equity_bar(1.0 - result.equity(), 30));
@@ -1054,20 +2783,161 @@ fn cmd_equity(
                     Cell::new("Equity".bold().to_string()),
                     Cell::new(format!("{:.1}%", result.equity() * 100.0).bold().to_string()),
                 ]);
-                table.add_row(vec![
-                    Cell::new("Sims".bold().to_string()),
-                    Cell::new(format!("{}", result.simulations)),
-                ]);
+                ci_row(&mut table, &result);
+                table.add_row(sims_row(&result));
                 println!("{}", table);
                 println!();
             }
-            Err(e) => print_error(&e.to_string()),
+            Err(e) => {
+                print_error(&e.to_string());
+                return;
+            }
+        }
+    }
+
+    if distribution {
+        let villain = if is_range {
+            parse_weighted_range(&hand2).unwrap_or_default()
+        } else {
+            vec![(hand2.clone(), 1.0)]
+        };
+        print_equity_distribution(&h1, &villain, &board_cards, &dead_cards);
+    }
+}
+
+/// Hero's equity after each next card: the best and worst cards, the share
+/// of cards hero is ahead on, and a histogram.
+fn print_equity_distribution(
+    hand: &[crate::cards::Card],
+    villain: &[(String, f64)],
+    board: &[crate::cards::Card],
+    dead_cards: &[crate::cards::Card],
+) {
+    use crate::display::equity_histogram;
+    use crate::equity::equity_distribution;
+
+    let mut by_card = match equity_distribution(hand, villain, board, dead_cards) {
+        Ok(d) => d,
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+    by_card.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let street = if board.len() == 3 { "turn" } else { "river" };
+    let ahead = by_card.iter().filter(|(_, e)| *e > 0.5).count();
+
+    println!(
+        "  {} over {} {} cards: ahead (>50%) on {} ({:.0}%)",
+        "Distribution".bold(),
+        by_card.len(),
+        street,
+        ahead,
+        ahead as f64 / by_card.len().max(1) as f64 * 100.0
+    );
+    println!();
+    let shown = by_card.len().min(5);
+    for (title, cards) in [("Best", &by_card[..shown]), ("Worst", &by_card[by_card.len() - shown..])] {
+        println!("  {}", title.bold());
+        for (card, equity) in cards {
+            println!("    {}  {}", card.pretty(), equity_bar(*equity, 20));
         }
     }
+    println!();
+    let equities: Vec<f64> = by_card.iter().map(|(_, e)| *e).collect();
+    println!("{}", equity_histogram(&equities, 20));
+    println!();
+}
+
+/// Two specific hole cards, e.g. "AhKs".
+fn parse_hole_cards(hand: &str) -> Result<Vec<crate::cards::Card>, String> {
+    match parse_board(hand) {
+        Ok(cards) if cards.len() == 2 => Ok(cards),
+        _ => Err(format!("Invalid hand: {}", hand)),
+    }
+}
+
+/// The cards of an optional `--board`.
+fn parse_board_arg(board: Option<&str>) -> Result<Option<Vec<crate::cards::Card>>, String> {
+    board.map(parse_board).transpose().map_err(|e| e.to_string())
+}
+
+/// True for two specific cards like "AhKs"; "AKs" or "TT+" is a range.
+fn is_exact_hand(s: &str) -> bool {
+    use crate::cards::parse_card;
+
+    s.len() == 4 && s.is_char_boundary(2) && parse_card(&s[..2]).is_ok() && parse_card(&s[2..]).is_ok()
+}
+
+/// `gto equity <range> vs <range>`: overall equity and each range entry's
+/// equity against the whole other range.
+fn cmd_equity_ranges(range1: &str, range2: &str, board: Option<&[crate::cards::Card]>, sims: usize) {
+    use crate::equity::{equity_range_vs_range, HandClassEquity};
+    use crate::ranges::parse_weighted_range;
+
+    let parse = |range: &str| match parse_weighted_range(range) {
+        Ok(r) if r.is_empty() => Err(format!("Invalid hand or range: {}", range)),
+        Ok(r) => Ok(r),
+        Err(e) => Err(e.to_string()),
+    };
+    let (r1, r2) = match (parse(range1), parse(range2)) {
+        (Ok(r1), Ok(r2)) => (r1, r2),
+        (Err(ref e), _) | (_, Err(ref e)) => {
+            print_error(e);
+            return;
+        }
+    };
+    let result = match equity_range_vs_range(&r1, &r2, board, sims) {
+        Ok(r) => r,
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+
+    println!();
+    let board_str = board.map_or(String::new(), |b| format!(" on {}", board_display(b)));
+    println!("  {} vs {}{}", range1.bold(), range2.bold(), board_str);
+    let method = if result.exact { "exact" } else { "sampled" };
+    println!("  {} matchups × {} runouts ({})", result.matchups, result.runouts, method.dimmed());
+    println!();
+    println!("  Range 1: {}", equity_bar(result.equity, 30));
+    println!("  Range 2: {}", equity_bar(1.0 - result.equity, 30));
+
+    let print_classes = |label: &str, classes: &[HandClassEquity]| {
+        let mut sorted: Vec<&HandClassEquity> = classes.iter().collect();
+        sorted.sort_by(|a, b| b.equity.unwrap_or(-1.0).total_cmp(&a.equity.unwrap_or(-1.0)));
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new(label.bold().to_string()),
+            Cell::new("Combos".bold().to_string()),
+            Cell::new("Equity".bold().to_string()),
+        ]);
+        for class in sorted {
+            let equity = class.equity.map_or("-".to_string(), |e| format!("{:.1}%", e * 100.0));
+            table.add_row(vec![Cell::new(&class.hand), Cell::new(class.combos), Cell::new(equity)]);
+        }
+        println!();
+        println!("{}", table);
+    };
+    print_classes("Range 1", &result.classes1);
+    print_classes("Range 2", &result.classes2);
+    println!();
 }
 
-fn cmd_odds(pot: f64, bet: f64, equity_val: Option<f64>, future: Option<f64>) {
-    use crate::math_engine::{ev, implied_odds, pot_odds};
+#[allow(clippy::too_many_arguments)]
+fn cmd_odds(
+    pot: f64,
+    bet: f64,
+    equity_val: Option<String>,
+    future: Option<f64>,
+    matchup: Option<(String, String)>,
+    board: Option<String>,
+    draw: Option<DrawArg>,
+    sims: usize,
+) {
+    use crate::math_engine::{ev, implied_odds, next_card_equity, parse_equity, pot_odds};
 
     let needed = match pot_odds(pot, bet) {
         Ok(v) => v,
@@ -1076,6 +2946,40 @@ fn cmd_odds(pot: f64, bet: f64, equity_val: Option<f64>, future: Option<f64>) {
             return;
         }
     };
+    let implied = match future.map(|fut| implied_odds(pot, bet, fut)).transpose() {
+        Ok(v) => v,
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+    let board_cards = match parse_board_arg(board.as_deref()) {
+        Ok(cards) => cards,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    // Hero's equity, and where it came from when it wasn't given
+    let equity = match (equity_val, matchup, draw) {
+        (Some(e), _, _) => parse_equity(&e).map(|e| Some((e, None))).map_err(|e| e.to_string()),
+        (None, Some((hand, villain)), _) => odds_equity(&hand, &villain, board_cards.as_deref(), sims).map(Some),
+        (None, None, Some(draw)) => {
+            let draw = draw.draw();
+            next_card_equity(draw.outs(), board_cards.as_ref().map_or(3, |b| b.len()))
+                .map(|e| Some((e, Some(format!("{}: {} outs, next card", draw.as_str(), draw.outs())))))
+                .map_err(|e| e.to_string())
+        }
+        (None, None, None) => Ok(None),
+    };
+    let equity = match equity {
+        Ok(e) => e,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
 
     println!();
 
@@ -1100,8 +3004,8 @@ fn cmd_odds(pot: f64, bet: f64, equity_val: Option<f64>, future: Option<f64>) {
         Cell::new(format!("${:.0}", pot + bet + bet)),
     ]);
 
-    if let Some(eq) = equity_val {
-        let ev_val = ev(eq, pot, bet);
+    if let Some((eq, source)) = &equity {
+        let ev_val = ev(*eq, pot, bet);
         let ev_str = if ev_val >= 0.0 {
             format!("${:.2}", ev_val).green().to_string()
         } else {
@@ -1111,40 +3015,75 @@ fn cmd_odds(pot: f64, bet: f64, equity_val: Option<f64>, future: Option<f64>) {
             Cell::new("Your Equity"),
             Cell::new(format!("{:.1}%", eq * 100.0)),
         ]);
+        if let Some(source) = source {
+            table.add_row(vec![Cell::new("Equity From"), Cell::new(source.dimmed().to_string())]);
+        }
         table.add_row(vec![Cell::new("EV of Call"), Cell::new(ev_str)]);
-        let verdict = if ev_val >= 0.0 {
-            "CALL".green().bold().to_string()
-        } else {
-            "FOLD".red().bold().to_string()
-        };
-        table.add_row(vec![Cell::new("Verdict"), Cell::new(verdict)]);
     }
 
-    if let Some(fut) = future {
-        match implied_odds(pot, bet, fut) {
-            Ok(imp) => {
-                table.add_row(vec![
-                    Cell::new("Implied Odds"),
-                    Cell::new(format!("{:.1}%", imp * 100.0)),
-                ]);
-                table.add_row(vec![
-                    Cell::new("Future Value"),
-                    Cell::new(format!("${:.0}", fut)),
-                ]);
-            }
-            Err(e) => {
-                print_error(&e.to_string());
-                return;
-            }
-        }
+    if let (Some(imp), Some(fut)) = (implied, future) {
+        table.add_row(vec![
+            Cell::new("Implied Odds"),
+            Cell::new(format!("{:.1}%", imp * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("Future Value"),
+            Cell::new(format!("${:.0}", fut)),
+        ]);
+    }
+
+    // A call that loses now can still pay with what it wins later
+    if let Some((eq, _)) = equity {
+        let verdict = if ev(eq, pot, bet) >= 0.0 {
+            "CALL".green().bold().to_string()
+        } else if implied.is_some_and(|imp| eq >= imp) {
+            "CALL (on implied odds)".yellow().bold().to_string()
+        } else {
+            "FOLD".red().bold().to_string()
+        };
+        table.add_row(vec![Cell::new("Verdict"), Cell::new(verdict)]);
     }
 
     println!("{}", table);
     println!();
 }
 
+/// `gto odds --hand --villain`: hero's equity against the villain's hand or
+/// range, and a description of how it was estimated.
+fn odds_equity(
+    hand: &str,
+    villain: &str,
+    board: Option<&[crate::cards::Card]>,
+    sims: usize,
+) -> Result<(f64, Option<String>), String> {
+    use crate::cards::check_distinct;
+    use crate::equity::{equity_vs_hand_sampled, equity_vs_weighted_range_sampled, Sampling};
+    use crate::ranges::parse_weighted_range;
+
+    let hero = parse_hole_cards(hand)?;
+    let board = board.unwrap_or_default();
+    let result = if is_exact_hand(&normalize_cards(villain).unwrap_or_default()) {
+        let opponent = parse_hole_cards(villain)?;
+        check_distinct(&[&hero[..], &opponent, board].concat()).map_err(|e| e.to_string())?;
+        equity_vs_hand_sampled(&hero, &opponent, Some(board), &[], sims, Sampling::default())
+    } else {
+        check_distinct(&[&hero[..], board].concat()).map_err(|e| e.to_string())?;
+        let range = parse_weighted_range(villain).map_err(|e| e.to_string())?;
+        equity_vs_weighted_range_sampled(&hero, &range, Some(board), &[], sims, Sampling::default())
+    }
+    .map_err(|e| e.to_string())?;
+
+    let on_board = if board.is_empty() { String::new() } else { format!(" on {}", board_display(board)) };
+    let method = if result.exact {
+        format!("{} runouts, exact", result.simulations)
+    } else {
+        format!("{} sims, ±{:.1}%", result.simulations, result.ci_half_width() * 100.0)
+    };
+    Ok((result.equity(), Some(format!("{} vs {}{} ({})", hand, villain, on_board, method))))
+}
+
 fn cmd_board(cards: String) {
-    use crate::postflop::{analyze_board, cbet_recommendation};
+    use crate::postflop::{analyze_board, board_transitions, cbet_recommendation};
 
     let board_cards = match parse_board(&cards) {
         Ok(c) => c,
@@ -1207,6 +3146,18 @@ fn cmd_board(cards: String) {
             Cell::new(texture.draws.join(", ")),
         ]);
     }
+    if board_cards.len() > 3 {
+        if let Ok(flop) = analyze_board(&board_cards[..3]) {
+            table.add_row(vec![Cell::new("Flop".bold().to_string()), Cell::new(&flop.category)]);
+        }
+        for transition in board_transitions(&board_cards) {
+            let kinds: Vec<String> = transition.kinds.iter().map(|k| k.to_string()).collect();
+            table.add_row(vec![
+                Cell::new(if transition.street == "turn" { "Turn" } else { "River" }.bold().to_string()),
+                Cell::new(format!("{} {}", transition.card.pretty(), kinds.join(", "))),
+            ]);
+        }
+    }
     println!("{}", table);
     println!();
 
@@ -1236,9 +3187,10 @@ fn cmd_action_preflop(
     table_size: &str,
     stack_bb: f64,
     rake: f64,
+    sizing: &PreflopSizingArgs,
 ) {
     use crate::game_tree::hand_to_bucket;
-    use crate::preflop_solver::{Position, PreflopSolution};
+    use crate::preflop_solver::{evs_over_fold, Position};
 
     let position = match validate_position(&position, table_size) {
         Ok(p) => p,
@@ -1256,13 +3208,17 @@ fn cmd_action_preflop(
         }
     };
 
-    let solution = match PreflopSolution::load(table_size, stack_bb, rake) {
+    let sizing = match sizing.build(stack_bb) {
         Ok(s) => s,
-        Err(_) => {
-            print_error(&format!(
-                "No cached solution for {}bb. Run 'gto solve preflop --stack {}' first.",
-                stack_bb, stack_bb,
-            ));
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    };
+    let solution = match load_preflop_solution(table_size, stack_bb, rake, &sizing) {
+        Ok(s) => s,
+        Err(e) => {
+            print_error(&e);
             return;
         }
     };
@@ -1277,11 +3233,12 @@ fn cmd_action_preflop(
 
     println!();
     println!(
-        "  {} {} in {} | {}bb",
+        "  {} {} in {} | {}bb | {}",
         "GTO".bold(),
         hand.bold(),
         position.bold(),
         stack_bb,
+        solution.sizing.label(),
     );
 
     match vs {
@@ -1294,7 +3251,12 @@ fn cmd_action_preflop(
                     let fold_freq = 1.0 - open_freq;
 
                     println!();
-                    print_action_freqs(&[("RAISE 2.5bb", open_freq), ("FOLD", fold_freq)]);
+                    let raise = format!("RAISE {}bb", solution.sizing.open_size_bb);
+                    print_action_freqs(&[(raise.as_str(), open_freq), ("FOLD", fold_freq)]);
+                    print_preflop_evs(spot.evs.as_ref().map(|evs| {
+                        let [open, fold] = evs_over_fold(evs.open[bucket]);
+                        vec![(raise.as_str(), open), ("FOLD", fold)]
+                    }));
 
                     // Show vs each responder if we're opening
                     if open_freq > 0.1 {
@@ -1349,6 +3311,10 @@ fn cmd_action_preflop(
                         println!("  vs {} open", vs_str.bold());
                         println!();
                         print_action_freqs(&[("3-BET", threebet), ("CALL", call), ("FOLD", fold)]);
+                        print_preflop_evs(spot.evs.as_ref().map(|evs| {
+                            let [threebet, call, fold] = evs_over_fold(evs.vs_open[bucket]);
+                            vec![("3-BET", threebet), ("CALL", call), ("FOLD", fold)]
+                        }));
 
                         if threebet > 0.1 {
                             let allin = spot.vs_4bet_allin[bucket];
@@ -1379,6 +3345,10 @@ fn cmd_action_preflop(
                         println!("  vs {} 3-bet", vs_str.bold());
                         println!();
                         print_action_freqs(&[("4-BET", fourbet), ("CALL", call3), ("FOLD", fold3)]);
+                        print_preflop_evs(spot.evs.as_ref().map(|evs| {
+                            let [fourbet, call, fold] = evs_over_fold(evs.vs_3bet[bucket]);
+                            vec![("4-BET", fourbet), ("CALL", call), ("FOLD", fold)]
+                        }));
 
                         if fourbet > 0.1 {
                             let call5 = spot.vs_5bet_call[bucket];
@@ -1401,6 +3371,42 @@ fn cmd_action_preflop(
     }
 }
 
+/// The cached preflop solution for this table, stack, rake and sizing, or
+/// the message to print: how to solve it, or which sizing the cached file
+/// was solved for.
+fn load_preflop_solution(
+    table_size: &str,
+    stack_bb: f64,
+    rake_pct: f64,
+    sizing: &crate::preflop_solver::PreflopSizing,
+) -> Result<crate::preflop_solver::PreflopSolution, String> {
+    crate::preflop_solver::PreflopSolution::load(table_size, stack_bb, rake_pct, sizing).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::InvalidData {
+            return e.to_string();
+        }
+        let default = crate::preflop_solver::PreflopSizing::default();
+        let sizing_flags = if *sizing == default {
+            String::new()
+        } else {
+            format!(
+                " --open-size {} --threebet-mult {} --fourbet-mult {}",
+                sizing.open_size_bb, sizing.three_bet_multiplier, sizing.four_bet_multiplier
+            )
+        };
+        format!(
+            "No cached solution found for {} {}bb {}% rake ({}). Run 'gto solve preflop --stack {} --rake {}{}' first.",
+            table_size,
+            stack_bb,
+            rake_pct,
+            sizing.label(),
+            stack_bb,
+            rake_pct,
+            sizing_flags,
+        )
+    })
+    .map_err(io_error)
+}
+
 /// Preflop open order (who RFIs first). Lower = opens first.
 fn preflop_open_order(pos: crate::preflop_solver::Position) -> usize {
     use crate::preflop_solver::Position;
@@ -1432,6 +3438,18 @@ fn print_action_freqs(actions: &[(&str, f64)]) {
     }
 }
 
+/// The EV line under preflop frequencies, or a note when the cached solve
+/// predates stored EVs.
+fn print_preflop_evs(actions: Option<Vec<(&str, f64)>>) {
+    match actions {
+        Some(actions) => println!("  EV:     {}", crate::strategy::format_preflop_evs(&actions)),
+        None => println!(
+            "  {}",
+            "EVs aren't stored in this cached solve; re-run `gto solve preflop` to add them".dimmed()
+        ),
+    }
+}
+
 fn cmd_action(
     hand: String,
     position: String,
@@ -1566,6 +3584,128 @@ fn cmd_mdf(pot: f64, bet: f64, players: usize) {
     println!();
 }
 
+/// Solved cache key for `gto mdf --board`: (board, oop_pos, ip_pos, stack).
+type SolvedSpot = (String, String, String, f64);
+
+fn cmd_browse(board: String, oop_pos: Option<String>, ip_pos: Option<String>, pot: f64, stack: f64) {
+    let board = match crate::cards::normalize_cards(&board) {
+        Ok(b) => b,
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+    let (oop, ip) = (oop_pos.unwrap_or_default().to_uppercase(), ip_pos.unwrap_or_default().to_uppercase());
+    let Some(solution) = crate::flop_solver::FlopSolution::load_cache(&board, &oop, &ip, pot, stack, None) else {
+        let seats = if oop.is_empty() { String::new() } else { format!(" {} vs {}", oop, ip) };
+        print_failure(ExitCode::CacheIo, &format!(
+            "No cached flop solve for {}{} (pot {:.0}, stack {:.0}) — run `gto solve flop` first",
+            board, seats, pot, stack
+        ));
+        return;
+    };
+    let seats = if oop.is_empty() { "OOP vs IP".to_string() } else { format!("{} vs {}", oop, ip) };
+    let title = format!("{} — {}, pot {:.1}, stack {:.1}", board, seats, pot, stack);
+    if let Err(e) = crate::browse::run(&solution, &title) {
+        print_failure(ExitCode::CacheIo, &e);
+    }
+}
+
+fn cmd_mdf_vs_raise(pot: f64, bet: f64, raise: f64, solved: Option<SolvedSpot>) {
+    use crate::flop_solver::FlopSolution;
+    use crate::math_engine::mdf_vs_raise;
+    use crate::strategy::flop_line_frequencies;
+
+    println!();
+    let defense = match mdf_vs_raise(pot, bet, raise) {
+        Ok(d) => d,
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+    println!(
+        "  {} bet {:.1} into {:.1}, raised to {:.1}",
+        "Facing raise:".bold(),
+        bet,
+        pot,
+        raise
+    );
+    println!(
+        "  Call {:.1} to win {:.1} — pot odds {:.1}%",
+        defense.to_call,
+        pot + bet + raise,
+        defense.pot_odds * 100.0
+    );
+    println!("  {} {:.1}%", "MDF:".bold(), defense.mdf * 100.0);
+    println!(
+        "  Continue with at least {:.1}% of your betting range so the raiser",
+        defense.mdf * 100.0
+    );
+    println!("  can't profit raising any two cards.");
+
+    if let Some((board, oop, ip, stack)) = solved {
+        let board = match crate::cards::normalize_cards(&board) {
+            Ok(b) => b,
+            Err(e) => {
+                print_error(&e.to_string());
+                println!();
+                return;
+            }
+        };
+        let Some(solution) = FlopSolution::load_cache(&board, &oop, &ip, pot, stack, None) else {
+            print_failure(ExitCode::CacheIo, &format!(
+                "No cached flop solve for {} {} vs {} (pot {:.0}, stack {:.0}) — run `gto solve flop` first",
+                board, oop, ip, pot, stack
+            ));
+            println!();
+            return;
+        };
+        // Bet in percent of the pot; raise beyond the call in percent of the
+        // pot after calling, as the tree sizes them
+        let line = [
+            "check".to_string(),
+            format!("bet{:.0}", bet / pot * 100.0),
+            format!("raise{:.0}", (raise - bet) / (pot + 2.0 * bet) * 100.0),
+        ];
+        match flop_line_frequencies(&solution, &line) {
+            Ok(node) => {
+                let fold: f64 = node
+                    .actions
+                    .iter()
+                    .zip(&node.frequencies)
+                    .filter(|(a, _)| a.starts_with("Fold"))
+                    .map(|(_, f)| f)
+                    .sum();
+                let cont = 1.0 - fold;
+                println!();
+                println!(
+                    "  {} {} ({} to act)",
+                    "Solver line:".bold(),
+                    node.line.join(" → "),
+                    node.player
+                );
+                for (action, freq) in node.actions.iter().zip(&node.frequencies) {
+                    println!("    {:<12} {:>5.1}%", action, freq * 100.0);
+                }
+                let verdict = if cont + 0.005 < defense.mdf {
+                    "below MDF".yellow()
+                } else {
+                    "at or above MDF".green()
+                };
+                println!(
+                    "  Solver continues {:.1}% vs MDF {:.1}% ({})",
+                    cont * 100.0,
+                    defense.mdf * 100.0,
+                    verdict
+                );
+            }
+            Err(e) => print_error(&e),
+        }
+    }
+    println!();
+}
+
 fn cmd_spr(stack_size: f64, pot_size: f64) {
     use crate::math_engine::spr;
 
@@ -1603,9 +3743,26 @@ fn cmd_spr(stack_size: f64, pot_size: f64) {
 }
 
 fn cmd_combos(range_str: String) {
-    use crate::ranges::{combo_count, parse_range, range_pct, total_combos};
-
-    let hands = parse_range(&range_str);
+    use crate::ranges::{combo_count, parse_weighted_range, weighted_total_combos};
+
+    let range = match parse_weighted_range(&range_str) {
+        Ok(r) => r,
+        Err(crate::error::GtoError::InvalidRange(rejected)) => {
+            print_error(&format!("Rejected {} range term{}:", rejected.len(), if rejected.len() == 1 { "" } else { "s" }));
+            for term in &rejected {
+                eprintln!("    {}", term);
+            }
+            return;
+        }
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+    let hands: Vec<String> = range.iter().map(|(h, _)| h.clone()).collect();
+    // With frequencies, combos count fractionally: AKo:0.5 is 6 combos
+    let weighted = range.iter().any(|&(_, w)| w < 1.0);
+    let count = |n: f64| if weighted { format!("{:.2}", n) } else { format!("{}", n) };
 
     println!();
     let mut table = Table::new();
@@ -1615,20 +3772,21 @@ fn cmd_combos(range_str: String) {
         Cell::new("Combos").set_alignment(CellAlignment::Right),
     ]);
 
-    for h in &hands {
+    for (h, w) in &range {
+        let label = if *w < 1.0 { format!("{} ({})", h, w) } else { h.clone() };
         table.add_row(vec![
-            Cell::new(h.bold().to_string()),
-            Cell::new(format!("{}", combo_count(h))),
+            Cell::new(label.bold().to_string()),
+            Cell::new(count(combo_count(h) as f64 * w)),
         ]);
     }
 
-    let total = total_combos(&hands);
-    let pct = range_pct(&hands);
+    let total = weighted_total_combos(&range);
+    let pct = total / 1326.0 * 100.0;
 
     // Add separator and totals
     table.add_row(vec![
         Cell::new("Total".bold().to_string()),
-        Cell::new(format!("{}", total).bold().to_string()),
+        Cell::new(count(total).bold().to_string()),
     ]);
     table.add_row(vec![
         Cell::new("% of hands".bold().to_string()),
@@ -1694,6 +3852,59 @@ fn cmd_bluff(pot: f64, bet: f64) {
     println!();
 }
 
+fn cmd_geometric(pot: f64, stack: f64, streets: usize) {
+    use crate::math_engine::geometric_sizing;
+
+    let plan = match geometric_sizing(pot, stack, streets) {
+        Ok(p) => p,
+        Err(e) => {
+            print_error(&e.to_string());
+            return;
+        }
+    };
+
+    println!();
+    println!(
+        "  {} {:.0}% pot on each of {} street{}",
+        "Geometric sizing:".bold(),
+        plan.fraction * 100.0,
+        streets,
+        if streets == 1 { "" } else { "s" }
+    );
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Street"),
+        Cell::new("Pot"),
+        Cell::new("Bet"),
+        Cell::new("Pot %"),
+        Cell::new("Stack After"),
+        Cell::new("Villain MDF"),
+        Cell::new("Break-Even Fold"),
+    ]);
+    let names = ["Flop", "Turn", "River"];
+    for (name, street) in names[names.len() - streets..].iter().zip(&plan.streets) {
+        table.add_row(vec![
+            Cell::new(name.bold().to_string()),
+            Cell::new(format!("{:.1}bb", street.pot)),
+            Cell::new(format!("{:.1}bb", street.bet)),
+            Cell::new(format!("{:.0}%", street.bet / street.pot * 100.0)),
+            Cell::new(format!("{:.1}bb", street.stack_after)),
+            Cell::new(format!("{:.1}%", street.mdf * 100.0)),
+            Cell::new(format!("{:.1}%", street.break_even_fold * 100.0)),
+        ]);
+    }
+    println!("{}", table);
+
+    println!("\n  Total risk: {}", format!("{:.1}bb", plan.total_risk).bold());
+    println!(
+        "  Villain defending exactly MDF each street folds {} of the time by the river.",
+        format!("{:.1}%", plan.cumulative_fold * 100.0).bold()
+    );
+    println!();
+}
+
+#[allow(clippy::too_many_arguments)]
 fn cmd_query(
     hand: String,
     position: String,
@@ -1701,12 +3912,60 @@ fn cmd_query(
     board: Option<String>,
     pot: Option<f64>,
     stack: f64,
-    iterations: usize,
+    pot_type: Option<String>,
+    iterations: Option<usize>,
+    time: Option<std::time::Duration>,
+    villain_range: Option<String>,
+    hero_range: Option<String>,
+    force: bool,
+    min_grade: Option<String>,
+    line: Option<String>,
+    refine: bool,
+    dead: Option<String>,
+    river_card: Option<String>,
+    drill: Option<String>,
+    show_villain: bool,
+    simplify: SimplifyArgs,
+    sizing: &PreflopSizingArgs,
+    forced_bets: ForcedBetArgs,
 ) {
+    use crate::cards::{check_dead_cards, parse_card};
     use crate::preflop_solver::Position;
     use crate::strategy::{
-        default_villain, detect_street, format_strategy, pretty_board, pretty_hand,
-        PotType, StrategyEngine, StrategySource,
+        check_dealt_cards, default_villain, detect_street, format_ev_bars, format_evs, format_recommendation,
+        format_showdown, format_strategy, pretty_board, pretty_hand, PostflopSpot, PotType, StrategyEngine,
+        StrategySource,
+    };
+
+    // Accept pasted output like "A♥K♠" / "K♠ 9♦ 4♣"; leave anything else as typed
+    let hand = normalize_cards(&hand).unwrap_or(hand);
+    let board = board.map(|b| normalize_cards(&b).unwrap_or(b));
+    if let Some(board) = &board {
+        if let Err(ref e) = check_dealt_cards(&hand, board) {
+            print_error(e);
+            return;
+        }
+    }
+    let dead_cards = match dead.as_deref().map(crate::cards::parse_cards).transpose() {
+        Ok(cards) => cards.unwrap_or_default(),
+        Err(e) => {
+            print_error(&format!("Invalid --dead cards: {}", e));
+            return;
+        }
+    };
+    if let Some(board) = &board {
+        let hole = parse_board(&hand).unwrap_or_default();
+        if let Err(e) = check_dead_cards(&dead_cards, &[&hole], &parse_board(board).unwrap_or_default()) {
+            print_error(&e.to_string());
+            return;
+        }
+    }
+    let river_card = match river_card.as_deref().map(parse_card).transpose() {
+        Ok(card) => card,
+        Err(e) => {
+            print_error(&format!("Invalid --river-card: {}", e));
+            return;
+        }
     };
 
     let hero = match Position::from_str(&position) {
@@ -1731,7 +3990,72 @@ fn cmd_query(
         None => default_villain(hero),
     };
 
-    let mut engine = StrategyEngine::new(stack);
+    if (villain_range.is_some() || hero_range.is_some()) && board.is_none() {
+        print_error("--villain-range and --hero-range only apply to postflop queries (pass a board)");
+        return;
+    }
+    let min_grade = match parse_grade(min_grade.as_deref()) {
+        Ok(g) => g,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let pot_type = match parse_pot_type_arg(pot_type.as_deref()) {
+        Ok(t) => t,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let simplify = match simplify.build() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    if simplify.is_some() && board.is_none() {
+        print_error("--simplify and --purify only apply to postflop queries (pass a board)");
+        return;
+    }
+    let forced = match forced_bets.build(stack) {
+        Ok(f) => f,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let sizing = match sizing.build_with(stack, &forced) {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let forced_label = match forced.label() {
+        label if label.is_empty() => String::new(),
+        label => format!("  |  {}", label),
+    };
+
+    let mut engine = StrategyEngine::new(stack)
+        .with_villain_range(villain_range.clone())
+        .with_hero_range(hero_range.clone())
+        .with_force(force)
+        .with_min_grade(min_grade)
+        .with_refine(refine)
+        .with_simplify(simplify)
+        .with_dead_cards(dead_cards.clone())
+        .with_river_card(river_card)
+        .with_drill(drill.as_deref().map(split_line))
+        .with_show_villain(show_villain)
+        .with_time_budget(match iterations {
+            Some(_) => None,
+            None => Some(time.unwrap_or(crate::strategy::DEFAULT_SOLVE_TIME)),
+        })
+        .with_preflop_game(&sizing, forced);
+    // Without --iterations the time budget sets the iterations
+    let iterations = iterations.unwrap_or_default();
 
     let hero_side = if hero.is_ip_vs(&villain) { "IP" } else { "OOP" };
     let villain_str = villain.as_str();
@@ -1740,9 +4064,22 @@ fn cmd_query(
         None => {
             // Preflop query
             if !engine.has_preflop() {
+                let mut flags = String::new();
+                if sizing != crate::preflop_solver::PreflopSizing::default_for(&forced) {
+                    flags.push_str(&format!(
+                        " --open-size {} --threebet-mult {} --fourbet-mult {}",
+                        sizing.open_size_bb, sizing.three_bet_multiplier, sizing.four_bet_multiplier
+                    ));
+                }
+                if forced.ante_bb > 0.0 {
+                    flags.push_str(&format!(" --ante {}", forced.ante_bb));
+                }
+                if forced.is_straddled() {
+                    flags.push_str(&format!(" --straddle {}", forced.straddle_bb));
+                }
                 print_error(&format!(
-                    "No preflop solution found. Run `gto solve preflop --stack {}` first.",
-                    stack
+                    "No preflop solution found. Run `gto solve preflop --stack {}{}` first.",
+                    stack, flags
                 ));
                 return;
             }
@@ -1752,7 +4089,7 @@ fn cmd_query(
                 Some(result) => {
                     println!();
                     println!(
-                        "  {}  {}  {}{}  |  Preflop",
+                        "  {}  {}  {}{}  |  Preflop{}",
                         "GTO".bold(),
                         pretty_hand(&hand).bold(),
                         position.bold(),
@@ -1761,9 +4098,13 @@ fn cmd_query(
                         } else {
                             String::new()
                         },
+                        forced_label,
                     );
                     println!();
                     println!("  {}", format_strategy(&result));
+                    print_preflop_evs(result.evs.as_ref().map(|evs| {
+                        result.actions.iter().map(String::as_str).zip(evs.iter().copied()).collect()
+                    }));
                     println!();
                 }
                 None => {
@@ -1776,14 +4117,15 @@ fn cmd_query(
             let street = detect_street(board_str);
 
             // Auto-derive pot/stack if not specified
-            let (pot_val, stack_val) = match pot {
-                Some(p) => (p, stack),
-                None => PotType::Srp.pot_and_stack(),
+            let (pot_val, stack_val) = match (pot, pot_type) {
+                (Some(p), _) => (p, stack),
+                (None, Some(t)) => t.pot_and_stack_with(stack, &forced, (hero, villain)),
+                (None, None) => PotType::Srp.pot_and_stack_with(100.0, &forced, (hero, villain)),
             };
 
             println!();
             println!(
-                "  {}  {}  {} vs {}  |  Board: {}  |  {}  |  {}",
+                "  {}  {}  {} vs {}  |  Board: {}  |  {}  |  {}{}",
                 "GTO".bold(),
                 pretty_hand(&hand).bold(),
                 position.bold(),
@@ -1791,11 +4133,47 @@ fn cmd_query(
                 pretty_board(board_str),
                 street,
                 hero_side,
+                forced_label,
             );
+            let spot = match pot_type {
+                Some(t) => crate::notes::SpotDescriptor::new(t, hero, villain, stack, board_str, None).ok(),
+                None => crate::notes::spot_for(pot_val, hero, villain, stack, board_str),
+            };
+            if let Some(spot) = spot {
+                for line in crate::notes::notes_banner(&spot) {
+                    println!("  {}", line);
+                }
+            }
+            // Why turn and river frequencies shift from the street before
+            let transition = parse_board(board_str).ok().and_then(|b| crate::postflop::board_transitions(&b).pop());
+            if let Some(transition) = transition {
+                println!("  {}", format!("{}: {}", transition.card.pretty(), transition).dimmed());
+            }
+            if !dead_cards.is_empty() {
+                println!("  {}", format!("dead: {}", board_display(&dead_cards)).dimmed());
+            }
+            if engine.is_exploitative() {
+                for (who, range) in [("hero", &hero_range), ("villain", &villain_range)] {
+                    if let Some(range) = range {
+                        println!("  {}  {} range: {}", "EXPLOIT".yellow().bold(), who, range);
+                    }
+                }
+            }
+            if let Some(flop_line) = &drill {
+                println!("  {}", format!("drill: flop {}", split_line(flop_line).join(" → ")).dimmed());
+            }
+            let action_path: Vec<String> = line.as_deref().map(split_line).unwrap_or_default();
+            if !action_path.is_empty() {
+                println!("  {}", format!("line: {}", action_path.join(" → ")).dimmed());
+            }
+            if let Some(card) = &river_card {
+                println!("  {}", format!("river: {}", card.pretty()).dimmed());
+            }
 
-            match engine.query_postflop(
-                &hand, hero, villain, board_str, pot_val, stack_val, iterations, &[],
-            ) {
+            let spot = PostflopSpot::new(hero, villain, board_str, pot_val, stack_val)
+                .with_pot_type(pot_type.unwrap_or(PotType::Srp))
+                .with_line(&action_path);
+            match engine.query_postflop(&hand, &spot, iterations) {
                 Ok(result) => {
                     if result.source == StrategySource::NotInRange {
                         println!();
@@ -1803,6 +4181,37 @@ fn cmd_query(
                     } else {
                         println!();
                         println!("  {}", format_strategy(&result));
+                        if hand.len() < 4 {
+                            println!("  {}", format!("averaged over {}'s combos in range", hand).dimmed());
+                        }
+                        if let Some(outcome) = result.showdown {
+                            println!("  {}", format_showdown(outcome));
+                        }
+                        if let Some(best) = format_recommendation(&result) {
+                            println!("  {}", best.bold());
+                            for bar in format_ev_bars(&result) {
+                                println!("    {}", bar);
+                            }
+                        }
+                        if let Some(evs) = format_evs(&result) {
+                            println!("  {}", evs.dimmed());
+                        }
+                        if let Some(q) = &result.quality {
+                            println!("  {}", format!("confidence: {}", q.describe()).dimmed());
+                        }
+                        if let Some(simplified) = &result.simplified {
+                            println!("  {}", format!("Strategy {}", simplified.describe()).yellow());
+                        }
+                        if show_villain {
+                            let origin = match &villain_range {
+                                Some(_) => "--villain-range".to_string(),
+                                None => format!("{}'s range", villain_str),
+                            };
+                            match &result.villain {
+                                Some(view) => print_villain_view(view, &origin),
+                                None => println!("  {}", "--show-villain covers flop boards".dimmed()),
+                            }
+                        }
                     }
                     println!();
                 }
@@ -1815,70 +4224,76 @@ fn cmd_query(
     }
 }
 
-/// Convert specific cards "AhKs" to canonical notation "AKo" for preflop lookup.
-fn hand_to_canonical(hand: &str) -> String {
-    if hand.len() != 4 {
-        return hand.to_string();
+/// `gto query --show-villain`: villain's range (`origin` says where it came
+/// from) and its answer to each of hero's options.
+fn print_villain_view(view: &crate::flop_solver::VillainView, origin: &str) {
+    println!();
+    println!("  {} ({}, {}): {}", "Villain".bold(), view.side, origin, view.range);
+    println!(
+        "  {}",
+        format!("{:.0} combos on this board, {:.1} reach this spot past your blockers", view.combos, view.reaching)
+            .dimmed()
+    );
+    if view.responses.is_empty() {
+        println!("  {}", "None of your options leave villain a decision here".dimmed());
     }
-    let chars: Vec<char> = hand.chars().collect();
-    let r1 = chars[0];
-    let s1 = chars[1];
-    let r2 = chars[2];
-    let s2 = chars[3];
-
-    if r1 == r2 {
-        format!("{}{}", r1, r2)
-    } else if s1 == s2 {
-        // Suited — put higher rank first
-        let (h, l) = if rank_value(r1) >= rank_value(r2) {
-            (r1, r2)
-        } else {
-            (r2, r1)
-        };
-        format!("{}{}s", h, l)
-    } else {
-        // Offsuit — put higher rank first
-        let (h, l) = if rank_value(r1) >= rank_value(r2) {
-            (r1, r2)
-        } else {
-            (r2, r1)
-        };
-        format!("{}{}o", h, l)
+    for response in &view.responses {
+        let parts: Vec<String> = response
+            .actions
+            .iter()
+            .zip(&response.frequencies)
+            .map(|(action, f)| format!("{} {:.0}%", action.to_lowercase(), f * 100.0))
+            .collect();
+        println!("    vs your {}: {}", response.hero_action.to_lowercase(), parts.join(", "));
     }
 }
 
-fn rank_value(c: char) -> u8 {
-    match c {
-        '2' => 2, '3' => 3, '4' => 4, '5' => 5, '6' => 6, '7' => 7, '8' => 8,
-        '9' => 9, 'T' => 10, 'J' => 11, 'Q' => 12, 'K' => 13, 'A' => 14,
-        _ => 0,
-    }
+/// A comma-separated action line ("check, bet33") as its steps.
+fn split_line(line: &str) -> Vec<String> {
+    line.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect()
 }
 
-fn cmd_solve_pushfold(stack: f64, rake: f64, iterations: usize) {
-    use crate::game_tree::solve_push_fold;
+/// The board set named by `--boardset`, if given.
+fn resolve_boardset_arg(name: Option<String>) -> Result<Option<crate::config::BoardSet>, String> {
+    name.map(|n| crate::config::resolve_boardset(&n)).transpose()
+}
 
-    if stack <= 0.0 {
-        print_error("Stack must be positive");
-        return;
-    }
-    if rake < 0.0 || rake > 100.0 {
-        print_error("Rake must be between 0 and 100");
-        return;
+/// Boards from `--board` or `--boardset` (clap requires exactly one).
+fn boards_arg(board: Option<String>, boardset: Option<String>) -> Result<Vec<String>, String> {
+    match resolve_boardset_arg(boardset)? {
+        Some(set) => Ok(set.boards),
+        None => Ok(board.into_iter().collect()),
     }
+}
 
-    println!();
-    println!(
-        "  Solving push/fold for {}bb stack, {}% rake, {} iterations...",
-        stack, rake, iterations
-    );
+/// Parse an optional `--min-grade`/`--below` value.
+fn parse_grade(grade: Option<&str>) -> Result<Option<crate::quality::Grade>, String> {
+    grade
+        .map(|g| crate::quality::Grade::parse(g).ok_or_else(|| format!("Invalid grade '{}'. Valid: A, B, C, D", g)))
+        .transpose()
+}
 
-    let result = solve_push_fold(stack, iterations, rake);
-    result.display();
+/// Parse an optional cache `--type` value.
+fn parse_cache_kind(kind: Option<&str>) -> Result<Option<crate::cache::CacheKind>, String> {
+    kind.map(|k| {
+        crate::cache::CacheKind::parse(k)
+            .ok_or_else(|| format!("Invalid cache type '{}'. Valid: preflop, pushfold, flop, turn, river", k))
+    })
+    .transpose()
 }
 
-fn cmd_solve_preflop(table_size: TableSize, stack: f64, rake: f64, iterations: usize) {
-    use crate::preflop_solver::solve_preflop_6max;
+/// Parse an optional `--pot-type` value.
+fn parse_pot_type_arg(pot_type: Option<&str>) -> Result<Option<crate::strategy::PotType>, String> {
+    pot_type
+        .map(|t| {
+            crate::strategy::PotType::parse(t).ok_or_else(|| format!("Invalid pot type '{}'. Valid: srp, 3bet, 4bet", t))
+        })
+        .transpose()
+}
+
+fn cmd_solve_pushfold(stack: f64, rake: f64, position: Option<String>, ante: f64, iterations: usize, force: bool) {
+    use crate::game_tree::{solve_multiway_push_fold, solve_push_fold, MultiwayPushFold};
+    use crate::preflop_solver::Position;
 
     if stack <= 0.0 {
         print_error("Stack must be positive");
@@ -1888,27 +4303,140 @@ fn cmd_solve_preflop(table_size: TableSize, stack: f64, rake: f64, iterations: u
         print_error("Rake must be between 0 and 100");
         return;
     }
-
-    match table_size {
-        TableSize::NineMax => {
-            print_error("Preflop solver currently only supports 6max");
-            return;
-        }
-        _ => {}
+    if ante < 0.0 || ante >= stack {
+        print_error("Ante must be at least 0 and below the stack");
+        return;
+    }
+    if !check_bounds(crate::limits::PUSHFOLD_ITERATIONS, iterations, force) {
+        return;
     }
 
-    println!();
+    // Heads-up SB vs BB without antes keeps the exact solver
+    if position.is_some() || ante > 0.0 {
+        let shover = match position.as_deref().map(Position::from_str) {
+            None => Position::SB,
+            Some(Some(p)) => p,
+            Some(None) => {
+                print_error(&format!("Invalid position: {}", position.unwrap_or_default()));
+                return;
+            }
+        };
+        let spot = match MultiwayPushFold::new(shover, stack, ante, rake) {
+            Ok(spot) => spot,
+            Err(e) => {
+                print_error(&e);
+                return;
+            }
+        };
+
+        println!();
+        println!(
+            "  Solving push/fold for {} shoving {}bb, {}bb ante, {}% rake, {} iterations...",
+            shover, stack, ante, rake, iterations
+        );
+
+        let result = solve_multiway_push_fold(&spot, iterations, 0);
+        result.display();
+        match result.save() {
+            Ok(()) => println!(
+                "  Solution saved to {}",
+                result.cache_path().display().to_string().dimmed()
+            ),
+            Err(e) => {
+                let msg = format!("Failed to save solution: {}", e);
+                print_failure(ExitCode::CacheIo, &msg);
+            }
+        }
+        println!();
+        return;
+    }
+
+    println!();
+    println!(
+        "  Solving push/fold for {}bb stack, {}% rake, {} iterations...",
+        stack, rake, iterations
+    );
+
+    let result = solve_push_fold(stack, iterations, rake);
+    result.display();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_solve_preflop(
+    table_size: TableSize,
+    stack: f64,
+    rake: f64,
+    iterations: usize,
+    target: Option<f64>,
+    force: bool,
+    sizing: &PreflopSizingArgs,
+    forced_bets: &ForcedBetArgs,
+    export: Option<String>,
+) {
+    use crate::preflop_solver::solve_preflop_6max;
+
+    if stack <= 0.0 {
+        print_error("Stack must be positive");
+        return;
+    }
+    if rake < 0.0 || rake > 100.0 {
+        print_error("Rake must be between 0 and 100");
+        return;
+    }
+    if target.is_some_and(|t| t.is_nan() || t <= 0.0) {
+        print_error("--target must be positive");
+        return;
+    }
+    if !check_bounds(crate::limits::PREFLOP_ITERATIONS, iterations, force) {
+        return;
+    }
+    let forced = match forced_bets.build(stack) {
+        Ok(f) => f,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    };
+    let sizing = match sizing.build_with(stack, &forced) {
+        Ok(s) => s,
+        Err(e) => {
+            print_error(&e);
+            return;
+        }
+    };
+
+    match table_size {
+        TableSize::NineMax => {
+            print_error("Preflop solver currently only supports 6max");
+            return;
+        }
+        _ => {}
+    }
+
+    let iterations_label = match target {
+        Some(t) => format!("to {} bb exploitability, at most {} iterations per spot", t, iterations),
+        None => format!("{} iterations", iterations),
+    };
+    let forced_label = match forced.label() {
+        label if label.is_empty() => String::new(),
+        label => format!(" | {}", label),
+    };
+    println!();
     println!(
-        "  {} Solving preflop for {} | {}bb stack | {}% rake | {} iterations",
+        "  {} Solving preflop for {} | {}bb stack | {}% rake{} | {} | {}",
         "GTO".bold(),
         table_size.as_str(),
         stack,
         rake,
-        iterations,
+        forced_label,
+        sizing.label(),
+        iterations_label,
     );
     println!();
 
-    let solution = solve_preflop_6max(stack, iterations, rake);
+    let started = std::time::Instant::now();
+    let solution = solve_preflop_6max(stack, iterations, target, rake, sizing, forced);
+    let elapsed = started.elapsed();
 
     // Display summary table
     println!();
@@ -1923,19 +4451,29 @@ fn cmd_solve_preflop(table_size: TableSize, stack: f64, rake: f64, iterations: u
         Cell::new("3-Bet %").set_alignment(CellAlignment::Right),
         Cell::new("Flat %").set_alignment(CellAlignment::Right),
         Cell::new("Exploit").set_alignment(CellAlignment::Right),
+        Cell::new("Iters").set_alignment(CellAlignment::Right),
     ]);
 
     for spot in &solution.spots {
+        // A spot that ran to the cap without reaching the target
+        let missed = target.is_some_and(|t| spot.exploitability > t);
+        let exploit = format!("{:.4}", spot.exploitability);
         table.add_row(vec![
             Cell::new(format!("{} vs {}", spot.opener, spot.responder)),
             Cell::new(format!("{:.1}", spot.open_pct())),
             Cell::new(format!("{:.1}", spot.three_bet_pct())),
             Cell::new(format!("{:.1}", spot.flat_call_pct())),
-            Cell::new(format!("{:.4}", spot.exploitability)),
+            Cell::new(if missed { exploit.yellow().to_string() } else { exploit }),
+            Cell::new(format!("{}", spot.iterations)),
         ]);
     }
 
     println!("{}", table);
+    println!(
+        "  Solved in {:.1}s, {} iterations in total",
+        elapsed.as_secs_f64(),
+        solution.spots.iter().map(|s| s.iterations).sum::<usize>()
+    );
 
     // Save to disk
     match solution.save() {
@@ -1951,14 +4489,103 @@ fn cmd_solve_preflop(table_size: TableSize, stack: f64, rake: f64, iterations: u
             );
         }
         Err(e) => {
-            print_error(&format!("Failed to save solution: {}", e));
+            let msg = format!("Failed to save solution: {}", e);
+            print_failure(ExitCode::CacheIo, &msg);
+        }
+    }
+    if let Some(path) = export {
+        match crate::preflop_json::write_export(&solution, std::path::Path::new(&path)) {
+            Ok(()) => println!("  Ranges exported to {}", path.dimmed()),
+            Err(ref e) => print_error(e),
+        }
+    }
+    println!();
+}
+
+fn cmd_import_preflop(file: String, stack: f64, rake: f64, force: bool) {
+    if stack <= 0.0 {
+        print_error("Stack must be positive");
+        return;
+    }
+    if rake < 0.0 || rake > 100.0 {
+        print_error("Rake must be between 0 and 100");
+        return;
+    }
+    let json = match std::fs::read_to_string(&file) {
+        Ok(json) => json,
+        Err(e) => {
+            print_failure(ExitCode::CacheIo, &format!("Cannot read {}: {}", file, e));
+            return;
+        }
+    };
+    let solution = match crate::preflop_json::import(&json, stack, rake) {
+        Ok(solution) => solution,
+        Err(ref e) => {
+            print_error(&format!("{}: {}", file, e));
+            return;
         }
+    };
+    let path = solution.cache_path();
+    if path.exists() && !force {
+        print_error(&format!(
+            "{} already exists; pass --force to replace it with the imported ranges",
+            path.display()
+        ));
+        return;
+    }
+    if let Err(e) = solution.save() {
+        let msg = format!("Failed to save solution: {}", e);
+        print_failure(ExitCode::CacheIo, &msg);
+        return;
+    }
+
+    println!();
+    println!(
+        "  {} Imported {} spots for {} | {}bb stack | {}% rake | {}",
+        "GTO".bold(),
+        solution.spots.len(),
+        solution.table_size,
+        stack,
+        rake,
+        solution.sizing.label(),
+    );
+    println!("  Saved to {}", path.display().to_string().dimmed());
+    if !solution.meets_quality_floor() {
+        println!(
+            "  {} {} iterations is below the floor; `gto query` won't use these ranges",
+            "Note:".yellow(),
+            solution.iterations
+        );
     }
+    println!("  Use {} to view them.", "gto range <POS> --solved --stack <BB>".bold());
     println!();
 }
 
-fn cmd_solve_river(board: String, oop: String, ip: String, pot: f64, stack: f64, iterations: usize) {
-    use crate::river_solver::{RiverSolverConfig, solve_river};
+#[allow(clippy::too_many_arguments)]
+fn cmd_solve_river(
+    board: String,
+    oop: String,
+    ip: String,
+    pot: f64,
+    stack: f64,
+    iterations: usize,
+    sizing: Option<(Vec<f64>, bool)>,
+    raises: RaiseArgs,
+    seats: Option<(String, String)>,
+    locks: Vec<String>,
+    weights_files: [Option<String>; 2],
+    cfr_variant: CfrVariantArg,
+    force: bool,
+    postprocess: PostProcessArgs,
+    simplify: SimplifyArgs,
+    output: SolutionOutputArgs,
+    target: TargetArgs,
+) {
+    use crate::postflop_tree::Player;
+    use crate::river_solver::{parse_combo_weights_csv, RiverSolverConfig, solve_river};
+    use crate::strategy::postflop_seats;
+
+    let board = normalize_cards(&board).unwrap_or(board);
 
     if pot <= 0.0 {
         print_error("Pot must be positive");
@@ -1968,28 +4595,148 @@ fn cmd_solve_river(board: String, oop: String, ip: String, pot: f64, stack: f64,
         print_error("Stack must be positive");
         return;
     }
+    let (iterations, target_pct) = match target.resolve(iterations, crate::limits::RIVER_ITERATIONS) {
+        Ok(t) => t,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    if !check_bounds(crate::limits::RIVER_ITERATIONS, iterations, force) {
+        return;
+    }
+    let seats = match seats.map(|(oop_pos, ip_pos)| postflop_seats(&oop_pos, &ip_pos)).transpose() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
 
-    let config = match RiverSolverConfig::new(&board, &oop, &ip, pot, stack, iterations) {
+    let mut config = match RiverSolverConfig::new(&board, &oop, &ip, pot, stack, iterations) {
         Ok(c) => c,
+        Err(ref e) => {
+            print_solver_error(e);
+            return;
+        }
+    };
+    let custom_sizing = sizing.is_some();
+    if let Some((bet_sizes, allin)) = sizing {
+        if bet_sizes.is_empty() || bet_sizes.iter().any(|&f| !(f > 0.0 && f.is_finite())) {
+            print_error("Bet sizes must be positive fractions of the pot");
+            return;
+        }
+        config.bet_sizes = bet_sizes;
+        config.add_allin = allin;
+    }
+    match raises.sizes() {
+        Ok(sizes) => config.raise_sizes = sizes.unwrap_or(config.raise_sizes),
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    }
+    config.max_raises = raises.max_raises.unwrap_or(config.max_raises);
+    config.max_tree_nodes = raises.max_nodes;
+    let custom_sizing = custom_sizing || raises.raise_sizes.is_some() || raises.max_raises.is_some();
+    let nodes = match config.validate_tree_size() {
+        Ok(n) => n,
+        Err(ref e) => {
+            print_solver_error(e);
+            return;
+        }
+    };
+    config.postprocess = match postprocess.build() {
+        Ok(p) => p,
         Err(ref e) => {
             print_error(e);
             return;
         }
     };
+    let simplify = match simplify.build() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    config.progress_interval = output.progress_interval(iterations, target.time.is_some());
+    config.on_progress = output.progress_callback();
+    config.time_budget = target.time;
+    config.target_exploitability = target_pct;
+    config.cfr_variant = cfr_variant.variant();
+    for spec in &locks {
+        if let Err(ref e) = config.add_lock(spec) {
+            print_error(e);
+            return;
+        }
+    }
+    let weighted = weights_files.iter().any(Option::is_some);
+    for (file, player) in weights_files.iter().zip([Player::OOP, Player::IP]) {
+        let Some(file) = file else { continue };
+        let weights = match std::fs::read_to_string(file) {
+            Ok(text) => parse_combo_weights_csv(&text, &config.combos(player)).map_err(|e| format!("{}: {}", file, e)),
+            Err(e) => Err(io_error(format!("Cannot read {}: {}", file, e))),
+        };
+        match weights {
+            Ok(w) if player == Player::OOP => config.oop_combo_weights = Some(w),
+            Ok(w) => config.ip_combo_weights = Some(w),
+            Err(ref e) => {
+                print_error(e);
+                return;
+            }
+        }
+    }
+    if let Err(ref e) = config.validate_combo_weights() {
+        print_solver_error(e);
+        return;
+    }
 
-    println!();
-    println!(
-        "  Solving river: board={}, pot={}, stack={}, {} iterations...",
-        board, pot, stack, iterations
-    );
+    if !output.json {
+        println!();
+        println!(
+            "  Solving river: board={}, pot={}, stack={}, {}...",
+            board, pot, stack, target.run_length(iterations)
+        );
+        println!("  {}", format!("Tree: {} decision nodes", nodes).dimmed());
+        for (weights, side) in [(&config.oop_combo_weights, "OOP"), (&config.ip_combo_weights, "IP")] {
+            if let Some(w) = weights {
+                let listed = w.iter().filter(|&&w| w > 0.0).count();
+                println!("  {}", format!("{} starts from file weights: {} of {} combos", side, listed, w.len()).dimmed());
+            }
+        }
+        // Locked, custom-sized and weighted solves are cached apart from equilibria; `gto query` doesn't use them
+        if locks.is_empty() && !custom_sizing && !weighted {
+            print_seats(seats, &board, pot, stack, None);
+        }
+    }
 
-    let result = solve_river(&config);
-    result.display();
+    let mut result = solve_river(&config);
+    crate::progress::end_progress_line(&result.convergence_history);
+    if let Some((oop_pos, ip_pos)) = seats {
+        result.oop_pos = oop_pos.as_str().to_string();
+        result.ip_pos = ip_pos.as_str().to_string();
+    }
+    let shown = simplify.map(|s| result.simplified(s));
+    output.emit(shown.as_ref().unwrap_or(&result), |r| {
+        r.display_with(output.verbose);
+        target.report(r.iterations, iterations, r.exploitability, r.starting_pot);
+    });
     result.save_cache();
 }
 
-fn cmd_solve_turn(board: String, oop: String, ip: String, pot: f64, stack: f64, iterations: usize) {
-    use crate::turn_solver::{TurnSolverConfig, solve_turn};
+fn cmd_solve_river_multiway(
+    board: String,
+    ranges: [String; 3],
+    pot: f64,
+    stack: f64,
+    iterations: usize,
+    force: bool,
+    output: SolutionOutputArgs,
+) {
+    use crate::multiway_solver::{MultiwaySolverConfig, solve_river_multiway};
+
+    let board = normalize_cards(&board).unwrap_or(board);
 
     if pot <= 0.0 {
         print_error("Pot must be positive");
@@ -1999,8 +4746,11 @@ fn cmd_solve_turn(board: String, oop: String, ip: String, pot: f64, stack: f64,
         print_error("Stack must be positive");
         return;
     }
-
-    let config = match TurnSolverConfig::new(&board, &oop, &ip, pot, stack, iterations) {
+    if !check_bounds(crate::limits::RIVER_ITERATIONS, iterations, force) {
+        return;
+    }
+    let [oop, mp, ip] = &ranges;
+    let config = match MultiwaySolverConfig::new(&board, [oop, mp, ip], pot, stack, iterations) {
         Ok(c) => c,
         Err(ref e) => {
             print_error(e);
@@ -2008,19 +4758,43 @@ fn cmd_solve_turn(board: String, oop: String, ip: String, pot: f64, stack: f64,
         }
     };
 
-    println!();
-    println!(
-        "  Solving turn: board={}, pot={}, stack={}, {} iterations...",
-        board, pot, stack, iterations
-    );
+    if !output.json {
+        println!();
+        println!(
+            "  Solving 3-way river: board={}, pot={}, stack={}, {} iterations...",
+            board, pot, stack, iterations
+        );
+    }
 
-    let result = solve_turn(&config);
-    result.display();
-    result.save_cache();
+    let result = solve_river_multiway(&config);
+    output.emit(&result, |r| r.display());
 }
 
-fn cmd_solve_flop(board: String, oop: String, ip: String, pot: f64, stack: f64, iterations: usize) {
-    use crate::flop_solver::{FlopSolverConfig, solve_flop};
+#[allow(clippy::too_many_arguments)]
+fn cmd_solve_turn(
+    board: String,
+    oop: String,
+    ip: String,
+    pot: f64,
+    stack: f64,
+    iterations: usize,
+    seats: Option<(String, String)>,
+    cfr_variant: CfrVariantArg,
+    force: bool,
+    river_card: Option<String>,
+    no_rivers: bool,
+    raises: RaiseArgs,
+    postprocess: PostProcessArgs,
+    simplify: SimplifyArgs,
+    output: SolutionOutputArgs,
+    target: TargetArgs,
+) {
+    use crate::cache_key::range_key;
+    use crate::cards::parse_card;
+    use crate::turn_solver::{TurnSolverConfig, TurnSolution, solve_turn};
+    use crate::strategy::postflop_seats;
+
+    let board = normalize_cards(&board).unwrap_or(board);
 
     if pot <= 0.0 {
         print_error("Pot must be positive");
@@ -2030,22 +4804,772 @@ fn cmd_solve_flop(board: String, oop: String, ip: String, pot: f64, stack: f64,
         print_error("Stack must be positive");
         return;
     }
+    let (iterations, target_pct) = match target.resolve(iterations, crate::limits::TURN_ITERATIONS) {
+        Ok(t) => t,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    if !check_bounds(crate::limits::TURN_ITERATIONS, iterations, force) {
+        return;
+    }
+    let seats = match seats.map(|(oop_pos, ip_pos)| postflop_seats(&oop_pos, &ip_pos)).transpose() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
 
-    let config = match FlopSolverConfig::new(&board, &oop, &ip, pot, stack, iterations) {
+    // "@flop:FLOP:LINE" takes a range from a cached flop solve
+    let (oop, ip) = match (resolve_range_arg(oop, &board), resolve_range_arg(ip, &board)) {
+        (Ok(oop), Ok(ip)) => (oop, ip),
+        (Err(ref e), _) | (_, Err(ref e)) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    let mut config = match TurnSolverConfig::new(&board, &oop, &ip, pot, stack, iterations) {
         Ok(c) => c,
+        Err(ref e) => {
+            print_solver_error(e);
+            return;
+        }
+    };
+    config.postprocess = match postprocess.build() {
+        Ok(p) => p,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let simplify = match simplify.build() {
+        Ok(s) => s,
         Err(ref e) => {
             print_error(e);
             return;
         }
     };
+    config.progress_interval = output.progress_interval(iterations, target.time.is_some());
+    config.on_progress = output.progress_callback();
+    config.time_budget = target.time;
+    config.target_exploitability = target_pct;
+    config.cfr_variant = cfr_variant.variant();
+    config.extract_rivers = !no_rivers;
+    match raises.sizes() {
+        Ok(Some(sizes)) => {
+            config.raise_sizes = sizes.clone();
+            config.river_raise_sizes = sizes;
+        }
+        Ok(None) => {}
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    }
+    if let Some(max) = raises.max_raises {
+        config.max_raises = max;
+        config.river_max_raises = max;
+    }
+    config.max_tree_nodes = raises.max_nodes;
+    let custom_raising = raises.raise_sizes.is_some() || raises.max_raises.is_some();
+    let nodes = match config.validate_tree_size() {
+        Ok(n) => n,
+        Err(ref e) => {
+            print_solver_error(e);
+            return;
+        }
+    };
+    let river_card = match river_card.as_deref().map(parse_card).transpose() {
+        Ok(Some(card)) if config.board.contains(&crate::card_encoding::card_to_index(&card)) => {
+            print_error(&format!("--river-card {} is already on the board", card));
+            return;
+        }
+        Ok(card) => card.map(|c| c.to_string()),
+        Err(e) => {
+            print_error(&format!("Invalid --river-card: {}", e));
+            return;
+        }
+    };
+    // River play is shown for one card at a time
+    let show = |r: &TurnSolution| {
+        r.display_with(output.verbose);
+        if let Some(card) = &river_card {
+            r.display_river(card, output.verbose);
+        }
+    };
+    let filtered = |r: &TurnSolution| -> TurnSolution {
+        let simplified = simplify.map(|s| r.simplified(s)).unwrap_or_else(|| r.clone());
+        match &river_card {
+            Some(card) => simplified.with_river_card(card),
+            None => simplified,
+        }
+    };
 
-    println!();
-    println!(
-        "  Solving flop: board={}, pot={}, stack={}, {} iterations...",
-        board, pot, stack, iterations
-    );
+    // The same spot and ranges already solved at least as far is shown as cached
+    if !force {
+        let (oop_pos, ip_pos) = seats.map(|(o, i)| (o.as_str(), i.as_str())).unwrap_or_default();
+        let key = range_key(&config.oop_range, &config.oop_weights, &config.ip_range, &config.ip_weights);
+        let raising = config.raise_suffix();
+        let cached = TurnSolution::load_cache_raised(&board, oop_pos, ip_pos, pot, stack, &raising, Some(&key)).filter(|s| {
+            let far_enough = match target_pct {
+                Some(pct) => s.exploitability_pct_pot <= pct,
+                None => s.iterations >= iterations,
+            };
+            // A solve without river play can't show a river card
+            let has_rivers = river_card.is_none() || !s.river_strategies.is_empty();
+            let same_tree = s.raise_sizes == config.raise_sizes
+                && s.max_raises == config.max_raises
+                && s.river_raise_sizes == config.river_raise_sizes
+                && s.river_max_raises == config.river_max_raises;
+            far_enough && has_rivers && same_tree && s.postprocess.is_some() == config.postprocess.is_some()
+        });
+        if let Some(cached) = cached {
+            output.emit(&filtered(&cached), |r| {
+                println!();
+                println!(
+                    "  {} ({} iterations); --force to re-solve",
+                    "Cached turn solve".dimmed(),
+                    r.iterations
+                );
+                show(r);
+            });
+            return;
+        }
+    }
 
-    let result = solve_flop(&config);
-    result.display();
+    if !output.json {
+        println!();
+        println!(
+            "  Solving turn: board={}, pot={}, stack={}, {}...",
+            board, pot, stack, target.run_length(iterations)
+        );
+        println!("  {}", format!("Tree: {} decision nodes", nodes).dimmed());
+        // Other raising is cached apart from the default trees `gto query` reads
+        if !custom_raising {
+            print_seats(seats, &board, pot, stack, None);
+        }
+    }
+
+    let mut result = solve_turn(&config);
+    crate::progress::end_progress_line(&result.convergence_history);
+    if let Some((oop_pos, ip_pos)) = seats {
+        result.oop_pos = oop_pos.as_str().to_string();
+        result.ip_pos = ip_pos.as_str().to_string();
+    }
+    output.emit(&filtered(&result), |r| {
+        show(r);
+        target.report(r.iterations, iterations, r.exploitability, r.starting_pot);
+    });
+    result.save_cache();
+}
+
+/// A turn range as given, or resolved from "@flop:FLOP:LINE".
+fn resolve_range_arg(range: String, board: &str) -> Result<String, String> {
+    if range.starts_with("@flop:") {
+        crate::flop_solver::resolve_flop_range_ref(&range, board)
+    } else {
+        Ok(range)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_solve_flop(
+    board: String,
+    oop: Option<String>,
+    ip: Option<String>,
+    pot: f64,
+    stack: Option<f64>,
+    pot_type: Option<String>,
+    iterations: usize,
+    seats: Option<(String, String)>,
+    from_preflop: Option<String>,
+    resume: bool,
+    checkpoint_every: Option<usize>,
+    seed: Option<u64>,
+    cfr_variant: CfrVariantArg,
+    bucketing: crate::bucketing::BucketingStrategy,
+    grid: bool,
+    export_range: Option<String>,
+    max_precompute: u64,
+    refine_combos: bool,
+    compare_sizes: Option<String>,
+    force: bool,
+    postprocess: PostProcessArgs,
+    simplify: SimplifyArgs,
+    output: SolutionOutputArgs,
+    target: TargetArgs,
+) {
+    use crate::flop_solver::{solve_flop_resumable, FlopCheckpoint, FlopSolverConfig};
+    use crate::strategy::{postflop_seats, StrategyEngine};
+
+    let board = normalize_cards(&board).unwrap_or(board);
+
+    let pot_type = match parse_pot_type_arg(pot_type.as_deref()) {
+        // A matchup is a single raised pot unless told otherwise
+        Ok(t) => t.or(from_preflop.is_some().then_some(crate::strategy::PotType::Srp)),
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    // With a pot type, --stack is the preflop depth and the spot follows from it
+    let depth = stack.unwrap_or(100.0);
+    let (pot, stack) = match pot_type {
+        Some(t) => t.pot_and_stack_at(depth),
+        None => (pot, stack.unwrap_or(50.0)),
+    };
+
+    if pot <= 0.0 {
+        print_error("Pot must be positive");
+        return;
+    }
+    if stack <= 0.0 {
+        print_error("Stack must be positive");
+        return;
+    }
+    let (iterations, target_pct) = match target.resolve(iterations, crate::limits::FLOP_ITERATIONS) {
+        Ok(t) => t,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    if !check_bounds(crate::limits::FLOP_ITERATIONS, iterations, force) {
+        return;
+    }
+    let compare_sizes = match compare_sizes.as_deref().map(crate::size_compare::parse_compare_sizes).transpose() {
+        Ok(c) => c,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let seats = match seats.map(|(oop_pos, ip_pos)| postflop_seats(&oop_pos, &ip_pos)).transpose() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    if pot_type.is_some() && seats.is_none() && from_preflop.is_none() {
+        print_error("--pot-type needs the seats: --oop-pos and --ip-pos, or --from-preflop");
+        return;
+    }
+
+    // Ranges not given come from the preflop solve for the pot type
+    let (oop, ip, seats) = match (from_preflop, pot_type) {
+        (Some(matchup), Some(t)) => match matchup_spot(&matchup, t, depth, oop, ip) {
+            Ok((oop, ip, seats)) => (oop, ip, Some(seats)),
+            Err(ref e) => {
+                print_error(e);
+                return;
+            }
+        },
+        _ => match (oop, ip, pot_type.zip(seats)) {
+            (Some(oop), Some(ip), _) => (oop, ip, seats),
+            (oop, ip, Some((t, (oop_seat, ip_seat)))) => {
+                match StrategyEngine::new(depth).derive_pot_type_ranges(oop_seat, ip_seat, t) {
+                    Ok((oop_range, ip_range)) => {
+                        (oop.unwrap_or(oop_range.join(",")), ip.unwrap_or(ip_range.join(",")), seats)
+                    }
+                    Err(ref e) => {
+                        print_error(e);
+                        return;
+                    }
+                }
+            }
+            (oop, ip, None) => (oop.unwrap_or_default(), ip.unwrap_or_default(), seats),
+        },
+    };
+
+    let mut config = match FlopSolverConfig::new(&board, &oop, &ip, pot, stack, iterations) {
+        Ok(c) => c,
+        Err(ref e) => {
+            print_solver_error(e);
+            return;
+        }
+    };
+    config.postprocess = match postprocess.build() {
+        Ok(p) => p,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let simplify = match simplify.build() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    config.progress_interval = output.progress_interval(iterations, target.time.is_some());
+    config.on_progress = output.progress_callback();
+    config.time_budget = target.time;
+    config.target_exploitability = target_pct;
+    config.cfr_variant = cfr_variant.variant();
+    config.bucketing = bucketing;
+    config.seed = seed;
+    config.max_precompute_bytes = max_precompute;
+    config.refine_flop_combos = refine_combos;
+    let export_range = match parse_export_range(export_range.as_deref(), &config.tree_config()) {
+        Ok(e) => e,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    // Cache keys carry the pot type, so `gto query --pot-type` finds the solve
+    let seat_keys = seats.map(|(o, i)| {
+        let t = pot_type.unwrap_or(crate::strategy::PotType::Srp);
+        (t.seat_key(o.as_str()), t.seat_key(i.as_str()))
+    });
+    let (oop_name, ip_name) = seat_keys.as_ref().map_or(("", ""), |(o, i)| (o.as_str(), i.as_str()));
+
+    if let Some(candidates) = compare_sizes {
+        println!();
+        println!(
+            "  Comparing flop bet sizes: board={}, pot={}, stack={}, {} iterations per tree...",
+            board, pot, stack, iterations
+        );
+        print_seats(seats, &board, pot, stack, pot_type.map(|t| (t, depth)));
+        if let Err(ref e) = crate::size_compare::run_size_comparison(config, &candidates, (oop_name, ip_name)) {
+            print_error(e);
+        }
+        return;
+    }
+
+    let checkpoint_path = FlopCheckpoint::path(&config, oop_name, ip_name);
+    let checkpoint = if resume {
+        match FlopCheckpoint::load(&checkpoint_path) {
+            Ok(c) => c,
+            Err(ref e) => {
+                print_failure(ExitCode::CacheIo, e);
+                return;
+            }
+        }
+    } else {
+        None
+    };
+    config.checkpoint_path = Some(checkpoint_path);
+    config.checkpoint_every = checkpoint_every;
+
+    if !output.json {
+        println!();
+        println!(
+            "  Solving flop: board={}, pot={}, stack={}, {}...",
+            board, pot, stack, target.run_length(iterations)
+        );
+        match &checkpoint {
+            Some(c) => println!("  Resuming from checkpoint at {} iterations", c.iterations),
+            None if resume => println!("  {}", "No checkpoint for this spot yet; starting fresh.".yellow()),
+            None => {}
+        }
+        println!("  Precompute: {}", config.precompute_mode());
+        print_seats(seats, &board, pot, stack, pot_type.map(|t| (t, depth)));
+    }
+
+    let (mut result, _) = match solve_flop_resumable(&config, checkpoint) {
+        Ok(r) => r,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    crate::progress::end_progress_line(&result.convergence_history);
+    if let Some((oop_key, ip_key)) = seat_keys {
+        result.oop_pos = oop_key;
+        result.ip_pos = ip_key;
+    }
+    let shown = simplify.map(|s| result.simplified(s));
+    output.emit(shown.as_ref().unwrap_or(&result), |r| {
+        r.display_with(output.verbose);
+        if grid {
+            print_flop_grids(r);
+        }
+        if let Some(export) = &export_range {
+            print_exported_range(r, export);
+        }
+        target.report(r.iterations, iterations, r.exploitability, r.starting_pot);
+    });
+    result.save_cache();
+}
+
+/// `--grid`: each player's first decision by canonical hand.
+fn print_flop_grids(solution: &crate::flop_solver::FlopSolution) {
+    for side in ["OOP", "IP"] {
+        if let Some(strat) = solution.strategies.iter().find(|s| s.player == side) {
+            println!("{}", solution.display_grid(strat.node_id));
+            println!();
+        }
+    }
+}
+
+/// `--export-range`, checked against the flop tree before solving.
+fn parse_export_range(
+    spec: Option<&str>,
+    tree: &crate::postflop_tree::TreeConfig,
+) -> Result<Option<crate::flop_solver::RangeExport>, String> {
+    let Some(spec) = spec else {
+        return Ok(None);
+    };
+    let export = crate::flop_solver::RangeExport::parse(spec)?;
+    export.check(tree)?;
+    Ok(Some(export))
+}
+
+/// `--export-range`: the range taking the action, in range syntax.
+fn print_exported_range(solution: &crate::flop_solver::FlopSolution, export: &crate::flop_solver::RangeExport) {
+    match solution.action_range(export.node_id, &export.action, export.threshold) {
+        Ok(range) => {
+            println!(
+                "  {} at node {} ({} combos):",
+                format!("Range taking '{}'", export.action).bold(),
+                export.node_id,
+                range.len()
+            );
+            println!("  {}", crate::ranges::weighted_range_string(&range));
+            println!();
+        }
+        Err(ref e) => print_error(e),
+    }
+}
+
+/// Header line naming the seats a manual solve is cached under.
+fn print_seats(
+    seats: Option<(crate::preflop_solver::Position, crate::preflop_solver::Position)>,
+    board: &str,
+    pot: f64,
+    stack: f64,
+    pot_type: Option<(crate::strategy::PotType, f64)>,
+) {
+    match seats {
+        Some((oop_pos, ip_pos)) => {
+            println!("  Seats: {} (OOP) vs {} (IP)", oop_pos, ip_pos);
+            let spot = match pot_type {
+                Some((t, depth)) => format!("--pot-type {} --stack {}", t.as_str().to_lowercase(), depth),
+                None => format!("--pot {} --stack {}", pot, stack),
+            };
+            println!("  Query with: gto query <HAND> {} --vs {} {} {}", ip_pos, oop_pos, board, spot);
+        }
+        None => println!(
+            "  {}",
+            "No --oop-pos/--ip-pos given: `gto query` won't find this solve.".yellow()
+        ),
+    }
+}
+
+/// Ranges and seats for a turn or river solve: with `--from-preflop`, the
+/// matchup's seats and its single raised pot ranges for any range not given.
+fn preflop_spot(
+    matchup: Option<&str>,
+    stack_bb: f64,
+    oop: Option<String>,
+    ip: Option<String>,
+    seats: Option<(String, String)>,
+) -> Result<(String, String, Option<(String, String)>), String> {
+    let Some(matchup) = matchup else {
+        return Ok((oop.unwrap_or_default(), ip.unwrap_or_default(), seats));
+    };
+    let (oop, ip, (oop_pos, ip_pos)) = matchup_spot(matchup, crate::strategy::PotType::Srp, stack_bb, oop, ip)?;
+    Ok((oop, ip, Some((oop_pos.as_str().to_string(), ip_pos.as_str().to_string()))))
+}
+
+/// `matchup`'s ranges in `pot_type` at `stack_bb` for any range not given,
+/// and its (OOP, IP) seats. Warns when there's no preflop solve to read and
+/// the static charts stand in.
+fn matchup_spot(
+    matchup: &str,
+    pot_type: crate::strategy::PotType,
+    stack_bb: f64,
+    oop: Option<String>,
+    ip: Option<String>,
+) -> Result<(String, String, (crate::preflop_solver::Position, crate::preflop_solver::Position)), String> {
+    let ranges = crate::strategy::matchup_ranges(matchup, pot_type, stack_bb)?;
+    if ranges.from_charts && (oop.is_none() || ip.is_none()) {
+        let warning = format!(
+            "No solved {}bb preflop ranges cached (run `gto solve preflop --stack {}`); using the static charts for {} vs {}.",
+            stack_bb, stack_bb, ranges.oop_pos, ranges.ip_pos
+        );
+        eprintln!("  {}", warning.yellow());
+    }
+    Ok((oop.unwrap_or(ranges.oop_range), ip.unwrap_or(ranges.ip_range), (ranges.oop_pos, ranges.ip_pos)))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_sweep_stacks(
+    boards: Vec<String>,
+    oop_pos: String,
+    ip_pos: String,
+    pot: f64,
+    stacks: String,
+    iterations: usize,
+    ranges: Option<(String, String)>,
+    csv: Option<String>,
+) {
+    use crate::strategy::{postflop_seats, StrategyEngine};
+    use crate::sweep::{parse_stacks, run_stack_sweep};
+
+    if pot <= 0.0 {
+        print_error("Pot must be positive");
+        return;
+    }
+    let stacks = match parse_stacks(&stacks) {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let (oop_seat, ip_seat) = match postflop_seats(&oop_pos, &ip_pos) {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let (oop_range, ip_range) = match ranges {
+        Some(r) => r,
+        None => match StrategyEngine::new(100.0).derive_postflop_ranges(oop_seat, ip_seat) {
+            Ok((oop, ip)) => (oop.join(","), ip.join(",")),
+            Err(ref e) => {
+                print_error(e);
+                return;
+            }
+        },
+    };
+
+    let several = boards.len() > 1;
+    for board in boards {
+        let board = normalize_cards(&board).unwrap_or(board);
+        // One CSV per board when sweeping a board set: sweep.csv -> sweep-Ks9d4c.csv
+        let csv = csv.as_deref().map(|path| {
+            let path = std::path::Path::new(path);
+            if !several {
+                return path.to_path_buf();
+            }
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("sweep");
+            let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("csv");
+            path.with_file_name(format!("{}-{}.{}", stem, board, ext))
+        });
+        if let Err(ref e) = run_stack_sweep(
+            &board,
+            oop_seat.as_str(),
+            ip_seat.as_str(),
+            &oop_range,
+            &ip_range,
+            pot,
+            &stacks,
+            iterations,
+            csv.as_deref(),
+        ) {
+            print_error(e);
+        }
+    }
+}
+
+fn cmd_compare_textures(
+    ranks: String,
+    oop_pos: String,
+    ip_pos: String,
+    pot: f64,
+    stack: f64,
+    iterations: usize,
+    ranges: Option<(String, String)>,
+) {
+    use crate::strategy::{postflop_seats, StrategyEngine};
+    use crate::texture_compare::run_texture_compare;
+
+    if pot <= 0.0 {
+        print_error("Pot must be positive");
+        return;
+    }
+    if stack <= 0.0 {
+        print_error("Stack must be positive");
+        return;
+    }
+    let (oop_seat, ip_seat) = match postflop_seats(&oop_pos, &ip_pos) {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let (oop_range, ip_range) = match ranges {
+        Some(r) => r,
+        None => match StrategyEngine::new(100.0).derive_postflop_ranges(oop_seat, ip_seat) {
+            Ok((oop, ip)) => (oop.join(","), ip.join(",")),
+            Err(ref e) => {
+                print_error(e);
+                return;
+            }
+        },
+    };
+
+    if let Err(ref e) = run_texture_compare(
+        &ranks,
+        oop_seat.as_str(),
+        ip_seat.as_str(),
+        &oop_range,
+        &ip_range,
+        pot,
+        stack,
+        iterations,
+    ) {
+        print_error(e);
+    }
+}
+
+/// Share of the cached run's iterations used to continue after a range edit.
+const EDIT_ITERATION_FRACTION: f64 = 0.2;
+
+#[allow(clippy::too_many_arguments)]
+fn cmd_solve_flop_edit(
+    path: String,
+    oop_edit: Option<String>,
+    ip_edit: Option<String>,
+    iterations: Option<usize>,
+    seed: Option<u64>,
+    cfr_variant: CfrVariantArg,
+    grid: bool,
+    export_range: Option<String>,
+    max_precompute: u64,
+    refine_combos: bool,
+    force: bool,
+    postprocess: PostProcessArgs,
+    simplify: SimplifyArgs,
+    output: SolutionOutputArgs,
+    target: TargetArgs,
+) {
+    use crate::flop_solver::{FlopSolution, FlopSolverConfig, solve_flop_warm};
+    use crate::ranges::{apply_weighted_range_edit, weighted_range_string};
+
+    let prior = match FlopSolution::load_file(std::path::Path::new(&path)) {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    // The prior's frequencies carry over; an edit of "" keeps the range as is
+    let edit = |base: &[String], weights: &[f64], edit: &Option<String>| {
+        apply_weighted_range_edit(base, weights, edit.as_deref().unwrap_or("")).map(|r| weighted_range_string(&r))
+    };
+    let (oop, ip) = match (
+        edit(&prior.oop_range, &prior.oop_weights, &oop_edit),
+        edit(&prior.ip_range, &prior.ip_weights, &ip_edit),
+    ) {
+        (Ok(oop), Ok(ip)) => (oop, ip),
+        (Err(ref e), _) | (_, Err(ref e)) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    let iterations = iterations
+        .unwrap_or(((prior.iterations as f64 * EDIT_ITERATION_FRACTION) as usize).max(1));
+    // The warm start keeps the prior's iterations, so bound the combined run
+    let edit_bounds = crate::limits::Bounds {
+        max: crate::limits::FLOP_ITERATIONS.max.saturating_sub(prior.iterations),
+        ..crate::limits::FLOP_ITERATIONS
+    };
+    let (iterations, target_pct) = match target.resolve(iterations, edit_bounds) {
+        Ok(t) => t,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    if iterations == 0 {
+        print_error("Flop iterations must be positive");
+        return;
+    }
+    if !check_bounds(crate::limits::FLOP_ITERATIONS, prior.iterations + iterations, force) {
+        return;
+    }
+    let mut config = match FlopSolverConfig::new(
+        &prior.board, &oop, &ip, prior.starting_pot, prior.effective_stack, iterations,
+    ) {
+        Ok(c) => c,
+        Err(ref e) => {
+            print_solver_error(e);
+            return;
+        }
+    };
+    if prior.num_buckets > 0 {
+        config.num_buckets = prior.num_buckets;
+    }
+    config.bucketing = prior.bucketing;
+    config.postprocess = match postprocess.build() {
+        Ok(p) => p,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    let simplify = match simplify.build() {
+        Ok(s) => s,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    config.progress_interval = output.progress_interval(iterations, target.time.is_some());
+    config.on_progress = output.progress_callback();
+    config.time_budget = target.time;
+    config.target_exploitability = target_pct;
+    config.cfr_variant = cfr_variant.variant();
+    config.seed = seed;
+    config.max_precompute_bytes = max_precompute;
+    config.refine_flop_combos = refine_combos;
+    let export_range = match parse_export_range(export_range.as_deref(), &prior.tree_config()) {
+        Ok(e) => e,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+
+    if !output.json {
+        println!();
+        println!(
+            "  Re-solving flop {} after range edit, {} iterations from {}...",
+            prior.board, iterations, path
+        );
+        println!("  Precompute: {}", config.precompute_mode());
+    }
+
+    let mut result = match solve_flop_warm(&config, &prior) {
+        Ok(r) => r,
+        Err(ref e) => {
+            print_error(e);
+            return;
+        }
+    };
+    crate::progress::end_progress_line(&result.convergence_history);
+    result.oop_pos = prior.oop_pos.clone();
+    result.ip_pos = prior.ip_pos.clone();
+    let shown = simplify.map(|s| result.simplified(s));
+    output.emit(shown.as_ref().unwrap_or(&result), |r| {
+        r.display_with(output.verbose);
+        if grid {
+            print_flop_grids(r);
+        }
+        if let Some(export) = &export_range {
+            print_exported_range(r, export);
+        }
+        println!(
+            "  Exploitability: {:.4} before edit → {:.4} after",
+            prior.exploitability, r.exploitability
+        );
+        println!();
+        target.report(r.iterations - prior.iterations, iterations, r.exploitability, r.starting_pot);
+    });
     result.save_cache();
 }
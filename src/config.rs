@@ -0,0 +1,204 @@
+//! User config in `~/.gto-cli/config.json`.
+//!
+//! Holds named board sets: fixed flop study sets that batch, sweep and report
+//! commands take in place of a single board or the built-in flop lists via
+//! `--boardset <name>`:
+//!
+//! ```json
+//! { "boardsets": { "my25": ["Ks9d4c", "As7d2c", "JhTs9c"] } }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::normalize_cards;
+use crate::flop_enumerator::{canonical_board, flop_weight};
+use crate::status::io_error;
+
+pub const CONFIG_FILE: &str = "config.json";
+
+/// Raw flops (52 choose 3) that canonical flop weights add up to.
+const TOTAL_FLOPS: usize = 22_100;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Board set name -> flops, as written by the user.
+    #[serde(default)]
+    pub boardsets: BTreeMap<String, Vec<String>>,
+}
+
+impl Config {
+    /// Load the config; a missing file is an empty config.
+    pub fn load_from(path: &Path) -> Result<Config, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Invalid config {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(format!("Cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    /// The named board set, validated.
+    pub fn boardset(&self, name: &str) -> Result<BoardSet, String> {
+        let boards = self.boardsets.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.boardsets.keys().map(|k| k.as_str()).collect();
+            if known.is_empty() {
+                format!("No board set '{}'. Add one with `gto boardset add {} <boards>`", name, name)
+            } else {
+                format!("No board set '{}'. Known: {}", name, known.join(", "))
+            }
+        })?;
+        BoardSet::new(name, boards)
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".gto-cli").join(CONFIG_FILE)
+}
+
+/// A named set of flops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardSet {
+    pub name: String,
+    /// Flops in ASCII notation, in the order given, without isomorphic repeats.
+    pub boards: Vec<String>,
+}
+
+impl BoardSet {
+    /// Validate `boards` as flops; isomorphic repeats ("Ks9d4c", "Kh9s4d") are
+    /// kept once since they solve to the same strategy.
+    pub fn new(name: &str, boards: &[String]) -> Result<BoardSet, String> {
+        let mut seen = Vec::new();
+        let mut flops = Vec::new();
+        for board in boards {
+            let flop = normalize_cards(board).map_err(|e| format!("Board set '{}': {}", name, e))?;
+            if flop.len() != 6 {
+                return Err(format!("Board set '{}': '{}' is not a flop", name, board));
+            }
+            let canonical = canonical_board(&flop)?;
+            if !seen.contains(&canonical) {
+                seen.push(canonical);
+                flops.push(flop);
+            }
+        }
+        if flops.is_empty() {
+            return Err(format!("Board set '{}' is empty", name));
+        }
+        Ok(BoardSet { name: name.to_string(), boards: flops })
+    }
+
+    /// Share of all flops the set stands for, counting each board with its
+    /// isomorphic variants.
+    pub fn coverage(&self) -> f64 {
+        let weight: usize = self.boards.iter().filter_map(|b| flop_weight(b).ok()).sum();
+        weight as f64 / TOTAL_FLOPS as f64
+    }
+
+    /// "my25 (25 flops, 31.2% of all flops by weight)"
+    pub fn label(&self) -> String {
+        format!(
+            "{} ({} flops, {:.1}% of all flops by weight)",
+            self.name,
+            self.boards.len(),
+            self.coverage() * 100.0
+        )
+    }
+}
+
+/// Load `name` from the user's config.
+pub fn resolve_boardset(name: &str) -> Result<BoardSet, String> {
+    Config::load_from(&config_path()).map_err(io_error)?.boardset(name)
+}
+
+/// `gto boardset add`: create or replace a set.
+pub fn run_boardset_add(name: &str, boards: &[String]) -> Result<(), String> {
+    // Accept "Ks9d4c,As7d2c" as well as separate arguments
+    let boards: Vec<String> = boards
+        .iter()
+        .flat_map(|b| b.split(','))
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty())
+        .collect();
+    let set = BoardSet::new(name, &boards)?;
+    let path = config_path();
+    let mut config = Config::load_from(&path).map_err(io_error)?;
+    config.boardsets.insert(name.to_string(), set.boards.clone());
+    config.save_to(&path).map_err(io_error)?;
+    println!("  Saved board set {}", set.label().bold());
+    Ok(())
+}
+
+/// `gto boardset remove`.
+pub fn run_boardset_remove(name: &str) -> Result<(), String> {
+    let path = config_path();
+    let mut config = Config::load_from(&path).map_err(io_error)?;
+    if config.boardsets.remove(name).is_none() {
+        return Err(format!("No board set '{}'", name));
+    }
+    config.save_to(&path).map_err(io_error)?;
+    println!("  Removed board set {}", name.bold());
+    Ok(())
+}
+
+/// `gto boardset list`.
+pub fn run_boardset_list() -> Result<(), String> {
+    let config = Config::load_from(&config_path()).map_err(io_error)?;
+    println!();
+    if config.boardsets.is_empty() {
+        println!("  No board sets. Add one with `gto boardset add <name> <boards>`.");
+    }
+    for name in config.boardsets.keys() {
+        let set = config.boardset(name)?;
+        println!("  {}", set.label().bold());
+        println!("    {}", set.boards.join(" "));
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boards(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_boardset_dedupes_isomorphic_flops_and_weighs_coverage() {
+        let set = BoardSet::new("study", &boards(&["Ks9d4c", "Kh9s4d", "KsKd7c", "9s5s2s"])).unwrap();
+        assert_eq!(set.boards, boards(&["Ks9d4c", "KsKd7c", "9s5s2s"]));
+        // Rainbow 24 + paired 12 + monotone 4 of 22,100
+        assert!((set.coverage() - 40.0 / 22_100.0).abs() < 1e-12);
+
+        assert!(BoardSet::new("bad", &boards(&["Ks9d4c2h"])).is_err());
+        assert!(BoardSet::new("bad", &boards(&["Ks9dXc"])).is_err());
+        assert!(BoardSet::new("empty", &[]).is_err());
+    }
+
+    #[test]
+    fn test_config_round_trips_and_reports_unknown_sets() {
+        let dir = std::env::temp_dir().join(format!("gto-config-test-{}", std::process::id()));
+        let path = dir.join(CONFIG_FILE);
+        assert!(Config::load_from(&path).unwrap().boardsets.is_empty());
+
+        let mut config = Config::default();
+        config.boardsets.insert("my25".to_string(), boards(&["Ks9d4c", "As7d2c"]));
+        config.save_to(&path).unwrap();
+        let loaded = Config::load_from(&path).unwrap();
+        assert_eq!(loaded.boardset("my25").unwrap().boards.len(), 2);
+        assert!(loaded.boardset("other").unwrap_err().contains("Known: my25"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,7 +1,9 @@
 use colored::Colorize;
 use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
 
-use crate::cards::{Card, Suit};
+use crate::breakdown::{describe_action, Breakdown};
+use crate::cards::{suit_style, Card, Suit};
+use crate::range_summary::RangeSummary;
 
 const RANGE_GRID_RANKS: [char; 13] = ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'];
 
@@ -84,6 +86,41 @@ pub fn strategy_grid(strategy: &[f64], title: &str) -> String {
     format!("  {}\n{}", title.bold(), table)
 }
 
+/// Display a 13x13 grid of signed frequency changes in percentage points,
+/// indexed like `strategy_grid`. Gains are green and losses red, bold from 25
+/// points; changes that round to 0 are dimmed.
+pub fn diff_grid(deltas: &[f64], title: &str) -> String {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    let mut header = vec![Cell::new("")];
+    for &r in &RANGE_GRID_RANKS {
+        header.push(Cell::new(r).set_alignment(CellAlignment::Center));
+    }
+    table.set_header(header);
+
+    for (i, &r1) in RANGE_GRID_RANKS.iter().enumerate() {
+        let mut row = vec![Cell::new(format!("{}", r1).bold().to_string())];
+        for j in 0..RANGE_GRID_RANKS.len() {
+            let delta = deltas[i * 13 + j];
+            let points = (delta * 100.0).round() as i32;
+            let label = if points == 0 { format!("{:>3}", 0) } else { format!("{:>+3}", points) };
+
+            let cell = match points {
+                0 => Cell::new(label.dimmed().to_string()),
+                p if p >= 25 => Cell::new(label.green().bold().to_string()),
+                p if p > 0 => Cell::new(label.green().to_string()),
+                p if p <= -25 => Cell::new(label.red().bold().to_string()),
+                _ => Cell::new(label.red().to_string()),
+            };
+            row.push(cell.set_alignment(CellAlignment::Center));
+        }
+        table.add_row(row);
+    }
+
+    format!("  {}\n{}", title.bold(), table)
+}
+
 pub fn equity_bar(equity: f64, width: usize) -> String {
     let filled = (equity * width as f64) as usize;
     let bar: String = "\u{2588}".repeat(filled) + &"\u{2591}".repeat(width - filled);
@@ -98,12 +135,73 @@ pub fn equity_bar(equity: f64, width: usize) -> String {
     }
 }
 
+/// Histogram of `equities` in ten 10% buckets, highest first: each bar is
+/// the bucket's share of the entries, colored like `equity_bar`.
+pub fn equity_histogram(equities: &[f64], width: usize) -> String {
+    let mut counts = [0usize; 10];
+    for &equity in equities {
+        counts[((equity * 10.0) as usize).min(9)] += 1;
+    }
+    let total = equities.len().max(1) as f64;
+    let mut lines = Vec::new();
+    for (bucket, &count) in counts.iter().enumerate().rev() {
+        let share = count as f64 / total;
+        let filled = (share * width as f64).round() as usize;
+        let bar: String = "\u{2588}".repeat(filled) + &"\u{2591}".repeat(width - filled);
+        let bar = match bucket {
+            6.. => bar.green(),
+            4 | 5 => bar.yellow(),
+            _ => bar.red(),
+        };
+        lines.push(format!("  {:>3}–{:<4} {} {}", bucket * 10, format!("{}%", bucket * 10 + 10), bar, count));
+    }
+    lines.join("\n")
+}
+
+/// Bar for an EV in pots: filled in proportion to its size up to one pot,
+/// green for a gain and red for a loss.
+pub fn ev_bar(ev: f64, width: usize) -> String {
+    let filled = (ev.abs().min(1.0) * width as f64) as usize;
+    let bar: String = "\u{2588}".repeat(filled) + &"\u{2591}".repeat(width - filled);
+    let value = format!("{:+.2}", ev);
+
+    if ev >= 0.0 {
+        format!("{} {}", bar.green(), value)
+    } else {
+        format!("{} {}", bar.red(), value)
+    }
+}
+
+/// Eight-level block sparkline of `values`, one character each, scaled
+/// between their min and max. A flat series (or a single point) sits on the
+/// bottom level; non-finite values are blanks.
+pub fn sparkline(values: &[f64]) -> String {
+    const LEVELS: [char; 8] =
+        ['\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
+    let finite = values.iter().copied().filter(|v| v.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+    let span = max - min;
+    values
+        .iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                ' '
+            } else if span > 0.0 {
+                LEVELS[(((v - min) / span) * (LEVELS.len() - 1) as f64).round() as usize]
+            } else {
+                LEVELS[0]
+            }
+        })
+        .collect()
+}
+
 pub fn board_display(cards: &[Card]) -> String {
     cards
         .iter()
         .map(|card| {
             let rank = card.rank.to_char();
-            let symbol = card.suit.symbol();
+            let symbol = card.suit.render(suit_style());
             let colored = match card.suit {
                 Suit::Spades => format!("{}{}", rank, symbol).white().to_string(),
                 Suit::Hearts => format!("{}{}", rank, symbol).red().to_string(),
@@ -201,10 +299,205 @@ pub fn print_section(title: &str, content: &str) {
     println!("  {}", content);
 }
 
+/// Print an error; the run exits with code 2 (invalid input) unless a more
+/// specific failure was recorded first (see `status`).
 pub fn print_error(msg: &str) {
+    print_failure(crate::status::ExitCode::InvalidInput, msg);
+}
+
+/// Print an error the run exits with as `code`, unless a failure was
+/// recorded first.
+pub fn print_failure(code: crate::status::ExitCode, msg: &str) {
+    crate::status::fail(code, msg);
     eprintln!("{} {}", "Error:".red().bold(), msg);
 }
 
+/// Print a solver config error with what to change to fix it.
+pub fn print_solver_error(err: &crate::error::SolverError) {
+    print_error(&err.to_string());
+    eprintln!("  {}", err.suggestion().dimmed());
+}
+
 pub fn print_success(msg: &str) {
     println!("{}", msg.green().bold());
 }
+
+/// Frequencies closer than this count as the same strategy when grouping combos.
+pub const COMBO_GROUP_EPSILON: f64 = 0.005;
+
+/// Combos of one canonical hand that play the same strategy (see `group_combos`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComboGroup {
+    /// Canonical hand, e.g. "AKs".
+    pub hand: String,
+    pub combos: Vec<String>,
+    /// Action frequencies averaged over `combos`.
+    pub frequencies: Vec<f64>,
+}
+
+impl ComboGroup {
+    /// "AKs (3 combos)", or the combo itself when it stands alone.
+    pub fn label(&self) -> String {
+        match self.combos.as_slice() {
+            [combo] => combo.clone(),
+            combos => format!("{} ({} combos)", self.hand, combos.len()),
+        }
+    }
+}
+
+/// Group combos (e.g. "AhKh") of the same canonical hand whose action
+/// frequencies all agree within `COMBO_GROUP_EPSILON` of the group's first
+/// combo. Combos a board card or blocker makes play differently stay apart.
+/// Groups are in the order of their first combo.
+pub fn group_combos(combos: &[String], frequencies: &[Vec<f64>]) -> Vec<ComboGroup> {
+    let mut groups: Vec<(ComboGroup, &[f64])> = Vec::new();
+    for (combo, freqs) in combos.iter().zip(frequencies) {
+        let hand = crate::cards::hand_to_canonical(combo);
+        let same = |first: &[f64]| first.iter().zip(freqs).all(|(a, b)| (a - b).abs() <= COMBO_GROUP_EPSILON);
+        match groups.iter_mut().find(|(g, first)| g.hand == hand && same(first)) {
+            Some((group, _)) => {
+                let n = group.combos.len() as f64;
+                for (avg, f) in group.frequencies.iter_mut().zip(freqs) {
+                    *avg = (*avg * n + f) / (n + 1.0);
+                }
+                group.combos.push(combo.clone());
+            }
+            None => groups.push((ComboGroup { hand, combos: vec![combo.clone()], frequencies: freqs.clone() }, freqs)),
+        }
+    }
+    groups.into_iter().map(|(group, _)| group).collect()
+}
+
+/// A solution's `RangeSummary` as a table: one row per decision, each action
+/// with the share of the range taking it.
+pub fn range_summary_table(summary: &RangeSummary) -> String {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![Cell::new("Decision"), Cell::new("Range")]);
+    for decision in &summary.decisions {
+        let label = match decision.line.as_slice() {
+            [] => format!("{} at root", decision.player),
+            line => format!("{} vs {}", decision.player, line.join(", ")),
+        };
+        let mut row = vec![Cell::new(label.bold().to_string())];
+        row.extend(
+            decision
+                .actions
+                .iter()
+                .zip(&decision.frequencies)
+                .map(|(action, f)| Cell::new(format!("{} {:.1}%", action, f * 100.0))),
+        );
+        table.add_row(row);
+    }
+    table.to_string()
+}
+
+/// Print each decision of `breakdown`: what takes each action, and for bets
+/// its bluff:value ratio against the one the size supports.
+pub fn print_breakdown(breakdown: &Breakdown) {
+    println!();
+    println!("  {}", "Hand classes by action".bold());
+    for decision in &breakdown.decisions {
+        let label = match decision.line.as_slice() {
+            [] => format!("{} at root", decision.player),
+            line => format!("{} vs {}", decision.player, line.join(", ")),
+        };
+        println!("  {}", label.bold());
+        for action in &decision.actions {
+            let Some(line) = describe_action(action, breakdown.complete_board()) else {
+                continue;
+            };
+            println!("    {}", line);
+            if let (Some(ratio), Some(balanced)) = (action.bluff_to_value(), action.balanced_bluff_to_value) {
+                let note = format!("bluff:value {:.2} vs {:.2} balanced", ratio, balanced);
+                println!("      {}", note.dimmed());
+            }
+        }
+    }
+}
+
+/// Rows of a node's strategy shown by the solution summaries.
+const STRATEGY_ROWS: usize = 20;
+
+/// Print a node's per-combo strategy, one row per `group_combos` group, or
+/// per combo with `expanded`; at most `STRATEGY_ROWS` rows.
+pub fn print_combo_strategies(combos: &[String], frequencies: &[Vec<f64>], actions: &[String], expanded: bool) {
+    let rows: Vec<(String, Vec<f64>, usize)> = if expanded {
+        combos.iter().zip(frequencies).map(|(c, f)| (c.clone(), f.clone(), 1)).collect()
+    } else {
+        group_combos(combos, frequencies).into_iter().map(|g| (g.label(), g.frequencies, g.combos.len())).collect()
+    };
+    for (label, freqs, _) in rows.iter().take(STRATEGY_ROWS) {
+        let freq_str = freqs
+            .iter()
+            .zip(actions)
+            .map(|(f, a)| {
+                let pct = (f * 100.0).round() as u32;
+                if pct > 70 {
+                    format!("{}:{}", a, format!("{}%", pct).green())
+                } else if pct > 30 {
+                    format!("{}:{}", a, format!("{}%", pct).yellow())
+                } else {
+                    format!("{}:{}%", a, pct)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("    {}  {}", label.bold(), freq_str);
+    }
+    let hidden: usize = rows.iter().skip(STRATEGY_ROWS).map(|(_, _, n)| n).sum();
+    if hidden > 0 {
+        println!("    ... and {} more combos", hidden);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn equal_strategies_of_one_hand_share_a_row() {
+        let combos = strings(&["AhKh", "AdKd", "AcKc", "AhKd"]);
+        let freqs = vec![vec![0.65, 0.35], vec![0.652, 0.348], vec![0.65, 0.35], vec![0.65, 0.35]];
+        let groups = group_combos(&combos, &freqs);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].label(), "AKs (3 combos)");
+        assert!((groups[0].frequencies[0] - 0.6507).abs() < 1e-3);
+        // Same frequencies, but a different canonical hand
+        assert_eq!(groups[1].label(), "AhKd");
+    }
+
+    #[test]
+    fn combos_with_blocker_driven_frequencies_stay_apart() {
+        // The flush-blocking AsKs bets more than the other suited AK
+        let combos = strings(&["AsKs", "AhKh", "AdKd"]);
+        let freqs = vec![vec![0.9, 0.1], vec![0.4, 0.6], vec![0.4, 0.6]];
+        let groups = group_combos(&combos, &freqs);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].combos, strings(&["AsKs"]));
+        assert_eq!(groups[1].combos, strings(&["AhKh", "AdKd"]));
+    }
+
+    #[test]
+    fn sparkline_of_a_decreasing_series_steps_down_from_the_top() {
+        let line: Vec<char> = sparkline(&[8.0, 5.0, 3.0, 2.0, 1.0]).chars().collect();
+        assert_eq!(line.len(), 5);
+        assert_eq!(line[0], '\u{2588}');
+        assert_eq!(line[4], '\u{2581}');
+        assert!(line.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn sparkline_of_a_flat_series_or_one_point_sits_on_the_bottom() {
+        assert_eq!(sparkline(&[0.4, 0.4, 0.4]), "\u{2581}\u{2581}\u{2581}");
+        assert_eq!(sparkline(&[2.5]), "\u{2581}");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[1.0, f64::NAN, 0.0]), "\u{2588} \u{2581}");
+    }
+}
@@ -1,46 +1,128 @@
 use std::fmt;
 
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rayon::prelude::*;
 
-use crate::card_encoding::{card_to_index, remaining_deck};
-use crate::cards::{hand_combos, Card};
+use crate::card_encoding::{card_to_index, index_to_card, remaining_deck, remaining_deck_excluding};
+use crate::cards::{check_dead_cards, check_distinct, hand_combos, Card};
 use crate::error::{GtoError, GtoResult};
 use crate::lookup_eval::evaluate_fast;
+use crate::ranges::split_weight;
+use crate::river_solver::expand_range_to_combos;
+use crate::rng::stream_rng;
 
 pub struct EquityResult {
     pub win: f64,
     pub tie: f64,
     pub lose: f64,
     pub simulations: usize,
+    /// Villain combos left after card removal, each counted at its range
+    /// frequency (1.0 against a single hand).
+    pub villain_combos: f64,
+    /// Every runout was enumerated; `simulations` counts the showdowns.
+    pub exact: bool,
+    /// Standard error of `equity()`; 0 when exact.
+    pub std_error: f64,
+    /// Sampling stopped early because the estimate reached its precision
+    /// target (see `Sampling`). Always true when exact.
+    pub converged: bool,
 }
 
 impl EquityResult {
     pub fn equity(&self) -> f64 {
         self.win + self.tie / 2.0
     }
+
+    /// Half-width of the 95% confidence interval around `equity()`.
+    pub fn ci_half_width(&self) -> f64 {
+        Z_95 * self.std_error
+    }
 }
 
 impl fmt::Display for EquityResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Win {:.1}% | Tie {:.1}% | Lose {:.1}% (equity: {:.1}%)",
+            "Win {:.1}% | Tie {:.1}% | Lose {:.1}% (equity: {:.1}%",
             self.win * 100.0,
             self.tie * 100.0,
             self.lose * 100.0,
             self.equity() * 100.0,
-        )
+        )?;
+        if !self.exact {
+            write!(f, " ± {:.1}%", self.ci_half_width() * 100.0)?;
+        }
+        write!(f, ")")
     }
 }
 
+/// z-score of a two-sided 95% confidence interval.
+pub const Z_95: f64 = 1.96;
+
+/// Sampled runouts per villain combo between precision checks, spread over
+/// the range (a single hand gets all of them).
+pub const BATCH_SIMS: usize = 5_000;
+
+/// Runouts one RNG stream deals in hand-against-hand sampling; chunks run in
+/// parallel.
+const CHUNK_SIMS: usize = 1_000;
+
+/// How preflop equity is sampled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sampling {
+    /// Stop once the 95% confidence interval's half-width is at most this
+    /// (0.005 is ±0.5% equity); the simulation count becomes a cap. Checked
+    /// every `BATCH_SIMS` runouts.
+    pub precision: Option<f64>,
+    /// Seed the runouts. Every chunk of work draws from its own stream, so a
+    /// seeded run gives the same result however many threads rayon uses.
+    pub seed: Option<u64>,
+}
+
+fn check_sampling(simulations: usize, sampling: Sampling) -> GtoResult<()> {
+    if simulations == 0 {
+        return Err(GtoError::InvalidValue("simulations must be positive".to_string()));
+    }
+    if let Some(p) = sampling.precision {
+        if !(p > 0.0 && p < 1.0) {
+            return Err(GtoError::InvalidValue(format!("precision must be between 0 and 1, got {}", p)));
+        }
+    }
+    Ok(())
+}
+
+/// Hand against hand. From the flop on, every runout is enumerated (at most
+/// 1,081) and `simulations` is ignored; preflop samples `simulations` runouts
+/// (see `exact_equity_vs_hand` to enumerate them). Errors if the hands and
+/// board share a card.
 pub fn equity_vs_hand(
     hand1: &[Card],
     hand2: &[Card],
     board: Option<&[Card]>,
     simulations: usize,
 ) -> GtoResult<EquityResult> {
+    equity_vs_hand_sampled(hand1, hand2, board, &[], simulations, Sampling::default())
+}
+
+/// `equity_vs_hand` with `dead_cards` out of the deck (see
+/// `check_dead_cards`) and a precision target and seed for the preflop
+/// sampling.
+pub fn equity_vs_hand_sampled(
+    hand1: &[Card],
+    hand2: &[Card],
+    board: Option<&[Card]>,
+    dead_cards: &[Card],
+    simulations: usize,
+    sampling: Sampling,
+) -> GtoResult<EquityResult> {
+    check_sampling(simulations, sampling)?;
     let board = board.unwrap_or(&[]);
+    check_distinct(&[hand1, hand2, board].concat())?;
+    check_dead_cards(dead_cards, &[hand1, hand2], board)?;
+    if board.len() >= EXACT_MIN_BOARD {
+        return exact_equity_vs_hand(hand1, hand2, board, dead_cards);
+    }
 
     // Convert everything to u8 indices for the fast path
     let h1: [u8; 2] = [card_to_index(&hand1[0]), card_to_index(&hand1[1])];
@@ -51,135 +133,584 @@ pub fn equity_vs_hand(
     dead.extend_from_slice(&h1);
     dead.extend_from_slice(&h2);
     dead.extend_from_slice(&board_idx);
-    let remaining = remaining_deck(&dead);
-    let cards_needed = 5 - board_idx.len();
-
-    let results: Vec<(u64, u64, u64)> = (0..simulations)
-        .into_par_iter()
-        .map(|_| {
-            let mut rng = rand::thread_rng();
-            let mut deck = remaining.clone();
-            deck.shuffle(&mut rng);
-
-            // Build 7-card hands directly as [u8; 7]
-            let mut all1 = [0u8; 7];
-            let mut all2 = [0u8; 7];
-            all1[0] = h1[0]; all1[1] = h1[1];
-            all2[0] = h2[0]; all2[1] = h2[1];
-            for (i, &c) in board_idx.iter().chain(deck[..cards_needed].iter()).enumerate() {
-                all1[2 + i] = c;
-                all2[2 + i] = c;
-            }
+    let remaining = remaining_deck_excluding(&dead, &indices(dead_cards));
 
-            let r1 = evaluate_fast(&all1);
-            let r2 = evaluate_fast(&all2);
+    let combos = [(h2, 1.0)];
+    Ok(sample_batches(&combos, simulations, sampling, |batch, sims| {
+        let counts = (0..sims.div_ceil(CHUNK_SIMS))
+            .into_par_iter()
+            .map(|chunk| {
+                let mut rng = stream_rng(sampling.seed, batch << 32 | chunk as u64);
+                let n = CHUNK_SIMS.min(sims - chunk * CHUNK_SIMS);
+                sample_showdowns(h1, h2, &board_idx, &remaining, n, &mut rng)
+            })
+            .reduce(|| (0, 0, 0, 0), add_counts);
+        vec![counts]
+    }))
+}
 
-            match r1.cmp(&r2) {
-                std::cmp::Ordering::Greater => (1, 0, 0),
-                std::cmp::Ordering::Equal => (0, 1, 0),
-                std::cmp::Ordering::Less => (0, 0, 1),
-            }
-        })
-        .collect();
+/// Exact hand-against-hand equity over every runout of `board` that deals
+/// none of `dead_cards`. Preflop that is 1,712,304 boards.
+pub fn exact_equity_vs_hand(
+    hand1: &[Card],
+    hand2: &[Card],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> GtoResult<EquityResult> {
+    check_distinct(&[hand1, hand2, board].concat())?;
+    check_dead_cards(dead_cards, &[hand1, hand2], board)?;
+    let h1: [u8; 2] = [card_to_index(&hand1[0]), card_to_index(&hand1[1])];
+    let h2: [u8; 2] = [card_to_index(&hand2[0]), card_to_index(&hand2[1])];
+    let board_idx: Vec<u8> = board.iter().map(card_to_index).collect();
+    let mut dead = Vec::with_capacity(4 + board.len());
+    dead.extend_from_slice(&h1);
+    dead.extend_from_slice(&h2);
+    dead.extend_from_slice(&board_idx);
+    let deck = remaining_deck_excluding(&dead, &indices(dead_cards));
 
-    let (wins, ties, losses) = results
-        .iter()
-        .fold((0u64, 0u64, 0u64), |acc, &(w, t, l)| {
-            (acc.0 + w, acc.1 + t, acc.2 + l)
-        });
-
-    let total = (wins + ties + losses) as f64;
-    Ok(EquityResult {
-        win: wins as f64 / total,
-        tie: ties as f64 / total,
-        lose: losses as f64 / total,
-        simulations: total as usize,
-    })
+    // Split on the first card dealt so long enumerations run in parallel
+    let (w, t, l, n) = if board_idx.len() < 5 {
+        (0..deck.len())
+            .into_par_iter()
+            .map(|i| {
+                let mut runout = board_idx.clone();
+                runout.push(deck[i]);
+                enumerate_showdowns(h1, h2, &runout, &deck[i + 1..])
+            })
+            .reduce(|| (0, 0, 0, 0), add_counts)
+    } else {
+        enumerate_showdowns(h1, h2, &board_idx, &deck)
+    };
+    Ok(weighted_result(&[(h2, 1.0)], &[(w, t, l, n)], true))
 }
 
+/// Equity against a range. Terms may carry a frequency (`"KK:0.5"`, see
+/// `ranges::parse_weighted_range`); terms without one count in full. Exact
+/// from the flop on, like `equity_vs_hand`. Hero's hand may not share a card
+/// with the board; villain combos that do are left out.
 pub fn equity_vs_range(
     hand: &[Card],
     villain_range: &[String],
     board: Option<&[Card]>,
     simulations: usize,
 ) -> GtoResult<EquityResult> {
-    let board = board.unwrap_or(&[]);
-
-    let hero: [u8; 2] = [card_to_index(&hand[0]), card_to_index(&hand[1])];
-    let board_idx: Vec<u8> = board.iter().map(card_to_index).collect();
+    let weighted = villain_range
+        .iter()
+        .map(|term| split_weight(term).map(|(hands, w)| (hands.to_string(), w)))
+        .collect::<GtoResult<Vec<_>>>()?;
+    equity_vs_weighted_range(hand, &weighted, board, simulations)
+}
 
-    // Dead cards for filtering combos
-    let dead_set: std::collections::HashSet<Card> = hand.iter().chain(board.iter()).copied().collect();
+fn indices(cards: &[Card]) -> Vec<u8> {
+    cards.iter().map(card_to_index).collect()
+}
 
-    // Convert villain combos to u8 index pairs
-    let mut all_combos: Vec<[u8; 2]> = Vec::new();
-    for notation in villain_range {
+/// Villain's live combos with their weights: each combo keeps its hand's
+/// frequency, so hero's blockers (and dead cards) remove combos without
+/// reweighting the rest of the hand.
+fn weighted_combos(
+    hand: &[Card],
+    villain_range: &[(String, f64)],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> GtoResult<Vec<([u8; 2], f64)>> {
+    check_distinct(&[hand, board].concat())?;
+    check_dead_cards(dead_cards, &[hand], board)?;
+    let dead_set: std::collections::HashSet<Card> =
+        hand.iter().chain(board.iter()).chain(dead_cards.iter()).copied().collect();
+    let mut combos: Vec<([u8; 2], f64)> = Vec::new();
+    for (notation, weight) in villain_range {
+        if *weight <= 0.0 {
+            continue;
+        }
         for (c1, c2) in hand_combos(notation)? {
             if !dead_set.contains(&c1) && !dead_set.contains(&c2) {
-                all_combos.push([card_to_index(&c1), card_to_index(&c2)]);
+                combos.push(([card_to_index(&c1), card_to_index(&c2)], *weight));
             }
         }
     }
-
-    if all_combos.is_empty() {
+    if combos.is_empty() {
         return Err(GtoError::NoValidCombos);
     }
+    Ok(combos)
+}
+
+/// Combine per-combo (wins, ties, losses, runouts) counts, weighting each
+/// combo's rates by its range frequency. Sampled, each combo is a stratum:
+/// the variance of the estimate is the sum of the combos' sample variances,
+/// each scaled by its squared weight share over its runouts.
+fn weighted_result(combos: &[([u8; 2], f64)], counts: &[(u64, u64, u64, u64)], exact: bool) -> EquityResult {
+    let (mut win, mut tie, mut lose, mut runs, mut variance) = (0.0, 0.0, 0.0, 0u64, 0.0);
+    let total_weight: f64 = combos.iter().map(|(_, w)| w).sum();
+    for ((_, weight), &(w, t, l, n)) in combos.iter().zip(counts) {
+        let frequency = weight / total_weight;
+        let share = frequency / n.max(1) as f64;
+        win += w as f64 * share;
+        tie += t as f64 * share;
+        lose += l as f64 * share;
+        runs += n;
+        if !exact {
+            variance += frequency * frequency * score_variance((w, t, l, n)) / n.max(1) as f64;
+        }
+    }
+    EquityResult {
+        win,
+        tie,
+        lose,
+        simulations: runs as usize,
+        villain_combos: total_weight,
+        exact,
+        std_error: variance.sqrt(),
+        converged: exact,
+    }
+}
+
+/// Unbiased sample variance of one combo's showdown scores (1 win, ½ tie,
+/// 0 loss). Under two runouts it takes the largest a score's variance can be.
+fn score_variance((w, t, _, n): (u64, u64, u64, u64)) -> f64 {
+    if n < 2 {
+        return 0.25;
+    }
+    let n = n as f64;
+    let mean = (w as f64 + t as f64 / 2.0) / n;
+    let square = (w as f64 + t as f64 / 4.0) / n;
+    ((square - mean * mean) * n / (n - 1.0)).max(0.0)
+}
+
+fn add_counts(a: (u64, u64, u64, u64), b: (u64, u64, u64, u64)) -> (u64, u64, u64, u64) {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2, a.3 + b.3)
+}
+
+/// Sample up to `simulations` runouts against `combos` in batches:
+/// `deal(batch, sims)` returns each combo's counts over `sims` more runouts.
+/// Without a precision target everything is one batch; with one, sampling
+/// stops after the first batch whose estimate is within it.
+fn sample_batches(
+    combos: &[([u8; 2], f64)],
+    simulations: usize,
+    sampling: Sampling,
+    deal: impl Fn(u64, usize) -> Vec<(u64, u64, u64, u64)>,
+) -> EquityResult {
+    let per_combo = (simulations / combos.len()).max(1);
+    let batch_size = match sampling.precision {
+        Some(_) => (BATCH_SIMS / combos.len()).clamp(1, per_combo),
+        None => per_combo,
+    };
+    let mut counts = vec![(0, 0, 0, 0); combos.len()];
+    let (mut dealt, mut batch) = (0, 0);
+    loop {
+        let sims = batch_size.min(per_combo - dealt);
+        for (total, more) in counts.iter_mut().zip(deal(batch, sims)) {
+            *total = add_counts(*total, more);
+        }
+        dealt += sims;
+        batch += 1;
+
+        let mut result = weighted_result(combos, &counts, false);
+        if sampling.precision.is_some_and(|p| result.ci_half_width() <= p) {
+            result.converged = true;
+            return result;
+        }
+        if dealt >= per_combo {
+            return result;
+        }
+    }
+}
+
+/// (wins, ties, losses, runouts) for `hero` against `villain` over `sims`
+/// random completions of `board` from `deck`.
+fn sample_showdowns(
+    hero: [u8; 2],
+    villain: [u8; 2],
+    board: &[u8],
+    deck: &[u8],
+    sims: usize,
+    rng: &mut StdRng,
+) -> (u64, u64, u64, u64) {
+    let cards_needed = 5 - board.len();
+    let mut deck = deck.to_vec();
+    let mut runout = board.to_vec();
+    let mut counts = (0, 0, 0, 0);
+    for _ in 0..sims {
+        let (dealt, _) = deck.partial_shuffle(rng, cards_needed);
+        runout.truncate(board.len());
+        runout.extend_from_slice(dealt);
+        match showdown(hero, villain, &runout) {
+            std::cmp::Ordering::Greater => counts.0 += 1,
+            std::cmp::Ordering::Equal => counts.1 += 1,
+            std::cmp::Ordering::Less => counts.2 += 1,
+        }
+        counts.3 += 1;
+    }
+    counts
+}
+
+/// Compare hero and villain on one completed board.
+fn showdown(hero: [u8; 2], villain: [u8; 2], board: &[u8]) -> std::cmp::Ordering {
+    let mut all1 = [0u8; 7];
+    let mut all2 = [0u8; 7];
+    all1[0] = hero[0]; all1[1] = hero[1];
+    all2[0] = villain[0]; all2[1] = villain[1];
+    for (i, &c) in board.iter().enumerate() {
+        all1[2 + i] = c;
+        all2[2 + i] = c;
+    }
+    evaluate_fast(&all1).cmp(&evaluate_fast(&all2))
+}
+
+/// (wins, ties, losses, runouts) for `hero` against `villain` over every way
+/// to complete `board` with cards from `deck`.
+fn enumerate_showdowns(hero: [u8; 2], villain: [u8; 2], board: &[u8], deck: &[u8]) -> (u64, u64, u64, u64) {
+    fn deal(hero: [u8; 2], villain: [u8; 2], deck: &[u8], runout: &mut Vec<u8>, counts: &mut (u64, u64, u64, u64)) {
+        if runout.len() == 5 {
+            match showdown(hero, villain, runout) {
+                std::cmp::Ordering::Greater => counts.0 += 1,
+                std::cmp::Ordering::Equal => counts.1 += 1,
+                std::cmp::Ordering::Less => counts.2 += 1,
+            }
+            counts.3 += 1;
+            return;
+        }
+        for (i, &c) in deck.iter().enumerate() {
+            runout.push(c);
+            deal(hero, villain, &deck[i + 1..], runout, counts);
+            runout.pop();
+        }
+    }
+    let mut counts = (0, 0, 0, 0);
+    deal(hero, villain, deck, &mut board.to_vec(), &mut counts);
+    counts
+}
+
+/// Boards (flop or later) from which equity is enumerated rather than
+/// sampled: two cards to come at most.
+pub const EXACT_MIN_BOARD: usize = 3;
 
-    let sims_per = (simulations / all_combos.len()).max(1);
-    let cards_needed = 5 - board_idx.len();
+/// Exact equity against a weighted range by enumerating every runout without
+/// `dead_cards` for every live villain combo. Preflop that is 1,712,304
+/// boards per combo.
+pub fn exact_equity_vs_weighted_range(
+    hand: &[Card],
+    villain_range: &[(String, f64)],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> GtoResult<EquityResult> {
+    let hero: [u8; 2] = [card_to_index(&hand[0]), card_to_index(&hand[1])];
+    let board_idx: Vec<u8> = board.iter().map(card_to_index).collect();
+    let combos = weighted_combos(hand, villain_range, board, dead_cards)?;
+    let dead_idx = indices(dead_cards);
 
-    let results: Vec<(u64, u64, u64)> = all_combos
+    let counts: Vec<(u64, u64, u64, u64)> = combos
         .par_iter()
-        .map(|villain| {
+        .map(|(villain, _)| {
             let mut dead = Vec::with_capacity(4 + board_idx.len());
             dead.extend_from_slice(&hero);
             dead.extend_from_slice(&board_idx);
             dead.extend_from_slice(villain);
-            let remaining = remaining_deck(&dead);
-
-            let mut wins = 0u64;
-            let mut ties = 0u64;
-            let mut losses = 0u64;
-
-            let mut rng = rand::thread_rng();
-            for _ in 0..sims_per {
-                let mut deck = remaining.clone();
-                deck.shuffle(&mut rng);
-
-                let mut all1 = [0u8; 7];
-                let mut all2 = [0u8; 7];
-                all1[0] = hero[0]; all1[1] = hero[1];
-                all2[0] = villain[0]; all2[1] = villain[1];
-                for (i, &c) in board_idx.iter().chain(deck[..cards_needed].iter()).enumerate() {
-                    all1[2 + i] = c;
-                    all2[2 + i] = c;
-                }
+            enumerate_showdowns(hero, *villain, &board_idx, &remaining_deck_excluding(&dead, &dead_idx))
+        })
+        .collect();
 
-                let r1 = evaluate_fast(&all1);
-                let r2 = evaluate_fast(&all2);
+    Ok(weighted_result(&combos, &counts, true))
+}
 
-                match r1.cmp(&r2) {
-                    std::cmp::Ordering::Greater => wins += 1,
-                    std::cmp::Ordering::Equal => ties += 1,
-                    std::cmp::Ordering::Less => losses += 1,
-                }
-            }
+/// Equity against a weighted range. From the flop on this is
+/// `exact_equity_vs_weighted_range`; preflop every live villain combo gets
+/// the same number of sampled runouts and its result counts at its frequency.
+pub fn equity_vs_weighted_range(
+    hand: &[Card],
+    villain_range: &[(String, f64)],
+    board: Option<&[Card]>,
+    simulations: usize,
+) -> GtoResult<EquityResult> {
+    equity_vs_weighted_range_sampled(hand, villain_range, board, &[], simulations, Sampling::default())
+}
+
+/// `equity_vs_weighted_range` with `dead_cards` out of the deck and a
+/// precision target and seed for the preflop sampling. Each batch deals
+/// every combo's runouts in parallel.
+pub fn equity_vs_weighted_range_sampled(
+    hand: &[Card],
+    villain_range: &[(String, f64)],
+    board: Option<&[Card]>,
+    dead_cards: &[Card],
+    simulations: usize,
+    sampling: Sampling,
+) -> GtoResult<EquityResult> {
+    check_sampling(simulations, sampling)?;
+    let board = board.unwrap_or(&[]);
+    if board.len() >= EXACT_MIN_BOARD {
+        return exact_equity_vs_weighted_range(hand, villain_range, board, dead_cards);
+    }
 
-            (wins, ties, losses)
+    let hero: [u8; 2] = [card_to_index(&hand[0]), card_to_index(&hand[1])];
+    let board_idx: Vec<u8> = board.iter().map(card_to_index).collect();
+    let all_combos = weighted_combos(hand, villain_range, board, dead_cards)?;
+    let dead_idx = indices(dead_cards);
+    let decks: Vec<Vec<u8>> = all_combos
+        .iter()
+        .map(|(villain, _)| {
+            let mut dead = Vec::with_capacity(4 + board_idx.len());
+            dead.extend_from_slice(&hero);
+            dead.extend_from_slice(&board_idx);
+            dead.extend_from_slice(villain);
+            remaining_deck_excluding(&dead, &dead_idx)
         })
         .collect();
 
-    let (wins, ties, losses) = results
+    Ok(sample_batches(&all_combos, simulations, sampling, |batch, sims| {
+        all_combos
+            .par_iter()
+            .zip(&decks)
+            .enumerate()
+            .map(|(i, ((villain, _), deck))| {
+                let mut rng = stream_rng(sampling.seed, batch << 32 | i as u64);
+                sample_showdowns(hero, *villain, &board_idx, deck, sims, &mut rng)
+            })
+            .collect()
+    }))
+}
+
+/// Hero's exact equity against a weighted range after each card that can
+/// come next on a flop or turn `board`, in deck order: the spread behind the
+/// single equity number. A card that blocks every villain combo is left out.
+pub fn equity_distribution(
+    hand: &[Card],
+    villain_range: &[(String, f64)],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> GtoResult<Vec<(Card, f64)>> {
+    if !(3..=4).contains(&board.len()) {
+        return Err(GtoError::InvalidValue(format!(
+            "an equity distribution needs a flop or turn board, got {} cards",
+            board.len()
+        )));
+    }
+    // Reports a bad range or conflicting cards up front
+    weighted_combos(hand, villain_range, board, dead_cards)?;
+
+    let dealt = indices(&[hand, board].concat());
+    let next_cards = remaining_deck_excluding(&dealt, &indices(dead_cards));
+    next_cards
+        .par_iter()
+        .filter_map(|&card| {
+            let card = index_to_card(card);
+            let runout = [board, &[card]].concat();
+            match exact_equity_vs_weighted_range(hand, villain_range, &runout, dead_cards) {
+                Ok(result) => Some(Ok((card, result.equity()))),
+                Err(GtoError::NoValidCombos) => None,
+                Err(e) => Some(Err(e)),
+            }
+        })
+        .collect()
+}
+
+/// Showdowns (matchups × runouts) up to which range-vs-range equity is
+/// enumerated exactly instead of sampled.
+pub const EXACT_MAX_SHOWDOWNS: usize = 50_000_000;
+
+/// Fewest runouts a sampled range-vs-range run deals, however many matchups.
+const MIN_SAMPLED_RUNOUTS: usize = 100;
+
+/// One entry of a parsed range (e.g. "AKs") in a range-vs-range breakdown.
+#[derive(Debug, Clone)]
+pub struct HandClassEquity {
+    pub hand: String,
+    /// Combos left after board removal.
+    pub combos: usize,
+    /// Equity against the whole opposing range; `None` when every matchup
+    /// is blocked.
+    pub equity: Option<f64>,
+}
+
+pub struct RangeEquityResult {
+    /// The first range's equity; the second's is `1 - equity`.
+    pub equity: f64,
+    /// Entries of each range, in range order.
+    pub classes1: Vec<HandClassEquity>,
+    pub classes2: Vec<HandClassEquity>,
+    /// Combo pairs that don't share a card.
+    pub matchups: usize,
+    /// Runouts dealt: all of them when `exact`, else a sample.
+    pub runouts: usize,
+    pub exact: bool,
+}
+
+/// Live combos of each range entry: (entry, weight, combos).
+type RangeCombos = Vec<(String, f64, Vec<[u8; 2]>)>;
+
+fn range_combos(range: &[(String, f64)], board: &[u8]) -> GtoResult<RangeCombos> {
+    range
+        .iter()
+        .filter(|(_, w)| *w > 0.0)
+        .map(|(hand, w)| {
+            // expand_range_to_combos skips bad notation; report it instead
+            hand_combos(hand)?;
+            let combos = expand_range_to_combos(std::slice::from_ref(hand), board);
+            Ok((hand.clone(), *w, combos.iter().map(|c| [c.0, c.1]).collect()))
+        })
+        .collect()
+}
+
+/// Per-combo weighted (equity points, showdowns) for both ranges after one
+/// completed board. Matchups sharing a card with each other or the runout
+/// are skipped.
+fn tally_runout(
+    combos1: &[([u8; 2], f64)],
+    combos2: &[([u8; 2], f64)],
+    runout: &[u8],
+    tally: &mut [(f64, f64)],
+) {
+    let live = |c: &[u8; 2]| !runout.contains(&c[0]) && !runout.contains(&c[1]);
+    let score = |c: &[u8; 2]| {
+        let mut all = [0u8; 7];
+        all[0] = c[0];
+        all[1] = c[1];
+        all[2..].copy_from_slice(runout);
+        evaluate_fast(&all)
+    };
+    let scores2: Vec<Option<u32>> = combos2.iter().map(|(c, _)| live(c).then(|| score(c))).collect();
+    let offset = combos1.len();
+    for (i, (c1, w1)) in combos1.iter().enumerate() {
+        if !live(c1) {
+            continue;
+        }
+        let s1 = score(c1);
+        for (j, ((c2, w2), s2)) in combos2.iter().zip(&scores2).enumerate() {
+            let Some(s2) = *s2 else { continue };
+            if c1.contains(&c2[0]) || c1.contains(&c2[1]) {
+                continue;
+            }
+            let points = match s1.cmp(&s2) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+            let w = w1 * w2;
+            tally[i].0 += w * points;
+            tally[i].1 += w;
+            tally[offset + j].0 += w * (1.0 - points);
+            tally[offset + j].1 += w;
+        }
+    }
+}
+
+fn add_tallies(mut a: Vec<(f64, f64)>, b: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    for (x, y) in a.iter_mut().zip(b) {
+        x.0 += y.0;
+        x.1 += y.1;
+    }
+    a
+}
+
+/// Equity of one range against another, overall and per range entry. Card
+/// conflicts between the two combos of a matchup remove that matchup. Runouts
+/// are enumerated when matchups × runouts is at most `EXACT_MAX_SHOWDOWNS`
+/// (flop or later), otherwise sampled: `simulations / matchups` runouts, at
+/// least `MIN_SAMPLED_RUNOUTS`.
+pub fn equity_range_vs_range(
+    range1: &[(String, f64)],
+    range2: &[(String, f64)],
+    board: Option<&[Card]>,
+    simulations: usize,
+) -> GtoResult<RangeEquityResult> {
+    if simulations == 0 {
+        return Err(GtoError::InvalidValue("simulations must be positive".to_string()));
+    }
+    let board_idx: Vec<u8> = board.unwrap_or(&[]).iter().map(card_to_index).collect();
+    let classes1 = range_combos(range1, &board_idx)?;
+    let classes2 = range_combos(range2, &board_idx)?;
+    let flat = |classes: &RangeCombos| -> Vec<([u8; 2], f64)> {
+        classes.iter().flat_map(|(_, w, combos)| combos.iter().map(move |&c| (c, *w))).collect()
+    };
+    let combos1 = flat(&classes1);
+    let combos2 = flat(&classes2);
+    let matchups = combos1
         .iter()
-        .fold((0u64, 0u64, 0u64), |acc, &(w, t, l)| {
-            (acc.0 + w, acc.1 + t, acc.2 + l)
-        });
-
-    let total = (wins + ties + losses) as f64;
-    Ok(EquityResult {
-        win: wins as f64 / total,
-        tie: ties as f64 / total,
-        lose: losses as f64 / total,
-        simulations: total as usize,
+        .map(|(c1, _)| combos2.iter().filter(|(c2, _)| !c1.contains(&c2[0]) && !c1.contains(&c2[1])).count())
+        .sum::<usize>();
+    if matchups == 0 {
+        return Err(GtoError::NoValidCombos);
+    }
+
+    let deck = remaining_deck(&board_idx);
+    let to_come = 5 - board_idx.len();
+    let all_runouts = match to_come {
+        0 => 1,
+        1 => deck.len(),
+        2 => deck.len() * (deck.len() - 1) / 2,
+        _ => usize::MAX,
+    };
+    let exact = all_runouts.saturating_mul(matchups) <= EXACT_MAX_SHOWDOWNS;
+
+    // Runouts are tallied in chunks, one tally per chunk
+    let size = combos1.len() + combos2.len();
+    let (tally, runouts) = if exact {
+        let runouts: Vec<Vec<u8>> = match to_come {
+            0 => vec![board_idx.clone()],
+            1 => deck.iter().map(|&c| [board_idx.as_slice(), &[c]].concat()).collect(),
+            _ => (0..deck.len())
+                .flat_map(|i| ((i + 1)..deck.len()).map(move |j| (i, j)))
+                .map(|(i, j)| [board_idx.as_slice(), &[deck[i], deck[j]]].concat())
+                .collect(),
+        };
+        let tally = runouts
+            .par_chunks(64)
+            .map(|chunk| {
+                let mut tally = vec![(0.0, 0.0); size];
+                for runout in chunk {
+                    tally_runout(&combos1, &combos2, runout, &mut tally);
+                }
+                tally
+            })
+            .reduce(|| vec![(0.0, 0.0); size], add_tallies);
+        (tally, runouts.len())
+    } else {
+        let count = (simulations / matchups).max(MIN_SAMPLED_RUNOUTS);
+        let chunks: Vec<usize> = (0..count).step_by(64).map(|start| 64.min(count - start)).collect();
+        let tally = chunks
+            .par_iter()
+            .map(|&n| {
+                let mut rng = rand::thread_rng();
+                let mut tally = vec![(0.0, 0.0); size];
+                let mut runout = board_idx.clone();
+                for _ in 0..n {
+                    runout.truncate(board_idx.len());
+                    runout.extend(deck.choose_multiple(&mut rng, to_come));
+                    tally_runout(&combos1, &combos2, &runout, &mut tally);
+                }
+                tally
+            })
+            .reduce(|| vec![(0.0, 0.0); size], add_tallies);
+        (tally, count)
+    };
+
+    let summarize = |classes: &RangeCombos, tally: &[(f64, f64)]| -> Vec<HandClassEquity> {
+        let mut start = 0;
+        classes
+            .iter()
+            .map(|(hand, _, combos)| {
+                let (points, total) = tally[start..start + combos.len()]
+                    .iter()
+                    .fold((0.0, 0.0), |acc, t| (acc.0 + t.0, acc.1 + t.1));
+                start += combos.len();
+                HandClassEquity {
+                    hand: hand.clone(),
+                    combos: combos.len(),
+                    equity: (total > 0.0).then(|| points / total),
+                }
+            })
+            .collect()
+    };
+    let (tally1, tally2) = tally.split_at(combos1.len());
+    let (points, total) = tally1.iter().fold((0.0, 0.0), |acc, t| (acc.0 + t.0, acc.1 + t.1));
+
+    Ok(RangeEquityResult {
+        equity: if total > 0.0 { points / total } else { 0.5 },
+        classes1: summarize(&classes1, tally1),
+        classes2: summarize(&classes2, tally2),
+        matchups,
+        runouts,
+        exact,
     })
 }
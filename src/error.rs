@@ -14,9 +14,16 @@ pub enum GtoError {
     #[error("Invalid board notation: {0}")]
     InvalidBoardNotation(String),
 
+    #[error("A board has at most 5 cards, got {0}")]
+    BoardTooLong(usize),
+
     #[error("Invalid hand notation: {0}")]
     InvalidHandNotation(String),
 
+    /// Range terms `parse_range` can't read, each with the reason.
+    #[error("Invalid range: can't read {}", .0.join(", "))]
+    InvalidRange(Vec<String>),
+
     #[error("Need at least {need} cards, got {got}")]
     NotEnoughCards { need: usize, got: usize },
 
@@ -26,6 +33,12 @@ pub enum GtoError {
     #[error("Hand must be exactly 2 cards")]
     InvalidHandSize,
 
+    #[error("Cards used more than once: {0}")]
+    ConflictingCards(String),
+
+    #[error("Dead card {card} is {place}")]
+    DeadCardDealt { card: String, place: &'static str },
+
     #[error("Invalid value: {0}")]
     InvalidValue(String),
 
@@ -40,3 +53,61 @@ pub enum GtoError {
 }
 
 pub type GtoResult<T> = Result<T, GtoError>;
+
+/// Errors building a solver config from a spot (board, ranges, pot, stack).
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SolverError {
+    #[error("Invalid board: {reason}")]
+    InvalidBoard { reason: String },
+
+    #[error("Board must have exactly {expected} cards, got {got}")]
+    WrongBoardSize { expected: usize, got: usize },
+
+    #[error("{side} range has no combos")]
+    EmptyRange { side: &'static str },
+
+    #[error("{card} appears more than once")]
+    ConflictingCards { card: String },
+
+    #[error("Missing {field}")]
+    MissingField { field: &'static str },
+
+    #[error("Invalid {field}: {reason}")]
+    InvalidConfig { field: &'static str, reason: String },
+
+    #[error("The tree has {nodes} decision nodes, over the limit of {limit}")]
+    TreeTooLarge { nodes: usize, limit: usize },
+}
+
+impl SolverError {
+    /// What to change to fix the error, for the CLI to show under it.
+    pub fn suggestion(&self) -> String {
+        match self {
+            SolverError::InvalidBoard { .. } => "Write boards as rank+suit pairs, e.g. Ks9d4c".to_string(),
+            SolverError::WrongBoardSize { expected, got } => match street_name(*got) {
+                Some(street) => format!("A {}-card board is a {} spot: use `gto solve {}`", got, street, street),
+                None => format!("Give exactly {} board cards", expected),
+            },
+            SolverError::EmptyRange { .. } => {
+                "Check the range string; combos that share a card with the board are removed".to_string()
+            }
+            SolverError::ConflictingCards { .. } => "Every card can only be dealt once".to_string(),
+            SolverError::MissingField { field } => format!("Set the {} before building", field),
+            SolverError::InvalidConfig { .. } => "See `gto solve --help` for valid values".to_string(),
+            SolverError::TreeTooLarge { .. } => {
+                "Use fewer --raise-sizes or a lower --max-raises, or raise --max-nodes".to_string()
+            }
+        }
+    }
+}
+
+fn street_name(board_cards: usize) -> Option<&'static str> {
+    match board_cards {
+        3 => Some("flop"),
+        4 => Some("turn"),
+        5 => Some("river"),
+        _ => None,
+    }
+}
+
+pub type SolverResult<T> = Result<T, SolverError>;
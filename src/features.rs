@@ -0,0 +1,139 @@
+//! Fixed-length feature encoding of a postflop decision, and a canonical action
+//! space that every solver tree's actions map onto. Used by the training-data
+//! export.
+//!
+//! Feature layout (`FEATURE_LEN` = 108 floats):
+//!   [0..52)    board cards, one-hot by card index (see `card_encoding`)
+//!   [52..104)  hero hole cards, one-hot by card index
+//!   104        pot / 100 (bb)
+//!   105        acting stack / 100 (bb)
+//!   106        SPR (stack / pot), capped at `SPR_CAP`, divided by `SPR_CAP`
+//!   107        1.0 if hero is in position, else 0.0
+
+use crate::postflop_tree::Action;
+
+pub const FEATURE_LEN: usize = 108;
+
+/// Canonical action space: tree actions are mapped onto these slots.
+pub const ACTION_TAXONOMY: [&str; 6] = [
+    "check_call",
+    "bet_small",
+    "bet_medium",
+    "bet_large",
+    "all_in",
+    "fold",
+];
+
+const SPR_CAP: f64 = 20.0;
+/// Bets/raises below this fraction of the pot are "small".
+const SMALL_MAX: f64 = 0.5;
+/// Bets/raises below this fraction of the pot (and at least `SMALL_MAX`) are "medium".
+const MEDIUM_MAX: f64 = 0.9;
+
+/// Encode a decision point as `FEATURE_LEN` floats.
+pub fn encode_state(board: &[u8], hole: [u8; 2], pot: f64, stack: f64, hero_is_ip: bool) -> Vec<f32> {
+    let mut features = vec![0.0f32; FEATURE_LEN];
+    for &c in board {
+        features[c as usize] = 1.0;
+    }
+    for c in hole {
+        features[52 + c as usize] = 1.0;
+    }
+    features[104] = (pot / 100.0) as f32;
+    features[105] = (stack / 100.0) as f32;
+    let spr = if pot > 0.0 { (stack / pot).min(SPR_CAP) } else { SPR_CAP };
+    features[106] = (spr / SPR_CAP) as f32;
+    features[107] = if hero_is_ip { 1.0 } else { 0.0 };
+    features
+}
+
+/// Slot in `ACTION_TAXONOMY` for one tree action.
+///
+/// `pot` and `stack` are the node's pot and the actor's remaining stack;
+/// `call_amount` is what the actor faces (0 when not facing a bet). Raise sizes
+/// are measured as the raise increment over the pot after calling.
+pub fn action_class(action: &Action, pot: f64, stack: f64, call_amount: f64) -> usize {
+    let (put_in, fraction) = match *action {
+        Action::Check | Action::Call(_) => return 0,
+        Action::Fold => return 5,
+        Action::Bet(amt) => (amt, amt / pot.max(1e-9)),
+        Action::Raise(total) => (total, (total - call_amount) / (pot + call_amount).max(1e-9)),
+    };
+    if put_in >= stack - 0.01 {
+        4
+    } else if fraction < SMALL_MAX {
+        1
+    } else if fraction < MEDIUM_MAX {
+        2
+    } else {
+        3
+    }
+}
+
+/// Fold a node's action frequencies into the canonical action space.
+pub fn canonical_strategy(actions: &[Action], freqs: &[f64], pot: f64, stack: f64) -> [f64; 6] {
+    let call_amount = actions
+        .iter()
+        .find_map(|a| if let Action::Call(c) = a { Some(*c) } else { None })
+        .unwrap_or(0.0);
+    let mut out = [0.0; 6];
+    for (action, &f) in actions.iter().zip(freqs) {
+        out[action_class(action, pot, stack, call_amount)] += f;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_state_layout() {
+        let f = encode_state(&[0, 17, 51], [4, 9], 10.0, 50.0, true);
+        assert_eq!(f.len(), FEATURE_LEN);
+        assert_eq!(f[..52].iter().sum::<f32>(), 3.0);
+        assert_eq!(f[17], 1.0);
+        assert_eq!(f[52..104].iter().sum::<f32>(), 2.0);
+        assert_eq!(f[52 + 9], 1.0);
+        assert!((f[104] - 0.1).abs() < 1e-6);
+        assert!((f[105] - 0.5).abs() < 1e-6);
+        assert!((f[106] - 5.0 / 20.0).abs() < 1e-6);
+        assert_eq!(f[107], 1.0);
+    }
+
+    #[test]
+    fn test_encode_state_caps_spr() {
+        let f = encode_state(&[0, 1, 2], [3, 4], 1.0, 500.0, false);
+        assert_eq!(f[106], 1.0);
+        assert_eq!(f[107], 0.0);
+    }
+
+    #[test]
+    fn test_action_class_bet_sizes() {
+        assert_eq!(action_class(&Action::Check, 10.0, 50.0, 0.0), 0);
+        assert_eq!(action_class(&Action::Bet(3.3), 10.0, 50.0, 0.0), 1);
+        assert_eq!(action_class(&Action::Bet(7.5), 10.0, 50.0, 0.0), 2);
+        assert_eq!(action_class(&Action::Bet(10.0), 10.0, 50.0, 0.0), 3);
+        assert_eq!(action_class(&Action::Bet(50.0), 10.0, 50.0, 0.0), 4);
+        assert_eq!(action_class(&Action::Fold, 10.0, 50.0, 5.0), 5);
+    }
+
+    #[test]
+    fn test_action_class_raise_uses_increment() {
+        // Facing 5 into 15: call makes it 20, raising 20 more is pot-sized
+        assert_eq!(action_class(&Action::Call(5.0), 15.0, 50.0, 5.0), 0);
+        assert_eq!(action_class(&Action::Raise(25.0), 15.0, 50.0, 5.0), 3);
+        assert_eq!(action_class(&Action::Raise(12.0), 15.0, 50.0, 5.0), 1);
+    }
+
+    #[test]
+    fn test_canonical_strategy_merges_slots() {
+        let actions = [Action::Check, Action::Bet(3.3), Action::Bet(4.0), Action::Bet(50.0)];
+        let s = canonical_strategy(&actions, &[0.4, 0.2, 0.1, 0.3], 10.0, 50.0);
+        let expected = [0.4, 0.3, 0.0, 0.0, 0.3, 0.0];
+        for (got, want) in s.iter().zip(&expected) {
+            assert!((got - want).abs() < 1e-9, "{:?}", s);
+        }
+        assert!((s.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+}
@@ -11,6 +11,10 @@
 //!
 //! This keeps all hands at the same tree position contiguous for good
 //! cache locality during CFR iteration.
+//!
+//! Linear CFR and DCFR discount lazily: each info set remembers the iteration
+//! it was last updated in and catches up on the missed discounts at its next
+//! update, so info sets that aren't visited cost nothing.
 
 /// Flat-array CFR+ storage.
 ///
@@ -19,6 +23,8 @@
 /// strategy weights are stored in parallel contiguous arrays.
 use serde::{Serialize, Deserialize};
 
+use crate::cfr::{CfrVariant, Discounts};
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct FlatCfr {
     regrets: Vec<f32>,
@@ -29,6 +35,13 @@ pub struct FlatCfr {
     num_hands: Vec<u16>,
     /// Start offset in the data arrays for each node.
     offsets: Vec<u32>,
+    variant: CfrVariant,
+    /// Start of each node's hands in `last_iteration`.
+    hand_offsets: Vec<u32>,
+    /// Iteration each info set was last updated in (empty for CFR+).
+    last_iteration: Vec<u32>,
+    #[serde(skip)]
+    discounts: Discounts,
 }
 
 impl FlatCfr {
@@ -37,28 +50,47 @@ impl FlatCfr {
     /// Nodes are indexed 0..nodes.len()-1. The order must match the node_ids
     /// used during CFR traversal.
     pub fn new(nodes: &[(u8, u16)]) -> Self {
+        FlatCfr::with_variant(nodes, CfrVariant::CfrPlus)
+    }
+
+    /// Like [`FlatCfr::new`], running the given CFR variant.
+    pub fn with_variant(nodes: &[(u8, u16)], variant: CfrVariant) -> Self {
         let mut offsets = Vec::with_capacity(nodes.len());
+        let mut hand_offsets = Vec::with_capacity(nodes.len());
         let mut num_actions = Vec::with_capacity(nodes.len());
         let mut num_hands = Vec::with_capacity(nodes.len());
         let mut offset: u32 = 0;
+        let mut hand_offset: u32 = 0;
 
         for &(actions, hands) in nodes {
             offsets.push(offset);
+            hand_offsets.push(hand_offset);
             num_actions.push(actions);
             num_hands.push(hands);
             offset += actions as u32 * hands as u32;
+            hand_offset += hands as u32;
         }
 
         let total = offset as usize;
+        let info_sets = if variant == CfrVariant::CfrPlus { 0 } else { hand_offset as usize };
         FlatCfr {
             regrets: vec![0.0f32; total],
             cum_strategy: vec![0.0f32; total],
             num_actions,
             num_hands,
             offsets,
+            variant,
+            hand_offsets,
+            last_iteration: vec![0; info_sets],
+            discounts: Discounts::default(),
         }
     }
 
+    /// The CFR variant this instance runs.
+    pub fn variant(&self) -> CfrVariant {
+        self.variant
+    }
+
     /// Number of nodes in this instance.
     #[inline]
     pub fn num_nodes(&self) -> usize {
@@ -71,17 +103,31 @@ impl FlatCfr {
         self.num_actions[node]
     }
 
+    /// Number of hands (buckets) at the given node.
+    #[inline]
+    pub fn node_num_hands(&self, node: usize) -> u16 {
+        self.num_hands[node]
+    }
+
     /// Total number of f32 entries (regrets or cum_strategy).
     pub fn total_entries(&self) -> usize {
         self.regrets.len()
     }
 
+    /// True when `other` has the same nodes, actions and hands, i.e. its
+    /// data can stand in for this instance's.
+    pub fn same_shape(&self, other: &FlatCfr) -> bool {
+        self.num_actions == other.num_actions && self.num_hands == other.num_hands
+    }
+
     /// Memory usage in bytes (both arrays).
     pub fn memory_bytes(&self) -> usize {
         self.regrets.len() * 4 * 2
             + self.num_actions.len()
             + self.num_hands.len() * 2
             + self.offsets.len() * 4
+            + self.hand_offsets.len() * 4
+            + self.last_iteration.len() * 4
     }
 
     // -----------------------------------------------------------------------
@@ -149,8 +195,29 @@ impl FlatCfr {
         }
     }
 
+    /// Warm-start one info set from a known strategy.
+    ///
+    /// Regrets are set to `strategy * regret_scale` so regret matching starts at
+    /// `strategy`; the cumulative strategy gets `strategy * weight`, i.e. the seed
+    /// counts as `weight` units of reach in the average.
+    pub fn seed(&mut self, node: usize, hand: usize, strategy: &[f32], regret_scale: f32, weight: f32) {
+        let na = self.num_actions[node] as usize;
+        let base = self.base(node, hand);
+        for (i, &p) in strategy[..na].iter().enumerate() {
+            self.regrets[base + i] = p.max(0.0) * regret_scale;
+            self.cum_strategy[base + i] = p.max(0.0) * weight;
+        }
+    }
+
+    /// Overwrite one info set's average strategy, leaving regrets alone.
+    pub fn set_average_strategy(&mut self, node: usize, hand: usize, strategy: &[f32]) {
+        let na = self.num_actions[node] as usize;
+        let base = self.base(node, hand);
+        self.cum_strategy[base..base + na].copy_from_slice(&strategy[..na]);
+    }
+
     // -----------------------------------------------------------------------
-    // CFR+ update
+    // CFR update
     // -----------------------------------------------------------------------
 
     /// Update regrets and cumulative strategy for one info set.
@@ -158,8 +225,9 @@ impl FlatCfr {
     /// - `action_values`: counterfactual value of each action (len = num_actions)
     /// - `node_value`: weighted value of the node under current strategy
     /// - `reach_prob`: probability of reaching this info set (for strategy weighting)
+    /// - `iteration`: 0-based solver iteration, for Linear CFR / DCFR weighting
     ///
-    /// Regrets are floored at 0.0 (CFR+).
+    /// Under CFR+ regrets are floored at 0.0 and `iteration` is unused.
     #[inline]
     pub fn update(
         &mut self,
@@ -168,7 +236,12 @@ impl FlatCfr {
         action_values: &[f32],
         node_value: f32,
         reach_prob: f32,
+        iteration: usize,
     ) {
+        if self.variant != CfrVariant::CfrPlus {
+            self.update_discounted(node, hand, action_values, node_value, reach_prob, iteration);
+            return;
+        }
         let na = self.num_actions[node] as usize;
         let base = self.base(node, hand);
 
@@ -192,6 +265,56 @@ impl FlatCfr {
             self.cum_strategy[base + i] += reach_prob * sigma;
         }
     }
+
+    /// Linear CFR / DCFR update: discount the info set up to `iteration`,
+    /// then add this iteration's regrets (not floored) and strategy weight.
+    fn update_discounted(
+        &mut self,
+        node: usize,
+        hand: usize,
+        action_values: &[f32],
+        node_value: f32,
+        reach_prob: f32,
+        iteration: usize,
+    ) {
+        let na = self.num_actions[node] as usize;
+        let base = self.base(node, hand);
+        let info_set = self.hand_offsets[node] as usize + hand;
+        let last = self.last_iteration[info_set] as usize;
+        let [positive, negative, strat] = self.discounts.between(self.variant, last, iteration).map(|f| f as f32);
+        self.last_iteration[info_set] = iteration as u32;
+
+        let mut positive_sum: f32 = 0.0;
+        for (r, &v) in self.regrets[base..base + na].iter_mut().zip(action_values) {
+            *r *= if *r > 0.0 { positive } else { negative };
+            *r += v - node_value;
+            positive_sum += r.max(0.0);
+        }
+
+        // Like CFR+ above, accumulate the updated strategy
+        for i in 0..na {
+            let sigma = if positive_sum > 0.0 {
+                self.regrets[base + i].max(0.0) / positive_sum
+            } else {
+                1.0 / na as f32
+            };
+            let s = &mut self.cum_strategy[base + i];
+            *s = *s * strat + reach_prob * sigma;
+        }
+    }
+}
+
+/// A collected regret update for deferred application after parallel
+/// traversal, which only reads the tables.
+pub(crate) struct RegretUpdate {
+    /// 0 = flop, 1 = turn, 2 = river
+    pub street: u8,
+    pub node_id: usize,
+    /// Hand index or bucket within the node.
+    pub bucket: usize,
+    pub action_values: Vec<f32>,
+    pub node_value: f32,
+    pub reach_prob: f32,
 }
 
 #[cfg(test)]
@@ -232,7 +355,7 @@ mod tests {
 
         // Action 0 has value 10, action 1 has value -5, node value = 2.5
         // (as if strategy was [0.5, 0.5])
-        cfr.update(0, 0, &[10.0, -5.0], 2.5, 1.0);
+        cfr.update(0, 0, &[10.0, -5.0], 2.5, 1.0, 0);
 
         let mut out = [0.0f32; 2];
         cfr.current_strategy(0, 0, &mut out);
@@ -243,17 +366,33 @@ mod tests {
         assert!(out[1] < 1e-6);
     }
 
+    #[test]
+    fn seed_sets_current_and_average_strategy() {
+        let mut cfr = FlatCfr::new(&[(3, 2)]);
+        cfr.seed(0, 1, &[0.2, 0.0, 0.8], 5.0, 100.0);
+
+        let mut out = [0.0f32; 3];
+        cfr.current_strategy(0, 1, &mut out);
+        assert!((out[0] - 0.2).abs() < 1e-6 && out[1] < 1e-6 && (out[2] - 0.8).abs() < 1e-6);
+        cfr.average_strategy(0, 1, &mut out);
+        assert!((out[0] - 0.2).abs() < 1e-6 && (out[2] - 0.8).abs() < 1e-6);
+
+        // Other hands untouched
+        cfr.current_strategy(0, 0, &mut out);
+        assert!((out[0] - 1.0 / 3.0).abs() < 1e-6);
+    }
+
     #[test]
     fn cfr_plus_floors_regret_at_zero() {
         let mut cfr = FlatCfr::new(&[(2, 1)]);
 
         // First update: give action 1 positive regret
-        cfr.update(0, 0, &[-10.0, 5.0], 0.0, 1.0);
+        cfr.update(0, 0, &[-10.0, 5.0], 0.0, 1.0, 0);
         // regret[0] = max(0 + -10, 0) = 0
         // regret[1] = max(0 + 5, 0) = 5
 
         // Second update: punish action 1
-        cfr.update(0, 0, &[3.0, -20.0], 0.0, 1.0);
+        cfr.update(0, 0, &[3.0, -20.0], 0.0, 1.0, 0);
         // regret[0] = max(0 + 3, 0) = 3
         // regret[1] = max(5 + -20, 0) = 0  (floored!)
 
@@ -268,9 +407,9 @@ mod tests {
         let mut cfr = FlatCfr::new(&[(2, 3)]);
 
         // Update hand 0 to prefer action 0
-        cfr.update(0, 0, &[10.0, 0.0], 5.0, 1.0);
+        cfr.update(0, 0, &[10.0, 0.0], 5.0, 1.0, 0);
         // Update hand 1 to prefer action 1
-        cfr.update(0, 1, &[0.0, 10.0], 5.0, 1.0);
+        cfr.update(0, 1, &[0.0, 10.0], 5.0, 1.0, 0);
         // Hand 2 untouched
 
         let mut out = [0.0f32; 2];
@@ -290,7 +429,7 @@ mod tests {
         let mut cfr = FlatCfr::new(&[(3, 2), (2, 2)]);
 
         // Update node 0, hand 0
-        cfr.update(0, 0, &[10.0, 0.0, 0.0], 3.33, 1.0);
+        cfr.update(0, 0, &[10.0, 0.0, 0.0], 3.33, 1.0, 0);
         // Node 1 should be unaffected
         let mut out = [0.0f32; 2];
         cfr.current_strategy(1, 0, &mut out);
@@ -303,7 +442,7 @@ mod tests {
 
         // Multiple updates accumulate into average strategy
         for _ in 0..10 {
-            cfr.update(0, 0, &[5.0, 0.0], 2.5, 1.0);
+            cfr.update(0, 0, &[5.0, 0.0], 2.5, 1.0, 0);
         }
 
         let mut out = [0.0f32; 2];
@@ -312,6 +451,29 @@ mod tests {
         assert!(out[0] > out[1]);
     }
 
+    #[test]
+    fn dcfr_discounts_skipped_iterations() {
+        let mut dcfr = FlatCfr::with_variant(&[(2, 1)], CfrVariant::DCFR);
+        dcfr.update(0, 0, &[-4.0, 6.0], 0.0, 1.0, 0);
+        // Three iterations later: negative regret halves each time (β = 0),
+        // positive regret shrinks by t^1.5 / (t^1.5 + 1) for t = 1, 2, 3
+        dcfr.update(0, 0, &[0.0, 0.0], 0.0, 1.0, 3);
+        let positive: f32 = (1..=3).map(|t| {
+            let p = (t as f32).powf(1.5);
+            p / (p + 1.0)
+        }).product();
+        assert!((dcfr.regrets[0] - (-0.5)).abs() < 1e-5);
+        assert!((dcfr.regrets[1] - 6.0 * positive).abs() < 1e-5);
+
+        // Unlike CFR+, negative regret carries over
+        let mut linear = FlatCfr::with_variant(&[(2, 1)], CfrVariant::LinearCfr);
+        linear.update(0, 0, &[-10.0, 5.0], 0.0, 1.0, 0);
+        linear.update(0, 0, &[3.0, 0.0], 0.0, 1.0, 1);
+        let mut out = [0.0f32; 2];
+        linear.current_strategy(0, 0, &mut out);
+        assert!(out[0] < 1e-6 && (out[1] - 1.0).abs() < 1e-6);
+    }
+
     #[test]
     fn memory_bytes_reasonable() {
         // 1000 nodes × 4 actions × 500 hands = 2M entries
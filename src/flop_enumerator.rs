@@ -34,6 +34,67 @@ pub fn generate_canonical_flops() -> Vec<String> {
     canonical_set.into_iter().collect()
 }
 
+/// Suit texture of a flop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuitTexture {
+    Rainbow,
+    TwoTone,
+    Monotone,
+}
+
+impl SuitTexture {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuitTexture::Rainbow => "rainbow",
+            SuitTexture::TwoTone => "two-tone",
+            SuitTexture::Monotone => "monotone",
+        }
+    }
+}
+
+/// Canonical rainbow, two-tone and monotone flops for a rank pattern like
+/// "K94" (any order, `T` for ten).
+///
+/// Textures the ranks can't form are left out: paired boards have no monotone
+/// version and trips are rainbow only. The two-tone board suits the highest
+/// cards it can (Ks9s4h, KsKh4h).
+pub fn texture_boards(ranks: &str) -> Result<Vec<(SuitTexture, String)>, String> {
+    let chars: Vec<char> = ranks.trim().chars().map(|c| c.to_ascii_uppercase()).collect();
+    if chars.len() != 3 {
+        return Err(format!("Rank pattern '{}' must have exactly 3 ranks (e.g. K94)", ranks));
+    }
+    let mut rank_idx = [0u8; 3];
+    for (i, c) in chars.iter().enumerate() {
+        rank_idx[i] = RANK_CHARS
+            .iter()
+            .position(|r| r == c)
+            .ok_or_else(|| format!("Invalid rank '{}' in '{}'", c, ranks))? as u8;
+    }
+    rank_idx.sort_by(|a, b| b.cmp(a));
+
+    let candidates: [(SuitTexture, &[[u8; 3]]); 3] = [
+        (SuitTexture::Rainbow, &[[0, 1, 2]]),
+        (SuitTexture::TwoTone, &[[0, 0, 1], [0, 1, 0], [0, 1, 1]]),
+        (SuitTexture::Monotone, &[[0, 0, 0]]),
+    ];
+    let mut boards = Vec::new();
+    for (texture, suit_patterns) in candidates {
+        // Same rank and suit twice is the same card
+        let valid = suit_patterns.iter().find(|suits| {
+            (0..3).all(|i| ((i + 1)..3).all(|j| rank_idx[i] != rank_idx[j] || suits[i] != suits[j]))
+        });
+        if let Some(suits) = valid {
+            let cards = [
+                (rank_idx[0], suits[0]),
+                (rank_idx[1], suits[1]),
+                (rank_idx[2], suits[2]),
+            ];
+            boards.push((texture, canonicalize(&cards)));
+        }
+    }
+    Ok(boards)
+}
+
 /// Map a 3-card flop to its canonical string representation.
 ///
 /// Two flops are equivalent under suit isomorphism if there exists a suit
@@ -94,13 +155,136 @@ fn permutations_within_rank_groups(cards: &[(u8, u8); 3]) -> Vec<[(u8, u8); 3]>
     }
 }
 
+/// Canonical form of a flop, turn or river board: the flop is canonicalized
+/// as in `generate_canonical_flops` and the turn and river are relabeled with
+/// the same suit mapping, so isomorphic boards give the same string
+/// ("Kh9s4c" and "Ks9h4d" both give "Ks9h4d").
+pub fn canonical_board(board: &str) -> Result<String, String> {
+    canonical_board_with_suits(board).map(|(canonical, _)| canonical)
+}
+
+/// `canonical_board`, plus the suit relabeling that produces it: entry `s` is
+/// the canonical suit for suit `s` (indices into "shdc"). Suits not on the
+/// board take the remaining canonical suits in order.
+fn canonical_board_with_suits(board: &str) -> Result<(String, [u8; 4]), String> {
+    let chars: Vec<char> = board.trim().chars().collect();
+    if !chars.len().is_multiple_of(2) || !(6..=10).contains(&chars.len()) {
+        return Err(format!("Board '{}' must be 3 to 5 cards, e.g. Ks9d4c", board));
+    }
+    let mut cards = Vec::with_capacity(chars.len() / 2);
+    for pair in chars.chunks(2) {
+        let rank = RANK_CHARS.iter().position(|&r| r == pair[0].to_ascii_uppercase());
+        let suit = SUIT_CHARS.iter().position(|&s| s == pair[1].to_ascii_lowercase());
+        match (rank, suit) {
+            (Some(r), Some(s)) => cards.push((r as u8, s as u8)),
+            _ => return Err(format!("Invalid card '{}{}' in board '{}'", pair[0], pair[1], board)),
+        }
+    }
+    for (i, c) in cards.iter().enumerate() {
+        if cards[..i].contains(c) {
+            return Err(format!("Duplicate card in board '{}'", board));
+        }
+    }
+
+    let mut flop = [cards[0], cards[1], cards[2]];
+    flop.sort_by_key(|c| std::cmp::Reverse(c.0));
+    let (canonical, suit_map) = permutations_within_rank_groups(&flop)
+        .into_iter()
+        .map(|ordering| {
+            let mut sequence = ordering.to_vec();
+            sequence.extend_from_slice(&cards[3..]);
+            first_appearance_suits(&sequence)
+        })
+        .min_by(|a, b| a.0.cmp(&b.0))
+        .unwrap();
+
+    let mut next = suit_map.iter().flatten().count() as u8;
+    Ok((
+        canonical,
+        suit_map.map(|s| {
+            s.unwrap_or_else(|| {
+                next += 1;
+                next - 1
+            })
+        }),
+    ))
+}
+
+/// The suit relabeling that turns board `from` into board `to` (entry `s`
+/// is the new suit for suit `s`, as indices into "shdc"), or `None` if the
+/// boards aren't suit-isomorphic. Turn and river cards must match in order.
+pub fn suit_permutation(from: &str, to: &str) -> Result<Option<[u8; 4]>, String> {
+    let (from_canonical, from_map) = canonical_board_with_suits(from)?;
+    let (to_canonical, to_map) = canonical_board_with_suits(to)?;
+    if from_canonical != to_canonical {
+        return Ok(None);
+    }
+    let mut to_inverse = [0u8; 4];
+    for (suit, &canonical) in to_map.iter().enumerate() {
+        to_inverse[canonical as usize] = suit as u8;
+    }
+    Ok(Some(from_map.map(|canonical| to_inverse[canonical as usize])))
+}
+
+/// Relabel the suits of a card string ("AhKd", a board) with a relabeling
+/// from `suit_permutation`. Characters that aren't suits are kept.
+pub fn relabel_suits(cards: &str, suit_map: &[u8; 4]) -> String {
+    cards
+        .chars()
+        .map(|c| match SUIT_CHARS.iter().position(|&s| s == c.to_ascii_lowercase()) {
+            Some(s) => SUIT_CHARS[suit_map[s] as usize],
+            None => c,
+        })
+        .collect()
+}
+
+/// Number of the 22,100 raw flops that are isomorphic to `board` (its
+/// weight when aggregating over canonical flops): 4 for a monotone or trips
+/// flop, up to 24 for an unpaired rainbow flop.
+pub fn flop_weight(board: &str) -> Result<usize, String> {
+    let canonical = canonical_board(board)?;
+    if canonical.len() != 6 {
+        return Err(format!("Board '{}' is not a flop", board));
+    }
+    let cards: Vec<(u8, u8)> = canonical
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let rank = RANK_CHARS.iter().position(|&r| r == pair[0] as char).unwrap_or(0);
+            let suit = SUIT_CHARS.iter().position(|&s| s == pair[1] as char).unwrap_or(0);
+            (rank as u8, suit as u8)
+        })
+        .collect();
+
+    // Distinct boards under all 24 suit relabelings
+    let mut images: BTreeSet<Vec<(u8, u8)>> = BTreeSet::new();
+    for a in 0..4u8 {
+        for b in (0..4u8).filter(|&b| b != a) {
+            for c in (0..4u8).filter(|&c| c != a && c != b) {
+                let d = 6 - a - b - c;
+                let relabel = [a, b, c, d];
+                let mut image: Vec<(u8, u8)> = cards.iter().map(|&(r, s)| (r, relabel[s as usize])).collect();
+                image.sort_unstable();
+                images.insert(image);
+            }
+        }
+    }
+    Ok(images.len())
+}
+
 /// Compute canonical string by mapping suits to 0,1,2,3 in order of first
 /// appearance (left to right).
-fn first_appearance_canonical(cards: &[(u8, u8); 3]) -> String {
+fn first_appearance_canonical(cards: &[(u8, u8)]) -> String {
+    first_appearance_suits(cards).0
+}
+
+/// `first_appearance_canonical`, plus the canonical suit given to each suit
+/// that appears.
+fn first_appearance_suits(cards: &[(u8, u8)]) -> (String, [Option<u8>; 4]) {
     let mut suit_map: [Option<u8>; 4] = [None; 4];
     let mut next_suit: u8 = 0;
 
-    let mut result = String::with_capacity(6);
+    let mut result = String::with_capacity(cards.len() * 2);
     for &(rank, suit) in cards {
         let canonical_suit = match suit_map[suit as usize] {
             Some(s) => s,
@@ -115,7 +299,7 @@ fn first_appearance_canonical(cards: &[(u8, u8); 3]) -> String {
         result.push(SUIT_CHARS[canonical_suit as usize]);
     }
 
-    result
+    (result, suit_map)
 }
 
 /// Return the strategic priority score for a canonical flop string.
@@ -283,6 +467,39 @@ mod tests {
         assert_ne!(a, c, "Different two-tone patterns should be distinct");
     }
 
+    #[test]
+    fn test_texture_boards_unpaired() {
+        let boards = texture_boards("94k").unwrap();
+        assert_eq!(
+            boards,
+            vec![
+                (SuitTexture::Rainbow, "Ks9h4d".to_string()),
+                (SuitTexture::TwoTone, "Ks9s4h".to_string()),
+                (SuitTexture::Monotone, "Ks9s4s".to_string()),
+            ]
+        );
+        let canonical = generate_canonical_flops();
+        for (_, board) in &boards {
+            assert!(canonical.contains(board), "{} should be canonical", board);
+        }
+    }
+
+    #[test]
+    fn test_texture_boards_paired_and_trips() {
+        let paired = texture_boards("KK4").unwrap();
+        assert_eq!(
+            paired,
+            vec![
+                (SuitTexture::Rainbow, "KsKh4d".to_string()),
+                (SuitTexture::TwoTone, "KsKh4h".to_string()),
+            ]
+        );
+        let trips = texture_boards("777").unwrap();
+        assert_eq!(trips, vec![(SuitTexture::Rainbow, "7s7h7d".to_string())]);
+        assert!(texture_boards("K9").is_err());
+        assert!(texture_boards("K9X").is_err());
+    }
+
     #[test]
     fn test_strategic_priority_a_high_beats_low() {
         let a_high = strategic_priority("As7d2c");
@@ -333,4 +550,50 @@ mod tests {
         let r1 = rank_value(top.chars().nth(0).unwrap());
         assert_eq!(r1, 14, "Top board should be A-high, got '{}'", top);
     }
+
+    #[test]
+    fn test_flop_weights_cover_every_flop() {
+        let flops = generate_canonical_flops();
+        let total: usize = flops.iter().map(|f| flop_weight(f).unwrap()).sum();
+        assert_eq!(total, 22_100);
+        assert_eq!(flop_weight("Ks9h4d").unwrap(), 24);
+        assert_eq!(flop_weight("Ks9s4d").unwrap(), 12);
+        assert_eq!(flop_weight("Ks9s4s").unwrap(), 4);
+        assert_eq!(flop_weight("KsKh4d").unwrap(), 12);
+        assert_eq!(flop_weight("KsKhKd").unwrap(), 4);
+    }
+
+    #[test]
+    fn test_canonical_board_matches_isomorphic_boards() {
+        // Flops agree with the enumerator's canonical form
+        for flop in generate_canonical_flops().iter().take(50) {
+            assert_eq!(canonical_board(flop).unwrap(), *flop);
+        }
+        assert_eq!(canonical_board("Kh9s4c").unwrap(), canonical_board("Ks9d4c").unwrap());
+        assert_eq!(canonical_board("kh9S4c").unwrap(), "Ks9h4d");
+        // The turn keeps its suit relative to the flop
+        assert_eq!(canonical_board("Ks9d4cKd").unwrap(), canonical_board("Kh9c4sKc").unwrap());
+        assert_ne!(canonical_board("Ks9d4cKd").unwrap(), canonical_board("Ks9d4cKh").unwrap());
+        // Paired flops: either card order maps the turn the same way
+        assert_eq!(canonical_board("9s9d4c2s").unwrap(), canonical_board("9d9s4c2d").unwrap());
+        assert!(canonical_board("Ks9d").is_err());
+        assert!(canonical_board("KsKs4c").is_err());
+    }
+
+    #[test]
+    fn test_suit_permutation_maps_board_onto_board() {
+        for (from, to) in [("Ks9d4c", "Kh9c4d"), ("Ks9s4c7h", "Kd9d4h7s"), ("9s9d4c2s", "9h9c4s2h"), ("KsTd3c8h2s", "KhTs3d8c2h")] {
+            let map = suit_permutation(from, to).unwrap().expect("isomorphic");
+            assert_eq!(relabel_suits(from, &map), to);
+            let mut seen = map.to_vec();
+            seen.sort_unstable();
+            assert_eq!(seen, vec![0, 1, 2, 3], "{:?} is a permutation", map);
+        }
+        // Hands follow the board's relabeling: s->h, d->c, c->d, h->s
+        let map = suit_permutation("Ks9d4c", "Kh9c4d").unwrap().unwrap();
+        assert_eq!(relabel_suits("AhAs", &map), "AsAh");
+        assert_eq!(suit_permutation("Ks9d4c", "Ks9s4c").unwrap(), None);
+        assert_eq!(suit_permutation("Ks9d4cKd", "Ks9d4cKh").unwrap(), None);
+        assert!(suit_permutation("Ks9d", "Ks9d4c").is_err());
+    }
 }
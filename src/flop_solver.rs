@@ -17,20 +17,50 @@
 //! Hand combos are grouped into equity buckets (~200 per street) to further
 //! reduce the info set space.
 
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
 use rand::Rng;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::bucketing::assign_buckets;
-use crate::card_encoding::{index_to_card, remaining_deck};
-use crate::cards::parse_board;
-use crate::flat_cfr::FlatCfr;
-use crate::lookup_eval::evaluate_fast;
+use crate::bucketing::BucketingStrategy;
+use crate::cache_index::{solver_cache_dir, write_atomic};
+use crate::cache_key::{range_key, read_spot_cache};
+use crate::card_encoding::{card_to_index, index_to_card, remaining_deck_excluding};
+use crate::cards::{parse_board, Card};
+use crate::cfr::CfrVariant;
+use crate::error::SolverResult;
+use crate::display::{print_combo_strategies, range_grid, range_summary_table, strategy_grid};
+use crate::flat_cfr::{FlatCfr, RegretUpdate};
+use crate::flop_enumerator::{canonical_board, relabel_suits, suit_permutation};
+use crate::game_tree::hand_to_bucket;
 use crate::postflop_tree::{
-    build_tree, collect_node_metadata, Player, TerminalType, TreeConfig, TreeNode,
+    action_index, build_tree, collect_node_metadata, path_to_node, player_label, sizing_suffix, walk_line, Player,
+    TerminalType, TreeConfig, TreeNode,
+};
+use crate::postprocess::{combo_equities, hand_class, PostProcess, PostProcessInfo, Simplify, SimplifyInfo};
+use crate::progress::{PhaseTimer, ProgressCallback, ProgressTracker, SolveStats};
+use crate::breakdown::{breakdown, Breakdown};
+use crate::range_summary::{combo_weights, summarize, RangeSummary, WeightedCombos};
+use crate::quality::{pct_of_pot, SolveQuality, RECOMMENDED_FLOP_ITERATIONS};
+use crate::ranges::{format_weighted_range, weighted_range_string};
+use crate::rng::stream_rng;
+use crate::runout_table::{runout_bytes, PrecomputeMode, RunoutTable, DEFAULT_MAX_PRECOMPUTE_BYTES};
+use crate::river_solver::{
+    parse_spot, ParsedSpot,
+    average_by_canonical, combo_blockers, find_combo_index, expand_weighted_range_to_combos, parse_combo_strings,
+    relabel_combos, reorder_rows, valid_opponents, Combo, COMBO_ORDER_VERSION,
 };
-use crate::ranges::parse_range;
-use crate::river_solver::{expand_range_to_combos, Combo};
+
+/// Maximum runouts sampled when computing range-vs-range equity.
+const RANGE_EQUITY_RUNOUTS: usize = 256;
+
+/// Runouts sampled for the reported exploitability estimate.
+const EXPLOITABILITY_SAMPLES: usize = 100;
+
+/// Runouts sampled for progress snapshots: cheaper, noisier.
+const PROGRESS_SAMPLES: usize = 10;
 
 // ---------------------------------------------------------------------------
 // Config & result
@@ -39,12 +69,61 @@ use crate::river_solver::{expand_range_to_combos, Combo};
 pub struct FlopSolverConfig {
     /// 3-card flop board as u8 indices.
     pub board: Vec<u8>,
+    /// Cards known to be out of the deck (`--dead`): combos holding one are
+    /// left out and no turn or river deals one. Bucket equities are still
+    /// estimated over the full deck.
+    pub dead_cards: Vec<u8>,
     pub oop_range: Vec<String>,
     pub ip_range: Vec<String>,
+    /// Frequency (0 to 1) of each hand in `oop_range`, e.g. 0.5 for `AKo:0.5`.
+    pub oop_weights: Vec<f64>,
+    /// Frequency of each hand in `ip_range`.
+    pub ip_weights: Vec<f64>,
     pub starting_pot: f64,
     pub effective_stack: f64,
     pub iterations: usize,
     pub num_buckets: usize,
+    /// How combos are grouped into those buckets.
+    pub bucketing: BucketingStrategy,
+    /// Smoothing/purification applied to flop-level strategies before extraction.
+    pub postprocess: Option<PostProcess>,
+    /// Measure exploitability every this many iterations (see `progress`).
+    pub progress_interval: Option<usize>,
+    /// Called with each progress snapshot.
+    pub on_progress: Option<ProgressCallback>,
+    /// Stop once exploitability is at or below this percent of the starting
+    /// pot; `iterations` is then a cap.
+    pub target_exploitability: Option<f64>,
+    /// Stop once this much wall time has gone into iterating; `iterations`
+    /// is then a cap (see `progress`).
+    pub time_budget: Option<std::time::Duration>,
+    /// Seed for everything the solve samples (bucket equities, runouts, the
+    /// exploitability estimate): the same seed, spot and iterations give the
+    /// same solution. `None` samples fresh randomness each run.
+    pub seed: Option<u64>,
+    /// Regret-minimization variant (CFR+ by default).
+    pub cfr_variant: CfrVariant,
+    /// Solve the quick tree (`quick_flop_tree_config`) and mark the solution
+    /// low-fidelity.
+    pub quick: bool,
+    /// Bet sizes of the flop tree, as fractions of the pot. A quick solve
+    /// uses its own tree.
+    pub flop_bet_sizes: Vec<f64>,
+    /// Bet sizes of the river template tree, as fractions of the pot.
+    pub river_bet_sizes: Vec<f64>,
+    /// Most memory the per-runout river tables may take before they're built
+    /// lazily instead (see `runout_table`).
+    pub max_precompute_bytes: u64,
+    /// After the bucketed solve, run a tenth as many iterations again on
+    /// per-combo flop tables (see `refine_flop_combos`), so combos sharing a
+    /// bucket can play differently by what they block.
+    pub refine_flop_combos: bool,
+    /// Where to save checkpoints (see `FlopCheckpoint`). One is saved every
+    /// `checkpoint_every` iterations, and at the end when the solve stopped
+    /// short of `iterations`, resumed from a checkpoint or saved at
+    /// intervals. `None` saves none.
+    pub checkpoint_path: Option<std::path::PathBuf>,
+    pub checkpoint_every: Option<usize>,
 }
 
 impl FlopSolverConfig {
@@ -55,35 +134,169 @@ impl FlopSolverConfig {
         starting_pot: f64,
         effective_stack: f64,
         iterations: usize,
-    ) -> Result<Self, String> {
-        let board_cards = parse_board(board_str).map_err(|e| e.to_string())?;
-        if board_cards.len() != 3 {
-            return Err("Flop board must have exactly 3 cards".to_string());
-        }
-        let board: Vec<u8> = board_cards
-            .iter()
-            .map(|c| crate::card_encoding::card_to_index(c))
-            .collect();
-        let oop_range = parse_range(oop_range_str);
-        let ip_range = parse_range(ip_range_str);
-
-        if oop_range.is_empty() {
-            return Err("OOP range is empty".to_string());
-        }
-        if ip_range.is_empty() {
-            return Err("IP range is empty".to_string());
-        }
+    ) -> SolverResult<Self> {
+        crate::limits::FLOP_ITERATIONS.check_config(iterations)?;
+        let ParsedSpot { board, oop_range, oop_weights, ip_range, ip_weights } =
+            parse_spot(board_str, 3, oop_range_str, ip_range_str)?;
 
         Ok(FlopSolverConfig {
             board,
+            dead_cards: Vec::new(),
             oop_range,
             ip_range,
+            oop_weights,
+            ip_weights,
             starting_pot,
             effective_stack,
             iterations,
             num_buckets: 200,
+            bucketing: BucketingStrategy::EquityOnly,
+            postprocess: None,
+            progress_interval: None,
+            on_progress: None,
+            target_exploitability: None,
+            time_budget: None,
+            seed: None,
+            cfr_variant: CfrVariant::CfrPlus,
+            quick: false,
+            flop_bet_sizes: DEFAULT_FLOP_BET_SIZES.to_vec(),
+            river_bet_sizes: DEFAULT_TEMPLATE_RIVER_BET_SIZES.to_vec(),
+            max_precompute_bytes: DEFAULT_MAX_PRECOMPUTE_BYTES,
+            refine_flop_combos: false,
+            checkpoint_path: None,
+            checkpoint_every: None,
         })
     }
+
+    /// How the solve will build its per-runout river tables.
+    pub fn precompute_mode(&self) -> PrecomputeMode {
+        let blocked = self.blocked_cards();
+        let (oop, _) = expand_weighted_range_to_combos(&self.oop_range, &self.oop_weights, &blocked);
+        let (ip, _) = expand_weighted_range_to_combos(&self.ip_range, &self.ip_weights, &blocked);
+        self.precompute_mode_for(oop.len(), ip.len())
+    }
+
+    /// Rough peak memory of the solve: the per-runout river tables in the
+    /// mode they'd be built in, the per-turn bucket tables, and
+    /// `SOLVE_OVERHEAD_BYTES` for the trees and bucketed regret tables.
+    pub fn estimated_memory_bytes(&self) -> u64 {
+        let blocked = self.blocked_cards();
+        let (oop, _) = expand_weighted_range_to_combos(&self.oop_range, &self.oop_weights, &blocked);
+        let (ip, _) = expand_weighted_range_to_combos(&self.ip_range, &self.ip_weights, &blocked);
+        let remaining = (52 - blocked.len()) as u64;
+        let runouts = match self.precompute_mode_for(oop.len(), ip.len()) {
+            PrecomputeMode::Full => remaining * (remaining - 1),
+            PrecomputeMode::Lazy { cache_runouts } => cache_runouts as u64,
+        };
+        let turn_buckets = remaining * (oop.len() + ip.len()) as u64 * std::mem::size_of::<u16>() as u64;
+        runouts * runout_bytes(oop.len(), ip.len()) + turn_buckets + SOLVE_OVERHEAD_BYTES
+    }
+
+    fn precompute_mode_for(&self, num_oop: usize, num_ip: usize) -> PrecomputeMode {
+        let remaining = 52 - self.board.len() - self.dead_cards.len();
+        PrecomputeMode::choose(remaining * (remaining - 1), num_oop, num_ip, self.max_precompute_bytes)
+    }
+
+    /// Board and dead cards: what no combo may hold.
+    fn blocked_cards(&self) -> Vec<u8> {
+        [self.board.as_slice(), &self.dead_cards].concat()
+    }
+
+    /// A quick, approximate solve for when nothing is cached: one bet size,
+    /// `QUICK_FLOP_BUCKETS` buckets and `QUICK_FLOP_ITERATIONS` iterations.
+    pub fn quick(
+        board_str: &str,
+        oop_range_str: &str,
+        ip_range_str: &str,
+        starting_pot: f64,
+        effective_stack: f64,
+    ) -> SolverResult<Self> {
+        let mut config =
+            Self::new(board_str, oop_range_str, ip_range_str, starting_pot, effective_stack, QUICK_FLOP_ITERATIONS)?;
+        config.num_buckets = QUICK_FLOP_BUCKETS;
+        config.quick = true;
+        Ok(config)
+    }
+
+    /// Flop-street tree this config solves.
+    pub fn tree_config(&self) -> TreeConfig {
+        if self.quick {
+            quick_flop_tree_config(self.starting_pot, self.effective_stack)
+        } else {
+            sized_flop_tree_config(self.starting_pot, self.effective_stack, &self.flop_bet_sizes)
+        }
+    }
+}
+
+/// Random streams of a solve; an iteration's stream is its index.
+const TURN_BUCKETS_STREAM: u64 = 1 << 48;
+const FLOP_BUCKETS_STREAM: u64 = 2 << 48;
+const ESTIMATE_STREAM: u64 = 3 << 48;
+const REFINE_STREAM: u64 = 4 << 48;
+const BEST_RESPONSE_STREAM: u64 = 5 << 48;
+
+/// Runouts each `refine_flop_combos` iteration sums its values over: on a
+/// single runout, which cards fall swamps the blocker effects it refines.
+const REFINE_RUNOUTS: usize = 8;
+
+/// OOP and IP buckets on the turn board of remaining card `turn_idx`. Each turn
+/// card has its own stream, so a seeded solve's training and exploitability
+/// estimate bucket a turn the same way.
+fn turn_buckets(
+    oop_pairs: &[(u8, u8)],
+    ip_pairs: &[(u8, u8)],
+    turn_board: &[u8],
+    turn_idx: usize,
+    num_buckets: usize,
+    bucketing: BucketingStrategy,
+    seed: Option<u64>,
+) -> (Vec<u16>, Vec<u16>) {
+    let mut rng = stream_rng(seed, TURN_BUCKETS_STREAM + turn_idx as u64);
+    (
+        bucketing.assign(oop_pairs, turn_board, num_buckets, 200, &mut rng),
+        bucketing.assign(ip_pairs, turn_board, num_buckets, 200, &mut rng),
+    )
+}
+
+/// Where a flop line that closes the flop leaves the hand (see
+/// `FlopSolution::line_reach`).
+#[derive(Debug, Clone)]
+pub struct LineReach {
+    /// The line's action labels (e.g. ["Bet 3.3", "Call"]).
+    pub line: Vec<String>,
+    /// Reach of each combo in `oop_combos` into the turn.
+    pub oop: Vec<f64>,
+    /// Reach of each combo in `ip_combos` into the turn.
+    pub ip: Vec<f64>,
+    /// Pot the turn starts with.
+    pub pot: f64,
+    pub effective_stack: f64,
+}
+
+/// Villain's side of a flop query: its range and how it answers each of
+/// hero's options (see `FlopSolution::villain_view`).
+#[derive(Debug, Clone)]
+pub struct VillainView {
+    /// "OOP" or "IP".
+    pub side: String,
+    /// Villain's range as solved, with any frequencies ("AA,KK,AKo (0.5)").
+    pub range: String,
+    /// Villain's combos on the board, each counted at its range frequency.
+    pub combos: f64,
+    /// The part of `combos` that reaches hero's decision and doesn't hold
+    /// one of hero's cards.
+    pub reaching: f64,
+    /// One per hero action that hands villain a decision.
+    pub responses: Vec<VillainResponse>,
+}
+
+/// Villain's range-weighted frequencies after one of hero's actions.
+#[derive(Debug, Clone)]
+pub struct VillainResponse {
+    /// Hero's action label, e.g. "Bet 7.5".
+    pub hero_action: String,
+    pub actions: Vec<String>,
+    pub frequencies: Vec<f64>,
 }
 
 /// Per-node strategy for the flop solution.
@@ -94,6 +307,19 @@ pub struct FlopNodeStrategy {
     pub actions: Vec<String>,
     /// Average strategy frequencies: [combo_idx][action_idx].
     pub frequencies: Vec<Vec<f64>>,
+    /// Each action's EV in starting pots, given play reaches this node with
+    /// the combo: [combo_idx][action_idx]. Zero for combos that never face the
+    /// node; empty in solutions cached before EVs were stored.
+    #[serde(default)]
+    pub evs: Vec<Vec<f64>>,
+}
+
+impl FlopNodeStrategy {
+    /// A combo's EV at this node under its own strategy, in starting pots.
+    pub fn combo_ev(&self, combo_idx: usize) -> Option<f64> {
+        let evs = self.evs.get(combo_idx)?;
+        Some(self.frequencies[combo_idx].iter().zip(evs).map(|(f, ev)| f * ev).sum())
+    }
 }
 
 /// Bucket-level strategy from a template tree (turn or river within flop solve).
@@ -117,7 +343,7 @@ pub struct TreeEdge {
 }
 
 /// Full solution from the flop solver.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlopSolution {
     pub board: String,
     pub oop_range: Vec<String>,
@@ -154,61 +380,502 @@ pub struct FlopSolution {
     /// Tree edges for river template navigation.
     #[serde(default)]
     pub river_tree_edges: Vec<TreeEdge>,
+    /// Combo ordering used by `oop_combos`/`ip_combos` (see `COMBO_ORDER_VERSION`).
+    #[serde(default)]
+    pub combo_order_version: u32,
+    /// OOP's range-vs-range showdown equity (IP's is `1 - oop_equity`).
+    #[serde(default)]
+    pub oop_equity: f64,
+    /// OOP's EV under the solved strategies as a share of the starting pot.
+    #[serde(default)]
+    pub oop_ev_share: f64,
+    /// IP's EV under the solved strategies as a share of the starting pot.
+    #[serde(default)]
+    pub ip_ev_share: f64,
+    /// Set when `strategies` were post-processed; `exploitability` is then
+    /// that of the processed strategy.
+    #[serde(default)]
+    pub postprocess: Option<PostProcessInfo>,
+    /// Set on a copy whose strategies were rounded for display (see
+    /// `simplified`); such copies aren't cached.
+    #[serde(default)]
+    pub simplified: Option<SimplifyInfo>,
+    /// Solve quality grade (see `quality`), set when the solve finishes.
+    #[serde(default)]
+    pub quality: Option<SolveQuality>,
+    /// (iterations, exploitability) snapshots when solved with a
+    /// `progress_interval`, ending with the final value.
+    #[serde(default)]
+    pub convergence_history: Vec<(usize, f64)>,
+    /// Frequency of each hand in `oop_range`; empty for solves from before
+    /// weighted ranges, where every hand is in full.
+    #[serde(default)]
+    pub oop_weights: Vec<f64>,
+    /// Frequency of each hand in `ip_range`, like `oop_weights`.
+    #[serde(default)]
+    pub ip_weights: Vec<f64>,
+    /// `exploitability` as a percentage of the starting pot, comparable
+    /// across spots.
+    #[serde(default)]
+    pub exploitability_pct_pot: f64,
+    /// Standard error of the sampled `exploitability`, in the same units.
+    #[serde(default)]
+    pub exploitability_std_err: f64,
+    /// A quick solve (`FlopSolverConfig::quick`): smaller tree, fewer
+    /// buckets and iterations. `gto query --refine` replaces it.
+    #[serde(default)]
+    pub low_fidelity: bool,
+    /// Bet sizes of the river template tree, as fractions of the pot.
+    #[serde(default = "default_template_river_bet_sizes")]
+    pub river_bet_sizes: Vec<f64>,
+    /// How combos were bucketed; turn template lookups must bucket the same way.
+    #[serde(default)]
+    pub bucketing: BucketingStrategy,
+    /// Flop strategies were refined per combo after the bucketed solve
+    /// (`FlopSolverConfig::refine_flop_combos`) rather than copied from
+    /// each combo's bucket.
+    #[serde(default)]
+    pub combos_refined: bool,
+    /// Bet sizes of the flop tree, as fractions of the pot.
+    #[serde(default = "default_flop_bet_sizes")]
+    pub flop_bet_sizes: Vec<f64>,
+    /// OOP's EV as a share of the starting pot when IP best-responds to
+    /// OOP's solved strategy without seeing the runout before it's dealt,
+    /// over a fixed sample of turns and rivers (see `size_compare`).
+    #[serde(default)]
+    pub oop_ev_vs_best_response: f64,
+    /// Stopped by the config's `time_budget` instead of an iteration count.
+    /// Such a solve meets the quality floor whatever its iterations, since
+    /// solving again on the same machine would stop in the same place.
+    #[serde(default)]
+    pub time_limited: bool,
+}
+
+fn default_template_river_bet_sizes() -> Vec<f64> {
+    DEFAULT_TEMPLATE_RIVER_BET_SIZES.to_vec()
+}
+
+fn default_flop_bet_sizes() -> Vec<f64> {
+    DEFAULT_FLOP_BET_SIZES.to_vec()
 }
 
 // ---------------------------------------------------------------------------
 // Solver
 // ---------------------------------------------------------------------------
 
-/// Solve a flop spot using External Sampling MCCFR with template trees.
-pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
-    // 1. Build three separate trees
-    let flop_tree_config = TreeConfig {
-        bet_sizes: vec![0.33, 0.75],
+/// Flop bet sizes when none are configured, as fractions of the pot.
+pub const DEFAULT_FLOP_BET_SIZES: [f64; 2] = [0.33, 0.75];
+
+/// Flop-street tree shape used by the solver; node ids in a `FlopSolution`
+/// refer to the tree built from this config.
+pub fn flop_tree_config(starting_pot: f64, effective_stack: f64) -> TreeConfig {
+    sized_flop_tree_config(starting_pot, effective_stack, &DEFAULT_FLOP_BET_SIZES)
+}
+
+/// `flop_tree_config` with other bet sizes.
+pub fn sized_flop_tree_config(starting_pot: f64, effective_stack: f64, bet_sizes: &[f64]) -> TreeConfig {
+    TreeConfig {
+        bet_sizes: bet_sizes.to_vec(),
         raise_sizes: vec![1.0],
         max_raises: 2,
-        starting_pot: config.starting_pot,
-        effective_stack: config.effective_stack,
+        starting_pot,
+        effective_stack,
         add_allin: true,
-    };
-    let (flop_tree, _flop_nodes) = build_tree(&flop_tree_config);
+    }
+}
+
+/// Memory a flop solve takes besides its runout and bucket tables (see
+/// `FlopSolverConfig::estimated_memory_bytes`).
+pub const SOLVE_OVERHEAD_BYTES: u64 = 64 << 20;
+
+/// Iterations of a quick solve.
+pub const QUICK_FLOP_ITERATIONS: usize = 50_000;
+
+/// Strategy buckets of a quick solve.
+pub const QUICK_FLOP_BUCKETS: usize = 50;
+
+/// Flop-street tree of a quick solve: one bet size and one raise.
+pub fn quick_flop_tree_config(starting_pot: f64, effective_stack: f64) -> TreeConfig {
+    TreeConfig {
+        bet_sizes: vec![0.5],
+        raise_sizes: vec![1.0],
+        max_raises: 1,
+        starting_pot,
+        effective_stack,
+        add_allin: true,
+    }
+}
+
+/// Solve a flop spot using External Sampling MCCFR with template trees.
+pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
+    solve_flop_timed(config).0
+}
+
+/// `solve_flop`, also returning where the time went.
+pub fn solve_flop_timed(config: &FlopSolverConfig) -> (FlopSolution, SolveStats) {
+    let (solution, _, stats) = solve_flop_from(config, None, None, None).expect("cold solve has no prior to validate");
+    (solution, stats)
+}
+
+/// `solve_flop` on tables already built for the spot, e.g. to solve it with
+/// several trees (see `FlopPrecompute`).
+pub fn solve_flop_with(config: &FlopSolverConfig, precompute: &FlopPrecompute) -> Result<FlopSolution, String> {
+    let (solution, _, _) = solve_flop_from(config, None, None, Some(precompute))?;
+    Ok(solution)
+}
+
+/// Solve, continuing from `checkpoint` if given, and return the solution with
+/// a checkpoint to continue it later (`None` when a range has no live combos).
+///
+/// `config.iterations` is the total: a checkpoint at 200K continued with
+/// `iterations: 1_000_000` runs 800K more. The checkpoint must be for the
+/// same board, combos, pot, stack, bucket count and tree shapes.
+pub fn solve_flop_resumable(
+    config: &FlopSolverConfig,
+    checkpoint: Option<FlopCheckpoint>,
+) -> Result<(FlopSolution, Option<FlopCheckpoint>), String> {
+    let (solution, checkpoint, _) = solve_flop_from(config, None, checkpoint, None)?;
+    Ok((solution, checkpoint))
+}
+
+/// Continue from a cached solution after a range edit ("delta solve").
+///
+/// Flop info sets are seeded with each new bucket's average strategy over the
+/// combos the prior solution already had (dropped combos simply disappear; buckets
+/// made only of added combos start cold). Turn/river template buckets are seeded
+/// directly. `config.iterations` is the continuation budget; the prior must share
+/// the board, pot, stack and bucket count.
+pub fn solve_flop_warm(config: &FlopSolverConfig, prior: &FlopSolution) -> Result<FlopSolution, String> {
+    let board: String = config.board.iter().map(|&b| format!("{}", index_to_card(b))).collect();
+    if prior.board != board {
+        return Err(format!("Cached solution is for board {}, not {}", prior.board, board));
+    }
+    if (prior.starting_pot - config.starting_pot).abs() > 1e-9
+        || (prior.effective_stack - config.effective_stack).abs() > 1e-9
+    {
+        return Err("Cached solution was solved with a different pot or stack".to_string());
+    }
+    if prior.num_buckets != config.num_buckets {
+        return Err(format!(
+            "Cached solution uses {} buckets, expected {}",
+            prior.num_buckets, config.num_buckets
+        ));
+    }
+    if prior.bucketing != config.bucketing {
+        return Err(format!(
+            "Cached solution uses {} bucketing, expected {}",
+            prior.bucketing.name(),
+            config.bucketing.name()
+        ));
+    }
+    if prior.river_bet_sizes != config.river_bet_sizes {
+        return Err("Cached solution was solved with different river template sizes".to_string());
+    }
+    if prior.flop_bet_sizes != config.flop_bet_sizes {
+        return Err("Cached solution was solved with different flop bet sizes".to_string());
+    }
+    let (mut solution, _, _) = solve_flop_from(config, Some(prior), None, None)?;
+    solution.iterations += prior.iterations;
+    for point in &mut solution.convergence_history {
+        point.0 += prior.iterations;
+    }
+    Ok(solution)
+}
 
-    let turn_template_config = TreeConfig {
+/// Stack behind in the unit-pot turn and river templates. Deep enough that
+/// the templates never run out of chips; the real stack is applied by capping
+/// their bets during traversal (see `capped_invested`).
+const TEMPLATE_STACK: f64 = 100.0;
+
+/// Shape of the turn template tree shared by every turn card.
+pub fn turn_template_config() -> TreeConfig {
+    TreeConfig {
         bet_sizes: vec![0.66],
         raise_sizes: vec![1.0],
         max_raises: 1,
         starting_pot: 1.0,
-        effective_stack: 100.0,
+        effective_stack: TEMPLATE_STACK,
         add_allin: false,
-    };
-    let (turn_template, _turn_nodes) = build_tree(&turn_template_config);
+    }
+}
 
-    let river_template_config = TreeConfig {
-        bet_sizes: vec![0.5, 1.0],
+/// River template bet sizes when none are configured, as fractions of the pot.
+pub const DEFAULT_TEMPLATE_RIVER_BET_SIZES: [f64; 2] = [0.5, 1.0];
+
+/// Shape of the river template tree shared by every runout.
+pub fn river_template_config(bet_sizes: &[f64]) -> TreeConfig {
+    TreeConfig {
+        bet_sizes: bet_sizes.to_vec(),
         raise_sizes: vec![1.0],
         max_raises: 1,
         starting_pot: 1.0,
-        effective_stack: 100.0,
+        effective_stack: TEMPLATE_STACK,
         add_allin: false,
+    }
+}
+
+/// Chips each player has put in on a template street, scaled from the unit
+/// pot to `scale` and capped at the `stack` both players had behind when the
+/// street began.
+fn capped_invested(invested: &[f64; 2], scale: f64, stack: f64) -> [f64; 2] {
+    [(invested[0] * scale).min(stack), (invested[1] * scale).min(stack)]
+}
+
+/// Capped investments at which a template node goes to showdown: its showdown
+/// terminals, and any action node where both players are already all in
+/// because the template's bets were capped at the stack.
+fn template_showdown(node: &TreeNode, scale: f64, stack: f64) -> Option<[f64; 2]> {
+    match node {
+        TreeNode::Terminal { terminal_type: TerminalType::Showdown, invested, .. } => {
+            Some(capped_invested(invested, scale, stack))
+        }
+        TreeNode::Action { stacks, .. } => {
+            let put_in = [TEMPLATE_STACK - stacks[0], TEMPLATE_STACK - stacks[1]];
+            let capped = capped_invested(&put_in, scale, stack);
+            (capped[0] >= stack - 1e-9 && capped[1] >= stack - 1e-9).then_some(capped)
+        }
+        _ => None,
+    }
+}
+
+/// Value of a template fold terminal for `player`, weighted by `opp_reach_sum`.
+fn template_fold_value(
+    player: Player,
+    folder: Player,
+    invested: &[f64; 2],
+    scale: f64,
+    stack: f64,
+    opp_reach_sum: f64,
+) -> f64 {
+    let put_in = capped_invested(invested, scale, stack);
+    let my_invested = put_in[player.index()];
+    if folder == player {
+        -my_invested * opp_reach_sum
+    } else {
+        (scale + put_in[0] + put_in[1] - my_invested) * opp_reach_sum
+    }
+}
+
+/// Value of a river showdown for `player` holding combo `hand_idx`, against
+/// the opponent's reach, when the river began with `scale` in the pot and the
+/// players put in `invested` chips on it.
+#[allow(clippy::too_many_arguments)]
+fn river_showdown_value(
+    player: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    oop_scores: &[u32],
+    ip_scores: &[u32],
+    valid_ip_for_oop: &[Vec<u16>],
+    valid_oop_for_ip: &[Vec<u16>],
+    scale: f64,
+    invested: [f64; 2],
+) -> f64 {
+    let pot = scale + invested[0] + invested[1];
+    let my_invested = invested[player.index()];
+    let win_payoff = pot - my_invested;
+    let lose_payoff = -my_invested;
+    let tie_payoff = pot / 2.0 - my_invested;
+    let (my_score, opp_scores, valid) = match player {
+        Player::OOP => (oop_scores[hand_idx], ip_scores, &valid_ip_for_oop[hand_idx]),
+        Player::IP => (ip_scores[hand_idx], oop_scores, &valid_oop_for_ip[hand_idx]),
     };
-    let (river_template, _river_nodes) = build_tree(&river_template_config);
+    let mut value = 0.0;
+    for &j in valid {
+        let j = j as usize;
+        if opp_reach[j] < 1e-10 {
+            continue;
+        }
+        let opp_score = opp_scores[j];
+        let payoff = if my_score > opp_score {
+            win_payoff
+        } else if my_score < opp_score {
+            lose_payoff
+        } else {
+            tie_payoff
+        };
+        value += opp_reach[j] * payoff;
+    }
+    value
+}
+
+/// Everything a flop solve derives from the spot before training: combos,
+/// flop and turn buckets, blockers and the per-runout river tables. None of
+/// it depends on the flop tree, so solves of one spot with different bet
+/// sizes can share it (see `solve_flop_with`).
+pub struct FlopPrecompute {
+    oop_combos: Vec<Combo>,
+    ip_combos: Vec<Combo>,
+    oop_weights: Vec<f64>,
+    ip_weights: Vec<f64>,
+    flop_oop_buckets: Vec<u16>,
+    flop_ip_buckets: Vec<u16>,
+    oop_blockers: Vec<[bool; 52]>,
+    ip_blockers: Vec<[bool; 52]>,
+    /// Which combos don't share cards, per combo of the other player.
+    valid_ip_for_oop: Vec<Vec<u16>>,
+    valid_oop_for_ip: Vec<Vec<u16>>,
+    remaining_after_flop: Vec<u8>,
+    /// OOP and IP buckets per remaining turn card.
+    turn_bucket_table: Vec<(Vec<u16>, Vec<u16>)>,
+    runout_table: RunoutTable,
+}
+
+impl FlopPrecompute {
+    /// Tables for `config`'s spot, or `None` if a range has no combos left
+    /// after the board and dead cards.
+    pub fn new(config: &FlopSolverConfig) -> Option<Self> {
+        let (oop_combos, oop_weights) =
+            expand_weighted_range_to_combos(&config.oop_range, &config.oop_weights, &config.blocked_cards());
+        let (ip_combos, ip_weights) =
+            expand_weighted_range_to_combos(&config.ip_range, &config.ip_weights, &config.blocked_cards());
+        if oop_combos.is_empty() || ip_combos.is_empty() {
+            return None;
+        }
+        Some(Self::build(config, oop_combos, oop_weights, ip_combos, ip_weights, None))
+    }
+
+    /// Equities are sampled, so a resumed solve takes its buckets from the
+    /// checkpoint its tables were trained on.
+    fn build(
+        config: &FlopSolverConfig,
+        oop_combos: Vec<Combo>,
+        oop_weights: Vec<f64>,
+        ip_combos: Vec<Combo>,
+        ip_weights: Vec<f64>,
+        checkpoint: Option<&FlopCheckpoint>,
+    ) -> Self {
+        let oop_combo_pairs: Vec<(u8, u8)> = oop_combos.iter().map(|c| (c.0, c.1)).collect();
+        let ip_combo_pairs: Vec<(u8, u8)> = ip_combos.iter().map(|c| (c.0, c.1)).collect();
+
+        let [flop_oop_buckets, flop_ip_buckets] = match checkpoint {
+            Some(checkpoint) => checkpoint.flop_buckets.clone(),
+            None => {
+                let mut rng = stream_rng(config.seed, FLOP_BUCKETS_STREAM);
+                [
+                    config.bucketing.assign(&oop_combo_pairs, &config.board, config.num_buckets, 500, &mut rng),
+                    config.bucketing.assign(&ip_combo_pairs, &config.board, config.num_buckets, 500, &mut rng),
+                ]
+            }
+        };
+
+        let remaining_after_flop = remaining_deck_excluding(&config.board, &config.dead_cards);
+
+        // Bucket and score lookup tables for every runout spare the solve
+        // per-iteration assign_buckets() calls (~100x speedup)
+        let turn_bucket_table = match checkpoint {
+            Some(checkpoint) => checkpoint.turn_buckets.clone(),
+            None => remaining_after_flop
+                .par_iter()
+                .enumerate()
+                .map(|(turn_idx, &turn_card)| {
+                    let turn_board = [config.board[0], config.board[1], config.board[2], turn_card];
+                    turn_buckets(
+                        &oop_combo_pairs, &ip_combo_pairs, &turn_board, turn_idx, config.num_buckets,
+                        config.bucketing, config.seed,
+                    )
+                })
+                .collect(),
+        };
+
+        // River buckets and scores per runout: all of them up front, or built
+        // as sampled when that would exceed the memory budget
+        let runout_table = RunoutTable::new(
+            &config.board,
+            &remaining_after_flop,
+            &oop_combo_pairs,
+            &ip_combo_pairs,
+            config.num_buckets,
+            config.precompute_mode_for(oop_combos.len(), ip_combos.len()),
+        );
+
+        FlopPrecompute {
+            oop_blockers: combo_blockers(&oop_combos),
+            ip_blockers: combo_blockers(&ip_combos),
+            valid_ip_for_oop: valid_opponents(&oop_combos, &ip_combos),
+            valid_oop_for_ip: valid_opponents(&ip_combos, &oop_combos),
+            oop_combos,
+            ip_combos,
+            oop_weights,
+            ip_weights,
+            flop_oop_buckets,
+            flop_ip_buckets,
+            remaining_after_flop,
+            turn_bucket_table,
+            runout_table,
+        }
+    }
+}
+
+fn solve_flop_from(
+    config: &FlopSolverConfig,
+    prior: Option<&FlopSolution>,
+    checkpoint: Option<FlopCheckpoint>,
+    shared: Option<&FlopPrecompute>,
+) -> Result<(FlopSolution, Option<FlopCheckpoint>, SolveStats), String> {
+    let mut timer = PhaseTimer::start();
+    let mut stats = SolveStats::default();
+
+    // 1. Build three separate trees
+    let (flop_tree, _flop_nodes) = build_tree(&config.tree_config());
+
+    let (turn_template, _turn_nodes) = build_tree(&turn_template_config());
+    let (river_template, _river_nodes) = build_tree(&river_template_config(&config.river_bet_sizes));
 
     // 2. Expand ranges to combos
-    let oop_combos = expand_range_to_combos(&config.oop_range, &config.board);
-    let ip_combos = expand_range_to_combos(&config.ip_range, &config.board);
+    let (oop_combos, oop_weights) =
+        expand_weighted_range_to_combos(&config.oop_range, &config.oop_weights, &config.blocked_cards());
+    let (ip_combos, ip_weights) =
+        expand_weighted_range_to_combos(&config.ip_range, &config.ip_weights, &config.blocked_cards());
 
     if oop_combos.is_empty() || ip_combos.is_empty() {
-        return empty_solution(config);
+        stats.setup_ms = timer.lap();
+        return Ok((empty_solution(config), None, stats));
+    }
+
+    if let Some(checkpoint) = &checkpoint {
+        checkpoint.check_matches(config, &oop_combos, &ip_combos)?;
+        if checkpoint.iterations >= config.iterations {
+            return Err(format!(
+                "Checkpoint already has {} iterations; ask for more than that to continue",
+                checkpoint.iterations
+            ));
+        }
     }
 
-    // 3. Compute flop buckets
-    let oop_combo_pairs: Vec<(u8, u8)> = oop_combos.iter().map(|c| (c.0, c.1)).collect();
-    let ip_combo_pairs: Vec<(u8, u8)> = ip_combos.iter().map(|c| (c.0, c.1)).collect();
+    stats.setup_ms = timer.lap();
 
-    let flop_oop_buckets =
-        assign_buckets(&oop_combo_pairs, &config.board, config.num_buckets, 500);
-    let flop_ip_buckets =
-        assign_buckets(&ip_combo_pairs, &config.board, config.num_buckets, 500);
+    // 3. Buckets, blockers and runout tables, unless shared with other
+    // solves of the spot
+    let owned;
+    let precompute = match shared {
+        Some(shared) => {
+            if shared.oop_combos != oop_combos || shared.ip_combos != ip_combos {
+                return Err("Precomputed tables are for another spot".to_string());
+            }
+            shared
+        }
+        None => {
+            owned = FlopPrecompute::build(config, oop_combos, oop_weights, ip_combos, ip_weights, checkpoint.as_ref());
+            &owned
+        }
+    };
+    let FlopPrecompute {
+        oop_combos,
+        ip_combos,
+        oop_weights,
+        ip_weights,
+        flop_oop_buckets,
+        flop_ip_buckets,
+        oop_blockers,
+        ip_blockers,
+        valid_ip_for_oop,
+        valid_oop_for_ip,
+        remaining_after_flop,
+        turn_bucket_table,
+        runout_table,
+    } = precompute;
+    stats.precompute_ms = timer.lap();
 
     let num_oop_buckets = (*flop_oop_buckets.iter().max().unwrap_or(&0) + 1) as u16;
     let num_ip_buckets = (*flop_ip_buckets.iter().max().unwrap_or(&0) + 1) as u16;
@@ -289,60 +956,46 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
         })
         .collect();
 
-    let mut flop_oop_cfr = FlatCfr::new(&flop_oop_nodes);
-    let mut flop_ip_cfr = FlatCfr::new(&flop_ip_nodes);
-    let mut turn_oop_cfr = FlatCfr::new(&turn_oop_nodes);
-    let mut turn_ip_cfr = FlatCfr::new(&turn_ip_nodes);
-    let mut river_oop_cfr = FlatCfr::new(&river_oop_nodes);
-    let mut river_ip_cfr = FlatCfr::new(&river_ip_nodes);
-
-    // 5. Precompute blocker sets
-    let oop_blockers: Vec<[bool; 52]> = oop_combos
-        .iter()
-        .map(|c| {
-            let mut b = [false; 52];
-            b[c.0 as usize] = true;
-            b[c.1 as usize] = true;
-            b
-        })
-        .collect();
-    let ip_blockers: Vec<[bool; 52]> = ip_combos
-        .iter()
-        .map(|c| {
-            let mut b = [false; 52];
-            b[c.0 as usize] = true;
-            b[c.1 as usize] = true;
-            b
-        })
-        .collect();
+    let variant = config.cfr_variant;
+    let mut flop_oop_cfr = FlatCfr::with_variant(&flop_oop_nodes, variant);
+    let mut flop_ip_cfr = FlatCfr::with_variant(&flop_ip_nodes, variant);
+    let mut turn_oop_cfr = FlatCfr::with_variant(&turn_oop_nodes, variant);
+    let mut turn_ip_cfr = FlatCfr::with_variant(&turn_ip_nodes, variant);
+    let mut river_oop_cfr = FlatCfr::with_variant(&river_oop_nodes, variant);
+    let mut river_ip_cfr = FlatCfr::with_variant(&river_ip_nodes, variant);
+
+    if let Some(prior) = prior {
+        // One iteration's worth of regret lets the current strategy move right
+        // away; the average counts the prior run as if it had been trained here
+        // (each player traverses on every other iteration).
+        let weight = prior.iterations as f32 * 0.5;
+        let oop_scale = (config.starting_pot * ip_combos.len() as f64) as f32;
+        let ip_scale = (config.starting_pot * oop_combos.len() as f64) as f32;
+        seed_flop_nodes(
+            &prior.strategies, "OOP", &prior.oop_combos, oop_combos, flop_oop_buckets,
+            &mut flop_oop_cfr, oop_scale, weight,
+        )?;
+        seed_flop_nodes(
+            &prior.strategies, "IP", &prior.ip_combos, ip_combos, flop_ip_buckets,
+            &mut flop_ip_cfr, ip_scale, weight,
+        )?;
+        let per_bucket = |n: usize| weight * (n as f32 / config.num_buckets as f32).max(1.0);
+        seed_template_nodes(&prior.turn_strategies, "OOP", &mut turn_oop_cfr, oop_scale, per_bucket(oop_combos.len()))?;
+        seed_template_nodes(&prior.turn_strategies, "IP", &mut turn_ip_cfr, ip_scale, per_bucket(ip_combos.len()))?;
+        seed_template_nodes(&prior.river_strategies, "OOP", &mut river_oop_cfr, oop_scale, per_bucket(oop_combos.len()))?;
+        seed_template_nodes(&prior.river_strategies, "IP", &mut river_ip_cfr, ip_scale, per_bucket(ip_combos.len()))?;
+    }
 
-    // Precompute validity tables (which combos don't share cards)
-    let valid_ip_for_oop: Vec<Vec<u16>> = oop_combos
-        .iter()
-        .map(|oop| {
-            ip_combos
-                .iter()
-                .enumerate()
-                .filter(|(_, ip)| {
-                    oop.0 != ip.0 && oop.0 != ip.1 && oop.1 != ip.0 && oop.1 != ip.1
-                })
-                .map(|(j, _)| j as u16)
-                .collect()
-        })
-        .collect();
-    let valid_oop_for_ip: Vec<Vec<u16>> = ip_combos
-        .iter()
-        .map(|ip| {
-            oop_combos
-                .iter()
-                .enumerate()
-                .filter(|(_, oop)| {
-                    ip.0 != oop.0 && ip.0 != oop.1 && ip.1 != oop.0 && ip.1 != oop.1
-                })
-                .map(|(i, _)| i as u16)
-                .collect()
-        })
-        .collect();
+    let mut start = 0;
+    if let Some(checkpoint) = checkpoint {
+        let FlopCheckpoint { iterations, cfr, .. } = checkpoint;
+        let fresh = [&flop_oop_cfr, &flop_ip_cfr, &turn_oop_cfr, &turn_ip_cfr, &river_oop_cfr, &river_ip_cfr];
+        if cfr.iter().zip(fresh).any(|(saved, fresh)| !saved.same_shape(fresh)) {
+            return Err("Checkpoint's CFR tables don't match this spot's trees and buckets".to_string());
+        }
+        [flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr, river_oop_cfr, river_ip_cfr] = cfr;
+        start = iterations;
+    }
 
     // Reusable buffers
     let all_metas = [&flop_metas, &turn_metas, &river_metas];
@@ -356,7 +1009,6 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
     let mut action_values = vec![0.0f32; max_actions];
 
     // Available turn and river cards
-    let remaining_after_flop = remaining_deck(&config.board);
     let num_remaining = remaining_after_flop.len();
 
     // Build card -> index mapping for remaining cards
@@ -365,91 +1017,41 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
         card_to_remaining_idx[card as usize] = idx;
     }
 
-    // 6. Precompute bucket and score lookup tables for all runouts
-    // This eliminates per-iteration assign_buckets() calls (~100x speedup)
-
-    // Precompute turn buckets: turn_bucket_table[turn_idx] = (oop_buckets, ip_buckets)
-    let turn_bucket_table: Vec<(Vec<u16>, Vec<u16>)> = remaining_after_flop
-        .par_iter()
-        .map(|&turn_card| {
-            let turn_board = [config.board[0], config.board[1], config.board[2], turn_card];
-            let turn_oop = assign_buckets(&oop_combo_pairs, &turn_board, config.num_buckets, 200);
-            let turn_ip = assign_buckets(&ip_combo_pairs, &turn_board, config.num_buckets, 200);
-            (turn_oop, turn_ip)
-        })
-        .collect();
-
-    // Precompute river data for all runouts: river_bucket_table and score_table
-    // Indexed by runout_idx = turn_idx * (num_remaining - 1) + adjusted_river_idx
-    let num_runouts = num_remaining * (num_remaining - 1);
-    let river_bucket_table: Vec<(Vec<u16>, Vec<u16>)>;
-    let score_table: Vec<(Vec<u32>, Vec<u32>)>;
+    let mut progress = ProgressTracker::new(config.progress_interval, config.on_progress, config.iterations)
+        .with_target(config.target_exploitability, config.starting_pot)
+        .with_time_budget(config.time_budget)
+        .resuming_from(start);
 
-    {
-        let results: Vec<((Vec<u16>, Vec<u16>), (Vec<u32>, Vec<u32>))> = (0..num_runouts)
-            .into_par_iter()
-            .map(|runout_idx| {
-                let turn_idx = runout_idx / (num_remaining - 1);
-                let river_adj = runout_idx % (num_remaining - 1);
-                let river_idx = if river_adj >= turn_idx {
-                    river_adj + 1
-                } else {
-                    river_adj
-                };
-                let turn_card = remaining_after_flop[turn_idx];
-                let river_card = remaining_after_flop[river_idx];
-                let river_board = [
-                    config.board[0],
-                    config.board[1],
-                    config.board[2],
-                    turn_card,
-                    river_card,
-                ];
-                let r_oop =
-                    assign_buckets(&oop_combo_pairs, &river_board, config.num_buckets, 0);
-                let r_ip =
-                    assign_buckets(&ip_combo_pairs, &river_board, config.num_buckets, 0);
-                let s_oop: Vec<u32> = oop_combo_pairs
-                    .iter()
-                    .map(|&(c0, c1)| {
-                        evaluate_fast(&[
-                            c0,
-                            c1,
-                            river_board[0],
-                            river_board[1],
-                            river_board[2],
-                            river_board[3],
-                            river_board[4],
-                        ])
-                    })
-                    .collect();
-                let s_ip: Vec<u32> = ip_combo_pairs
-                    .iter()
-                    .map(|&(c0, c1)| {
-                        evaluate_fast(&[
-                            c0,
-                            c1,
-                            river_board[0],
-                            river_board[1],
-                            river_board[2],
-                            river_board[3],
-                            river_board[4],
-                        ])
-                    })
-                    .collect();
-                ((r_oop, r_ip), (s_oop, s_ip))
+    // 5. Run MCCFR iterations
+    for iter in start..config.iterations {
+        if progress.out_of_time(iter) {
+            break;
+        }
+        if let (Some(path), Some(every)) = (&config.checkpoint_path, config.checkpoint_every) {
+            if iter > start && iter % every.max(1) == 0 {
+                let cfr = [&flop_oop_cfr, &flop_ip_cfr, &turn_oop_cfr, &turn_ip_cfr, &river_oop_cfr, &river_ip_cfr];
+                FlopCheckpoint::new(config, precompute, iter, cfr.map(FlatCfr::clone)).save(path);
+            }
+        }
+        // A target needs the full estimate: a noisy one can stop the solve early
+        let samples = if config.target_exploitability.is_some() { EXPLOITABILITY_SAMPLES } else { PROGRESS_SAMPLES };
+        if progress.due(iter)
+            && progress.measure(iter, || {
+                estimate_exploitability(
+                    &flop_tree, &turn_template, &river_template,
+                    &flop_oop_cfr, &flop_ip_cfr, &turn_oop_cfr, &turn_ip_cfr,
+                    &river_oop_cfr, &river_ip_cfr,
+                    oop_combos, ip_combos, oop_weights, ip_weights, oop_blockers, ip_blockers,
+                    flop_oop_buckets, flop_ip_buckets,
+                    valid_ip_for_oop, valid_oop_for_ip, runout_table,
+                    &config.board, config.starting_pot, config.num_buckets, config.bucketing, samples, config.seed,
+                    Some(turn_bucket_table),
+                )
+                .0
             })
-            .collect();
-
-        let (rb, st): (Vec<_>, Vec<_>) = results.into_iter().unzip();
-        river_bucket_table = rb;
-        score_table = st;
-    }
-
-    let mut rng = rand::thread_rng();
-
-    // 7. Run MCCFR iterations
-    for iter in 0..config.iterations {
+        {
+            break;
+        }
         let traverser = if iter % 2 == 0 {
             Player::OOP
         } else {
@@ -457,6 +1059,7 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
         };
 
         // Sample a turn card
+        let mut rng = stream_rng(config.seed, iter as u64);
         let turn_raw_idx = rng.gen_range(0..num_remaining);
         let turn_card = remaining_after_flop[turn_raw_idx];
 
@@ -475,14 +1078,9 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
 
         // Lookup precomputed buckets and scores
         let (turn_oop_buckets, turn_ip_buckets) = &turn_bucket_table[turn_raw_idx];
-        let runout_idx = turn_raw_idx * (num_remaining - 1)
-            + if river_raw_idx > turn_raw_idx {
-                river_raw_idx - 1
-            } else {
-                river_raw_idx
-            };
-        let (river_oop_buckets, river_ip_buckets) = &river_bucket_table[runout_idx];
-        let (oop_scores, ip_scores) = &score_table[runout_idx];
+        let runout = runout_table.get(runout_table.runout_idx(turn_raw_idx, river_raw_idx));
+        let (river_oop_buckets, river_ip_buckets) = (&runout.oop_buckets, &runout.ip_buckets);
+        let (oop_scores, ip_scores) = (&runout.oop_scores, &runout.ip_scores);
 
         let num_combos = match traverser {
             Player::OOP => oop_combos.len(),
@@ -513,7 +1111,7 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
                             if !ip_blockers[j][turn_card as usize]
                                 && !ip_blockers[j][river_card as usize]
                             {
-                                reach[j] = 1.0;
+                                reach[j] = ip_weights[j];
                             }
                         }
                         reach
@@ -526,7 +1124,7 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
                             if !oop_blockers[i][turn_card as usize]
                                 && !oop_blockers[i][river_card as usize]
                             {
-                                reach[i] = 1.0;
+                                reach[i] = oop_weights[i];
                             }
                         }
                         reach
@@ -548,13 +1146,13 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
 
                 cfr_traverse_flop(
                     &flop_tree, traverser, h, flop_bucket, turn_bucket, river_bucket,
-                    &opp_reach, &oop_combos, &ip_combos,
-                    &oop_blockers, &ip_blockers,
-                    &flop_oop_buckets, &flop_ip_buckets,
+                    &opp_reach, oop_combos, ip_combos,
+                    oop_blockers, ip_blockers,
+                    flop_oop_buckets, flop_ip_buckets,
                     turn_oop_buckets, turn_ip_buckets,
                     river_oop_buckets, river_ip_buckets,
                     oop_scores, ip_scores,
-                    &valid_ip_for_oop, &valid_oop_for_ip,
+                    valid_ip_for_oop, valid_oop_for_ip,
                     config.starting_pot, &turn_template, &river_template,
                     &mut flop_oop_cfr, &mut flop_ip_cfr,
                     &mut turn_oop_cfr, &mut turn_ip_cfr,
@@ -566,15 +1164,9 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
             continue;
         }
 
-        // Parallel path for large ranges (>= 20 combos)
-        // Snapshot CFR instances for parallel readonly traversal
-        let snap_flop_oop = flop_oop_cfr.clone();
-        let snap_flop_ip = flop_ip_cfr.clone();
-        let snap_turn_oop = turn_oop_cfr.clone();
-        let snap_turn_ip = turn_ip_cfr.clone();
-        let snap_river_oop = river_oop_cfr.clone();
-        let snap_river_ip = river_ip_cfr.clone();
-
+        // Parallel path for large ranges (>= 20 combos). The traversal only
+        // reads the CFR instances, so it borrows them; updates are collected
+        // and applied once every combo has been traversed.
         let all_updates: Vec<Vec<RegretUpdate>> = (0..num_combos)
             .into_par_iter()
             .filter_map(|h| {
@@ -599,7 +1191,7 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
                             if !ip_blockers[j][turn_card as usize]
                                 && !ip_blockers[j][river_card as usize]
                             {
-                                reach[j] = 1.0;
+                                reach[j] = ip_weights[j];
                             }
                         }
                         reach
@@ -612,7 +1204,7 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
                             if !oop_blockers[i][turn_card as usize]
                                 && !oop_blockers[i][river_card as usize]
                             {
-                                reach[i] = 1.0;
+                                reach[i] = oop_weights[i];
                             }
                         }
                         reach
@@ -635,23 +1227,25 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
                 let mut updates = Vec::new();
                 cfr_traverse_flop_ro(
                     &flop_tree, traverser, h, flop_bucket, turn_bucket, river_bucket,
-                    &opp_reach, &oop_combos, &ip_combos,
-                    &oop_blockers, &ip_blockers,
-                    &flop_oop_buckets, &flop_ip_buckets,
+                    &opp_reach, oop_combos, ip_combos,
+                    oop_blockers, ip_blockers,
+                    flop_oop_buckets, flop_ip_buckets,
                     turn_oop_buckets, turn_ip_buckets,
                     river_oop_buckets, river_ip_buckets,
                     oop_scores, ip_scores,
-                    &valid_ip_for_oop, &valid_oop_for_ip,
+                    valid_ip_for_oop, valid_oop_for_ip,
                     config.starting_pot, &turn_template, &river_template,
-                    &snap_flop_oop, &snap_flop_ip,
-                    &snap_turn_oop, &snap_turn_ip,
-                    &snap_river_oop, &snap_river_ip,
+                    &flop_oop_cfr, &flop_ip_cfr,
+                    &turn_oop_cfr, &turn_ip_cfr,
+                    &river_oop_cfr, &river_ip_cfr,
                     &mut updates, iter,
                 );
                 Some(updates)
             })
             .collect();
 
+        // Collected in combo order, so updates sharing a bucket apply in the
+        // same order every run
         for hand_updates in all_updates {
             for upd in hand_updates {
                 let cfr = match (traverser, upd.street) {
@@ -663,21 +1257,76 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
                     (Player::IP, 2) => &mut river_ip_cfr,
                     _ => unreachable!(),
                 };
-                cfr.update(upd.node_id, upd.bucket, &upd.action_values, upd.node_value, upd.reach_prob);
+                cfr.update(upd.node_id, upd.bucket, &upd.action_values, upd.node_value, upd.reach_prob, iter);
             }
         }
     }
+    progress.end_iterations();
+    stats.iterate_ms = timer.lap();
+
+    let oop_equity = range_vs_range_equity(
+        oop_combos, ip_combos, oop_weights, ip_weights, valid_ip_for_oop, remaining_after_flop, runout_table,
+    );
+
+    // 6. Optionally refine flop strategies per combo. The checkpoint keeps
+    // the bucket tables, which are what a resumed solve trains.
+    let bucket_flop_cfr = config.refine_flop_combos.then(|| {
+        let [oop_cfr, ip_cfr] = refine_flop_combos(
+            config, &flop_tree, &turn_template, &river_template,
+            [&flop_oop_cfr, &flop_ip_cfr], [&turn_oop_cfr, &turn_ip_cfr], [&river_oop_cfr, &river_ip_cfr],
+            [oop_combos, ip_combos], [oop_weights, ip_weights], [oop_blockers, ip_blockers],
+            [flop_oop_buckets, flop_ip_buckets], [valid_ip_for_oop, valid_oop_for_ip],
+            turn_bucket_table, runout_table, remaining_after_flop,
+        );
+        (std::mem::replace(&mut flop_oop_cfr, oop_cfr), std::mem::replace(&mut flop_ip_cfr, ip_cfr))
+    });
+    // Refined tables have one "bucket" per combo
+    let combo_ids = |n: usize| (0..n as u16).collect::<Vec<u16>>();
+    let (strategy_oop_buckets, strategy_ip_buckets) = match bucket_flop_cfr {
+        Some(_) => (combo_ids(oop_combos.len()), combo_ids(ip_combos.len())),
+        None => (flop_oop_buckets.clone(), flop_ip_buckets.clone()),
+    };
+
+    // 7. Post-process flop strategies, keeping the raw exploitability and,
+    // for the checkpoint, the raw flop tables
+    let pp = config.postprocess.filter(|p| p.is_active());
+    let mut raw_exploitability = 0.0;
+    let raw_flop_cfr = bucket_flop_cfr.or_else(|| pp.is_some().then(|| (flop_oop_cfr.clone(), flop_ip_cfr.clone())));
+    if let Some(pp) = &pp {
+        raw_exploitability = estimate_exploitability(
+            &flop_tree, &turn_template, &river_template,
+            &flop_oop_cfr, &flop_ip_cfr, &turn_oop_cfr, &turn_ip_cfr,
+            &river_oop_cfr, &river_ip_cfr,
+            oop_combos, ip_combos, oop_weights, ip_weights, oop_blockers, ip_blockers,
+            &strategy_oop_buckets, &strategy_ip_buckets,
+            valid_ip_for_oop, valid_oop_for_ip, runout_table,
+            &config.board, config.starting_pot, config.num_buckets, config.bucketing, EXPLOITABILITY_SAMPLES, config.seed,
+            Some(turn_bucket_table),
+        )
+        .0;
+        let oop_equities = combo_equities(oop_combos, ip_combos, &config.board, RANGE_EQUITY_RUNOUTS);
+        let ip_equities = combo_equities(ip_combos, oop_combos, &config.board, RANGE_EQUITY_RUNOUTS);
+        postprocess_flop_strategies(
+            pp, &flop_tree, &mut flop_oop_cfr, oop_combos, &strategy_oop_buckets, &oop_equities, Player::OOP,
+        );
+        postprocess_flop_strategies(
+            pp, &flop_tree, &mut flop_ip_cfr, ip_combos, &strategy_ip_buckets, &ip_equities, Player::IP,
+        );
+    }
 
-    // 7. Extract solution
-    extract_solution(
+    // 8. Extract solution
+    let mut solution = extract_solution(
         config,
+        progress.iterations_run(),
         &flop_tree,
         &flop_oop_cfr,
         &flop_ip_cfr,
-        &oop_combos,
-        &ip_combos,
-        &flop_oop_buckets,
-        &flop_ip_buckets,
+        oop_combos,
+        ip_combos,
+        oop_weights,
+        ip_weights,
+        &strategy_oop_buckets,
+        &strategy_ip_buckets,
         &flop_metas,
         &turn_template,
         &river_template,
@@ -685,16 +1334,305 @@ pub fn solve_flop(config: &FlopSolverConfig) -> FlopSolution {
         &turn_ip_cfr,
         &river_oop_cfr,
         &river_ip_cfr,
-        &oop_blockers,
-        &ip_blockers,
-        &valid_ip_for_oop,
-        &valid_oop_for_ip,
-    )
+        oop_blockers,
+        ip_blockers,
+        valid_ip_for_oop,
+        valid_oop_for_ip,
+        runout_table,
+        turn_bucket_table,
+    );
+    solution.oop_equity = oop_equity;
+    solution.postprocess = pp.map(|pp| pp.applied(raw_exploitability));
+    // Converged raw strategy, comparable with the snapshots
+    let last = if pp.is_some() { raw_exploitability } else { solution.exploitability };
+    solution.convergence_history = progress.finish(last);
+
+    let (flop_oop_cfr, flop_ip_cfr) = raw_flop_cfr.unwrap_or((flop_oop_cfr, flop_ip_cfr));
+    let cfr = [flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr, river_oop_cfr, river_ip_cfr];
+    let checkpoint = FlopCheckpoint::new(config, precompute, solution.iterations, cfr);
+    if let Some(path) = &config.checkpoint_path {
+        if solution.iterations < config.iterations || start > 0 || config.checkpoint_every.is_some() {
+            checkpoint.save(path);
+        }
+    }
+    stats.extract_ms = timer.lap();
+    Ok((solution, Some(checkpoint), stats))
+}
+
+/// Per-combo flop tables refined from a bucketed solve, indexed like
+/// `Player::index`. Each combo starts at its bucket's average strategy, then
+/// `refine_flop_combos` iterations (a tenth of the solve's) of the same
+/// MCCFR update the flop tables only, each over `REFINE_RUNOUTS` runouts,
+/// with turn and river play frozen at the bucketed averages. The flop tree is
+/// small, so combo-level tables are cheap; combos sharing a bucket part ways
+/// where their blockers change what the opponent continues with.
+#[allow(clippy::too_many_arguments)]
+fn refine_flop_combos(
+    config: &FlopSolverConfig,
+    flop_tree: &TreeNode,
+    turn_template: &TreeNode,
+    river_template: &TreeNode,
+    flop_cfr: [&FlatCfr; 2],
+    turn_cfr: [&FlatCfr; 2],
+    river_cfr: [&FlatCfr; 2],
+    combos: [&[Combo]; 2],
+    weights: [&[f64]; 2],
+    blockers: [&[[bool; 52]]; 2],
+    buckets: [&[u16]; 2],
+    valid_opp: [&[Vec<u16>]; 2],
+    turn_bucket_table: &[(Vec<u16>, Vec<u16>)],
+    runout_table: &RunoutTable,
+    remaining: &[u8],
+) -> [FlatCfr; 2] {
+    let metas = collect_node_metadata(flop_tree);
+    let mut buf = vec![0.0f32; metas.iter().map(|m| m.num_actions as usize).max().unwrap_or(1)];
+    let mut refined = [Player::OOP, Player::IP].map(|player| {
+        let p = player.index();
+        let nodes: Vec<(u8, u16)> = metas
+            .iter()
+            .map(|m| (m.num_actions, if m.player == player { combos[p].len() as u16 } else { 0 }))
+            .collect();
+        let mut cfr = FlatCfr::with_variant(&nodes, config.cfr_variant);
+        // Like a warm start, one iteration's worth of regret so play starts
+        // at the bucket strategy, and next to no weight in the average
+        let regret_scale = (config.starting_pot * combos[1 - p].len() as f64 * REFINE_RUNOUTS as f64) as f32;
+        for m in metas.iter().filter(|m| m.player == player) {
+            let nid = m.node_id as usize;
+            for (h, &b) in buckets[p].iter().enumerate() {
+                flop_cfr[p].average_strategy(nid, b as usize, &mut buf);
+                cfr.seed(nid, h, &buf, regret_scale, 1.0);
+            }
+        }
+        cfr
+    });
+
+    // Later streets play their average strategies: regret matching on
+    // regrets set to them reproduces them, and their updates are dropped
+    let frozen = |cfr: &FlatCfr| {
+        let mut frozen = cfr.clone();
+        let mut buf = vec![0.0f32; u8::MAX as usize];
+        for node in 0..cfr.num_nodes() {
+            for hand in 0..cfr.node_num_hands(node) as usize {
+                cfr.average_strategy(node, hand, &mut buf);
+                frozen.seed(node, hand, &buf, 1.0, 0.0);
+            }
+        }
+        frozen
+    };
+    let turn_cfr = turn_cfr.map(frozen);
+    let river_cfr = river_cfr.map(frozen);
+    let combo_ids = combos.map(|c| (0..c.len() as u16).collect::<Vec<u16>>());
+
+    for iter in 0..(config.iterations / 10).max(1) {
+        let traverser = if iter % 2 == 0 { Player::OOP } else { Player::IP };
+        let (t, o) = (traverser.index(), 1 - traverser.index());
+
+        let mut summed: std::collections::BTreeMap<(usize, usize), (Vec<f32>, f32, f32)> = Default::default();
+        let mut rng = stream_rng(config.seed, REFINE_STREAM + iter as u64);
+        for _ in 0..REFINE_RUNOUTS {
+            let turn_raw_idx = rng.gen_range(0..remaining.len());
+            let river_raw_idx = loop {
+                let ri = rng.gen_range(0..remaining.len());
+                if ri != turn_raw_idx {
+                    break ri;
+                }
+            };
+            let (turn, river) = (remaining[turn_raw_idx] as usize, remaining[river_raw_idx] as usize);
+            let (turn_oop_buckets, turn_ip_buckets) = &turn_bucket_table[turn_raw_idx];
+            let runout = runout_table.get(runout_table.runout_idx(turn_raw_idx, river_raw_idx));
+            let turn_buckets = [turn_oop_buckets, turn_ip_buckets];
+            let river_buckets = [&runout.oop_buckets, &runout.ip_buckets];
+
+            let all_updates: Vec<Vec<RegretUpdate>> = (0..combos[t].len())
+                .into_par_iter()
+                .filter_map(|h| {
+                    if blockers[t][h][turn] || blockers[t][h][river] {
+                        return None;
+                    }
+                    let mut opp_reach = vec![0.0f64; combos[o].len()];
+                    for &j in &valid_opp[t][h] {
+                        let j = j as usize;
+                        if !blockers[o][j][turn] && !blockers[o][j][river] {
+                            opp_reach[j] = weights[o][j];
+                        }
+                    }
+                    let mut updates = Vec::new();
+                    cfr_traverse_flop_ro(
+                        flop_tree, traverser, h, h, turn_buckets[t][h] as usize, river_buckets[t][h] as usize,
+                        &opp_reach, combos[0], combos[1],
+                        blockers[0], blockers[1],
+                        &combo_ids[0], &combo_ids[1],
+                        turn_oop_buckets, turn_ip_buckets,
+                        &runout.oop_buckets, &runout.ip_buckets,
+                        &runout.oop_scores, &runout.ip_scores,
+                        valid_opp[0], valid_opp[1],
+                        config.starting_pot, turn_template, river_template,
+                        &refined[0], &refined[1],
+                        &turn_cfr[0], &turn_cfr[1],
+                        &river_cfr[0], &river_cfr[1],
+                        &mut updates, iter,
+                    );
+                    Some(updates)
+                })
+                .collect();
+
+            for upd in all_updates.into_iter().flatten().filter(|u| u.street == 0) {
+                let (values, node_value, reach) = summed
+                    .entry((upd.node_id, upd.bucket))
+                    .or_insert_with(|| (vec![0.0; upd.action_values.len()], 0.0, 0.0));
+                for (sum, v) in values.iter_mut().zip(&upd.action_values) {
+                    *sum += v;
+                }
+                *node_value += upd.node_value;
+                *reach += upd.reach_prob;
+            }
+        }
+        for ((node, combo), (values, node_value, reach)) in summed {
+            refined[t].update(node, combo, &values, node_value, reach, iter);
+        }
+    }
+    refined
+}
+
+/// Apply `pp` to one player's flop-level strategies in place.
+///
+/// Strategies live at bucket level, so combos are smoothed individually, then
+/// averaged back into their bucket, and purification runs on the bucket
+/// strategy. Extracted per-combo strategies therefore match what was evaluated.
+fn postprocess_flop_strategies(
+    pp: &PostProcess,
+    flop_tree: &TreeNode,
+    cfr: &mut FlatCfr,
+    combos: &[Combo],
+    buckets: &[u16],
+    equities: &[f64],
+    player: Player,
+) {
+    let smooth = PostProcess { purify_threshold: 0.0, ..*pp };
+    let purify = PostProcess { smooth: 0.0, ..*pp };
+    let classes: Vec<u16> = combos.iter().map(hand_class).collect();
+
+    let mut stack = vec![flop_tree];
+    while let Some(node) = stack.pop() {
+        let TreeNode::Action { node_id, player: actor, children, actions, .. } = node else {
+            continue;
+        };
+        stack.extend(children.iter());
+        if *actor != player {
+            continue;
+        }
+        let nid = *node_id as usize;
+        let num_actions = actions.len();
+        let num_buckets = cfr.node_num_hands(nid) as usize;
+
+        let mut buf = vec![0.0f32; num_actions];
+        let mut freqs: Vec<Vec<f64>> = buckets
+            .iter()
+            .map(|&b| {
+                cfr.average_strategy(nid, b as usize, &mut buf);
+                buf.iter().map(|&v| v as f64).collect()
+            })
+            .collect();
+        smooth.apply(&mut freqs, &classes, equities);
+
+        let mut bucket_freqs = vec![vec![0.0f64; num_actions]; num_buckets];
+        let mut counts = vec![0usize; num_buckets];
+        for (f, &b) in freqs.iter().zip(buckets) {
+            for (sum, v) in bucket_freqs[b as usize].iter_mut().zip(f) {
+                *sum += v;
+            }
+            counts[b as usize] += 1;
+        }
+        let used: Vec<usize> = (0..num_buckets).filter(|&b| counts[b] > 0).collect();
+        let mut used_freqs: Vec<Vec<f64>> = used
+            .iter()
+            .map(|&b| bucket_freqs[b].iter().map(|v| v / counts[b] as f64).collect())
+            .collect();
+        purify.apply(&mut used_freqs, &vec![0; used.len()], &vec![0.0; used.len()]);
+        for (&b, f) in used.iter().zip(&used_freqs) {
+            let f32s: Vec<f32> = f.iter().map(|&v| v as f32).collect();
+            cfr.set_average_strategy(nid, b, &f32s);
+        }
+    }
+}
+
+/// Seed one player's flop info sets from a prior solution's per-combo strategies.
+///
+/// Each bucket gets the average prior strategy of its combos that the prior also
+/// had; its average-strategy weight scales with how many such combos there were.
+#[allow(clippy::too_many_arguments)]
+fn seed_flop_nodes(
+    strategies: &[FlopNodeStrategy],
+    player: &str,
+    prior_combos: &[String],
+    combos: &[Combo],
+    buckets: &[u16],
+    cfr: &mut FlatCfr,
+    regret_scale: f32,
+    weight: f32,
+) -> Result<(), String> {
+    let prior_idx: Vec<Option<usize>> = combos
+        .iter()
+        .map(|c| find_combo_index(&c.to_string(), prior_combos))
+        .collect();
+
+    for strat in strategies.iter().filter(|s| s.player == player) {
+        let node = strat.node_id as usize;
+        if node >= cfr.num_nodes() || cfr.node_num_actions(node) as usize != strat.actions.len() {
+            return Err(format!("Cached flop tree does not match at node {}", node));
+        }
+        let num_actions = strat.actions.len();
+        let num_buckets = cfr.node_num_hands(node) as usize;
+        let mut sums = vec![vec![0.0f64; num_actions]; num_buckets];
+        let mut counts = vec![0usize; num_buckets];
+        for (h, pi) in prior_idx.iter().enumerate() {
+            if let Some(freqs) = pi.and_then(|pi| strat.frequencies.get(pi)) {
+                let b = buckets[h] as usize;
+                for (sum, f) in sums[b].iter_mut().zip(freqs) {
+                    *sum += f;
+                }
+                counts[b] += 1;
+            }
+        }
+        let mut avg = vec![0.0f32; num_actions];
+        for b in 0..num_buckets {
+            if counts[b] == 0 {
+                continue;
+            }
+            for (a, sum) in avg.iter_mut().zip(&sums[b]) {
+                *a = (*sum / counts[b] as f64) as f32;
+            }
+            cfr.seed(node, b, &avg, regret_scale, weight * counts[b] as f32);
+        }
+    }
+    Ok(())
+}
+
+/// Seed one player's turn/river template info sets from prior bucket strategies.
+fn seed_template_nodes(
+    strategies: &[TemplateBucketStrategy],
+    player: &str,
+    cfr: &mut FlatCfr,
+    regret_scale: f32,
+    weight: f32,
+) -> Result<(), String> {
+    for strat in strategies.iter().filter(|s| s.player == player) {
+        let node = strat.node_id as usize;
+        if node >= cfr.num_nodes() || cfr.node_num_actions(node) as usize != strat.actions.len() {
+            return Err(format!("Cached template tree does not match at node {}", node));
+        }
+        let num_buckets = cfr.node_num_hands(node) as usize;
+        for (b, freqs) in strat.frequencies.iter().enumerate().take(num_buckets) {
+            let seed: Vec<f32> = freqs.iter().map(|&f| f as f32).collect();
+            cfr.seed(node, b, &seed, regret_scale, weight);
+        }
+    }
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
-// MCCFR traversal: flop level
-// ---------------------------------------------------------------------------
+// MCCFR traversal: flop level
+// ---------------------------------------------------------------------------
 
 /// Traverse the flop action tree. At Showdown terminals, chain to the turn template.
 #[allow(clippy::too_many_arguments)]
@@ -737,6 +1675,7 @@ fn cfr_traverse_flop(
         TreeNode::Terminal {
             terminal_type,
             pot,
+            stacks,
             invested,
             ..
         } => {
@@ -757,6 +1696,7 @@ fn cfr_traverse_flop(
                 TerminalType::Showdown => {
                     // Chain to turn template, scaling by the pot at this point
                     let turn_scale = *pot;
+                    let turn_stack = stacks[0].min(stacks[1]);
                     let turn_value = cfr_traverse_turn_template(
                         turn_template,
                         traverser,
@@ -775,6 +1715,7 @@ fn cfr_traverse_flop(
                         valid_ip_for_oop,
                         valid_oop_for_ip,
                         turn_scale,
+                        turn_stack,
                         river_template,
                         turn_oop_cfr,
                         turn_ip_cfr,
@@ -867,6 +1808,7 @@ fn cfr_traverse_flop(
                     &action_values_buf[..num_actions],
                     node_value as f32,
                     reach_prob,
+                    iter,
                 );
 
                 node_value
@@ -977,6 +1919,7 @@ fn cfr_traverse_turn_template(
     valid_ip_for_oop: &[Vec<u16>],
     valid_oop_for_ip: &[Vec<u16>],
     scale: f64,
+    stack: f64,
     river_template: &TreeNode,
     turn_oop_cfr: &mut FlatCfr,
     turn_ip_cfr: &mut FlatCfr,
@@ -986,54 +1929,33 @@ fn cfr_traverse_turn_template(
     action_values_buf: &mut [f32],
     iter: usize,
 ) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let opp_reach_sum: f64 = opp_reach.iter().sum();
+        if opp_reach_sum < 1e-10 {
+            return 0.0;
+        }
+        // Chain to the river template with whatever stack is left behind
+        let river_scale = scale + put_in[0] + put_in[1];
+        let river_stack = stack - put_in[0].max(put_in[1]);
+        let river_value = cfr_traverse_river_template(
+            river_template, traverser, hand_idx, river_bucket,
+            opp_reach, oop_combos, ip_combos,
+            river_oop_buckets, river_ip_buckets,
+            oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
+            river_scale, river_stack, river_oop_cfr, river_ip_cfr, strategy_buf, action_values_buf, iter,
+        );
+        return river_value - put_in[traverser.index()] * opp_reach_sum;
+    }
     match node {
-        TreeNode::Terminal {
-            terminal_type,
-            pot,
-            invested,
-            ..
-        } => {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
             let opp_reach_sum: f64 = opp_reach.iter().sum();
             if opp_reach_sum < 1e-10 {
                 return 0.0;
             }
-
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    let my_invested = invested[traverser.index()] * scale;
-                    if *folder == traverser {
-                        -my_invested * opp_reach_sum
-                    } else {
-                        let pot_scaled = *pot * scale;
-                        (pot_scaled - my_invested) * opp_reach_sum
-                    }
-                }
-                TerminalType::Showdown => {
-                    // Chain to river template
-                    let river_scale = *pot * scale;
-                    cfr_traverse_river_template(
-                        river_template,
-                        traverser,
-                        hand_idx,
-                        river_bucket,
-                        opp_reach,
-                        oop_combos,
-                        ip_combos,
-                        river_oop_buckets,
-                        river_ip_buckets,
-                        oop_scores,
-                        ip_scores,
-                        valid_ip_for_oop,
-                        valid_oop_for_ip,
-                        river_scale,
-                        river_oop_cfr,
-                        river_ip_cfr,
-                        strategy_buf,
-                        action_values_buf,
-                        iter,
-                    )
-                }
-            }
+            template_fold_value(traverser, *folder, invested, scale, stack, opp_reach_sum)
         }
         TreeNode::Action {
             node_id,
@@ -1077,6 +1999,7 @@ fn cfr_traverse_turn_template(
                         valid_ip_for_oop,
                         valid_oop_for_ip,
                         scale,
+                        stack,
                         river_template,
                         turn_oop_cfr,
                         turn_ip_cfr,
@@ -1103,6 +2026,7 @@ fn cfr_traverse_turn_template(
                     &action_values_buf[..num_actions],
                     node_value as f32,
                     reach_prob,
+                    iter,
                 );
 
                 node_value
@@ -1158,6 +2082,7 @@ fn cfr_traverse_turn_template(
                         valid_ip_for_oop,
                         valid_oop_for_ip,
                         scale,
+                        stack,
                         river_template,
                         turn_oop_cfr,
                         turn_ip_cfr,
@@ -1200,85 +2125,33 @@ fn cfr_traverse_river_template(
     valid_ip_for_oop: &[Vec<u16>],
     valid_oop_for_ip: &[Vec<u16>],
     scale: f64,
+    stack: f64,
     river_oop_cfr: &mut FlatCfr,
     river_ip_cfr: &mut FlatCfr,
     strategy_buf: &mut [f32],
     action_values_buf: &mut [f32],
     iter: usize,
 ) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let opp_reach_sum: f64 = opp_reach.iter().sum();
+        if opp_reach_sum < 1e-10 {
+            return 0.0;
+        }
+        return river_showdown_value(
+            traverser, hand_idx, opp_reach, oop_scores, ip_scores,
+            valid_ip_for_oop, valid_oop_for_ip, scale, put_in,
+        );
+    }
     match node {
-        TreeNode::Terminal {
-            terminal_type,
-            pot,
-            invested,
-            ..
-        } => {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
             let opp_reach_sum: f64 = opp_reach.iter().sum();
             if opp_reach_sum < 1e-10 {
                 return 0.0;
             }
-
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    let my_invested = invested[traverser.index()] * scale;
-                    if *folder == traverser {
-                        -my_invested * opp_reach_sum
-                    } else {
-                        let pot_scaled = *pot * scale;
-                        (pot_scaled - my_invested) * opp_reach_sum
-                    }
-                }
-                TerminalType::Showdown => {
-                    // Actual showdown evaluation
-                    let pot_scaled = *pot * scale;
-                    let my_invested = invested[traverser.index()] * scale;
-                    let win_payoff = pot_scaled - my_invested;
-                    let lose_payoff = -my_invested;
-                    let tie_payoff = pot_scaled / 2.0 - my_invested;
-                    let mut value = 0.0;
-
-                    match traverser {
-                        Player::OOP => {
-                            let my_score = oop_scores[hand_idx];
-                            for &j in &valid_ip_for_oop[hand_idx] {
-                                let j = j as usize;
-                                if opp_reach[j] < 1e-10 {
-                                    continue;
-                                }
-                                let opp_score = ip_scores[j];
-                                let payoff = if my_score > opp_score {
-                                    win_payoff
-                                } else if my_score < opp_score {
-                                    lose_payoff
-                                } else {
-                                    tie_payoff
-                                };
-                                value += opp_reach[j] * payoff;
-                            }
-                        }
-                        Player::IP => {
-                            let my_score = ip_scores[hand_idx];
-                            for &i in &valid_oop_for_ip[hand_idx] {
-                                let i = i as usize;
-                                if opp_reach[i] < 1e-10 {
-                                    continue;
-                                }
-                                let opp_score = oop_scores[i];
-                                let payoff = if my_score > opp_score {
-                                    win_payoff
-                                } else if my_score < opp_score {
-                                    lose_payoff
-                                } else {
-                                    tie_payoff
-                                };
-                                value += opp_reach[i] * payoff;
-                            }
-                        }
-                    }
-
-                    value
-                }
-            }
+            template_fold_value(traverser, *folder, invested, scale, stack, opp_reach_sum)
         }
         TreeNode::Action {
             node_id,
@@ -1319,6 +2192,7 @@ fn cfr_traverse_river_template(
                         valid_ip_for_oop,
                         valid_oop_for_ip,
                         scale,
+                        stack,
                         river_oop_cfr,
                         river_ip_cfr,
                         strategy_buf,
@@ -1342,6 +2216,7 @@ fn cfr_traverse_river_template(
                     &action_values_buf[..num_actions],
                     node_value as f32,
                     reach_prob,
+                    iter,
                 );
 
                 node_value
@@ -1394,6 +2269,7 @@ fn cfr_traverse_river_template(
                         valid_ip_for_oop,
                         valid_oop_for_ip,
                         scale,
+                        stack,
                         river_oop_cfr,
                         river_ip_cfr,
                         strategy_buf,
@@ -1415,17 +2291,6 @@ fn cfr_traverse_river_template(
 // Parallel traversal: readonly + collected updates
 // ---------------------------------------------------------------------------
 
-/// A collected regret update for deferred application after parallel traversal.
-struct RegretUpdate {
-    /// 0 = flop, 1 = turn, 2 = river
-    street: u8,
-    node_id: usize,
-    bucket: usize,
-    action_values: Vec<f32>,
-    node_value: f32,
-    reach_prob: f32,
-}
-
 /// Readonly flop traversal that collects RegretUpdates instead of mutating CFR.
 #[allow(clippy::too_many_arguments)]
 fn cfr_traverse_flop_ro(
@@ -1445,7 +2310,7 @@ fn cfr_traverse_flop_ro(
     updates: &mut Vec<RegretUpdate>, iter: usize,
 ) -> f64 {
     match node {
-        TreeNode::Terminal { terminal_type, pot, invested, .. } => {
+        TreeNode::Terminal { terminal_type, pot, stacks, invested } => {
             let opp_reach_sum: f64 = opp_reach.iter().sum();
             if opp_reach_sum < 1e-10 { return 0.0; }
             let my_invested = invested[traverser.index()];
@@ -1456,13 +2321,14 @@ fn cfr_traverse_flop_ro(
                 }
                 TerminalType::Showdown => {
                     let turn_scale = *pot;
+                    let turn_stack = stacks[0].min(stacks[1]);
                     let turn_value = cfr_traverse_turn_template_ro(
                         turn_template, traverser, hand_idx, turn_bucket, river_bucket,
                         opp_reach, oop_combos, ip_combos,
                         turn_oop_buckets, turn_ip_buckets,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        turn_scale, river_template,
+                        turn_scale, turn_stack, river_template,
                         turn_oop_cfr, turn_ip_cfr, river_oop_cfr, river_ip_cfr,
                         updates, iter,
                     );
@@ -1570,32 +2436,38 @@ fn cfr_traverse_turn_template_ro(
     river_oop_buckets: &[u16], river_ip_buckets: &[u16],
     oop_scores: &[u32], ip_scores: &[u32],
     valid_ip_for_oop: &[Vec<u16>], valid_oop_for_ip: &[Vec<u16>],
-    scale: f64, river_template: &TreeNode,
+    scale: f64, stack: f64, river_template: &TreeNode,
     turn_oop_cfr: &FlatCfr, turn_ip_cfr: &FlatCfr,
     river_oop_cfr: &FlatCfr, river_ip_cfr: &FlatCfr,
     updates: &mut Vec<RegretUpdate>, iter: usize,
 ) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let opp_reach_sum: f64 = opp_reach.iter().sum();
+        if opp_reach_sum < 1e-10 {
+            return 0.0;
+        }
+        // Chain to the river template with whatever stack is left behind
+        let river_scale = scale + put_in[0] + put_in[1];
+        let river_stack = stack - put_in[0].max(put_in[1]);
+        let river_value = cfr_traverse_river_template_ro(
+            river_template, traverser, hand_idx, river_bucket,
+            opp_reach, oop_combos, ip_combos,
+            river_oop_buckets, river_ip_buckets,
+            oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
+            river_scale, river_stack, river_oop_cfr, river_ip_cfr, updates, iter,
+        );
+        return river_value - put_in[traverser.index()] * opp_reach_sum;
+    }
     match node {
-        TreeNode::Terminal { terminal_type, pot, invested, .. } => {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
             let opp_reach_sum: f64 = opp_reach.iter().sum();
-            if opp_reach_sum < 1e-10 { return 0.0; }
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    let my_invested = invested[traverser.index()] * scale;
-                    if *folder == traverser { -my_invested * opp_reach_sum }
-                    else { (*pot * scale - my_invested) * opp_reach_sum }
-                }
-                TerminalType::Showdown => {
-                    let river_scale = *pot * scale;
-                    cfr_traverse_river_template_ro(
-                        river_template, traverser, hand_idx, river_bucket,
-                        opp_reach, oop_combos, ip_combos,
-                        river_oop_buckets, river_ip_buckets,
-                        oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        river_scale, river_oop_cfr, river_ip_cfr, updates, iter,
-                    )
-                }
+            if opp_reach_sum < 1e-10 {
+                return 0.0;
             }
+            template_fold_value(traverser, *folder, invested, scale, stack, opp_reach_sum)
         }
         TreeNode::Action { node_id, player, children, actions, .. } => {
             let num_actions = actions.len();
@@ -1617,7 +2489,7 @@ fn cfr_traverse_turn_template_ro(
                         turn_oop_buckets, turn_ip_buckets,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        scale, river_template,
+                        scale, stack, river_template,
                         turn_oop_cfr, turn_ip_cfr, river_oop_cfr, river_ip_cfr,
                         updates, iter,
                     );
@@ -1663,7 +2535,7 @@ fn cfr_traverse_turn_template_ro(
                         turn_oop_buckets, turn_ip_buckets,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        scale, river_template,
+                        scale, stack, river_template,
                         turn_oop_cfr, turn_ip_cfr, river_oop_cfr, river_ip_cfr,
                         updates, iter,
                     );
@@ -1684,49 +2556,29 @@ fn cfr_traverse_river_template_ro(
     river_oop_buckets: &[u16], river_ip_buckets: &[u16],
     oop_scores: &[u32], ip_scores: &[u32],
     valid_ip_for_oop: &[Vec<u16>], valid_oop_for_ip: &[Vec<u16>],
-    scale: f64, river_oop_cfr: &FlatCfr, river_ip_cfr: &FlatCfr,
+    scale: f64, stack: f64, river_oop_cfr: &FlatCfr, river_ip_cfr: &FlatCfr,
     updates: &mut Vec<RegretUpdate>, iter: usize,
 ) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let opp_reach_sum: f64 = opp_reach.iter().sum();
+        if opp_reach_sum < 1e-10 {
+            return 0.0;
+        }
+        return river_showdown_value(
+            traverser, hand_idx, opp_reach, oop_scores, ip_scores,
+            valid_ip_for_oop, valid_oop_for_ip, scale, put_in,
+        );
+    }
     match node {
-        TreeNode::Terminal { terminal_type, pot, invested, .. } => {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
             let opp_reach_sum: f64 = opp_reach.iter().sum();
-            if opp_reach_sum < 1e-10 { return 0.0; }
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    let my_invested = invested[traverser.index()] * scale;
-                    if *folder == traverser { -my_invested * opp_reach_sum }
-                    else { (*pot * scale - my_invested) * opp_reach_sum }
-                }
-                TerminalType::Showdown => {
-                    let pot_scaled = *pot * scale;
-                    let my_invested = invested[traverser.index()] * scale;
-                    let win_payoff = pot_scaled - my_invested;
-                    let lose_payoff = -my_invested;
-                    let tie_payoff = pot_scaled / 2.0 - my_invested;
-                    let mut value = 0.0;
-                    match traverser {
-                        Player::OOP => {
-                            let my_score = oop_scores[hand_idx];
-                            for &j in &valid_ip_for_oop[hand_idx] {
-                                let j = j as usize;
-                                if opp_reach[j] < 1e-10 { continue; }
-                                let opp_score = ip_scores[j];
-                                value += opp_reach[j] * if my_score > opp_score { win_payoff } else if my_score < opp_score { lose_payoff } else { tie_payoff };
-                            }
-                        }
-                        Player::IP => {
-                            let my_score = ip_scores[hand_idx];
-                            for &i in &valid_oop_for_ip[hand_idx] {
-                                let i = i as usize;
-                                if opp_reach[i] < 1e-10 { continue; }
-                                let opp_score = oop_scores[i];
-                                value += opp_reach[i] * if my_score > opp_score { win_payoff } else if my_score < opp_score { lose_payoff } else { tie_payoff };
-                            }
-                        }
-                    }
-                    value
-                }
+            if opp_reach_sum < 1e-10 {
+                return 0.0;
             }
+            template_fold_value(traverser, *folder, invested, scale, stack, opp_reach_sum)
         }
         TreeNode::Action { node_id, player, children, actions, .. } => {
             let num_actions = actions.len();
@@ -1747,7 +2599,7 @@ fn cfr_traverse_river_template_ro(
                         opp_reach, oop_combos, ip_combos,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        scale, river_oop_cfr, river_ip_cfr, updates, iter,
+                        scale, stack, river_oop_cfr, river_ip_cfr, updates, iter,
                     );
                     action_vals[a] = av as f32;
                     node_value += strategy[a] as f64 * av;
@@ -1790,7 +2642,7 @@ fn cfr_traverse_river_template_ro(
                         &new_opp_reach, oop_combos, ip_combos,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        scale, river_oop_cfr, river_ip_cfr, updates, iter,
+                        scale, stack, river_oop_cfr, river_ip_cfr, updates, iter,
                     );
                 }
                 node_value
@@ -1805,6 +2657,17 @@ fn cfr_traverse_river_template_ro(
 // ---------------------------------------------------------------------------
 
 /// Estimate exploitability via Monte Carlo best-response sampling.
+///
+/// Returns the estimate, its standard error (from the spread of the
+/// per-runout estimates) and the flop action values, all from the same
+/// sampled runouts. The best response here sees each runout before acting on
+/// the flop, which is cheap enough for progress snapshots but overstates it;
+/// `best_response_totals` doesn't.
+///
+/// Progress snapshots use the fast mode: few `num_samples` and the solve's
+/// own `turn_bucket_table` (indexed like the remaining cards), so a check
+/// costs about as much as a handful of iterations. Without the table, turn
+/// buckets are built for the sampled turn cards.
 #[allow(clippy::too_many_arguments)]
 fn estimate_exploitability(
     flop_tree: &TreeNode,
@@ -1818,98 +2681,82 @@ fn estimate_exploitability(
     river_ip_cfr: &FlatCfr,
     oop_combos: &[Combo],
     ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
     oop_blockers: &[[bool; 52]],
     ip_blockers: &[[bool; 52]],
     flop_oop_buckets: &[u16],
     flop_ip_buckets: &[u16],
     valid_ip_for_oop: &[Vec<u16>],
     valid_oop_for_ip: &[Vec<u16>],
+    runout_table: &RunoutTable,
     board: &[u8],
     starting_pot: f64,
     num_buckets: usize,
-) -> f64 {
-    let remaining = remaining_deck(board);
+    bucketing: BucketingStrategy,
+    num_samples: usize,
+    seed: Option<u64>,
+    turn_bucket_table: Option<&[(Vec<u16>, Vec<u16>)]>,
+) -> (f64, f64, ActionValues) {
+    let remaining = runout_table.remaining();
     let num_remaining = remaining.len();
-    let num_samples = 100;
-    let mut rng = rand::thread_rng();
+    let mut rng = stream_rng(seed, ESTIMATE_STREAM);
 
     let oop_pairs: Vec<(u8, u8)> = oop_combos.iter().map(|c| (c.0, c.1)).collect();
     let ip_pairs: Vec<(u8, u8)> = ip_combos.iter().map(|c| (c.0, c.1)).collect();
 
-    // Precompute turn buckets for exploitability estimation
-    let turn_bucket_table: Vec<(Vec<u16>, Vec<u16>)> = remaining
-        .par_iter()
-        .map(|&turn_card| {
-            let turn_board = [board[0], board[1], board[2], turn_card];
-            let t_oop = assign_buckets(&oop_pairs, &turn_board, num_buckets, 200);
-            let t_ip = assign_buckets(&ip_pairs, &turn_board, num_buckets, 200);
-            (t_oop, t_ip)
-        })
-        .collect();
-
-    // Precompute river data for exploitability estimation
-    let num_runouts = num_remaining * (num_remaining - 1);
-    let river_data: Vec<((Vec<u16>, Vec<u16>), (Vec<u32>, Vec<u32>))> = (0..num_runouts)
-        .into_par_iter()
-        .map(|runout_idx| {
-            let turn_idx = runout_idx / (num_remaining - 1);
-            let river_adj = runout_idx % (num_remaining - 1);
-            let river_idx = if river_adj >= turn_idx {
-                river_adj + 1
-            } else {
-                river_adj
+    // Draw the runouts first so turn buckets are only built for those
+    let runouts: Vec<(usize, usize)> = (0..num_samples)
+        .map(|_| {
+            let turn_raw_idx = rng.gen_range(0..num_remaining);
+            let river_raw_idx = loop {
+                let ri = rng.gen_range(0..num_remaining);
+                if ri != turn_raw_idx {
+                    break ri;
+                }
             };
-            let turn_card = remaining[turn_idx];
-            let river_card = remaining[river_idx];
-            let river_board = [board[0], board[1], board[2], turn_card, river_card];
-            let r_oop = assign_buckets(&oop_pairs, &river_board, num_buckets, 0);
-            let r_ip = assign_buckets(&ip_pairs, &river_board, num_buckets, 0);
-            let s_oop: Vec<u32> = oop_pairs
-                .iter()
-                .map(|&(c0, c1)| {
-                    evaluate_fast(&[
-                        c0, c1, river_board[0], river_board[1], river_board[2],
-                        river_board[3], river_board[4],
-                    ])
-                })
-                .collect();
-            let s_ip: Vec<u32> = ip_pairs
-                .iter()
-                .map(|&(c0, c1)| {
-                    evaluate_fast(&[
-                        c0, c1, river_board[0], river_board[1], river_board[2],
-                        river_board[3], river_board[4],
-                    ])
-                })
-                .collect();
-            ((r_oop, r_ip), (s_oop, s_ip))
+            (turn_raw_idx, river_raw_idx)
         })
         .collect();
 
-    let mut oop_total_gain = 0.0;
-    let mut ip_total_gain = 0.0;
-    let mut sample_count = 0;
+    // Turn buckets for each sampled turn card, unless the solve's are given
+    let built_turn_buckets: std::collections::HashMap<usize, _> = match turn_bucket_table {
+        Some(_) => Default::default(),
+        None => {
+            let mut turn_idxs: Vec<usize> = runouts.iter().map(|&(t, _)| t).collect();
+            turn_idxs.sort_unstable();
+            turn_idxs.dedup();
+            turn_idxs
+                .par_iter()
+                .map(|&turn_idx| {
+                    let turn_board = [board[0], board[1], board[2], remaining[turn_idx]];
+                    (turn_idx, turn_buckets(&oop_pairs, &ip_pairs, &turn_board, turn_idx, num_buckets, bucketing, seed))
+                })
+                .collect()
+        }
+    };
 
-    for _ in 0..num_samples {
-        let turn_raw_idx = rng.gen_range(0..num_remaining);
+    // Each combo's gain and value count at its range frequency; each side's
+    // gain is normalized by its own combo weight, since the ranges needn't
+    // be the same size
+    let mut total_gain = [0.0f64; 2];
+    let mut sample_weight = [0.0f64; 2];
+    let mut action_values = ActionValues::default();
+    // Each side's (gain, weight) on each runout, for the standard error
+    let mut per_runout: Vec<[(f64, f64); 2]> = Vec::with_capacity(runouts.len());
+
+    for &(turn_raw_idx, river_raw_idx) in &runouts {
+        let (gain_before, weight_before) = (total_gain, sample_weight);
         let turn_card = remaining[turn_raw_idx];
-        let river_raw_idx = loop {
-            let ri = rng.gen_range(0..num_remaining);
-            if ri != turn_raw_idx {
-                break ri;
-            }
-        };
         let river_card = remaining[river_raw_idx];
 
-        let (turn_oop_buckets, turn_ip_buckets) = &turn_bucket_table[turn_raw_idx];
-        let runout_idx = turn_raw_idx * (num_remaining - 1)
-            + if river_raw_idx > turn_raw_idx {
-                river_raw_idx - 1
-            } else {
-                river_raw_idx
-            };
-        let ((river_oop_buckets, river_ip_buckets), (oop_scores, ip_scores)) =
-            &river_data[runout_idx];
+        let (turn_oop_buckets, turn_ip_buckets) = match turn_bucket_table {
+            Some(table) => &table[turn_raw_idx],
+            None => &built_turn_buckets[&turn_raw_idx],
+        };
+        let river = runout_table.get(runout_table.runout_idx(turn_raw_idx, river_raw_idx));
+        let (river_oop_buckets, river_ip_buckets) = (&river.oop_buckets, &river.ip_buckets);
+        let (oop_scores, ip_scores) = (&river.oop_scores, &river.ip_scores);
 
         let mut strat_buf = vec![0.0f32; 16];
 
@@ -1922,7 +2769,7 @@ fn estimate_exploitability(
             for &j in &valid_ip_for_oop[h] {
                 let j = j as usize;
                 if !ip_blockers[j][turn_card as usize] && !ip_blockers[j][river_card as usize] {
-                    opp_reach[j] = 1.0;
+                    opp_reach[j] = ip_weights[j];
                 }
             }
 
@@ -1941,7 +2788,7 @@ fn estimate_exploitability(
                 flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
                 river_oop_cfr, river_ip_cfr, &mut strat_buf, true,
             );
-            let avg_val = br_traverse_flop(
+            let avg_val = ev_traverse_flop(
                 flop_tree, Player::OOP, h, flop_bucket, turn_bucket, river_bucket,
                 &opp_reach, oop_combos, ip_combos,
                 flop_oop_buckets, flop_ip_buckets,
@@ -1950,10 +2797,11 @@ fn estimate_exploitability(
                 oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
                 starting_pot, turn_template, river_template,
                 flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
-                river_oop_cfr, river_ip_cfr, &mut strat_buf, false,
+                river_oop_cfr, river_ip_cfr, &mut strat_buf, &mut action_values,
             );
-            oop_total_gain += br_val - avg_val;
-            sample_count += 1;
+            let weight = oop_weights[h];
+            total_gain[0] += weight * (br_val - avg_val);
+            sample_weight[0] += weight;
         }
 
         // Compute BR and avg value for IP
@@ -1965,7 +2813,7 @@ fn estimate_exploitability(
             for &i in &valid_oop_for_ip[h] {
                 let i = i as usize;
                 if !oop_blockers[i][turn_card as usize] && !oop_blockers[i][river_card as usize] {
-                    opp_reach[i] = 1.0;
+                    opp_reach[i] = oop_weights[i];
                 }
             }
 
@@ -1984,7 +2832,7 @@ fn estimate_exploitability(
                 flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
                 river_oop_cfr, river_ip_cfr, &mut strat_buf, true,
             );
-            let avg_val = br_traverse_flop(
+            let avg_val = ev_traverse_flop(
                 flop_tree, Player::IP, h, flop_bucket, turn_bucket, river_bucket,
                 &opp_reach, oop_combos, ip_combos,
                 flop_oop_buckets, flop_ip_buckets,
@@ -1993,17 +2841,148 @@ fn estimate_exploitability(
                 oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
                 starting_pot, turn_template, river_template,
                 flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
-                river_oop_cfr, river_ip_cfr, &mut strat_buf, false,
+                river_oop_cfr, river_ip_cfr, &mut strat_buf, &mut action_values,
             );
-            ip_total_gain += br_val - avg_val;
+            let weight = ip_weights[h];
+            total_gain[1] += weight * (br_val - avg_val);
+            sample_weight[1] += weight;
         }
+        per_runout.push([0, 1].map(|p| (total_gain[p] - gain_before[p], sample_weight[p] - weight_before[p])));
     }
 
-    if sample_count > 0 {
-        (oop_total_gain + ip_total_gain) / (2.0 * sample_count as f64)
-    } else {
-        0.0
+    let (exploitability, gain_per_weight) = combine_side_gains(total_gain, sample_weight);
+    let std_err = ratio_std_err(&per_runout, gain_per_weight);
+    (exploitability, std_err, action_values)
+}
+
+/// Exploitability from each side's summed gain and combo weight: each
+/// side's gain per unit of its own weight (also returned), averaged over
+/// the two, as the turn solver computes it.
+fn combine_side_gains(total_gain: [f64; 2], weight: [f64; 2]) -> (f64, [f64; 2]) {
+    let per_weight = [0, 1].map(|p| if weight[p] > 0.0 { total_gain[p] / weight[p] } else { 0.0 });
+    ((per_weight[0] + per_weight[1]) / 2.0, per_weight)
+}
+
+/// Standard error of the estimate `(Σ gain_oop / Σ weight_oop + Σ gain_ip /
+/// Σ weight_ip) / 2` over independent samples of each side's (gain, weight),
+/// by linearization; `ratios` are the two per-side ratios.
+fn ratio_std_err(samples: &[[(f64, f64); 2]], ratios: [f64; 2]) -> f64 {
+    let n = samples.len();
+    let total_weight = [0, 1].map(|p| samples.iter().map(|s| s[p].1).sum::<f64>());
+    if n < 2 {
+        return 0.0;
+    }
+    let residuals: f64 = samples
+        .iter()
+        .map(|s| {
+            (0..2)
+                .filter(|&p| total_weight[p] > 0.0)
+                .map(|p| (s[p].0 - ratios[p] * s[p].1) / (2.0 * total_weight[p]))
+                .sum::<f64>()
+                .powi(2)
+        })
+        .sum();
+    (residuals * n as f64 / (n - 1) as f64).sqrt()
+}
+
+/// Average-strategy values of each flop action, summed over sampled runouts
+/// and opponent combos, with the opponent reach they were weighted by.
+#[derive(Default)]
+struct ActionValues {
+    /// [node_id][combo_idx][action_idx]
+    values: Vec<Vec<Vec<f64>>>,
+    /// [node_id][combo_idx]
+    reach: Vec<Vec<f64>>,
+}
+
+impl ActionValues {
+    fn add(&mut self, nid: usize, hand_idx: usize, num_combos: usize, values: &[f64], reach: f64) {
+        if self.values.len() <= nid {
+            self.values.resize(nid + 1, Vec::new());
+            self.reach.resize(nid + 1, Vec::new());
+        }
+        if self.values[nid].is_empty() {
+            self.values[nid] = vec![vec![0.0; values.len()]; num_combos];
+            self.reach[nid] = vec![0.0; num_combos];
+        }
+        for (sum, v) in self.values[nid][hand_idx].iter_mut().zip(values) {
+            *sum += v;
+        }
+        self.reach[nid][hand_idx] += reach;
     }
+
+    /// Per-combo action EVs at a node in starting pots (see `FlopNodeStrategy::evs`).
+    fn evs(&self, nid: usize, num_combos: usize, num_actions: usize, starting_pot: f64) -> Vec<Vec<f64>> {
+        (0..num_combos)
+            .map(|h| {
+                let reach = self.reach.get(nid).and_then(|r| r.get(h)).copied().unwrap_or(0.0);
+                if reach < 1e-10 {
+                    return vec![0.0; num_actions];
+                }
+                self.values[nid][h].iter().map(|v| v / reach / starting_pot).collect()
+            })
+            .collect()
+    }
+}
+
+/// OOP's range-vs-range showdown equity, read from the runout table's scores.
+///
+/// Uses an evenly spaced subset of at most `RANGE_EQUITY_RUNOUTS` runouts so the
+/// cost stays small next to the solve; combos blocked by the runout or by each
+/// other are skipped.
+fn range_vs_range_equity(
+    oop_combos: &[Combo],
+    ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
+    valid_ip_for_oop: &[Vec<u16>],
+    remaining: &[u8],
+    runout_table: &RunoutTable,
+) -> f64 {
+    if remaining.len() < 2 || runout_table.is_empty() {
+        return 0.0;
+    }
+    let step = (runout_table.len() / RANGE_EQUITY_RUNOUTS).max(1);
+
+    let (wins, total) = (0..runout_table.len())
+        .step_by(step)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|runout_idx| {
+            let (turn_idx, river_idx) = runout_table.cards(runout_idx);
+            let dead = [remaining[turn_idx], remaining[river_idx]];
+            let blocked = |c: &Combo| dead.contains(&c.0) || dead.contains(&c.1);
+            let runout = runout_table.get(runout_idx);
+            let (oop_scores, ip_scores) = (&runout.oop_scores, &runout.ip_scores);
+
+            let mut wins = 0.0f64;
+            let mut total = 0.0f64;
+            for (i, oop) in oop_combos.iter().enumerate() {
+                if blocked(oop) {
+                    continue;
+                }
+                for &j in &valid_ip_for_oop[i] {
+                    let j = j as usize;
+                    if blocked(&ip_combos[j]) {
+                        continue;
+                    }
+                    let weight = oop_weights[i] * ip_weights[j];
+                    total += weight;
+                    match oop_scores[i].cmp(&ip_scores[j]) {
+                        std::cmp::Ordering::Greater => wins += weight,
+                        std::cmp::Ordering::Equal => wins += 0.5 * weight,
+                        std::cmp::Ordering::Less => {}
+                    }
+                }
+            }
+            (wins, total)
+        })
+        .collect::<Vec<_>>()
+        // Summed in runout order so the result doesn't depend on thread timing
+        .into_iter()
+        .fold((0.0, 0.0), |a, b| (a.0 + b.0, a.1 + b.1));
+
+    if total > 0.0 { wins / total } else { 0.0 }
 }
 
 // ---------------------------------------------------------------------------
@@ -2047,6 +3026,7 @@ fn br_traverse_flop(
         TreeNode::Terminal {
             terminal_type,
             pot,
+            stacks,
             invested,
             ..
         } => {
@@ -2065,6 +3045,7 @@ fn br_traverse_flop(
                 }
                 TerminalType::Showdown => {
                     let turn_scale = *pot;
+                    let turn_stack = stacks[0].min(stacks[1]);
                     let turn_val = br_traverse_turn_template(
                         turn_template,
                         br_player,
@@ -2083,6 +3064,7 @@ fn br_traverse_flop(
                         valid_ip_for_oop,
                         valid_oop_for_ip,
                         turn_scale,
+                        turn_stack,
                         river_template,
                         turn_oop_cfr,
                         turn_ip_cfr,
@@ -2129,6 +3111,8 @@ fn br_traverse_flop(
                         Player::IP => flop_ip_cfr,
                     };
                     cfr.average_strategy(nid, flop_bucket, strat_buf);
+                    // Children overwrite strat_buf
+                    let strategy: Vec<f64> = strat_buf[..num_actions].iter().map(|&p| p as f64).collect();
                     let mut node_value = 0.0;
                     for a in 0..num_actions {
                         let v = br_traverse_flop(
@@ -2142,7 +3126,7 @@ fn br_traverse_flop(
                             flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
                             river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                         );
-                        node_value += strat_buf[a] as f64 * v;
+                        node_value += strategy[a] * v;
                     }
                     node_value
                 }
@@ -2186,16 +3170,21 @@ fn br_traverse_flop(
     }
 }
 
+/// Average-strategy value of `node` for `hero`, as `br_traverse_flop` with
+/// `is_br: false`, also adding each of hero's flop actions' values to `values`.
 #[allow(clippy::too_many_arguments)]
-fn br_traverse_turn_template(
+fn ev_traverse_flop(
     node: &TreeNode,
-    br_player: Player,
+    hero: Player,
     hand_idx: usize,
+    flop_bucket: usize,
     turn_bucket: usize,
     river_bucket: usize,
     opp_reach: &[f64],
     oop_combos: &[Combo],
     ip_combos: &[Combo],
+    flop_oop_buckets: &[u16],
+    flop_ip_buckets: &[u16],
     turn_oop_buckets: &[u16],
     turn_ip_buckets: &[u16],
     river_oop_buckets: &[u16],
@@ -2204,46 +3193,138 @@ fn br_traverse_turn_template(
     ip_scores: &[u32],
     valid_ip_for_oop: &[Vec<u16>],
     valid_oop_for_ip: &[Vec<u16>],
-    scale: f64,
+    flop_pot: f64,
+    turn_template: &TreeNode,
     river_template: &TreeNode,
-    turn_oop_cfr: &FlatCfr,
+    flop_oop_cfr: &FlatCfr,
+    flop_ip_cfr: &FlatCfr,
+    turn_oop_cfr: &FlatCfr,
+    turn_ip_cfr: &FlatCfr,
+    river_oop_cfr: &FlatCfr,
+    river_ip_cfr: &FlatCfr,
+    strat_buf: &mut [f32],
+    values: &mut ActionValues,
+) -> f64 {
+    let TreeNode::Action { node_id, player, children, actions, .. } = node else {
+        return br_traverse_flop(
+            node, hero, hand_idx, flop_bucket, turn_bucket, river_bucket,
+            opp_reach, oop_combos, ip_combos,
+            flop_oop_buckets, flop_ip_buckets,
+            turn_oop_buckets, turn_ip_buckets,
+            river_oop_buckets, river_ip_buckets,
+            oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
+            flop_pot, turn_template, river_template,
+            flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
+            river_oop_cfr, river_ip_cfr, strat_buf, false,
+        );
+    };
+    let num_actions = actions.len();
+    let nid = *node_id as usize;
+    let (hero_cfr, opp_cfr, opp_buckets, num_hero) = match hero {
+        Player::OOP => (flop_oop_cfr, flop_ip_cfr, flop_ip_buckets, oop_combos.len()),
+        Player::IP => (flop_ip_cfr, flop_oop_cfr, flop_oop_buckets, ip_combos.len()),
+    };
+
+    let mut node_value = 0.0;
+    if *player == hero {
+        hero_cfr.average_strategy(nid, flop_bucket, strat_buf);
+        let strategy: Vec<f64> = strat_buf[..num_actions].iter().map(|&p| p as f64).collect();
+        let mut action_values = vec![0.0; num_actions];
+        for (a, child) in children.iter().enumerate() {
+            action_values[a] = ev_traverse_flop(
+                child, hero, hand_idx, flop_bucket, turn_bucket, river_bucket,
+                opp_reach, oop_combos, ip_combos,
+                flop_oop_buckets, flop_ip_buckets,
+                turn_oop_buckets, turn_ip_buckets,
+                river_oop_buckets, river_ip_buckets,
+                oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
+                flop_pot, turn_template, river_template,
+                flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
+                river_oop_cfr, river_ip_cfr, strat_buf, values,
+            );
+            node_value += strategy[a] * action_values[a];
+        }
+        values.add(nid, hand_idx, num_hero, &action_values, opp_reach.iter().sum());
+    } else {
+        for (a, child) in children.iter().enumerate() {
+            let mut new_opp_reach = vec![0.0f64; opp_reach.len()];
+            for (j, r) in new_opp_reach.iter_mut().enumerate() {
+                if opp_reach[j] > 0.0 {
+                    opp_cfr.average_strategy(nid, opp_buckets[j] as usize, strat_buf);
+                    *r = opp_reach[j] * strat_buf[a] as f64;
+                }
+            }
+            node_value += ev_traverse_flop(
+                child, hero, hand_idx, flop_bucket, turn_bucket, river_bucket,
+                &new_opp_reach, oop_combos, ip_combos,
+                flop_oop_buckets, flop_ip_buckets,
+                turn_oop_buckets, turn_ip_buckets,
+                river_oop_buckets, river_ip_buckets,
+                oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
+                flop_pot, turn_template, river_template,
+                flop_oop_cfr, flop_ip_cfr, turn_oop_cfr, turn_ip_cfr,
+                river_oop_cfr, river_ip_cfr, strat_buf, values,
+            );
+        }
+    }
+    node_value
+}
+
+#[allow(clippy::too_many_arguments)]
+fn br_traverse_turn_template(
+    node: &TreeNode,
+    br_player: Player,
+    hand_idx: usize,
+    turn_bucket: usize,
+    river_bucket: usize,
+    opp_reach: &[f64],
+    oop_combos: &[Combo],
+    ip_combos: &[Combo],
+    turn_oop_buckets: &[u16],
+    turn_ip_buckets: &[u16],
+    river_oop_buckets: &[u16],
+    river_ip_buckets: &[u16],
+    oop_scores: &[u32],
+    ip_scores: &[u32],
+    valid_ip_for_oop: &[Vec<u16>],
+    valid_oop_for_ip: &[Vec<u16>],
+    scale: f64,
+    stack: f64,
+    river_template: &TreeNode,
+    turn_oop_cfr: &FlatCfr,
     turn_ip_cfr: &FlatCfr,
     river_oop_cfr: &FlatCfr,
     river_ip_cfr: &FlatCfr,
     strat_buf: &mut [f32],
     is_br: bool,
 ) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let opp_reach_sum: f64 = opp_reach.iter().sum();
+        if opp_reach_sum < 1e-10 {
+            return 0.0;
+        }
+        // Chain to the river template with whatever stack is left behind
+        let river_scale = scale + put_in[0] + put_in[1];
+        let river_stack = stack - put_in[0].max(put_in[1]);
+        let river_value = br_traverse_river_template(
+            river_template, br_player, hand_idx, river_bucket,
+            opp_reach, oop_combos, ip_combos,
+            river_oop_buckets, river_ip_buckets,
+            oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
+            river_scale, river_stack, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
+        );
+        return river_value - put_in[br_player.index()] * opp_reach_sum;
+    }
     match node {
-        TreeNode::Terminal {
-            terminal_type,
-            pot,
-            invested,
-            ..
-        } => {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
             let opp_reach_sum: f64 = opp_reach.iter().sum();
             if opp_reach_sum < 1e-10 {
                 return 0.0;
             }
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    let my_invested = invested[br_player.index()] * scale;
-                    if *folder == br_player {
-                        -my_invested * opp_reach_sum
-                    } else {
-                        (*pot * scale - my_invested) * opp_reach_sum
-                    }
-                }
-                TerminalType::Showdown => {
-                    let river_scale = *pot * scale;
-                    br_traverse_river_template(
-                        river_template, br_player, hand_idx, river_bucket,
-                        opp_reach, oop_combos, ip_combos,
-                        river_oop_buckets, river_ip_buckets,
-                        oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        river_scale, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
-                    )
-                }
-            }
+            template_fold_value(br_player, *folder, invested, scale, stack, opp_reach_sum)
         }
         TreeNode::Action {
             node_id,
@@ -2265,7 +3346,7 @@ fn br_traverse_turn_template(
                             turn_oop_buckets, turn_ip_buckets,
                             river_oop_buckets, river_ip_buckets,
                             oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                            scale, river_template, turn_oop_cfr, turn_ip_cfr,
+                            scale, stack, river_template, turn_oop_cfr, turn_ip_cfr,
                             river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                         );
                         if v > best { best = v; }
@@ -2277,6 +3358,8 @@ fn br_traverse_turn_template(
                         Player::IP => turn_ip_cfr,
                     };
                     cfr.average_strategy(nid, turn_bucket, strat_buf);
+                    // Children overwrite strat_buf
+                    let strategy: Vec<f64> = strat_buf[..num_actions].iter().map(|&p| p as f64).collect();
                     let mut nv = 0.0;
                     for a in 0..num_actions {
                         let v = br_traverse_turn_template(
@@ -2285,10 +3368,10 @@ fn br_traverse_turn_template(
                             turn_oop_buckets, turn_ip_buckets,
                             river_oop_buckets, river_ip_buckets,
                             oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                            scale, river_template, turn_oop_cfr, turn_ip_cfr,
+                            scale, stack, river_template, turn_oop_cfr, turn_ip_cfr,
                             river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                         );
-                        nv += strat_buf[a] as f64 * v;
+                        nv += strategy[a] * v;
                     }
                     nv
                 }
@@ -2318,7 +3401,7 @@ fn br_traverse_turn_template(
                         turn_oop_buckets, turn_ip_buckets,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        scale, river_template, turn_oop_cfr, turn_ip_cfr,
+                        scale, stack, river_template, turn_oop_cfr, turn_ip_cfr,
                         river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                     );
                 }
@@ -2345,61 +3428,32 @@ fn br_traverse_river_template(
     valid_ip_for_oop: &[Vec<u16>],
     valid_oop_for_ip: &[Vec<u16>],
     scale: f64,
+    stack: f64,
     river_oop_cfr: &FlatCfr,
     river_ip_cfr: &FlatCfr,
     strat_buf: &mut [f32],
     is_br: bool,
 ) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let opp_reach_sum: f64 = opp_reach.iter().sum();
+        if opp_reach_sum < 1e-10 {
+            return 0.0;
+        }
+        return river_showdown_value(
+            br_player, hand_idx, opp_reach, oop_scores, ip_scores,
+            valid_ip_for_oop, valid_oop_for_ip, scale, put_in,
+        );
+    }
     match node {
-        TreeNode::Terminal {
-            terminal_type,
-            pot,
-            invested,
-            ..
-        } => {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
             let opp_reach_sum: f64 = opp_reach.iter().sum();
             if opp_reach_sum < 1e-10 {
                 return 0.0;
             }
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    let my_invested = invested[br_player.index()] * scale;
-                    if *folder == br_player {
-                        -my_invested * opp_reach_sum
-                    } else {
-                        (*pot * scale - my_invested) * opp_reach_sum
-                    }
-                }
-                TerminalType::Showdown => {
-                    let pot_s = *pot * scale;
-                    let my_inv = invested[br_player.index()] * scale;
-                    let win = pot_s - my_inv;
-                    let lose = -my_inv;
-                    let tie = pot_s / 2.0 - my_inv;
-                    let mut value = 0.0;
-                    match br_player {
-                        Player::OOP => {
-                            let ms = oop_scores[hand_idx];
-                            for &j in &valid_ip_for_oop[hand_idx] {
-                                let j = j as usize;
-                                if opp_reach[j] < 1e-10 { continue; }
-                                let os = ip_scores[j];
-                                value += opp_reach[j] * if ms > os { win } else if ms < os { lose } else { tie };
-                            }
-                        }
-                        Player::IP => {
-                            let ms = ip_scores[hand_idx];
-                            for &i in &valid_oop_for_ip[hand_idx] {
-                                let i = i as usize;
-                                if opp_reach[i] < 1e-10 { continue; }
-                                let os = oop_scores[i];
-                                value += opp_reach[i] * if ms > os { win } else if ms < os { lose } else { tie };
-                            }
-                        }
-                    }
-                    value
-                }
-            }
+            template_fold_value(br_player, *folder, invested, scale, stack, opp_reach_sum)
         }
         TreeNode::Action {
             node_id,
@@ -2420,7 +3474,7 @@ fn br_traverse_river_template(
                             opp_reach, oop_combos, ip_combos,
                             river_oop_buckets, river_ip_buckets,
                             oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                            scale, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
+                            scale, stack, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                         );
                         if v > best { best = v; }
                     }
@@ -2431,6 +3485,8 @@ fn br_traverse_river_template(
                         Player::IP => river_ip_cfr,
                     };
                     cfr.average_strategy(nid, river_bucket, strat_buf);
+                    // Children overwrite strat_buf
+                    let strategy: Vec<f64> = strat_buf[..num_actions].iter().map(|&p| p as f64).collect();
                     let mut nv = 0.0;
                     for a in 0..num_actions {
                         let v = br_traverse_river_template(
@@ -2438,9 +3494,9 @@ fn br_traverse_river_template(
                             opp_reach, oop_combos, ip_combos,
                             river_oop_buckets, river_ip_buckets,
                             oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                            scale, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
+                            scale, stack, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                         );
-                        nv += strat_buf[a] as f64 * v;
+                        nv += strategy[a] * v;
                     }
                     nv
                 }
@@ -2469,7 +3525,7 @@ fn br_traverse_river_template(
                         &new_opp_reach, oop_combos, ip_combos,
                         river_oop_buckets, river_ip_buckets,
                         oop_scores, ip_scores, valid_ip_for_oop, valid_oop_for_ip,
-                        scale, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
+                        scale, stack, river_oop_cfr, river_ip_cfr, strat_buf, is_br,
                     );
                 }
                 nv
@@ -2479,6 +3535,331 @@ fn br_traverse_river_template(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Best response that can't see the runout
+// ---------------------------------------------------------------------------
+
+/// Turn cards `best_response_totals` scores on, and rivers dealt under each:
+/// as many runouts as `EXPLOITABILITY_SAMPLES`, grouped by turn so a turn
+/// decision is made once for all of its rivers.
+const BR_TURNS: usize = 10;
+const BR_RIVERS_PER_TURN: usize = 10;
+
+/// A sampled turn card (its position in the remaining deck), both ranges'
+/// buckets on it and the rivers (positions too) dealt under it.
+struct TurnSample {
+    turn_idx: usize,
+    buckets: (Vec<u16>, Vec<u16>),
+    rivers: Vec<usize>,
+}
+
+/// Draw `BR_TURNS` turn cards out of `remaining` and `BR_RIVERS_PER_TURN`
+/// rivers under each; `buckets` gives both ranges' buckets on a turn card.
+fn sample_turns(
+    remaining: usize,
+    seed: Option<u64>,
+    buckets: impl Fn(usize) -> (Vec<u16>, Vec<u16>),
+) -> Vec<TurnSample> {
+    let mut rng = stream_rng(seed, BEST_RESPONSE_STREAM);
+    let turns = rand::seq::index::sample(&mut rng, remaining, BR_TURNS.min(remaining)).into_vec();
+    turns
+        .into_iter()
+        .map(|turn_idx| {
+            let others: Vec<usize> = (0..remaining).filter(|&r| r != turn_idx).collect();
+            let rivers = others.choose_multiple(&mut rng, BR_RIVERS_PER_TURN).copied().collect();
+            TurnSample { turn_idx, buckets: buckets(turn_idx), rivers }
+        })
+        .collect()
+}
+
+/// A flop profile's strategy tables and everything a best response to it
+/// reads. Pairs are `[OOP, IP]`, indexed like `Player::index`.
+struct FlopProfile<'a> {
+    flop_tree: &'a TreeNode,
+    turn_template: &'a TreeNode,
+    river_template: &'a TreeNode,
+    flop_cfr: [&'a FlatCfr; 2],
+    turn_cfr: [&'a FlatCfr; 2],
+    river_cfr: [&'a FlatCfr; 2],
+    flop_buckets: [&'a [u16]; 2],
+    oop_combos: &'a [Combo],
+    ip_combos: &'a [Combo],
+    weights: [&'a [f64]; 2],
+    blockers: [&'a [[bool; 52]]; 2],
+    valid_ip_for_oop: &'a [Vec<u16>],
+    valid_oop_for_ip: &'a [Vec<u16>],
+    runout_table: &'a RunoutTable,
+    turns: &'a [TurnSample],
+    starting_pot: f64,
+}
+
+/// One player's sums from `best_response_totals`, each combo counted at its
+/// range weight on every sampled runout it can be dealt on.
+#[derive(Debug, Clone, Copy, Default)]
+struct SideTotals {
+    /// Best-response value less average-strategy value.
+    gain: f64,
+    /// Average-strategy value.
+    value: f64,
+    weight: f64,
+    /// Opponent reach the values were summed against.
+    opp_mass: f64,
+}
+
+impl SideTotals {
+    fn add(&mut self, other: &SideTotals) {
+        self.gain += other.gain;
+        self.value += other.value;
+        self.weight += other.weight;
+        self.opp_mass += other.opp_mass;
+    }
+}
+
+/// Each player's best response to the other's average strategy, on the
+/// profile's sampled runouts. Unlike `estimate_exploitability`'s, it can't
+/// see cards before they're dealt: it picks each flop action once for every
+/// runout and each turn action once for every river of its turn.
+fn best_response_totals(profile: &FlopProfile) -> [SideTotals; 2] {
+    [Player::OOP, Player::IP].map(|player| {
+        let per_combo: Vec<SideTotals> = (0..profile.weights[player.index()].len())
+            .into_par_iter()
+            .map(|h| combo_best_response(profile, player, h))
+            .collect();
+        // Summed in combo order so the result doesn't depend on thread timing
+        let mut totals = SideTotals::default();
+        for combo in &per_combo {
+            totals.add(combo);
+        }
+        totals
+    })
+}
+
+/// Each player's per-matchup EV as a share of the two's combined value, so
+/// it lines up with the equity split (sums to 1.0), and OOP's share when IP
+/// best-responds, from `best_response_totals`.
+fn ev_shares(totals: &[SideTotals; 2]) -> ([f64; 2], f64) {
+    let per_matchup = totals.map(|t| if t.opp_mass > 0.0 { t.value / t.opp_mass } else { 0.0 });
+    let combined = per_matchup[0] + per_matchup[1];
+    if combined <= 1e-9 {
+        return ([0.0; 2], 0.0);
+    }
+    // What IP's best response gains comes out of OOP's share
+    let ip_gain = if totals[1].opp_mass > 0.0 { totals[1].gain / totals[1].opp_mass } else { 0.0 };
+    ([per_matchup[0] / combined, per_matchup[1] / combined], (per_matchup[0] - ip_gain) / combined)
+}
+
+/// `best_response_totals` for `player`'s combo `hand_idx`.
+fn combo_best_response(profile: &FlopProfile, player: Player, hand_idx: usize) -> SideTotals {
+    let p = player.index();
+    let (valid, opp_weights) = match player {
+        Player::OOP => (&profile.valid_ip_for_oop[hand_idx], profile.weights[1]),
+        Player::IP => (&profile.valid_oop_for_ip[hand_idx], profile.weights[0]),
+    };
+    let mut opp_reach = vec![0.0f64; opp_weights.len()];
+    for &j in valid {
+        opp_reach[j as usize] = opp_weights[j as usize];
+    }
+    let mut strat_buf = vec![0.0f32; 16];
+    let best = br_flop(profile.flop_tree, profile, player, hand_idx, &opp_reach, &mut strat_buf);
+
+    // The average strategy on the same runouts
+    let (mut average, mut runouts, mut opp_mass) = (0.0, 0usize, 0.0);
+    let remaining = profile.runout_table.remaining();
+    for turn in profile.turns {
+        if profile.blockers[p][hand_idx][remaining[turn.turn_idx] as usize] {
+            continue;
+        }
+        let turn_reach = dealt_reach(profile, player, &opp_reach, remaining[turn.turn_idx]);
+        let (turn_oop_buckets, turn_ip_buckets) = &turn.buckets;
+        let turn_bucket = [turn_oop_buckets, turn_ip_buckets][p][hand_idx] as usize;
+        for (river_idx, reach) in dealt_rivers(profile, player, hand_idx, turn, &turn_reach) {
+            let river = profile.runout_table.get(profile.runout_table.runout_idx(turn.turn_idx, river_idx));
+            let river_bucket = [&river.oop_buckets, &river.ip_buckets][p][hand_idx] as usize;
+            average += br_traverse_flop(
+                profile.flop_tree, player, hand_idx,
+                profile.flop_buckets[p][hand_idx] as usize, turn_bucket, river_bucket,
+                &reach, profile.oop_combos, profile.ip_combos,
+                profile.flop_buckets[0], profile.flop_buckets[1],
+                turn_oop_buckets, turn_ip_buckets,
+                &river.oop_buckets, &river.ip_buckets,
+                &river.oop_scores, &river.ip_scores, profile.valid_ip_for_oop, profile.valid_oop_for_ip,
+                profile.starting_pot, profile.turn_template, profile.river_template,
+                profile.flop_cfr[0], profile.flop_cfr[1], profile.turn_cfr[0], profile.turn_cfr[1],
+                profile.river_cfr[0], profile.river_cfr[1], &mut strat_buf, false,
+            );
+            runouts += 1;
+            opp_mass += reach.iter().sum::<f64>();
+        }
+    }
+
+    let weight = profile.weights[p][hand_idx];
+    SideTotals {
+        gain: weight * (best - average),
+        value: weight * average,
+        weight: weight * runouts as f64,
+        opp_mass: weight * opp_mass,
+    }
+}
+
+/// `opp_reach` less the opponent combos holding `card`.
+fn dealt_reach(profile: &FlopProfile, player: Player, opp_reach: &[f64], card: u8) -> Vec<f64> {
+    let opp_blockers = profile.blockers[1 - player.index()];
+    opp_reach
+        .iter()
+        .zip(opp_blockers)
+        .map(|(&r, b)| if b[card as usize] { 0.0 } else { r })
+        .collect()
+}
+
+/// The rivers of `turn` that `player`'s combo `hand_idx` doesn't hold, each
+/// with `turn_reach` less the opponent combos holding it.
+fn dealt_rivers<'a>(
+    profile: &'a FlopProfile,
+    player: Player,
+    hand_idx: usize,
+    turn: &'a TurnSample,
+    turn_reach: &'a [f64],
+) -> impl Iterator<Item = (usize, Vec<f64>)> + 'a {
+    let remaining = profile.runout_table.remaining();
+    turn.rivers
+        .iter()
+        .filter(move |&&r| !profile.blockers[player.index()][hand_idx][remaining[r] as usize])
+        .map(move |&r| (r, dealt_reach(profile, player, turn_reach, remaining[r])))
+}
+
+/// Best-response value of a flop node for `player`'s combo `hand_idx`,
+/// summed over the sampled runouts it can be dealt on.
+fn br_flop(
+    node: &TreeNode,
+    profile: &FlopProfile,
+    player: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    strat_buf: &mut [f32],
+) -> f64 {
+    match node {
+        TreeNode::Terminal { terminal_type, pot, stacks, invested, .. } => {
+            let my_invested = invested[player.index()];
+            let remaining = profile.runout_table.remaining();
+            let mut value = 0.0;
+            for turn in profile.turns {
+                let turn_card = remaining[turn.turn_idx];
+                if profile.blockers[player.index()][hand_idx][turn_card as usize] {
+                    continue;
+                }
+                let turn_reach = dealt_reach(profile, player, opp_reach, turn_card);
+                let reached: f64 = dealt_rivers(profile, player, hand_idx, turn, &turn_reach)
+                    .map(|(_, reach)| reach.iter().sum::<f64>())
+                    .sum();
+                value += match terminal_type {
+                    TerminalType::Fold { folder } if *folder == player => -my_invested * reached,
+                    TerminalType::Fold { .. } => (*pot - my_invested) * reached,
+                    TerminalType::Showdown => {
+                        let turn_stack = stacks[0].min(stacks[1]);
+                        br_turn(profile.turn_template, profile, player, hand_idx, turn, &turn_reach, *pot, turn_stack, strat_buf)
+                            - my_invested * reached
+                    }
+                };
+            }
+            value
+        }
+        TreeNode::Action { node_id, player: to_act, children, .. } => {
+            if *to_act == player {
+                return children
+                    .iter()
+                    .map(|child| br_flop(child, profile, player, hand_idx, opp_reach, strat_buf))
+                    .fold(f64::NEG_INFINITY, f64::max);
+            }
+            let opp = to_act.index();
+            let mut value = 0.0;
+            for (a, child) in children.iter().enumerate() {
+                let mut child_reach = vec![0.0f64; opp_reach.len()];
+                for (j, r) in child_reach.iter_mut().enumerate() {
+                    if opp_reach[j] > 0.0 {
+                        let bucket = profile.flop_buckets[opp][j] as usize;
+                        profile.flop_cfr[opp].average_strategy(*node_id as usize, bucket, strat_buf);
+                        *r = opp_reach[j] * strat_buf[a] as f64;
+                    }
+                }
+                value += br_flop(child, profile, player, hand_idx, &child_reach, strat_buf);
+            }
+            value
+        }
+        TreeNode::Chance { .. } => unreachable!("Flop tree should not contain chance nodes"),
+    }
+}
+
+/// Best-response value of a turn template node for `player`'s combo
+/// `hand_idx` on `turn`, summed over its rivers, as `br_traverse_turn_template`
+/// values a single runout.
+#[allow(clippy::too_many_arguments)]
+fn br_turn(
+    node: &TreeNode,
+    profile: &FlopProfile,
+    player: Player,
+    hand_idx: usize,
+    turn: &TurnSample,
+    opp_reach: &[f64],
+    scale: f64,
+    stack: f64,
+    strat_buf: &mut [f32],
+) -> f64 {
+    if let Some(put_in) = template_showdown(node, scale, stack) {
+        let river_scale = scale + put_in[0] + put_in[1];
+        let river_stack = stack - put_in[0].max(put_in[1]);
+        let mut value = 0.0;
+        for (river_idx, reach) in dealt_rivers(profile, player, hand_idx, turn, opp_reach) {
+            let reached: f64 = reach.iter().sum();
+            if reached < 1e-10 {
+                continue;
+            }
+            let river = profile.runout_table.get(profile.runout_table.runout_idx(turn.turn_idx, river_idx));
+            let river_bucket = [&river.oop_buckets, &river.ip_buckets][player.index()][hand_idx] as usize;
+            value += br_traverse_river_template(
+                profile.river_template, player, hand_idx, river_bucket,
+                &reach, profile.oop_combos, profile.ip_combos,
+                &river.oop_buckets, &river.ip_buckets,
+                &river.oop_scores, &river.ip_scores, profile.valid_ip_for_oop, profile.valid_oop_for_ip,
+                river_scale, river_stack, profile.river_cfr[0], profile.river_cfr[1], strat_buf, true,
+            ) - put_in[player.index()] * reached;
+        }
+        return value;
+    }
+    match node {
+        TreeNode::Terminal { terminal_type, invested, .. } => {
+            let TerminalType::Fold { folder } = terminal_type else {
+                unreachable!("template showdowns are handled above")
+            };
+            dealt_rivers(profile, player, hand_idx, turn, opp_reach)
+                .map(|(_, reach)| template_fold_value(player, *folder, invested, scale, stack, reach.iter().sum()))
+                .sum()
+        }
+        TreeNode::Action { node_id, player: to_act, children, .. } => {
+            if *to_act == player {
+                return children
+                    .iter()
+                    .map(|child| br_turn(child, profile, player, hand_idx, turn, opp_reach, scale, stack, strat_buf))
+                    .fold(f64::NEG_INFINITY, f64::max);
+            }
+            let opp = to_act.index();
+            let opp_buckets = if opp == 0 { &turn.buckets.0 } else { &turn.buckets.1 };
+            let mut value = 0.0;
+            for (a, child) in children.iter().enumerate() {
+                let mut child_reach = vec![0.0f64; opp_reach.len()];
+                for (j, r) in child_reach.iter_mut().enumerate() {
+                    if opp_reach[j] > 0.0 {
+                        profile.turn_cfr[opp].average_strategy(*node_id as usize, opp_buckets[j] as usize, strat_buf);
+                        *r = opp_reach[j] * strat_buf[a] as f64;
+                    }
+                }
+                value += br_turn(child, profile, player, hand_idx, turn, &child_reach, scale, stack, strat_buf);
+            }
+            value
+        }
+        TreeNode::Chance { .. } => unreachable!(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Solution extraction
 // ---------------------------------------------------------------------------
@@ -2486,11 +3867,14 @@ fn br_traverse_river_template(
 #[allow(clippy::too_many_arguments)]
 fn extract_solution(
     config: &FlopSolverConfig,
+    iterations: usize,
     flop_tree: &TreeNode,
     flop_oop_cfr: &FlatCfr,
     flop_ip_cfr: &FlatCfr,
     oop_combos: &[Combo],
     ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
     flop_oop_buckets: &[u16],
     flop_ip_buckets: &[u16],
     _metas: &[crate::postflop_tree::NodeMeta],
@@ -2504,30 +3888,62 @@ fn extract_solution(
     ip_blockers: &[[bool; 52]],
     valid_ip_for_oop: &[Vec<u16>],
     valid_oop_for_ip: &[Vec<u16>],
+    runout_table: &RunoutTable,
+    turn_bucket_table: &[(Vec<u16>, Vec<u16>)],
 ) -> FlopSolution {
-    // Compute exploitability
-    let exploitability = estimate_exploitability(
+    // Compute exploitability (and action values as a by-product)
+    let (exploitability, exploitability_std_err, action_values) =
+        estimate_exploitability(
+            flop_tree,
+            turn_template,
+            river_template,
+            flop_oop_cfr,
+            flop_ip_cfr,
+            turn_oop_cfr,
+            turn_ip_cfr,
+            river_oop_cfr,
+            river_ip_cfr,
+            oop_combos,
+            ip_combos,
+            oop_weights,
+            ip_weights,
+            oop_blockers,
+            ip_blockers,
+            flop_oop_buckets,
+            flop_ip_buckets,
+            valid_ip_for_oop,
+            valid_oop_for_ip,
+            runout_table,
+            &config.board,
+            config.starting_pot,
+            config.num_buckets,
+            config.bucketing,
+            EXPLOITABILITY_SAMPLES,
+            config.seed,
+            None,
+        );
+
+    // EV shares, and OOP's against a best response that can't see the runout
+    let turns = sample_turns(runout_table.remaining().len(), config.seed, |t| turn_bucket_table[t].clone());
+    let profile = FlopProfile {
         flop_tree,
         turn_template,
         river_template,
-        flop_oop_cfr,
-        flop_ip_cfr,
-        turn_oop_cfr,
-        turn_ip_cfr,
-        river_oop_cfr,
-        river_ip_cfr,
+        flop_cfr: [flop_oop_cfr, flop_ip_cfr],
+        turn_cfr: [turn_oop_cfr, turn_ip_cfr],
+        river_cfr: [river_oop_cfr, river_ip_cfr],
+        flop_buckets: [flop_oop_buckets, flop_ip_buckets],
         oop_combos,
         ip_combos,
-        oop_blockers,
-        ip_blockers,
-        flop_oop_buckets,
-        flop_ip_buckets,
+        weights: [oop_weights, ip_weights],
+        blockers: [oop_blockers, ip_blockers],
         valid_ip_for_oop,
         valid_oop_for_ip,
-        &config.board,
-        config.starting_pot,
-        config.num_buckets,
-    );
+        runout_table,
+        turns: &turns,
+        starting_pot: config.starting_pot,
+    };
+    let (ev_shares, oop_ev_vs_best_response) = ev_shares(&best_response_totals(&profile));
 
     // Extract flop-level strategies (combo-level from bucket-level)
     let mut strategies = Vec::new();
@@ -2541,6 +3957,14 @@ fn extract_solution(
         flop_ip_buckets,
         &mut strategies,
     );
+    for strategy in &mut strategies {
+        strategy.evs = action_values.evs(
+            strategy.node_id as usize,
+            strategy.frequencies.len(),
+            strategy.actions.len(),
+            config.starting_pot,
+        );
+    }
 
     // Extract turn/river template strategies at bucket level (zero extra compute)
     let mut turn_strategies = Vec::new();
@@ -2574,14 +3998,8 @@ fn extract_solution(
         .map(|&b| format!("{}", index_to_card(b)))
         .collect::<String>();
 
-    let oop_combo_strs: Vec<String> = oop_combos
-        .iter()
-        .map(|c| format!("{}{}", index_to_card(c.0), index_to_card(c.1)))
-        .collect();
-    let ip_combo_strs: Vec<String> = ip_combos
-        .iter()
-        .map(|c| format!("{}{}", index_to_card(c.0), index_to_card(c.1)))
-        .collect();
+    let oop_combo_strs: Vec<String> = oop_combos.iter().map(|c| c.to_string()).collect();
+    let ip_combo_strs: Vec<String> = ip_combos.iter().map(|c| c.to_string()).collect();
 
     FlopSolution {
         board: board_str,
@@ -2589,19 +4007,45 @@ fn extract_solution(
         ip_range: config.ip_range.clone(),
         starting_pot: config.starting_pot,
         effective_stack: config.effective_stack,
-        iterations: config.iterations,
+        iterations,
         exploitability,
+        oop_equity: 0.0,
+        oop_ev_share: ev_shares[0],
+        ip_ev_share: ev_shares[1],
         oop_combos: oop_combo_strs,
         ip_combos: ip_combo_strs,
         strategies,
         oop_pos: String::new(),
         ip_pos: String::new(),
+        combo_order_version: COMBO_ORDER_VERSION,
         turn_strategies,
         river_strategies,
         num_buckets: config.num_buckets,
         flop_tree_edges,
         turn_tree_edges,
         river_tree_edges,
+        postprocess: None,
+        simplified: None,
+        quality: Some(SolveQuality::grade(
+            exploitability,
+            config.starting_pot,
+            iterations,
+            RECOMMENDED_FLOP_ITERATIONS,
+            Some(config.num_buckets),
+            oop_combos.len().max(ip_combos.len()),
+        )),
+        convergence_history: vec![],
+        oop_weights: config.oop_weights.clone(),
+        ip_weights: config.ip_weights.clone(),
+        exploitability_pct_pot: pct_of_pot(exploitability, config.starting_pot),
+        exploitability_std_err,
+        low_fidelity: config.quick,
+        river_bet_sizes: config.river_bet_sizes.clone(),
+        bucketing: config.bucketing,
+        combos_refined: config.refine_flop_combos,
+        flop_bet_sizes: config.flop_bet_sizes.clone(),
+        oop_ev_vs_best_response,
+        time_limited: config.time_budget.is_some(),
     }
 }
 
@@ -2647,6 +4091,7 @@ fn extract_flop_strategies(
                 },
                 actions: actions.iter().map(|a| a.label()).collect(),
                 frequencies,
+                evs: vec![],
             });
 
             for child in children {
@@ -2720,6 +4165,39 @@ fn extract_template_strategies(
     }
 }
 
+/// CFR tables over `tree` ([OOP, IP]) holding stored average strategies,
+/// `(node_id, player, frequencies)` with a row per hand, `hands` rows a
+/// node; `None` if one doesn't fit the tree.
+fn stored_tables<'a>(
+    tree: &TreeNode,
+    hands: [usize; 2],
+    strategies: impl Iterator<Item = (u16, &'a str, &'a [Vec<f64>])>,
+) -> Option<[FlatCfr; 2]> {
+    let metas = collect_node_metadata(tree);
+    let mut tables = [Player::OOP, Player::IP].map(|side| {
+        let nodes: Vec<(u8, u16)> = metas
+            .iter()
+            .map(|m| (m.num_actions, if m.player == side { hands[side.index()] as u16 } else { 0 }))
+            .collect();
+        FlatCfr::new(&nodes)
+    });
+    for (node_id, player, frequencies) in strategies {
+        let cfr = &mut tables[if player == "OOP" { 0 } else { 1 }];
+        let nid = node_id as usize;
+        if nid >= cfr.num_nodes()
+            || cfr.node_num_hands(nid) as usize != frequencies.len()
+            || frequencies.iter().any(|f| f.len() != cfr.node_num_actions(nid) as usize)
+        {
+            return None;
+        }
+        for (h, freqs) in frequencies.iter().enumerate() {
+            let freqs: Vec<f32> = freqs.iter().map(|&f| f as f32).collect();
+            cfr.set_average_strategy(nid, h, &freqs);
+        }
+    }
+    Some(tables)
+}
+
 /// Extract edges between action nodes in a tree for navigation.
 /// Records (from_node_id, action_label, to_node_id) for each action→action edge.
 fn extract_tree_edges(node: &TreeNode, edges: &mut Vec<TreeEdge>) {
@@ -2746,32 +4224,49 @@ fn extract_tree_edges(node: &TreeNode, edges: &mut Vec<TreeEdge>) {
     }
 }
 
-fn empty_solution(config: &FlopSolverConfig) -> FlopSolution {
-    let board_str = config
-        .board
-        .iter()
-        .map(|&b| format!("{}", index_to_card(b)))
-        .collect::<String>();
+fn board_string(board: &[u8]) -> String {
+    board.iter().map(|&b| format!("{}", index_to_card(b))).collect()
+}
 
+fn empty_solution(config: &FlopSolverConfig) -> FlopSolution {
     FlopSolution {
-        board: board_str,
+        board: board_string(&config.board),
         oop_range: config.oop_range.clone(),
         ip_range: config.ip_range.clone(),
         starting_pot: config.starting_pot,
         effective_stack: config.effective_stack,
         iterations: config.iterations,
         exploitability: 0.0,
+        oop_equity: 0.0,
+        oop_ev_share: 0.0,
+        ip_ev_share: 0.0,
         oop_combos: vec![],
         ip_combos: vec![],
         strategies: vec![],
         oop_pos: String::new(),
         ip_pos: String::new(),
+        combo_order_version: COMBO_ORDER_VERSION,
         turn_strategies: vec![],
         river_strategies: vec![],
         num_buckets: 0,
         flop_tree_edges: vec![],
         turn_tree_edges: vec![],
         river_tree_edges: vec![],
+        postprocess: None,
+        simplified: None,
+        quality: None,
+        convergence_history: vec![],
+        oop_weights: config.oop_weights.clone(),
+        ip_weights: config.ip_weights.clone(),
+        exploitability_pct_pot: 0.0,
+        exploitability_std_err: 0.0,
+        low_fidelity: config.quick,
+        river_bet_sizes: config.river_bet_sizes.clone(),
+        bucketing: config.bucketing,
+        combos_refined: false,
+        flop_bet_sizes: config.flop_bet_sizes.clone(),
+        oop_ev_vs_best_response: 0.0,
+        time_limited: false,
     }
 }
 
@@ -2781,6 +4276,12 @@ fn empty_solution(config: &FlopSolverConfig) -> FlopSolution {
 
 impl FlopSolution {
     pub fn display(&self) {
+        self.display_with(false);
+    }
+
+    /// The summary; `expanded` lists the root combos one by one instead of
+    /// grouping those that play alike (see `display::group_combos`).
+    pub fn display_with(&self, expanded: bool) {
         use colored::Colorize;
 
         println!();
@@ -2792,15 +4293,44 @@ impl FlopSolution {
             self.effective_stack,
             self.iterations,
         );
-        println!("  Exploitability: {:.4}", self.exploitability);
+        println!(
+            "  Exploitability: {:.4}  ({:.1}% ± {:.1}% of pot)",
+            self.exploitability,
+            self.exploitability_pct_pot,
+            pct_of_pot(self.exploitability_std_err, self.starting_pot),
+        );
+        if let Some(pp) = &self.postprocess {
+            println!("  {}", pp.describe(self.exploitability).dimmed());
+        }
+        if let Some(simplified) = &self.simplified {
+            println!("  {}", format!("Strategies {}", simplified.describe()).yellow());
+        }
+        if self.combos_refined {
+            println!("  {}", "Flop strategies refined per combo".dimmed());
+        }
+        if self.oop_equity > 0.0 {
+            println!(
+                "  Equity: OOP {:.1}% / IP {:.1}%  |  EV share: OOP {:.1}% / IP {:.1}%",
+                self.oop_equity * 100.0,
+                (1.0 - self.oop_equity) * 100.0,
+                self.oop_ev_share * 100.0,
+                self.ip_ev_share * 100.0,
+            );
+        }
         println!(
             "  OOP range: {} ({} combos)  |  IP range: {} ({} combos)",
-            self.oop_range.join(","),
+            format_weighted_range(&self.oop_range, &self.oop_weights),
             self.oop_combos.len(),
-            self.ip_range.join(","),
+            format_weighted_range(&self.ip_range, &self.ip_weights),
             self.ip_combos.len(),
         );
 
+        let summary = self.summary();
+        if !summary.decisions.is_empty() {
+            println!();
+            println!("{}", range_summary_table(&summary));
+        }
+
         if let Some(root_strat) = self.strategies.first() {
             println!();
             println!(
@@ -2810,40 +4340,109 @@ impl FlopSolution {
             );
             println!("  Actions: {}", root_strat.actions.join(" | "));
 
-            let num_to_show = root_strat.frequencies.len().min(20);
             let combos = if root_strat.player == "OOP" {
                 &self.oop_combos
             } else {
                 &self.ip_combos
             };
+            print_combo_strategies(combos, &root_strat.frequencies, &root_strat.actions, expanded);
+            print_ev_extremes(root_strat, combos);
+        }
 
-            for i in 0..num_to_show {
-                let freq_str: String = root_strat.frequencies[i]
-                    .iter()
-                    .zip(&root_strat.actions)
-                    .map(|(f, a)| {
-                        let pct = (f * 100.0).round() as u32;
-                        if pct > 70 {
-                            format!("{}:{}", a, format!("{}%", pct).green())
-                        } else if pct > 30 {
-                            format!("{}:{}", a, format!("{}%", pct).yellow())
-                        } else {
-                            format!("{}:{}%", a, pct)
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join("  ");
-                println!("    {}  {}", combos[i].bold(), freq_str);
-            }
-            if root_strat.frequencies.len() > num_to_show {
-                println!(
-                    "    ... and {} more combos",
-                    root_strat.frequencies.len() - num_to_show
-                );
+        println!();
+    }
+
+    /// Strategy at `node_id` per canonical hand ("AKo"): each action's
+    /// frequency averaged over the hand's combos in the acting range. Empty if
+    /// the solution has no such node.
+    pub fn strategy_by_canonical(&self, node_id: u16) -> HashMap<String, Vec<f64>> {
+        match self.strategies.iter().find(|s| s.node_id == node_id) {
+            Some(strat) => average_by_canonical(self.combos_of(&strat.player), &strat.frequencies),
+            None => HashMap::new(),
+        }
+    }
+
+    /// 13x13 view of the strategy at `node_id`: the acting range, each hand's
+    /// bet/raise frequency, and the range's overall action mix.
+    pub fn display_grid(&self, node_id: u16) -> String {
+        use colored::Colorize;
+
+        let Some(strat) = self.strategies.iter().find(|s| s.node_id == node_id) else {
+            return String::new();
+        };
+        let by_hand = self.strategy_by_canonical(node_id);
+        let aggressive: Vec<bool> = strat
+            .actions
+            .iter()
+            .map(|a| a.starts_with("Bet") || a.starts_with("Raise"))
+            .collect();
+        let mut bet_freq = vec![0.0; 169];
+        for (hand, freqs) in &by_hand {
+            if let Some(bucket) = hand_to_bucket(hand) {
+                bet_freq[bucket] = freqs.iter().zip(&aggressive).filter(|(_, &agg)| agg).map(|(f, _)| f).sum();
             }
         }
+        let hands: Vec<String> = by_hand.keys().cloned().collect();
 
-        println!();
+        let combos = strat.frequencies.len().max(1) as f64;
+        let mix = strat
+            .actions
+            .iter()
+            .enumerate()
+            .map(|(a, label)| {
+                let freq = strat.frequencies.iter().map(|f| f[a]).sum::<f64>() / combos;
+                let pct = format!("{:.0}%", freq * 100.0);
+                let pct = if aggressive[a] { pct.red().to_string() } else { pct.green().to_string() };
+                format!("{} {}", label, pct)
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        format!(
+            "{}\n{}\n  Range mix: {}",
+            range_grid(&hands, &format!("{} range at node {}", strat.player, node_id)),
+            strategy_grid(&bet_freq, &format!("{} bet/raise % by hand", strat.player)),
+            mix
+        )
+    }
+
+    /// `oop_combos` or `ip_combos`, for a node's "OOP"/"IP" player.
+    fn combos_of(&self, player: &str) -> &[String] {
+        if player == "OOP" {
+            &self.oop_combos
+        } else {
+            &self.ip_combos
+        }
+    }
+}
+
+/// Number of combos listed at each end of the root EV ranking.
+const EV_EXTREMES: usize = 10;
+
+/// The root player's best and worst combos by EV, side by side.
+fn print_ev_extremes(strategy: &FlopNodeStrategy, combos: &[String]) {
+    use colored::Colorize;
+
+    let mut ranked: Vec<(&str, f64)> = (0..combos.len())
+        .filter_map(|i| Some((combos[i].as_str(), strategy.combo_ev(i)?)))
+        .collect();
+    if ranked.is_empty() {
+        return;
+    }
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let top = &ranked[..ranked.len().min(EV_EXTREMES)];
+    let bottom_len = (ranked.len() - top.len()).min(EV_EXTREMES);
+    let bottom = &ranked[ranked.len() - bottom_len..];
+
+    println!();
+    println!("  {} EV at root, in starting pots:", strategy.player.bold());
+    println!("    {:<18}{}", "Highest", if bottom.is_empty() { "" } else { "Lowest" });
+    for (i, (combo, ev)) in top.iter().enumerate() {
+        let high = format!("{}  {:+.2}", combo, ev);
+        match bottom.get(i) {
+            Some((combo, ev)) => println!("    {:<18}{}  {:+.2}", high, combo, ev),
+            None => println!("    {}", high),
+        }
     }
 }
 
@@ -2851,31 +4450,843 @@ impl FlopSolution {
 // Cache
 // ---------------------------------------------------------------------------
 
+fn flop_file_stem(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64) -> String {
+    format!("flop_{}_{}_{}_{:.0}_{:.0}", board, oop_pos, ip_pos, pot, stack)
+}
+
+/// File name suffix for non-default river template sizes, which `gto query`
+/// doesn't look up.
+fn river_sizing_suffix(river_bet_sizes: &[f64]) -> String {
+    let suffix = sizing_suffix(river_bet_sizes, false, &DEFAULT_TEMPLATE_RIVER_BET_SIZES, false);
+    if suffix.is_empty() { suffix } else { format!("_river{}", suffix) }
+}
+
+/// File name suffix for non-default flop bet sizes, like `river_sizing_suffix`.
+fn flop_sizing_suffix(flop_bet_sizes: &[f64]) -> String {
+    let suffix = sizing_suffix(flop_bet_sizes, false, &DEFAULT_FLOP_BET_SIZES, false);
+    if suffix.is_empty() { suffix } else { format!("_flop{}", suffix) }
+}
+
+/// File name suffix for bucketing other than the default equity slices,
+/// like `river_sizing_suffix`.
+fn bucketing_suffix(bucketing: BucketingStrategy) -> String {
+    match bucketing {
+        BucketingStrategy::EquityOnly => String::new(),
+        other => format!("_{}", other.name()),
+    }
+}
+
 impl FlopSolution {
+    /// Cache file name for a spot and its ranges' `range_key`, relative to
+    /// the solver cache directory. Suit-isomorphic flops share a file (see
+    /// `canonical_board`).
+    pub fn cache_file_name(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64, ranges: &str) -> String {
+        let board = canonical_board(board).unwrap_or_else(|_| board.to_string());
+        format!("{}_{}.bin", flop_file_stem(&board, oop_pos, ip_pos, pot, stack), ranges)
+    }
+
+    /// `range_key` of the solve's ranges.
+    pub fn range_key(&self) -> String {
+        range_key(&self.oop_range, &self.oop_weights, &self.ip_range, &self.ip_weights)
+    }
+
     pub fn cache_path(&self) -> std::path::PathBuf {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let dir = std::path::Path::new(&home).join(".gto-cli").join("solver");
+        let dir = solver_cache_dir();
         std::fs::create_dir_all(&dir).ok();
+        let board = canonical_board(&self.board).unwrap_or_else(|_| self.board.clone());
         dir.join(format!(
-            "flop_{}_{}_{}_{:.0}_{:.0}.bin",
-            self.board, self.oop_pos, self.ip_pos, self.starting_pot, self.effective_stack,
+            "{}_{}{}{}{}.bin",
+            flop_file_stem(&board, &self.oop_pos, &self.ip_pos, self.starting_pot, self.effective_stack),
+            self.range_key(),
+            flop_sizing_suffix(&self.flop_bet_sizes),
+            river_sizing_suffix(&self.river_bet_sizes),
+            bucketing_suffix(self.bucketing)
         ))
     }
 
     pub fn save_cache(&self) {
         if let Ok(data) = bincode::serialize(self) {
             let path = self.cache_path();
-            std::fs::write(path, data).ok();
+            let written = write_atomic(&path, &data);
+            if written.is_ok() {
+                crate::cache_index::record_saved(&path, &data, self);
+            }
+            crate::status::cache_written(&path, written);
+        }
+    }
+
+    /// The cached solve for a spot with the ranges of `ranges` (a
+    /// `range_key`), or for a suit-isomorphic flop, in the suits of `board`.
+    /// `None` takes the spot's latest solve whatever its ranges (see
+    /// `read_spot_cache`).
+    pub fn load_cache(
+        board: &str,
+        oop_pos: &str,
+        ip_pos: &str,
+        pot: f64,
+        stack: f64,
+        ranges: Option<&str>,
+    ) -> Option<FlopSolution> {
+        let canonical = canonical_board(board).unwrap_or_else(|_| board.to_string());
+        // Solves cached before file names were canonicalized are under their own board
+        let stems = [
+            flop_file_stem(&canonical, oop_pos, ip_pos, pot, stack),
+            flop_file_stem(board, oop_pos, ip_pos, pot, stack),
+        ];
+        let read = |path: &std::path::Path| FlopSolution::load_file(path).ok();
+        let mut solution = read_spot_cache(&stems, ranges, read, FlopSolution::range_key)?;
+        solution.relabel_to(board).ok()?;
+        Some(solution)
+    }
+
+    /// Express the solution in the suits of `board`, a suit-isomorphic flop
+    /// (a Ks9d4c solve read for Kh9c4d): board, combos and every per-combo
+    /// array are relabeled. Turn and river templates are per bucket, so
+    /// they carry over as they are.
+    pub fn relabel_to(&mut self, board: &str) -> Result<(), String> {
+        let map = suit_permutation(&self.board, board)?
+            .ok_or_else(|| format!("Board {} is not a suit relabeling of {}", board, self.board))?;
+        if map == [0, 1, 2, 3] {
+            return Ok(());
+        }
+        let (oop_combos, oop_order) = relabel_combos(&self.oop_combos, &map);
+        let (ip_combos, ip_order) = relabel_combos(&self.ip_combos, &map);
+        for strategy in &mut self.strategies {
+            let order = if strategy.player == "OOP" { &oop_order } else { &ip_order };
+            strategy.frequencies = reorder_rows(&strategy.frequencies, order);
+            strategy.evs = reorder_rows(&strategy.evs, order);
+        }
+        self.board = relabel_suits(&self.board, &map);
+        self.oop_combos = oop_combos;
+        self.ip_combos = ip_combos;
+        Ok(())
+    }
+
+    /// False for solves run below the iteration floor (via `--force`); those
+    /// aren't served to `gto query`. Time-limited solves always meet it.
+    pub fn meets_quality_floor(&self) -> bool {
+        self.time_limited || crate::limits::FLOP_ITERATIONS.meets_floor(self.iterations)
+    }
+
+    /// The stored quality grade, or one computed from the headline numbers
+    /// for solutions built without it.
+    pub fn solve_quality(&self) -> SolveQuality {
+        self.quality.clone().unwrap_or_else(|| {
+            SolveQuality::grade(
+                self.exploitability,
+                self.starting_pot,
+                self.iterations,
+                RECOMMENDED_FLOP_ITERATIONS,
+                Some(self.num_buckets),
+                self.oop_combos.len().max(self.ip_combos.len()),
+            )
+        })
+    }
+
+    /// Flop-street tree the solution was solved on.
+    pub fn tree_config(&self) -> TreeConfig {
+        if self.low_fidelity {
+            quick_flop_tree_config(self.starting_pot, self.effective_stack)
+        } else {
+            sized_flop_tree_config(self.starting_pot, self.effective_stack, &self.flop_bet_sizes)
+        }
+    }
+
+    /// River template tree the solution's river strategies refer to.
+    pub fn river_template_config(&self) -> TreeConfig {
+        river_template_config(&self.river_bet_sizes)
+    }
+
+    /// Load a solution from an explicit cache file (e.g. one printed by `solve flop`).
+    pub fn load_file(path: &std::path::Path) -> Result<FlopSolution, String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let mut solution: FlopSolution = bincode::deserialize(&data)
+            .map_err(|e| format!("{} is not a flop solution: {}", path.display(), e))?;
+        if solution.combo_order_version != COMBO_ORDER_VERSION {
+            return Err(format!(
+                "{} was written with an older combo ordering; re-solve it",
+                path.display()
+            ));
+        }
+        solution.fill_exploitability_pct();
+        Ok(solution)
+    }
+
+    /// Fill in `exploitability_pct_pot` for solves cached before it was
+    /// recorded, which read it back as 0.
+    pub fn fill_exploitability_pct(&mut self) {
+        if self.exploitability_pct_pot == 0.0 {
+            self.exploitability_pct_pot = pct_of_pot(self.exploitability, self.starting_pot);
+        }
+    }
+
+    /// Initial reach of each combo in `oop_combos`: its hand's frequency in
+    /// the range (1.0 for solves from before weighted ranges).
+    pub fn oop_combo_weights(&self) -> Vec<f64> {
+        self.combo_weights("OOP")
+    }
+
+    /// Initial reach of each of `player`'s combos ("OOP" or "IP").
+    fn combo_weights(&self, player: &str) -> Vec<f64> {
+        let (range, weights) = if player == "OOP" {
+            (&self.oop_range, &self.oop_weights)
+        } else {
+            (&self.ip_range, &self.ip_weights)
+        };
+        combo_weights(range, weights, &self.board, self.combos_of(player))
+    }
+
+    /// How each range splits at the root and answers each root action
+    /// (see `range_summary`).
+    pub fn summary(&self) -> RangeSummary {
+        let (tree, _) = build_tree(&self.tree_config());
+        summarize(
+            &tree,
+            |id| {
+                self.strategies
+                    .iter()
+                    .find(|s| s.node_id == id)
+                    .map(|s| (s.actions.as_slice(), s.frequencies.as_slice()))
+            },
+            WeightedCombos { combos: &self.oop_combos, weights: &self.combo_weights("OOP") },
+            WeightedCombos { combos: &self.ip_combos, weights: &self.combo_weights("IP") },
+        )
+    }
+
+    /// Hand-class composition of each action the summary covers (see
+    /// `breakdown`).
+    pub fn breakdown(&self) -> Breakdown {
+        let (tree, _) = build_tree(&self.tree_config());
+        breakdown(
+            &tree,
+            |id| {
+                self.strategies
+                    .iter()
+                    .find(|s| s.node_id == id)
+                    .map(|s| (s.actions.as_slice(), s.frequencies.as_slice()))
+            },
+            WeightedCombos { combos: &self.oop_combos, weights: &self.combo_weights("OOP") },
+            WeightedCombos { combos: &self.ip_combos, weights: &self.combo_weights("IP") },
+            &self.board,
+        )
+    }
+
+    /// Each combo of the player to act at flop decision `node_id` weighted
+    /// by its initial reach and its own earlier actions on the way there, in
+    /// the order of `oop_combos`/`ip_combos`.
+    pub fn decision_reach(&self, node_id: u16) -> Result<Vec<f64>, String> {
+        let (tree, _) = build_tree(&self.tree_config());
+        let (node, path) = path_to_node(&tree, node_id)
+            .ok_or_else(|| format!("No flop decision {} in this solve", node_id))?;
+        let TreeNode::Action { player, .. } = node else {
+            unreachable!("path_to_node only returns action nodes");
+        };
+        self.reach_along(&path, *player)
+    }
+
+    /// `player`'s reach after the decisions of `path` (see `path_to_node`).
+    fn reach_along(&self, path: &[(&TreeNode, usize)], player: Player) -> Result<Vec<f64>, String> {
+        let mut reach = self.combo_weights(player_label(player));
+        for (earlier, a) in path {
+            let TreeNode::Action { node_id: id, player: actor, .. } = earlier else {
+                continue;
+            };
+            if *actor == player {
+                for (r, freqs) in reach.iter_mut().zip(&self.strategy_at(*id)?.frequencies) {
+                    *r *= freqs[*a];
+                }
+            }
+        }
+        Ok(reach)
+    }
+
+    fn strategy_at(&self, node_id: u16) -> Result<&FlopNodeStrategy, String> {
+        self.strategies
+            .iter()
+            .find(|s| s.node_id == node_id)
+            .ok_or_else(|| format!("Solution has no strategy for node {}", node_id))
+    }
+
+    /// The range that takes `action` at flop decision `node_id`: each combo
+    /// of the player to act, weighted by its initial reach, its own earlier
+    /// actions on the way to the node and its frequency for `action`.
+    /// Weights are rounded to 0.001 and combos below `threshold` are left
+    /// out. `action` is a step as
+    /// `walk_line` takes it ("bet 75%", "bet75", "check").
+    pub fn action_range(&self, node_id: u16, action: &str, threshold: f64) -> Result<Vec<(String, f64)>, String> {
+        let (tree, _) = build_tree(&self.tree_config());
+        let (node, path) = path_to_node(&tree, node_id)
+            .ok_or_else(|| format!("No flop decision {} in this solve", node_id))?;
+        let TreeNode::Action { player, .. } = node else {
+            unreachable!("path_to_node only returns action nodes");
+        };
+        let taken = action_index(node, action)?;
+        let side = player_label(*player);
+        let reach = self.reach_along(&path, *player)?;
+        let strategy = self.strategy_at(node_id)?;
+        Ok(self
+            .combos_of(side)
+            .iter()
+            .zip(reach)
+            .zip(&strategy.frequencies)
+            .map(|((combo, r), freqs)| (combo.clone(), (r * freqs[taken] * 1000.0).round() / 1000.0))
+            .filter(|&(_, weight)| weight > 0.0 && weight >= threshold)
+            .collect())
+    }
+
+    /// Each side's reach into the turn along flop `line`, which must close
+    /// the flop (e.g. ["check", "check"] or ["bet33", "call"]; steps as
+    /// `walk_line` takes them): every combo's initial reach times its
+    /// frequency for each of its own actions on the line.
+    pub fn line_reach(&self, line: &[String]) -> Result<LineReach, String> {
+        let (tree, _) = build_tree(&self.tree_config());
+        let mut oop = self.combo_weights("OOP");
+        let mut ip = self.combo_weights("IP");
+        let mut followed: Vec<String> = Vec::new();
+        let mut node = &tree;
+        for step in line {
+            let TreeNode::Action { node_id, player, actions, children, .. } = node else {
+                return Err(format!("[{}] closes the flop before {}", followed.join(", "), step));
+            };
+            let a = action_index(node, step).map_err(|why| {
+                let at = if followed.is_empty() {
+                    "at the root".to_string()
+                } else {
+                    format!("after [{}]", followed.join(", "))
+                };
+                format!("{} for {} {}", why, player_label(*player), at)
+            })?;
+            let strategy = self
+                .strategies
+                .iter()
+                .find(|s| s.node_id == *node_id)
+                .ok_or_else(|| format!("Solution has no strategy for node {}", node_id))?;
+            let reach = if *player == Player::OOP { &mut oop } else { &mut ip };
+            for (r, freqs) in reach.iter_mut().zip(&strategy.frequencies) {
+                *r *= freqs[a];
+            }
+            followed.push(actions[a].label());
+            node = &children[a];
+        }
+        match node {
+            TreeNode::Terminal { terminal_type: TerminalType::Showdown, pot, stacks, .. } => {
+                let effective_stack = stacks[0].min(stacks[1]);
+                if effective_stack < 0.01 {
+                    return Err(format!("[{}] puts a player all in; there is no turn play to solve", followed.join(", ")));
+                }
+                Ok(LineReach { line: followed, oop, ip, pot: *pot, effective_stack })
+            }
+            TreeNode::Terminal { .. } => Err(format!("[{}] ends the hand with a fold", followed.join(", "))),
+            _ => Err(format!(
+                "[{}] doesn't close the flop; give both players' flop actions, e.g. check,check",
+                followed.join(", ")
+            )),
+        }
+    }
+
+    /// Villain's view of hero's flop decision `node_id`: villain's combos
+    /// weighted by their initial reach and their own actions on the way to
+    /// the node, less any holding one of `hero_cards`, and that range's
+    /// average frequencies at each villain decision one hero action away.
+    pub fn villain_view(&self, node_id: u16, hero_cards: &[Card]) -> Result<VillainView, String> {
+        let (tree, _) = build_tree(&self.tree_config());
+        let (node, path) = path_to_node(&tree, node_id)
+            .ok_or_else(|| format!("No flop decision {} in this solve", node_id))?;
+        let TreeNode::Action { player: hero, actions, children, .. } = node else {
+            unreachable!("path_to_node only returns action nodes");
+        };
+        let villain = if *hero == Player::OOP { Player::IP } else { Player::OOP };
+        let side = player_label(villain);
+        let strategy_at = |id: u16| {
+            self.strategies
+                .iter()
+                .find(|s| s.node_id == id)
+                .ok_or_else(|| format!("Solution has no strategy for node {}", id))
+        };
+
+        let weights = self.combo_weights(side);
+        let mut reach = weights.clone();
+        for (earlier, a) in &path {
+            if let TreeNode::Action { node_id: id, player: actor, .. } = earlier {
+                if *actor == villain {
+                    for (r, freqs) in reach.iter_mut().zip(&strategy_at(*id)?.frequencies) {
+                        *r *= freqs[*a];
+                    }
+                }
+            }
+        }
+        for (r, combo) in reach.iter_mut().zip(self.combos_of(side)) {
+            let cards = parse_board(combo).unwrap_or_default();
+            if cards.iter().any(|c| hero_cards.contains(c)) {
+                *r = 0.0;
+            }
+        }
+        let reaching: f64 = reach.iter().sum();
+
+        let mut responses = Vec::new();
+        for (action, child) in actions.iter().zip(children) {
+            let TreeNode::Action { node_id: id, player, .. } = child else {
+                continue;
+            };
+            if *player != villain || reaching <= 0.0 {
+                continue;
+            }
+            let strategy = strategy_at(*id)?;
+            let mut frequencies = vec![0.0; strategy.actions.len()];
+            for (r, freqs) in reach.iter().zip(&strategy.frequencies) {
+                for (total, f) in frequencies.iter_mut().zip(freqs) {
+                    *total += r * f;
+                }
+            }
+            frequencies.iter_mut().for_each(|f| *f /= reaching);
+            responses.push(VillainResponse {
+                hero_action: action.label(),
+                actions: strategy.actions.clone(),
+                frequencies,
+            });
+        }
+
+        let (range, range_weights) = if villain == Player::OOP {
+            (&self.oop_range, &self.oop_weights)
+        } else {
+            (&self.ip_range, &self.ip_weights)
+        };
+        Ok(VillainView {
+            side: side.to_string(),
+            range: format_weighted_range(range, range_weights),
+            combos: weights.iter().sum(),
+            reaching,
+            responses,
+        })
+    }
+
+    /// A copy with every flop strategy rounded by `simplify` (turn and river
+    /// templates are left alone). Its EV loss is how much more a best
+    /// response wins against each side's rounded strategy than against its
+    /// original, on the same runouts and averaged over the two sides (see
+    /// `paired_best_responses`).
+    pub fn simplified(&self, simplify: Simplify) -> FlopSolution {
+        let mut simplified = self.clone();
+        for node in &mut simplified.strategies {
+            simplify.apply_all(&mut node.frequencies);
+        }
+        let loss = self.paired_best_responses(&simplified).map(|(before, after)| {
+            let conceded: f64 = (0..2).map(|p| (after[p] - before[p]).max(0.0)).sum();
+            pct_of_pot(conceded / 2.0, self.starting_pot)
+        });
+        simplified.simplified = Some(simplify.info(loss));
+        simplified
+    }
+
+    /// What each player's best response wins, per combo and runout, against
+    /// the stored strategies and against the same turn and river templates
+    /// under `other`'s flop strategies: `[OOP's, IP's]`, in the units of
+    /// `exploitability`. Both come from `best_response_totals` on the same
+    /// runouts and turn buckets, so their difference isn't sampling noise.
+    /// `None` if a combo or a stored strategy doesn't fit the rebuilt trees.
+    fn paired_best_responses(&self, other: &FlopSolution) -> Option<([f64; 2], [f64; 2])> {
+        let board: Vec<u8> = parse_board(&self.board).ok()?.iter().map(card_to_index).collect();
+        let oop_combos = parse_combo_strings(&self.oop_combos)?;
+        let ip_combos = parse_combo_strings(&self.ip_combos)?;
+        if board.len() != 3 || oop_combos.is_empty() || ip_combos.is_empty() || self.num_buckets == 0 {
+            return None;
+        }
+        let hands = [oop_combos.len(), ip_combos.len()];
+        let (flop_tree, _) = build_tree(&self.tree_config());
+        let (turn_template, _) = build_tree(&turn_template_config());
+        let (river_template, _) = build_tree(&self.river_template_config());
+        let flop_cfr = stored_tables(&flop_tree, hands, self.strategies.iter().map(|s| (s.node_id, s.player.as_str(), &s.frequencies[..])))?;
+        let other_flop_cfr = stored_tables(&flop_tree, hands, other.strategies.iter().map(|s| (s.node_id, s.player.as_str(), &s.frequencies[..])))?;
+        let templates = |tree: &TreeNode, strategies: &[TemplateBucketStrategy]| {
+            stored_tables(tree, [self.num_buckets; 2], strategies.iter().map(|s| (s.node_id, s.player.as_str(), &s.frequencies[..])))
+        };
+        let turn_cfr = templates(&turn_template, &self.turn_strategies)?;
+        let river_cfr = templates(&river_template, &self.river_strategies)?;
+
+        // Flop strategies are stored per combo, so each combo is its own bucket
+        let flop_buckets = hands.map(|n| (0..n as u16).collect::<Vec<u16>>());
+        let weights = [self.combo_weights("OOP"), self.combo_weights("IP")];
+        let blockers = [combo_blockers(&oop_combos), combo_blockers(&ip_combos)];
+        let valid_ip_for_oop = valid_opponents(&oop_combos, &ip_combos);
+        let valid_oop_for_ip = valid_opponents(&ip_combos, &oop_combos);
+        let oop_pairs: Vec<(u8, u8)> = oop_combos.iter().map(|c| (c.0, c.1)).collect();
+        let ip_pairs: Vec<(u8, u8)> = ip_combos.iter().map(|c| (c.0, c.1)).collect();
+        let remaining = remaining_deck_excluding(&board, &[]);
+        let runout_table = RunoutTable::new(
+            &board,
+            &remaining,
+            &oop_pairs,
+            &ip_pairs,
+            self.num_buckets,
+            PrecomputeMode::Lazy { cache_runouts: BR_TURNS * BR_RIVERS_PER_TURN },
+        );
+        // The solve's seed isn't stored; a fixed one keeps the result the
+        // same from one call to the next
+        let turns = sample_turns(remaining.len(), Some(0), |t| {
+            let turn_board = [board[0], board[1], board[2], remaining[t]];
+            turn_buckets(&oop_pairs, &ip_pairs, &turn_board, t, self.num_buckets, self.bucketing, Some(0))
+        });
+
+        let best_responses = |flop_cfr: &[FlatCfr; 2]| {
+            let profile = FlopProfile {
+                flop_tree: &flop_tree,
+                turn_template: &turn_template,
+                river_template: &river_template,
+                flop_cfr: [&flop_cfr[0], &flop_cfr[1]],
+                turn_cfr: [&turn_cfr[0], &turn_cfr[1]],
+                river_cfr: [&river_cfr[0], &river_cfr[1]],
+                flop_buckets: [&flop_buckets[0], &flop_buckets[1]],
+                oop_combos: &oop_combos,
+                ip_combos: &ip_combos,
+                weights: [&weights[0], &weights[1]],
+                blockers: [&blockers[0], &blockers[1]],
+                valid_ip_for_oop: &valid_ip_for_oop,
+                valid_oop_for_ip: &valid_oop_for_ip,
+                runout_table: &runout_table,
+                turns: &turns,
+                starting_pot: self.starting_pot,
+            };
+            best_response_totals(&profile).map(|t| if t.weight > 0.0 { (t.gain + t.value) / t.weight } else { 0.0 })
+        };
+        Some((best_responses(&flop_cfr), best_responses(&other_flop_cfr)))
+    }
+
+    /// Index of a combo (either card order) in `oop_combos`.
+    pub fn oop_combo_index(&self, combo: &str) -> Option<usize> {
+        find_combo_index(combo, &self.oop_combos)
+    }
+
+    /// Index of a combo (either card order) in `ip_combos`.
+    pub fn ip_combo_index(&self, combo: &str) -> Option<usize> {
+        find_combo_index(combo, &self.ip_combos)
+    }
+}
+
+/// Default `threshold` of exported ranges: combos taking the action less
+/// often than this are left out.
+pub const EXPORT_RANGE_THRESHOLD: f64 = 0.01;
+
+/// An `--export-range` spec, "node=0,action=bet 75%", optionally with a
+/// threshold (",threshold=0.05").
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeExport {
+    pub node_id: u16,
+    pub action: String,
+    pub threshold: f64,
+}
+
+impl RangeExport {
+    pub fn parse(spec: &str) -> Result<RangeExport, String> {
+        let (mut node_id, mut action, mut threshold) = (None, None, EXPORT_RANGE_THRESHOLD);
+        for part in spec.split(',') {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid export '{}': expected node=..,action=..", spec))?;
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "node" => node_id = Some(value.parse::<u16>().map_err(|_| format!("Invalid export node '{}'", value))?),
+                "action" => action = Some(value.to_string()),
+                "threshold" => {
+                    threshold = value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|t| (0.0..=1.0).contains(t))
+                        .ok_or_else(|| format!("Invalid export threshold '{}'", value))?
+                }
+                other => return Err(format!("Unknown export key '{}'. Valid: node, action, threshold", other)),
+            }
+        }
+        let (Some(node_id), Some(action)) = (node_id, action) else {
+            return Err(format!("Export '{}' needs node and action", spec));
+        };
+        Ok(RangeExport { node_id, action, threshold })
+    }
+
+    /// Check the node and action exist on the flop tree of `config`, so a
+    /// typo is caught before the solve rather than after.
+    pub fn check(&self, config: &TreeConfig) -> Result<(), String> {
+        let (tree, _) = build_tree(config);
+        let (node, _) = path_to_node(&tree, self.node_id).ok_or_else(|| {
+            let nodes: Vec<String> = collect_node_metadata(&tree)
+                .iter()
+                .map(|m| format!("{} ({:?})", m.node_id, m.player))
+                .collect();
+            format!("No decision node {}. Nodes: {}", self.node_id, nodes.join(", "))
+        })?;
+        action_index(node, &self.action).map(|_| ())
+    }
+}
+
+/// Resolve "@flop:FLOP:LINE", a range taken from a cached flop solve: the
+/// combos of the player making the last action of LINE (steps joined by
+/// commas, as `gto query --line` takes them), weighted as in
+/// `FlopSolution::action_range`. FLOP is the flop of `board`, or the name of
+/// a cache file when several solves of that flop are cached.
+pub fn resolve_flop_range_ref(reference: &str, board: &str) -> Result<String, String> {
+    let spec = reference
+        .strip_prefix("@flop:")
+        .ok_or_else(|| format!("'{}' is not a flop range reference (@flop:FLOP:LINE)", reference))?;
+    let (flop, line) = spec
+        .split_once(':')
+        .ok_or_else(|| format!("'{}' has no line; use @flop:FLOP:LINE, e.g. @flop:Ks9d4c:bet75", reference))?;
+    let mut steps: Vec<String> = line.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect();
+    let action = steps.pop().ok_or_else(|| format!("'{}' has an empty line", reference))?;
+
+    let dir = solver_cache_dir();
+    let mut solution = if flop.ends_with(".bin") {
+        FlopSolution::load_file(&dir.join(flop))?
+    } else {
+        let canonical = canonical_board(flop)?;
+        let prefix = format!("flop_{}_", canonical);
+        let mut files: Vec<String> = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .filter(|name| name.starts_with(&prefix) && name.ends_with(".bin"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+        match files.as_slice() {
+            [] => return Err(format!("No cached flop solve for {}; run `gto solve flop --board {}` first", flop, flop)),
+            [file] => FlopSolution::load_file(&dir.join(file))?,
+            _ => {
+                return Err(format!(
+                    "Several cached solves of {}; name one in place of the flop: {}",
+                    flop,
+                    files.join(", ")
+                ))
+            }
+        }
+    };
+
+    let flop_cards = parse_board(&solution.board).map_err(|e| e.to_string())?;
+    let board_cards = parse_board(board).map_err(|e| e.to_string())?;
+    let canonical_flop = canonical_board(&solution.board)?;
+    let board_flop: String = board_cards.iter().take(flop_cards.len()).map(|c| c.to_string()).collect();
+    if board_cards.len() <= flop_cards.len() || canonical_board(&board_flop)? != canonical_flop {
+        return Err(format!("{} doesn't deal out from the flop {} of {}", board, solution.board, reference));
+    }
+    solution.relabel_to(&board_flop)?;
+
+    let (tree, _) = build_tree(&solution.tree_config());
+    let TreeNode::Action { node_id, .. } = walk_line(&tree, &steps)? else {
+        return Err(format!("[{}] doesn't end at a flop decision", steps.join(", ")));
+    };
+    let range = solution.action_range(*node_id, &action, EXPORT_RANGE_THRESHOLD)?;
+    if range.is_empty() {
+        return Err(format!("No combos take {} at the end of [{}]", action, steps.join(", ")));
+    }
+    Ok(weighted_range_string(&range))
+}
+
+// ---------------------------------------------------------------------------
+// Checkpoints
+// ---------------------------------------------------------------------------
+
+/// Bump when `FlopCheckpoint`'s layout or the solver's CFR layout changes.
+const CHECKPOINT_VERSION: u32 = 5;
+
+/// Raw solver state to continue a flop solve later (`gto solve flop --resume`).
+///
+/// Unlike a warm start, which seeds a new solve from extracted strategies,
+/// this keeps the six CFR tables (regrets and strategy sums) as they were,
+/// so the solve picks up exactly where it stopped.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FlopCheckpoint {
+    pub version: u32,
+    pub board: String,
+    pub dead_cards: String,
+    pub oop_range: Vec<String>,
+    pub ip_range: Vec<String>,
+    pub oop_weights: Vec<f64>,
+    pub ip_weights: Vec<f64>,
+    pub oop_combos: Vec<String>,
+    pub ip_combos: Vec<String>,
+    pub starting_pot: f64,
+    pub effective_stack: f64,
+    pub num_buckets: usize,
+    pub bucketing: BucketingStrategy,
+    /// Flop, turn template and river template trees.
+    pub trees: [TreeConfig; 3],
+    /// Iterations run so far.
+    pub iterations: usize,
+    /// Flop buckets (OOP, IP) and turn buckets per turn card. Bucket equities
+    /// are sampled, so re-bucketing would scramble the tables.
+    flop_buckets: [Vec<u16>; 2],
+    turn_buckets: Vec<(Vec<u16>, Vec<u16>)>,
+    /// Flop OOP/IP, turn OOP/IP, river OOP/IP.
+    cfr: [FlatCfr; 6],
+}
+
+fn checkpoint_trees(config: &FlopSolverConfig) -> [TreeConfig; 3] {
+    [
+        config.tree_config(),
+        turn_template_config(),
+        river_template_config(&config.river_bet_sizes),
+    ]
+}
+
+impl FlopCheckpoint {
+    fn new(config: &FlopSolverConfig, precompute: &FlopPrecompute, iterations: usize, cfr: [FlatCfr; 6]) -> Self {
+        let combo_strings = |combos: &[Combo]| combos.iter().map(|c| c.to_string()).collect();
+        FlopCheckpoint {
+            version: CHECKPOINT_VERSION,
+            board: board_string(&config.board),
+            dead_cards: board_string(&config.dead_cards),
+            oop_range: config.oop_range.clone(),
+            ip_range: config.ip_range.clone(),
+            oop_weights: config.oop_weights.clone(),
+            ip_weights: config.ip_weights.clone(),
+            oop_combos: combo_strings(&precompute.oop_combos),
+            ip_combos: combo_strings(&precompute.ip_combos),
+            starting_pot: config.starting_pot,
+            effective_stack: config.effective_stack,
+            num_buckets: config.num_buckets,
+            bucketing: config.bucketing,
+            trees: checkpoint_trees(config),
+            iterations,
+            flop_buckets: [precompute.flop_oop_buckets.clone(), precompute.flop_ip_buckets.clone()],
+            turn_buckets: precompute.turn_bucket_table.clone(),
+            cfr,
+        }
+    }
+
+    /// Checkpoint file for a spot, next to its cached solution.
+    pub fn path(config: &FlopSolverConfig, oop_pos: &str, ip_pos: &str) -> std::path::PathBuf {
+        // Checkpoints hold the exact board's CFR tables, so they aren't shared across suits
+        let name = format!(
+            "{}_{}{}{}.bin",
+            flop_file_stem(&board_string(&config.board), oop_pos, ip_pos, config.starting_pot, config.effective_stack),
+            range_key(&config.oop_range, &config.oop_weights, &config.ip_range, &config.ip_weights),
+            flop_sizing_suffix(&config.flop_bet_sizes),
+            river_sizing_suffix(&config.river_bet_sizes)
+        );
+        solver_cache_dir().join(name).with_extension("ckpt")
+    }
+
+    pub fn save(&self, path: &std::path::Path) {
+        if let Ok(data) = bincode::serialize(self) {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).ok();
+            }
+            // Only failures are reported: the status line names the solution's cache
+            if let Err(e) = std::fs::write(path, data) {
+                crate::status::cache_written(path, Err(e));
+            }
+        }
+    }
+
+    /// The checkpoint at `path`, or `None` if there isn't one.
+    pub fn load(path: &std::path::Path) -> Result<Option<FlopCheckpoint>, String> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+        };
+        let checkpoint: FlopCheckpoint = bincode::deserialize(&data)
+            .map_err(|e| format!("{} is not a flop checkpoint: {}", path.display(), e))?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(format!("{} was written by an older solver; start a fresh solve", path.display()));
+        }
+        Ok(Some(checkpoint))
+    }
+
+    /// Refuse to continue a different spot: every input that shapes the CFR
+    /// tables or the game must match.
+    fn check_matches(&self, config: &FlopSolverConfig, oop_combos: &[Combo], ip_combos: &[Combo]) -> Result<(), String> {
+        let board = board_string(&config.board);
+        let combo_strings = |combos: &[Combo]| combos.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let dead_cards = board_string(&config.dead_cards);
+        let or_none = |cards: &str| if cards.is_empty() { "none".to_string() } else { cards.to_string() };
+        let mismatch = if self.board != board {
+            Some(format!("board {} (checkpoint has {})", board, self.board))
+        } else if self.dead_cards != dead_cards {
+            Some(format!("dead cards {} (checkpoint has {})", or_none(&dead_cards), or_none(&self.dead_cards)))
+        } else if self.oop_range != config.oop_range
+            || self.oop_weights != config.oop_weights
+            || self.oop_combos != combo_strings(oop_combos)
+        {
+            Some("OOP range".to_string())
+        } else if self.ip_range != config.ip_range
+            || self.ip_weights != config.ip_weights
+            || self.ip_combos != combo_strings(ip_combos)
+        {
+            Some("IP range".to_string())
+        } else if self.starting_pot != config.starting_pot {
+            Some(format!("pot {} (checkpoint has {})", config.starting_pot, self.starting_pot))
+        } else if self.effective_stack != config.effective_stack {
+            Some(format!("stack {} (checkpoint has {})", config.effective_stack, self.effective_stack))
+        } else if self.num_buckets != config.num_buckets {
+            Some(format!("{} buckets (checkpoint has {})", config.num_buckets, self.num_buckets))
+        } else if self.bucketing != config.bucketing {
+            Some(format!("{} bucketing (checkpoint has {})", config.bucketing.name(), self.bucketing.name()))
+        } else if self.trees != checkpoint_trees(config) {
+            Some("tree config".to_string())
+        } else if self.cfr[0].variant() != config.cfr_variant {
+            Some(format!("CFR variant {} (checkpoint ran {})", config.cfr_variant.name(), self.cfr[0].variant().name()))
+        } else {
+            None
+        };
+        match mismatch {
+            Some(what) => Err(format!("Checkpoint is for a different spot: {} differs", what)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_payoffs_stay_within_a_short_stack() {
+        // 10 in the pot and 15 behind: the unit-pot templates bet up to 100
+        // pots, so most of their lines get capped
+        let (scale, stack) = (10.0, 15.0);
+        let mut all_in_showdowns = 0;
+        for config in [turn_template_config(), river_template_config(&DEFAULT_TEMPLATE_RIVER_BET_SIZES)] {
+            let (template, _) = build_tree(&config);
+            let mut nodes = vec![&template];
+            while let Some(node) = nodes.pop() {
+                if let Some(put_in) = template_showdown(node, scale, stack) {
+                    assert!(put_in.iter().all(|&c| (0.0..=stack).contains(&c)), "{:?}", put_in);
+                    assert!(scale + put_in[0] + put_in[1] <= scale + 2.0 * stack);
+                    if put_in == [stack, stack] {
+                        all_in_showdowns += 1;
+                    }
+                    continue;
+                }
+                match node {
+                    TreeNode::Terminal { terminal_type: TerminalType::Fold { folder }, invested, .. } => {
+                        for player in [Player::OOP, Player::IP] {
+                            let value = template_fold_value(player, *folder, invested, scale, stack, 1.0);
+                            assert!((-stack..=scale + 2.0 * stack).contains(&value), "{}", value);
+                        }
+                    }
+                    TreeNode::Action { children, .. } => nodes.extend(children),
+                    _ => {}
+                }
+            }
         }
+        assert!(all_in_showdowns > 0);
     }
 
-    pub fn load_cache(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64) -> Option<FlopSolution> {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let path = std::path::Path::new(&home)
-            .join(".gto-cli")
-            .join("solver")
-            .join(format!("flop_{}_{}_{}_{:.0}_{:.0}.bin", board, oop_pos, ip_pos, pot, stack));
-        let data = std::fs::read(path).ok()?;
-        bincode::deserialize(&data).ok()
+    #[test]
+    fn test_lopsided_ranges_weigh_each_side_by_its_own_combos() {
+        // 40 OOP combos and 600 IP combos, each gaining 2 and 1 chips: pooling
+        // the gains over OOP's weight alone would report (80 + 600) / 80
+        let (exploitability, per_weight) = combine_side_gains([80.0, 600.0], [40.0, 600.0]);
+        assert_eq!(per_weight, [2.0, 1.0]);
+        assert!((exploitability - 1.5).abs() < 1e-12);
+        assert_eq!(combine_side_gains([0.0, 6.0], [0.0, 3.0]).0, 1.0);
+
+        // Every runout at the same ratios: no spread
+        let samples = vec![[(4.0, 2.0), (30.0, 30.0)], [(8.0, 4.0), (60.0, 60.0)], [(2.0, 1.0), (15.0, 15.0)]];
+        assert!(ratio_std_err(&samples, [2.0, 1.0]).abs() < 1e-12);
+        let noisy = vec![[(6.0, 2.0), (30.0, 30.0)], [(4.0, 4.0), (60.0, 60.0)], [(2.0, 1.0), (15.0, 15.0)]];
+        assert!(ratio_std_err(&noisy, [12.0 / 7.0, 1.0]) > 0.0);
     }
 }
@@ -3,16 +3,23 @@
 //! Implements a CFR+ solver for the simplest poker decision:
 //! SB shoves all-in or folds, BB calls or folds. Produces Nash
 //! equilibrium push/call ranges for any stack depth.
+//!
+//! `solve_multiway_push_fold` generalizes the game to any 6-max seat
+//! shoving with folds ahead: every player left to act calls or folds in
+//! turn, antes are posted, and multiway all-ins are settled on sampled
+//! deals.
 
 use rand::Rng;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::card_encoding::{card_to_index, remaining_deck};
 use crate::cards::hand_combos;
 use crate::cfr::{CfrTrainer, InfoSetKey};
 use crate::lookup_eval::evaluate_fast;
+use crate::preflop_solver::Position;
 use crate::ranges::combo_count;
 
 /// The 13 ranks in grid order: A, K, Q, J, T, 9, 8, 7, 6, 5, 4, 3, 2.
@@ -608,6 +615,376 @@ fn compute_exploitability(
     (sb_gain / sb_total_combos + bb_gain / bb_total_combos) / 2.0
 }
 
+// ---------------------------------------------------------------------------
+// Multiway push/fold
+// ---------------------------------------------------------------------------
+
+/// 6-max seats in preflop acting order.
+pub const PREFLOP_ORDER: [Position; 6] = [
+    Position::UTG,
+    Position::HJ,
+    Position::CO,
+    Position::BTN,
+    Position::SB,
+    Position::BB,
+];
+
+/// Node ID of the shover's decision.
+const SHOVE_NODE: u16 = 0;
+
+/// Node ID of caller `caller` (1-based) facing the shove, with bit `i - 1`
+/// of `ahead` set when caller `i` already called.
+fn call_node(caller: usize, ahead: usize) -> u16 {
+    (caller * 32 + ahead) as u16
+}
+
+/// Bucket (0-168) of a two-card hand given as card indices.
+fn combo_bucket(c1: u8, c2: u8) -> usize {
+    // Card indices rank Two..Ace as 0..12; the grid runs Ace..Two
+    let (r1, r2) = (12 - (c1 / 4) as usize, 12 - (c2 / 4) as usize);
+    if r1 == r2 {
+        r1 * 13 + r1
+    } else if c1 % 4 == c2 % 4 {
+        r1.min(r2) * 13 + r1.max(r2)
+    } else {
+        r1.max(r2) * 13 + r1.min(r2)
+    }
+}
+
+/// A shove at a 6-max table folded to `shover`: the players behind call
+/// or fold in preflop order.
+///
+/// Every player starts with `stack_bb`, posts `ante_bb`, and the blinds
+/// post 0.5bb / 1bb, all out of their stacks. Antes of the seats that
+/// folded before the shove are dead money. Rake comes out of showdown pots
+/// only.
+#[derive(Debug, Clone, Copy)]
+pub struct MultiwayPushFold {
+    pub shover: Position,
+    pub stack_bb: f64,
+    pub ante_bb: f64,
+    pub rake: f64, // as fraction (0.0 - 1.0)
+}
+
+impl MultiwayPushFold {
+    pub fn new(shover: Position, stack_bb: f64, ante_bb: f64, rake_pct: f64) -> Result<Self, String> {
+        if shover == Position::BB {
+            return Err("BB can't shove first: there is nobody left to act".to_string());
+        }
+        Ok(MultiwayPushFold {
+            shover,
+            stack_bb,
+            ante_bb,
+            rake: rake_pct / 100.0,
+        })
+    }
+
+    /// The shover followed by the players left to act, in order.
+    pub fn players(&self) -> Vec<Position> {
+        PREFLOP_ORDER.iter().copied().skip_while(|&p| p != self.shover).collect()
+    }
+
+    /// What `pos` has in the pot before the shove: ante plus blind.
+    pub fn posted(&self, pos: Position) -> f64 {
+        self.ante_bb + pos.blind_amount()
+    }
+
+    /// The pot when the shove gets through: every ante plus both blinds.
+    pub fn dead_pot(&self) -> f64 {
+        PREFLOP_ORDER.iter().map(|&p| self.posted(p)).sum()
+    }
+
+    /// Each player's net result (indexed like `players()`) once the callers
+    /// in `mask` (bit `i` for caller `i`) have called the shove. `scores`
+    /// are the players' hand strengths at showdown.
+    pub fn payoffs(&self, players: &[Position], mask: usize, scores: &[u32]) -> Vec<f64> {
+        let mut result: Vec<f64> = players.iter().map(|&p| -self.posted(p)).collect();
+        if mask == 0 {
+            result[0] += self.dead_pot();
+            return result;
+        }
+
+        let all_in: Vec<usize> = (0..players.len()).filter(|&i| i == 0 || mask & (1 << i) != 0).collect();
+        let folded: f64 = (0..players.len())
+            .filter(|i| !all_in.contains(i))
+            .map(|i| self.posted(players[i]))
+            .sum();
+        let seats_out = PREFLOP_ORDER.len() - players.len();
+        let pot = self.stack_bb * all_in.len() as f64 + folded + self.ante_bb * seats_out as f64;
+
+        let best = all_in.iter().map(|&i| scores[i]).max().unwrap_or(0);
+        let winners = all_in.iter().filter(|&&i| scores[i] == best).count();
+        let share = pot * (1.0 - self.rake) / winners as f64;
+        for &i in &all_in {
+            result[i] = if scores[i] == best { share } else { 0.0 } - self.stack_bb;
+        }
+        result
+    }
+}
+
+/// One caller's decision facing the shove.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallNode {
+    pub position: Position,
+    /// Players who called the shove before this one.
+    pub callers_ahead: Vec<Position>,
+    /// Call probability for each hand bucket (0-168).
+    pub call_strategy: Vec<f64>,
+}
+
+impl CallNode {
+    /// Total call range as percentage of all hands.
+    pub fn call_pct(&self) -> f64 {
+        range_pct(&self.call_strategy)
+    }
+}
+
+/// Result of solving a multiway push/fold spot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiwayPushFoldResult {
+    pub shover: Position,
+    pub stack_bb: f64,
+    pub ante_bb: f64,
+    pub rake_pct: f64,
+    /// Number of CFR iterations run; each deals every shoving hand once.
+    pub iterations: usize,
+    /// Shove probability for each hand bucket (0-168).
+    pub shove_strategy: Vec<f64>,
+    /// Every caller decision, in tree order.
+    pub calls: Vec<CallNode>,
+}
+
+/// Percentage of all 1326 combos in buckets played more than half the time.
+fn range_pct(strategy: &[f64]) -> f64 {
+    let combos = (0..NUM_HANDS)
+        .filter(|&i| strategy[i] > 0.5)
+        .fold(0.0, |sum, i| sum + combo_count(&bucket_to_hand(i)) as f64);
+    combos / 1326.0 * 100.0
+}
+
+impl MultiwayPushFoldResult {
+    /// Total shove range as percentage of all hands.
+    pub fn shove_pct(&self) -> f64 {
+        range_pct(&self.shove_strategy)
+    }
+
+    /// `position`'s call-off strategy when everyone between it and the
+    /// shover folded.
+    pub fn call_off(&self, position: Position) -> Option<&CallNode> {
+        self.calls
+            .iter()
+            .find(|c| c.position == position && c.callers_ahead.is_empty())
+    }
+
+    /// The call-off decisions, one per player left to act.
+    pub fn call_offs(&self) -> impl Iterator<Item = &CallNode> {
+        self.calls.iter().filter(|c| c.callers_ahead.is_empty())
+    }
+
+    /// False for solves run below the iteration floor (via `--force`).
+    pub fn meets_quality_floor(&self) -> bool {
+        crate::limits::PUSHFOLD_ITERATIONS.meets_floor(self.iterations)
+    }
+
+    /// Cache file path for a spot.
+    pub fn path_for(shover: Position, stack_bb: f64, ante_bb: f64, rake_pct: f64) -> std::path::PathBuf {
+        crate::cache_index::solver_cache_dir().join(format!(
+            "pushfold_{}_{}bb_{}ante_{}pct.json",
+            shover.as_str(),
+            stack_bb,
+            ante_bb,
+            rake_pct,
+        ))
+    }
+
+    /// Get the cache file path for this solution.
+    pub fn cache_path(&self) -> std::path::PathBuf {
+        Self::path_for(self.shover, self.stack_bb, self.ante_bb, self.rake_pct)
+    }
+
+    /// Save solution to disk cache.
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = self.cache_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(&path, json)
+    }
+
+    /// Load solution from disk cache.
+    pub fn load(shover: Position, stack_bb: f64, ante_bb: f64, rake_pct: f64) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(Self::path_for(shover, stack_bb, ante_bb, rake_pct))?;
+        serde_json::from_str(&json).map_err(std::io::Error::other)
+    }
+
+    /// Display the shove grid and each caller's call-off grid.
+    pub fn display(&self) {
+        use colored::Colorize;
+        use crate::display::strategy_grid;
+
+        println!();
+        println!(
+            "  {} Push/Fold Solution  |  {} shoves  |  Stack: {}bb  |  Ante: {}bb  |  {} iterations",
+            "GTO".bold(),
+            self.shover,
+            self.stack_bb,
+            self.ante_bb,
+            self.iterations,
+        );
+
+        println!();
+        println!("{}", strategy_grid(
+            &self.shove_strategy,
+            &format!("{} Shove Frequency (%) — {:.1}% of hands", self.shover, self.shove_pct()),
+        ));
+
+        for node in self.call_offs() {
+            println!();
+            println!("{}", strategy_grid(
+                &node.call_strategy,
+                &format!(
+                    "{} Call vs {} Shove (%) — {:.1}% of hands",
+                    node.position,
+                    self.shover,
+                    node.call_pct(),
+                ),
+            ));
+        }
+
+        println!();
+    }
+}
+
+/// Solve a push/fold spot with any number of players left to act using
+/// chance-sampled CFR+.
+///
+/// Each iteration deals every shoving hand once, against random hands for
+/// the players behind and a random board, and updates every decision on
+/// the way to the showdowns. `seed` makes the deals reproducible.
+pub fn solve_multiway_push_fold(spot: &MultiwayPushFold, iterations: usize, seed: u64) -> MultiwayPushFoldResult {
+    let players = spot.players();
+    let callers = players.len() - 1;
+    let hand_combos_list: Vec<Vec<[u8; 2]>> = (0..NUM_HANDS)
+        .map(|bucket| {
+            hand_combos(&bucket_to_hand(bucket))
+                .unwrap_or_default()
+                .iter()
+                .map(|(c1, c2)| [card_to_index(c1), card_to_index(c2)])
+                .collect()
+        })
+        .collect();
+
+    let mut trainer = CfrTrainer::new();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut hands = vec![[0u8; 2]; players.len()];
+    let mut buckets = vec![0usize; players.len()];
+    let mut scores = vec![0u32; players.len()];
+
+    for _ in 0..iterations {
+        for combos in &hand_combos_list {
+            hands[0] = combos[rng.gen_range(0..combos.len())];
+
+            // Deal the callers and the board from what's left
+            let mut deck = remaining_deck(&hands[0]);
+            let needed = 2 * callers + 5;
+            for k in 0..needed {
+                let swap = rng.gen_range(k..deck.len());
+                deck.swap(k, swap);
+            }
+            for i in 1..players.len() {
+                hands[i] = [deck[2 * (i - 1)], deck[2 * (i - 1) + 1]];
+            }
+            let board = &deck[2 * callers..needed];
+            for i in 0..players.len() {
+                buckets[i] = combo_bucket(hands[i][0], hands[i][1]);
+                let mut cards = [0u8; 7];
+                cards[..2].copy_from_slice(&hands[i]);
+                cards[2..].copy_from_slice(board);
+                scores[i] = evaluate_fast(&cards);
+            }
+
+            let deal = Deal { spot, players: &players, buckets: &buckets, scores: &scores };
+            let mut reach = vec![1.0; players.len()];
+            deal.shover_node(&mut trainer, &mut reach);
+        }
+    }
+
+    let average = |bucket: usize, node_id: u16| {
+        trainer.get_average_strategy(&InfoSetKey { hand_bucket: bucket as u16, node_id }, 2)[0]
+    };
+    let shove_strategy = (0..NUM_HANDS).map(|h| average(h, SHOVE_NODE)).collect();
+    let mut calls = Vec::new();
+    for caller in 1..=callers {
+        for ahead in 0..1usize << (caller - 1) {
+            calls.push(CallNode {
+                position: players[caller],
+                callers_ahead: (1..caller).filter(|&i| ahead & (1 << (i - 1)) != 0).map(|i| players[i]).collect(),
+                call_strategy: (0..NUM_HANDS).map(|h| average(h, call_node(caller, ahead))).collect(),
+            });
+        }
+    }
+
+    MultiwayPushFoldResult {
+        shover: spot.shover,
+        stack_bb: spot.stack_bb,
+        ante_bb: spot.ante_bb,
+        rake_pct: spot.rake * 100.0,
+        iterations,
+        shove_strategy,
+        calls,
+    }
+}
+
+/// One sampled deal: the hands' buckets and showdown scores.
+struct Deal<'a> {
+    spot: &'a MultiwayPushFold,
+    players: &'a [Position],
+    buckets: &'a [usize],
+    scores: &'a [u32],
+}
+
+impl Deal<'_> {
+    fn shover_node(&self, trainer: &mut CfrTrainer, reach: &mut [f64]) {
+        let key = InfoSetKey { hand_bucket: self.buckets[0] as u16, node_id: SHOVE_NODE };
+        let strat = trainer.get_strategy(&key, 2);
+
+        reach[0] = strat[0];
+        let shove_value = self.caller_node(trainer, 1, 0, reach)[0];
+        reach[0] = 1.0;
+        let fold_value = -self.spot.posted(self.players[0]);
+
+        let node_value = strat[0] * shove_value + strat[1] * fold_value;
+        trainer.get_or_create(&key, 2).update(&[shove_value, fold_value], node_value, 1.0);
+    }
+
+    /// Every player's value from caller `caller`'s decision on, with the
+    /// callers in `mask` already in.
+    fn caller_node(&self, trainer: &mut CfrTrainer, caller: usize, mask: usize, reach: &mut [f64]) -> Vec<f64> {
+        if caller == self.players.len() {
+            return self.spot.payoffs(self.players, mask, self.scores);
+        }
+        let key = InfoSetKey { hand_bucket: self.buckets[caller] as u16, node_id: call_node(caller, mask >> 1) };
+        let strat = trainer.get_strategy(&key, 2);
+
+        let own_reach = reach[caller];
+        reach[caller] = own_reach * strat[0];
+        let call = self.caller_node(trainer, caller + 1, mask | (1 << caller), reach);
+        reach[caller] = own_reach * strat[1];
+        let fold = self.caller_node(trainer, caller + 1, mask, reach);
+        reach[caller] = own_reach;
+
+        let value: Vec<f64> = call.iter().zip(&fold).map(|(c, f)| strat[0] * c + strat[1] * f).collect();
+        let opp_reach: f64 = reach.iter().enumerate().filter(|&(i, _)| i != caller).map(|(_, r)| r).product();
+        trainer.get_or_create(&key, 2).update(
+            &[opp_reach * call[caller], opp_reach * fold[caller]],
+            opp_reach * value[caller],
+            own_reach,
+        );
+        value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -771,4 +1148,82 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn multiway_payoffs_account_for_antes_and_dead_money() {
+        let spot = MultiwayPushFold::new(Position::CO, 10.0, 0.125, 0.0).unwrap();
+        let players = spot.players();
+        assert_eq!(players, [Position::CO, Position::BTN, Position::SB, Position::BB]);
+        assert!((spot.dead_pot() - 2.25).abs() < 1e-9);
+
+        // Everyone folds: CO picks up the blinds and all six antes
+        let p = spot.payoffs(&players, 0, &[0; 4]);
+        assert!((p[0] - 2.125).abs() < 1e-9);
+        assert!((p[3] + 1.125).abs() < 1e-9);
+
+        // CO and BB all in, CO wins: BTN/SB money and UTG/HJ antes are dead
+        let p = spot.payoffs(&players, 1 << 3, &[9, 0, 0, 5]);
+        assert!((p[0] - (10.0 + 0.125 + 0.625 + 0.25)).abs() < 1e-9);
+        assert!((p[3] + 10.0).abs() < 1e-9);
+        assert!(p.iter().sum::<f64>() > 0.0, "dead antes come from outside the hand");
+
+        // Three-way chop between CO and BTN, SB busts
+        let p = spot.payoffs(&players, (1 << 1) | (1 << 2), &[7, 7, 3, 0]);
+        assert!((p[0] - p[1]).abs() < 1e-9);
+        assert!((p[2] + 10.0).abs() < 1e-9);
+        let total: f64 = p.iter().sum();
+        assert!((total - 0.25).abs() < 1e-9, "only the UTG/HJ antes are new money: {}", total);
+
+        assert!(MultiwayPushFold::new(Position::BB, 10.0, 0.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn combo_bucket_matches_grid() {
+        let idx = |s: &str| {
+            let cards = crate::cards::parse_board(s).unwrap();
+            (card_to_index(&cards[0]), card_to_index(&cards[1]))
+        };
+        for (cards, hand) in [("AhAd", "AA"), ("AsKs", "AKs"), ("KdAc", "AKo"), ("7c2d", "72o"), ("2h2c", "22")] {
+            let (c1, c2) = idx(cards);
+            assert_eq!(bucket_to_hand(combo_bucket(c1, c2)), hand);
+        }
+    }
+
+    #[test]
+    fn multiway_solver_heads_up_matches_exact_solver() {
+        // SB vs BB with no ante is the heads-up game; the exact solver
+        // shoves ~59% at 10bb
+        let spot = MultiwayPushFold::new(Position::SB, 10.0, 0.0, 0.0).unwrap();
+        let sampled = solve_multiway_push_fold(&spot, 2000, 1);
+        assert_eq!(sampled.calls.len(), 1);
+        let call = &sampled.call_off(Position::BB).unwrap().call_strategy;
+
+        for hand in ["AA", "KK", "A5s", "K9o"] {
+            let h = hand_to_bucket(hand).unwrap();
+            assert!(sampled.shove_strategy[h] > 0.9, "{} shove {}", hand, sampled.shove_strategy[h]);
+        }
+        let aa = hand_to_bucket("AA").unwrap();
+        assert!(call[aa] > 0.9);
+        let worst = hand_to_bucket("72o").unwrap();
+        assert!(call[worst] < 0.2, "72o call {}", call[worst]);
+        assert!(
+            (sampled.shove_pct() - 59.0).abs() < 8.0,
+            "sampled shove range {:.1}%",
+            sampled.shove_pct()
+        );
+    }
+
+    #[test]
+    fn multiway_solver_has_a_decision_per_caller_line() {
+        let spot = MultiwayPushFold::new(Position::CO, 12.0, 0.125, 0.0).unwrap();
+        let result = solve_multiway_push_fold(&spot, 200, 1);
+        // BTN once, SB with or without BTN in, BB behind any of four lines
+        assert_eq!(result.calls.len(), 7);
+        let call_offs: Vec<Position> = result.call_offs().map(|c| c.position).collect();
+        assert_eq!(call_offs, [Position::BTN, Position::SB, Position::BB]);
+        assert_eq!(result.calls.last().unwrap().callers_ahead, [Position::BTN, Position::SB]);
+        let aa = hand_to_bucket("AA").unwrap();
+        assert!(result.shove_strategy[aa] > 0.9);
+        assert!(result.call_off(Position::BB).unwrap().call_strategy[aa] > 0.9);
+    }
 }
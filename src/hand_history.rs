@@ -0,0 +1,778 @@
+//! Hand history import and spot analysis (`gto analyze`).
+//!
+//! Hands use a small generic format, one field per line, amounts in bb:
+//!
+//! ```text
+//! Hand: 1
+//! Seats: BTN 100, BB 100
+//! Hero: BTN AhKd
+//! Preflop: BTN raise 2.5, BB call
+//! Flop: Ks9d4c | BB check, BTN bet 2, BB call
+//! Turn: 7h | BB check, BTN check
+//! River: 2s | BB bet 4, BTN fold
+//! ```
+//!
+//! Blinds are posted automatically. A bet or raise amount is the player's
+//! total for the street ("raise 8" raises to 8bb), and a call is capped at
+//! the caller's stack. `#` starts a comment. Hands are replayed as they are
+//! parsed, so an action out of turn or an illegal check or bet is reported
+//! with its line number like any other parse error.
+//!
+//! Each hero decision is looked up with the `StrategyEngine`: preflop spots
+//! the preflop solution covers (open, facing an open, facing a 3-bet) and
+//! heads-up postflop spots, at the pot and stacks the hand's action gives.
+
+use std::collections::VecDeque;
+
+use colored::Colorize;
+
+use crate::cards::{hand_to_canonical, normalize_cards};
+use crate::preflop_solver::Position;
+use crate::strategy::{
+    pretty_board, pretty_hand, PostflopSpot, PotType, StrategyEngine, StrategyResult, StrategySource,
+};
+
+const EPS: f64 = 1e-6;
+
+/// Field names in the order a hand lists them.
+const FIELDS: [&str; 6] = ["Seats", "Hero", "Preflop", "Flop", "Turn", "River"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Street::Preflop => "Preflop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+        }
+    }
+
+    /// Board cards dealt at the start of this street.
+    fn new_cards(&self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn | Street::River => 1,
+        }
+    }
+}
+
+/// One action as written; bet and raise amounts are the street total in bb.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HandAction {
+    Fold,
+    Check,
+    Call,
+    Bet(f64),
+    Raise(f64),
+}
+
+impl std::fmt::Display for HandAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandAction::Fold => write!(f, "fold"),
+            HandAction::Check => write!(f, "check"),
+            HandAction::Call => write!(f, "call"),
+            HandAction::Bet(x) => write!(f, "bet {}", x),
+            HandAction::Raise(x) => write!(f, "raise {}", x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerAction {
+    pub position: Position,
+    pub action: HandAction,
+}
+
+/// A street's line: the cards it deals and the actions on it.
+#[derive(Debug, Clone)]
+pub struct StreetActions {
+    pub street: Street,
+    /// Cards dealt on this street ("" preflop).
+    pub cards: String,
+    pub actions: Vec<PlayerAction>,
+    /// Line of the hand history file this street is on.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandHistory {
+    pub id: String,
+    /// Seated positions with their starting stacks in bb.
+    pub seats: Vec<(Position, f64)>,
+    pub hero: Position,
+    pub hole_cards: String,
+    /// Preflop first, then each street that was dealt.
+    pub streets: Vec<StreetActions>,
+}
+
+impl HandHistory {
+    /// Hero's stack against the deepest opponent.
+    pub fn effective_stack(&self) -> f64 {
+        let hero = self.stack_of(self.hero);
+        let deepest = self
+            .seats
+            .iter()
+            .filter(|(p, _)| *p != self.hero)
+            .map(|&(_, s)| s)
+            .fold(0.0, f64::max);
+        hero.min(deepest)
+    }
+
+    fn stack_of(&self, position: Position) -> f64 {
+        self.seats.iter().find(|(p, _)| *p == position).map_or(0.0, |&(_, s)| s)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Parse every hand in `text`. Errors name the line and what was expected.
+pub fn parse_hand_histories(text: &str) -> Result<Vec<HandHistory>, String> {
+    let mut hands = Vec::new();
+    let mut current: Option<HandBuilder> = None;
+    for (i, raw) in text.lines().enumerate() {
+        let n = i + 1;
+        let line = raw.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(format!("line {}: expected 'Field: value', got '{}'", n, line));
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.eq_ignore_ascii_case("hand") {
+            if let Some(builder) = current.take() {
+                hands.push(builder.finish()?);
+            }
+            current = Some(HandBuilder::new(value, n));
+            continue;
+        }
+        let Some(builder) = current.as_mut() else {
+            return Err(format!("line {}: expected 'Hand:' to start a hand, got '{}'", n, key));
+        };
+        builder.add(key, value, n)?;
+    }
+    if let Some(builder) = current {
+        hands.push(builder.finish()?);
+    }
+    if hands.is_empty() {
+        return Err("No hands found (each hand starts with a 'Hand:' line)".to_string());
+    }
+    Ok(hands)
+}
+
+struct HandBuilder {
+    id: String,
+    line: usize,
+    /// Index into `FIELDS` of the next field expected.
+    next: usize,
+    seats: Vec<(Position, f64)>,
+    hero: Option<(Position, String)>,
+    streets: Vec<StreetActions>,
+}
+
+impl HandBuilder {
+    fn new(id: &str, line: usize) -> Self {
+        HandBuilder {
+            id: if id.is_empty() { line.to_string() } else { id.to_string() },
+            line,
+            next: 0,
+            seats: Vec::new(),
+            hero: None,
+            streets: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, key: &str, value: &str, n: usize) -> Result<(), String> {
+        let Some(field) = FIELDS.iter().position(|f| f.eq_ignore_ascii_case(key)) else {
+            return Err(format!("line {}: expected one of Hand, {}; got '{}'", n, FIELDS.join(", "), key));
+        };
+        if field != self.next {
+            let expected = match FIELDS.get(self.next) {
+                Some(f) => format!("a {} line", f),
+                None => "a new 'Hand:'".to_string(),
+            };
+            return Err(format!("line {}: expected {}, got {}", n, expected, FIELDS[field]));
+        }
+        self.next += 1;
+        match field {
+            0 => self.seats = parse_seats(value, n)?,
+            1 => self.hero = Some(self.parse_hero(value, n)?),
+            _ => {
+                let street = [Street::Preflop, Street::Flop, Street::Turn, Street::River][field - 2];
+                let street = self.parse_street(street, value, n)?;
+                self.streets.push(street);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_hero(&self, value: &str, n: usize) -> Result<(Position, String), String> {
+        let (pos, cards) = value.split_once(char::is_whitespace).unwrap_or((value, ""));
+        let position = parse_position(pos, n)?;
+        if !self.seats.iter().any(|(p, _)| *p == position) {
+            return Err(format!("line {}: hero's position {} has no seat", n, position));
+        }
+        match normalize_cards(cards.trim()) {
+            Ok(c) if c.len() == 4 => {
+                if c[..2] == c[2..] {
+                    return Err(format!("line {}: card {} is dealt twice", n, &c[..2]));
+                }
+                Ok((position, c))
+            }
+            _ => Err(format!("line {}: expected two hole cards after {}, got '{}'", n, position, cards.trim())),
+        }
+    }
+
+    fn parse_street(&self, street: Street, value: &str, n: usize) -> Result<StreetActions, String> {
+        let (cards, actions) = if street == Street::Preflop {
+            (String::new(), value)
+        } else {
+            let (cards, actions) = value.split_once('|').unwrap_or((value, ""));
+            let want = street.new_cards();
+            let cards = match normalize_cards(cards.trim()) {
+                Ok(c) if c.len() == want * 2 => c,
+                _ => {
+                    let what = if want == 1 { "1 card".to_string() } else { format!("{} cards", want) };
+                    return Err(format!(
+                        "line {}: expected {} for the {}, got '{}'",
+                        n,
+                        what,
+                        street.as_str().to_lowercase(),
+                        cards.trim()
+                    ));
+                }
+            };
+            (cards, actions)
+        };
+
+        // Every card dealt so far, hole cards first
+        let mut dealt: Vec<&str> = Vec::new();
+        if let Some((_, hole)) = &self.hero {
+            dealt.extend([&hole[..2], &hole[2..]]);
+        }
+        let board: Vec<&str> = self.streets.iter().map(|s| s.cards.as_str()).collect();
+        for chunk in board.iter().chain(std::iter::once(&cards.as_str())) {
+            for i in (0..chunk.len()).step_by(2) {
+                let card = &chunk[i..i + 2];
+                if dealt.contains(&card) {
+                    return Err(format!("line {}: card {} is dealt twice", n, card));
+                }
+                dealt.push(card);
+            }
+        }
+
+        let actions = actions
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(|a| parse_action(a, n))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(StreetActions { street, cards, actions, line: n })
+    }
+
+    fn finish(self) -> Result<HandHistory, String> {
+        let Some((hero, hole_cards)) = self.hero.filter(|_| self.next > 2) else {
+            return Err(format!(
+                "line {}: hand {} ends without a {} line",
+                self.line, self.id, FIELDS[self.next.min(2)]
+            ));
+        };
+        let hand = HandHistory {
+            id: self.id,
+            seats: self.seats,
+            hero,
+            hole_cards,
+            streets: self.streets,
+        };
+        replay(&hand)?;
+        Ok(hand)
+    }
+}
+
+fn parse_position(token: &str, n: usize) -> Result<Position, String> {
+    Position::from_str(token)
+        .ok_or_else(|| format!("line {}: expected a position (UTG, HJ, CO, BTN, SB, BB), got '{}'", n, token))
+}
+
+/// An amount in bb, with or without a "bb" suffix.
+fn parse_amount(token: &str, n: usize) -> Result<f64, String> {
+    let lower = token.to_lowercase();
+    match lower.trim_end_matches("bb").parse::<f64>() {
+        Ok(x) if x > 0.0 && x.is_finite() => Ok(x),
+        _ => Err(format!("line {}: expected an amount in bb, got '{}'", n, token)),
+    }
+}
+
+fn parse_seats(value: &str, n: usize) -> Result<Vec<(Position, f64)>, String> {
+    let mut seats: Vec<(Position, f64)> = Vec::new();
+    for seat in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let tokens: Vec<&str> = seat.split_whitespace().collect();
+        let [pos, stack] = tokens[..] else {
+            return Err(format!("line {}: expected 'POSITION STACK', got '{}'", n, seat));
+        };
+        let position = parse_position(pos, n)?;
+        if seats.iter().any(|(p, _)| *p == position) {
+            return Err(format!("line {}: {} is seated twice", n, position));
+        }
+        seats.push((position, parse_amount(stack, n)?));
+    }
+    if seats.len() < 2 {
+        return Err(format!("line {}: expected at least two seats, got '{}'", n, value));
+    }
+    Ok(seats)
+}
+
+fn parse_action(text: &str, n: usize) -> Result<PlayerAction, String> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let position = parse_position(tokens[0], n)?;
+    let verb = tokens.get(1).map(|v| v.to_lowercase()).unwrap_or_default();
+    let args = tokens.get(2..).unwrap_or_default();
+    let action = match (verb.as_str(), args) {
+        ("fold", []) => HandAction::Fold,
+        ("check", []) => HandAction::Check,
+        ("call", []) => HandAction::Call,
+        ("bet", [amount]) => HandAction::Bet(parse_amount(amount, n)?),
+        ("raise", [amount]) => HandAction::Raise(parse_amount(amount, n)?),
+        ("bet" | "raise", _) => {
+            return Err(format!("line {}: expected '{} {} AMOUNT', got '{}'", n, position, verb, text));
+        }
+        _ => {
+            return Err(format!(
+                "line {}: expected fold, check, call, bet or raise after {}, got '{}'",
+                n, position, text
+            ));
+        }
+    };
+    Ok(PlayerAction { position, action })
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+/// What the strategy engine is asked about a hero decision.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Spot {
+    /// A preflop spot: opening (`None`), or facing the given player's open or 3-bet.
+    Preflop { villain: Option<Position> },
+    /// A heads-up postflop spot; `line` is the street's action before hero
+    /// in `walk_line` steps.
+    Postflop { villain: Position, pot_type: PotType, line: Vec<String> },
+    /// Why no solution covers this spot.
+    Uncovered(String),
+}
+
+/// A decision hero made, with the state of the hand when it came up.
+#[derive(Debug, Clone)]
+pub struct Decision {
+    pub street: Street,
+    /// Board dealt so far.
+    pub board: String,
+    /// Actions on this street before hero's, folds left out.
+    pub facing: Vec<PlayerAction>,
+    pub action: HandAction,
+    /// Chips hero's action puts in, in bb.
+    pub chips: f64,
+    /// Pot at the start of the street, in bb.
+    pub pot: f64,
+    /// Effective stack behind at the start of the street, in bb.
+    pub stack: f64,
+    pub spot: Spot,
+}
+
+struct Seat {
+    position: Position,
+    behind: f64,
+    /// Chips put in on the current street.
+    street: f64,
+    folded: bool,
+}
+
+/// Play `hand` through and collect hero's decisions. Fails on an action
+/// out of turn or one the situation doesn't allow.
+pub fn replay(hand: &HandHistory) -> Result<Vec<Decision>, String> {
+    // Seats in preflop order; `Position` is declared UTG..BB
+    let mut seats: Vec<Seat> = hand
+        .seats
+        .iter()
+        .map(|&(position, stack)| Seat { position, behind: stack, street: 0.0, folded: false })
+        .collect();
+    seats.sort_by_key(|s| s.position as usize);
+
+    let mut pot = 0.0;
+    let mut raisers: Vec<Position> = Vec::new();
+    let mut limped = false;
+    let mut board = String::new();
+    let mut decisions = Vec::new();
+
+    for (s, street) in hand.streets.iter().enumerate() {
+        let n = street.line;
+        let preflop = street.street == Street::Preflop;
+        board.push_str(&street.cards);
+
+        // Acting order: preflop as seated, postflop blinds first
+        let mut order: Vec<usize> = (0..seats.len()).collect();
+        if !preflop {
+            order.sort_by_key(|&i| (seats[i].position as usize + 2) % 6);
+        }
+        let mut current_bet = 0.0;
+        for seat in seats.iter_mut() {
+            seat.street = 0.0;
+            if preflop {
+                let blind = seat.position.blind_amount().min(seat.behind);
+                seat.street = blind;
+                seat.behind -= blind;
+                pot += blind;
+                current_bet = f64::max(current_bet, blind);
+            }
+        }
+        let street_pot = pot;
+        let in_hand: Vec<usize> = order.iter().copied().filter(|&i| !seats[i].folded).collect();
+        let stack = in_hand.iter().map(|&i| seats[i].behind).fold(f64::INFINITY, f64::min);
+        let mut to_act: VecDeque<usize> = in_hand.iter().copied().filter(|&i| seats[i].behind > EPS).collect();
+        let mut facing: Vec<PlayerAction> = Vec::new();
+        let mut steps: Vec<String> = Vec::new();
+        // Calls of the current raise (or limps) preflop
+        let mut callers = 0;
+
+        for &PlayerAction { position, action } in &street.actions {
+            if seats.iter().filter(|s| !s.folded).count() < 2 {
+                return Err(format!("line {}: {} acts after the hand is over", n, position));
+            }
+            let Some(&i) = to_act.front() else {
+                return Err(format!("line {}: {} acts after the {} action is closed", n, position, street.street.as_str().to_lowercase()));
+            };
+            if seats[i].position != position {
+                return Err(format!("line {}: expected {} to act, got {}", n, seats[i].position, position));
+            }
+
+            let seat = &seats[i];
+            let to_call = current_bet - seat.street;
+            let chips = match action {
+                HandAction::Fold | HandAction::Check => 0.0,
+                HandAction::Call => to_call.min(seat.behind),
+                HandAction::Bet(x) | HandAction::Raise(x) => x - seat.street,
+            };
+            match action {
+                HandAction::Check if to_call > EPS => {
+                    return Err(format!("line {}: {} cannot check facing {}; call, raise or fold", n, position, current_bet));
+                }
+                HandAction::Call if to_call <= EPS => {
+                    return Err(format!("line {}: {} has nothing to call; check or bet", n, position));
+                }
+                HandAction::Bet(_) if current_bet > EPS => {
+                    return Err(format!("line {}: {} faces {}; raise instead of bet", n, position, current_bet));
+                }
+                HandAction::Raise(_) if current_bet <= EPS => {
+                    return Err(format!("line {}: {} has nothing to raise; bet instead", n, position));
+                }
+                HandAction::Raise(x) if x <= current_bet + EPS => {
+                    return Err(format!("line {}: {} raises to {}, not above {}", n, position, x, current_bet));
+                }
+                HandAction::Bet(x) | HandAction::Raise(x) if chips > seat.behind + EPS => {
+                    return Err(format!("line {}: {} puts in {} with {} behind", n, position, x, seat.behind + seat.street));
+                }
+                _ => {}
+            }
+
+            if position == hand.hero {
+                let spot = if preflop {
+                    preflop_spot(hand.hero, &raisers, limped, callers)
+                } else {
+                    postflop_spot(hand.hero, &seats, &in_hand, &raisers, &steps)
+                };
+                decisions.push(Decision {
+                    street: street.street,
+                    board: board.clone(),
+                    facing: facing.clone(),
+                    action,
+                    chips,
+                    pot: street_pot,
+                    stack,
+                    spot,
+                });
+            }
+
+            // The step `walk_line` would take for this action
+            let pot_now = pot;
+            let covers_everyone = seats
+                .iter()
+                .enumerate()
+                .filter(|&(j, s)| j != i && !s.folded)
+                .all(|(_, s)| seats[i].street + chips + EPS >= s.street + s.behind);
+            let allin = chips + 0.01 >= seats[i].behind || covers_everyone;
+            steps.push(match action {
+                HandAction::Fold => "fold".to_string(),
+                HandAction::Check => "check".to_string(),
+                HandAction::Call => "call".to_string(),
+                HandAction::Bet(_) | HandAction::Raise(_) if allin => "allin".to_string(),
+                HandAction::Bet(_) => format!("bet{:.0}", chips / pot_now * 100.0),
+                HandAction::Raise(_) => format!("raise{:.0}", (chips - to_call) / (pot_now + to_call) * 100.0),
+            });
+
+            to_act.pop_front();
+            let seat = &mut seats[i];
+            seat.behind -= chips;
+            seat.street += chips;
+            pot += chips;
+            match action {
+                HandAction::Fold => seat.folded = true,
+                HandAction::Call => {
+                    callers += 1;
+                    if preflop && raisers.is_empty() {
+                        limped = true;
+                    }
+                }
+                HandAction::Bet(x) | HandAction::Raise(x) => {
+                    current_bet = x;
+                    callers = 0;
+                    if preflop {
+                        raisers.push(position);
+                    }
+                    // Everyone else still able to act gets to respond, in order
+                    let at = order.iter().position(|&j| j == i).unwrap_or(0);
+                    to_act = (1..order.len())
+                        .map(|k| order[(at + k) % order.len()])
+                        .filter(|&j| !seats[j].folded && seats[j].behind > EPS)
+                        .collect();
+                }
+                HandAction::Check => {}
+            }
+            if action != HandAction::Fold {
+                facing.push(PlayerAction { position, action });
+            }
+        }
+
+        let last = s + 1 == hand.streets.len();
+        let players = seats.iter().filter(|s| !s.folded).count();
+        if !last && players < 2 {
+            return Err(format!(
+                "line {}: the hand is over after the {}, but a {} line follows",
+                hand.streets[s + 1].line,
+                street.street.as_str().to_lowercase(),
+                hand.streets[s + 1].street.as_str()
+            ));
+        }
+        // An unfinished street is fine only at the end of the hand
+        if let Some(&i) = to_act.front().filter(|_| !last) {
+            return Err(format!(
+                "line {}: the {} action isn't finished; {} is still to act",
+                n,
+                street.street.as_str().to_lowercase(),
+                seats[i].position
+            ));
+        }
+    }
+    Ok(decisions)
+}
+
+/// The preflop solution's spot for hero's decision, given the raises so far.
+fn preflop_spot(hero: Position, raisers: &[Position], limped: bool, callers: usize) -> Spot {
+    if limped {
+        return Spot::Uncovered("limped pots are not covered".to_string());
+    }
+    match raisers {
+        [] => Spot::Preflop { villain: None },
+        [opener] if callers == 0 && *opener != hero => Spot::Preflop { villain: Some(*opener) },
+        [opener, three_bettor] if callers == 0 && *opener == hero => Spot::Preflop { villain: Some(*three_bettor) },
+        [_] | [_, _] if callers > 0 => Spot::Uncovered("multiway: the raise was already called".to_string()),
+        _ => Spot::Uncovered("the preflop solution covers opens and 3-bets only".to_string()),
+    }
+}
+
+/// The heads-up postflop spot for hero's decision.
+fn postflop_spot(hero: Position, seats: &[Seat], in_hand: &[usize], raisers: &[Position], steps: &[String]) -> Spot {
+    if in_hand.len() > 2 {
+        return Spot::Uncovered(format!("multiway pot ({} players)", in_hand.len()));
+    }
+    let pot_type = match raisers.len() {
+        0 => return Spot::Uncovered("limped pots are not covered".to_string()),
+        1 => PotType::Srp,
+        2 => PotType::ThreeBet,
+        _ => PotType::FourBet,
+    };
+    let villain = in_hand.iter().map(|&i| seats[i].position).find(|&p| p != hero).unwrap_or(hero);
+    Spot::Postflop { villain, pot_type, line: steps.to_vec() }
+}
+
+// ---------------------------------------------------------------------------
+// Analysis
+// ---------------------------------------------------------------------------
+
+/// The solver's view of one hero decision.
+pub struct DecisionAnalysis {
+    pub decision: Decision,
+    /// The strategy for hero's hand, or why there is none.
+    pub result: Result<StrategyResult, String>,
+    /// Index into the result's actions of the one hero took.
+    pub chosen: Option<usize>,
+    /// Hero's EV given up against the best action, in bb, when the solution
+    /// stores per-action EVs.
+    pub ev_loss: Option<f64>,
+}
+
+/// Look up every hero decision in `hand`. Postflop spots missing from the
+/// cache are solved with `iterations`, as `gto query` does.
+pub fn analyze_hand(
+    hand: &HandHistory,
+    engine: &mut StrategyEngine,
+    iterations: usize,
+) -> Result<Vec<DecisionAnalysis>, String> {
+    let decisions = replay(hand)?;
+    let mut analyses = Vec::new();
+    for decision in decisions {
+        let result = match &decision.spot {
+            Spot::Uncovered(why) => Err(why.clone()),
+            Spot::Preflop { .. } if !engine.has_preflop() => Err(format!(
+                "no preflop solution at {}bb; run `gto solve preflop --stack {}`",
+                hand.effective_stack(),
+                hand.effective_stack()
+            )),
+            Spot::Preflop { villain } => engine
+                .query_preflop(&hand_to_canonical(&hand.hole_cards), hand.hero, *villain)
+                .ok_or_else(|| "spot not found in the preflop solution".to_string()),
+            Spot::Postflop { villain, pot_type, line } => engine.query_postflop(
+                &hand.hole_cards,
+                &PostflopSpot::new(hand.hero, *villain, &decision.board, decision.pot, decision.stack)
+                    .with_pot_type(*pot_type)
+                    .with_line(line),
+                iterations,
+            ),
+        };
+        let result = result.and_then(|r| match r.source {
+            StrategySource::NotInRange => Err(format!("{} is not in hero's range here", hand.hole_cards)),
+            _ => Ok(r),
+        });
+        let chosen = result.as_ref().ok().and_then(|r| match_action(&r.actions, decision.action, decision.chips));
+        let ev_loss = match (&result, chosen) {
+            (Ok(StrategyResult { evs: Some(evs), .. }), Some(c)) => {
+                let best = evs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                Some((best - evs[c]) * decision.pot)
+            }
+            _ => None,
+        };
+        analyses.push(DecisionAnalysis { decision, result, chosen, ev_loss });
+    }
+    Ok(analyses)
+}
+
+/// The solver action matching what hero did: by kind, and for bets and
+/// raises the size nearest the chips hero put in. Turn and river templates
+/// embedded in a flop solve label sizes in starting pots rather than bb, so
+/// there the nearest size can be off.
+fn match_action(actions: &[String], action: HandAction, chips: f64) -> Option<usize> {
+    let lower: Vec<String> = actions.iter().map(|a| a.to_lowercase()).collect();
+    let kind = |prefixes: &[&str]| -> Vec<usize> {
+        (0..lower.len()).filter(|&a| prefixes.iter().any(|p| lower[a].starts_with(p))).collect()
+    };
+    match action {
+        HandAction::Fold => kind(&["fold"]).first().copied(),
+        HandAction::Check => kind(&["check"]).first().copied(),
+        HandAction::Call => kind(&["call"]).first().copied(),
+        HandAction::Bet(_) | HandAction::Raise(_) => {
+            let sized = kind(&["bet", "raise", "3-bet", "4-bet", "all"]);
+            let amount = |a: usize| -> Option<f64> {
+                lower[a].split_whitespace().last()?.trim_end_matches("bb").parse::<f64>().ok()
+            };
+            sized.iter().copied().min_by(|&x, &y| {
+                let dist = |a: usize| amount(a).map_or(f64::INFINITY, |v| (v - chips).abs());
+                dist(x).total_cmp(&dist(y))
+            })
+        }
+    }
+}
+
+/// `gto analyze`: parse `file` and print each hand's decisions against the
+/// solver, one table per street.
+pub fn run_analyze(file: &str, iterations: usize, force: bool) -> Result<(), String> {
+    use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+    let text = std::fs::read_to_string(file).map_err(|e| crate::status::io_error(format!("Cannot read {}: {}", file, e)))?;
+    let hands = parse_hand_histories(&text)?;
+    for hand in &hands {
+        let stack = hand.effective_stack();
+        let mut engine = StrategyEngine::new(stack).with_force(force);
+        let analyses = analyze_hand(hand, &mut engine, iterations)?;
+
+        println!();
+        println!(
+            "  {}  Hand {}  |  Hero {} {}  |  {}bb effective",
+            "GTO".bold(),
+            hand.id.bold(),
+            hand.hero,
+            pretty_hand(&hand.hole_cards).bold(),
+            stack
+        );
+        if analyses.is_empty() {
+            println!("  {}", "hero makes no decisions in this hand".dimmed());
+        }
+
+        for street in &hand.streets {
+            let rows: Vec<&DecisionAnalysis> =
+                analyses.iter().filter(|a| a.decision.street == street.street).collect();
+            let Some(first) = rows.first() else {
+                continue;
+            };
+            println!();
+            let mut header = street.street.as_str().bold().to_string();
+            if street.street != Street::Preflop {
+                header = format!("{}  {}", header, pretty_board(&first.decision.board));
+            }
+            println!("  {}  |  pot {}bb", header, first.decision.pot);
+
+            let mut table = Table::new();
+            table.set_content_arrangement(ContentArrangement::Dynamic);
+            table.set_header(vec!["Facing", "Hero", "Solver", "Freq", "EV loss"]);
+            let right = |s: String| Cell::new(s).set_alignment(CellAlignment::Right);
+            for a in rows {
+                let facing: Vec<String> =
+                    a.decision.facing.iter().map(|p| format!("{} {}", p.position, p.action)).collect();
+                let facing = if facing.is_empty() { "-".to_string() } else { facing.join(", ") };
+                let (solver, freq) = match &a.result {
+                    Ok(r) => {
+                        let parts: Vec<String> = r
+                            .actions
+                            .iter()
+                            .zip(&r.frequencies)
+                            .filter(|(_, f)| **f > 0.01)
+                            .map(|(action, f)| format!("{} {:.0}%", action, f * 100.0))
+                            .collect();
+                        let freq = a.chosen.map_or("-".to_string(), |c| format!("{:.0}%", r.frequencies[c] * 100.0));
+                        (parts.join(" · "), freq)
+                    }
+                    Err(why) => (why.clone(), "-".to_string()),
+                };
+                let ev_loss = a.ev_loss.map_or("-".to_string(), |l| format!("{:.2}bb", l));
+                table.add_row(vec![
+                    Cell::new(facing),
+                    Cell::new(a.decision.action.to_string()),
+                    Cell::new(solver),
+                    right(freq),
+                    right(ev_loss),
+                ]);
+            }
+            for line in table.to_string().lines() {
+                println!("  {}", line);
+            }
+        }
+    }
+    println!();
+    println!(
+        "  {}",
+        "Freq is how often the solver takes hero's action; EV loss is against the best action (flop solves only).".dimmed()
+    );
+    println!();
+    Ok(())
+}
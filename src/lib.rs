@@ -1,25 +1,103 @@
+//! GTO poker solver: preflop ranges, equity, and CFR solvers for flop, turn
+//! and river spots.
+//!
+//! The items re-exported here are the library API; the modules behind them
+//! are public too, but shaped around the `gto` CLI.
+//!
+//! Equity of a hand against a range:
+//!
+//! ```
+//! use gto_cli::{equity_vs_range, parse_board, parse_range};
+//!
+//! let hand = parse_board("AhKh")?;
+//! let board = parse_board("Kd9s4c")?;
+//! let villain = parse_range("QQ+,AQs+")?;
+//! let result = equity_vs_range(&hand, &villain, Some(&board), 1_000)?;
+//! assert!(result.equity() > 0.0 && result.equity() < 1.0);
+//! # Ok::<(), gto_cli::GtoError>(())
+//! ```
+//!
+//! Solving a spot (see `builder` for the defaults):
+//!
+//! ```no_run
+//! use gto_cli::{solve_flop, FlopSolverConfig};
+//!
+//! let config = FlopSolverConfig::builder()
+//!     .board("Ks9d4c")
+//!     .oop("TT+,AQs+")
+//!     .ip("88+,AJs+,KQs")
+//!     .pot(6.0)
+//!     .stack(97.0)
+//!     .build()?;
+//! let solution = solve_flop(&config);
+//! solution.display();
+//! # Ok::<(), gto_cli::SolverError>(())
+//! ```
+
+pub mod baseline;
 pub mod batch;
+pub mod bench;
+pub mod breakdown;
+pub mod browse;
+pub mod cache;
+pub mod cache_index;
+pub mod cache_key;
 pub mod card_encoding;
 pub mod cards;
 pub mod cfr;
 pub mod flat_cfr;
+pub mod config;
 pub mod display;
 pub mod equity;
 pub mod error;
+pub mod features;
 pub mod flop_enumerator;
 pub mod game_tree;
 pub mod hand_evaluator;
+pub mod hand_history;
+pub mod limits;
 pub mod lookup_eval;
 pub mod math_engine;
+pub mod notes;
 pub mod multiway;
+pub mod multiway_solver;
 pub mod play;
 pub mod postflop;
 pub mod postflop_tree;
+pub mod postprocess;
+pub mod prefetch;
 pub mod preflop;
+pub mod preflop_json;
 pub mod preflop_solver;
+pub mod progress;
+pub mod quality;
+pub mod range_diff;
+pub mod range_summary;
 pub mod ranges;
 pub mod bucketing;
+pub mod builder;
 pub mod flop_solver;
 pub mod river_solver;
+pub mod rng;
+pub mod runout_table;
+pub mod size_compare;
+pub mod solution_json;
+pub mod status;
 pub mod strategy;
+pub mod strategy_csv;
+pub mod sweep;
+pub mod texture_compare;
+pub mod texture_report;
+pub mod training_export;
+pub mod tree_nav;
 pub mod turn_solver;
+
+pub use builder::{FlopSolverConfigBuilder, RiverSolverConfigBuilder, SpotBuilder, TurnSolverConfigBuilder};
+pub use cards::{parse_board, parse_card, Card};
+pub use equity::{equity_vs_hand, equity_vs_range, EquityResult};
+pub use error::{GtoError, GtoResult, SolverError, SolverResult};
+pub use flop_solver::{solve_flop, FlopSolution, FlopSolverConfig};
+pub use hand_evaluator::{evaluate_hand, HandCategory, HandResult};
+pub use ranges::parse_range;
+pub use river_solver::{expand_range_to_combos, solve_river, try_solve_river, Combo, RiverSolution, RiverSolverConfig};
+pub use turn_solver::{solve_turn, try_solve_turn, TurnSolution, TurnSolverConfig};
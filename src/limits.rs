@@ -0,0 +1,93 @@
+//! Bounds on solver iterations and equity simulations.
+//!
+//! Below `min` a solver's average strategy is still close to uniform, so the
+//! floor doubles as a quality mark: cached solutions with fewer iterations are
+//! not served to `gto query` (see each solution's `meets_quality_floor`).
+//! `max` keeps runs to something that finishes. `--force` lifts both, but never
+//! allows 0 or more than `FORCE_HEADROOM` times `max`; the solver configs
+//! enforce that much on their own (`check_config`).
+//!
+//! | Setting                 | min    | max         |
+//! |-------------------------|--------|-------------|
+//! | push/fold iterations    | 1,000  | 10,000,000  |
+//! | preflop iterations      | 1,000  | 5,000,000   |
+//! | river iterations        | 1,000  | 10,000,000  |
+//! | turn iterations         | 1,000  | 5,000,000   |
+//! | flop (MCCFR) iterations | 10,000 | 50,000,000  |
+//! | equity simulations      | 100    | 100,000,000 |
+
+use crate::error::{SolverError, SolverResult};
+
+/// How far past `max` `--force` can go: further than that a run won't finish.
+pub const FORCE_HEADROOM: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    /// What is being bounded, as it reads in a message ("River iterations").
+    pub name: &'static str,
+    pub min: usize,
+    pub max: usize,
+}
+
+pub const PUSHFOLD_ITERATIONS: Bounds = Bounds { name: "Push/fold iterations", min: 1_000, max: 10_000_000 };
+pub const PREFLOP_ITERATIONS: Bounds = Bounds { name: "Preflop iterations", min: 1_000, max: 5_000_000 };
+pub const RIVER_ITERATIONS: Bounds = Bounds { name: "River iterations", min: 1_000, max: 10_000_000 };
+pub const TURN_ITERATIONS: Bounds = Bounds { name: "Turn iterations", min: 1_000, max: 5_000_000 };
+pub const FLOP_ITERATIONS: Bounds = Bounds { name: "Flop iterations", min: 10_000, max: 50_000_000 };
+pub const EQUITY_SIMS: Bounds = Bounds { name: "Simulations", min: 100, max: 100_000_000 };
+
+impl Bounds {
+    /// Check `value`; with `force` only 0 and more than `hard_max` are
+    /// rejected.
+    pub fn check(&self, value: usize, force: bool) -> Result<(), String> {
+        if value == 0 {
+            return Err(format!("{} must be positive", self.name));
+        }
+        if value > self.hard_max() {
+            return Err(format!(
+                "{} must be at most {} even with --force (got {})",
+                self.name,
+                self.hard_max(),
+                value
+            ));
+        }
+        if force {
+            return Ok(());
+        }
+        if value < self.min {
+            return Err(format!(
+                "{} must be at least {} (got {}); pass --force to run anyway",
+                self.name, self.min, value
+            ));
+        }
+        if value > self.max {
+            return Err(format!(
+                "{} must be at most {} (got {}); pass --force to run anyway",
+                self.name, self.max, value
+            ));
+        }
+        Ok(())
+    }
+
+    /// The most iterations `--force` allows.
+    pub fn hard_max(&self) -> usize {
+        self.max.saturating_mul(FORCE_HEADROOM)
+    }
+
+    /// A solver config's iteration count: what `check` allows with `force`.
+    pub fn check_config(&self, iterations: usize) -> SolverResult<()> {
+        let reason = if iterations == 0 {
+            "must be positive".to_string()
+        } else if iterations > self.hard_max() {
+            format!("{} is more than the {} a run can finish", iterations, self.hard_max())
+        } else {
+            return Ok(());
+        };
+        Err(SolverError::InvalidConfig { field: "iterations", reason })
+    }
+
+    /// True when a result from `value` iterations is good enough to serve.
+    pub fn meets_floor(&self, value: usize) -> bool {
+        value >= self.min
+    }
+}
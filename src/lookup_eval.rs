@@ -91,37 +91,53 @@ fn top_n_from_mask(mask: u16, n: usize) -> [u8; 5] {
 pub fn evaluate_fast(cards: &[u8]) -> u32 {
     debug_assert!(cards.len() >= 5 && cards.len() <= 7);
 
-    let mut rank_counts = [0u8; 13]; // index 0=Two .. 12=Ace
-    let mut suit_masks = [0u16; 4];  // 13-bit rank mask per suit
-    let mut suit_counts = [0u8; 4];
-
+    let mut tally = Tally::default();
     for &c in cards {
+        tally.add(c);
+    }
+    tally.score()
+}
+
+/// Rank and suit counts of a set of cards: all the evaluator needs.
+#[derive(Clone, Copy, Default)]
+struct Tally {
+    rank_counts: [u8; 13], // index 0=Two .. 12=Ace
+    suit_masks: [u16; 4],  // 13-bit rank mask per suit
+    suit_counts: [u8; 4],
+}
+
+impl Tally {
+    #[inline]
+    fn add(&mut self, c: u8) {
         let rank = (c >> 2) as usize;   // c / 4
         let suit = (c & 0x3) as usize;  // c % 4
-        rank_counts[rank] += 1;
-        suit_masks[suit] |= 1 << rank;
-        suit_counts[suit] += 1;
-    }
-
-    // --- Flush path (5+ cards of one suit) ---
-    // If a flush exists, it always beats any non-flush hand that can
-    // coexist in the same 7 cards (quads/full-house can't coexist with
-    // a flush due to pigeonhole on suits).
-    if let Some(suit) = suit_counts.iter().position(|&c| c >= 5) {
-        let fmask = suit_masks[suit];
-        let sf_high = STRAIGHT_TABLE[fmask as usize];
-        if sf_high > 0 {
-            if sf_high == 14 {
-                return hand_score(9, &[14]); // Royal flush
+        self.rank_counts[rank] += 1;
+        self.suit_masks[suit] |= 1 << rank;
+        self.suit_counts[suit] += 1;
+    }
+
+    #[inline]
+    fn score(&self) -> u32 {
+        // --- Flush path (5+ cards of one suit) ---
+        // If a flush exists, it always beats any non-flush hand that can
+        // coexist in the same 7 cards (quads/full-house can't coexist with
+        // a flush due to pigeonhole on suits).
+        if let Some(suit) = self.suit_counts.iter().position(|&c| c >= 5) {
+            let fmask = self.suit_masks[suit];
+            let sf_high = STRAIGHT_TABLE[fmask as usize];
+            if sf_high > 0 {
+                if sf_high == 14 {
+                    return hand_score(9, &[14]); // Royal flush
+                }
+                return hand_score(8, &[sf_high]); // Straight flush
             }
-            return hand_score(8, &[sf_high]); // Straight flush
+            let ranks = top_n_from_mask(fmask, 5);
+            return hand_score(5, &ranks); // Flush
         }
-        let ranks = top_n_from_mask(fmask, 5);
-        return hand_score(5, &ranks); // Flush
-    }
 
-    // --- Non-flush path ---
-    evaluate_non_flush(&rank_counts)
+        // --- Non-flush path ---
+        evaluate_non_flush(&self.rank_counts)
+    }
 }
 
 /// Evaluate the best 5-card non-flush hand from rank frequency counts.
@@ -194,6 +210,31 @@ fn evaluate_non_flush(rc: &[u8; 13]) -> u32 {
     hand_score(0, &[sing[0], sing[1], sing[2], sing[3], sing[4]])
 }
 
+// -------------------------------------------------------------------------
+// Batch evaluation — many hole-card pairs on one board
+// -------------------------------------------------------------------------
+
+/// Score every `(c0, c1)` hole-card pair in `combos` on the 5-card `board`
+/// into `out` (same order; `out` must be at least as long as `combos`).
+///
+/// Same scores as `evaluate_fast` on the 7 cards, but the board's rank and
+/// suit tallies are built once: each combo only adds its two hole cards to
+/// a copy of them.
+pub fn evaluate_many(combos: &[(u8, u8)], board: &[u8; 5], out: &mut [u32]) {
+    debug_assert!(out.len() >= combos.len());
+
+    let mut board_tally = Tally::default();
+    for &c in board {
+        board_tally.add(c);
+    }
+    for (score, &(c0, c1)) in out.iter_mut().zip(combos) {
+        let mut tally = board_tally;
+        tally.add(c0);
+        tally.add(c1);
+        *score = tally.score();
+    }
+}
+
 // -------------------------------------------------------------------------
 // Score → HandCategory (for display code)
 // -------------------------------------------------------------------------
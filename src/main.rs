@@ -1,28 +1,59 @@
+mod baseline;
 mod batch;
+mod bench;
+mod breakdown;
+mod browse;
 mod bucketing;
+mod cache;
+mod cache_index;
+mod cache_key;
 mod card_encoding;
 mod cards;
 mod cfr;
 mod cli;
+mod config;
 mod display;
 mod equity;
 mod error;
+mod features;
 mod flat_cfr;
 mod flop_enumerator;
 mod flop_solver;
 mod game_tree;
 mod hand_evaluator;
+mod hand_history;
+mod limits;
 mod lookup_eval;
 mod math_engine;
+mod notes;
 mod multiway;
+mod multiway_solver;
 mod play;
 mod postflop;
 mod postflop_tree;
+mod postprocess;
+mod prefetch;
 mod preflop;
+mod preflop_json;
 mod preflop_solver;
+mod progress;
+mod quality;
+mod range_diff;
+mod range_summary;
 mod ranges;
 mod river_solver;
+mod rng;
+mod runout_table;
+mod size_compare;
+mod solution_json;
+mod status;
 mod strategy;
+mod strategy_csv;
+mod sweep;
+mod texture_compare;
+mod texture_report;
+mod training_export;
+mod tree_nav;
 mod turn_solver;
 
 fn main() {
@@ -31,30 +62,51 @@ fn main() {
     cli::run_with_args(args);
 }
 
-/// Detect shorthand: `gto AhKs BTN Ks9d4c` → `gto query AhKs BTN Ks9d4c`
-/// A hand looks like 4 chars where chars 1,3 are valid ranks and chars 2,4 are valid suits.
+/// Detect shorthand: `gto AhKs BTN Ks9d4c` → `gto query AhKs BTN Ks9d4c`.
+/// The hand is two cards in any case ("ahks") or a hand class ("QQ", "aks"),
+/// which is passed on canonically for `query` to average over its combos.
 fn preprocess_args(args: Vec<String>) -> Vec<String> {
-    if args.len() >= 3 && looks_like_hand(&args[1]) {
-        let mut new_args = vec![args[0].clone(), "query".to_string()];
-        new_args.extend_from_slice(&args[1..]);
-        new_args
-    } else {
-        args
+    match args.get(1).filter(|_| args.len() >= 3).and_then(|a| shorthand_hand(a)) {
+        Some(hand) => {
+            let mut new_args = vec![args[0].clone(), "query".to_string(), hand];
+            new_args.extend_from_slice(&args[2..]);
+            new_args
+        }
+        None => args,
     }
 }
 
-fn looks_like_hand(s: &str) -> bool {
-    if s.len() != 4 {
-        return false;
+/// `s` as `query` takes it, if it's a hand.
+fn shorthand_hand(s: &str) -> Option<String> {
+    // Also accepts rendered hands pasted back in, e.g. "A♥K♠"
+    if cards::parse_board(s).is_ok_and(|c| c.len() == 2) {
+        return Some(s.to_string());
     }
-    let chars: Vec<char> = s.chars().collect();
-    is_rank(chars[0]) && is_suit(chars[1]) && is_rank(chars[2]) && is_suit(chars[3])
+    cards::canonical_hand_class(s)
 }
 
-fn is_rank(c: char) -> bool {
-    matches!(c, '2'..='9' | 'T' | 'J' | 'Q' | 'K' | 'A')
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
 
-fn is_suit(c: char) -> bool {
-    matches!(c, 's' | 'h' | 'd' | 'c')
+    #[test]
+    fn test_preprocess_routes_hands_to_query() {
+        assert_eq!(preprocess_args(args("gto AhKs BTN Ks9d4c")), args("gto query AhKs BTN Ks9d4c"));
+        assert_eq!(preprocess_args(args("gto ahks BTN")), args("gto query ahks BTN"));
+        assert_eq!(preprocess_args(args("gto A♥K♠ BTN")), args("gto query A♥K♠ BTN"));
+        assert_eq!(preprocess_args(args("gto AKs BTN")), args("gto query AKs BTN"));
+        assert_eq!(preprocess_args(args("gto qq BTN Ks9d4c")), args("gto query QQ BTN Ks9d4c"));
+        assert_eq!(preprocess_args(args("gto 9to CO --vs BB")), args("gto query T9o CO --vs BB"));
+    }
+
+    #[test]
+    fn test_preprocess_leaves_commands_alone() {
+        for line in ["gto range BTN", "gto Range BTN", "gto spr 10 100", "gto odds 10 5", "gto AKs", "gto AK BTN"] {
+            assert_eq!(preprocess_args(args(line)), args(line), "{}", line);
+        }
+    }
 }
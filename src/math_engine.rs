@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::error::{GtoError, GtoResult};
+use crate::postflop::geometric_fraction;
 
 pub fn pot_odds(pot: f64, bet: f64) -> GtoResult<f64> {
     if pot <= 0.0 || bet <= 0.0 {
@@ -18,6 +19,66 @@ pub fn implied_odds(pot: f64, bet: f64, expected_future: f64) -> GtoResult<f64>
     Ok(bet / (pot + bet + bet + expected_future))
 }
 
+/// An equity typed as a fraction ("0.35") or a percentage ("35", "35%"):
+/// values above 1 are percentages.
+pub fn parse_equity(s: &str) -> GtoResult<f64> {
+    let invalid = || GtoError::InvalidValue(format!("Invalid equity '{}': expected e.g. 0.35, 35 or 35%", s));
+    let trimmed = s.trim();
+    let (number, percent) = match trimmed.strip_suffix('%') {
+        Some(n) => (n.trim(), true),
+        None => (trimmed, false),
+    };
+    let value: f64 = number.parse().map_err(|_| invalid())?;
+    let equity = if percent || value > 1.0 { value / 100.0 } else { value };
+    if !(0.0..=1.0).contains(&equity) {
+        return Err(invalid());
+    }
+    Ok(equity)
+}
+
+/// A draw by its usual number of outs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawType {
+    Flush,
+    OpenEnded,
+    Gutshot,
+    /// Flush draw plus open-ended straight draw.
+    Combo,
+    Overcards,
+}
+
+impl DrawType {
+    pub fn outs(self) -> u32 {
+        match self {
+            DrawType::Flush => 9,
+            DrawType::OpenEnded => 8,
+            DrawType::Gutshot => 4,
+            DrawType::Combo => 15,
+            DrawType::Overcards => 6,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DrawType::Flush => "flush draw",
+            DrawType::OpenEnded => "open-ended straight draw",
+            DrawType::Gutshot => "gutshot",
+            DrawType::Combo => "combo draw",
+            DrawType::Overcards => "two overcards",
+        }
+    }
+}
+
+/// Chance the next card is one of `outs` with `board_cards` (3 or 4) out:
+/// the equity a draw calls with when facing another bet on the next street.
+pub fn next_card_equity(outs: u32, board_cards: usize) -> GtoResult<f64> {
+    if !(3..=4).contains(&board_cards) {
+        return Err(GtoError::InvalidValue("Draws need a flop or turn: there's a card to come".to_string()));
+    }
+    let unseen = 52 - 2 - board_cards as u32;
+    Ok(outs.min(unseen) as f64 / unseen as f64)
+}
+
 pub fn reverse_implied_odds(pot: f64, bet: f64, risk: f64) -> GtoResult<f64> {
     if bet <= 0.0 {
         return Err(GtoError::InvalidValue("Bet must be positive".to_string()));
@@ -37,6 +98,119 @@ pub fn mdf(bet_size: f64, pot_size: f64) -> GtoResult<f64> {
     Ok(pot_size / (pot_size + bet_size))
 }
 
+/// What the original bettor needs to continue after being raised.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaiseDefense {
+    /// Additional chips needed to call the raise.
+    pub to_call: f64,
+    /// Required equity to call: to_call / final pot after calling.
+    pub pot_odds: f64,
+    /// Share of the betting range that must continue so a raise of any two
+    /// cards doesn't profit immediately.
+    pub mdf: f64,
+}
+
+/// Defense requirements for a bettor facing a raise.
+///
+/// `pot` is the pot before the bet, `bet` what the bettor put in, and `raise`
+/// the raiser's total for the street (raise-to). The raiser risks `raise` to win
+/// `pot + bet`, so the bettor's chips already in count toward what the raiser
+/// wins, not toward what the bettor still risks.
+pub fn mdf_vs_raise(pot: f64, bet: f64, raise: f64) -> GtoResult<RaiseDefense> {
+    if pot <= 0.0 || bet <= 0.0 {
+        return Err(GtoError::InvalidValue(
+            "Pot and bet must be positive".to_string(),
+        ));
+    }
+    if raise <= bet {
+        return Err(GtoError::InvalidValue(
+            "Raise must be larger than the bet".to_string(),
+        ));
+    }
+    let to_call = raise - bet;
+    Ok(RaiseDefense {
+        to_call,
+        pot_odds: to_call / (pot + bet + raise + to_call),
+        mdf: (pot + bet) / (pot + bet + raise),
+    })
+}
+
+/// Smallest bet a geometric plan may make: one big blind.
+pub const MIN_BET_BB: f64 = 1.0;
+
+/// One street of a `GeometricPlan`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeometricStreet {
+    /// Pot before the bet.
+    pub pot: f64,
+    pub bet: f64,
+    /// Bettor's stack behind after the bet.
+    pub stack_after: f64,
+    /// Share of villain's range that must continue: pot / (pot + bet).
+    pub mdf: f64,
+    /// Folds a pure bluff needs to break even on the street: bet / (pot + bet).
+    pub break_even_fold: f64,
+}
+
+/// Equal pot-fraction bets that put the stack in by the last street.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeometricPlan {
+    /// Every street's bet as a fraction of the pot.
+    pub fraction: f64,
+    pub streets: Vec<GeometricStreet>,
+    /// Share of villain's starting range that must fold on some street when
+    /// it defends exactly its MDF on each: 1 - the product of the MDFs.
+    pub cumulative_fold: f64,
+    /// Chips the bettor risks over the plan: the whole stack.
+    pub total_risk: f64,
+}
+
+/// Geometric sizing: the pot fraction (see `postflop::geometric_fraction`)
+/// that, bet and called on each of `streets` streets, gets `stack` all in.
+/// Amounts are in big blinds; a stack too shallow for a first bet of
+/// `MIN_BET_BB` is an error (plan fewer streets).
+pub fn geometric_sizing(pot: f64, stack: f64, streets: usize) -> GtoResult<GeometricPlan> {
+    if pot <= 0.0 || stack <= 0.0 {
+        return Err(GtoError::InvalidValue("Pot and stack must be positive".to_string()));
+    }
+    if !(1..=3).contains(&streets) {
+        return Err(GtoError::InvalidValue(format!(
+            "Streets must be 1 to 3 (flop, turn, river), got {}",
+            streets
+        )));
+    }
+    let fraction = geometric_fraction(stack / pot, streets as u32);
+    if fraction * pot < MIN_BET_BB {
+        return Err(GtoError::InvalidValue(format!(
+            "A {}bb stack is too shallow for {} streets into a {}bb pot: the first bet would be {:.2}bb, under the \
+             {}bb minimum; plan fewer streets",
+            stack,
+            streets,
+            pot,
+            fraction * pot,
+            MIN_BET_BB
+        )));
+    }
+
+    let mut plan = Vec::with_capacity(streets);
+    let (mut street_pot, mut behind) = (pot, stack);
+    for street in 0..streets {
+        // The last bet is whatever is left, so rounding can't strand chips
+        let bet = if street + 1 == streets { behind } else { fraction * street_pot };
+        behind -= bet;
+        plan.push(GeometricStreet {
+            pot: street_pot,
+            bet,
+            stack_after: behind,
+            mdf: street_pot / (street_pot + bet),
+            break_even_fold: bet / (street_pot + bet),
+        });
+        street_pot += 2.0 * bet;
+    }
+    let cumulative_fold = 1.0 - plan.iter().map(|s| s.mdf).product::<f64>();
+    Ok(GeometricPlan { fraction, streets: plan, cumulative_fold, total_risk: stack })
+}
+
 pub fn fold_equity(fold_pct: f64, pot: f64, bet: f64) -> f64 {
     fold_pct * pot - (1.0 - fold_pct) * bet
 }
@@ -0,0 +1,409 @@
+//! Three-handed river solver.
+//!
+//! Solves 3-way river spots (OOP, MP and IP, all with the same stack) on the
+//! tree from `build_multiway_tree`. Each seat has its own `FlatCfr`; an
+//! iteration visits every combo of each seat in turn and samples one
+//! non-conflicting hand for each opponent, so it costs about as much as a
+//! heads-up iteration.
+//!
+//! With more than two players CFR has no equilibrium guarantee: the average
+//! strategy is an approximation and no exploitability is measured.
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::card_encoding::card_to_index;
+use crate::cards::parse_board;
+use crate::flat_cfr::FlatCfr;
+use crate::lookup_eval::evaluate_fast;
+use crate::postflop_tree::{build_multiway_tree, MultiwayNode, TreeConfig, MULTIWAY_SEATS};
+use crate::ranges::{format_weighted_range, parse_range_weights};
+use crate::river_solver::{expand_weighted_range_to_combos, Combo};
+
+/// Attempts at dealing an opponent a hand that doesn't collide with the
+/// cards already dealt before the deal is skipped.
+const MAX_DEAL_ATTEMPTS: usize = 50;
+
+/// Configuration for a three-handed river solve. Per-seat arrays are in
+/// `MULTIWAY_SEATS` order.
+#[derive(Debug, Clone)]
+pub struct MultiwaySolverConfig {
+    pub board: Vec<u8>,
+    pub ranges: [Vec<String>; 3],
+    /// Frequency (0 to 1) of each hand in the matching range.
+    pub weights: [Vec<f64>; 3],
+    pub starting_pot: f64,
+    pub effective_stack: f64,
+    pub iterations: usize,
+    pub bet_sizes: Vec<f64>,
+    pub raise_sizes: Vec<f64>,
+    pub max_raises: usize,
+    /// Seed for sampling opponents' hands.
+    pub seed: u64,
+}
+
+impl MultiwaySolverConfig {
+    pub fn new(
+        board_str: &str,
+        ranges: [&str; 3],
+        starting_pot: f64,
+        effective_stack: f64,
+        iterations: usize,
+    ) -> Result<Self, String> {
+        let board_cards = parse_board(board_str).map_err(|e| e.to_string())?;
+        if board_cards.len() != 5 {
+            return Err("River board must have exactly 5 cards".to_string());
+        }
+        let board: Vec<u8> = board_cards.iter().map(card_to_index).collect();
+
+        let mut parsed: [(Vec<String>, Vec<f64>); 3] = Default::default();
+        for (seat, range) in ranges.iter().enumerate() {
+            parsed[seat] = parse_range_weights(range).map_err(|e| e.to_string())?;
+            if parsed[seat].0.is_empty() {
+                return Err(format!("{} range is empty", MULTIWAY_SEATS[seat]));
+            }
+        }
+        let [(r0, w0), (r1, w1), (r2, w2)] = parsed;
+
+        Ok(MultiwaySolverConfig {
+            board,
+            ranges: [r0, r1, r2],
+            weights: [w0, w1, w2],
+            starting_pot,
+            effective_stack,
+            iterations,
+            // Fewer sizes than heads-up: the tree grows with every extra player
+            bet_sizes: vec![0.33, 0.75],
+            raise_sizes: vec![1.0],
+            max_raises: 1,
+            seed: 0,
+        })
+    }
+
+    pub fn tree_config(&self) -> TreeConfig {
+        TreeConfig {
+            bet_sizes: self.bet_sizes.clone(),
+            raise_sizes: self.raise_sizes.clone(),
+            max_raises: self.max_raises,
+            starting_pot: self.starting_pot,
+            effective_stack: self.effective_stack,
+            add_allin: true,
+        }
+    }
+}
+
+/// Average strategy at one seat's action node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiwayNodeStrategy {
+    /// "OOP", "MP" or "IP".
+    pub seat: String,
+    pub node_id: u16,
+    /// Actions leading here from the root, e.g. ["Check", "Bet 3.3"].
+    pub line: Vec<String>,
+    pub actions: Vec<String>,
+    /// Per-combo frequencies, indexed like the seat's entry in `combos`.
+    pub frequencies: Vec<Vec<f64>>,
+}
+
+/// Result of a three-handed river solve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiwaySolution {
+    pub board: String,
+    pub ranges: [Vec<String>; 3],
+    pub weights: [Vec<f64>; 3],
+    pub combos: [Vec<String>; 3],
+    pub starting_pot: f64,
+    pub effective_stack: f64,
+    pub iterations: usize,
+    /// Every action node, in tree order.
+    pub strategies: Vec<MultiwayNodeStrategy>,
+}
+
+/// Per-seat hands and showdown scores, in `MULTIWAY_SEATS` order.
+struct Seats {
+    combos: [Vec<Combo>; 3],
+    scores: [Vec<u32>; 3],
+}
+
+/// Solve a three-handed river spot with CFR+ (an approximation; see module docs).
+pub fn solve_river_multiway(config: &MultiwaySolverConfig) -> MultiwaySolution {
+    let (tree, node_counts) = build_multiway_tree(&config.tree_config());
+
+    let mut combos: [Vec<Combo>; 3] = Default::default();
+    let mut reach: [Vec<f64>; 3] = Default::default();
+    for seat in 0..3 {
+        (combos[seat], reach[seat]) =
+            expand_weighted_range_to_combos(&config.ranges[seat], &config.weights[seat], &config.board);
+    }
+    let b = &config.board;
+    let scores = combos
+        .clone()
+        .map(|cs| cs.iter().map(|c| evaluate_fast(&[c.0, c.1, b[0], b[1], b[2], b[3], b[4]])).collect());
+    let seats = Seats { combos, scores };
+
+    let mut shapes: [Vec<u8>; 3] = node_counts.map(|n| vec![0; n as usize]);
+    collect_shapes(&tree, &mut shapes);
+    let mut cfrs: Vec<FlatCfr> = (0..3)
+        .map(|seat| {
+            let hands = seats.combos[seat].len() as u16;
+            FlatCfr::new(&shapes[seat].iter().map(|&na| (na, hands)).collect::<Vec<_>>())
+        })
+        .collect();
+
+    // A seat with no combos left on this board (or only zero weights) can't be dealt
+    let samplers: Option<Vec<WeightedIndex<f64>>> =
+        reach.iter().map(|w| WeightedIndex::new(w).ok()).collect();
+    if let Some(samplers) = samplers {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        for iter in 0..config.iterations {
+            for (traverser, weights) in reach.iter().enumerate() {
+                for (hand, &weight) in weights.iter().enumerate() {
+                    if weight <= 0.0 {
+                        continue;
+                    }
+                    if let Some(deal) = deal_opponents(traverser, hand, &seats.combos, &samplers, &mut rng) {
+                        traverse(&tree, traverser, &deal, [1.0; 3], &seats, &mut cfrs, iter);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut strategies = Vec::new();
+    extract_strategies(&tree, &mut Vec::new(), &cfrs, &seats.combos, &mut strategies);
+
+    MultiwaySolution {
+        board: b.iter().map(|&c| crate::card_encoding::index_to_card(c).to_string()).collect(),
+        ranges: config.ranges.clone(),
+        weights: config.weights.clone(),
+        combos: seats.combos.map(|cs| cs.iter().map(|c| c.to_string()).collect()),
+        starting_pot: config.starting_pot,
+        effective_stack: config.effective_stack,
+        iterations: config.iterations,
+        strategies,
+    }
+}
+
+/// Number of actions at each seat's nodes, indexed by node id.
+fn collect_shapes(node: &MultiwayNode, shapes: &mut [Vec<u8>; 3]) {
+    if let MultiwayNode::Action { node_id, seat, actions, children, .. } = node {
+        shapes[*seat][*node_id as usize] = actions.len() as u8;
+        for child in children {
+            collect_shapes(child, shapes);
+        }
+    }
+}
+
+/// Deal each opponent of `traverser` a hand from their range that doesn't
+/// share a card with the hands already dealt. `None` if that keeps failing
+/// (heavily blocked ranges).
+fn deal_opponents(
+    traverser: usize,
+    hand: usize,
+    combos: &[Vec<Combo>; 3],
+    samplers: &[WeightedIndex<f64>],
+    rng: &mut StdRng,
+) -> Option<[usize; 3]> {
+    let mut deal = [0usize; 3];
+    deal[traverser] = hand;
+    let mut dealt: Vec<Combo> = vec![combos[traverser][hand]];
+    for seat in (0..3).filter(|&s| s != traverser) {
+        let pick = (0..MAX_DEAL_ATTEMPTS)
+            .map(|_| samplers[seat].sample(rng))
+            .find(|&i| dealt.iter().all(|d| !conflicts(*d, combos[seat][i])))?;
+        deal[seat] = pick;
+        dealt.push(combos[seat][pick]);
+    }
+    Some(deal)
+}
+
+fn conflicts(a: Combo, b: Combo) -> bool {
+    a.0 == b.0 || a.0 == b.1 || a.1 == b.0 || a.1 == b.1
+}
+
+/// Chips `seat` wins (or loses) at a terminal: its share of the pot if it
+/// holds the best hand among the players still in, less what it put in.
+fn payoff(seat: usize, active: &[bool; 3], invested: &[f64; 3], pot: f64, deal: &[usize; 3], seats: &Seats) -> f64 {
+    if !active[seat] {
+        return -invested[seat];
+    }
+    let score = |s: usize| seats.scores[s][deal[s]];
+    let best = (0..3).filter(|&s| active[s]).map(score).max().unwrap_or(0);
+    if score(seat) < best {
+        return -invested[seat];
+    }
+    let winners = (0..3).filter(|&s| active[s] && score(s) == best).count();
+    pot / winners as f64 - invested[seat]
+}
+
+/// One CFR pass for `traverser` with everyone's hand fixed by `deal`.
+/// Returns the traverser's value at `node`; `reach` is each seat's
+/// probability of playing to it.
+fn traverse(
+    node: &MultiwayNode,
+    traverser: usize,
+    deal: &[usize; 3],
+    reach: [f64; 3],
+    seats: &Seats,
+    cfrs: &mut [FlatCfr],
+    iter: usize,
+) -> f64 {
+    match node {
+        MultiwayNode::Terminal { active, invested, pot } => payoff(traverser, active, invested, *pot, deal, seats),
+        MultiwayNode::Action { node_id, seat, children, .. } => {
+            let (node, hand) = (*node_id as usize, deal[*seat]);
+            let mut sigma = vec![0.0f32; children.len()];
+            cfrs[*seat].current_strategy(node, hand, &mut sigma);
+
+            if *seat != traverser {
+                let mut value = 0.0;
+                for (child, &p) in children.iter().zip(&sigma) {
+                    if p > 0.0 {
+                        let mut next = reach;
+                        next[*seat] *= p as f64;
+                        value += p as f64 * traverse(child, traverser, deal, next, seats, cfrs, iter);
+                    }
+                }
+                return value;
+            }
+
+            let mut values = vec![0.0f64; children.len()];
+            for (a, child) in children.iter().enumerate() {
+                let mut next = reach;
+                next[traverser] *= sigma[a] as f64;
+                values[a] = traverse(child, traverser, deal, next, seats, cfrs, iter);
+            }
+            let value: f64 = values.iter().zip(&sigma).map(|(v, &p)| v * p as f64).sum();
+
+            // Regrets count in proportion to how often the opponents get here
+            let opp_reach: f64 = (0..3).filter(|&s| s != traverser).map(|s| reach[s]).product();
+            let action_values: Vec<f32> = values.iter().map(|v| (v * opp_reach) as f32).collect();
+            cfrs[traverser].update(node, hand, &action_values, (value * opp_reach) as f32, reach[traverser] as f32, iter);
+            value
+        }
+    }
+}
+
+fn extract_strategies(
+    node: &MultiwayNode,
+    line: &mut Vec<String>,
+    cfrs: &[FlatCfr],
+    combos: &[Vec<Combo>; 3],
+    out: &mut Vec<MultiwayNodeStrategy>,
+) {
+    let MultiwayNode::Action { node_id, seat, actions, children, .. } = node else {
+        return;
+    };
+    let mut buf = vec![0.0f32; actions.len()];
+    let frequencies = (0..combos[*seat].len())
+        .map(|h| {
+            cfrs[*seat].average_strategy(*node_id as usize, h, &mut buf);
+            buf.iter().map(|&f| f as f64).collect()
+        })
+        .collect();
+    out.push(MultiwayNodeStrategy {
+        seat: MULTIWAY_SEATS[*seat].to_string(),
+        node_id: *node_id,
+        line: line.clone(),
+        actions: actions.iter().map(|a| a.label()).collect(),
+        frequencies,
+    });
+    for (action, child) in actions.iter().zip(children) {
+        line.push(action.label());
+        extract_strategies(child, line, cfrs, combos, out);
+        line.pop();
+    }
+}
+
+impl MultiwaySolution {
+    /// Strategy at the node `line` (action labels from the root) leads to.
+    pub fn strategy_after(&self, line: &[&str]) -> Option<&MultiwayNodeStrategy> {
+        self.strategies.iter().find(|s| s.line.iter().map(String::as_str).eq(line.iter().copied()))
+    }
+
+    /// Each action's frequency over the whole range of the seat acting at
+    /// `strategy` (combos are weighted equally).
+    pub fn range_mix(&self, strategy: &MultiwayNodeStrategy) -> Vec<f64> {
+        let n = strategy.frequencies.len().max(1) as f64;
+        (0..strategy.actions.len())
+            .map(|a| strategy.frequencies.iter().map(|f| f[a]).sum::<f64>() / n)
+            .collect()
+    }
+
+    pub fn display(&self) {
+        use colored::Colorize;
+
+        println!();
+        println!(
+            "  {} 3-Way River Solution  |  Board: {}  |  Pot: {:.0}  |  Stack: {:.0}  |  {} iterations",
+            "GTO".bold(),
+            self.board,
+            self.starting_pot,
+            self.effective_stack,
+            self.iterations,
+        );
+        println!(
+            "  {}",
+            "CFR approximation: no equilibrium guarantee with three players, and exploitability isn't measured"
+                .yellow()
+        );
+        for (seat, label) in MULTIWAY_SEATS.iter().enumerate() {
+            println!(
+                "  {} range: {} ({} combos)",
+                label,
+                format_weighted_range(&self.ranges[seat], &self.weights[seat]),
+                self.combos[seat].len(),
+            );
+        }
+
+        // Each seat's first decision when checked to
+        let mut line: Vec<&str> = Vec::new();
+        for seat in 0..3 {
+            let Some(strat) = self.strategy_after(&line) else {
+                break;
+            };
+            let at = if line.is_empty() {
+                "at root".to_string()
+            } else {
+                format!("after [{}]", line.join(", "))
+            };
+            println!();
+            println!("  {} {} (node {}):", strat.seat.bold(), at, strat.node_id);
+            println!("  Range: {}", format_frequencies(&strat.actions, &self.range_mix(strat)));
+
+            let num_to_show = strat.frequencies.len().min(10);
+            for (combo, freqs) in self.combos[seat].iter().zip(&strat.frequencies).take(num_to_show) {
+                println!("    {}  {}", combo.bold(), format_frequencies(&strat.actions, freqs));
+            }
+            if strat.frequencies.len() > num_to_show {
+                println!("    ... and {} more combos", strat.frequencies.len() - num_to_show);
+            }
+            line.push("Check");
+        }
+        println!();
+        println!("  {}", "3-way solves aren't cached, so `gto query` can't look them up.".dimmed());
+        println!();
+    }
+}
+
+fn format_frequencies(actions: &[String], freqs: &[f64]) -> String {
+    use colored::Colorize;
+
+    actions
+        .iter()
+        .zip(freqs)
+        .map(|(a, f)| {
+            let pct = (f * 100.0).round() as u32;
+            if pct > 70 {
+                format!("{}:{}", a, format!("{}%", pct).green())
+            } else if pct > 30 {
+                format!("{}:{}", a, format!("{}%", pct).yellow())
+            } else {
+                format!("{}:{}%", a, pct)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
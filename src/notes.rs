@@ -0,0 +1,381 @@
+//! Study notes attached to spots.
+//!
+//! A spot is written `pot:matchup:stack:board[:line]`, e.g.
+//! `srp:BTNvsBB:100bb:Ks9d4c` or `srp:BTNvsBB:100bb:Ks9d4c:x-b75`. Notes are
+//! stored in `~/.gto-cli/notes.json`, keyed by the canonical descriptor: pot
+//! type lowercased, seats in table order, stack in bb, and the board in the
+//! suit-isomorphic form from `flop_enumerator::canonical_board`, so
+//! `Kh9s4c` and `Ks9d4c` share notes.
+//!
+//! A lookup without a line shows every note for the spot; a lookup with a
+//! line shows the spot-wide notes plus those for that line.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::normalize_cards;
+use crate::flop_enumerator::canonical_board;
+use crate::preflop_solver::Position;
+use crate::status::io_error;
+use crate::strategy::PotType;
+
+pub const NOTES_FILE: &str = "notes.json";
+
+const POT_TYPES: [PotType; 3] = [PotType::Srp, PotType::ThreeBet, PotType::FourBet];
+
+/// "srp", "3bp" or "4bp", any case.
+pub fn parse_pot_type(s: &str) -> Option<PotType> {
+    POT_TYPES.into_iter().find(|p| p.as_str().eq_ignore_ascii_case(s.trim()))
+}
+
+/// A parsed spot descriptor in canonical form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpotDescriptor {
+    /// "srp", "3bp" or "4bp".
+    pub pot_type: String,
+    /// Seats in table order, e.g. "BTNvsBB".
+    pub matchup: String,
+    /// Starting stack, e.g. "100bb".
+    pub stack: String,
+    /// Suit-canonical board.
+    pub board: String,
+    /// Action line after the board, if any.
+    pub line: Option<String>,
+}
+
+impl SpotDescriptor {
+    pub fn parse(descriptor: &str) -> Result<SpotDescriptor, String> {
+        let parts: Vec<&str> = descriptor.trim().splitn(5, ':').map(str::trim).collect();
+        if parts.len() < 4 {
+            return Err(format!(
+                "Spot '{}' must look like pot:matchup:stack:board[:line], e.g. srp:BTNvsBB:100bb:Ks9d4c",
+                descriptor
+            ));
+        }
+
+        let pot_type = parse_pot_type(parts[0])
+            .ok_or_else(|| format!("Unknown pot type '{}'. Valid: srp, 3bp, 4bp", parts[0]))?;
+
+        let upper = parts[1].to_uppercase();
+        let (a, b) = upper
+            .split_once("VS")
+            .ok_or_else(|| format!("Matchup '{}' must look like BTNvsBB", parts[1]))?;
+        let seat = |s: &str| Position::from_str(s).ok_or_else(|| format!("Invalid position '{}' in matchup", s));
+        let (a, b) = (seat(a)?, seat(b)?);
+
+        let stack: f64 = parts[2]
+            .to_lowercase()
+            .trim_end_matches("bb")
+            .parse()
+            .map_err(|_| format!("Stack '{}' must look like 100bb", parts[2]))?;
+        if stack <= 0.0 {
+            return Err(format!("Stack '{}' must be positive", parts[2]));
+        }
+
+        let board = normalize_cards(parts[3]).unwrap_or_else(|_| parts[3].to_string());
+        let line = parts.get(4).map(|l| l.to_lowercase()).filter(|l| !l.is_empty());
+
+        SpotDescriptor::new(pot_type, a, b, stack, &board, line)
+    }
+
+    /// Descriptor for a spot seen in `gto query` or `gto play`.
+    pub fn new(
+        pot_type: PotType,
+        a: Position,
+        b: Position,
+        stack: f64,
+        board: &str,
+        line: Option<String>,
+    ) -> Result<SpotDescriptor, String> {
+        let (first, second) = if (a as u8) <= (b as u8) { (a, b) } else { (b, a) };
+        Ok(SpotDescriptor {
+            pot_type: pot_type.as_str().to_lowercase(),
+            matchup: format!("{}vs{}", first.as_str(), second.as_str()),
+            stack: format!("{}bb", stack),
+            board: canonical_board(board)?,
+            line,
+        })
+    }
+
+    /// (opener, responder): the matchup's seats in table order.
+    pub fn seats(&self) -> (Position, Position) {
+        let (a, b) = self.matchup.split_once("vs").unwrap_or_default();
+        (
+            Position::from_str(a).unwrap_or(Position::BTN),
+            Position::from_str(b).unwrap_or(Position::BB),
+        )
+    }
+
+    pub fn pot_kind(&self) -> PotType {
+        parse_pot_type(&self.pot_type).unwrap_or(PotType::Srp)
+    }
+
+    /// Starting stack in bb.
+    pub fn stack_bb(&self) -> f64 {
+        self.stack.trim_end_matches("bb").parse().unwrap_or(100.0)
+    }
+
+    /// The descriptor without its line.
+    pub fn spot_key(&self) -> String {
+        format!("{}:{}:{}:{}", self.pot_type, self.matchup, self.stack, self.board)
+    }
+
+    /// The full canonical descriptor, used as the notes-file key.
+    pub fn key(&self) -> String {
+        match &self.line {
+            Some(line) => format!("{}:{}", self.spot_key(), line),
+            None => self.spot_key(),
+        }
+    }
+
+    /// True when a note filed under `note_spot` should show for this lookup.
+    fn shows(&self, note_spot: &SpotDescriptor) -> bool {
+        note_spot.spot_key() == self.spot_key()
+            && match (&self.line, &note_spot.line) {
+                (Some(line), Some(note_line)) => line == note_line,
+                _ => self.line.is_none() || note_spot.line.is_none(),
+            }
+    }
+}
+
+impl std::fmt::Display for SpotDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub text: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Seconds since the epoch.
+    pub added: u64,
+}
+
+impl Note {
+    fn matches_query(&self, query: &str) -> bool {
+        self.text.to_lowercase().contains(query) || self.tags.iter().any(|t| t.to_lowercase().contains(query))
+    }
+}
+
+/// All notes, keyed by canonical descriptor.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteStore {
+    pub notes: BTreeMap<String, Vec<Note>>,
+}
+
+impl NoteStore {
+    /// Load the store at `path`; a missing file is an empty store.
+    pub fn load_from(path: &Path) -> Result<NoteStore, String> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Cannot parse {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(NoteStore::default()),
+            Err(e) => Err(format!("Cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    pub fn add(&mut self, spot: &SpotDescriptor, text: &str, tags: Vec<String>) {
+        let added = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.notes.entry(spot.key()).or_default().push(Note {
+            text: text.trim().to_string(),
+            tags: tags.into_iter().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()).collect(),
+            added,
+        });
+    }
+
+    /// Every note with its descriptor, in key order, filtered by tag.
+    pub fn list(&self, tag: Option<&str>) -> Vec<(&str, &Note)> {
+        let tag = tag.map(|t| t.trim().to_lowercase());
+        self.entries()
+            .filter(|(_, n)| tag.as_ref().is_none_or(|t| n.tags.contains(t)))
+            .collect()
+    }
+
+    /// Notes whose text, tags or descriptor contain `query` (case-insensitive).
+    pub fn search(&self, query: &str) -> Vec<(&str, &Note)> {
+        let query = query.trim().to_lowercase();
+        self.entries()
+            .filter(|(key, n)| key.to_lowercase().contains(&query) || n.matches_query(&query))
+            .collect()
+    }
+
+    /// Notes to show for `spot` (see the module docs for line matching).
+    pub fn matching(&self, spot: &SpotDescriptor) -> Vec<(&str, &Note)> {
+        self.entries()
+            .filter(|(key, _)| SpotDescriptor::parse(key).is_ok_and(|s| spot.shows(&s)))
+            .collect()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&str, &Note)> {
+        self.notes
+            .iter()
+            .flat_map(|(key, notes)| notes.iter().map(move |n| (key.as_str(), n)))
+    }
+}
+
+/// Descriptor for a query or play spot: the standard pot type whose pot is
+/// `pot`, or `None` for a custom pot no descriptor can name.
+pub fn spot_for(
+    pot: f64,
+    hero: Position,
+    villain: Position,
+    stack: f64,
+    board: &str,
+) -> Option<SpotDescriptor> {
+    let pot_type = POT_TYPES.iter().find(|p| (p.pot_and_stack().0 - pot).abs() < 1e-9)?;
+    SpotDescriptor::new(*pot_type, hero, villain, stack, board, None).ok()
+}
+
+/// `~/.gto-cli/notes.json`.
+pub fn notes_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".gto-cli").join(NOTES_FILE)
+}
+
+/// One line per note: descriptor, tags, text.
+pub fn format_note(key: &str, note: &Note) -> String {
+    let tags = if note.tags.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", note.tags.join(", "))
+    };
+    format!("{}{}  {}", key.dimmed(), tags.cyan(), note.text)
+}
+
+/// Notes for `spot` from the user's notes file, formatted for display above
+/// query and play output. Empty when there are none or the file is unreadable.
+pub fn notes_banner(spot: &SpotDescriptor) -> Vec<String> {
+    let Ok(store) = NoteStore::load_from(&notes_path()) else {
+        return vec![];
+    };
+    store
+        .matching(spot)
+        .into_iter()
+        .map(|(key, note)| format!("{} {}", "NOTE".magenta().bold(), format_note(key, note)))
+        .collect()
+}
+
+/// `gto note add`.
+pub fn run_note_add(spot: &str, text: &str, tags: Vec<String>) -> Result<(), String> {
+    let spot = SpotDescriptor::parse(spot)?;
+    if text.trim().is_empty() {
+        return Err("Note text is empty".to_string());
+    }
+    let path = notes_path();
+    let mut store = NoteStore::load_from(&path).map_err(io_error)?;
+    store.add(&spot, text, tags);
+    store.save_to(&path).map_err(io_error)?;
+    println!("  Saved note for {}", spot.key().bold());
+    Ok(())
+}
+
+/// `gto note list`.
+pub fn run_note_list(spot: Option<&str>, tag: Option<&str>) -> Result<(), String> {
+    let store = NoteStore::load_from(&notes_path()).map_err(io_error)?;
+    let notes = match spot {
+        Some(s) => {
+            let spot = SpotDescriptor::parse(s)?;
+            store
+                .matching(&spot)
+                .into_iter()
+                .filter(|(_, n)| tag.is_none_or(|t| n.tags.contains(&t.trim().to_lowercase())))
+                .collect()
+        }
+        None => store.list(tag),
+    };
+    print_notes(&notes, "notes");
+    Ok(())
+}
+
+/// `gto note search`.
+pub fn run_note_search(query: &str) -> Result<(), String> {
+    let store = NoteStore::load_from(&notes_path()).map_err(io_error)?;
+    print_notes(&store.search(query), &format!("notes matching \"{}\"", query));
+    Ok(())
+}
+
+fn print_notes(notes: &[(&str, &Note)], what: &str) {
+    println!();
+    println!("  {} {} {}", "GTO".bold(), notes.len().to_string().bold(), what);
+    if !notes.is_empty() {
+        println!();
+    }
+    for (key, note) in notes {
+        println!("  {}", format_note(key, note));
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_canonicalizes_isomorphic_spots() {
+        let a = SpotDescriptor::parse("srp:BTNvsBB:100bb:Ks9d4c").unwrap();
+        let b = SpotDescriptor::parse("SRP:bbvsbtn:100:Kh9s4d").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.key(), "srp:BTNvsBB:100bb:Ks9h4d");
+        let line = SpotDescriptor::parse("srp:BTNvsBB:100bb:Ks9d4c:X-B75").unwrap();
+        assert_eq!(line.key(), "srp:BTNvsBB:100bb:Ks9h4d:x-b75");
+        assert_eq!(line.spot_key(), a.spot_key());
+
+        assert!(SpotDescriptor::parse("srp:BTNvsBB:100bb").is_err());
+        assert!(SpotDescriptor::parse("limp:BTNvsBB:100bb:Ks9d4c").is_err());
+        assert!(SpotDescriptor::parse("srp:BTNvsXX:100bb:Ks9d4c").is_err());
+    }
+
+    #[test]
+    fn test_matching_ignores_line_unless_specified() {
+        let mut store = NoteStore::default();
+        let spot = SpotDescriptor::parse("srp:BTNvsBB:100bb:Ks9d4c").unwrap();
+        let bet_line = SpotDescriptor::parse("srp:BTNvsBB:100bb:Ks9d4c:x-b75").unwrap();
+        store.add(&spot, "Range bet small", vec![]);
+        store.add(&bet_line, "BB overfolds here vs 75%", vec!["Exploit".to_string()]);
+        store.add(&SpotDescriptor::parse("srp:COvsBB:100bb:Ks9d4c").unwrap(), "Other seats", vec![]);
+
+        // An isomorphic board without a line sees both notes for the spot
+        let iso = SpotDescriptor::parse("srp:BTNvsBB:100bb:Kd9h4s").unwrap();
+        assert_eq!(store.matching(&iso).len(), 2);
+        // A different line sees only the spot-wide note
+        let check_line = SpotDescriptor::parse("srp:BTNvsBB:100bb:Kd9h4s:x-x").unwrap();
+        let notes = store.matching(&check_line);
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].1.text, "Range bet small");
+        assert_eq!(store.matching(&bet_line).len(), 2);
+
+        assert_eq!(store.list(Some("exploit")).len(), 1);
+        assert_eq!(store.list(None).len(), 3);
+        assert_eq!(store.search("OVERFOLD").len(), 1);
+        assert_eq!(store.search("covsbb").len(), 1);
+    }
+
+    #[test]
+    fn test_store_round_trips_through_json() {
+        let dir = std::env::temp_dir().join(format!("gto-notes-{}", std::process::id()));
+        let path = dir.join(NOTES_FILE);
+        assert_eq!(NoteStore::load_from(&path).unwrap(), NoteStore::default());
+
+        let mut store = NoteStore::default();
+        store.add(&SpotDescriptor::parse("3bp:SBvsBB:100bb:AhKd2c").unwrap(), "Check range", vec!["oop".into()]);
+        store.save_to(&path).unwrap();
+        assert_eq!(NoteStore::load_from(&path).unwrap(), store);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
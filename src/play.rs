@@ -1,7 +1,10 @@
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
 use crate::cards::{parse_board, parse_card, simplify_hand, Card};
 use crate::display::{board_display, equity_bar, styled_action};
@@ -9,14 +12,16 @@ use crate::equity::equity_vs_range;
 use crate::hand_evaluator::{evaluate_hand, HandCategory, HandResult};
 use crate::math_engine::{break_even_pct, spr as calc_spr, SprZone};
 use crate::multiway::multiway_range_adjustment;
-use crate::postflop::{analyze_board, street_strategy, Wetness};
+use crate::postflop::{analyze_board, street_strategy_sized, Wetness};
 use crate::preflop::{
     get_rfi_pct, get_rfi_range, preflop_action, positions_for,
 };
 use crate::preflop_solver::Position;
+use crate::progress::Spinner;
 use crate::ranges::{blockers_remove, range_from_top_pct, HAND_RANKING};
 use crate::strategy::{
-    default_villain, detect_street, format_strategy, StrategyEngine, StrategySource,
+    default_villain, detect_street, format_evs, format_recommendation, format_strategy, PostflopSpot, PotType,
+    StrategyEngine, StrategyResult, StrategySource,
 };
 
 // ---------------------------------------------------------------------------
@@ -411,25 +416,40 @@ pub fn play_command() {
     let stdout = io::stdout();
     let mut reader = stdin.lock();
     let mut writer = stdout.lock();
-    run_interactive_session(&mut reader, &mut writer);
+    run_interactive_session(&mut reader, &mut writer, Some(&sessions_dir()));
 }
 
-pub fn run_interactive_session(reader: &mut dyn BufRead, writer: &mut dyn Write) {
+/// Play hands until the user quits, appending each completed hand to the
+/// day's log in `log_dir` (see `session_log_path`) when given.
+pub fn run_interactive_session(reader: &mut dyn BufRead, writer: &mut dyn Write, log_dir: Option<&Path>) {
     writeln!(writer).ok();
     writeln!(writer, "{}", "GTO Play \u{2014} solver-backed interactive advisor".cyan().bold()).ok();
-    writeln!(writer, "Type {} at any prompt to quit. Defaults: 6max, 100bb, heads-up SRP.\n", "'q'".bold()).ok();
+    writeln!(writer, "Type {} at any prompt to quit. Defaults: 6max, heads-up SRP.\n", "'q'".bold()).ok();
 
-    // Initialize strategy engine (100bb default)
-    let mut engine = StrategyEngine::new(100.0);
+    let Some((stack_bb, quick_solve)) = prompt_session_options(reader, writer) else {
+        writeln!(writer, "\n{}\n", "Good luck at the tables.".cyan().bold()).ok();
+        return;
+    };
+    let mut engine = StrategyEngine::new(stack_bb).with_cache_only(!quick_solve);
     if engine.has_preflop() {
         writeln!(writer, "  {} Preflop solver loaded", "\u{2713}".green()).ok();
     } else {
         writeln!(writer, "  {} No preflop solution \u{2014} run `gto solve preflop` for solver-backed advice", "\u{2717}".yellow()).ok();
     }
 
+    let mut log_failed = false;
     loop {
         match play_one_hand(&mut engine, reader, writer) {
-            Ok(()) => {}
+            Ok(hand) => {
+                let logged = log_dir.map(|dir| append_hand(&session_log_path(dir, &utc_date(hand.timestamp)), &hand));
+                // One warning is enough; play goes on without the log
+                if let Some(Err(e)) = logged {
+                    if !log_failed {
+                        writeln!(writer, "  {}", format!("Hand not logged: {}", e).yellow()).ok();
+                        log_failed = true;
+                    }
+                }
+            }
             Err(QuitSession) => {
                 writeln!(writer, "\n{}\n", "Good luck at the tables.".cyan().bold()).ok();
                 return;
@@ -446,11 +466,27 @@ pub fn run_interactive_session(reader: &mut dyn BufRead, writer: &mut dyn Write)
     }
 }
 
+/// Stack depth and whether to quick-solve cache misses; `None` to quit.
+fn prompt_session_options(reader: &mut dyn BufRead, writer: &mut dyn Write) -> Option<(f64, bool)> {
+    let stack_bb = loop {
+        let answer = prompt("  Stack depth (bb)", Some("100"), reader, writer);
+        if answer.to_lowercase() == "q" {
+            return None;
+        }
+        match answer.trim_end_matches("bb").parse::<f64>() {
+            Ok(stack) if stack > 3.0 => break stack,
+            _ => writeln!(writer, "  {}", "Invalid. Enter a stack in big blinds, e.g. 100".red()).ok(),
+        };
+    };
+    let quick_solve = prompt_yn("  Quick-solve spots missing from the cache?", "n", reader, writer)?;
+    Some((stack_bb, quick_solve))
+}
+
 fn play_one_hand(
     engine: &mut StrategyEngine,
     reader: &mut dyn BufRead,
     writer: &mut dyn Write,
-) -> Result<(), QuitSession> {
+) -> Result<HandRecord, QuitSession> {
     let table_size = "6max";
     let bb_amount = 1.0; // Use bb as unit
     let valid_positions = positions_for(table_size);
@@ -488,23 +524,44 @@ fn play_one_hand(
     let hero = Position::from_str(&hero_pos).unwrap_or(Position::BTN);
     let villain = default_villain(hero);
     let villain_pos_str = villain.as_str().to_string();
+    let mut record = HandRecord {
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        stack_bb: engine.stack_bb,
+        hero: hero_pos.clone(),
+        villain: villain_pos_str.clone(),
+        hole_cards: hand_str.clone(),
+        board: String::new(),
+        pot_type: PotType::Srp.as_str().to_string(),
+        decisions: Vec::new(),
+    };
 
     // -- Preflop advice --
     writeln!(writer, "\n{}", "--- Preflop ---".cyan().bold()).ok();
 
-    let has_solver_preflop = show_preflop_advice(
+    let advice = show_preflop_advice(
         engine, &hand_name, &hand_str, &hero_pos, hero, table_size, writer,
     );
 
-    // Check if we should fold
-    if should_fold_preflop(engine, &hand_name, hero) {
+    // Check if we should fold; play doesn't ask, so the log has the fold or
+    // open the hand goes on with
+    let folds = should_fold_preflop(engine, &hand_name, hero);
+    record.decisions.push(DecisionRecord {
+        street: "preflop".to_string(),
+        board: String::new(),
+        pot: 1.5,
+        stack: engine.stack_bb,
+        advice,
+        action: if folds { "Fold" } else { "Raise" }.to_string(),
+        amount: None,
+    });
+    if folds {
         writeln!(writer, "\n  {}", "Hand over \u{2014} fold preflop.".dimmed()).ok();
-        return Ok(());
+        return Ok(record);
     }
 
     // -- Pot tracking (in bb) --
     let mut pot = 6.0; // SRP default: 2.5bb open + BB call + 1.5bb blinds
-    let mut remaining_stack = 97.0; // 100bb - 3bb invested
+    let mut remaining_stack = engine.stack_bb - 3.0; // 3bb invested
 
     let hero_ip = hero.is_ip_vs(&villain);
     let ip_label = if hero_ip { "IP" } else { "OOP" };
@@ -530,7 +587,7 @@ fn play_one_hand(
             if input.is_empty() {
                 // Skip to end
                 writeln!(writer, "\n  {}", format!("Hand ended before the {}.", street_name.to_lowercase()).dimmed()).ok();
-                return Ok(());
+                return Ok(record);
             }
             if let Some(cards) = parse_board_input(&input) {
                 if cards.len() != num_cards {
@@ -548,13 +605,19 @@ fn play_one_hand(
         };
         board.extend(&new_cards);
         board_str = board.iter().map(|c| format!("{}", c)).collect();
+        record.board = board_str.clone();
 
         // Show solver-backed advice or fall back to heuristics
         writeln!(writer, "\n{}", format!("--- {} ---", capitalize(street_name)).cyan().bold()).ok();
         writeln!(writer, "  Board: {}  |  {}  |  Pot: {:.0}bb  |  Stack: {:.0}bb",
             board_display(&board), ip_label, pot, remaining_stack).ok();
+        if let Some(spot) = crate::notes::spot_for(6.0, hero, villain, engine.stack_bb, &board_str) {
+            for line in crate::notes::notes_banner(&spot) {
+                writeln!(writer, "  {}", line).ok();
+            }
+        }
 
-        show_street_advice(
+        let advice = show_street_advice(
             engine,
             &hand_str,
             hero,
@@ -574,16 +637,25 @@ fn play_one_hand(
 
         // Ask what happened and update pot
         match update_pot_after_action(pot, remaining_stack, reader, writer) {
-            Some((new_pot, new_stack)) => {
-                pot = new_pot;
-                remaining_stack = new_stack;
+            Some(played) => {
+                record.decisions.push(DecisionRecord {
+                    street: street_name.to_lowercase(),
+                    board: board_str.clone(),
+                    pot,
+                    stack: remaining_stack,
+                    advice,
+                    action: played.action,
+                    amount: played.amount,
+                });
+                pot = played.pot;
+                remaining_stack = played.stack;
             }
             None => return Err(QuitSession),
         }
     }
 
     writeln!(writer, "\n{}", "--- Hand Complete ---".cyan().bold()).ok();
-    Ok(())
+    Ok(record)
 }
 
 // ---------------------------------------------------------------------------
@@ -591,7 +663,7 @@ fn play_one_hand(
 // ---------------------------------------------------------------------------
 
 /// Show preflop advice using solver if available, falling back to heuristics.
-/// Returns true if solver was used.
+/// Returns the advice shown.
 fn show_preflop_advice(
     engine: &StrategyEngine,
     hand_name: &str,
@@ -600,12 +672,12 @@ fn show_preflop_advice(
     hero: Position,
     table_size: &str,
     writer: &mut dyn Write,
-) -> bool {
+) -> Advice {
     // Try solver first
     if engine.has_preflop() {
         if let Some(result) = engine.query_preflop(hand_name, hero, None) {
             writeln!(writer, "  {}", format_strategy(&result)).ok();
-            return true;
+            return Advice::from_solver(&result);
         }
     }
 
@@ -618,11 +690,11 @@ fn show_preflop_advice(
             position: hero_pos.to_string(),
         });
 
-    writeln!(writer, "  \u{2192} {}", styled_action(&pf_action.action)).ok();
+    writeln!(writer, "  \u{2192} {} {}", styled_action(&pf_action.action), "(heuristic)".dimmed()).ok();
     if !engine.has_preflop() {
         writeln!(writer, "  {}", "Tip: run `gto solve preflop` for solver-backed advice".dimmed()).ok();
     }
-    false
+    Advice::heuristic(&pf_action.action)
 }
 
 /// Check if we should fold preflop.
@@ -640,7 +712,8 @@ fn should_fold_preflop(engine: &StrategyEngine, hand_name: &str, hero: Position)
     matches!(action, Ok(ref a) if a.action == "FOLD")
 }
 
-/// Show postflop street advice. Uses solver when available, falls back to heuristics.
+/// Show postflop street advice. Uses solver when available, falls back to
+/// heuristics. Returns the advice shown.
 fn show_street_advice(
     engine: &mut StrategyEngine,
     hand_str: &str,
@@ -657,7 +730,7 @@ fn show_street_advice(
     villain_pos: &str,
     table_size: &str,
     writer: &mut dyn Write,
-) {
+) -> Advice {
     // Try solver-backed advice
     let iterations = match street {
         "flop" => 500000,
@@ -666,16 +739,26 @@ fn show_street_advice(
         _ => 10000,
     };
 
-    match engine.query_postflop(hand_str, hero, villain, board_str, pot, stack, iterations, &[]) {
+    let answer = {
+        let _spinner = (!engine.is_cache_only()).then(|| Spinner::start(&format!("Solving {} {}...", street, board_str)));
+        engine.query_postflop(hand_str, &PostflopSpot::new(hero, villain, board_str, pot, stack), iterations)
+    };
+    match answer {
         Ok(result) if result.source != StrategySource::NotInRange && !result.actions.is_empty() => {
             writeln!(writer, "  {}", format_strategy(&result)).ok();
-            return;
+            if let Some(best) = format_recommendation(&result) {
+                writeln!(writer, "  {}", best.bold()).ok();
+            }
+            if let Some(evs) = format_evs(&result) {
+                writeln!(writer, "  {}", evs.dimmed()).ok();
+            }
+            return Advice::from_solver(&result);
         }
         Ok(result) if result.source == StrategySource::NotInRange => {
             writeln!(writer, "  {} not in solver range \u{2014} using heuristic", hand_str.dimmed()).ok();
         }
-        Err(_) => {
-            // Solver failed, fall through to heuristic
+        Err(e) => {
+            writeln!(writer, "  {}", format!("{} \u{2014} using heuristic", e).dimmed()).ok();
         }
         _ => {}
     }
@@ -684,10 +767,12 @@ fn show_street_advice(
     show_heuristic_analysis(
         street, hole_cards, board, pot, stack, hero_pos,
         Some(villain_pos), ip_label, "RFI", table_size, writer,
-    );
+    )
+    .map_or_else(Advice::default, |action| Advice::heuristic(&action))
 }
 
 /// Heuristic-based postflop analysis (fallback when solver unavailable).
+/// Returns the recommended action, or `None` if the spot couldn't be analyzed.
 fn show_heuristic_analysis(
     street: &str,
     hole_cards: &[Card],
@@ -700,13 +785,13 @@ fn show_heuristic_analysis(
     situation: &str,
     table_size: &str,
     writer: &mut dyn Write,
-) {
+) -> Option<String> {
     // Board texture
     let texture = match analyze_board(board) {
         Ok(t) => t,
         Err(e) => {
             writeln!(writer, "  {}", format!("Error: {}", e).red()).ok();
-            return;
+            return None;
         }
     };
 
@@ -715,7 +800,7 @@ fn show_heuristic_analysis(
         Ok(r) => r,
         Err(e) => {
             writeln!(writer, "  {}", format!("Error: {}", e).red()).ok();
-            return;
+            return None;
         }
     };
     writeln!(writer, "  Made: {}", hand_result.category.to_string().bold()).ok();
@@ -733,22 +818,33 @@ fn show_heuristic_analysis(
 
     let strength = classify_hand_strength(&hand_result, hole_cards, board, equity);
 
-    // Strategy recommendation
-    let strat = street_strategy(strength, &texture, pot, stack, ip_label, street);
-    writeln!(writer, "  \u{2192} {} {}", styled_action(&strat.action), strat.sizing).ok();
+    // Strategy recommendation (equity vs villain's range stands in for the hand percentile)
+    let strat = street_strategy_sized(strength, &texture, pot, stack, ip_label, street, Some(equity));
+    writeln!(writer, "  \u{2192} {} {} {}", styled_action(&strat.action), strat.sizing, "(heuristic)".dimmed()).ok();
     writeln!(writer, "  {}", strat.reasoning.dimmed()).ok();
+    Some(strat.action)
 }
 
 // ---------------------------------------------------------------------------
 // Pot tracking
 // ---------------------------------------------------------------------------
 
+/// What was played at a decision and the pot and stack after it.
+struct Played {
+    pot: f64,
+    stack: f64,
+    /// The menu choice, e.g. "Bet/Raise".
+    action: String,
+    /// Chips bet or called, in bb.
+    amount: Option<f64>,
+}
+
 fn update_pot_after_action(
     pot: f64,
     stack: f64,
     reader: &mut dyn BufRead,
     writer: &mut dyn Write,
-) -> Option<(f64, f64)> {
+) -> Option<Played> {
     let action = prompt_menu(
         "What happened?",
         &["Bet/Raise", "Check", "Call", "Fold", "All-in"],
@@ -775,10 +871,11 @@ fn update_pot_after_action(
         "check"
     };
 
+    let played = |pot, stack, amount| Some(Played { pot, stack, action: action.clone(), amount });
     match action_key {
-        "check" => Some((pot, stack)),
-        "fold" => Some((pot, stack)),
-        "allin" => Some((pot + stack * 2.0, 0.0)),
+        "check" => played(pot, stack, None),
+        "fold" => played(pot, stack, None),
+        "allin" => played(pot + stack * 2.0, 0.0, Some(stack)),
         "bet" => {
             let default_bet = format!("{}", (pot * 0.5) as u64);
             let amount_str = prompt(&format!("  Bet/raise size (pot={:.0}bb)", pot), Some(&default_bet), reader, writer);
@@ -786,7 +883,7 @@ fn update_pot_after_action(
                 return None;
             }
             let amount: f64 = amount_str.parse().unwrap_or(pot * 0.5);
-            Some((pot + amount * 2.0, (stack - amount).max(0.0)))
+            played(pot + amount * 2.0, (stack - amount).max(0.0), Some(amount))
         }
         "call" => {
             let default_call = format!("{}", (pot * 0.3) as u64);
@@ -795,9 +892,9 @@ fn update_pot_after_action(
                 return None;
             }
             let amount: f64 = amount_str.parse().unwrap_or(pot * 0.3);
-            Some((pot + amount, (stack - amount).max(0.0)))
+            played(pot + amount, (stack - amount).max(0.0), Some(amount))
         }
-        _ => Some((pot, stack)),
+        _ => played(pot, stack, None),
     }
 }
 
@@ -809,6 +906,356 @@ fn capitalize(s: &str) -> String {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Session log
+// ---------------------------------------------------------------------------
+
+/// Advice shown at a decision: the solver's actions and frequencies, or the
+/// heuristic's one action at frequency 1.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Advice {
+    pub actions: Vec<String>,
+    pub frequencies: Vec<f64>,
+    /// From a solver solution rather than the heuristics.
+    pub solver: bool,
+}
+
+impl Advice {
+    fn from_solver(result: &StrategyResult) -> Self {
+        Advice { actions: result.actions.clone(), frequencies: result.frequencies.clone(), solver: true }
+    }
+
+    fn heuristic(action: &str) -> Self {
+        Advice { actions: vec![action.to_string()], frequencies: vec![1.0], solver: false }
+    }
+}
+
+/// One hero decision of a logged hand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// "preflop", "flop", "turn" or "river".
+    pub street: String,
+    /// Board at the decision, e.g. "Ks7d2c"; empty preflop.
+    pub board: String,
+    /// Pot and hero's stack before the action, in bb.
+    pub pot: f64,
+    pub stack: f64,
+    pub advice: Advice,
+    /// What was played, as chosen from the menu ("Bet/Raise", "Check", ...).
+    /// Play doesn't ask preflop, so there it's the fold or open the hand
+    /// went on with, and `gto review` leaves it out of the agreement.
+    pub action: String,
+    /// Chips bet or called, in bb.
+    pub amount: Option<f64>,
+}
+
+/// A completed `gto play` hand: one line of the session log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandRecord {
+    /// When the hand started, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub stack_bb: f64,
+    pub hero: String,
+    pub villain: String,
+    pub hole_cards: String,
+    /// Every board card dealt.
+    pub board: String,
+    /// The pot type the postflop streets were played as ("SRP", "3BP" or
+    /// "4BP", see `PotType::as_str`); hands logged before it was recorded
+    /// were all single raised pots.
+    #[serde(default = "default_pot_type")]
+    pub pot_type: String,
+    pub decisions: Vec<DecisionRecord>,
+}
+
+fn default_pot_type() -> String {
+    PotType::Srp.as_str().to_string()
+}
+
+/// Overrides the session log directory, e.g. to keep tests out of `$HOME`.
+pub const SESSIONS_DIR_ENV: &str = "GTO_SESSIONS_DIR";
+
+/// Where session logs live: `$GTO_SESSIONS_DIR` when set, otherwise
+/// `~/.gto-cli/sessions`.
+pub fn sessions_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(SESSIONS_DIR_ENV).filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".gto-cli").join("sessions")
+}
+
+/// The log in `dir` for a date like "2024-05-01".
+pub fn session_log_path(dir: &Path, date: &str) -> PathBuf {
+    dir.join(format!("{}.jsonl", date))
+}
+
+/// UTC date of a Unix timestamp, as "YYYY-MM-DD".
+pub fn utc_date(timestamp: u64) -> String {
+    // Days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = (timestamp / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Append `hand` to the log at `path` as one JSON line.
+pub fn append_hand(path: &Path, hand: &HandRecord) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    }
+    let line = serde_json::to_string(hand).map_err(|e| e.to_string())?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// The hands logged at `path`, skipping blank lines.
+pub fn load_hands(path: &Path) -> Result<Vec<HandRecord>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| format!("{} line {}: not a logged hand ({})", path.display(), i + 1, e))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Review
+// ---------------------------------------------------------------------------
+
+/// Streets in review order. Preflop isn't reviewed: play logs its own
+/// advice there, not a choice hero made.
+const REVIEW_STREETS: [&str; 3] = ["flop", "turn", "river"];
+
+/// Agreement with the solver over a group of decisions.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Agreement {
+    pub decisions: usize,
+    /// Decisions some cached solution covers.
+    pub covered: usize,
+    /// Covered decisions where the action played was the solver's most
+    /// frequent one.
+    pub agreed: usize,
+}
+
+impl Agreement {
+    /// Count one decision: `None` when no solution covers it.
+    pub fn add(&mut self, agrees: Option<bool>) {
+        self.decisions += 1;
+        if let Some(agrees) = agrees {
+            self.covered += 1;
+            self.agreed += usize::from(agrees);
+        }
+    }
+
+    pub fn merge(&mut self, other: &Agreement) {
+        self.decisions += other.decisions;
+        self.covered += other.covered;
+        self.agreed += other.agreed;
+    }
+
+    /// Share of covered decisions that agreed.
+    pub fn rate(&self) -> Option<f64> {
+        (self.covered > 0).then(|| self.agreed as f64 / self.covered as f64)
+    }
+}
+
+/// Fold, check, call, or "bet" for bets, raises and all-ins of any size.
+fn action_kind(action: &str) -> Option<&'static str> {
+    let lower = action.trim().to_lowercase();
+    if lower.starts_with("fold") {
+        Some("fold")
+    } else if lower.starts_with("check") {
+        Some("check")
+    } else if lower.starts_with("call") {
+        Some("call")
+    } else if ["bet", "raise", "3-bet", "4-bet", "all", "jam", "open"].iter().any(|p| lower.starts_with(p)) {
+        Some("bet")
+    } else {
+        None
+    }
+}
+
+/// Whether `played` is the same kind of action as the solver's `advised`.
+/// Sizes aren't compared: play's bet amounts rarely match a tree's sizes.
+pub fn same_action_kind(played: &str, advised: &str) -> bool {
+    action_kind(played).is_some_and(|kind| action_kind(advised) == Some(kind))
+}
+
+/// The solver's most frequent action and its frequency.
+fn top_action(result: &StrategyResult) -> Option<(&str, f64)> {
+    result
+        .actions
+        .iter()
+        .zip(&result.frequencies)
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(action, &f)| (action.as_str(), f))
+}
+
+/// Look a logged postflop decision up again, from the cache only, the way
+/// play queried it.
+fn requery(engine: &mut StrategyEngine, hand: &HandRecord, decision: &DecisionRecord) -> Result<StrategyResult, String> {
+    let hero = Position::from_str(&hand.hero).ok_or_else(|| format!("Unknown position {}", hand.hero))?;
+    let villain = Position::from_str(&hand.villain).ok_or_else(|| format!("Unknown position {}", hand.villain))?;
+    let pot_type = PotType::parse(&hand.pot_type).ok_or_else(|| format!("Unknown pot type {}", hand.pot_type))?;
+    let spot = PostflopSpot::new(hero, villain, &decision.board, decision.pot, decision.stack).with_pot_type(pot_type);
+    let result = engine.query_postflop(&hand.hole_cards, &spot, 0)?;
+    match result.source {
+        StrategySource::NotInRange => Err(format!("{} is not in hero's range here", hand.hole_cards)),
+        _ if result.actions.is_empty() => Err("no actions".to_string()),
+        _ => Ok(result),
+    }
+}
+
+/// How a set of logged hands compares with the solver caches.
+#[derive(Debug, Clone, Default)]
+pub struct Review {
+    /// Flop, turn and river, in that order.
+    pub by_street: [Agreement; 3],
+    /// One line per covered decision played differently from the solver.
+    pub misses: Vec<String>,
+}
+
+impl Review {
+    pub fn postflop(&self) -> Agreement {
+        let mut postflop = Agreement::default();
+        for a in &self.by_street {
+            postflop.merge(a);
+        }
+        postflop
+    }
+}
+
+/// Re-query every postflop decision in `hands` against the solver caches as
+/// they are now. Preflop decisions are skipped (see `REVIEW_STREETS`).
+pub fn review_hands(hands: &[HandRecord]) -> Review {
+    let mut review = Review::default();
+    let mut engines: Vec<StrategyEngine> = Vec::new();
+    for hand in hands {
+        let engine = match engines.iter().position(|e| e.stack_bb == hand.stack_bb) {
+            Some(i) => &mut engines[i],
+            None => {
+                engines.push(StrategyEngine::new(hand.stack_bb).with_cache_only(true));
+                engines.last_mut().unwrap()
+            }
+        };
+        for decision in &hand.decisions {
+            let Some(street) = REVIEW_STREETS.iter().position(|&s| s == decision.street) else {
+                continue;
+            };
+            let result = requery(engine, hand, decision);
+            let top = result.as_ref().ok().and_then(top_action);
+            let agrees = top.map(|(action, _)| same_action_kind(&decision.action, action));
+            review.by_street[street].add(agrees);
+            if let (Some(false), Some((action, f))) = (agrees, top) {
+                review.misses.push(format!(
+                    "{} {} {} {}: played {}, solver {} ({:.0}%)",
+                    hand.hero, hand.hole_cards, decision.street, decision.board, decision.action, action, f * 100.0
+                ));
+            }
+        }
+    }
+    review
+}
+
+/// `gto review`: re-query every decision logged on `date` (default today,
+/// UTC) against the solver caches as they are now, and summarize how often
+/// the action played was the solver's most frequent one.
+pub fn run_review(date: Option<&str>) -> Result<(), String> {
+    use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+    let today = utc_date(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+    let date = date.unwrap_or(&today);
+    let valid = date.len() == 10
+        && date.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+    if !valid {
+        return Err(format!("Invalid date '{}'. Use YYYY-MM-DD, e.g. 2024-05-01", date));
+    }
+    let path = session_log_path(&sessions_dir(), date);
+    if !path.exists() {
+        return Err(format!("No hands logged on {} ({})", date, path.display()));
+    }
+    let hands = load_hands(&path).map_err(crate::status::io_error)?;
+    let review = review_hands(&hands);
+
+    let postflop = review.postflop();
+    println!();
+    println!(
+        "  {} Review  |  {}  |  {} hands, {} postflop decisions",
+        "GTO".bold(),
+        date,
+        hands.len(),
+        postflop.decisions
+    );
+    println!();
+
+    let rows = [
+        ("Flop", review.by_street[0]),
+        ("Turn", review.by_street[1]),
+        ("River", review.by_street[2]),
+        ("Postflop", postflop),
+    ];
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("Street".bold().to_string()),
+        Cell::new("Decisions").set_alignment(CellAlignment::Right),
+        Cell::new("Solved").set_alignment(CellAlignment::Right),
+        Cell::new("Agree").set_alignment(CellAlignment::Right),
+        Cell::new("Agreement").set_alignment(CellAlignment::Right),
+    ]);
+    for (name, a) in rows {
+        let name = if name == "Postflop" { name.bold().to_string() } else { name.to_string() };
+        table.add_row(vec![
+            Cell::new(name),
+            Cell::new(a.decisions).set_alignment(CellAlignment::Right),
+            Cell::new(a.covered).set_alignment(CellAlignment::Right),
+            Cell::new(a.agreed).set_alignment(CellAlignment::Right),
+            Cell::new(a.rate().map_or("-".to_string(), |r| format!("{:.0}%", r * 100.0)))
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!("{}", table);
+    println!(
+        "  {}",
+        "Preflop isn't reviewed: play follows its own preflop advice rather than asking.".dimmed()
+    );
+
+    if !review.misses.is_empty() {
+        println!();
+        println!("  {}", "Played differently from the solver:".bold());
+        for miss in &review.misses {
+            println!("    {}", miss);
+        }
+    }
+    if postflop.covered < postflop.decisions {
+        println!();
+        println!(
+            "  {}",
+            format!(
+                "{} decisions have no cached solution and aren't in the agreement rate; solve those spots and review again.",
+                postflop.decisions - postflop.covered
+            )
+            .dimmed()
+        );
+    }
+    println!();
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1029,18 +1476,32 @@ mod tests {
         let input = b"q\n";
         let mut reader = &input[..];
         let mut output = Vec::new();
-        run_interactive_session(&mut reader, &mut output);
+        run_interactive_session(&mut reader, &mut output, None);
         let out = String::from_utf8(output).unwrap();
         assert!(out.contains("GTO Play"));
     }
 
+    #[test]
+    fn test_session_options_reprompt_on_bad_stack() {
+        let input = b"deep\n50bb\ny\n";
+        let mut reader = &input[..];
+        let mut output = Vec::new();
+        assert_eq!(prompt_session_options(&mut reader, &mut output), Some((50.0, true)));
+        assert!(String::from_utf8(output).unwrap().contains("Invalid"));
+
+        let mut reader = &b"\n\n"[..];
+        assert_eq!(prompt_session_options(&mut reader, &mut Vec::new()), Some((100.0, false)));
+        let mut reader = &b"q\n"[..];
+        assert_eq!(prompt_session_options(&mut reader, &mut Vec::new()), None);
+    }
+
     #[test]
     fn test_interactive_full_preflop_fold() {
         // UTG, 72o (should fold)
-        let input = b"7h2c\nUTG\nn\n";
+        let input = b"\nn\n7h2c\nUTG\nn\n";
         let mut reader = &input[..];
         let mut output = Vec::new();
-        run_interactive_session(&mut reader, &mut output);
+        run_interactive_session(&mut reader, &mut output, None);
         let out = String::from_utf8(output).unwrap();
         assert!(out.contains("FOLD"));
     }
@@ -49,6 +49,9 @@ pub struct BoardTexture {
     pub straight_draw_possible: bool,
     pub connectedness: Connectedness,
     pub wetness: Wetness,
+    /// Numeric wetness behind `wetness`: +3 monotone / +1 two-tone,
+    /// +2 connected / +1 semi-connected, -1 paired.
+    pub wet_score: i32,
     pub category: String,
     pub draws: Vec<String>,
 }
@@ -172,6 +175,7 @@ pub fn analyze_board(board_cards: &[Card]) -> GtoResult<BoardTexture> {
         straight_draw_possible: straight_draw,
         connectedness,
         wetness,
+        wet_score,
         category,
         draws,
     })
@@ -222,6 +226,114 @@ fn value_to_rank(value: u8) -> char {
     }
 }
 
+/// One way a turn or river card changes the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransitionKind {
+    /// Third card of a suit: a flush is now possible.
+    FlushCompleting,
+    /// Fourth card of a suit: one card of it makes a flush.
+    FourFlush,
+    /// Some two-card straight is possible that wasn't before.
+    StraightCompleting,
+    /// Matches a rank already on the board.
+    BoardPairing,
+    /// Higher than every card before it.
+    Overcard,
+    /// Second card of a suit on the turn, giving a new flush draw.
+    FlushDrawAdded,
+    /// None of the above.
+    Brick,
+}
+
+impl std::fmt::Display for TransitionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransitionKind::FlushCompleting => write!(f, "flush-completing"),
+            TransitionKind::FourFlush => write!(f, "four-flush"),
+            TransitionKind::StraightCompleting => write!(f, "straight-completing"),
+            TransitionKind::BoardPairing => write!(f, "board-pairing"),
+            TransitionKind::Overcard => write!(f, "overcard"),
+            TransitionKind::FlushDrawAdded => write!(f, "flush-draw"),
+            TransitionKind::Brick => write!(f, "brick"),
+        }
+    }
+}
+
+/// How a turn or river card changed the board: every `TransitionKind` that
+/// applies, most significant first, or just `Brick`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureTransition {
+    pub card: Card,
+    /// "turn" or "river".
+    pub street: &'static str,
+    pub kinds: Vec<TransitionKind>,
+}
+
+impl TextureTransition {
+    pub fn has(&self, kind: TransitionKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    pub fn is_brick(&self) -> bool {
+        self.has(TransitionKind::Brick)
+    }
+}
+
+impl std::fmt::Display for TextureTransition {
+    /// "board-pairing, flush-completing turn".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kinds: Vec<String> = self.kinds.iter().map(|k| k.to_string()).collect();
+        write!(f, "{} {}", kinds.join(", "), self.street)
+    }
+}
+
+/// Classify `new_card` dealt onto `prev_board` (a flop or a turn board).
+pub fn texture_transition(prev_board: &[Card], new_card: Card) -> TextureTransition {
+    let street = if prev_board.len() <= 3 { "turn" } else { "river" };
+    let suited = prev_board.iter().filter(|c| c.suit == new_card.suit).count();
+    let values: Vec<u8> = prev_board.iter().map(|c| c.value()).collect();
+    let mut with_new = values.clone();
+    with_new.push(new_card.value());
+
+    let mut kinds = Vec::new();
+    match suited {
+        2 => kinds.push(TransitionKind::FlushCompleting),
+        3 => kinds.push(TransitionKind::FourFlush),
+        _ => {}
+    }
+    if straight_possible(&with_new) && !straight_possible(&values) {
+        kinds.push(TransitionKind::StraightCompleting);
+    }
+    if values.contains(&new_card.value()) {
+        kinds.push(TransitionKind::BoardPairing);
+    }
+    if values.iter().all(|&v| new_card.value() > v) {
+        kinds.push(TransitionKind::Overcard);
+    }
+    if street == "turn" && suited == 1 {
+        kinds.push(TransitionKind::FlushDrawAdded);
+    }
+    if kinds.is_empty() {
+        kinds.push(TransitionKind::Brick);
+    }
+    TextureTransition { card: new_card, street, kinds }
+}
+
+/// The transition of each card after the flop: the turn, then the river.
+pub fn board_transitions(board: &[Card]) -> Vec<TextureTransition> {
+    (3..board.len()).map(|i| texture_transition(&board[..i], board[i])).collect()
+}
+
+/// Whether some pair of hole cards makes a straight with these board values:
+/// three distinct ranks within one five-rank window (ace plays low too).
+fn straight_possible(values: &[u8]) -> bool {
+    let mut ranks: HashSet<u8> = values.iter().copied().collect();
+    if ranks.contains(&14) {
+        ranks.insert(1);
+    }
+    (1..=10u8).any(|low| (low..low + 5).filter(|v| ranks.contains(v)).count() >= 3)
+}
+
 pub struct CBetRecommendation {
     pub should_cbet: bool,
     pub frequency: f64,
@@ -374,6 +486,85 @@ pub struct StreetStrategy {
     pub sizing: String,
     pub reasoning: String,
     pub hand_strength: String,
+    /// Concrete size from `recommend_bet_size`, when a hand percentile was given.
+    pub size: Option<BetSizeRecommendation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetSizeRecommendation {
+    /// Bet size as a fraction of the pot.
+    pub fraction: f64,
+    /// Bet size in chips/bb (fraction * pot, capped at the stack).
+    pub amount: f64,
+    pub plan: String,
+}
+
+/// Bet fraction that gets stacks in evenly over `streets` bets:
+/// pot * (1 + 2g)^streets = pot + 2 * stack  =>  g = ((1 + 2 * spr)^(1/streets) - 1) / 2
+pub fn geometric_fraction(spr_value: f64, streets: u32) -> f64 {
+    if spr_value <= 0.0 || streets == 0 {
+        return 0.0;
+    }
+    ((1.0 + 2.0 * spr_value).powf(1.0 / streets as f64) - 1.0) / 2.0
+}
+
+/// Recommend a concrete bet size from texture, SPR, street and hand percentile
+/// (0.0 = bottom of range, 1.0 = the nuts). Returns `None` when the hand should check.
+///
+/// Formula:
+/// - Hands in the 0.35-0.65 percentile band are marginal and check.
+/// - Bluffs (< 0.35) are sized like the value hands they represent (percentile 0.85).
+/// - Texture size = street base (flop 33%, turn 55%, river 66%)
+///   + 6% per wet-score point + (percentile - 0.65) * 50%.
+/// - Geometric size spreads the stack over the remaining streets.
+/// - Final size blends toward geometric with weight max(1 - SPR/6, 0) — low SPR
+///   commits — raised to at least 0.5 for near-nut hands (>= 0.9) planning
+///   to get stacks in. Clamped to 20%-150% pot.
+pub fn recommend_bet_size(
+    board_texture: &BoardTexture,
+    spr_value: f64,
+    street: &str,
+    hand_percentile: f64,
+    pot: f64,
+    stack: f64,
+) -> Option<BetSizeRecommendation> {
+    if pot <= 0.0 || stack <= 0.0 {
+        return None;
+    }
+    let pct = hand_percentile.clamp(0.0, 1.0);
+    if (0.35..0.65).contains(&pct) {
+        return None;
+    }
+    // Bluffs mirror the value region they represent
+    let sized_pct = if pct < 0.35 { 0.85 } else { pct };
+
+    let (base, streets_left) = match street {
+        "flop" => (0.33, 3),
+        "turn" => (0.55, 2),
+        _ => (0.66, 1),
+    };
+    let wet = board_texture.wet_score.clamp(0, 5) as f64;
+    let texture_frac = base + 0.06 * wet + (sized_pct - 0.65) * 0.5;
+
+    let geo = geometric_fraction(spr_value, streets_left);
+    let mut weight = (1.0 - spr_value / 6.0).clamp(0.0, 1.0);
+    if sized_pct >= 0.9 {
+        weight = weight.max(0.5);
+    }
+
+    let fraction = ((1.0 - weight) * texture_frac + weight * geo).clamp(0.2, 1.5);
+    let amount = (fraction * pot).min(stack);
+    let plan = if weight >= 0.5 {
+        format!("geometric over {} street(s) ({:.0}% pot each)", streets_left, geo * 100.0)
+    } else {
+        "texture-based single-street sizing".to_string()
+    };
+
+    Some(BetSizeRecommendation {
+        fraction,
+        amount,
+        plan,
+    })
 }
 
 pub fn street_strategy(
@@ -394,6 +585,7 @@ pub fn street_strategy(
                     sizing: "all-in or 66-100% pot".to_string(),
                     reasoning: "Low SPR with strong hand \u{2014} build pot for stacks".to_string(),
                     hand_strength: hand_strength.to_string(),
+                    size: None,
                 }
             } else {
                 let sizing = bet_sizing(board_texture, spr_val, street, true);
@@ -402,6 +594,7 @@ pub fn street_strategy(
                     sizing,
                     reasoning: "Strong hand \u{2014} value bet".to_string(),
                     hand_strength: hand_strength.to_string(),
+                    size: None,
                 }
             }
         }
@@ -417,6 +610,7 @@ pub fn street_strategy(
                 sizing,
                 reasoning: reasoning.to_string(),
                 hand_strength: hand_strength.to_string(),
+                size: None,
             }
         }
         "medium" => {
@@ -426,6 +620,7 @@ pub fn street_strategy(
                     sizing: "50% pot if betting".to_string(),
                     reasoning: "Medium hand IP \u{2014} pot control or thin value".to_string(),
                     hand_strength: hand_strength.to_string(),
+                    size: None,
                 }
             } else {
                 StreetStrategy {
@@ -433,6 +628,7 @@ pub fn street_strategy(
                     sizing: "-".to_string(),
                     reasoning: "Medium hand OOP \u{2014} pot control".to_string(),
                     hand_strength: hand_strength.to_string(),
+                    size: None,
                 }
             }
         }
@@ -444,6 +640,7 @@ pub fn street_strategy(
                     sizing,
                     reasoning: "Draw IP \u{2014} semi-bluff for fold equity + equity".to_string(),
                     hand_strength: hand_strength.to_string(),
+                    size: None,
                 }
             } else {
                 StreetStrategy {
@@ -451,6 +648,7 @@ pub fn street_strategy(
                     sizing: "-".to_string(),
                     reasoning: "Draw \u{2014} realize equity cheaply".to_string(),
                     hand_strength: hand_strength.to_string(),
+                    size: None,
                 }
             }
         }
@@ -466,6 +664,7 @@ pub fn street_strategy(
                 sizing,
                 reasoning: format!("Bluff \u{2014} need ~{:.0}% fold equity to profit", freq * 100.0),
                 hand_strength: hand_strength.to_string(),
+                size: None,
             }
         }
         _ => StreetStrategy {
@@ -473,6 +672,41 @@ pub fn street_strategy(
             sizing: "-".to_string(),
             reasoning: "Weak hand \u{2014} give up without equity".to_string(),
             hand_strength: hand_strength.to_string(),
+            size: None,
         },
     }
 }
+
+/// `street_strategy` with a concrete size from `recommend_bet_size` when the
+/// hand's percentile in range is known. Without one (or for non-betting
+/// actions) this is exactly `street_strategy`.
+pub fn street_strategy_sized(
+    hand_strength: &str,
+    board_texture: &BoardTexture,
+    pot: f64,
+    stack: f64,
+    position: &str,
+    street: &str,
+    hand_percentile: Option<f64>,
+) -> StreetStrategy {
+    let mut strat = street_strategy(hand_strength, board_texture, pot, stack, position, street);
+    let pct = match hand_percentile {
+        Some(p) if strat.action.starts_with("BET") => p,
+        _ => return strat,
+    };
+
+    let spr_val = if pot > 0.0 { stack / pot } else { 10.0 };
+    match recommend_bet_size(board_texture, spr_val, street, pct, pot, stack) {
+        Some(rec) => {
+            strat.sizing = format!("{:.0}% pot ({:.1}bb)", rec.fraction * 100.0, rec.amount);
+            strat.reasoning = format!("{} \u{2014} {}", strat.reasoning, rec.plan);
+            strat.size = Some(rec);
+        }
+        None => {
+            strat.action = "CHECK".to_string();
+            strat.sizing = "-".to_string();
+            strat.reasoning = "Marginal for its range \u{2014} check for pot control".to_string();
+        }
+    }
+    strat
+}
@@ -8,7 +8,7 @@
 //! earlier street are replaced with Chance nodes that branch into the
 //! next street's action subtrees.
 
-use crate::card_encoding::remaining_deck;
+use crate::card_encoding::{index_to_card, remaining_deck_excluding};
 
 /// Which player is acting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -115,9 +115,21 @@ impl TreeNode {
             TreeNode::Terminal { .. } => 1,
         }
     }
+
+    /// The action node with `node_id`, if the tree has one.
+    pub fn find_action(&self, node_id: u16) -> Option<&TreeNode> {
+        match self {
+            TreeNode::Action { node_id: id, .. } if *id == node_id => Some(self),
+            TreeNode::Action { children, .. } | TreeNode::Chance { children, .. } => {
+                children.iter().find_map(|c| c.find_action(node_id))
+            }
+            TreeNode::Terminal { .. } => None,
+        }
+    }
 }
 
 /// Configuration for building a postflop game tree.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TreeConfig {
     /// Bet sizes as fractions of pot (e.g., [0.33, 0.67, 1.0]).
     pub bet_sizes: Vec<f64>,
@@ -157,6 +169,46 @@ impl TreeConfig {
     }
 }
 
+/// Cache file name suffix for a tree's sizing: empty for the default
+/// sizing, otherwise the bet sizes in percent of pot plus "a" for an all-in
+/// ("_s50-100-200a" for half-pot, pot and 2x pot bets and an all-in).
+pub fn sizing_suffix(bet_sizes: &[f64], add_allin: bool, default_sizes: &[f64], default_allin: bool) -> String {
+    if bet_sizes == default_sizes && add_allin == default_allin {
+        return String::new();
+    }
+    let sizes: Vec<String> = bet_sizes.iter().map(|f| format!("{:.0}", f * 100.0)).collect();
+    format!("_s{}{}", sizes.join("-"), if add_allin { "a" } else { "" })
+}
+
+/// Cache file name suffix for a street's raising: empty for the default,
+/// otherwise `tag`, the raise sizes in percent of pot and the raise cap
+/// ("_raise250-400x3" for 2.5x and 4x pot raises, three per street).
+pub fn raise_suffix(tag: &str, raise_sizes: &[f64], max_raises: usize, default_sizes: &[f64], default_max: usize) -> String {
+    if raise_sizes == default_sizes && max_raises == default_max {
+        return String::new();
+    }
+    let sizes: Vec<String> = raise_sizes.iter().map(|f| format!("{:.0}", f * 100.0)).collect();
+    format!("_{}{}x{}", tag, sizes.join("-"), max_raises)
+}
+
+/// Most decision nodes a turn or river solve builds unless told otherwise
+/// (`--max-nodes`).
+pub const DEFAULT_MAX_TREE_NODES: usize = 50_000;
+
+/// Most decision nodes one tree can number: ids are 16-bit, and nodes past
+/// the last id all share `u16::MAX` (see `take_node_id`).
+pub const MAX_NUMBERED_NODES: usize = u16::MAX as usize - 1;
+
+/// The next decision node's id. Counts are kept in full so an oversized tree
+/// is measured exactly, but ids past `u16::MAX` collide: check the count
+/// against `MAX_NUMBERED_NODES` before solving on the tree, as the turn and
+/// river solvers do.
+fn take_node_id(next_id: &mut usize) -> u16 {
+    let id = u16::try_from(*next_id).unwrap_or(u16::MAX);
+    *next_id += 1;
+    id
+}
+
 /// Configuration for a turn+river tree.
 pub struct TurnTreeConfig {
     pub turn: TreeConfig,
@@ -165,6 +217,8 @@ pub struct TurnTreeConfig {
     pub river_max_raises: usize,
     /// 4-card turn board as u8 indices (used to enumerate river cards).
     pub board: Vec<u8>,
+    /// Cards known to be out of the deck, never dealt on the river.
+    pub dead_cards: Vec<u8>,
 }
 
 impl TurnTreeConfig {
@@ -175,14 +229,15 @@ impl TurnTreeConfig {
             river_raise_sizes: vec![1.0],
             river_max_raises: 3,
             board,
+            dead_cards: Vec::new(),
         }
     }
 }
 
 /// Build a postflop game tree from the given config.
 /// Returns the root node and the total number of action nodes.
-pub fn build_tree(config: &TreeConfig) -> (TreeNode, u16) {
-    let mut next_id: u16 = 0;
+pub fn build_tree(config: &TreeConfig) -> (TreeNode, usize) {
+    let mut next_id = 0;
     let invested = [0.0, 0.0]; // how much each player has put in beyond starting pot
     let root = build_node(
         config,
@@ -193,6 +248,7 @@ pub fn build_tree(config: &TreeConfig) -> (TreeNode, u16) {
         0,      // raises this street
         false,  // facing_bet
         0.0,    // amount_to_call
+        true,   // reopened (raising allowed when facing a bet)
         false,  // check_back (IP checked after OOP check?)
         &mut next_id,
     );
@@ -209,8 +265,9 @@ fn build_node(
     raises: usize,
     facing_bet: bool,
     amount_to_call: f64,
+    reopened: bool,
     oop_checked: bool,
-    next_id: &mut u16,
+    next_id: &mut usize,
 ) -> TreeNode {
     let pi = player.index();
     let remaining = stacks[pi];
@@ -229,7 +286,7 @@ fn build_node(
         // Facing a bet/raise: Fold / Call / Raise(sizes)
         build_facing_bet(
             config, player, pot, stacks, invested, raises,
-            amount_to_call, next_id,
+            amount_to_call, reopened, next_id,
         )
     } else if player == Player::IP && oop_checked {
         // IP acts after OOP check: Check (back) / Bet(sizes)
@@ -248,6 +305,24 @@ fn build_node(
     }
 }
 
+/// Bet and raise amounts closer than this are the same action: they would
+/// print with the same label and play identically.
+const AMOUNT_EPSILON: f64 = 0.1;
+
+/// Clamp `amount` to `allin`, treating anything within `AMOUNT_EPSILON` of it
+/// as the all-in itself.
+fn snap_to_allin(amount: f64, allin: f64) -> f64 {
+    if amount >= allin - AMOUNT_EPSILON {
+        allin
+    } else {
+        amount
+    }
+}
+
+fn is_duplicate_amount(added: &[f64], amount: f64) -> bool {
+    added.iter().any(|&a| (a - amount).abs() < AMOUNT_EPSILON)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_open_action(
     config: &TreeConfig,
@@ -257,13 +332,13 @@ fn build_open_action(
     invested: [f64; 2],
     raises: usize,
     is_check_back: bool,
-    next_id: &mut u16,
+    next_id: &mut usize,
 ) -> TreeNode {
     let pi = player.index();
-    let remaining = stacks[pi];
+    // Can never put in more than the opponent is able to match
+    let remaining = stacks[pi].min(stacks[player.opponent().index()]);
 
-    let node_id = *next_id;
-    *next_id += 1;
+    let node_id = take_node_id(next_id);
 
     let mut actions = Vec::new();
     let mut children = Vec::new();
@@ -282,28 +357,31 @@ fn build_open_action(
         // OOP checks -> IP acts
         children.push(build_node(
             config, Player::IP, pot, stacks, invested,
-            raises, false, 0.0, true, next_id,
+            raises, false, 0.0, true, true, next_id,
         ));
     }
 
-    // Bet sizes
-    let mut added_allin = false;
+    // Bet sizes. Sizes past the stack become one all-in, and sizes that
+    // resolve to (almost) the same amount are offered once.
+    let mut bets: Vec<f64> = Vec::new();
     for &frac in &config.bet_sizes {
-        let raw_bet = pot * frac;
-        let bet = raw_bet.min(remaining);
-
-        if bet < 0.01 {
-            continue;
+        let bet = snap_to_allin(pot * frac, remaining);
+        if bet >= 0.01 && !is_duplicate_amount(&bets, bet) {
+            bets.push(bet);
         }
+    }
 
-        // Skip if this is effectively the same as all-in and we already added it
-        if (bet - remaining).abs() < 0.01 {
-            if added_allin {
-                continue;
-            }
-            added_allin = true;
-        }
+    // All-in option (only if bet sizes are configured — empty bet_sizes means check-only)
+    if config.add_allin
+        && remaining > 0.01
+        && !config.bet_sizes.is_empty()
+        && remaining > pot * 0.2
+        && !is_duplicate_amount(&bets, remaining)
+    {
+        bets.push(remaining);
+    }
 
+    for bet in bets {
         actions.push(Action::Bet(bet));
 
         let mut new_stacks = stacks;
@@ -315,29 +393,10 @@ fn build_open_action(
         // Opponent faces this bet
         children.push(build_node(
             config, player.opponent(), new_pot, new_stacks, new_invested,
-            raises, true, bet, false, next_id,
+            raises, true, bet, true, false, next_id,
         ));
     }
 
-    // All-in option (only if bet sizes are configured — empty bet_sizes means check-only)
-    if config.add_allin && !added_allin && remaining > 0.01 && !config.bet_sizes.is_empty() {
-        let min_bet_threshold = pot * 0.2;
-        if remaining > min_bet_threshold {
-            actions.push(Action::Bet(remaining));
-
-            let mut new_stacks = stacks;
-            new_stacks[pi] -= remaining;
-            let new_pot = pot + remaining;
-            let mut new_invested = invested;
-            new_invested[pi] += remaining;
-
-            children.push(build_node(
-                config, player.opponent(), new_pot, new_stacks, new_invested,
-                raises, true, remaining, false, next_id,
-            ));
-        }
-    }
-
     TreeNode::Action {
         node_id,
         player,
@@ -348,6 +407,17 @@ fn build_open_action(
     }
 }
 
+/// Build a node where `player` faces a bet or raise of `amount_to_call`.
+///
+/// No-limit rules applied here:
+/// - A call always closes the action heads-up (there is nobody left to act),
+///   so it leads straight to a Showdown terminal.
+/// - A raise must increase the bet by at least `amount_to_call` (the last
+///   full bet/raise). Sizes that fall short are bumped up to the min-raise,
+///   and if the player can't afford that they may still go all-in.
+/// - An all-in for less than a full raise does not reopen the betting: the
+///   opponent (who has already acted) may only call or fold. `reopened` is
+///   false in that case.
 #[allow(clippy::too_many_arguments)]
 fn build_facing_bet(
     config: &TreeConfig,
@@ -357,13 +427,14 @@ fn build_facing_bet(
     invested: [f64; 2],
     raises: usize,
     amount_to_call: f64,
-    next_id: &mut u16,
+    reopened: bool,
+    next_id: &mut usize,
 ) -> TreeNode {
     let pi = player.index();
+    let oi = player.opponent().index();
     let remaining = stacks[pi];
 
-    let node_id = *next_id;
-    *next_id += 1;
+    let node_id = take_node_id(next_id);
 
     let mut actions = Vec::new();
     let mut children = Vec::new();
@@ -377,91 +448,64 @@ fn build_facing_bet(
         invested,
     });
 
-    // Call
+    // Call (closes the action, whether or not anyone is all-in)
     let call_amount = amount_to_call.min(remaining);
     actions.push(Action::Call(call_amount));
     {
         let mut new_stacks = stacks;
         new_stacks[pi] -= call_amount;
-        let new_pot = pot + call_amount;
         let mut new_invested = invested;
         new_invested[pi] += call_amount;
 
-        // Check if both players are all-in or if remaining stack is gone
-        let opp_remaining = new_stacks[player.opponent().index()];
-        if new_stacks[pi] < 0.01 || opp_remaining < 0.01 {
-            // All-in: go to showdown
-            children.push(TreeNode::Terminal {
-                terminal_type: TerminalType::Showdown,
-                pot: new_pot,
-                stacks: new_stacks,
-                invested: new_invested,
-            });
-        } else {
-            // Call closes the action -> showdown
-            children.push(TreeNode::Terminal {
-                terminal_type: TerminalType::Showdown,
-                pot: new_pot,
-                stacks: new_stacks,
-                invested: new_invested,
-            });
-        }
+        children.push(TreeNode::Terminal {
+            terminal_type: TerminalType::Showdown,
+            pot: pot + call_amount,
+            stacks: new_stacks,
+            invested: new_invested,
+        });
     }
 
-    // Raise options (if under the cap and has remaining stack after calling)
-    if raises < config.max_raises {
-        let remaining_after_call = remaining - call_amount;
-        if remaining_after_call > 0.01 {
-            let pot_after_call = pot + call_amount;
-            let mut added_allin = false;
-
-            for &frac in &config.raise_sizes {
-                let raise_amount = (pot_after_call * frac).min(remaining_after_call);
-
-                if raise_amount < 0.01 {
-                    continue;
-                }
-
-                let total_put_in = call_amount + raise_amount;
+    // Raise options: only if the action was reopened, we're under the cap,
+    // and both players have more than a sliver behind after the call.
+    let remaining_after_call = remaining - call_amount;
+    let max_raise = remaining_after_call.min(stacks[oi]);
+    if reopened && raises < config.max_raises && max_raise >= AMOUNT_EPSILON {
+        let pot_after_call = pot + call_amount;
+        let min_raise = amount_to_call;
+        let mut added: Vec<f64> = Vec::new();
+
+        let mut sizes: Vec<f64> = config
+            .raise_sizes
+            .iter()
+            .map(|&frac| snap_to_allin((pot_after_call * frac).max(min_raise), max_raise))
+            .collect();
+        if config.add_allin {
+            sizes.push(max_raise);
+        }
 
-                if (total_put_in - remaining).abs() < 0.01 {
-                    if added_allin {
-                        continue;
-                    }
-                    added_allin = true;
-                }
+        for raise_amount in sizes {
+            if is_duplicate_amount(&added, raise_amount) {
+                continue;
+            }
+            added.push(raise_amount);
 
-                actions.push(Action::Raise(total_put_in));
+            let total_put_in = call_amount + raise_amount;
+            // All-in short of a full raise doesn't reopen the action
+            let full_raise = raise_amount >= min_raise - 0.01;
 
-                let mut new_stacks = stacks;
-                new_stacks[pi] -= total_put_in;
-                let new_pot = pot + total_put_in;
-                let mut new_invested = invested;
-                new_invested[pi] += total_put_in;
+            actions.push(Action::Raise(total_put_in));
 
-                // Opponent now faces this raise
-                children.push(build_node(
-                    config, player.opponent(), new_pot, new_stacks, new_invested,
-                    raises + 1, true, raise_amount, false, next_id,
-                ));
-            }
+            let mut new_stacks = stacks;
+            new_stacks[pi] -= total_put_in;
+            let new_pot = pot + total_put_in;
+            let mut new_invested = invested;
+            new_invested[pi] += total_put_in;
 
-            // All-in raise
-            if config.add_allin && !added_allin && remaining_after_call > 0.01 {
-                let total_put_in = remaining;
-                actions.push(Action::Raise(total_put_in));
-
-                let mut new_stacks = stacks;
-                new_stacks[pi] = 0.0;
-                let new_pot = pot + total_put_in;
-                let mut new_invested = invested;
-                new_invested[pi] += total_put_in;
-
-                children.push(build_node(
-                    config, player.opponent(), new_pot, new_stacks, new_invested,
-                    raises + 1, true, total_put_in - call_amount, false, next_id,
-                ));
-            }
+            // Opponent now faces this raise
+            children.push(build_node(
+                config, player.opponent(), new_pot, new_stacks, new_invested,
+                raises + 1, true, raise_amount, full_raise, false, next_id,
+            ));
         }
     }
 
@@ -482,12 +526,12 @@ fn build_facing_bet(
 /// river card). Fold terminals are left as-is.
 ///
 /// Returns (root, total_action_nodes).
-pub fn build_turn_tree(config: &TurnTreeConfig) -> (TreeNode, u16) {
+pub fn build_turn_tree(config: &TurnTreeConfig) -> (TreeNode, usize) {
     // Build single-street turn action tree
     let (turn_tree, mut next_id) = build_tree(&config.turn);
 
-    // Possible river cards = 52 minus board cards
-    let river_cards = remaining_deck(&config.board);
+    // Possible river cards = 52 minus board and dead cards
+    let river_cards = remaining_deck_excluding(&config.board, &config.dead_cards);
 
     // Transform: replace Showdown terminals with Chance → river subtrees
     let root = attach_river_streets(
@@ -502,6 +546,49 @@ pub fn build_turn_tree(config: &TurnTreeConfig) -> (TreeNode, u16) {
     (root, next_id)
 }
 
+/// Number of decision nodes `build_turn_tree` would build, counted without
+/// building the river subtrees, so it's safe for trees too large to number.
+pub fn turn_tree_size(config: &TurnTreeConfig) -> usize {
+    fn river_nodes(node: &TreeNode, config: &TurnTreeConfig, river_cards: usize) -> usize {
+        match node {
+            TreeNode::Terminal { terminal_type: TerminalType::Showdown, pot, stacks, .. } => {
+                let river = river_street_config(
+                    &config.river_bet_sizes,
+                    &config.river_raise_sizes,
+                    config.river_max_raises,
+                    *pot,
+                    stacks[0].min(stacks[1]),
+                );
+                build_tree(&river).1 * river_cards
+            }
+            TreeNode::Action { children, .. } => children.iter().map(|c| river_nodes(c, config, river_cards)).sum(),
+            _ => 0,
+        }
+    }
+    let (turn_tree, turn_nodes) = build_tree(&config.turn);
+    let river_cards = remaining_deck_excluding(&config.board, &config.dead_cards).len();
+    turn_nodes + river_nodes(&turn_tree, config, river_cards)
+}
+
+/// The river street dealt after a turn line ends at `pot` with
+/// `effective_stack` behind.
+fn river_street_config(
+    bet_sizes: &[f64],
+    raise_sizes: &[f64],
+    max_raises: usize,
+    pot: f64,
+    effective_stack: f64,
+) -> TreeConfig {
+    TreeConfig {
+        bet_sizes: bet_sizes.to_vec(),
+        raise_sizes: raise_sizes.to_vec(),
+        max_raises,
+        starting_pot: pot,
+        effective_stack,
+        add_allin: true,
+    }
+}
+
 /// Recursively walk the tree and replace Showdown terminals with
 /// Chance nodes leading to river action subtrees.
 fn attach_river_streets(
@@ -510,7 +597,7 @@ fn attach_river_streets(
     river_raise_sizes: &[f64],
     river_max_raises: usize,
     river_cards: &[u8],
-    next_id: &mut u16,
+    next_id: &mut usize,
 ) -> TreeNode {
     match node {
         TreeNode::Terminal {
@@ -524,14 +611,7 @@ fn attach_river_streets(
             let mut children = Vec::with_capacity(river_cards.len());
 
             for &_card in river_cards {
-                let river_config = TreeConfig {
-                    bet_sizes: river_bet_sizes.to_vec(),
-                    raise_sizes: river_raise_sizes.to_vec(),
-                    max_raises: river_max_raises,
-                    starting_pot: pot,
-                    effective_stack: eff_stack,
-                    add_allin: true,
-                };
+                let river_config = river_street_config(river_bet_sizes, river_raise_sizes, river_max_raises, pot, eff_stack);
                 let river_root = build_node(
                     &river_config,
                     Player::OOP,
@@ -541,6 +621,7 @@ fn attach_river_streets(
                     0,
                     false,
                     0.0,
+                    true,
                     false,
                     next_id,
                 );
@@ -633,6 +714,400 @@ fn collect_meta_recursive(node: &TreeNode, metas: &mut Vec<NodeMeta>) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Three-handed trees
+// ---------------------------------------------------------------------------
+
+/// Seats in a three-handed tree, in the order they act.
+pub const MULTIWAY_SEATS: [&str; 3] = ["OOP", "MP", "IP"];
+
+/// A node in a three-handed betting tree (see `build_multiway_tree`).
+///
+/// Everyone starts with `effective_stack`, so all players still in at
+/// showdown have put in the same amount and there are no side pots.
+#[derive(Debug)]
+pub enum MultiwayNode {
+    Action {
+        /// Index among `seat`'s own action nodes.
+        node_id: u16,
+        /// Index into `MULTIWAY_SEATS`.
+        seat: usize,
+        pot: f64,
+        actions: Vec<Action>,
+        children: Vec<MultiwayNode>,
+    },
+    /// The hand is over: won by the last player in, or shown down between
+    /// everyone still `active`. `pot` includes the starting pot.
+    Terminal {
+        active: [bool; 3],
+        invested: [f64; 3],
+        pot: f64,
+    },
+}
+
+/// Betting state while building a three-handed tree.
+#[derive(Clone, Copy)]
+struct MultiwayRound {
+    seat: usize,
+    active: [bool; 3],
+    /// Players who still have to act on the current bet (or check).
+    pending: [bool; 3],
+    invested: [f64; 3],
+    raises: usize,
+}
+
+impl MultiwayRound {
+    /// After `seat` bets or raises, everyone else still in has to act again.
+    fn reopen(mut self) -> Self {
+        for s in 0..3 {
+            self.pending[s] = self.active[s] && s != self.seat;
+        }
+        self
+    }
+
+    /// Pass the action to the next player in seat order who still has to act.
+    fn advance(mut self) -> Self {
+        self.pending[self.seat] = false;
+        if let Some(next) = (1..3).map(|i| (self.seat + i) % 3).find(|&s| self.pending[s]) {
+            self.seat = next;
+        }
+        self
+    }
+}
+
+/// Build a single-street betting tree for three players (OOP, MP, IP).
+///
+/// Sizing follows `build_tree`: bets are fractions of the pot, raises a
+/// fraction of the pot after calling, capped at `max_raises` per street.
+/// A fold removes that player and the hand goes on between the other two.
+/// Returns the tree and how many action nodes each seat has (node ids are
+/// numbered per seat).
+pub fn build_multiway_tree(config: &TreeConfig) -> (MultiwayNode, [u16; 3]) {
+    let root = MultiwayRound {
+        seat: 0,
+        active: [true; 3],
+        pending: [true; 3],
+        invested: [0.0; 3],
+        raises: 0,
+    };
+    let mut next_ids = [0u16; 3];
+    let tree = build_multiway_node(config, root, &mut next_ids);
+    (tree, next_ids)
+}
+
+fn build_multiway_node(config: &TreeConfig, round: MultiwayRound, next_ids: &mut [u16; 3]) -> MultiwayNode {
+    let pot = config.starting_pot + round.invested.iter().sum::<f64>();
+    let players_in = round.active.iter().filter(|&&a| a).count();
+    if players_in == 1 || !round.pending.iter().any(|&p| p) {
+        return MultiwayNode::Terminal { active: round.active, invested: round.invested, pot };
+    }
+
+    let seat = round.seat;
+    let level = round.invested.iter().cloned().fold(0.0, f64::max);
+    let to_call = level - round.invested[seat];
+    let remaining = config.effective_stack - round.invested[seat];
+
+    let node_id = next_ids[seat];
+    next_ids[seat] += 1;
+
+    let mut actions = Vec::new();
+    let mut next_rounds = Vec::new();
+
+    if to_call < 0.01 {
+        actions.push(Action::Check);
+        next_rounds.push(round.advance());
+
+        if remaining > 0.01 {
+            let mut amounts: Vec<f64> = Vec::new();
+            let sized = config.bet_sizes.iter().map(|&frac| snap_to_allin(frac * pot, remaining));
+            let allin = config.add_allin.then_some(remaining);
+            for amount in sized.chain(allin) {
+                if amount > 0.01 && !is_duplicate_amount(&amounts, amount) {
+                    amounts.push(amount);
+                    let mut next = round;
+                    next.invested[seat] += amount;
+                    actions.push(Action::Bet(amount));
+                    next_rounds.push(next.reopen().advance());
+                }
+            }
+        }
+    } else {
+        let mut folded = round;
+        folded.active[seat] = false;
+        actions.push(Action::Fold);
+        next_rounds.push(folded.advance());
+
+        let call = to_call.min(remaining);
+        let mut called = round;
+        called.invested[seat] += call;
+        actions.push(Action::Call(call));
+        next_rounds.push(called.advance());
+
+        let raise_room = remaining - to_call;
+        if round.raises < config.max_raises && raise_room > 0.01 {
+            let mut totals: Vec<f64> = Vec::new();
+            let sized = config
+                .raise_sizes
+                .iter()
+                .map(|&frac| to_call + snap_to_allin(frac * (pot + to_call), raise_room));
+            let allin = config.add_allin.then_some(remaining);
+            for total in sized.chain(allin) {
+                if !is_duplicate_amount(&totals, total) {
+                    totals.push(total);
+                    let mut next = round;
+                    next.invested[seat] += total;
+                    next.raises += 1;
+                    actions.push(Action::Raise(total));
+                    next_rounds.push(next.reopen().advance());
+                }
+            }
+        }
+    }
+
+    let children = next_rounds
+        .into_iter()
+        .map(|next| build_multiway_node(config, next, next_ids))
+        .collect();
+    MultiwayNode::Action { node_id, seat, pot, actions, children }
+}
+
+// ---------------------------------------------------------------------------
+// Action lines
+// ---------------------------------------------------------------------------
+
+/// Follow `line` (e.g. ["check", "bet33"]) from `root` to the action node
+/// where it ends.
+///
+/// A step names one action at the current node: by its label ("Bet 3.3",
+/// case and spaces ignored), by kind ("check"/"x", "call"/"c", "fold"/"f",
+/// or "bet"/"raise" when the node has only one size), by size in percent of
+/// the pot ("bet33", "raise100"), or as "allin". Errors list the legal
+/// actions where the line goes wrong.
+pub fn walk_line<'a>(root: &'a TreeNode, line: &[String]) -> Result<&'a TreeNode, String> {
+    walk(root, line, None)
+}
+
+/// `walk_line` through a turn tree and on into the river: `line` closes the
+/// turn (e.g. ["check", "check"]) and any further steps are river actions in
+/// the subtree that `river_card` deals.
+pub fn walk_line_to_river<'a>(root: &'a TreeNode, line: &[String], river_card: u8) -> Result<&'a TreeNode, String> {
+    walk(root, line, Some(river_card))
+}
+
+fn walk<'a>(root: &'a TreeNode, line: &[String], mut river_card: Option<u8>) -> Result<&'a TreeNode, String> {
+    let mut node = root;
+    let mut followed: Vec<String> = Vec::new();
+    for step in line {
+        let TreeNode::Action { player, actions, children, .. } = node else {
+            return Err("The tree has no decisions".to_string());
+        };
+        let a = match_step(node, step).map_err(|why| {
+            let at = if followed.is_empty() {
+                "at the root".to_string()
+            } else {
+                format!("after [{}]", followed.join(", "))
+            };
+            format!("{} for {} {}; legal: {}", why, player_label(*player), at, legal_actions(node))
+        })?;
+        followed.push(actions[a].label());
+        node = &children[a];
+        if let (TreeNode::Chance { cards, children, .. }, Some(card)) = (node, river_card) {
+            river_card = None;
+            let Some(c) = cards.iter().position(|&c| c == card) else {
+                return Err(format!("{} can't come on the river", index_to_card(card)));
+            };
+            followed.push(index_to_card(card).to_string());
+            node = &children[c];
+        }
+        match node {
+            TreeNode::Action { .. } => {}
+            TreeNode::Terminal { terminal_type: TerminalType::Fold { .. }, .. } => {
+                return Err(format!("[{}] ends the hand with a fold", followed.join(", ")));
+            }
+            _ => return Err(format!("[{}] closes the street", followed.join(", "))),
+        }
+    }
+    if river_card.is_some() {
+        return Err(format!(
+            "[{}] doesn't close the turn; give the turn's actions first, e.g. check,check",
+            followed.join(", ")
+        ));
+    }
+    Ok(node)
+}
+
+/// The action node `node_id` and the steps on the way to it from `root`:
+/// each earlier action node with the index of the action taken there, and
+/// each chance node with the index of the card dealt.
+pub fn path_to_node(root: &TreeNode, node_id: u16) -> Option<(&TreeNode, Vec<(&TreeNode, usize)>)> {
+    let children = match root {
+        TreeNode::Action { node_id: id, .. } if *id == node_id => return Some((root, Vec::new())),
+        TreeNode::Action { children, .. } | TreeNode::Chance { children, .. } => children,
+        TreeNode::Terminal { .. } => return None,
+    };
+    children.iter().enumerate().find_map(|(a, child)| {
+        let (node, mut path) = path_to_node(child, node_id)?;
+        path.insert(0, (root, a));
+        Some((node, path))
+    })
+}
+
+/// Every action node on `root`'s street with the action labels leading to it
+/// (e.g. `["Check", "Bet 3.3"]`; empty for the root), in tree order. The
+/// walk stops at chance nodes, so a turn tree yields its turn nodes only.
+pub fn action_lines(root: &TreeNode) -> Vec<(u16, Vec<String>)> {
+    fn visit(node: &TreeNode, line: &mut Vec<String>, out: &mut Vec<(u16, Vec<String>)>) {
+        let TreeNode::Action { node_id, actions, children, .. } = node else {
+            return;
+        };
+        out.push((*node_id, line.clone()));
+        for (action, child) in actions.iter().zip(children) {
+            line.push(action.label());
+            visit(child, line, out);
+            line.pop();
+        }
+    }
+    let mut out = Vec::new();
+    visit(root, &mut Vec::new(), &mut out);
+    out
+}
+
+/// Index of the action at `node` that `step` names, in any form `walk_line`
+/// takes; errors list the legal actions.
+pub fn action_index(node: &TreeNode, step: &str) -> Result<usize, String> {
+    match_step(node, step).map_err(|why| format!("{}; legal: {}", why, legal_actions(node)))
+}
+
+/// The actions at an action node as "Check, Bet 3.3 (bet33), ...", each bet
+/// or raise with the short name `walk_line` accepts for it.
+pub fn legal_actions(node: &TreeNode) -> String {
+    let TreeNode::Action { actions, .. } = node else {
+        return String::new();
+    };
+    actions
+        .iter()
+        .map(|action| match action {
+            Action::Bet(_) | Action::Raise(_) => {
+                format!("{} ({})", action.label(), short_name(node, action))
+            }
+            _ => action.label(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// "OOP" or "IP", as solutions label players.
+pub fn player_label(player: Player) -> &'static str {
+    match player {
+        Player::OOP => "OOP",
+        Player::IP => "IP",
+    }
+}
+
+/// Short name of `action` at `node`: "check", "call", "fold", "allin", or a
+/// bet/raise size in percent of the pot like "bet33".
+fn short_name(node: &TreeNode, action: &Action) -> String {
+    match action {
+        Action::Check => "check".to_string(),
+        Action::Call(_) => "call".to_string(),
+        Action::Fold => "fold".to_string(),
+        Action::Bet(_) | Action::Raise(_) => {
+            let (kind, pct) = sizing(node, action).expect("bets and raises have a size");
+            if is_allin(node, action) {
+                "allin".to_string()
+            } else {
+                format!("{}{:.0}", kind, pct)
+            }
+        }
+    }
+}
+
+/// Kind and size of a bet or raise in percent of the pot it's made into:
+/// a bet's amount over the pot, a raise's amount beyond the call over the
+/// pot after calling (how `raise_sizes` are applied).
+fn sizing(node: &TreeNode, action: &Action) -> Option<(&'static str, f64)> {
+    let TreeNode::Action { pot, actions, .. } = node else {
+        return None;
+    };
+    match *action {
+        Action::Bet(amount) => Some(("bet", amount / pot * 100.0)),
+        Action::Raise(total) => {
+            let call = actions
+                .iter()
+                .find_map(|a| match a {
+                    Action::Call(c) => Some(*c),
+                    _ => None,
+                })
+                .unwrap_or(0.0);
+            Some(("raise", (total - call) / (pot + call) * 100.0))
+        }
+        _ => None,
+    }
+}
+
+fn is_allin(node: &TreeNode, action: &Action) -> bool {
+    let TreeNode::Action { player, stacks, .. } = node else {
+        return false;
+    };
+    match action {
+        Action::Bet(amount) | Action::Raise(amount) => *amount >= stacks[player.index()] - 0.01,
+        _ => false,
+    }
+}
+
+/// Index of the action at `node` that `step` names, or why there is none.
+fn match_step(node: &TreeNode, step: &str) -> Result<usize, String> {
+    let TreeNode::Action { actions, .. } = node else {
+        return Err("Not a decision".to_string());
+    };
+    let norm = |s: &str| s.to_lowercase().replace([' ', '%'], "");
+    let wanted = match norm(step).as_str() {
+        "x" => "check".to_string(),
+        "c" => "call".to_string(),
+        "f" => "fold".to_string(),
+        "b" => "bet".to_string(),
+        "r" => "raise".to_string(),
+        s => s.to_string(),
+    };
+
+    if let Some(a) = actions
+        .iter()
+        .position(|action| norm(&action.label()) == wanted || short_name(node, action) == wanted)
+    {
+        return Ok(a);
+    }
+
+    for kind in ["bet", "raise"] {
+        let Some(size) = wanted.strip_prefix(kind) else {
+            continue;
+        };
+        let sized: Vec<(usize, f64)> = actions
+            .iter()
+            .enumerate()
+            .filter_map(|(a, action)| match sizing(node, action) {
+                Some((k, pct)) if k == kind => Some((a, pct)),
+                _ => None,
+            })
+            .collect();
+        if size.is_empty() {
+            return match sized.as_slice() {
+                [(a, _)] => Ok(*a),
+                [] => Err(format!("No {}", kind)),
+                _ => Err(format!("'{}' is ambiguous; give a size", step)),
+            };
+        }
+        let target: f64 = size.parse().map_err(|_| format!("Unknown action '{}'", step))?;
+        // Sizes snap to the min-raise and all-in, so allow a few points of slack
+        return sized
+            .iter()
+            .filter(|(_, pct)| (pct - target).abs() <= 5.0)
+            .min_by(|x, y| (x.1 - target).abs().total_cmp(&(y.1 - target).abs()))
+            .map(|&(a, _)| a)
+            .ok_or_else(|| format!("No '{}'", step));
+    }
+    Err(format!("No '{}'", step))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -694,7 +1169,7 @@ mod tests {
         collect_node_ids(&root, &mut ids);
         ids.sort();
         ids.dedup();
-        assert_eq!(ids.len(), num_nodes as usize);
+        assert_eq!(ids.len(), num_nodes);
         // IDs should be 0..num_nodes
         for (i, &id) in ids.iter().enumerate() {
             assert_eq!(id, i as u16);
@@ -757,10 +1232,341 @@ mod tests {
         }
     }
 
+    // -----------------------------------------------------------------------
+    // No-limit betting rules
+    // -----------------------------------------------------------------------
+
+    /// A terminal reached by following `line` from the root.
+    #[derive(Debug)]
+    struct Line {
+        line: Vec<String>,
+        terminal_type: TerminalType,
+        pot: f64,
+        invested: [f64; 2],
+    }
+
+    /// Replay every action sequence in the tree against an independent model
+    /// of heads-up no-limit rules, panicking on the first illegal action.
+    /// Returns every terminal line reached.
+    fn enumerate_legal_lines(root: &TreeNode, pot: f64, stacks: [f64; 2]) -> Vec<Line> {
+        #[derive(Clone)]
+        struct State {
+            line: Vec<String>,
+            stacks: [f64; 2],
+            invested: [f64; 2],
+            /// Size of the last full bet/raise increment (the min-raise).
+            last_full: f64,
+            /// Whether the player to act may raise (false after an incomplete all-in).
+            reopened: bool,
+            /// Players who have acted since the last full bet/raise.
+            acted: [bool; 2],
+        }
+
+        fn walk(node: &TreeNode, start_pot: f64, st: State, out: &mut Vec<Line>) {
+            match node {
+                TreeNode::Terminal { terminal_type, pot, invested, .. } => {
+                    let expected_pot = start_pot + st.invested[0] + st.invested[1];
+                    assert!((pot - expected_pot).abs() < 0.01, "{:?}: pot {} != {}", st.line, pot, expected_pot);
+                    for (got, want) in invested.iter().zip(&st.invested) {
+                        assert!((got - want).abs() < 0.01, "{:?}: invested mismatch", st.line);
+                    }
+                    if *terminal_type == TerminalType::Showdown {
+                        let matched = (st.invested[0] - st.invested[1]).abs() < 0.01;
+                        let allin = st.stacks[0] < 0.01 || st.stacks[1] < 0.01;
+                        assert!(matched || allin, "{:?}: showdown with unmatched bets", st.line);
+                    }
+                    out.push(Line {
+                        line: st.line.clone(),
+                        terminal_type: *terminal_type,
+                        pot: *pot,
+                        invested: *invested,
+                    });
+                }
+                TreeNode::Action { player, pot, stacks, actions, children, .. } => {
+                    let pi = player.index();
+                    let oi = player.opponent().index();
+                    assert!((pot - (start_pot + st.invested[0] + st.invested[1])).abs() < 0.01);
+                    assert_eq!(*stacks, st.stacks, "{:?}: stack mismatch", st.line);
+                    assert!(st.stacks[pi] > 0.01, "{:?}: all-in player asked to act", st.line);
+                    let to_call = st.invested[oi] - st.invested[pi];
+
+                    for (action, child) in actions.iter().zip(children) {
+                        let mut next = st.clone();
+                        next.line.push(action.label());
+                        match *action {
+                            Action::Check => assert!(to_call < 0.01, "{:?}: check facing a bet", next.line),
+                            Action::Fold => assert!(to_call > 0.01, "{:?}: fold with nothing to call", next.line),
+                            Action::Bet(amt) => {
+                                assert!(to_call < 0.01, "{:?}: bet facing a bet", next.line);
+                                assert!(amt > 0.01 && amt <= st.stacks[pi] + 0.01);
+                                assert!(amt <= st.stacks[oi] + 0.01, "{:?}: bet exceeds what opponent can call", next.line);
+                                next.last_full = amt;
+                                next.acted = [false; 2];
+                            }
+                            Action::Call(amt) => {
+                                assert!(to_call > 0.01, "{:?}: call with nothing to call", next.line);
+                                assert!((amt - to_call.min(st.stacks[pi])).abs() < 0.01, "{:?}: wrong call amount", next.line);
+                            }
+                            Action::Raise(total) => {
+                                assert!(to_call > 0.01, "{:?}: raise with nothing to call", next.line);
+                                assert!(st.reopened, "{:?}: raise after an incomplete all-in", next.line);
+                                assert!(st.stacks[oi] > 0.01, "{:?}: raise against an all-in player", next.line);
+                                let increment = total - to_call;
+                                // All-in, or puts a shorter opponent all-in (effectively the same)
+                                let allin = (total - st.stacks[pi]).abs() < 0.01
+                                    || (increment - st.stacks[oi]).abs() < 0.01;
+                                assert!(total <= st.stacks[pi] + 0.01);
+                                assert!(increment <= st.stacks[oi] + 0.01, "{:?}: raise exceeds what opponent can call", next.line);
+                                assert!(increment >= st.last_full - 0.01 || allin, "{:?}: raise below min-raise", next.line);
+                                if increment >= st.last_full - 0.01 {
+                                    next.last_full = increment;
+                                    next.acted = [false; 2];
+                                    next.reopened = true;
+                                } else {
+                                    // Incomplete raise: opponent already acted, can only call/fold
+                                    next.reopened = !st.acted[oi];
+                                }
+                            }
+                        }
+                        let put_in = match *action {
+                            Action::Bet(a) | Action::Call(a) | Action::Raise(a) => a,
+                            _ => 0.0,
+                        };
+                        next.stacks[pi] -= put_in;
+                        next.invested[pi] += put_in;
+                        next.acted[pi] = true;
+                        walk(child, start_pot, next, out);
+                    }
+                }
+                TreeNode::Chance { .. } => panic!("single-street tree has no chance nodes"),
+            }
+        }
+
+        let mut out = Vec::new();
+        let st = State {
+            line: Vec::new(),
+            stacks,
+            invested: [0.0; 2],
+            last_full: 0.0,
+            reopened: true,
+            acted: [false; 2],
+        };
+        walk(root, pot, st, &mut out);
+        out
+    }
+
+    fn build_with_stacks(config: &TreeConfig, stacks: [f64; 2]) -> TreeNode {
+        let mut next_id = 0;
+        build_node(
+            config, Player::OOP, config.starting_pot, stacks, [0.0; 2],
+            0, false, 0.0, true, false, &mut next_id,
+        )
+    }
+
+    #[test]
+    fn shallow_tree_lines_match_hand_computed_table() {
+        // Pot 10, 12 behind: a 33% bet (3.3) can be raised all-in (to 12.0),
+        // a shove (12.0) can only be called or folded.
+        let config = TreeConfig {
+            bet_sizes: vec![0.33],
+            raise_sizes: vec![1.0],
+            max_raises: 3,
+            starting_pot: 10.0,
+            effective_stack: 12.0,
+            add_allin: true,
+        };
+        let (root, _) = build_tree(&config);
+        let lines = enumerate_legal_lines(&root, 10.0, [12.0; 2]);
+
+        let sd = TerminalType::Showdown;
+        let oop_folds = TerminalType::Fold { folder: Player::OOP };
+        let ip_folds = TerminalType::Fold { folder: Player::IP };
+        let expected: Vec<(&[&str], TerminalType, f64, [f64; 2])> = vec![
+            (&["Check", "Check"], sd, 10.0, [0.0, 0.0]),
+            (&["Check", "Bet 3.3", "Fold"], oop_folds, 13.3, [0.0, 3.3]),
+            (&["Check", "Bet 3.3", "Call 3.3"], sd, 16.6, [3.3, 3.3]),
+            (&["Check", "Bet 3.3", "Raise 12.0", "Fold"], ip_folds, 25.3, [12.0, 3.3]),
+            (&["Check", "Bet 3.3", "Raise 12.0", "Call 8.7"], sd, 34.0, [12.0, 12.0]),
+            (&["Check", "Bet 12.0", "Fold"], oop_folds, 22.0, [0.0, 12.0]),
+            (&["Check", "Bet 12.0", "Call 12.0"], sd, 34.0, [12.0, 12.0]),
+            (&["Bet 3.3", "Fold"], ip_folds, 13.3, [3.3, 0.0]),
+            (&["Bet 3.3", "Call 3.3"], sd, 16.6, [3.3, 3.3]),
+            (&["Bet 3.3", "Raise 12.0", "Fold"], oop_folds, 25.3, [3.3, 12.0]),
+            (&["Bet 3.3", "Raise 12.0", "Call 8.7"], sd, 34.0, [12.0, 12.0]),
+            (&["Bet 12.0", "Fold"], ip_folds, 22.0, [12.0, 0.0]),
+            (&["Bet 12.0", "Call 12.0"], sd, 34.0, [12.0, 12.0]),
+        ];
+
+        assert_eq!(lines.len(), expected.len(), "lines: {:?}", lines);
+        for (line, (labels, tt, pot, invested)) in lines.iter().zip(&expected) {
+            assert_eq!(line.line, labels.to_vec());
+            assert_eq!(line.terminal_type, *tt, "{:?}", line.line);
+            assert!((line.pot - pot).abs() < 0.01, "{:?}: pot {}", line.line, line.pot);
+            assert!((line.invested[0] - invested[0]).abs() < 0.01, "{:?}", line.line);
+            assert!((line.invested[1] - invested[1]).abs() < 0.01, "{:?}", line.line);
+        }
+    }
+
+    #[test]
+    fn raise_below_min_raise_bumped_up() {
+        // Pot 10, bet 10: a 25% raise (7.5) is below the 10.0 min-raise
+        let config = TreeConfig {
+            bet_sizes: vec![1.0],
+            raise_sizes: vec![0.25],
+            max_raises: 1,
+            starting_pot: 10.0,
+            effective_stack: 100.0,
+            add_allin: false,
+        };
+        let (root, _) = build_tree(&config);
+        enumerate_legal_lines(&root, 10.0, [100.0; 2]);
+
+        if let TreeNode::Action { children, .. } = &root {
+            if let TreeNode::Action { actions, .. } = &children[1] {
+                assert_eq!(actions[2], Action::Raise(20.0));
+            } else {
+                panic!("Expected IP to face the bet");
+            }
+        }
+    }
+
+    #[test]
+    fn incomplete_allin_raise_allowed_but_capped() {
+        // Pot 10, stacks 14: bet 10, all-in raise is only 4 more (< 10 min-raise).
+        // The short shove must still be offered; the bettor can only call or fold.
+        let config = TreeConfig {
+            bet_sizes: vec![1.0],
+            raise_sizes: vec![1.0],
+            max_raises: 3,
+            starting_pot: 10.0,
+            effective_stack: 14.0,
+            add_allin: true,
+        };
+        let (root, _) = build_tree(&config);
+        let lines = enumerate_legal_lines(&root, 10.0, [14.0; 2]);
+        assert!(lines.iter().any(|l| l.line == ["Bet 10.0", "Raise 14.0", "Call 4.0"]));
+        assert!(lines.iter().all(|l| l.line.len() <= 4));
+    }
+
+    #[test]
+    fn all_lines_legal_across_shallow_configs() {
+        for &stack in &[1.0, 3.0, 7.5, 12.0, 25.0, 60.0] {
+            for config in [
+                TreeConfig::default_river(10.0, stack),
+                TreeConfig::default_turn(10.0, stack),
+                TreeConfig {
+                    bet_sizes: vec![0.25, 0.75, 1.5],
+                    raise_sizes: vec![0.3, 1.0],
+                    max_raises: 4,
+                    starting_pot: 10.0,
+                    effective_stack: stack,
+                    add_allin: true,
+                },
+            ] {
+                let (root, _) = build_tree(&config);
+                let lines = enumerate_legal_lines(&root, 10.0, [stack; 2]);
+                assert!(!lines.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn asymmetric_stacks_never_overcommit() {
+        // OOP covers; IP is short. Nobody can bet or raise more than IP can call.
+        let config = TreeConfig {
+            bet_sizes: vec![0.5, 1.5],
+            raise_sizes: vec![0.5, 1.0],
+            max_raises: 3,
+            starting_pot: 10.0,
+            effective_stack: 12.0,
+            add_allin: true,
+        };
+        for stacks in [[40.0, 12.0], [12.0, 40.0], [9.0, 30.0]] {
+            let root = build_with_stacks(&config, stacks);
+            enumerate_legal_lines(&root, 10.0, stacks);
+        }
+    }
+
+    /// Panic if any action node offers two actions with the same label.
+    fn assert_unique_labels(node: &TreeNode) {
+        if let TreeNode::Action { actions, children, .. } = node {
+            let labels: Vec<String> = actions.iter().map(|a| a.label()).collect();
+            for (i, label) in labels.iter().enumerate() {
+                assert!(!labels[..i].contains(label), "duplicate action {} in {:?}", label, labels);
+            }
+            children.iter().for_each(assert_unique_labels);
+        }
+    }
+
+    #[test]
+    fn low_spr_trees_collapse_to_jam_or_check() {
+        // Pot 10: every configured size exceeds (or nearly matches) the stack
+        for &stack in &[3.0, 5.0, 10.0, 3.34] {
+            for config in [
+                TreeConfig::default_river(10.0, stack),
+                TreeConfig::default_turn(10.0, stack),
+                TreeConfig {
+                    bet_sizes: vec![0.33, 0.75],
+                    raise_sizes: vec![1.0],
+                    max_raises: 2,
+                    starting_pot: 10.0,
+                    effective_stack: stack,
+                    add_allin: true,
+                },
+            ] {
+                let (root, _) = build_tree(&config);
+                assert_unique_labels(&root);
+                enumerate_legal_lines(&root, 10.0, [stack; 2]);
+            }
+        }
+
+        // SPR 0.3: check or jam, and a jam can only be called or folded
+        let (root, _) = build_tree(&TreeConfig::default_river(10.0, 3.0));
+        let TreeNode::Action { actions, children, .. } = &root else {
+            panic!("Expected root action node");
+        };
+        assert_eq!(actions, &[Action::Check, Action::Bet(3.0)]);
+        let TreeNode::Action { actions, .. } = &children[1] else {
+            panic!("Expected IP to face the jam");
+        };
+        assert_eq!(actions, &[Action::Fold, Action::Call(3.0)]);
+    }
+
+    #[test]
+    fn near_allin_bet_snaps_to_allin() {
+        // 33% of 10 is 3.3, which would print like the 3.34 shove
+        let config = TreeConfig {
+            bet_sizes: vec![0.33],
+            raise_sizes: vec![1.0],
+            max_raises: 2,
+            starting_pot: 10.0,
+            effective_stack: 3.34,
+            add_allin: true,
+        };
+        let (root, _) = build_tree(&config);
+        if let TreeNode::Action { actions, .. } = &root {
+            assert_eq!(actions, &[Action::Check, Action::Bet(3.34)]);
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Turn tree tests
     // -----------------------------------------------------------------------
 
+    #[test]
+    fn turn_tree_size_counts_what_build_turn_tree_numbers() {
+        let mut config = TurnTreeConfig::new(vec![0, 1, 2, 3], 10.0, 100.0);
+        assert_eq!(turn_tree_size(&config), build_turn_tree(&config).1);
+
+        // Deeper raising on both streets grows the tree
+        let default = turn_tree_size(&config);
+        config.turn.raise_sizes = vec![1.0, 2.0];
+        config.turn.max_raises = 3;
+        config.river_raise_sizes = vec![1.0, 2.0];
+        assert!(turn_tree_size(&config) > default);
+        assert_eq!(turn_tree_size(&config), build_turn_tree(&config).1);
+    }
+
     #[test]
     fn turn_tree_has_chance_nodes() {
         // Board: 4 turn cards (indices 0,1,2,3)
@@ -805,6 +1611,25 @@ mod tests {
         assert_eq!(num_children, 48, "48 possible river cards");
     }
 
+    #[test]
+    fn turn_tree_never_deals_dead_cards() {
+        let mut config = TurnTreeConfig::new(vec![0, 1, 2, 3], 10.0, 20.0);
+        config.dead_cards = vec![4, 51];
+        let (root, _) = build_turn_tree(&config);
+
+        fn chance_cards(node: &TreeNode) -> Option<Vec<u8>> {
+            match node {
+                TreeNode::Chance { cards, .. } => Some(cards.clone()),
+                TreeNode::Action { children, .. } => children.iter().find_map(chance_cards),
+                TreeNode::Terminal { .. } => None,
+            }
+        }
+
+        let cards = chance_cards(&root).expect("Should have a chance node");
+        assert_eq!(cards.len(), 46);
+        assert!(!cards.contains(&4) && !cards.contains(&51));
+    }
+
     #[test]
     fn turn_tree_node_ids_unique() {
         let config = TurnTreeConfig::new(vec![0, 1, 2, 3], 10.0, 20.0);
@@ -822,7 +1647,7 @@ mod tests {
             ids.len(),
             total
         );
-        assert_eq!(ids.len(), num_nodes as usize);
+        assert_eq!(ids.len(), num_nodes);
     }
 
     #[test]
@@ -871,7 +1696,7 @@ mod tests {
         let metas = collect_node_metadata(&root);
         assert_eq!(
             metas.len(),
-            num_nodes as usize,
+            num_nodes,
             "Metadata count should match total action nodes"
         );
 
@@ -881,4 +1706,88 @@ mod tests {
             assert!(m.num_actions >= 1);
         }
     }
+
+    fn line(steps: &[&str]) -> Vec<String> {
+        steps.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn node_actions(node: &TreeNode) -> (Player, Vec<String>) {
+        match node {
+            TreeNode::Action { player, actions, .. } => (*player, actions.iter().map(|a| a.label()).collect()),
+            _ => panic!("expected an action node"),
+        }
+    }
+
+    #[test]
+    fn walk_line_matches_labels_kinds_and_pot_sizes() {
+        let (root, _) = build_tree(&TreeConfig::default_river(10.0, 100.0));
+
+        let (player, actions) = node_actions(walk_line(&root, &line(&["check"])).unwrap());
+        assert_eq!(player, Player::IP);
+        assert_eq!(actions[0], "Check");
+
+        // Percent of the pot, chip label and short kind all name the same bet
+        for step in ["bet67", "Bet 6.7", "bet 67%"] {
+            let (player, actions) = node_actions(walk_line(&root, &line(&["x", step])).unwrap());
+            assert_eq!(player, Player::OOP, "{}", step);
+            assert_eq!(actions[..2], ["Fold".to_string(), "Call 6.7".to_string()]);
+        }
+
+        // A raise's size is its amount beyond the call over the pot after calling
+        let (_, actions) = node_actions(walk_line(&root, &line(&["bet33", "raise100"])).unwrap());
+        assert_eq!(actions[1], "Call 16.6");
+
+        let (_, actions) = node_actions(walk_line(&root, &line(&["allin"])).unwrap());
+        assert_eq!(actions, ["Fold", "Call 100.0"]);
+    }
+
+    #[test]
+    fn walk_line_errors_list_the_legal_actions() {
+        let (root, _) = build_tree(&TreeConfig::default_river(10.0, 100.0));
+
+        let err = walk_line(&root, &line(&["check", "bet50"])).unwrap_err();
+        assert!(err.contains("No 'bet50' for IP after [Check]"), "{}", err);
+        assert!(err.contains("Bet 3.3 (bet33), Bet 6.7 (bet67), Bet 10.0 (bet100), Bet 100.0 (allin)"), "{}", err);
+
+        let err = walk_line(&root, &line(&["bet"])).unwrap_err();
+        assert!(err.contains("ambiguous") && err.contains("at the root"), "{}", err);
+
+        let err = walk_line(&root, &line(&["check", "check"])).unwrap_err();
+        assert!(err.contains("[Check, Check] closes the street"), "{}", err);
+
+        let err = walk_line(&root, &line(&["bet33", "fold", "call"])).unwrap_err();
+        assert!(err.contains("ends the hand with a fold"), "{}", err);
+    }
+
+    #[test]
+    fn walk_line_to_river_carries_on_after_the_river_card() {
+        let (root, _) = build_turn_tree(&TurnTreeConfig::new(vec![0, 1, 2, 3], 10.0, 20.0));
+
+        let (player, actions) = node_actions(walk_line_to_river(&root, &line(&["check", "check"]), 4).unwrap());
+        assert_eq!(player, Player::OOP);
+        assert_eq!(actions[0], "Check");
+        let (player, _) = node_actions(walk_line_to_river(&root, &line(&["x", "x", "x"]), 4).unwrap());
+        assert_eq!(player, Player::IP);
+
+        let err = walk_line_to_river(&root, &line(&["check"]), 4).unwrap_err();
+        assert!(err.contains("[Check] doesn't close the turn"), "{}", err);
+        // The river can't deal a board card
+        let err = walk_line_to_river(&root, &line(&["check", "check"]), 0).unwrap_err();
+        assert!(err.contains("can't come on the river"), "{}", err);
+    }
+
+    #[test]
+    fn sizing_suffix_is_empty_only_for_the_default_sizing() {
+        let default = [0.33, 0.67, 1.0];
+        assert_eq!(sizing_suffix(&default, true, &default, true), "");
+        assert_eq!(sizing_suffix(&default, false, &default, true), "_s33-67-100");
+        assert_eq!(sizing_suffix(&[0.5, 1.0, 2.0], true, &default, true), "_s50-100-200a");
+    }
+
+    #[test]
+    fn raise_suffix_is_empty_only_for_the_default_raising() {
+        assert_eq!(raise_suffix("raise", &[1.0], 3, &[1.0], 3), "");
+        assert_eq!(raise_suffix("raise", &[1.0], 2, &[1.0], 3), "_raise100x2");
+        assert_eq!(raise_suffix("rvraise", &[2.5, 4.0], 3, &[1.0], 3), "_rvraise250-400x3");
+    }
 }
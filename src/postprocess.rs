@@ -0,0 +1,406 @@
+//! Optional clean-up of solved per-combo strategies before they are stored.
+//!
+//! Averaged CFR strategies carry iteration noise: tiny mixes like 3%/97%, and
+//! combos of one hand class that zigzag (AhQh bets 71%, AhQd 64%, AhQc 77%).
+//! Two passes run per decision node:
+//!
+//! 1. Smoothing: each combo's strategy moves `smooth` of the way toward its
+//!    hand class's mean. Combos whose equity differs from the class mean by
+//!    more than `equity_tolerance` are left alone (and left out of the mean),
+//!    because that divergence is card removal and not noise.
+//! 2. Purification: frequencies below `purify_threshold` drop to 0 and the
+//!    remaining frequencies are renormalized.
+//!
+//! Solvers write the result back into their average strategy and recompute
+//! exploitability, so the cost of cleaning up is reported next to the raw
+//! number.
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::card_encoding::remaining_deck;
+use crate::lookup_eval::evaluate_fast;
+use crate::river_solver::Combo;
+
+/// Post-processing settings for a solve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostProcess {
+    /// Frequencies below this (0..0.5) are zeroed.
+    pub purify_threshold: f64,
+    /// Shrink factor toward the hand-class mean (0 = off, 1 = class mean).
+    pub smooth: f64,
+    /// Equity gap to the class mean beyond which a combo isn't smoothed.
+    pub equity_tolerance: f64,
+}
+
+/// What was applied to a stored solution, and the exploitability before it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostProcessInfo {
+    pub purify_threshold: f64,
+    pub smooth: f64,
+    pub equity_tolerance: f64,
+    /// Exploitability of the raw average strategy.
+    pub raw_exploitability: f64,
+}
+
+impl PostProcessInfo {
+    /// One-line summary for solution displays.
+    pub fn describe(&self, exploitability: f64) -> String {
+        format!(
+            "Post-processed (purify <{:.1}%, smooth {:.2}, blocker tolerance {:.1}%): exploitability {:.4} raw → {:.4}",
+            self.purify_threshold * 100.0,
+            self.smooth,
+            self.equity_tolerance * 100.0,
+            self.raw_exploitability,
+            exploitability
+        )
+    }
+}
+
+impl PostProcess {
+    pub fn new(purify_threshold: f64, smooth: f64, equity_tolerance: f64) -> Result<Self, String> {
+        if !(0.0..0.5).contains(&purify_threshold) {
+            return Err("Purify threshold must be between 0% and 50%".to_string());
+        }
+        if !(0.0..=1.0).contains(&smooth) {
+            return Err("Smoothing factor must be between 0 and 1".to_string());
+        }
+        if equity_tolerance < 0.0 {
+            return Err("Blocker tolerance must be non-negative".to_string());
+        }
+        Ok(PostProcess { purify_threshold, smooth, equity_tolerance })
+    }
+
+    /// True when either pass would change anything.
+    pub fn is_active(&self) -> bool {
+        self.purify_threshold > 0.0 || self.smooth > 0.0
+    }
+
+    /// Record of these settings for a solution, given the raw exploitability.
+    pub fn applied(&self, raw_exploitability: f64) -> PostProcessInfo {
+        PostProcessInfo {
+            purify_threshold: self.purify_threshold,
+            smooth: self.smooth,
+            equity_tolerance: self.equity_tolerance,
+            raw_exploitability,
+        }
+    }
+
+    /// Process one node's strategies in place.
+    ///
+    /// `freqs[h]` is combo `h`'s action distribution; `classes[h]` its hand
+    /// class (see `hand_class`) and `equities[h]` its equity vs the opponent.
+    pub fn apply(&self, freqs: &mut [Vec<f64>], classes: &[u16], equities: &[f64]) {
+        if self.smooth > 0.0 {
+            self.smooth_classes(freqs, classes, equities);
+        }
+        if self.purify_threshold > 0.0 {
+            for f in freqs.iter_mut() {
+                purify(f, self.purify_threshold);
+            }
+        }
+    }
+
+    fn smooth_classes(&self, freqs: &mut [Vec<f64>], classes: &[u16], equities: &[f64]) {
+        let mut order: Vec<usize> = (0..freqs.len()).collect();
+        order.sort_by_key(|&h| classes[h]);
+        for group in order.chunk_by(|&a, &b| classes[a] == classes[b]) {
+            if group.len() < 2 {
+                continue;
+            }
+            let n = group.len() as f64;
+            let mean_equity = group.iter().map(|&h| equities[h]).sum::<f64>() / n;
+            let kept: Vec<usize> = group
+                .iter()
+                .copied()
+                .filter(|&h| (equities[h] - mean_equity).abs() <= self.equity_tolerance)
+                .collect();
+            if kept.len() < 2 {
+                continue;
+            }
+            let mut mean = vec![0.0; freqs[kept[0]].len()];
+            for &h in &kept {
+                for (m, f) in mean.iter_mut().zip(&freqs[h]) {
+                    *m += f / kept.len() as f64;
+                }
+            }
+            for &h in &kept {
+                for (f, m) in freqs[h].iter_mut().zip(&mean) {
+                    *f += self.smooth * (m - *f);
+                }
+            }
+        }
+    }
+}
+
+/// Rounding of a solved strategy for display (`--simplify`, `--purify`),
+/// applied to a copy of a solution (`RiverSolution::simplified` and friends)
+/// so the stored one keeps its exact frequencies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Simplify {
+    /// Frequencies below this (0..0.5) drop to 0 and above `1 - threshold` go to 1.
+    pub threshold: f64,
+    /// Play each combo's most frequent action outright.
+    pub purify: bool,
+}
+
+/// What `Simplify` did to a solution and what it costs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SimplifyInfo {
+    pub threshold: f64,
+    pub purify: bool,
+    /// Estimated EV the simplified strategies give up against the solved
+    /// ones, as a percentage of the starting pot (the two players' average,
+    /// like exploitability); `None` when the solution can't be evaluated.
+    pub ev_loss_pct_pot: Option<f64>,
+}
+
+impl SimplifyInfo {
+    /// One-line note for solution and query displays.
+    pub fn describe(&self) -> String {
+        let loss = match self.ev_loss_pct_pot {
+            Some(loss) => format!("est. EV loss {:.1}% pot", loss),
+            None => "EV loss not estimated".to_string(),
+        };
+        if self.purify {
+            format!("purified ({})", loss)
+        } else {
+            format!("simplified (threshold {}%, {})", (self.threshold * 1000.0).round() / 10.0, loss)
+        }
+    }
+}
+
+impl Simplify {
+    /// `threshold` as a fraction; it's ignored with `purify`.
+    pub fn new(threshold: f64, purify: bool) -> Result<Self, String> {
+        if !(0.0..0.5).contains(&threshold) {
+            return Err("Simplify threshold must be between 0% and 50%".to_string());
+        }
+        Ok(Simplify { threshold, purify })
+    }
+
+    /// Round one combo's strategy in place.
+    pub fn apply(&self, freqs: &mut [f64]) {
+        let best = freqs
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        if self.purify || freqs.get(best).is_some_and(|&f| f > 1.0 - self.threshold) {
+            for (i, f) in freqs.iter_mut().enumerate() {
+                *f = if i == best { 1.0 } else { 0.0 };
+            }
+        } else if self.threshold > 0.0 {
+            purify(freqs, self.threshold);
+        }
+    }
+
+    /// Round every combo's strategy at a node.
+    pub fn apply_all(&self, freqs: &mut [Vec<f64>]) {
+        for f in freqs.iter_mut() {
+            self.apply(f);
+        }
+    }
+
+    /// Record of this rounding and its estimated cost.
+    pub fn info(&self, ev_loss_pct_pot: Option<f64>) -> SimplifyInfo {
+        SimplifyInfo { threshold: self.threshold, purify: self.purify, ev_loss_pct_pot }
+    }
+}
+
+/// Zero frequencies below `threshold` and renormalize. If every action is
+/// below it, the most frequent one is kept.
+fn purify(freqs: &mut [f64], threshold: f64) {
+    let kept: f64 = freqs.iter().filter(|&&f| f >= threshold).sum();
+    if kept <= 0.0 {
+        let best = freqs
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        for (i, f) in freqs.iter_mut().enumerate() {
+            *f = if i == best { 1.0 } else { 0.0 };
+        }
+        return;
+    }
+    for f in freqs.iter_mut() {
+        *f = if *f >= threshold { *f / kept } else { 0.0 };
+    }
+}
+
+/// Canonical hand class of a combo ("AQs", "AQo", "QQ") as a small integer.
+pub fn hand_class(combo: &Combo) -> u16 {
+    let (r1, r2) = (combo.0 / 4, combo.1 / 4);
+    let (hi, lo) = if r1 >= r2 { (r1, r2) } else { (r2, r1) };
+    let suited = r1 != r2 && combo.0 % 4 == combo.1 % 4;
+    (hi as u16) * 32 + (lo as u16) * 2 + suited as u16
+}
+
+/// Each of `own`'s combos' showdown equity against all of `opp` (uniform
+/// weights, card-removal aware) over the remaining runouts of `board`.
+/// Flop boards use every `step`-th turn/river pair so at most `max_runouts` are
+/// evaluated.
+pub fn combo_equities(own: &[Combo], opp: &[Combo], board: &[u8], max_runouts: usize) -> Vec<f64> {
+    let deck = remaining_deck(board);
+    let runouts: Vec<Vec<u8>> = match board.len() {
+        5 => vec![vec![]],
+        4 => deck.iter().map(|&r| vec![r]).collect(),
+        _ => {
+            let pairs: Vec<Vec<u8>> = deck
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &t)| deck[i + 1..].iter().map(move |&r| vec![t, r]))
+                .collect();
+            let step = (pairs.len() / max_runouts.max(1)).max(1);
+            pairs.into_iter().step_by(step).collect()
+        }
+    };
+
+    let overlaps = |a: &Combo, b: &Combo| a.0 == b.0 || a.0 == b.1 || a.1 == b.0 || a.1 == b.1;
+    let (wins, totals) = runouts
+        .par_iter()
+        .map(|extra| {
+            let full: Vec<u8> = board.iter().chain(extra.iter()).copied().collect();
+            let blocked = |c: &Combo| extra.contains(&c.0) || extra.contains(&c.1);
+            let score = |c: &Combo| {
+                let mut cards = full.clone();
+                cards.push(c.0);
+                cards.push(c.1);
+                evaluate_fast(&cards)
+            };
+            let opp_scores: Vec<Option<u32>> =
+                opp.iter().map(|c| (!blocked(c)).then(|| score(c))).collect();
+            let mut wins = vec![0.0; own.len()];
+            let mut totals = vec![0.0; own.len()];
+            for (h, c) in own.iter().enumerate() {
+                if blocked(c) {
+                    continue;
+                }
+                let s = score(c);
+                for (o, os) in opp.iter().zip(&opp_scores) {
+                    let Some(os) = os else { continue };
+                    if overlaps(c, o) {
+                        continue;
+                    }
+                    totals[h] += 1.0;
+                    wins[h] += match s.cmp(os) {
+                        std::cmp::Ordering::Greater => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Less => 0.0,
+                    };
+                }
+            }
+            (wins, totals)
+        })
+        .reduce(
+            || (vec![0.0; own.len()], vec![0.0; own.len()]),
+            |(mut w, mut t), (w2, t2)| {
+                for (a, b) in w.iter_mut().zip(w2) {
+                    *a += b;
+                }
+                for (a, b) in t.iter_mut().zip(t2) {
+                    *a += b;
+                }
+                (w, t)
+            },
+        );
+    wins.iter()
+        .zip(&totals)
+        .map(|(w, t)| if *t > 0.0 { w / t } else { 0.0 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_encoding::card_to_index;
+    use crate::cards::parse_card;
+
+    fn combo(s: &str) -> Combo {
+        let c = |i: usize| card_to_index(&parse_card(&s[i..i + 2]).unwrap());
+        Combo::new(c(0), c(2))
+    }
+
+    #[test]
+    fn test_purify_zeroes_small_and_renormalizes() {
+        let mut f = vec![0.03, 0.97];
+        purify(&mut f, 0.05);
+        assert_eq!(f, vec![0.0, 1.0]);
+
+        let mut f = vec![0.02, 0.49, 0.49];
+        purify(&mut f, 0.05);
+        assert!((f[1] - 0.5).abs() < 1e-12 && f[0] == 0.0);
+
+        let mut f = vec![0.3, 0.3, 0.4];
+        purify(&mut f, 0.45);
+        assert_eq!(f, vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_simplify_rounds_near_pure_and_small() {
+        let s = Simplify::new(0.05, false).unwrap();
+        let mut f = vec![0.97, 0.03];
+        s.apply(&mut f);
+        assert_eq!(f, vec![1.0, 0.0]);
+
+        let mut f = vec![0.02, 0.49, 0.49];
+        s.apply(&mut f);
+        assert!(f[0] == 0.0 && (f[1] - 0.5).abs() < 1e-12);
+
+        let mut f = vec![0.4, 0.6];
+        s.apply(&mut f);
+        assert_eq!(f, vec![0.4, 0.6]);
+
+        let mut f = vec![0.4, 0.6];
+        Simplify::new(0.0, true).unwrap().apply(&mut f);
+        assert_eq!(f, vec![0.0, 1.0]);
+        assert!(Simplify::new(0.5, false).is_err());
+    }
+
+    #[test]
+    fn test_simplify_info_describe() {
+        let s = Simplify::new(0.05, false).unwrap();
+        assert_eq!(s.info(Some(0.31)).describe(), "simplified (threshold 5%, est. EV loss 0.3% pot)");
+        assert_eq!(Simplify::new(0.0, true).unwrap().info(None).describe(), "purified (EV loss not estimated)");
+    }
+
+    #[test]
+    fn test_hand_class_groups_suits() {
+        assert_eq!(hand_class(&combo("AhQh")), hand_class(&combo("AdQd")));
+        assert_ne!(hand_class(&combo("AhQh")), hand_class(&combo("AhQd")));
+        assert_eq!(hand_class(&combo("AhQd")), hand_class(&combo("QcAs")));
+        assert_eq!(hand_class(&combo("QhQd")), hand_class(&combo("QcQs")));
+    }
+
+    #[test]
+    fn test_smoothing_respects_blocker_tolerance() {
+        let pp = PostProcess::new(0.0, 0.5, 0.03).unwrap();
+        // Four AQo-like combos: three agree on equity, one is a blocker outlier
+        let mut freqs = vec![vec![0.71, 0.29], vec![0.64, 0.36], vec![0.77, 0.23], vec![0.20, 0.80]];
+        let classes = [7, 7, 7, 7];
+        let equities = [0.60, 0.61, 0.60, 0.70];
+        pp.apply(&mut freqs, &classes, &equities);
+        let mean = (0.71 + 0.64 + 0.77) / 3.0;
+        assert!((freqs[0][0] - (0.71 + 0.5 * (mean - 0.71))).abs() < 1e-12);
+        assert!((freqs[1][0] - (0.64 + 0.5 * (mean - 0.64))).abs() < 1e-12);
+        assert_eq!(freqs[3], vec![0.20, 0.80]);
+        for f in &freqs {
+            assert!((f.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_combo_equities_river() {
+        let board: Vec<u8> = ["Ks", "9d", "4c", "7h", "2s"]
+            .iter()
+            .map(|s| card_to_index(&parse_card(s).unwrap()))
+            .collect();
+        let own = [combo("AhAd"), combo("QhQd")];
+        let opp = [combo("KhKd"), combo("JhJd")];
+        let eq = combo_equities(&own, &opp, &board, 64);
+        assert!((eq[0] - 0.5).abs() < 1e-12);
+        assert!((eq[1] - 0.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,367 @@
+//! Prefetch: solve a study session's spots ahead of time within a budget.
+//!
+//! Each line of the spots file is a spot descriptor
+//! (`srp:BTNvsBB:100bb:Ks9d4c`, see `notes`) or a query-style shorthand
+//! (`BTN BB Ks9d4c 3bp 100bb`; one seat means the query's default villain).
+//! Spots resolve to the batch solver's cache keys; those cached at
+//! `--min-grade` or better are skipped, and the rest are solved shortest
+//! first so the most spots fit in the time budget.
+//!
+//! Solve time is estimated as iterations × combos (both ranges), scaled by a
+//! seconds-per-unit rate that starts at `DEFAULT_SECS_PER_UNIT` and is
+//! replaced by the measured rate after each solve. A spot whose estimate
+//! doesn't fit in the remaining budget isn't started; solves already running
+//! always finish.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+
+use crate::batch::{solve_batch_spot, BatchSpot};
+use crate::cache_index::{solver_cache_dir, CacheIndex, SolveLock};
+use crate::cards::normalize_cards;
+use crate::flop_solver::FlopSolverConfig;
+use crate::notes::{parse_pot_type, SpotDescriptor};
+use crate::preflop_solver::{Position, PreflopSizing, PreflopSolution};
+use crate::quality::Grade;
+use crate::river_solver::expand_range_to_combos;
+use crate::status::{self, ExitCode};
+use crate::strategy::{default_villain, PotType};
+
+/// Seconds per (iteration × combo): about 2.5 minutes for a 500K-iteration
+/// solve with 300 combos a side.
+const DEFAULT_SECS_PER_UNIT: f64 = 5e-7;
+
+/// Parse one line of a spots file: a descriptor, or a shorthand of seats,
+/// board, and optional pot type and stack in any order. Hole cards are
+/// accepted and ignored, so pasted query arguments work.
+pub fn parse_spot_line(line: &str) -> Result<SpotDescriptor, String> {
+    let line = line.trim();
+    if line.contains(':') {
+        return SpotDescriptor::parse(line);
+    }
+
+    let mut seats: Vec<Position> = Vec::new();
+    let mut board: Option<String> = None;
+    let mut pot_type = PotType::Srp;
+    let mut stack = 100.0;
+    for token in line.split(|c: char| c.is_whitespace() || c == ',').filter(|t| !t.is_empty()) {
+        let upper = token.to_uppercase();
+        if upper == "VS" {
+            continue;
+        }
+        if let Some(p) = Position::from_str(token) {
+            seats.push(p);
+            continue;
+        }
+        if let Some((a, b)) = upper.split_once("VS") {
+            if let (Some(a), Some(b)) = (Position::from_str(a), Position::from_str(b)) {
+                seats.extend([a, b]);
+                continue;
+            }
+        }
+        if let Some(p) = parse_pot_type(token) {
+            pot_type = p;
+            continue;
+        }
+        if let Some(bb) = upper.strip_suffix("BB").and_then(|n| n.parse::<f64>().ok()) {
+            stack = bb;
+            continue;
+        }
+        match normalize_cards(token) {
+            Ok(cards) if cards.len() == 4 => {} // hole cards aren't part of the spot
+            Ok(cards) => board = Some(cards),
+            Err(_) => return Err(format!("Unrecognized '{}' in spot '{}'", token, line)),
+        }
+    }
+
+    let board = board.ok_or_else(|| format!("No board in spot '{}'", line))?;
+    let (a, b) = match seats[..] {
+        [hero] => (hero, default_villain(hero)),
+        [a, b] => (a, b),
+        _ => return Err(format!("Spot '{}' needs one or two seats", line)),
+    };
+    SpotDescriptor::new(pot_type, a, b, stack, &board, None)
+}
+
+/// Indices of `costs` cheapest first; equal costs keep file order.
+pub fn solve_order(costs: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..costs.len()).collect();
+    order.sort_by(|&a, &b| costs[a].total_cmp(&costs[b]));
+    order
+}
+
+/// What happened to one line of the spots file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefetchStatus {
+    Cached(Grade),
+    Solved { secs: f64, grade: Grade },
+    /// Not started: the estimate didn't fit in the remaining budget.
+    OverBudget { estimate_secs: f64 },
+    /// Another process was solving it.
+    Locked,
+    /// Same cache key as an earlier line.
+    Duplicate(usize),
+    Invalid(String),
+    Failed(String),
+}
+
+struct Entry {
+    /// 1-based line number in the spots file.
+    line: usize,
+    /// Canonical descriptor, or the raw line when it didn't parse.
+    spot: String,
+    status: Option<PrefetchStatus>,
+}
+
+/// A spot waiting to be solved.
+struct Pending {
+    entry: usize,
+    spot: BatchSpot,
+    units: f64,
+    previous: Option<Grade>,
+}
+
+/// `gto prefetch`.
+pub fn run_prefetch(
+    file: &str,
+    time_budget: Option<Duration>,
+    iterations: usize,
+    min_grade: Grade,
+) -> Result<(), String> {
+    let text = std::fs::read_to_string(file).map_err(|e| status::io_error(format!("Cannot read {}: {}", file, e)))?;
+    let dir = solver_cache_dir();
+    let mut index = CacheIndex::load(&dir);
+    let mut preflop: HashMap<u64, Option<PreflopSolution>> = HashMap::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut pending: Vec<Pending> = Vec::new();
+
+    for (i, raw) in text.lines().enumerate() {
+        let raw = raw.split('#').next().unwrap_or_default().trim();
+        if raw.is_empty() {
+            continue;
+        }
+        let mut entry = Entry { line: i + 1, spot: raw.to_string(), status: None };
+        match resolve(raw, &mut preflop) {
+            Err(status) => entry.status = Some(status),
+            Ok((descriptor, spot)) => {
+                entry.spot = descriptor.key();
+                let file_name = spot.cache_file_name();
+                if let Some(&first) = seen.get(&file_name) {
+                    entry.status = Some(PrefetchStatus::Duplicate(first));
+                } else {
+                    seen.insert(file_name.clone(), entry.line);
+                    let previous = index.summary(&file_name).map(|r| r.grade);
+                    match previous {
+                        Some(grade) if grade.meets(min_grade) => {
+                            entry.status = Some(PrefetchStatus::Cached(grade));
+                        }
+                        _ => match estimate_units(&spot, iterations) {
+                            Ok(units) => pending.push(Pending { entry: entries.len(), spot, units, previous }),
+                            Err(e) => entry.status = Some(PrefetchStatus::Invalid(e)),
+                        },
+                    }
+                }
+            }
+        }
+        entries.push(entry);
+    }
+
+    println!();
+    println!(
+        "  {} Prefetch: {} spots, {} to solve  |  Iterations: {}  |  Min grade: {}  |  Budget: {}",
+        "GTO".bold(),
+        entries.len().to_string().bold(),
+        pending.len().to_string().bold(),
+        iterations,
+        min_grade,
+        time_budget.map(format_duration).unwrap_or_else(|| "none".to_string()),
+    );
+    println!();
+
+    let start = Instant::now();
+    let mut secs_per_unit = DEFAULT_SECS_PER_UNIT;
+    let (mut solved_secs, mut solved_units) = (0.0, 0.0);
+    let order = solve_order(&pending.iter().map(|p| p.units).collect::<Vec<_>>());
+    let total = order.len();
+
+    for (n, &k) in order.iter().enumerate() {
+        let p = &pending[k];
+        let estimate_secs = p.units * secs_per_unit;
+        let entry = &mut entries[p.entry];
+        if let Some(budget) = time_budget {
+            let remaining = budget.as_secs_f64() - start.elapsed().as_secs_f64();
+            if estimate_secs > remaining {
+                status::fail(ExitCode::Interrupted, "Time budget ran out before every spot was solved");
+                entry.status = Some(PrefetchStatus::OverBudget { estimate_secs });
+                continue;
+            }
+        }
+
+        print!(
+            "  [{}/{}] Solving {}{} ~{} ... ",
+            n + 1,
+            total,
+            entry.spot,
+            p.previous.map(|g| format!(" (cached grade {})", g)).unwrap_or_default(),
+            format_duration(Duration::from_secs_f64(estimate_secs)),
+        );
+        let _ = std::io::stdout().flush();
+
+        let file_name = p.spot.cache_file_name();
+        let Some(_lock) = SolveLock::acquire(&dir, &file_name) else {
+            println!("{}", "solving in another process".dimmed());
+            entry.status = Some(PrefetchStatus::Locked);
+            continue;
+        };
+        // Another process may have solved the spot and released its lock
+        // since the cache was first read
+        if let Some(grade) = index.summary(&file_name).map(|r| r.grade).filter(|g| g.meets(min_grade)) {
+            println!("{}", format!("solved in another process, grade {}", grade).dimmed());
+            entry.status = Some(PrefetchStatus::Cached(grade));
+            continue;
+        }
+        let spot_start = Instant::now();
+        match solve_batch_spot(&p.spot, iterations) {
+            Ok(solution) => {
+                let secs = spot_start.elapsed().as_secs_f64();
+                let grade = solution.solve_quality().grade;
+                println!("done ({:.1}s, grade {})", secs, grade);
+                entry.status = Some(PrefetchStatus::Solved { secs, grade });
+                solved_secs += secs;
+                solved_units += p.units;
+                secs_per_unit = solved_secs / solved_units;
+            }
+            Err(e) => {
+                println!("{}", format!("error: {}", e).red());
+                status::fail(ExitCode::SolveFailed, &e);
+                entry.status = Some(PrefetchStatus::Failed(e));
+            }
+        }
+    }
+
+    print_report(&entries, start.elapsed(), time_budget);
+    Ok(())
+}
+
+/// Descriptor and batch spot for one line, or the status explaining why it
+/// can't be prefetched.
+fn resolve(
+    line: &str,
+    preflop: &mut HashMap<u64, Option<PreflopSolution>>,
+) -> Result<(SpotDescriptor, BatchSpot), PrefetchStatus> {
+    let descriptor = parse_spot_line(line).map_err(PrefetchStatus::Invalid)?;
+    if descriptor.board.len() != 6 {
+        return Err(PrefetchStatus::Invalid(format!(
+            "{}: only flop spots are prefetched",
+            descriptor.key()
+        )));
+    }
+    let stack = descriptor.stack_bb();
+    let solution = preflop
+        .entry(stack.to_bits())
+        .or_insert_with(|| PreflopSolution::load("6max", stack, 0.0, &PreflopSizing::default()).ok())
+        .as_ref()
+        .ok_or_else(|| {
+            PrefetchStatus::Failed(format!(
+                "no preflop solution at {}bb; run `gto solve preflop --stack {}`",
+                stack, stack
+            ))
+        })?;
+    let (opener, responder) = descriptor.seats();
+    let spot = BatchSpot::new(solution, opener, responder, &descriptor.board, descriptor.pot_kind(), stack)
+        .ok_or_else(|| PrefetchStatus::Failed(format!("no preflop ranges for {}", descriptor.matchup)))?;
+    Ok((descriptor, spot))
+}
+
+/// Expected solve cost in iteration × combo units.
+fn estimate_units(spot: &BatchSpot, iterations: usize) -> Result<f64, String> {
+    let config = FlopSolverConfig::new(&spot.board, &spot.oop_range, &spot.ip_range, spot.pot, spot.stack, iterations)
+        .map_err(|e| e.to_string())?;
+    let combos = expand_range_to_combos(&config.oop_range, &config.board).len()
+        + expand_range_to_combos(&config.ip_range, &config.board).len();
+    Ok(iterations as f64 * combos as f64)
+}
+
+/// 2.5h → "2.5 h", 95s → "1.6 min", 40s → "40s".
+pub(crate) fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 3600.0 {
+        format!("{:.1} h", secs / 3600.0)
+    } else if secs >= 60.0 {
+        format!("{:.1} min", secs / 60.0)
+    } else {
+        format!("{:.0}s", secs)
+    }
+}
+
+fn print_report(entries: &[Entry], elapsed: Duration, budget: Option<Duration>) {
+    let count = |f: fn(&PrefetchStatus) -> bool| entries.iter().filter(|e| e.status.as_ref().is_some_and(f)).count();
+    let solved = count(|s| matches!(s, PrefetchStatus::Solved { .. }));
+    let cached = count(|s| matches!(s, PrefetchStatus::Cached(_)));
+    let over = count(|s| matches!(s, PrefetchStatus::OverBudget { .. }));
+    let other = entries.len() - solved - cached - over;
+
+    println!();
+    println!(
+        "  {} Prefetch finished in {}{}",
+        "Session".bold(),
+        format_duration(elapsed),
+        budget.map(|b| format!(" of {} budget", format_duration(b))).unwrap_or_default(),
+    );
+    println!(
+        "  Solved: {}  Cached: {}  Over budget: {}  Not solvable: {}",
+        solved.to_string().green().bold(),
+        cached.to_string().bold(),
+        if over > 0 { over.to_string().yellow().bold() } else { over.to_string().bold() },
+        if other > 0 { other.to_string().red().bold() } else { other.to_string().bold() },
+    );
+    println!();
+    for e in entries {
+        let status = match e.status.as_ref() {
+            Some(PrefetchStatus::Solved { secs, grade }) => format!("solved in {:.1}s, grade {}", secs, grade).green(),
+            Some(PrefetchStatus::Cached(grade)) => format!("cached, grade {}", grade).dimmed(),
+            Some(PrefetchStatus::OverBudget { estimate_secs }) => format!(
+                "skipped: ~{} doesn't fit the budget",
+                format_duration(Duration::from_secs_f64(*estimate_secs))
+            )
+            .yellow(),
+            Some(PrefetchStatus::Locked) => "skipped: solving in another process".yellow(),
+            Some(PrefetchStatus::Duplicate(line)) => format!("same spot as line {}", line).dimmed(),
+            Some(PrefetchStatus::Invalid(e)) | Some(PrefetchStatus::Failed(e)) => e.red(),
+            None => "not reached".dimmed(),
+        };
+        println!("  {:>4}  {:<32} {}", e.line, e.spot, status);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorthand_matches_descriptor() {
+        let descriptor = parse_spot_line("srp:BTNvsBB:100bb:Ks9d4c").unwrap();
+        assert_eq!(parse_spot_line("BTN BB Ks9d4c").unwrap(), descriptor);
+        assert_eq!(parse_spot_line("bb vs btn Kh9s4d 100bb").unwrap(), descriptor);
+        // Query-style: hole cards ignored, one seat means the default villain
+        assert_eq!(parse_spot_line("AhKd BTN Ks9d4c").unwrap(), descriptor);
+
+        let three_bet = parse_spot_line("COvsBTN 3bp 50bb As7d2c").unwrap();
+        assert_eq!(three_bet.key(), "3bp:COvsBTN:50bb:As7h2d");
+
+        assert!(parse_spot_line("BTN BB").is_err());
+        assert!(parse_spot_line("BTN BB CO Ks9d4c").is_err());
+        assert!(parse_spot_line("BTN BB Ks9d4c limp").is_err());
+    }
+
+    #[test]
+    fn test_solve_order_is_cheapest_first_and_stable() {
+        assert_eq!(solve_order(&[3.0, 1.0, 2.0, 1.0]), vec![1, 3, 2, 0]);
+        assert!(solve_order(&[]).is_empty());
+    }
+}
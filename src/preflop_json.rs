@@ -0,0 +1,231 @@
+//! Portable JSON for preflop solutions (`gto solve preflop --export`,
+//! `gto import preflop`), for sharing solved ranges and importing ranges
+//! from other tools.
+//!
+//! ```text
+//! {
+//!   "format": "gto-preflop-ranges",
+//!   "schema_version": 1,
+//!   "table_size": "6max",
+//!   "stack_bb": 100.0,                       (optional; must match --stack)
+//!   "rake_pct": 0.0,                         (optional; must match --rake)
+//!   "sizing": { "open_size_bb": 2.5,         (optional; default sizing)
+//!               "three_bet_multiplier": 3.0,
+//!               "four_bet_multiplier": 2.5 },
+//!   "forced_bets": { "ante_bb": 0.125,       (optional; no antes or straddle)
+//!                    "straddle_bb": 2.0 },
+//!   "iterations": 50000,                     (below 1000, `gto query` won't use it)
+//!   "hands": ["AA", "AKs", ...],             (optional; the 169-hand order below)
+//!   "spots": [
+//!     { "opener": "UTG", "responder": "HJ",
+//!       "open": [...],                        opener: open vs fold
+//!       "three_bet": [...], "call_vs_open": [...],
+//!       "four_bet": [...], "call_vs_three_bet": [...],
+//!       "all_in": [...], "call_vs_four_bet": [...],
+//!       "call_vs_all_in": [...],              opener: call the 5-bet shove
+//!       "exploitability": 0.01, "iterations": 50000 }   (optional)
+//!   ]
+//! }
+//! ```
+//!
+//! Every frequency array has 169 entries in [0, 1], indexed like `hands`: the
+//! 13×13 grid row by row from AA, suited above the diagonal (AKs, AQs, ...)
+//! and offsuit below it. Fold is what's left, so the two frequencies of a node
+//! can't add up to more than 1. All 15 6-max spots must be present; with a
+//! straddle UTG acts last preflop, so it responds to every open (BB vs UTG is a
+//! spot) instead of opening.
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_tree::{bucket_to_hand, NUM_HANDS};
+use crate::preflop_solver::{ForcedBets, Position, PreflopSizing, PreflopSolution, PreflopSpotResult, PREFLOP_CACHE_VERSION};
+
+pub const FORMAT: &str = "gto-preflop-ranges";
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Slack for two frequencies of a node adding up to just over 1.
+const SUM_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortablePreflop {
+    pub format: String,
+    pub schema_version: u32,
+    pub table_size: String,
+    #[serde(default)]
+    pub stack_bb: Option<f64>,
+    #[serde(default)]
+    pub rake_pct: Option<f64>,
+    #[serde(default)]
+    pub sizing: PreflopSizing,
+    #[serde(default, skip_serializing_if = "is_default_forced_bets")]
+    pub forced_bets: ForcedBets,
+    pub iterations: usize,
+    #[serde(default)]
+    pub hands: Vec<String>,
+    pub spots: Vec<PortableSpot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortableSpot {
+    pub opener: Position,
+    pub responder: Position,
+    pub open: Vec<f64>,
+    pub three_bet: Vec<f64>,
+    pub call_vs_open: Vec<f64>,
+    pub four_bet: Vec<f64>,
+    pub call_vs_three_bet: Vec<f64>,
+    pub all_in: Vec<f64>,
+    pub call_vs_four_bet: Vec<f64>,
+    pub call_vs_all_in: Vec<f64>,
+    #[serde(default)]
+    pub exploitability: f64,
+    #[serde(default)]
+    pub iterations: usize,
+}
+
+fn is_default_forced_bets(forced: &ForcedBets) -> bool {
+    *forced == ForcedBets::default()
+}
+
+/// The 169 hands in strategy-array order.
+pub fn hand_order() -> Vec<String> {
+    (0..NUM_HANDS).map(bucket_to_hand).collect()
+}
+
+/// `solution` in the portable format.
+pub fn export(solution: &PreflopSolution) -> PortablePreflop {
+    PortablePreflop {
+        format: FORMAT.to_string(),
+        schema_version: SCHEMA_VERSION,
+        table_size: solution.table_size.clone(),
+        stack_bb: Some(solution.stack_bb),
+        rake_pct: Some(solution.rake_pct),
+        sizing: solution.sizing,
+        forced_bets: solution.forced_bets,
+        iterations: solution.iterations,
+        hands: hand_order(),
+        spots: solution
+            .spots
+            .iter()
+            .map(|s| PortableSpot {
+                opener: s.opener,
+                responder: s.responder,
+                open: s.open_strategy.clone(),
+                three_bet: s.vs_open_3bet.clone(),
+                call_vs_open: s.vs_open_call.clone(),
+                four_bet: s.vs_3bet_4bet.clone(),
+                call_vs_three_bet: s.vs_3bet_call.clone(),
+                all_in: s.vs_4bet_allin.clone(),
+                call_vs_four_bet: s.vs_4bet_call.clone(),
+                call_vs_all_in: s.vs_5bet_call.clone(),
+                exploitability: s.exploitability,
+                iterations: s.iterations,
+            })
+            .collect(),
+    }
+}
+
+/// Write `solution` to `path` as portable JSON.
+pub fn write_export(solution: &PreflopSolution, path: &std::path::Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&export(solution)).map_err(|e| e.to_string())?;
+    std::fs::write(path, json + "\n").map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Parse and validate portable JSON as a solution for `stack_bb` and
+/// `rake_pct`, with spots in the solver's order.
+pub fn import(json: &str, stack_bb: f64, rake_pct: f64) -> Result<PreflopSolution, String> {
+    let file: PortablePreflop = serde_json::from_str(json).map_err(|e| format!("Not a preflop ranges file: {}", e))?;
+    if file.format != FORMAT {
+        return Err(format!("Expected format \"{}\", got \"{}\"", FORMAT, file.format));
+    }
+    if file.schema_version != SCHEMA_VERSION {
+        return Err(format!("Unsupported schema_version {} (expected {})", file.schema_version, SCHEMA_VERSION));
+    }
+    if file.table_size != "6max" {
+        return Err(format!("Only 6max ranges can be imported, not {}", file.table_size));
+    }
+    if file.stack_bb.is_some_and(|s| s != stack_bb) {
+        return Err(format!("File is for {}bb, not {}bb", file.stack_bb.unwrap_or_default(), stack_bb));
+    }
+    if file.rake_pct.is_some_and(|r| r != rake_pct) {
+        return Err(format!("File is for {}% rake, not {}%", file.rake_pct.unwrap_or_default(), rake_pct));
+    }
+    let sizing = file.sizing;
+    PreflopSizing::new(sizing.open_size_bb, sizing.three_bet_multiplier, sizing.four_bet_multiplier, stack_bb)?;
+    let forced = ForcedBets::new(file.forced_bets.ante_bb, file.forced_bets.straddle_bb, stack_bb)?;
+    if !file.hands.is_empty() && file.hands != hand_order() {
+        return Err("`hands` isn't in the 169-hand order this format uses".to_string());
+    }
+
+    let mut spots = Vec::with_capacity(file.spots.len());
+    let expected = forced.spots();
+    for &(opener, responder) in &expected {
+        let mut matching = file.spots.iter().filter(|s| s.opener == opener && s.responder == responder);
+        let spot = matching.next().ok_or_else(|| format!("Missing spot {} vs {}", opener, responder))?;
+        if matching.next().is_some() {
+            return Err(format!("Spot {} vs {} appears more than once", opener, responder));
+        }
+        spots.push(import_spot(spot)?);
+    }
+    if let Some(extra) = file.spots.iter().find(|s| !expected.contains(&(s.opener, s.responder))) {
+        return Err(format!("{} vs {} isn't a 6max spot", extra.opener, extra.responder));
+    }
+
+    Ok(PreflopSolution {
+        version: PREFLOP_CACHE_VERSION,
+        table_size: file.table_size,
+        stack_bb,
+        rake_pct,
+        sizing,
+        forced_bets: forced,
+        iterations: file.iterations,
+        spots,
+    })
+}
+
+fn import_spot(spot: &PortableSpot) -> Result<PreflopSpotResult, String> {
+    let name = format!("{} vs {}", spot.opener, spot.responder);
+    let arrays: [(&str, &Vec<f64>); 8] = [
+        ("open", &spot.open),
+        ("three_bet", &spot.three_bet),
+        ("call_vs_open", &spot.call_vs_open),
+        ("four_bet", &spot.four_bet),
+        ("call_vs_three_bet", &spot.call_vs_three_bet),
+        ("all_in", &spot.all_in),
+        ("call_vs_four_bet", &spot.call_vs_four_bet),
+        ("call_vs_all_in", &spot.call_vs_all_in),
+    ];
+    for (field, values) in arrays {
+        if values.len() != NUM_HANDS {
+            return Err(format!("{}: `{}` has {} entries, expected {}", name, field, values.len(), NUM_HANDS));
+        }
+        if let Some(i) = values.iter().position(|v| !(0.0..=1.0).contains(v)) {
+            return Err(format!("{}: `{}` for {} is {}, outside [0, 1]", name, field, bucket_to_hand(i), values[i]));
+        }
+    }
+    let pairs = [
+        ("three_bet", &spot.three_bet, "call_vs_open", &spot.call_vs_open),
+        ("four_bet", &spot.four_bet, "call_vs_three_bet", &spot.call_vs_three_bet),
+        ("all_in", &spot.all_in, "call_vs_four_bet", &spot.call_vs_four_bet),
+    ];
+    for (a_name, a, b_name, b) in pairs {
+        if let Some(i) = (0..NUM_HANDS).find(|&i| a[i] + b[i] > 1.0 + SUM_TOLERANCE) {
+            return Err(format!("{}: `{}` + `{}` for {} is more than 1", name, a_name, b_name, bucket_to_hand(i)));
+        }
+    }
+    Ok(PreflopSpotResult {
+        opener: spot.opener,
+        responder: spot.responder,
+        open_strategy: spot.open.clone(),
+        vs_open_3bet: spot.three_bet.clone(),
+        vs_open_call: spot.call_vs_open.clone(),
+        vs_3bet_4bet: spot.four_bet.clone(),
+        vs_3bet_call: spot.call_vs_three_bet.clone(),
+        vs_4bet_allin: spot.all_in.clone(),
+        vs_4bet_call: spot.call_vs_four_bet.clone(),
+        vs_5bet_call: spot.call_vs_all_in.clone(),
+        exploitability: spot.exploitability,
+        iterations: spot.iterations,
+        evs: None,
+    })
+}
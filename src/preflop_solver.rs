@@ -3,6 +3,8 @@
 //! Solves 15 independent 2-player spots for 6-max using CFR+.
 //! Each spot is an (opener, responder) position pair with a 5-node game tree:
 //!
+//! With the default sizing (`PreflopSizing`):
+//!
 //! ```text
 //! Node 100 (Opener): Open 2.5bb / Fold
 //!   └─ Open → Node 101 (Responder): 3-Bet 7.5bb / Call 2.5bb / Fold
@@ -12,6 +14,9 @@
 //!        ├─ Call → Terminal (showdown with equity realization)
 //!        └─ Fold → Terminal (opener wins blinds)
 //! ```
+//!
+//! Antes and a straddle (`ForcedBets`) are dead money in every pot; a
+//! straddling UTG posts a bigger blind and acts last preflop.
 
 use serde::{Deserialize, Serialize};
 
@@ -131,6 +136,190 @@ pub fn all_6max_spots() -> Vec<(Position, Position)> {
     ]
 }
 
+// ---------------------------------------------------------------------------
+// Bet sizing
+// ---------------------------------------------------------------------------
+
+/// Raise sizes of the preflop tree. The 3-bet is a multiple of the open and
+/// the 4-bet a multiple of the 3-bet; the 5-bet is always all-in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PreflopSizing {
+    pub open_size_bb: f64,
+    pub three_bet_multiplier: f64,
+    pub four_bet_multiplier: f64,
+}
+
+impl Default for PreflopSizing {
+    fn default() -> Self {
+        PreflopSizing { open_size_bb: 2.5, three_bet_multiplier: 3.0, four_bet_multiplier: 2.5 }
+    }
+}
+
+impl PreflopSizing {
+    /// Validated sizing: the open must be a raise over the big blind that
+    /// leaves chips behind, and each multiplier must raise. A 3-bet or 4-bet
+    /// past the stack is played as all-in (see `PreflopPayoffs`).
+    pub fn new(
+        open_size_bb: f64,
+        three_bet_multiplier: f64,
+        four_bet_multiplier: f64,
+        stack_bb: f64,
+    ) -> Result<Self, String> {
+        if open_size_bb.is_nan() || open_size_bb <= 1.0 {
+            return Err(format!("Open size must be more than 1bb, got {}", open_size_bb));
+        }
+        if [three_bet_multiplier, four_bet_multiplier].iter().any(|m| m.is_nan() || *m <= 1.0) {
+            return Err("3-bet and 4-bet multipliers must be more than 1".to_string());
+        }
+        if open_size_bb >= stack_bb {
+            return Err(format!("A {}bb open is all-in with a {}bb stack", open_size_bb, stack_bb));
+        }
+        Ok(PreflopSizing { open_size_bb, three_bet_multiplier, four_bet_multiplier })
+    }
+
+    /// The default sizing with the open scaled to a straddle, as a
+    /// straddled game plays it.
+    pub fn default_for(forced: &ForcedBets) -> Self {
+        let default = PreflopSizing::default();
+        PreflopSizing { open_size_bb: default.open_size_bb * forced.biggest_blind(), ..default }
+    }
+
+    pub fn three_bet_size(&self) -> f64 {
+        self.open_size_bb * self.three_bet_multiplier
+    }
+
+    pub fn four_bet_size(&self) -> f64 {
+        self.three_bet_size() * self.four_bet_multiplier
+    }
+
+    /// "2.5bb open, 3x 3-bet, 2.5x 4-bet", for display headers.
+    pub fn label(&self) -> String {
+        format!(
+            "{}bb open, {}x 3-bet, {}x 4-bet",
+            self.open_size_bb, self.three_bet_multiplier, self.four_bet_multiplier
+        )
+    }
+
+    /// Cache file name suffix; empty for the default sizing, so solutions
+    /// saved before sizing was configurable still load.
+    fn cache_suffix(&self) -> String {
+        if *self == PreflopSizing::default() {
+            String::new()
+        } else {
+            format!("_open{}_3b{}x_4b{}x", self.open_size_bb, self.three_bet_multiplier, self.four_bet_multiplier)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Antes and straddle
+// ---------------------------------------------------------------------------
+
+/// Players at a 6-max table, each posting the ante.
+const TABLE_PLAYERS: f64 = 6.0;
+
+/// Forced bets on top of the blinds: an ante from every player and an
+/// optional UTG straddle, both in bb.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ForcedBets {
+    pub ante_bb: f64,
+    /// 0 for no straddle.
+    pub straddle_bb: f64,
+}
+
+impl ForcedBets {
+    /// Validated forced bets: a non-negative ante and, when straddled, a
+    /// straddle of more than the big blind that leaves a stack to play.
+    pub fn new(ante_bb: f64, straddle_bb: f64, stack_bb: f64) -> Result<Self, String> {
+        if ante_bb.is_nan() || !(0.0..1.0).contains(&ante_bb) {
+            return Err(format!("Ante must be at least 0 and under 1bb, got {}", ante_bb));
+        }
+        if straddle_bb.is_nan() || (straddle_bb != 0.0 && straddle_bb <= 1.0) {
+            return Err(format!("Straddle must be more than 1bb, got {}", straddle_bb));
+        }
+        if straddle_bb + ante_bb >= stack_bb {
+            return Err(format!("A {}bb straddle leaves nothing to play with a {}bb stack", straddle_bb, stack_bb));
+        }
+        Ok(ForcedBets { ante_bb, straddle_bb })
+    }
+
+    pub fn is_straddled(&self) -> bool {
+        self.straddle_bb > 0.0
+    }
+
+    /// Blind posted by `pos` (in bb): the straddle for UTG when straddled.
+    pub fn blind(&self, pos: Position) -> f64 {
+        match pos {
+            Position::UTG if self.is_straddled() => self.straddle_bb,
+            _ => pos.blind_amount(),
+        }
+    }
+
+    /// The biggest blind, which an open has to raise.
+    pub fn biggest_blind(&self) -> f64 {
+        self.straddle_bb.max(1.0)
+    }
+
+    /// Everything in the pot before the first voluntary action.
+    pub fn total_posted(&self) -> f64 {
+        1.5 + self.straddle_bb + self.ante_bb * TABLE_PLAYERS
+    }
+
+    /// Money in the pot beyond the standard 1.5bb of blinds.
+    pub fn extra_dead_money(&self) -> f64 {
+        self.total_posted() - 1.5
+    }
+
+    /// `extra_dead_money` in a pot between `a` and `b`: the antes, and the
+    /// straddle unless one of them posted it, as it's then part of their bet.
+    pub fn extra_dead_money_between(&self, a: Position, b: Position) -> f64 {
+        let in_pot = [a, b].contains(&Position::UTG) && self.is_straddled();
+        self.extra_dead_money() - if in_pot { self.straddle_bb } else { 0.0 }
+    }
+
+    /// The 15 (opener, responder) spots. A straddler acts last preflop, so
+    /// action starts at HJ and UTG responds to every open instead of opening.
+    pub fn spots(&self) -> Vec<(Position, Position)> {
+        if !self.is_straddled() {
+            return all_6max_spots();
+        }
+        use Position::*;
+        let order = [HJ, CO, BTN, SB, BB, UTG];
+        let mut spots = Vec::new();
+        for (i, &opener) in order.iter().enumerate() {
+            for &responder in &order[i + 1..] {
+                spots.push((opener, responder));
+            }
+        }
+        spots
+    }
+
+    /// "0.125bb ante, 2bb straddle", for display headers; empty without either.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ante_bb > 0.0 {
+            parts.push(format!("{}bb ante", self.ante_bb));
+        }
+        if self.is_straddled() {
+            parts.push(format!("{}bb straddle", self.straddle_bb));
+        }
+        parts.join(", ")
+    }
+
+    /// Cache file name suffix; empty without antes or a straddle, so
+    /// solutions saved before they were configurable still load.
+    fn cache_suffix(&self) -> String {
+        let mut suffix = String::new();
+        if self.ante_bb > 0.0 {
+            suffix.push_str(&format!("_ante{}", self.ante_bb));
+        }
+        if self.is_straddled() {
+            suffix.push_str(&format!("_straddle{}", self.straddle_bb));
+        }
+        suffix
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Payoff model
 // ---------------------------------------------------------------------------
@@ -142,7 +331,7 @@ pub struct PreflopPayoffs {
     pub dead_money: f64,     // blinds from players not in the spot
     pub opener_blind: f64,   // blind posted by opener
     pub responder_blind: f64,// blind posted by responder
-    pub open_size: f64,      // 2.5bb
+    pub open_size: f64,      // 2.5bb by default
     pub three_bet_size: f64, // 7.5bb (3x open)
     pub four_bet_size: f64,  // 18.75bb (2.5x 3-bet)
     pub ip_is_opener: bool,  // whether opener is IP
@@ -151,11 +340,37 @@ pub struct PreflopPayoffs {
 
 impl PreflopPayoffs {
     pub fn new(opener: Position, responder: Position, stack_bb: f64, rake_pct: f64) -> Self {
-        let opener_blind = opener.blind_amount();
-        let responder_blind = responder.blind_amount();
+        Self::with_sizing(opener, responder, stack_bb, rake_pct, &PreflopSizing::default())
+    }
+
+    pub fn with_sizing(
+        opener: Position,
+        responder: Position,
+        stack_bb: f64,
+        rake_pct: f64,
+        sizing: &PreflopSizing,
+    ) -> Self {
+        Self::with_forced_bets(opener, responder, stack_bb, rake_pct, sizing, &ForcedBets::default())
+    }
 
-        // Dead money = total blinds (1.5bb) minus what opener and responder post
-        let dead_money = 1.5 - opener_blind - responder_blind;
+    /// Payoffs with antes and a straddle. Payoffs count from after the
+    /// antes, so every ante (the players' own too) is dead money in the pot
+    /// and `stack_bb` less the ante is what's left to bet.
+    pub fn with_forced_bets(
+        opener: Position,
+        responder: Position,
+        stack_bb: f64,
+        rake_pct: f64,
+        sizing: &PreflopSizing,
+        forced: &ForcedBets,
+    ) -> Self {
+        let stack_bb = stack_bb - forced.ante_bb;
+        let opener_blind = forced.blind(opener);
+        let responder_blind = forced.blind(responder);
+
+        // Dead money = everything posted (blinds, straddle, antes) minus the
+        // blinds opener and responder post
+        let dead_money = forced.total_posted() - opener_blind - responder_blind;
 
         // IP determination: in general, opener is earlier position,
         // but SB vs BB is special (BB is IP postflop)
@@ -167,9 +382,10 @@ impl PreflopPayoffs {
             dead_money,
             opener_blind,
             responder_blind,
-            open_size: 2.5,
-            three_bet_size: 7.5,
-            four_bet_size: 18.75,
+            // A raise past the stack is an all-in
+            open_size: sizing.open_size_bb.min(stack_bb),
+            three_bet_size: sizing.three_bet_size().min(stack_bb),
+            four_bet_size: sizing.four_bet_size().min(stack_bb),
             ip_is_opener,
             eq_realization: 0.95,
         }
@@ -287,6 +503,33 @@ pub struct PreflopSpotResult {
     pub vs_5bet_call: Vec<f64>,
     pub exploitability: f64,
     pub iterations: usize,
+    /// Action EVs under the average strategies; `None` in solutions saved
+    /// before they were stored and in imported charts.
+    #[serde(default)]
+    pub evs: Option<PreflopSpotEvs>,
+}
+
+/// Each hand's EV in bb of every action at the five nodes, under a spot's
+/// average strategies. EVs count from the start of the hand (a folded blind
+/// is lost) and are conditional on reaching the node; actions are in node
+/// order, fold last.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreflopSpotEvs {
+    /// Node 100: [open, fold]
+    pub open: Vec<[f64; 2]>,
+    /// Node 101: [3-bet, call, fold]
+    pub vs_open: Vec<[f64; 3]>,
+    /// Node 102: [4-bet, call, fold]
+    pub vs_3bet: Vec<[f64; 3]>,
+    /// Node 103: [all-in, call, fold]
+    pub vs_4bet: Vec<[f64; 3]>,
+    /// Node 104: [call, fold]
+    pub vs_5bet: Vec<[f64; 2]>,
+}
+
+/// A node's action EVs (fold last) measured from folding, so fold is 0.
+pub fn evs_over_fold<const N: usize>(evs: [f64; N]) -> [f64; N] {
+    evs.map(|ev| ev - evs[N - 1])
 }
 
 impl PreflopSpotResult {
@@ -306,7 +549,8 @@ impl PreflopSpotResult {
     }
 }
 
-fn weighted_pct(strategy: &[f64]) -> f64 {
+/// Percentage of all combos a 169-hand strategy plays.
+pub fn weighted_pct(strategy: &[f64]) -> f64 {
     let mut total_combos = 0.0;
     let mut action_combos = 0.0;
     for i in 0..NUM_HANDS {
@@ -325,7 +569,8 @@ fn weighted_pct(strategy: &[f64]) -> f64 {
 // Core solver
 // ---------------------------------------------------------------------------
 
-/// Solve a single preflop spot (one opener vs one responder).
+/// Solve a single preflop spot (one opener vs one responder) at the default
+/// sizing.
 pub fn solve_preflop_spot(
     opener: Position,
     responder: Position,
@@ -334,7 +579,42 @@ pub fn solve_preflop_spot(
     rake_pct: f64,
     table: &EquityTable,
 ) -> PreflopSpotResult {
-    let payoffs = PreflopPayoffs::new(opener, responder, stack_bb, rake_pct);
+    solve_preflop_spot_sized(opener, responder, stack_bb, iterations, rake_pct, &PreflopSizing::default(), table)
+}
+
+/// Solve a single preflop spot with the given raise sizes.
+pub fn solve_preflop_spot_sized(
+    opener: Position,
+    responder: Position,
+    stack_bb: f64,
+    iterations: usize,
+    rake_pct: f64,
+    sizing: &PreflopSizing,
+    table: &EquityTable,
+) -> PreflopSpotResult {
+    solve_preflop_spot_to(opener, responder, stack_bb, iterations, None, rake_pct, sizing, &ForcedBets::default(), table)
+}
+
+/// Iterations between exploitability checks when solving to a target.
+const TARGET_CHECK_INTERVAL: usize = 1_000;
+
+/// Solve a single preflop spot until its exploitability is at most
+/// `target_bb` (checked every `TARGET_CHECK_INTERVAL` iterations) or
+/// `max_iterations` have run. Without a target or forced bets this is
+/// `solve_preflop_spot_sized`; `iterations` on the result is the count run.
+#[allow(clippy::too_many_arguments)]
+pub fn solve_preflop_spot_to(
+    opener: Position,
+    responder: Position,
+    stack_bb: f64,
+    max_iterations: usize,
+    target_bb: Option<f64>,
+    rake_pct: f64,
+    sizing: &PreflopSizing,
+    forced: &ForcedBets,
+    table: &EquityTable,
+) -> PreflopSpotResult {
+    let payoffs = PreflopPayoffs::with_forced_bets(opener, responder, stack_bb, rake_pct, sizing, forced);
     let mut trainer = CfrTrainer::new();
 
     // Pre-create all info sets.
@@ -348,50 +628,77 @@ pub fn solve_preflop_spot(
     }
 
     // Run CFR+ iterations.
-    for _ in 0..iterations {
-        preflop_cfr_iteration(&mut trainer, table, &payoffs);
+    let mut iterations = 0;
+    while iterations < max_iterations {
+        let batch = match target_bb {
+            Some(_) => TARGET_CHECK_INTERVAL.min(max_iterations - iterations),
+            None => max_iterations,
+        };
+        for _ in 0..batch {
+            preflop_cfr_iteration(&mut trainer, table, &payoffs);
+        }
+        iterations += batch;
+        if let Some(target) = target_bb {
+            if iterations < max_iterations && spot_result(&trainer, opener, responder, table, &payoffs, iterations).exploitability <= target {
+                break;
+            }
+        }
     }
 
-    // Extract average strategies.
-    let mut open_strategy = vec![0.0; NUM_HANDS];
-    let mut vs_open_3bet = vec![0.0; NUM_HANDS];
-    let mut vs_open_call = vec![0.0; NUM_HANDS];
-    let mut vs_3bet_4bet = vec![0.0; NUM_HANDS];
-    let mut vs_3bet_call = vec![0.0; NUM_HANDS];
-    let mut vs_4bet_allin = vec![0.0; NUM_HANDS];
-    let mut vs_4bet_call = vec![0.0; NUM_HANDS];
-    let mut vs_5bet_call = vec![0.0; NUM_HANDS];
-
-    for h in 0..NUM_HANDS {
-        let hb = h as u16;
-
-        let s = trainer.get_average_strategy(&InfoSetKey { hand_bucket: hb, node_id: NODE_OPEN }, ACTIONS_OPEN);
-        open_strategy[h] = s[0];
-
-        let s = trainer.get_average_strategy(&InfoSetKey { hand_bucket: hb, node_id: NODE_VS_OPEN }, ACTIONS_VS_OPEN);
-        vs_open_3bet[h] = s[0];
-        vs_open_call[h] = s[1];
-
-        let s = trainer.get_average_strategy(&InfoSetKey { hand_bucket: hb, node_id: NODE_VS_3BET }, ACTIONS_VS_3BET);
-        vs_3bet_4bet[h] = s[0];
-        vs_3bet_call[h] = s[1];
-
-        let s = trainer.get_average_strategy(&InfoSetKey { hand_bucket: hb, node_id: NODE_VS_4BET }, ACTIONS_VS_4BET);
-        vs_4bet_allin[h] = s[0];
-        vs_4bet_call[h] = s[1];
+    spot_result(&trainer, opener, responder, table, &payoffs, iterations)
+}
 
-        let s = trainer.get_average_strategy(&InfoSetKey { hand_bucket: hb, node_id: NODE_VS_5BET }, ACTIONS_VS_5BET);
-        vs_5bet_call[h] = s[0];
-    }
+/// The average strategies of `trainer`, their exploitability and the action
+/// EVs under them.
+fn spot_result(
+    trainer: &CfrTrainer,
+    opener: Position,
+    responder: Position,
+    table: &EquityTable,
+    payoffs: &PreflopPayoffs,
+    iterations: usize,
+) -> PreflopSpotResult {
+    // Extract average strategies.
+    let average = |node_id: u16, actions: usize| -> Vec<Vec<f64>> {
+        (0..NUM_HANDS)
+            .map(|h| trainer.get_average_strategy(&InfoSetKey { hand_bucket: h as u16, node_id }, actions))
+            .collect()
+    };
+    let avg_100: Vec<[f64; 2]> = average(NODE_OPEN, ACTIONS_OPEN).iter().map(|s| [s[0], s[1]]).collect();
+    let avg_101: Vec<[f64; 3]> = average(NODE_VS_OPEN, ACTIONS_VS_OPEN).iter().map(|s| [s[0], s[1], s[2]]).collect();
+    let avg_102: Vec<[f64; 3]> = average(NODE_VS_3BET, ACTIONS_VS_3BET).iter().map(|s| [s[0], s[1], s[2]]).collect();
+    let avg_103: Vec<[f64; 3]> = average(NODE_VS_4BET, ACTIONS_VS_4BET).iter().map(|s| [s[0], s[1], s[2]]).collect();
+    let avg_104: Vec<[f64; 2]> = average(NODE_VS_5BET, ACTIONS_VS_5BET).iter().map(|s| [s[0], s[1]]).collect();
+
+    let open_strategy: Vec<f64> = avg_100.iter().map(|s| s[0]).collect();
+    let vs_open_3bet: Vec<f64> = avg_101.iter().map(|s| s[0]).collect();
+    let vs_open_call: Vec<f64> = avg_101.iter().map(|s| s[1]).collect();
+    let vs_3bet_4bet: Vec<f64> = avg_102.iter().map(|s| s[0]).collect();
+    let vs_3bet_call: Vec<f64> = avg_102.iter().map(|s| s[1]).collect();
+    let vs_4bet_allin: Vec<f64> = avg_103.iter().map(|s| s[0]).collect();
+    let vs_4bet_call: Vec<f64> = avg_103.iter().map(|s| s[1]).collect();
+    let vs_5bet_call: Vec<f64> = avg_104.iter().map(|s| s[0]).collect();
 
     let exploitability = compute_preflop_exploitability(
         &open_strategy, &vs_open_3bet, &vs_open_call,
         &vs_3bet_4bet, &vs_3bet_call,
         &vs_4bet_allin, &vs_4bet_call,
         &vs_5bet_call,
-        table, &payoffs,
+        table, payoffs,
     );
 
+    // One evaluation pass of the average profile.
+    let mut evs = PreflopSpotEvs::default();
+    for h in 0..NUM_HANDS {
+        let (open, vs_3bet, vs_5bet) = opener_action_evs(h, &avg_101, &avg_103, &avg_102[h], &avg_104[h], table, payoffs);
+        let (vs_open, vs_4bet) = responder_action_evs(h, &avg_100, &avg_102, &avg_104, &avg_101[h], &avg_103[h], table, payoffs);
+        evs.open.push(open);
+        evs.vs_open.push(vs_open);
+        evs.vs_3bet.push(vs_3bet);
+        evs.vs_4bet.push(vs_4bet);
+        evs.vs_5bet.push(vs_5bet);
+    }
+
     PreflopSpotResult {
         opener,
         responder,
@@ -405,6 +712,7 @@ pub fn solve_preflop_spot(
         vs_5bet_call,
         exploitability,
         iterations,
+        evs: Some(evs),
     }
 }
 
@@ -421,7 +729,6 @@ fn preflop_cfr_iteration(
             [s[0], s[1], s[2]]
         })
         .collect();
-
     let resp_103: Vec<[f64; 3]> = (0..NUM_HANDS)
         .map(|h| {
             let s = trainer.get_strategy(&InfoSetKey { hand_bucket: h as u16, node_id: NODE_VS_4BET }, ACTIONS_VS_4BET);
@@ -446,119 +753,26 @@ fn preflop_cfr_iteration(
         .collect();
 
     for op in 0..NUM_HANDS {
+        let (evs_100, evs_102, evs_104) =
+            opener_action_evs(op, &resp_101, &resp_103, &opener_102[op], &opener_104[op], table, payoffs);
+
         let op_key_100 = InfoSetKey { hand_bucket: op as u16, node_id: NODE_OPEN };
         let op_strat_100 = trainer.get_strategy(&op_key_100, ACTIONS_OPEN);
-
-        // Compute EV of opening vs folding at node 100
-        let fold_ev_100 = payoffs.opener_folds_pre();
-        let mut open_ev = 0.0;
-        let mut total_w = 0.0;
-
-        for resp in 0..NUM_HANDS {
-            let w = table.weight(op, resp);
-            if w < 1e-10 { continue; }
-            total_w += w;
-
-            let eq = table.eq(op, resp);
-
-            // Responder actions at node 101: 3-bet, call, fold
-            let r_3bet = resp_101[resp][0];
-            let r_call = resp_101[resp][1];
-            let r_fold = resp_101[resp][2];
-
-            // EV when responder folds
-            let ev_resp_fold = payoffs.responder_folds_to_open();
-
-            // EV when responder calls (flat)
-            let ev_resp_call = payoffs.flat_call_showdown(eq);
-
-            // EV when responder 3-bets → go to node 102
-            let ev_resp_3bet = compute_ev_after_3bet(
-                eq, &opener_102[op], &resp_103[resp], &opener_104[op], payoffs,
-            );
-
-            let ev_open_vs_resp = r_fold * ev_resp_fold + r_call * ev_resp_call + r_3bet * ev_resp_3bet;
-            open_ev += w * ev_open_vs_resp;
-        }
-
-        if total_w > 0.0 {
-            open_ev /= total_w;
-        }
-
-        let node_value_100 = op_strat_100[0] * open_ev + op_strat_100[1] * fold_ev_100;
+        let node_value_100 = op_strat_100[0] * evs_100[0] + op_strat_100[1] * evs_100[1];
         let data = trainer.get_or_create(&op_key_100, ACTIONS_OPEN);
-        data.update(&[open_ev, fold_ev_100], node_value_100, 1.0);
+        data.update(&evs_100, node_value_100, 1.0);
 
-        // --- Update node 102 (opener vs 3-bet) ---
-        // EV is conditional on reaching node 102 (responder 3-bet)
         let op_key_102 = InfoSetKey { hand_bucket: op as u16, node_id: NODE_VS_3BET };
         let op_strat_102 = opener_102[op];
-
-        let mut fourbet_ev = 0.0;
-        let mut call3bet_ev = 0.0;
-        let fold3bet_ev = payoffs.opener_folds_to_3bet();
-        let mut total_w_102 = 0.0;
-
-        for resp in 0..NUM_HANDS {
-            let w = table.weight(op, resp);
-            if w < 1e-10 { continue; }
-            let r_3bet = resp_101[resp][0];
-            if r_3bet < 1e-10 { continue; }
-            let wt = w * r_3bet;
-            total_w_102 += wt;
-
-            let eq = table.eq(op, resp);
-
-            // Call 3-bet → showdown
-            call3bet_ev += wt * payoffs.call_3bet_showdown(eq);
-
-            // 4-bet → node 103
-            let ev_4bet = compute_ev_after_4bet(eq, &resp_103[resp], &opener_104[op], payoffs);
-            fourbet_ev += wt * ev_4bet;
-        }
-
-        if total_w_102 > 0.0 {
-            fourbet_ev /= total_w_102;
-            call3bet_ev /= total_w_102;
-        }
-
-        let node_value_102 = op_strat_102[0] * fourbet_ev + op_strat_102[1] * call3bet_ev + op_strat_102[2] * fold3bet_ev;
+        let node_value_102 = op_strat_102[0] * evs_102[0] + op_strat_102[1] * evs_102[1] + op_strat_102[2] * evs_102[2];
         let data = trainer.get_or_create(&op_key_102, ACTIONS_VS_3BET);
-        data.update(&[fourbet_ev, call3bet_ev, fold3bet_ev], node_value_102, 1.0);
+        data.update(&evs_102, node_value_102, 1.0);
 
-        // --- Update node 104 (opener vs 5-bet/all-in) ---
         let op_key_104 = InfoSetKey { hand_bucket: op as u16, node_id: NODE_VS_5BET };
         let op_strat_104 = opener_104[op];
-
-        let mut call5bet_ev = 0.0;
-        let fold5bet_ev = payoffs.opener_folds_to_5bet();
-        let mut total_w_104 = 0.0;
-
-        for resp in 0..NUM_HANDS {
-            let w = table.weight(op, resp);
-            if w < 1e-10 { continue; }
-            let r_3bet = resp_101[resp][0];
-            if r_3bet < 1e-10 { continue; }
-            let r_allin = resp_103[resp][0];
-            if r_allin < 1e-10 { continue; }
-            // Also need opener's 4-bet prob to reach this node
-            let op_4bet = opener_102[op][0];
-            if op_4bet < 1e-10 { continue; }
-
-            let wt = w * r_3bet * op_4bet * r_allin;
-            total_w_104 += wt;
-
-            let eq = table.eq(op, resp);
-            call5bet_ev += wt * payoffs.allin_showdown(eq);
-        }
-
-        if total_w_104 > 0.0 {
-            call5bet_ev /= total_w_104;
-        }
-
-        let node_value_104 = op_strat_104[0] * call5bet_ev + op_strat_104[1] * fold5bet_ev;
+        let node_value_104 = op_strat_104[0] * evs_104[0] + op_strat_104[1] * evs_104[1];
         let data = trainer.get_or_create(&op_key_104, ACTIONS_VS_5BET);
-        data.update(&[call5bet_ev, fold5bet_ev], node_value_104, 1.0);
+        data.update(&evs_104, node_value_104, 1.0);
     }
 
     // --- Now snapshot opener strategies for responder update ---
@@ -585,113 +799,243 @@ fn preflop_cfr_iteration(
 
     // --- Update responder nodes (101, 103) ---
     for resp in 0..NUM_HANDS {
-        // --- Node 101: Responder vs open ---
+        let (evs_101, evs_103) = responder_action_evs(
+            resp, &opener_100, &opener_102_new, &opener_104_new, &resp_101[resp], &resp_103[resp], table, payoffs,
+        );
+
         let resp_key_101 = InfoSetKey { hand_bucket: resp as u16, node_id: NODE_VS_OPEN };
         let resp_strat_101 = resp_101[resp];
+        let node_value_101 = resp_strat_101[0] * evs_101[0] + resp_strat_101[1] * evs_101[1] + resp_strat_101[2] * evs_101[2];
+        let data = trainer.get_or_create(&resp_key_101, ACTIONS_VS_OPEN);
+        data.update(&evs_101, node_value_101, 1.0);
 
-        let mut threebet_ev = 0.0;
-        let mut call_ev = 0.0;
-        let resp_fold_ev = -payoffs.responder_blind;
+        let resp_key_103 = InfoSetKey { hand_bucket: resp as u16, node_id: NODE_VS_4BET };
+        let resp_strat_103 = resp_103[resp];
+        let node_value_103 = resp_strat_103[0] * evs_103[0] + resp_strat_103[1] * evs_103[1] + resp_strat_103[2] * evs_103[2];
+        let data = trainer.get_or_create(&resp_key_103, ACTIONS_VS_4BET);
+        data.update(&evs_103, node_value_103, 1.0);
+    }
+}
 
-        let mut total_w_101 = 0.0;
+/// Opener hand `op`'s action EVs against the responder's strategies: node
+/// 100 [open, fold], node 102 [4-bet, call, fold] and node 104 [call, fold].
+/// Each is conditional on reaching its node.
+fn opener_action_evs(
+    op: usize,
+    resp_101: &[[f64; 3]],
+    resp_103: &[[f64; 3]],
+    opener_102: &[f64; 3],
+    opener_104: &[f64; 2],
+    table: &EquityTable,
+    payoffs: &PreflopPayoffs,
+) -> ([f64; 2], [f64; 3], [f64; 2]) {
+    // --- Node 100: EV of opening vs folding ---
+    let fold_ev_100 = payoffs.opener_folds_pre();
+    let mut open_ev = 0.0;
+    let mut total_w = 0.0;
 
-        for op in 0..NUM_HANDS {
-            let w = table.weight(op, resp);
-            if w < 1e-10 { continue; }
-            let op_open = opener_100[op][0];
-            if op_open < 1e-10 { continue; }
-            let wt = w * op_open;
-            total_w_101 += wt;
+    for resp in 0..NUM_HANDS {
+        let w = table.weight(op, resp);
+        if w < 1e-10 { continue; }
+        total_w += w;
 
-            let eq = table.eq(op, resp);
-            let resp_eq = 1.0 - eq;
+        let eq = table.eq(op, resp);
 
-            // Call → showdown from responder's perspective
-            let pot_flat = payoffs.open_size * 2.0 + payoffs.dead_money;
-            let resp_eq_real = if payoffs.ip_is_opener {
-                resp_eq
-            } else {
-                resp_eq * payoffs.eq_realization
-            };
-            let ev_call = resp_eq_real * pot_flat * (1.0 - payoffs.rake) - payoffs.open_size;
-            call_ev += wt * ev_call;
-
-            // 3-bet → subtree from responder's perspective
-            let ev_3bet = compute_resp_ev_after_3bet(
-                eq, &opener_102_new[op], &resp_103[resp], &opener_104_new[op], payoffs,
-            );
-            threebet_ev += wt * ev_3bet;
-        }
+        // Responder actions at node 101: 3-bet, call, fold
+        let r_3bet = resp_101[resp][0];
+        let r_call = resp_101[resp][1];
+        let r_fold = resp_101[resp][2];
 
-        if total_w_101 > 0.0 {
-            threebet_ev /= total_w_101;
-            call_ev /= total_w_101;
-        }
+        // EV when responder folds
+        let ev_resp_fold = payoffs.responder_folds_to_open();
 
-        let node_value_101 = resp_strat_101[0] * threebet_ev + resp_strat_101[1] * call_ev + resp_strat_101[2] * resp_fold_ev;
-        let data = trainer.get_or_create(&resp_key_101, ACTIONS_VS_OPEN);
-        data.update(&[threebet_ev, call_ev, resp_fold_ev], node_value_101, 1.0);
+        // EV when responder calls (flat)
+        let ev_resp_call = payoffs.flat_call_showdown(eq);
 
-        // --- Node 103: Responder vs 4-bet ---
-        let resp_key_103 = InfoSetKey { hand_bucket: resp as u16, node_id: NODE_VS_4BET };
-        let resp_strat_103 = resp_103[resp];
+        // EV when responder 3-bets → go to node 102
+        let ev_resp_3bet = compute_ev_after_3bet(eq, opener_102, &resp_103[resp], opener_104, payoffs);
 
-        let mut allin_ev = 0.0;
-        let mut call4bet_ev = 0.0;
-        let resp_fold_4bet_ev = -payoffs.three_bet_size;
-        let mut total_w_103 = 0.0;
+        let ev_open_vs_resp = r_fold * ev_resp_fold + r_call * ev_resp_call + r_3bet * ev_resp_3bet;
+        open_ev += w * ev_open_vs_resp;
+    }
 
-        for op in 0..NUM_HANDS {
-            let w = table.weight(op, resp);
-            if w < 1e-10 { continue; }
-            let op_open = opener_100[op][0];
-            if op_open < 1e-10 { continue; }
-            let op_4bet = opener_102_new[op][0];
-            if op_4bet < 1e-10 { continue; }
-            let r_3bet = resp_101[resp][0];
-            if r_3bet < 1e-10 { continue; }
+    if total_w > 0.0 {
+        open_ev /= total_w;
+    }
 
-            let wt = w * op_open * r_3bet * op_4bet;
-            total_w_103 += wt;
+    // --- Node 102 (opener vs 3-bet) ---
+    // EV is conditional on reaching node 102 (responder 3-bet)
+    let mut fourbet_ev = 0.0;
+    let mut call3bet_ev = 0.0;
+    let fold3bet_ev = payoffs.opener_folds_to_3bet();
+    let mut total_w_102 = 0.0;
 
-            let eq = table.eq(op, resp);
-            let resp_eq = 1.0 - eq;
+    for resp in 0..NUM_HANDS {
+        let w = table.weight(op, resp);
+        if w < 1e-10 { continue; }
+        let r_3bet = resp_101[resp][0];
+        if r_3bet < 1e-10 { continue; }
+        let wt = w * r_3bet;
+        total_w_102 += wt;
 
-            // Call 4-bet → showdown from responder's perspective
-            let pot_4bet = payoffs.four_bet_size * 2.0 + payoffs.dead_money;
-            let resp_eq_real = if payoffs.ip_is_opener {
-                resp_eq
-            } else {
-                resp_eq * payoffs.eq_realization
-            };
-            call4bet_ev += wt * (resp_eq_real * pot_4bet * (1.0 - payoffs.rake) - payoffs.four_bet_size);
-
-            // All-in → opener decides at node 104
-            let op_call_5bet = opener_104_new[op][0];
-            let op_fold_5bet = opener_104_new[op][1];
-
-            // Responder wins opener's 4-bet if opener folds
-            let ev_op_folds = payoffs.four_bet_size + payoffs.dead_money;
-            // All-in showdown from responder's perspective
-            let pot_allin = payoffs.stack_bb * 2.0 + payoffs.dead_money;
-            let ev_allin_showdown = if payoffs.ip_is_opener {
-                resp_eq
-            } else {
-                resp_eq * payoffs.eq_realization
-            } * pot_allin * (1.0 - payoffs.rake) - payoffs.stack_bb;
+        let eq = table.eq(op, resp);
 
-            allin_ev += wt * (op_fold_5bet * ev_op_folds + op_call_5bet * ev_allin_showdown);
-        }
+        // Call 3-bet → showdown
+        call3bet_ev += wt * payoffs.call_3bet_showdown(eq);
 
-        if total_w_103 > 0.0 {
-            allin_ev /= total_w_103;
-            call4bet_ev /= total_w_103;
-        }
+        // 4-bet → node 103
+        let ev_4bet = compute_ev_after_4bet(eq, &resp_103[resp], opener_104, payoffs);
+        fourbet_ev += wt * ev_4bet;
+    }
 
-        let node_value_103 = resp_strat_103[0] * allin_ev + resp_strat_103[1] * call4bet_ev + resp_strat_103[2] * resp_fold_4bet_ev;
-        let data = trainer.get_or_create(&resp_key_103, ACTIONS_VS_4BET);
-        data.update(&[allin_ev, call4bet_ev, resp_fold_4bet_ev], node_value_103, 1.0);
+    if total_w_102 > 0.0 {
+        fourbet_ev /= total_w_102;
+        call3bet_ev /= total_w_102;
+    }
+
+    // --- Node 104 (opener vs 5-bet/all-in) ---
+    let mut call5bet_ev = 0.0;
+    let fold5bet_ev = payoffs.opener_folds_to_5bet();
+    let mut total_w_104 = 0.0;
+
+    for resp in 0..NUM_HANDS {
+        let w = table.weight(op, resp);
+        if w < 1e-10 { continue; }
+        let r_3bet = resp_101[resp][0];
+        if r_3bet < 1e-10 { continue; }
+        let r_allin = resp_103[resp][0];
+        if r_allin < 1e-10 { continue; }
+        // Also need opener's 4-bet prob to reach this node
+        let op_4bet = opener_102[0];
+        if op_4bet < 1e-10 { continue; }
+
+        let wt = w * r_3bet * op_4bet * r_allin;
+        total_w_104 += wt;
+
+        let eq = table.eq(op, resp);
+        call5bet_ev += wt * payoffs.allin_showdown(eq);
     }
+
+    if total_w_104 > 0.0 {
+        call5bet_ev /= total_w_104;
+    }
+
+    (
+        [open_ev, fold_ev_100],
+        [fourbet_ev, call3bet_ev, fold3bet_ev],
+        [call5bet_ev, fold5bet_ev],
+    )
+}
+
+/// Responder hand `resp`'s action EVs against the opener's strategies: node
+/// 101 [3-bet, call, fold] and node 103 [all-in, call, fold], each
+/// conditional on reaching its node.
+#[allow(clippy::too_many_arguments)]
+fn responder_action_evs(
+    resp: usize,
+    opener_100: &[[f64; 2]],
+    opener_102: &[[f64; 3]],
+    opener_104: &[[f64; 2]],
+    resp_101: &[f64; 3],
+    resp_103: &[f64; 3],
+    table: &EquityTable,
+    payoffs: &PreflopPayoffs,
+) -> ([f64; 3], [f64; 3]) {
+    // --- Node 101: Responder vs open ---
+    let mut threebet_ev = 0.0;
+    let mut call_ev = 0.0;
+    let resp_fold_ev = -payoffs.responder_blind;
+
+    let mut total_w_101 = 0.0;
+
+    for op in 0..NUM_HANDS {
+        let w = table.weight(op, resp);
+        if w < 1e-10 { continue; }
+        let op_open = opener_100[op][0];
+        if op_open < 1e-10 { continue; }
+        let wt = w * op_open;
+        total_w_101 += wt;
+
+        let eq = table.eq(op, resp);
+        let resp_eq = 1.0 - eq;
+
+        // Call → showdown from responder's perspective
+        let pot_flat = payoffs.open_size * 2.0 + payoffs.dead_money;
+        let resp_eq_real = if payoffs.ip_is_opener {
+            resp_eq
+        } else {
+            resp_eq * payoffs.eq_realization
+        };
+        let ev_call = resp_eq_real * pot_flat * (1.0 - payoffs.rake) - payoffs.open_size;
+        call_ev += wt * ev_call;
+
+        // 3-bet → subtree from responder's perspective
+        let ev_3bet = compute_resp_ev_after_3bet(eq, &opener_102[op], resp_103, &opener_104[op], payoffs);
+        threebet_ev += wt * ev_3bet;
+    }
+
+    if total_w_101 > 0.0 {
+        threebet_ev /= total_w_101;
+        call_ev /= total_w_101;
+    }
+
+    // --- Node 103: Responder vs 4-bet ---
+    let mut allin_ev = 0.0;
+    let mut call4bet_ev = 0.0;
+    let resp_fold_4bet_ev = -payoffs.three_bet_size;
+    let mut total_w_103 = 0.0;
+
+    for op in 0..NUM_HANDS {
+        let w = table.weight(op, resp);
+        if w < 1e-10 { continue; }
+        let op_open = opener_100[op][0];
+        if op_open < 1e-10 { continue; }
+        let op_4bet = opener_102[op][0];
+        if op_4bet < 1e-10 { continue; }
+        let r_3bet = resp_101[0];
+        if r_3bet < 1e-10 { continue; }
+
+        let wt = w * op_open * r_3bet * op_4bet;
+        total_w_103 += wt;
+
+        let eq = table.eq(op, resp);
+        let resp_eq = 1.0 - eq;
+
+        // Call 4-bet → showdown from responder's perspective
+        let pot_4bet = payoffs.four_bet_size * 2.0 + payoffs.dead_money;
+        let resp_eq_real = if payoffs.ip_is_opener {
+            resp_eq
+        } else {
+            resp_eq * payoffs.eq_realization
+        };
+        call4bet_ev += wt * (resp_eq_real * pot_4bet * (1.0 - payoffs.rake) - payoffs.four_bet_size);
+
+        // All-in → opener decides at node 104
+        let op_call_5bet = opener_104[op][0];
+        let op_fold_5bet = opener_104[op][1];
+
+        // Responder wins opener's 4-bet if opener folds
+        let ev_op_folds = payoffs.four_bet_size + payoffs.dead_money;
+        // All-in showdown from responder's perspective
+        let pot_allin = payoffs.stack_bb * 2.0 + payoffs.dead_money;
+        let ev_allin_showdown = if payoffs.ip_is_opener {
+            resp_eq
+        } else {
+            resp_eq * payoffs.eq_realization
+        } * pot_allin * (1.0 - payoffs.rake) - payoffs.stack_bb;
+
+        allin_ev += wt * (op_fold_5bet * ev_op_folds + op_call_5bet * ev_allin_showdown);
+    }
+
+    if total_w_103 > 0.0 {
+        allin_ev /= total_w_103;
+        call4bet_ev /= total_w_103;
+    }
+
+    (
+        [threebet_ev, call_ev, resp_fold_ev],
+        [allin_ev, call4bet_ev, resp_fold_4bet_ev],
+    )
 }
 
 /// Compute opener's EV after responder 3-bets (for opener's node 100 update).
@@ -910,53 +1254,81 @@ pub fn compute_preflop_exploitability(
 // Batch solving + disk cache
 // ---------------------------------------------------------------------------
 
+/// Version of the cached `PreflopSolution` format. 2 added per-spot action
+/// EVs; files without a version predate it and load without them.
+pub const PREFLOP_CACHE_VERSION: u32 = 2;
+
 /// Complete solution for all spots at a given table size.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreflopSolution {
+    /// `PREFLOP_CACHE_VERSION` when saved; 0 for unversioned files.
+    #[serde(default)]
+    pub version: u32,
     pub table_size: String,
     pub stack_bb: f64,
     pub rake_pct: f64,
+    /// Raise sizes solved for; solutions saved before sizing was
+    /// configurable used the default.
+    #[serde(default)]
+    pub sizing: PreflopSizing,
+    /// Antes and straddle solved for; none in solutions saved before they
+    /// were configurable.
+    #[serde(default)]
+    pub forced_bets: ForcedBets,
+    /// Iterations per spot, a cap when solved to a target exploitability;
+    /// each spot records the count it ran.
     pub iterations: usize,
     pub spots: Vec<PreflopSpotResult>,
 }
 
-/// Solve all 15 6-max preflop spots.
+/// Solve all 15 6-max preflop spots in parallel, each for `iterations` or,
+/// with `target_bb`, until its exploitability is at most that (see
+/// `solve_preflop_spot_to`). Spots print as they finish.
 pub fn solve_preflop_6max(
     stack_bb: f64,
     iterations: usize,
+    target_bb: Option<f64>,
     rake_pct: f64,
+    sizing: PreflopSizing,
+    forced: ForcedBets,
 ) -> PreflopSolution {
     use colored::Colorize;
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     println!("  Computing equity table...");
     let table = precompute_equity_table(2000);
     println!("  Equity table ready.\n");
 
-    let spots_config = all_6max_spots();
-    let mut spots = Vec::with_capacity(spots_config.len());
-
-    for (i, (opener, responder)) in spots_config.iter().enumerate() {
-        print!(
-            "  [{}/{}] Solving {} vs {} ...",
-            i + 1,
-            spots_config.len(),
-            opener.as_str().bold(),
-            responder.as_str().bold(),
-        );
-        let result = solve_preflop_spot(*opener, *responder, stack_bb, iterations, rake_pct, &table);
-        println!(
-            " done (exploit: {:.4} bb, open: {:.1}%, 3bet: {:.1}%)",
-            result.exploitability,
-            result.open_pct(),
-            result.three_bet_pct(),
-        );
-        spots.push(result);
-    }
+    let spots_config = forced.spots();
+    let done = AtomicUsize::new(0);
+    let spots: Vec<PreflopSpotResult> = spots_config
+        .par_iter()
+        .map(|&(opener, responder)| {
+            let result =
+                solve_preflop_spot_to(opener, responder, stack_bb, iterations, target_bb, rake_pct, &sizing, &forced, &table);
+            println!(
+                "  [{}/{}] {} vs {} done in {} iterations (exploit: {:.4} bb, open: {:.1}%, 3bet: {:.1}%)",
+                done.fetch_add(1, Ordering::Relaxed) + 1,
+                spots_config.len(),
+                opener.as_str().bold(),
+                responder.as_str().bold(),
+                result.iterations,
+                result.exploitability,
+                result.open_pct(),
+                result.three_bet_pct(),
+            );
+            result
+        })
+        .collect();
 
     PreflopSolution {
+        version: PREFLOP_CACHE_VERSION,
         table_size: "6max".to_string(),
         stack_bb,
         rake_pct,
+        sizing,
+        forced_bets: forced,
         iterations,
         spots,
     }
@@ -968,15 +1340,15 @@ impl PreflopSolution {
         self.spots.iter().find(|s| s.opener == opener && s.responder == responder)
     }
 
+    /// False for solves run below the iteration floor (via `--force`); their
+    /// ranges aren't used by `gto query`.
+    pub fn meets_quality_floor(&self) -> bool {
+        crate::limits::PREFLOP_ITERATIONS.meets_floor(self.iterations)
+    }
+
     /// Get the cache file path for this solution.
     pub fn cache_path(&self) -> std::path::PathBuf {
-        let dir = dirs_cache_dir();
-        dir.join(format!(
-            "preflop_{}_{}bb_{}pct.json",
-            self.table_size,
-            self.stack_bb as u64,
-            self.rake_pct as u64,
-        ))
+        cache_path(&self.table_size, self.stack_bb, self.rake_pct, &self.sizing, &self.forced_bets)
     }
 
     /// Save solution to disk cache.
@@ -990,24 +1362,65 @@ impl PreflopSolution {
         std::fs::write(&path, json)
     }
 
-    /// Load solution from disk cache.
-    pub fn load(table_size: &str, stack_bb: f64, rake_pct: f64) -> std::io::Result<Self> {
-        let dir = dirs_cache_dir();
-        let path = dir.join(format!(
-            "preflop_{}_{}bb_{}pct.json",
-            table_size,
-            stack_bb as u64,
-            rake_pct as u64,
-        ));
+    /// Load solution from disk cache. A file solved at a different sizing
+    /// than `sizing` is an `InvalidData` error rather than a match.
+    pub fn load(table_size: &str, stack_bb: f64, rake_pct: f64, sizing: &PreflopSizing) -> std::io::Result<Self> {
+        Self::load_with(table_size, stack_bb, rake_pct, sizing, &ForcedBets::default())
+    }
+
+    /// `load` for a game with antes or a straddle.
+    pub fn load_with(
+        table_size: &str,
+        stack_bb: f64,
+        rake_pct: f64,
+        sizing: &PreflopSizing,
+        forced: &ForcedBets,
+    ) -> std::io::Result<Self> {
+        let path = cache_path(table_size, stack_bb, rake_pct, sizing, forced);
         let json = std::fs::read_to_string(&path)?;
-        serde_json::from_str(&json)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+        let solution: Self = serde_json::from_str(&json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        if solution.version > PREFLOP_CACHE_VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} was saved by a newer version of gto (format {})", path.display(), solution.version),
+            ));
+        }
+        if solution.sizing != *sizing {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} was solved for {}, not {}", path.display(), solution.sizing.label(), sizing.label()),
+            ));
+        }
+        if solution.forced_bets != *forced {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} was solved for other antes or straddle than {}", path.display(), forced.label()),
+            ));
+        }
+        Ok(solution)
     }
 }
 
+fn cache_path(
+    table_size: &str,
+    stack_bb: f64,
+    rake_pct: f64,
+    sizing: &PreflopSizing,
+    forced: &ForcedBets,
+) -> std::path::PathBuf {
+    dirs_cache_dir().join(format!(
+        "preflop_{}_{}bb_{}pct{}{}.json",
+        table_size,
+        stack_bb as u64,
+        rake_pct as u64,
+        sizing.cache_suffix(),
+        forced.cache_suffix(),
+    ))
+}
+
 fn dirs_cache_dir() -> std::path::PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    std::path::PathBuf::from(home).join(".gto-cli").join("solver")
+    crate::cache_index::solver_cache_dir()
 }
 
 // ---------------------------------------------------------------------------
@@ -1076,6 +1489,72 @@ mod tests {
         assert!((p.responder_folds_to_open() - 1.0).abs() < 1e-9);
     }
 
+    #[test]
+    fn sizing_sets_payoff_bet_sizes() {
+        let sizing = PreflopSizing::new(2.2, 3.0, 2.2, 100.0).unwrap();
+        let p = PreflopPayoffs::with_sizing(Position::BTN, Position::BB, 100.0, 0.0, &sizing);
+        assert!((p.open_size - 2.2).abs() < 1e-9);
+        assert!((p.three_bet_size - 6.6).abs() < 1e-9);
+        assert!((p.four_bet_size - 14.52).abs() < 1e-9);
+        assert!((p.opener_folds_to_3bet() + 2.2).abs() < 1e-9);
+
+        // The default matches the tree in the module docs
+        let p = PreflopPayoffs::new(Position::BTN, Position::BB, 100.0, 0.0);
+        assert_eq!((p.open_size, p.three_bet_size, p.four_bet_size), (2.5, 7.5, 18.75));
+        assert_eq!(PreflopSizing::default().cache_suffix(), "");
+        assert_ne!(sizing.cache_suffix(), "");
+    }
+
+    #[test]
+    fn sizing_rejects_non_raises_and_all_in_opens() {
+        assert!(PreflopSizing::new(1.0, 3.0, 2.5, 100.0).is_err());
+        assert!(PreflopSizing::new(2.5, 1.0, 2.5, 100.0).is_err());
+        assert!(PreflopSizing::new(2.5, 3.0, 0.5, 100.0).is_err());
+        assert!(PreflopSizing::new(2.5, 3.0, 2.5, 2.5).is_err());
+        assert!(PreflopSizing::new(f64::NAN, 3.0, 2.5, 100.0).is_err());
+        assert!(PreflopSizing::new(2.5, 3.0, 2.5, 15.0).is_ok());
+    }
+
+    #[test]
+    fn raises_past_the_stack_are_all_in() {
+        // 15bb deep the default 18.75bb 4-bet is a shove; the 3-bet isn't
+        let sizing = PreflopSizing::new(2.5, 3.0, 2.5, 15.0).unwrap();
+        let p = PreflopPayoffs::with_sizing(Position::BTN, Position::BB, 15.0, 0.0, &sizing);
+        assert_eq!((p.three_bet_size, p.four_bet_size), (7.5, 15.0));
+        assert_eq!(p.call_4bet_showdown(0.5), p.allin_showdown(0.5));
+
+        let p = PreflopPayoffs::with_sizing(Position::BTN, Position::BB, 6.0, 0.0, &sizing);
+        assert_eq!((p.three_bet_size, p.four_bet_size), (6.0, 6.0));
+    }
+
+    #[test]
+    fn forced_bets_add_dead_money_and_move_the_straddler() {
+        let forced = ForcedBets::new(0.125, 2.0, 100.0).unwrap();
+        // UTG straddles 2bb vs BB: dead = SB 0.5 + six 0.125 antes
+        let p = PreflopPayoffs::with_forced_bets(
+            Position::UTG, Position::BB, 100.0, 0.0, &PreflopSizing::default(), &forced,
+        );
+        assert!((p.opener_blind - 2.0).abs() < 1e-9);
+        assert!((p.dead_money - 1.25).abs() < 1e-9);
+        // Each player's ante comes out of the stack they can shove
+        assert!((p.stack_bb - 99.875).abs() < 1e-9);
+        assert!((p.allin_showdown(1.0) - (99.875 + 1.25)).abs() < 1e-9);
+        assert!((forced.extra_dead_money_between(Position::UTG, Position::BB) - 0.75).abs() < 1e-9);
+        assert!((forced.extra_dead_money_between(Position::BTN, Position::BB) - 2.75).abs() < 1e-9);
+
+        let spots = forced.spots();
+        assert_eq!(spots.len(), 15);
+        assert!(spots.contains(&(Position::BB, Position::UTG)));
+        assert!(!spots.iter().any(|&(opener, _)| opener == Position::UTG));
+        assert_eq!(ForcedBets::default().spots(), all_6max_spots());
+        assert_eq!(ForcedBets::default().cache_suffix(), "");
+        assert_eq!(forced.cache_suffix(), "_ante0.125_straddle2");
+
+        assert!(ForcedBets::new(-0.1, 0.0, 100.0).is_err());
+        assert!(ForcedBets::new(0.0, 1.0, 100.0).is_err());
+        assert!(ForcedBets::new(0.0, 100.0, 100.0).is_err());
+    }
+
     #[test]
     fn all_spots_count() {
         assert_eq!(all_6max_spots().len(), 15);
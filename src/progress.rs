@@ -0,0 +1,457 @@
+//! Progress snapshots for long postflop solves.
+//!
+//! With `progress_interval: Some(n)` on a solver config, the solver measures
+//! exploitability every `n` iterations, keeps each measurement in the
+//! solution's `convergence_history` (ending with the final exploitability),
+//! and hands a `SolveProgress` (with the history so far, which the CLI draws
+//! as a sparkline) to the config's `on_progress` callback, if any.
+//! Flop snapshots use the Monte Carlo estimate on fewer runouts than the final
+//! one, so they're noisy; turn and river snapshots are exact.
+//!
+//! With a `target_exploitability` (percent of the starting pot) the solver also
+//! stops as soon as a measurement is at or below the target, and `iterations`
+//! becomes a cap. Those checks run on their own schedule: a measurement can
+//! cost as much as many iterations (a full best response on the turn), so the
+//! next check waits until the iterations since the last one took
+//! `1 / CHECK_BUDGET` times as long as that check, and at least
+//! `CHECK_GROWTH` × the iterations run so far, since early checks rarely hit
+//! the target.
+//!
+//! With a `time_budget` the solver stops once that much wall time has gone
+//! into iterating (setup and extraction come on top), and `iterations` is
+//! again a cap. The clock is read every batch of iterations, the batch sized
+//! from the rate so far to take about `CLOCK_CHECK_SECS`. Without a
+//! `progress_interval`, a timed solve with an `on_progress` callback takes its
+//! snapshots every tenth of the budget instead.
+//!
+//! When snapshots are on, the callback also gets a last one once the solve
+//! is done, with the iterations actually run and the rate achieved.
+
+use serde::Serialize;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// One snapshot of a running solve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveProgress {
+    /// Iterations completed.
+    pub iteration: usize,
+    pub total: usize,
+    /// Iterations done before this run, for a resumed solve.
+    pub resumed: usize,
+    pub elapsed: Duration,
+    /// The solve's time budget, if it has one (`total` is then a cap).
+    pub budget: Option<Duration>,
+    pub exploitability: f64,
+    /// Every measurement of this solve so far, as (iteration,
+    /// exploitability), ending with this one.
+    pub history: Vec<(usize, f64)>,
+}
+
+impl SolveProgress {
+    pub fn iterations_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 { (self.iteration - self.resumed) as f64 / secs } else { 0.0 }
+    }
+
+    /// Lowest exploitability measured so far.
+    pub fn min_exploitability(&self) -> f64 {
+        self.history.iter().map(|&(_, e)| e).fold(self.exploitability, f64::min)
+    }
+}
+
+pub type ProgressCallback = fn(&SolveProgress);
+
+/// Most of the solve time target checks may take.
+const CHECK_BUDGET: f64 = 0.1;
+/// Each target check waits for at least this share more iterations.
+const CHECK_GROWTH: f64 = 0.25;
+/// Iterations before the first target check.
+const FIRST_CHECK: usize = 10;
+/// Seconds of iterations between reads of the clock in a timed solve.
+const CLOCK_CHECK_SECS: f64 = 0.01;
+/// Snapshots a timed solve without a progress interval takes.
+const TIMED_SNAPSHOTS: u32 = 10;
+
+/// Tracks when a solve is due a snapshot and collects the history.
+pub struct ProgressTracker {
+    interval: Option<usize>,
+    callback: Option<ProgressCallback>,
+    total: usize,
+    start: Instant,
+    /// Iterations already done when this run started (a resumed solve).
+    resumed: usize,
+    history: Vec<(usize, f64)>,
+    /// Stop at or below this exploitability, in chips.
+    target: Option<f64>,
+    next_check: usize,
+    stopped_at: Option<usize>,
+    /// When a timed solve has to stop.
+    deadline: Option<Instant>,
+    budget: Option<Duration>,
+    next_clock_check: usize,
+    /// Elapsed time of the next timed snapshot, and the time between them.
+    next_snapshot: Option<(Duration, Duration)>,
+    snapshot_due: bool,
+    /// Time spent iterating, once the iterations are over.
+    iterating: Option<Duration>,
+}
+
+impl ProgressTracker {
+    pub fn new(interval: Option<usize>, callback: Option<ProgressCallback>, total: usize) -> Self {
+        ProgressTracker {
+            interval: interval.filter(|&n| n > 0),
+            callback,
+            total,
+            start: Instant::now(),
+            resumed: 0,
+            history: Vec::new(),
+            target: None,
+            next_check: FIRST_CHECK,
+            stopped_at: None,
+            deadline: None,
+            budget: None,
+            next_clock_check: 0,
+            next_snapshot: None,
+            snapshot_due: false,
+            iterating: None,
+        }
+    }
+
+    /// Stop once `budget` of wall time has gone by (see the module docs).
+    pub fn with_time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.deadline = budget.map(|b| self.start + b);
+        self.budget = budget;
+        if let Some(budget) = budget.filter(|_| self.interval.is_none() && self.callback.is_some()) {
+            let every = budget / TIMED_SNAPSHOTS;
+            self.next_snapshot = Some((every, every));
+        }
+        self
+    }
+
+    /// Stop once exploitability is at or below `target_pct` percent of `starting_pot`.
+    pub fn with_target(mut self, target_pct: Option<f64>, starting_pot: f64) -> Self {
+        self.target = target_pct.map(|pct| pct / 100.0 * starting_pot);
+        self
+    }
+
+    /// Continue a solve that already ran `done` iterations: rates count only
+    /// this run's iterations, and the first target check waits as usual.
+    pub fn resuming_from(mut self, done: usize) -> Self {
+        self.resumed = done;
+        self.next_check = done + FIRST_CHECK;
+        self.next_clock_check = done;
+        self
+    }
+
+    /// True when the time budget has run out with `done` iterations
+    /// completed, i.e. the solve should stop. At least one iteration runs.
+    pub fn out_of_time(&mut self, done: usize) -> bool {
+        let Some(deadline) = self.deadline else {
+            return false;
+        };
+        if done <= self.resumed || done < self.next_clock_check {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            self.stopped_at = Some(done);
+            return true;
+        }
+        let elapsed = now - self.start;
+        if let Some((at, every)) = self.next_snapshot.filter(|&(at, _)| elapsed >= at) {
+            self.snapshot_due = true;
+            self.next_snapshot = Some((at + every, every));
+        }
+        let per_sec = (done - self.resumed) as f64 / elapsed.as_secs_f64().max(1e-9);
+        self.next_clock_check = done + ((per_sec * CLOCK_CHECK_SECS) as usize).max(1);
+        false
+    }
+
+    /// True when a measurement is due with `done` iterations completed. The
+    /// last iteration isn't: the solver measures the final exploitability anyway.
+    pub fn due(&self, done: usize) -> bool {
+        done > self.resumed
+            && done < self.total
+            && (self.interval.is_some_and(|n| done.is_multiple_of(n))
+                || (self.target.is_some() && done >= self.next_check)
+                || self.snapshot_due)
+    }
+
+    /// Take a measurement with `done` iterations completed. True when it meets
+    /// the target, i.e. the solve should stop.
+    pub fn measure(&mut self, done: usize, exploitability: impl FnOnce() -> f64) -> bool {
+        self.snapshot_due = false;
+        let check_start = Instant::now();
+        let exploitability = exploitability();
+        let check_secs = check_start.elapsed().as_secs_f64();
+        self.record(done, exploitability);
+
+        let Some(target) = self.target else {
+            return false;
+        };
+        if exploitability <= target {
+            self.stopped_at = Some(done);
+            return true;
+        }
+        let secs_per_iteration = (self.start.elapsed().as_secs_f64() - check_secs) / (done - self.resumed) as f64;
+        let by_cost = if secs_per_iteration > 0.0 {
+            (check_secs / CHECK_BUDGET / secs_per_iteration).ceil() as usize
+        } else {
+            0
+        };
+        let by_growth = ((done - self.resumed) as f64 * CHECK_GROWTH).ceil() as usize;
+        self.next_check = done + by_cost.max(by_growth).max(1);
+        false
+    }
+
+    /// Iterations run: the cap, or fewer if the target was reached or the
+    /// time ran out.
+    pub fn iterations_run(&self) -> usize {
+        self.stopped_at.unwrap_or(self.total)
+    }
+
+    /// Mark the iterations over, so the last snapshot's rate leaves out
+    /// extracting the solution.
+    pub fn end_iterations(&mut self) {
+        self.iterating = Some(self.start.elapsed());
+    }
+
+    fn record(&mut self, done: usize, exploitability: f64) {
+        self.history.push((done, exploitability));
+        if let Some(callback) = self.callback {
+            callback(&SolveProgress {
+                iteration: done,
+                total: self.total,
+                resumed: self.resumed,
+                elapsed: self.iterating.unwrap_or_else(|| self.start.elapsed()),
+                budget: self.budget,
+                exploitability,
+                history: self.history.clone(),
+            });
+        }
+    }
+
+    /// The history, closed with the final exploitability (also handed to
+    /// the callback). Empty when snapshots are off.
+    pub fn finish(mut self, exploitability: f64) -> Vec<(usize, f64)> {
+        let run = self.iterations_run();
+        if self.history.last().is_some_and(|&(i, _)| i == run) {
+            self.history.pop();
+        }
+        if (self.interval.is_some() || self.target.is_some() || self.next_snapshot.is_some()) && run > 0 {
+            self.record(run, exploitability);
+        }
+        self.history
+    }
+}
+
+/// Parse a time budget: numbers with units, `90s`, `2m`, `1h` or `1h30m`
+/// (`1.5m` is 90 seconds). Used by every flag that takes a duration
+/// (`--time`, `prefetch --time-budget`). A bare number is an error, since
+/// `prefetch --time-budget` once read it as minutes.
+pub fn parse_time_budget(text: &str) -> Result<Duration, String> {
+    let text = text.trim().to_lowercase();
+    let invalid = || format!("Invalid time '{}': expected e.g. 90s, 2m, 1h or 1h30m", text);
+    if text.parse::<f64>().is_ok() {
+        return Err(format!("Time '{}' needs a unit, e.g. {}s or {}m", text, text, text));
+    }
+    let (mut secs, mut number) = (0.0, String::new());
+    for c in text.chars() {
+        let unit_secs = match c {
+            '0'..='9' | '.' => {
+                number.push(c);
+                continue;
+            }
+            's' => 1.0,
+            'm' => 60.0,
+            'h' => 3600.0,
+            _ => return Err(invalid()),
+        };
+        secs += number.parse::<f64>().map_err(|_| invalid())? * unit_secs;
+        number.clear();
+    }
+    // A trailing number without a unit ("1h30") is ambiguous
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    if secs > 0.0 && secs.is_finite() {
+        Ok(Duration::from_secs_f64(secs))
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Where one solve spent its time, in milliseconds: building the tree and
+/// expanding ranges, precomputing showdown and blocker tables, the CFR
+/// iterations (progress measurements included), and extracting the solution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SolveStats {
+    pub setup_ms: f64,
+    pub precompute_ms: f64,
+    pub iterate_ms: f64,
+    pub extract_ms: f64,
+}
+
+impl SolveStats {
+    pub fn total_ms(&self) -> f64 {
+        self.setup_ms + self.precompute_ms + self.iterate_ms + self.extract_ms
+    }
+}
+
+/// Times consecutive phases of a solve: each `lap` returns the milliseconds
+/// since the previous one.
+pub(crate) struct PhaseTimer(Instant);
+
+impl PhaseTimer {
+    pub(crate) fn start() -> Self {
+        PhaseTimer(Instant::now())
+    }
+
+    pub(crate) fn lap(&mut self) -> f64 {
+        let now = Instant::now();
+        let ms = now.duration_since(self.0).as_secs_f64() * 1000.0;
+        self.0 = now;
+        ms
+    }
+}
+
+/// Measurements the progress line's sparkline shows, the latest last.
+const SPARKLINE_POINTS: usize = 24;
+
+/// CLI progress callback: one line on stderr, rewritten in place, with a
+/// sparkline of the recent measurements and the lowest one so far.
+pub fn print_progress(p: &SolveProgress) {
+    let recent: Vec<f64> = p.history.iter().rev().take(SPARKLINE_POINTS).rev().map(|&(_, e)| e).collect();
+    // A timed solve's total is only a cap: show the clock against the budget instead
+    let (count, time) = match p.budget {
+        Some(budget) => (
+            format!("{} it", p.iteration),
+            format!("{:.0}s/{:.0}s", p.elapsed.as_secs_f64(), budget.as_secs_f64()),
+        ),
+        None => (format!("{}/{}", p.iteration, p.total), format!("{:.0}s", p.elapsed.as_secs_f64())),
+    };
+    eprint!(
+        "\r  [{}] {}, {:.0} it/s, exploitability ~{:.4} (min {:.4}) {:<width$}   ",
+        count,
+        time,
+        p.iterations_per_sec(),
+        p.exploitability,
+        p.min_exploitability(),
+        crate::display::sparkline(&recent),
+        width = SPARKLINE_POINTS
+    );
+    let _ = std::io::stderr().flush();
+}
+
+/// End the progress line once the solve is done.
+pub fn end_progress_line(history: &[(usize, f64)]) {
+    if !history.is_empty() {
+        eprintln!();
+    }
+}
+
+/// A spinner on stderr for work with no progress snapshots (an interactive
+/// quick solve). Frames start after a short delay, so fast work (a cache
+/// hit) prints nothing; dropping it clears the line.
+pub struct Spinner {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Spinner {
+    pub fn start(message: &str) -> Spinner {
+        use std::sync::atomic::Ordering;
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = stop.clone();
+        let message = message.to_string();
+        let handle = std::thread::spawn(move || {
+            const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+            let mut shown = false;
+            for frame in FRAMES.iter().cycle() {
+                std::thread::sleep(Duration::from_millis(150));
+                if flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                eprint!("\r  {} {}", frame, message);
+                let _ = std::io::stderr().flush();
+                shown = true;
+            }
+            if shown {
+                eprint!("\r{}\r", " ".repeat(message.len() + 4));
+                let _ = std::io::stderr().flush();
+            }
+        });
+        Spinner { stop, handle: Some(handle) }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_snapshots_every_interval_and_closes_with_final_value() {
+        let mut tracker = ProgressTracker::new(Some(4), None, 10);
+        let due: Vec<usize> = (0..10).filter(|&i| tracker.due(i)).collect();
+        assert_eq!(due, vec![4, 8]);
+        assert!(!tracker.measure(4, || 2.0));
+        assert!(!tracker.measure(8, || 1.0));
+        assert_eq!(tracker.finish(0.5), vec![(4, 2.0), (8, 1.0), (10, 0.5)]);
+
+        let off = ProgressTracker::new(None, None, 10);
+        assert!(!(0..10).any(|i| off.due(i)));
+        assert!(off.finish(0.5).is_empty());
+        assert!(ProgressTracker::new(Some(0), None, 10).finish(0.5).is_empty());
+    }
+
+    #[test]
+    fn test_target_stops_the_solve_and_spaces_out_checks() {
+        // 1% of a 10-chip pot: stop at 0.1 chips or less
+        let mut tracker = ProgressTracker::new(None, None, 1000).with_target(Some(1.0), 10.0);
+        assert!(!tracker.due(9));
+        assert!(tracker.due(10));
+        assert!(!tracker.measure(10, || 0.5));
+        // The next check waits for at least 25% more iterations
+        assert!(!tracker.due(12));
+        let next = (13..1000).find(|&i| tracker.due(i)).unwrap();
+        assert!(!tracker.measure(next, || 0.2));
+        let last = (next + 1..1000).find(|&i| tracker.due(i)).unwrap();
+        assert!(last - next >= next / 4);
+        assert!(tracker.measure(last, || 0.1));
+        assert_eq!(tracker.iterations_run(), last);
+        assert_eq!(tracker.finish(0.09), vec![(10, 0.5), (next, 0.2), (last, 0.09)]);
+    }
+
+    #[test]
+    fn test_time_budget_stops_the_solve_and_parses_units() {
+        let mut tracker = ProgressTracker::new(None, None, usize::MAX).with_time_budget(Some(Duration::ZERO));
+        // At least one iteration runs
+        assert!(!tracker.out_of_time(0));
+        assert!(tracker.out_of_time(1));
+        assert_eq!(tracker.iterations_run(), 1);
+        let mut untimed = ProgressTracker::new(None, None, 10);
+        assert!(!(0..10).any(|i| untimed.out_of_time(i)));
+
+        assert_eq!(parse_time_budget("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_time_budget("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_time_budget("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_time_budget("1.5m"), Ok(Duration::from_secs(90)));
+        assert!(parse_time_budget("45").unwrap_err().contains("45s or 45m"));
+        assert_eq!(parse_time_budget("1h30m"), Ok(Duration::from_secs(5400)));
+        assert!(parse_time_budget("30x").is_err());
+        assert!(parse_time_budget("1h30").is_err());
+        assert!(parse_time_budget("0s").is_err());
+        assert!(parse_time_budget("soon").is_err());
+        assert!(parse_time_budget("").is_err());
+    }
+}
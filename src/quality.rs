@@ -0,0 +1,257 @@
+//! Solve quality grades: how far to trust a cached answer.
+//!
+//! A grade runs from A (trust it) to D (re-solve before relying on it) and is
+//! the worst of four components:
+//!
+//! | Component                              | A       | B       | C       |
+//! |----------------------------------------|---------|---------|---------|
+//! | exploitability, % of the starting pot  | ≤ 1%    | ≤ 3%    | ≤ 10%   |
+//! | iterations / recommended iterations    | ≥ 1.0   | ≥ 0.5   | ≥ 0.2   |
+//! | strategy buckets per combo             | ≥ 0.25  | ≥ 0.15  | ≥ 0.05  |
+//! | visits at the answered node / recommended iterations | ≥ 0.2 | ≥ 0.04 | ≥ 0.004 |
+//!
+//! Anything worse than C is D. Recommended iterations are the solve commands'
+//! defaults (500K flop, 5K turn, 10K river), so a default flop solve needs its
+//! answered node visited 100K times for an A. River and turn solves store one
+//! strategy per combo and count as full resolution. Visits are estimated as
+//! iterations × the node's reach under the stored average strategies.
+//!
+//! The first three components describe the whole solve and are stored with
+//! each solution (`SolveQuality`); the visit count depends on the node a query
+//! lands on (`AnswerQuality`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::flop_solver::FlopSolution;
+
+pub const RECOMMENDED_FLOP_ITERATIONS: usize = 500_000;
+pub const RECOMMENDED_TURN_ITERATIONS: usize = 5_000;
+pub const RECOMMENDED_RIVER_ITERATIONS: usize = 10_000;
+
+/// Quality grade, best first (`Grade::A < Grade::D`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Grade {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Grade> {
+        match s.trim().to_uppercase().as_str() {
+            "A" => Some(Grade::A),
+            "B" => Some(Grade::B),
+            "C" => Some(Grade::C),
+            "D" => Some(Grade::D),
+            _ => None,
+        }
+    }
+
+    /// True when this grade is `min` or better.
+    pub fn meets(&self, min: Grade) -> bool {
+        *self <= min
+    }
+}
+
+impl std::fmt::Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Grade for a value where higher is better, given the A/B/C minimums.
+fn grade_at_least(value: f64, [a, b, c]: [f64; 3]) -> Grade {
+    if value >= a {
+        Grade::A
+    } else if value >= b {
+        Grade::B
+    } else if value >= c {
+        Grade::C
+    } else {
+        Grade::D
+    }
+}
+
+/// Grade for a value where lower is better, given the A/B/C maximums.
+fn grade_at_most(value: f64, [a, b, c]: [f64; 3]) -> Grade {
+    grade_at_least(-value, [-a, -b, -c])
+}
+
+const EXPLOITABILITY_PCT: [f64; 3] = [1.0, 3.0, 10.0];
+const ITERATION_RATIO: [f64; 3] = [1.0, 0.5, 0.2];
+const BUCKETS_PER_COMBO: [f64; 3] = [0.25, 0.15, 0.05];
+const VISIT_RATIO: [f64; 3] = [0.2, 0.04, 0.004];
+
+/// `value` as a percentage of `starting_pot` (0 for an empty pot).
+pub fn pct_of_pot(value: f64, starting_pot: f64) -> f64 {
+    if starting_pot > 0.0 {
+        value / starting_pot * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Solve-wide quality, stored with each solution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolveQuality {
+    /// Worst of the solve-wide components.
+    pub grade: Grade,
+    /// Exploitability as a percentage of the starting pot.
+    pub exploitability_pct: f64,
+    pub iterations: usize,
+    pub recommended_iterations: usize,
+    /// Strategy buckets per combo in the larger range (1.0 = one per combo).
+    pub buckets_per_combo: f64,
+}
+
+impl SolveQuality {
+    /// Grade a solve. `buckets` is `None` for solvers that store one strategy
+    /// per combo.
+    pub fn grade(
+        exploitability: f64,
+        starting_pot: f64,
+        iterations: usize,
+        recommended_iterations: usize,
+        buckets: Option<usize>,
+        combos: usize,
+    ) -> SolveQuality {
+        let exploitability_pct = pct_of_pot(exploitability.max(0.0), starting_pot);
+        let buckets_per_combo = match buckets {
+            Some(b) if combos > 0 => (b as f64 / combos as f64).min(1.0),
+            _ => 1.0,
+        };
+        let grade = grade_at_most(exploitability_pct, EXPLOITABILITY_PCT)
+            .max(grade_at_least(iterations as f64 / recommended_iterations as f64, ITERATION_RATIO))
+            .max(grade_at_least(buckets_per_combo, BUCKETS_PER_COMBO));
+        SolveQuality {
+            grade,
+            exploitability_pct,
+            iterations,
+            recommended_iterations,
+            buckets_per_combo,
+        }
+    }
+}
+
+/// Quality of one answer: the solve's grade, capped by how often the
+/// answered node was visited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnswerQuality {
+    pub grade: Grade,
+    pub solve: SolveQuality,
+    pub node_visits: f64,
+}
+
+impl AnswerQuality {
+    pub fn at_node(solve: &SolveQuality, node_visits: f64) -> AnswerQuality {
+        let visit_grade = grade_at_least(node_visits / solve.recommended_iterations as f64, VISIT_RATIO);
+        AnswerQuality {
+            grade: solve.grade.max(visit_grade),
+            solve: solve.clone(),
+            node_visits,
+        }
+    }
+
+    /// "B — exploitability 0.8% pot, node visited 40K times"
+    pub fn describe(&self) -> String {
+        format!(
+            "{} — exploitability {:.1}% pot, node visited {} times",
+            self.grade,
+            self.solve.exploitability_pct,
+            format_count(self.node_visits)
+        )
+    }
+}
+
+/// 40000 → "40K", 1250000 → "1.2M".
+pub fn format_count(n: f64) -> String {
+    if n >= 1e6 {
+        format!("{:.1}M", n / 1e6)
+    } else if n >= 1e3 {
+        format!("{:.0}K", n / 1e3)
+    } else {
+        format!("{:.0}", n)
+    }
+}
+
+/// Probability of reaching flop `node` under the stored average strategies,
+/// each action's frequency averaged over the acting range.
+pub fn flop_node_reach(solution: &FlopSolution, node: u16) -> f64 {
+    let mut reach = 1.0;
+    let mut current = node;
+    // Walk up the tree; each node has one parent edge
+    while let Some(edge) = solution.flop_tree_edges.iter().find(|e| e.to == current) {
+        let freq = solution
+            .strategies
+            .iter()
+            .find(|s| s.node_id == edge.from)
+            .and_then(|s| {
+                let a = s.actions.iter().position(|l| *l == edge.action)?;
+                let n = s.frequencies.len();
+                (n > 0).then(|| s.frequencies.iter().map(|f| f[a]).sum::<f64>() / n as f64)
+            })
+            .unwrap_or(1.0);
+        reach *= freq;
+        current = edge.from;
+    }
+    reach
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grade_is_worst_component() {
+        // 0.5% pot, full iterations, 200 buckets for 400 combos: A
+        let q = SolveQuality::grade(0.03, 6.0, 500_000, RECOMMENDED_FLOP_ITERATIONS, Some(200), 400);
+        assert_eq!(q.grade, Grade::A);
+        assert!((q.exploitability_pct - 0.5).abs() < 1e-9);
+
+        // Same solve at 2% pot: B
+        let q = SolveQuality::grade(0.12, 6.0, 500_000, RECOMMENDED_FLOP_ITERATIONS, Some(200), 400);
+        assert_eq!(q.grade, Grade::B);
+
+        // A third of the recommended iterations: C
+        let q = SolveQuality::grade(0.03, 6.0, 150_000, RECOMMENDED_FLOP_ITERATIONS, Some(200), 400);
+        assert_eq!(q.grade, Grade::C);
+
+        // 200 buckets for 5000 combos: D
+        let q = SolveQuality::grade(0.03, 6.0, 500_000, RECOMMENDED_FLOP_ITERATIONS, Some(200), 5000);
+        assert_eq!(q.grade, Grade::D);
+
+        // Exact solver at its recommended iterations
+        let q = SolveQuality::grade(0.05, 10.0, 10_000, RECOMMENDED_RIVER_ITERATIONS, None, 300);
+        assert_eq!(q.grade, Grade::A);
+        assert_eq!(q.buckets_per_combo, 1.0);
+    }
+
+    #[test]
+    fn test_answer_grade_capped_by_visits() {
+        let solve = SolveQuality::grade(0.03, 6.0, 500_000, RECOMMENDED_FLOP_ITERATIONS, Some(200), 400);
+        assert_eq!(AnswerQuality::at_node(&solve, 500_000.0).grade, Grade::A);
+        let deep = AnswerQuality::at_node(&solve, 40_000.0);
+        assert_eq!(deep.grade, Grade::B);
+        assert_eq!(deep.describe(), "B — exploitability 0.5% pot, node visited 40K times");
+        assert_eq!(AnswerQuality::at_node(&solve, 500.0).grade, Grade::D);
+    }
+
+    #[test]
+    fn test_grade_order_and_parse() {
+        assert!(Grade::A.meets(Grade::B));
+        assert!(Grade::B.meets(Grade::B));
+        assert!(!Grade::C.meets(Grade::B));
+        assert_eq!(Grade::parse("b"), Some(Grade::B));
+        assert_eq!(Grade::parse("E"), None);
+    }
+}
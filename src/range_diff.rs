@@ -0,0 +1,160 @@
+//! Per-hand changes between two preflop ranges (`gto range diff`): one spot
+//! solved at two stack depths, or a solve against the static charts.
+//!
+//! A range here is the hero's frequency of playing each of the 169 hands at
+//! the spot's decision, in strategy-array order (see `preflop_json`).
+
+use crate::game_tree::{bucket_to_hand, hand_to_bucket, NUM_HANDS};
+use crate::preflop::{get_rfi_range, get_vs_3bet_range, get_vs_rfi_range};
+use crate::preflop_solver::{weighted_pct, Position, PreflopSolution, PreflopSpotResult};
+
+/// Changes smaller than this (under half a percentage point) count as none.
+const UNCHANGED: f64 = 0.005;
+
+/// The decision whose range is compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffSpot {
+    /// The hero's open.
+    Open,
+    /// The hero's defense (call or 3-bet) against `villain`'s open.
+    VsOpen(Position),
+    /// The hero's continue (call or 4-bet) after opening into `villain`'s 3-bet.
+    Vs3Bet(Position),
+}
+
+impl DiffSpot {
+    pub fn label(&self, hero: Position) -> String {
+        match self {
+            DiffSpot::Open => format!("{} open", hero.as_str()),
+            DiffSpot::VsOpen(villain) => format!("{} vs {} open (call + 3-bet)", hero.as_str(), villain.as_str()),
+            DiffSpot::Vs3Bet(villain) => format!("{} vs {} 3-bet (call + 4-bet)", hero.as_str(), villain.as_str()),
+        }
+    }
+
+    /// The hero's range at this spot in a preflop solve. Opens come from the
+    /// first spot the hero opens in, like `gto range --solved`.
+    pub fn solved(&self, solution: &PreflopSolution, hero: Position) -> Result<Vec<f64>, String> {
+        let plus = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x + y).collect();
+        let spot = |opener: Position, responder: Position| -> Result<&PreflopSpotResult, String> {
+            solution.find_spot(opener, responder).ok_or_else(|| {
+                format!("No solved spot for {} open vs {}", opener.as_str(), responder.as_str())
+            })
+        };
+        match *self {
+            DiffSpot::Open => solution
+                .spots
+                .iter()
+                .find(|s| s.opener == hero)
+                .map(|s| s.open_strategy.clone())
+                .ok_or_else(|| format!("No opening spot found for {}", hero.as_str())),
+            DiffSpot::VsOpen(villain) => spot(villain, hero).map(|s| plus(&s.vs_open_3bet, &s.vs_open_call)),
+            DiffSpot::Vs3Bet(villain) => spot(hero, villain).map(|s| plus(&s.vs_3bet_4bet, &s.vs_3bet_call)),
+        }
+    }
+
+    /// The hero's range at this spot in the static charts, each listed hand
+    /// at full frequency.
+    pub fn chart(&self, hero: Position, table_size: &str) -> Result<Vec<f64>, String> {
+        let hands = match self {
+            DiffSpot::Open => get_rfi_range(hero.as_str(), table_size),
+            DiffSpot::VsOpen(villain) => {
+                let r = get_vs_rfi_range(hero.as_str(), villain.as_str(), table_size);
+                r.call.into_iter().chain(r.three_bet).collect()
+            }
+            DiffSpot::Vs3Bet(villain) => {
+                let r = get_vs_3bet_range(hero.as_str(), villain.as_str(), table_size);
+                r.call.into_iter().chain(r.four_bet).collect()
+            }
+        };
+        if hands.is_empty() {
+            return Err(format!("No {} chart range for {}", table_size, self.label(hero)));
+        }
+        let mut freqs = vec![0.0; NUM_HANDS];
+        for hand in &hands {
+            if let Some(i) = hand_to_bucket(hand) {
+                freqs[i] = 1.0;
+            }
+        }
+        Ok(freqs)
+    }
+}
+
+/// One hand's change between the two ranges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandShift {
+    pub hand: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl HandShift {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// Two ranges of one spot, each 169 frequencies.
+pub struct RangeDiff {
+    pub before: Vec<f64>,
+    pub after: Vec<f64>,
+}
+
+impl RangeDiff {
+    pub fn new(before: Vec<f64>, after: Vec<f64>) -> Self {
+        RangeDiff { before, after }
+    }
+
+    /// `after - before` for each hand.
+    pub fn deltas(&self) -> Vec<f64> {
+        self.after.iter().zip(&self.before).map(|(a, b)| a - b).collect()
+    }
+
+    /// Percentage of all combos each range plays.
+    pub fn range_pcts(&self) -> (f64, f64) {
+        (weighted_pct(&self.before), weighted_pct(&self.after))
+    }
+
+    /// The `n` hands whose frequency moved the most, largest first; hands
+    /// that didn't move are left out.
+    pub fn largest_shifts(&self, n: usize) -> Vec<HandShift> {
+        let mut shifts: Vec<HandShift> = (0..NUM_HANDS)
+            .map(|i| HandShift { hand: bucket_to_hand(i), before: self.before[i], after: self.after[i] })
+            .filter(|s| s.delta().abs() >= UNCHANGED)
+            .collect();
+        shifts.sort_by(|a, b| b.delta().abs().total_cmp(&a.delta().abs()));
+        shifts.truncate(n);
+        shifts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn largest_shifts_rank_by_size_either_way() {
+        let mut before = vec![0.0; NUM_HANDS];
+        let mut after = vec![0.0; NUM_HANDS];
+        let aa = hand_to_bucket("AA").unwrap();
+        let a5s = hand_to_bucket("A5s").unwrap();
+        let k9o = hand_to_bucket("K9o").unwrap();
+        (before[aa], after[aa]) = (1.0, 1.0);
+        (before[a5s], after[a5s]) = (0.2, 0.9);
+        (before[k9o], after[k9o]) = (0.8, 0.0);
+
+        let shifts = RangeDiff::new(before, after).largest_shifts(10);
+        let hands: Vec<&str> = shifts.iter().map(|s| s.hand.as_str()).collect();
+        assert_eq!(hands, vec!["K9o", "A5s"]);
+        assert!((shifts[0].delta() + 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn chart_ranges_play_listed_hands_in_full() {
+        let open = DiffSpot::Open.chart(Position::UTG, "6max").unwrap();
+        assert_eq!(open[hand_to_bucket("AA").unwrap()], 1.0);
+        assert_eq!(open[hand_to_bucket("72o").unwrap()], 0.0);
+
+        let defend = DiffSpot::VsOpen(Position::UTG).chart(Position::BB, "6max").unwrap();
+        assert!(defend.iter().filter(|&&f| f == 1.0).count() > 10);
+    }
+}
@@ -0,0 +1,229 @@
+//! Top-line action frequencies of a solved street: how each player's range
+//! splits at the root, and how the other player's range answers each root
+//! action.
+//!
+//! Frequencies are averaged over combos at their range weight. A response
+//! is weighted by how often the opponent reaches it: each combo facing a
+//! bet counts in proportion to the opponent's betting combos it doesn't
+//! block, so a line the opponent rarely takes only shows the combos that
+//! actually face it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::cards::parse_board;
+use crate::card_encoding::card_to_index;
+use crate::postflop_tree::{player_label, Player, TreeNode};
+use crate::river_solver::expand_weighted_range_to_combos;
+
+/// One decision's aggregate frequencies.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionSummary {
+    pub node_id: u16,
+    /// "OOP" or "IP".
+    pub player: String,
+    /// Actions leading here from the root: empty at the root.
+    pub line: Vec<String>,
+    pub actions: Vec<String>,
+    /// Share of the range taking each action, indexed like `actions`.
+    pub frequencies: Vec<f64>,
+}
+
+/// The root decision and the responses to each root action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RangeSummary {
+    pub decisions: Vec<DecisionSummary>,
+}
+
+/// A player's combos with their range weights.
+pub struct WeightedCombos<'a> {
+    pub combos: &'a [String],
+    pub weights: &'a [f64],
+}
+
+/// Summarize the first two levels of `root`. `strategy` gives the actions
+/// and per-combo frequencies ([combo_idx][action_idx]) solved at a node.
+pub fn summarize<'a>(
+    root: &TreeNode,
+    strategy: impl Fn(u16) -> Option<(&'a [String], &'a [Vec<f64>])>,
+    oop: WeightedCombos,
+    ip: WeightedCombos,
+) -> RangeSummary {
+    let decisions = reached_decisions(root, strategy, &oop, &ip)
+        .into_iter()
+        .map(|d| DecisionSummary {
+            node_id: d.node_id,
+            player: player_label(d.player).to_string(),
+            line: d.line,
+            actions: d.actions.to_vec(),
+            frequencies: aggregate(d.frequencies, &d.reach),
+        })
+        .collect();
+    RangeSummary { decisions }
+}
+
+/// A decision `summarize` covers, with how much of each of the acting
+/// player's combos reaches it.
+pub(crate) struct ReachedDecision<'a, 't> {
+    pub node: &'t TreeNode,
+    pub node_id: u16,
+    pub player: Player,
+    pub line: Vec<String>,
+    pub actions: &'a [String],
+    pub frequencies: &'a [Vec<f64>],
+    /// Range weight times the opponent's frequency of the line, indexed like
+    /// the acting player's combos.
+    pub reach: Vec<f64>,
+}
+
+/// The root decision and the decisions right after each root action.
+pub(crate) fn reached_decisions<'a, 't>(
+    root: &'t TreeNode,
+    strategy: impl Fn(u16) -> Option<(&'a [String], &'a [Vec<f64>])>,
+    oop: &WeightedCombos,
+    ip: &WeightedCombos,
+) -> Vec<ReachedDecision<'a, 't>> {
+    let side = |player: Player| if player == Player::OOP { oop } else { ip };
+    let TreeNode::Action { node_id, player, children, .. } = root else {
+        return Vec::new();
+    };
+    let Some((actions, freqs)) = strategy(*node_id) else {
+        return Vec::new();
+    };
+    let first = side(*player);
+    let mut decisions = vec![ReachedDecision {
+        node: root,
+        node_id: *node_id,
+        player: *player,
+        line: Vec::new(),
+        actions,
+        frequencies: freqs,
+        reach: first.weights.to_vec(),
+    }];
+
+    for (a, child) in children.iter().enumerate() {
+        let TreeNode::Action { node_id: child_id, player: responder, .. } = child else {
+            continue;
+        };
+        let Some((child_actions, child_freqs)) = strategy(*child_id) else {
+            continue;
+        };
+        let second = side(*responder);
+        let reach: Vec<f64> = second
+            .combos
+            .iter()
+            .zip(second.weights)
+            .map(|(combo, w)| w * line_frequency(combo, first, freqs, a))
+            .collect();
+        decisions.push(ReachedDecision {
+            node: child,
+            node_id: *child_id,
+            player: *responder,
+            line: vec![actions.get(a).cloned().unwrap_or_default()],
+            actions: child_actions,
+            frequencies: child_freqs,
+            reach,
+        });
+    }
+    decisions
+}
+
+/// Initial weight of each of `combos`: its hand's frequency in the range.
+pub fn combo_weights(range: &[String], weights: &[f64], board: &str, combos: &[String]) -> Vec<f64> {
+    let board: Vec<u8> = parse_board(board)
+        .map(|cards| cards.iter().map(card_to_index).collect())
+        .unwrap_or_default();
+    let (expanded, expanded_weights) = expand_weighted_range_to_combos(range, weights, &board);
+    let weight_of: std::collections::HashMap<String, f64> =
+        expanded.iter().map(|c| c.to_string()).zip(expanded_weights).collect();
+    combos.iter().map(|c| weight_of.get(c).copied().unwrap_or(1.0)).collect()
+}
+
+/// Share of the range taking each action, each combo counted at `reach`.
+fn aggregate(freqs: &[Vec<f64>], reach: &[f64]) -> Vec<f64> {
+    let num_actions = freqs.first().map_or(0, |f| f.len());
+    let total: f64 = reach.iter().take(freqs.len()).sum();
+    (0..num_actions)
+        .map(|a| {
+            if total <= 0.0 {
+                return 0.0;
+            }
+            freqs.iter().zip(reach).map(|(f, r)| r * f[a]).sum::<f64>() / total
+        })
+        .collect()
+}
+
+/// How often `opponent` takes action `a`, among its combos that share no
+/// card with `combo`.
+fn line_frequency(combo: &str, opponent: &WeightedCombos, freqs: &[Vec<f64>], a: usize) -> f64 {
+    let (mut taking, mut total) = (0.0, 0.0);
+    for ((other, w), f) in opponent.combos.iter().zip(opponent.weights).zip(freqs) {
+        if shares_card(combo, other) {
+            continue;
+        }
+        taking += w * f[a];
+        total += w;
+    }
+    if total > 0.0 { taking / total } else { 0.0 }
+}
+
+fn shares_card(a: &str, b: &str) -> bool {
+    let cards = |c: &str| [c.get(0..2).unwrap_or("").to_string(), c.get(2..4).unwrap_or("").to_string()];
+    let (a, b) = (cards(a), cards(b));
+    a.iter().any(|card| b.contains(card))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::postflop_tree::{build_tree, TreeConfig};
+
+    #[test]
+    fn test_responses_are_weighted_by_the_line_and_card_removal() {
+        let (root, _) = build_tree(&TreeConfig {
+            bet_sizes: vec![1.0],
+            add_allin: false,
+            max_raises: 0,
+            ..TreeConfig::default_river(10.0, 100.0)
+        });
+        let TreeNode::Action { children, .. } = &root else { unreachable!() };
+        let (check_id, bet_id) = match (&children[0], &children[1]) {
+            (TreeNode::Action { node_id: c, .. }, TreeNode::Action { node_id: b, .. }) => (*c, *b),
+            _ => panic!("OOP's check and bet should lead to IP decisions"),
+        };
+
+        let oop_combos = vec!["AhAs".to_string(), "KhKs".to_string()];
+        let ip_combos = vec!["AdAc".to_string(), "AhKd".to_string()];
+        let root_actions = vec!["Check".to_string(), "Bet 10.0".to_string()];
+        // AhAs always bets, KhKs always checks
+        let root_freqs = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        let check_actions = vec!["Check".to_string(), "Bet 10.0".to_string()];
+        let check_freqs = vec![vec![1.0, 0.0], vec![1.0, 0.0]];
+        let bet_actions = vec!["Fold".to_string(), "Call 10.0".to_string()];
+        // AdAc calls, AhKd folds, but AhKd blocks OOP's only bet
+        let bet_freqs = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+
+        let strategy = |id: u16| -> Option<(&[String], &[Vec<f64>])> {
+            match id {
+                0 => Some((&root_actions, &root_freqs)),
+                id if id == check_id => Some((&check_actions, &check_freqs)),
+                id if id == bet_id => Some((&bet_actions, &bet_freqs)),
+                _ => None,
+            }
+        };
+        let summary = summarize(
+            &root,
+            strategy,
+            WeightedCombos { combos: &oop_combos, weights: &[1.0, 3.0] },
+            WeightedCombos { combos: &ip_combos, weights: &[1.0, 1.0] },
+        );
+
+        assert_eq!(summary.decisions.len(), 3);
+        assert_eq!(summary.decisions[0].frequencies, vec![0.75, 0.25]);
+        let vs_bet = &summary.decisions[2];
+        assert_eq!((vs_bet.player.as_str(), vs_bet.line.clone()), ("IP", vec!["Bet 10.0".to_string()]));
+        // Only AdAc ever faces the bet, and it calls
+        assert_eq!(vs_bet.frequencies, vec![0.0, 1.0]);
+        assert!(shares_card("AhKd", "AhAs"));
+        assert!(!shares_card("AdAc", "KhKs"));
+    }
+}
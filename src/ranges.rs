@@ -1,4 +1,4 @@
-use crate::cards::{hand_combos, Card, RANKS_STR};
+use crate::cards::{hand_combos, parse_card, Card, RANKS_STR};
 use crate::error::{GtoError, GtoResult};
 
 pub const HAND_RANKING: &[&str] = &[
@@ -36,104 +36,258 @@ pub fn combo_count(notation: &str) -> u32 {
     0
 }
 
-pub fn parse_range(range_str: &str) -> Vec<String> {
-    let mut hands = std::collections::HashSet::new();
-    for part in range_str.replace(' ', "").split(',') {
-        let part = part.trim();
-        if part.is_empty() {
-            continue;
-        }
-        if part.ends_with('+') {
-            for h in expand_plus(&part[..part.len() - 1]) {
-                hands.insert(h);
-            }
-        } else if part.contains('-') && part.len() > 3 {
-            for h in expand_dash(part) {
-                hands.insert(h);
-            }
-        } else {
-            hands.insert(part.to_string());
+/// Parse a range like `"QQ+, AKs, KQo-KTo, 76s-54s, top 15%"` into its
+/// hands, strongest first (see `HAND_RANKING`).
+///
+/// A term is a hand (`AA`, `AKs`, `AKo`, `AK` for both, or a combo like
+/// `AhKs`), a hand and better (`TT+`, `ATs+`), a dash range between two
+/// hands sharing a high card or a gap (`77-TT`, `KQo-KTo`, `76s-54s`), or
+/// `top X%` (see `range_from_top_pct`). Ranks may be in either case and
+/// spaces are ignored. Frequencies (`KK:0.5`) are for `parse_weighted_range`;
+/// the hands still count. Every term that isn't one of these is listed in
+/// the error.
+pub fn parse_range(range_str: &str) -> GtoResult<Vec<String>> {
+    Ok(parse_weighted_range(range_str)?.into_iter().map(|(hand, _)| hand).collect())
+}
+
+/// A two-rank hand class: ranks as indices into `RANKS_STR`, high first,
+/// and 's', 'o' or neither (both kinds, or a pair).
+#[derive(Clone, Copy)]
+struct HandClass {
+    high: usize,
+    low: usize,
+    kind: Option<char>,
+}
+
+impl HandClass {
+    fn parse(text: &str) -> Result<HandClass, String> {
+        let chars: Vec<char> = text.chars().collect();
+        if !(2..=3).contains(&chars.len()) {
+            return Err(format!("'{}' is not a hand", text));
         }
+        let rank = |c: char| RANKS_STR.find(c.to_ascii_uppercase()).ok_or_else(|| format!("'{}' is not a rank", c));
+        let (a, b) = (rank(chars[0])?, rank(chars[1])?);
+        let kind = match chars.get(2).map(|c| c.to_ascii_lowercase()) {
+            None => None,
+            Some(k @ ('s' | 'o')) if a != b => Some(k),
+            Some('s' | 'o') => return Err("pairs are neither suited nor offsuit".to_string()),
+            Some(k) => return Err(format!("'{}' is not s (suited) or o (offsuit)", k)),
+        };
+        Ok(HandClass { high: a.max(b), low: a.min(b), kind })
     }
-    let mut result: Vec<String> = hands.into_iter().collect();
-    result.sort_by_key(|h| hand_strength_index(h));
-    result
-}
 
-fn expand_plus(base: &str) -> Vec<String> {
-    let chars: Vec<char> = base.chars().collect();
+    fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
 
-    // Pair: "TT+"
-    if chars.len() == 2 && chars[0] == chars[1] {
-        if let Some(rank_idx) = RANKS_STR.find(chars[0]) {
-            let ranks: Vec<char> = RANKS_STR.chars().collect();
-            return (rank_idx..ranks.len())
-                .map(|i| format!("{}{}", ranks[i], ranks[i]))
-                .collect();
+    /// This class with `high` and `low` ranks instead, as hand names:
+    /// one for a pair or a given kind, the suited and offsuit hand otherwise.
+    fn names(&self, high: usize, low: usize) -> Vec<String> {
+        let ranks = RANKS_STR.as_bytes();
+        let (h, l) = (ranks[high] as char, ranks[low] as char);
+        match self.kind {
+            _ if high == low => vec![format!("{}{}", h, l)],
+            Some(kind) => vec![format!("{}{}{}", h, l, kind)],
+            None => vec![format!("{}{}s", h, l), format!("{}{}o", h, l)],
         }
-        return vec![base.to_string()];
     }
+}
 
-    // Suited/offsuit: "ATs+"
-    if chars.len() == 3 {
-        let high = chars[0];
-        let low = chars[1];
-        let kind = chars[2];
-        if let (Some(low_idx), Some(high_idx)) = (RANKS_STR.find(low), RANKS_STR.find(high)) {
-            let ranks: Vec<char> = RANKS_STR.chars().collect();
-            return (low_idx..high_idx)
-                .map(|i| format!("{}{}{}", high, ranks[i], kind))
-                .collect();
+/// The hands one range term (frequency split off) stands for, or why it
+/// stands for none.
+fn expand_term(term: &str) -> Result<Vec<String>, String> {
+    let lower = term.to_ascii_lowercase();
+    if let Some(pct) = lower.strip_suffix('%') {
+        let pct = pct.strip_prefix("top").unwrap_or(pct);
+        let pct: f64 = pct.parse().map_err(|_| "write a percentage like top 15%".to_string())?;
+        return range_from_top_pct(pct).map_err(|e| e.to_string());
+    }
+    if let Some(base) = term.strip_suffix('+') {
+        let class = HandClass::parse(base)?;
+        let top = if class.is_pair() { RANKS_STR.len() } else { class.high };
+        return Ok((class.low..top)
+            .flat_map(|r| if class.is_pair() { class.names(r, r) } else { class.names(class.high, r) })
+            .collect());
+    }
+    if let Some((from, to)) = term.split_once('-') {
+        let (a, b) = (HandClass::parse(from)?, HandClass::parse(to)?);
+        if a.is_pair() && b.is_pair() {
+            let (lo, hi) = (a.high.min(b.high), a.high.max(b.high));
+            return Ok((lo..=hi).flat_map(|r| a.names(r, r)).collect());
+        }
+        if a.is_pair() || b.is_pair() || a.kind != b.kind {
+            return Err("both ends of a dash range must be pairs, or the same kind of hand".to_string());
+        }
+        if a.high == b.high {
+            let (lo, hi) = (a.low.min(b.low), a.low.max(b.low));
+            return Ok((lo..=hi).flat_map(|r| a.names(a.high, r)).collect());
+        }
+        if a.high - a.low == b.high - b.low {
+            let gap = a.high - a.low;
+            let (lo, hi) = (a.high.min(b.high), a.high.max(b.high));
+            return Ok((lo..=hi).flat_map(|r| a.names(r, r - gap)).collect());
+        }
+        return Err("the ends of a dash range must share a high card (KQo-KTo) or a gap (76s-54s)".to_string());
+    }
+    if term.len() == 4 && term.is_char_boundary(2) {
+        let (c1, c2) = (parse_card(&term[..2]), parse_card(&term[2..]));
+        if let (Ok(c1), Ok(c2)) = (c1, c2) {
+            if c1 == c2 {
+                return Err(format!("{} is repeated", c1));
+            }
+            return Ok(vec![format!("{}{}", c1, c2)]);
         }
     }
+    let class = HandClass::parse(term)?;
+    Ok(class.names(class.high, class.low))
+}
 
-    vec![base.to_string()]
+fn sort_hands(hands: &mut [(String, f64)]) {
+    // Tie-break on the string so unranked hands come out in a stable order
+    hands.sort_by(|a, b| {
+        hand_strength_index(&a.0)
+            .cmp(&hand_strength_index(&b.0))
+            .then_with(|| a.0.cmp(&b.0))
+    });
 }
 
-fn expand_dash(range_str: &str) -> Vec<String> {
-    let parts: Vec<&str> = range_str.split('-').collect();
-    if parts.len() != 2 {
-        return vec![range_str.to_string()];
+/// Split a range term like `"KK:0.5"` into its hands and frequency (1.0 when
+/// no frequency is given).
+pub(crate) fn split_weight(term: &str) -> GtoResult<(&str, f64)> {
+    match term.split_once(':') {
+        None => Ok((term, 1.0)),
+        Some((hands, weight)) => match weight.trim().parse::<f64>() {
+            Ok(w) if (0.0..=1.0).contains(&w) => Ok((hands, w)),
+            _ => Err(GtoError::InvalidValue(format!(
+                "Invalid frequency '{}' in '{}'; use 0 to 1, e.g. KK:0.5",
+                weight, term
+            ))),
+        },
     }
+}
 
-    let (start, end) = (parts[0], parts[1]);
-    let start_chars: Vec<char> = start.chars().collect();
-    let end_chars: Vec<char> = end.chars().collect();
-    let ranks: Vec<char> = RANKS_STR.chars().collect();
+/// Parse a range whose terms may carry a frequency, like `"AA:1.0, KK:0.5,
+/// AKs"`. Each term expands like `parse_range` and every hand in it gets the
+/// term's frequency; a hand in several terms takes the last one. Sorted like
+/// `parse_range`.
+pub fn parse_weighted_range(range_str: &str) -> GtoResult<Vec<(String, f64)>> {
+    let mut weighted: Vec<(String, f64)> = Vec::new();
+    let mut rejected: Vec<String> = Vec::new();
+    for term in range_str.replace(' ', "").split(',') {
+        if term.is_empty() {
+            continue;
+        }
+        let expanded = split_weight(term)
+            .map_err(|_| "frequencies run from 0 to 1, e.g. KK:0.5".to_string())
+            .and_then(|(hands, weight)| Ok((expand_term(hands)?, weight)));
+        let (hands, weight) = match expanded {
+            Ok(expanded) => expanded,
+            Err(why) => {
+                rejected.push(format!("'{}' ({})", term, why));
+                continue;
+            }
+        };
+        for hand in hands {
+            match weighted.iter_mut().find(|(h, _)| *h == hand) {
+                Some(entry) => entry.1 = weight,
+                None => weighted.push((hand, weight)),
+            }
+        }
+    }
+    if !rejected.is_empty() {
+        return Err(GtoError::InvalidRange(rejected));
+    }
+    sort_hands(&mut weighted);
+    Ok(weighted)
+}
+
+/// A weighted range split into hands and their frequencies, as the postflop
+/// solvers take it. Hands at frequency 0 are left out.
+pub fn parse_range_weights(range_str: &str) -> GtoResult<(Vec<String>, Vec<f64>)> {
+    Ok(parse_weighted_range(range_str)?.into_iter().filter(|&(_, w)| w > 0.0).unzip())
+}
+
+/// A range for display: hands joined by commas, with any frequency below 1
+/// after the hand ("AA,KK,AKo (0.5)"). `weights` is indexed like `hands`;
+/// hands past its end count in full.
+pub fn format_weighted_range(hands: &[String], weights: &[f64]) -> String {
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| match weights.get(i) {
+            Some(&w) if w < 1.0 => format!("{} ({})", hand, w),
+            _ => hand.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
 
-    // Pair range: "77-TT"
-    if start_chars.len() == 2
-        && end_chars.len() == 2
-        && start_chars[0] == start_chars[1]
-        && end_chars[0] == end_chars[1]
-    {
-        if let (Some(si), Some(ei)) = (RANKS_STR.find(start_chars[0]), RANKS_STR.find(end_chars[0]))
-        {
-            let lo = si.min(ei);
-            let hi = si.max(ei);
-            return (lo..=hi).map(|i| format!("{}{}", ranks[i], ranks[i])).collect();
+/// Apply an edit like `"+A5o,-K7s"` to a parsed range.
+///
+/// Each comma-separated term must start with `+` (add) or `-` (remove) followed by
+/// normal range syntax (`+22-55`, `-ATs+`). Terms apply left to right; the result is
+/// re-sorted like `parse_range`.
+pub fn apply_range_edit(base: &[String], edit: &str) -> Result<Vec<String>, String> {
+    let mut hands: Vec<String> = base.to_vec();
+    for term in edit.replace(' ', "").split(',') {
+        if term.is_empty() {
+            continue;
+        }
+        let (adding, body) = if let Some(body) = term.strip_prefix('+') {
+            (true, body)
+        } else if let Some(body) = term.strip_prefix('-') {
+            (false, body)
+        } else {
+            return Err(format!("Range edit term '{}' must start with '+' or '-'", term));
+        };
+        let edited = parse_range(body).map_err(|e| e.to_string())?;
+        if edited.is_empty() {
+            return Err(format!("Empty range edit term '{}'", term));
+        }
+        if adding {
+            for h in edited {
+                if !hands.contains(&h) {
+                    hands.push(h);
+                }
+            }
+        } else {
+            hands.retain(|h| !edited.contains(h));
         }
     }
+    parse_range(&hands.join(",")).map_err(|e| e.to_string())
+}
 
-    // Suited/offsuit range: "KTs-KQs"
-    if start_chars.len() == 3
-        && end_chars.len() == 3
-        && start_chars[0] == end_chars[0]
-        && start_chars[2] == end_chars[2]
-    {
-        let high = start_chars[0];
-        let kind = start_chars[2];
-        if let (Some(si), Some(ei)) = (RANKS_STR.find(start_chars[1]), RANKS_STR.find(end_chars[1]))
-        {
-            let lo = si.min(ei);
-            let hi = si.max(ei);
-            return (lo..=hi)
-                .map(|i| format!("{}{}{}", high, ranks[i], kind))
-                .collect();
+/// `apply_range_edit` for a weighted range (`weights` indexed like `base`).
+/// Hands kept keep their frequency; added hands take their term's, as in
+/// `+A5o:0.5`, or 1.
+pub fn apply_weighted_range_edit(base: &[String], weights: &[f64], edit: &str) -> Result<Vec<(String, f64)>, String> {
+    let weight_in_base = |hand: &str| base.iter().position(|h| h == hand).and_then(|i| weights.get(i)).copied();
+    let mut weighted: Vec<(String, f64)> = apply_range_edit(base, edit)?
+        .into_iter()
+        .map(|h| {
+            let w = weight_in_base(&h).unwrap_or(1.0);
+            (h, w)
+        })
+        .collect();
+    for term in edit.replace(' ', "").split(',') {
+        let Some(added) = term.strip_prefix('+') else { continue };
+        for (hand, w) in parse_weighted_range(added).map_err(|e| e.to_string())? {
+            if let Some(entry) = weighted.iter_mut().find(|(h, _)| *h == hand) {
+                entry.1 = w;
+            }
         }
     }
+    Ok(weighted)
+}
 
-    vec![range_str.to_string()]
+/// Range text for a weighted range that `parse_weighted_range` reads back,
+/// e.g. "AA,KK,AKo:0.5".
+pub fn weighted_range_string(range: &[(String, f64)]) -> String {
+    range
+        .iter()
+        .map(|(hand, w)| if *w < 1.0 { format!("{}:{}", hand, w) } else { hand.clone() })
+        .collect::<Vec<_>>()
+        .join(",")
 }
 
 fn hand_strength_index(hand: &str) -> usize {
@@ -175,6 +329,11 @@ pub fn total_combos_strs(hands: &[&str]) -> u32 {
     hands.iter().map(|h| combo_count(h)).sum()
 }
 
+/// Combos in a weighted range, each counted at its hand's frequency.
+pub fn weighted_total_combos(range: &[(String, f64)]) -> f64 {
+    range.iter().map(|(h, w)| combo_count(h) as f64 * w).sum()
+}
+
 pub fn range_pct(hands: &[String]) -> f64 {
     total_combos(hands) as f64 / 1326.0 * 100.0
 }
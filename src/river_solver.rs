@@ -8,23 +8,176 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::cache_index::{solver_cache_dir, write_atomic};
+use crate::cache_key::{combo_weights_key, range_key, read_spot_cache};
 use crate::card_encoding::card_to_index;
-use crate::cards::{hand_combos, parse_board};
-use crate::cfr::{CfrTrainer, InfoSetKey};
+use crate::cards::{duplicate_cards, hand_combos, hand_to_canonical, parse_board, parse_cards};
+use crate::cfr::{CfrTrainer, CfrVariant, InfoSetKey};
+use crate::error::{SolverError, SolverResult};
+use crate::display::{print_combo_strategies, range_summary_table};
+use crate::flop_enumerator::{canonical_board, relabel_suits, suit_permutation};
 use crate::lookup_eval::evaluate_fast;
-use crate::postflop_tree::{build_tree, Player, TerminalType, TreeConfig, TreeNode};
-use crate::ranges::parse_range;
+use crate::postflop_tree::{
+    build_tree, collect_node_metadata, raise_suffix, sizing_suffix, Player, TerminalType, TreeConfig, TreeNode,
+    DEFAULT_MAX_TREE_NODES, MAX_NUMBERED_NODES,
+};
+use crate::postprocess::{combo_equities, hand_class, PostProcess, PostProcessInfo, Simplify, SimplifyInfo};
+use crate::progress::{PhaseTimer, ProgressCallback, ProgressTracker, SolveStats};
+use crate::quality::{pct_of_pot, SolveQuality, RECOMMENDED_RIVER_ITERATIONS};
+use crate::breakdown::{breakdown, Breakdown};
+use crate::range_summary::{combo_weights, summarize, RangeSummary, WeightedCombos};
+use crate::ranges::{format_weighted_range, parse_range_weights};
 
 // ---------------------------------------------------------------------------
 // Combo representation
 // ---------------------------------------------------------------------------
 
 /// A specific two-card combo as u8 indices (0-51).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Combo(pub u8, pub u8);
 
+/// Version of the canonical combo ordering stored in solutions.
+/// Bump whenever `expand_range_to_combos` ordering changes, so stale caches
+/// with positionally-indexed arrays are rejected instead of misread.
+pub const COMBO_ORDER_VERSION: u32 = 1;
+
+impl Combo {
+    /// Canonical combo: higher card index first (so "AhKh", never "KhAh").
+    pub fn new(a: u8, b: u8) -> Self {
+        if a >= b {
+            Combo(a, b)
+        } else {
+            Combo(b, a)
+        }
+    }
+}
+
+impl std::fmt::Display for Combo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use crate::card_encoding::index_to_card;
+        write!(f, "{}{}", index_to_card(self.0), index_to_card(self.1))
+    }
+}
+
+/// Canonical string for a combo given in either card order ("KhAh" -> "AhKh").
+pub fn canonical_combo_string(combo: &str) -> Option<String> {
+    let cards = parse_board(combo).ok()?;
+    if cards.len() != 2 {
+        return None;
+    }
+    let (a, b) = (card_to_index(&cards[0]), card_to_index(&cards[1]));
+    if a == b {
+        return None;
+    }
+    Some(Combo::new(a, b).to_string())
+}
+
+/// A solution's combo strings back as combos; `None` if one doesn't parse.
+pub(crate) fn parse_combo_strings(combos: &[String]) -> Option<Vec<Combo>> {
+    combos
+        .iter()
+        .map(|c| {
+            let cards = parse_board(c).ok()?;
+            (cards.len() == 2).then(|| Combo::new(card_to_index(&cards[0]), card_to_index(&cards[1])))
+        })
+        .collect()
+}
+
+/// The cards each combo holds, as a 52-card mask.
+pub(crate) fn combo_blockers(combos: &[Combo]) -> Vec<[bool; 52]> {
+    combos
+        .iter()
+        .map(|c| {
+            let mut b = [false; 52];
+            b[c.0 as usize] = true;
+            b[c.1 as usize] = true;
+            b
+        })
+        .collect()
+}
+
+/// For each of `combos`, the `opp_combos` sharing no card with it.
+pub(crate) fn valid_opponents(combos: &[Combo], opp_combos: &[Combo]) -> Vec<Vec<u16>> {
+    combos
+        .iter()
+        .map(|c| {
+            opp_combos
+                .iter()
+                .enumerate()
+                .filter(|(_, o)| c.0 != o.0 && c.0 != o.1 && c.1 != o.0 && c.1 != o.1)
+                .map(|(j, _)| j as u16)
+                .collect()
+        })
+        .collect()
+}
+
+/// Find the index of a specific hand combo (e.g., "AhQd") in a combo list.
+/// Checks both orderings (AhQd and QdAh).
+pub fn find_combo_index(hand_str: &str, combo_list: &[String]) -> Option<usize> {
+    let forward = canonical_combo_string(hand_str)?;
+    let reverse = format!("{}{}", &forward[2..], &forward[..2]);
+    combo_list.iter().position(|c| *c == forward || *c == reverse)
+}
+
+/// Positions in `combos` that `hand` covers: the combo itself ("AhKs", either
+/// card order), or every combo of a canonical hand ("AKo").
+pub(crate) fn hand_combo_indices(combos: &[String], hand: &str) -> Vec<usize> {
+    if hand.len() == 4 {
+        return find_combo_index(hand, combos).into_iter().collect();
+    }
+    (0..combos.len()).filter(|&i| hand_to_canonical(&combos[i]) == hand).collect()
+}
+
+/// Per-combo rows (e.g. one combo's action frequencies) averaged over each
+/// canonical hand's combos, keyed like "AKo". `rows` is indexed like `combos`.
+pub(crate) fn average_by_canonical(combos: &[String], rows: &[Vec<f64>]) -> HashMap<String, Vec<f64>> {
+    let mut sums: HashMap<String, (Vec<f64>, usize)> = HashMap::new();
+    for (combo, row) in combos.iter().zip(rows) {
+        let (sum, n) = sums
+            .entry(hand_to_canonical(combo))
+            .or_insert_with(|| (vec![0.0; row.len()], 0));
+        for (s, v) in sum.iter_mut().zip(row) {
+            *s += v;
+        }
+        *n += 1;
+    }
+    sums.into_iter()
+        .map(|(hand, (sum, n))| (hand, sum.into_iter().map(|s| s / n as f64).collect()))
+        .collect()
+}
+
+/// Relabel the suits of a solution's combos with a relabeling from
+/// `suit_permutation`. Returns the relabeled combos in canonical order and,
+/// for each, its position in `combos` (for `reorder_rows`).
+pub(crate) fn relabel_combos(combos: &[String], suit_map: &[u8; 4]) -> (Vec<String>, Vec<usize>) {
+    let mut relabeled: Vec<(Combo, usize)> = combos
+        .iter()
+        .enumerate()
+        .filter_map(|(i, combo)| {
+            let cards = parse_board(&relabel_suits(combo, suit_map)).ok()?;
+            Some((Combo::new(card_to_index(&cards[0]), card_to_index(&cards[1])), i))
+        })
+        .collect();
+    relabeled.sort_unstable_by_key(|&(combo, _)| std::cmp::Reverse(combo));
+    relabeled.into_iter().map(|(combo, i)| (combo.to_string(), i)).unzip()
+}
+
+/// Per-combo `rows` put in the order `relabel_combos` returned. Anything not
+/// per-combo (e.g. the empty EVs of older caches) is kept as it is.
+pub(crate) fn reorder_rows<T: Clone>(rows: &[T], order: &[usize]) -> Vec<T> {
+    if rows.len() != order.len() {
+        return rows.to_vec();
+    }
+    order.iter().map(|&i| rows[i].clone()).collect()
+}
+
 /// Expand a canonical range (["AA", "AKs", ...]) into specific combos,
 /// filtering out any combos that conflict with the board.
+///
+/// Output is in canonical order regardless of the order of `range`: each
+/// combo has its higher card index first, and combos are sorted descending
+/// (aces first, then by suit s/h/d/c) with duplicates removed. Per-combo
+/// arrays in solutions are indexed by this order (`COMBO_ORDER_VERSION`).
 pub fn expand_range_to_combos(range: &[String], board: &[u8]) -> Vec<Combo> {
     let board_set: [bool; 52] = {
         let mut s = [false; 52];
@@ -41,14 +194,112 @@ pub fn expand_range_to_combos(range: &[String], board: &[u8]) -> Vec<Combo> {
                 let i1 = card_to_index(&c1);
                 let i2 = card_to_index(&c2);
                 if !board_set[i1 as usize] && !board_set[i2 as usize] {
-                    combos.push(Combo(i1, i2));
+                    combos.push(Combo::new(i1, i2));
                 }
             }
         }
     }
+    combos.sort_unstable_by(|a, b| b.cmp(a));
+    combos.dedup();
     combos
 }
 
+/// `expand_range_to_combos` for a weighted range: the combos, plus each
+/// combo's initial reach (its hand's frequency in `weights`, which is indexed
+/// like `range`). Combos of hands past the end of `weights` count in full.
+pub fn expand_weighted_range_to_combos(range: &[String], weights: &[f64], board: &[u8]) -> (Vec<Combo>, Vec<f64>) {
+    let combos = expand_range_to_combos(range, board);
+    let mut weight_of: HashMap<Combo, f64> = HashMap::new();
+    for (hand, &w) in range.iter().zip(weights) {
+        if let Ok(pairs) = hand_combos(hand) {
+            for (c1, c2) in pairs {
+                weight_of.insert(Combo::new(card_to_index(&c1), card_to_index(&c2)), w);
+            }
+        }
+    }
+    let reach = combos.iter().map(|c| weight_of.get(c).copied().unwrap_or(1.0)).collect();
+    (combos, reach)
+}
+
+/// Weights for `combos` from `combo,weight` lines ("AhKd,0.35"), e.g. how
+/// often each combo reaches a river node in a turn solve. Blank lines, `#`
+/// comments and a `combo,weight` header are skipped; combos not listed get
+/// 0. Listing a combo outside `combos` (or twice) is an error.
+pub fn parse_combo_weights_csv(text: &str, combos: &[Combo]) -> Result<Vec<f64>, String> {
+    let mut weights = vec![0.0; combos.len()];
+    let mut seen = vec![false; combos.len()];
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.eq_ignore_ascii_case("combo,weight") {
+            continue;
+        }
+        let (combo, weight) =
+            line.split_once(',').ok_or_else(|| format!("Line {}: expected combo,weight, got '{}'", n + 1, line))?;
+        let canonical =
+            canonical_combo_string(combo.trim()).ok_or_else(|| format!("Line {}: invalid combo '{}'", n + 1, combo.trim()))?;
+        let weight: f64 = weight
+            .trim()
+            .parse()
+            .ok()
+            .filter(|w: &f64| w.is_finite() && *w >= 0.0)
+            .ok_or_else(|| format!("Line {}: invalid weight '{}'", n + 1, weight.trim()))?;
+        let i = combos.iter().position(|c| c.to_string() == canonical).ok_or_else(|| {
+            format!("Line {}: {} isn't in the range (or the board blocks it)", n + 1, canonical)
+        })?;
+        if seen[i] {
+            return Err(format!("Line {}: {} is listed twice", n + 1, canonical));
+        }
+        seen[i] = true;
+        weights[i] = weight;
+    }
+    Ok(weights)
+}
+
+/// A solver spot's board and ranges, parsed and checked (see `parse_spot`).
+pub(crate) struct ParsedSpot {
+    pub board: Vec<u8>,
+    pub oop_range: Vec<String>,
+    pub oop_weights: Vec<f64>,
+    pub ip_range: Vec<String>,
+    pub ip_weights: Vec<f64>,
+}
+
+/// Parse a spot's board, which must have `board_cards` distinct cards.
+pub(crate) fn parse_spot_board(board_str: &str, board_cards: usize) -> SolverResult<Vec<u8>> {
+    let cards = parse_cards(board_str).map_err(|e| SolverError::InvalidBoard { reason: e.to_string() })?;
+    if cards.len() != board_cards {
+        return Err(SolverError::WrongBoardSize { expected: board_cards, got: cards.len() });
+    }
+    if let Some(card) = duplicate_cards(&cards).first() {
+        return Err(SolverError::ConflictingCards { card: card.to_string() });
+    }
+    Ok(cards.iter().map(card_to_index).collect())
+}
+
+/// Parse a spot's board, which must have `board_cards` distinct cards, and
+/// both ranges, each of which must keep a combo the board doesn't block.
+pub(crate) fn parse_spot(
+    board_str: &str,
+    board_cards: usize,
+    oop_range_str: &str,
+    ip_range_str: &str,
+) -> SolverResult<ParsedSpot> {
+    let board = parse_spot_board(board_str, board_cards)?;
+
+    let parse_side = |range_str: &str, side: &'static str| -> SolverResult<(Vec<String>, Vec<f64>)> {
+        let (range, weights) = parse_range_weights(range_str)
+            .map_err(|e| SolverError::InvalidConfig { field: side, reason: e.to_string() })?;
+        // Hands the board blocks entirely would leave nothing to solve
+        if expand_range_to_combos(&range, &board).is_empty() {
+            return Err(SolverError::EmptyRange { side: if side == "OOP range" { "OOP" } else { "IP" } });
+        }
+        Ok((range, weights))
+    };
+    let (oop_range, oop_weights) = parse_side(oop_range_str, "OOP range")?;
+    let (ip_range, ip_weights) = parse_side(ip_range_str, "IP range")?;
+    Ok(ParsedSpot { board, oop_range, oop_weights, ip_range, ip_weights })
+}
+
 // ---------------------------------------------------------------------------
 // Showdown precomputation
 // ---------------------------------------------------------------------------
@@ -65,6 +316,10 @@ pub struct ShowdownTable {
     pub oop_scores: Vec<u32>,
     /// 7-card eval score for each IP combo against the board.
     pub ip_scores: Vec<u32>,
+    /// Initial reach of each OOP combo (its hand's range frequency).
+    pub oop_weights: Vec<f64>,
+    /// Initial reach of each IP combo.
+    pub ip_weights: Vec<f64>,
 }
 
 impl ShowdownTable {
@@ -119,6 +374,8 @@ impl ShowdownTable {
             .collect();
 
         ShowdownTable {
+            oop_weights: vec![1.0; oop_combos.len()],
+            ip_weights: vec![1.0; ip_combos.len()],
             oop_combos,
             ip_combos,
             valid_ip_for_oop,
@@ -128,6 +385,13 @@ impl ShowdownTable {
         }
     }
 
+    /// Start each combo at its range frequency instead of full reach.
+    pub fn with_weights(mut self, oop_weights: Vec<f64>, ip_weights: Vec<f64>) -> Self {
+        self.oop_weights = oop_weights;
+        self.ip_weights = ip_weights;
+        self
+    }
+
     pub fn num_oop(&self) -> usize {
         self.oop_combos.len()
     }
@@ -135,6 +399,68 @@ impl ShowdownTable {
     pub fn num_ip(&self) -> usize {
         self.ip_combos.len()
     }
+
+    /// Opponent reach at the root for `player`'s combo `h`: the range
+    /// frequency of every non-conflicting opponent combo, 0.0 for blocked ones.
+    fn root_opp_reach(&self, player: Player, h: usize) -> Vec<f64> {
+        let (valid, opp_weights) = match player {
+            Player::OOP => (&self.valid_ip_for_oop[h], &self.ip_weights),
+            Player::IP => (&self.valid_oop_for_ip[h], &self.oop_weights),
+        };
+        let mut reach = vec![0.0f64; opp_weights.len()];
+        for &j in valid {
+            reach[j as usize] = opp_weights[j as usize];
+        }
+        reach
+    }
+
+    /// Initial reach of `player`'s combo `h`.
+    fn weight(&self, player: Player, h: usize) -> f64 {
+        match player {
+            Player::OOP => self.oop_weights[h],
+            Player::IP => self.ip_weights[h],
+        }
+    }
+
+    /// Score, opponent scores and non-conflicting opponents for `player`'s
+    /// combo `hand_idx`.
+    fn matchups(&self, player: Player, hand_idx: usize) -> (u32, &[u32], &[u16]) {
+        match player {
+            Player::OOP => (self.oop_scores[hand_idx], &self.ip_scores, &self.valid_ip_for_oop[hand_idx]),
+            Player::IP => (self.ip_scores[hand_idx], &self.oop_scores, &self.valid_oop_for_ip[hand_idx]),
+        }
+    }
+}
+
+/// (win, tie, lose) shares of the showdowns a combo reaches under the final
+/// strategies; all zero for a combo that never reaches showdown.
+pub type ShowdownOutcome = (f64, f64, f64);
+
+/// Opponent reach mass that `my_score` beats, ties and loses to.
+pub(crate) fn showdown_outcome_mass(my_score: u32, opp_scores: &[u32], valid: &[u16], opp_reach: &[f64]) -> [f64; 3] {
+    let mut mass = [0.0f64; 3];
+    for &j in valid {
+        let j = j as usize;
+        if opp_reach[j] < 1e-10 {
+            continue;
+        }
+        let outcome = match my_score.cmp(&opp_scores[j]) {
+            std::cmp::Ordering::Greater => 0,
+            std::cmp::Ordering::Equal => 1,
+            std::cmp::Ordering::Less => 2,
+        };
+        mass[outcome] += opp_reach[j];
+    }
+    mass
+}
+
+/// Normalize win/tie/lose mass into shares of the showdowns reached.
+pub(crate) fn outcome_shares(mass: [f64; 3]) -> ShowdownOutcome {
+    let total: f64 = mass.iter().sum();
+    if total < 1e-12 {
+        return (0.0, 0.0, 0.0);
+    }
+    (mass[0] / total, mass[1] / total, mass[2] / total)
 }
 
 // ---------------------------------------------------------------------------
@@ -143,14 +469,63 @@ impl ShowdownTable {
 
 pub struct RiverSolverConfig {
     pub board: Vec<u8>,
+    /// Cards known to be out of the deck (`--dead`, e.g. a shown fold):
+    /// combos holding one are left out, as if they were on the board.
+    pub dead_cards: Vec<u8>,
     pub oop_range: Vec<String>,
     pub ip_range: Vec<String>,
+    /// Frequency (0 to 1) of each hand in `oop_range`, e.g. 0.5 for `AKo:0.5`.
+    pub oop_weights: Vec<f64>,
+    /// Frequency of each hand in `ip_range`.
+    pub ip_weights: Vec<f64>,
+    /// Initial reach of each OOP combo, indexed like `combos(Player::OOP)`:
+    /// e.g. how often each arrives at this river in a turn solve. Replaces
+    /// `oop_weights` when set; see `validate_combo_weights`.
+    pub oop_combo_weights: Option<Vec<f64>>,
+    /// Initial reach of each IP combo, like `oop_combo_weights`.
+    pub ip_combo_weights: Option<Vec<f64>>,
     pub starting_pot: f64,
     pub effective_stack: f64,
     pub iterations: usize,
+    /// Bet sizes as fractions of the pot; sizes past the stack become an all-in.
     pub bet_sizes: Vec<f64>,
+    /// Raise sizes as fractions of the pot after calling.
     pub raise_sizes: Vec<f64>,
+    /// Most raises in the betting round.
     pub max_raises: usize,
+    /// Offer an all-in alongside the bet sizes.
+    pub add_allin: bool,
+    /// Most decision nodes the tree may have (see `validate_tree_size`).
+    pub max_tree_nodes: usize,
+    /// Smoothing/purification applied to the average strategy before extraction.
+    pub postprocess: Option<PostProcess>,
+    /// Measure exploitability every this many iterations (see `progress`).
+    pub progress_interval: Option<usize>,
+    /// Called with each progress snapshot.
+    pub on_progress: Option<ProgressCallback>,
+    /// Stop once exploitability is at or below this percent of the starting
+    /// pot; `iterations` is then a cap.
+    pub target_exploitability: Option<f64>,
+    /// Stop once this much wall time has gone into iterating; `iterations`
+    /// is then a cap (see `progress`).
+    pub time_budget: Option<std::time::Duration>,
+    /// Nodes whose strategy is fixed rather than solved (see `LockedNode`).
+    pub locked_nodes: Vec<LockedNode>,
+    /// Regret-minimization variant (CFR+ by default).
+    pub cfr_variant: CfrVariant,
+}
+
+/// A strategy fixed for one player at one node (node locking): the solver
+/// plays it there as given and solves the rest of the tree against it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedNode {
+    pub node_id: u16,
+    pub player: Player,
+    /// Frequency of each of the node's actions, for every combo; empty to
+    /// lock only the combos in `combo_strategies`.
+    pub strategy: Vec<f64>,
+    /// Per-combo strategies (e.g. "AhKd"), overriding `strategy`.
+    pub combo_strategies: Vec<(String, Vec<f64>)>,
 }
 
 impl RiverSolverConfig {
@@ -161,34 +536,258 @@ impl RiverSolverConfig {
         starting_pot: f64,
         effective_stack: f64,
         iterations: usize,
-    ) -> Result<Self, String> {
-        let board_cards = parse_board(board_str).map_err(|e| e.to_string())?;
-        if board_cards.len() != 5 {
-            return Err("River board must have exactly 5 cards".to_string());
-        }
-        let board: Vec<u8> = board_cards.iter().map(|c| card_to_index(c)).collect();
-        let oop_range = parse_range(oop_range_str);
-        let ip_range = parse_range(ip_range_str);
-
-        if oop_range.is_empty() {
-            return Err("OOP range is empty".to_string());
-        }
-        if ip_range.is_empty() {
-            return Err("IP range is empty".to_string());
-        }
+    ) -> SolverResult<Self> {
+        crate::limits::RIVER_ITERATIONS.check_config(iterations)?;
+        let ParsedSpot { board, oop_range, oop_weights, ip_range, ip_weights } =
+            parse_spot(board_str, 5, oop_range_str, ip_range_str)?;
 
         Ok(RiverSolverConfig {
             board,
+            dead_cards: Vec::new(),
             oop_range,
             ip_range,
+            oop_weights,
+            ip_weights,
+            oop_combo_weights: None,
+            ip_combo_weights: None,
             starting_pot,
             effective_stack,
             iterations,
-            bet_sizes: vec![0.33, 0.67, 1.0],
-            raise_sizes: vec![1.0],
-            max_raises: 3,
+            bet_sizes: DEFAULT_RIVER_BET_SIZES.to_vec(),
+            raise_sizes: default_river_raise_sizes(),
+            max_raises: default_river_max_raises(),
+            add_allin: true,
+            max_tree_nodes: DEFAULT_MAX_TREE_NODES,
+            postprocess: None,
+            progress_interval: None,
+            on_progress: None,
+            target_exploitability: None,
+            time_budget: None,
+            locked_nodes: vec![],
+            cfr_variant: CfrVariant::CfrPlus,
         })
     }
+
+    pub fn tree_config(&self) -> TreeConfig {
+        TreeConfig {
+            bet_sizes: self.bet_sizes.clone(),
+            raise_sizes: self.raise_sizes.clone(),
+            max_raises: self.max_raises,
+            starting_pot: self.starting_pot,
+            effective_stack: self.effective_stack,
+            add_allin: self.add_allin,
+        }
+    }
+
+    /// The tree's decision node count, or an error when it's over
+    /// `max_tree_nodes` or too large to number.
+    pub fn validate_tree_size(&self) -> SolverResult<usize> {
+        let nodes = build_tree(&self.tree_config()).1;
+        let limit = self.max_tree_nodes.min(MAX_NUMBERED_NODES);
+        if nodes > limit {
+            return Err(SolverError::TreeTooLarge { nodes, limit });
+        }
+        Ok(nodes)
+    }
+
+    /// `player`'s combos as the solver expands them, without those the board
+    /// or dead cards block: the order `*_combo_weights` are indexed in.
+    pub fn combos(&self, player: Player) -> Vec<Combo> {
+        let range = match player {
+            Player::OOP => &self.oop_range,
+            Player::IP => &self.ip_range,
+        };
+        expand_range_to_combos(range, &[self.board.as_slice(), &self.dead_cards].concat())
+    }
+
+    /// Check the per-combo weights: one finite, non-negative weight for
+    /// each of the side's combos, not all zero.
+    pub fn validate_combo_weights(&self) -> SolverResult<()> {
+        for (player, weights, field, side) in [
+            (Player::OOP, &self.oop_combo_weights, "OOP combo weights", "OOP"),
+            (Player::IP, &self.ip_combo_weights, "IP combo weights", "IP"),
+        ] {
+            let Some(weights) = weights else { continue };
+            let expected = self.combos(player).len();
+            if weights.len() != expected {
+                return Err(SolverError::InvalidConfig {
+                    field,
+                    reason: format!("{} weights for {} combos", weights.len(), expected),
+                });
+            }
+            if let Some(w) = weights.iter().find(|w| !(w.is_finite() && **w >= 0.0)) {
+                return Err(SolverError::InvalidConfig { field, reason: format!("{} isn't a weight", w) });
+            }
+            if weights.iter().all(|&w| w == 0.0) {
+                return Err(SolverError::EmptyRange { side });
+            }
+        }
+        Ok(())
+    }
+
+    /// `player`'s combos and their initial reach: the per-combo weights if
+    /// set, else each hand's frequency. The weights must have passed
+    /// `validate_combo_weights`.
+    fn initial_reach(&self, player: Player) -> (Vec<Combo>, Vec<f64>) {
+        let (range, weights, combo_weights) = match player {
+            Player::OOP => (&self.oop_range, &self.oop_weights, &self.oop_combo_weights),
+            Player::IP => (&self.ip_range, &self.ip_weights, &self.ip_combo_weights),
+        };
+        let blocked = [self.board.as_slice(), &self.dead_cards].concat();
+        let (combos, reach) = expand_weighted_range_to_combos(range, weights, &blocked);
+        match combo_weights {
+            Some(w) => {
+                debug_assert_eq!(w.len(), combos.len());
+                (combos, w.clone())
+            }
+            None => (combos, reach),
+        }
+    }
+
+    /// Lock a node from a spec like "node=3,player=OOP,strategy=check:1.0".
+    /// More actions go in the strategy separated by `|` ("check:0.5|bet 6.6:0.5",
+    /// any unambiguous prefix of an action label will do) and unlisted ones get
+    /// 0; `combo=AhKd` locks just that combo. Locking a node again replaces
+    /// the earlier strategy.
+    pub fn add_lock(&mut self, spec: &str) -> Result<(), String> {
+        let (mut node_id, mut player, mut strategy, mut combo) = (None, None, None, None);
+        for part in spec.split(',') {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid lock '{}': expected node=..,player=..,strategy=..", spec))?;
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "node" => node_id = Some(value.parse::<u16>().map_err(|_| format!("Invalid lock node '{}'", value))?),
+                "player" => {
+                    player = Some(match value.to_uppercase().as_str() {
+                        "OOP" => Player::OOP,
+                        "IP" => Player::IP,
+                        _ => return Err(format!("Invalid lock player '{}'. Valid: OOP, IP", value)),
+                    })
+                }
+                "strategy" => strategy = Some(value),
+                "combo" => {
+                    combo = Some(canonical_combo_string(value).ok_or_else(|| format!("Invalid lock combo '{}'", value))?)
+                }
+                other => return Err(format!("Unknown lock key '{}'. Valid: node, player, strategy, combo", other)),
+            }
+        }
+        let (Some(node_id), Some(player), Some(strategy)) = (node_id, player, strategy) else {
+            return Err(format!("Lock '{}' needs node, player and strategy", spec));
+        };
+
+        let (tree, _) = build_tree(&self.tree_config());
+        let Some(TreeNode::Action { player: node_player, actions, .. }) = tree.find_action(node_id) else {
+            let nodes: Vec<String> = collect_node_metadata(&tree)
+                .iter()
+                .map(|m| format!("{} ({:?})", m.node_id, m.player))
+                .collect();
+            return Err(format!("No decision node {}. Nodes: {}", node_id, nodes.join(", ")));
+        };
+        if *node_player != player {
+            return Err(format!("Node {} is {:?}'s decision, not {:?}'s", node_id, node_player, player));
+        }
+        let labels: Vec<String> = actions.iter().map(|a| a.label()).collect();
+        let freqs = parse_lock_strategy(strategy, &labels)?;
+
+        let index = match self.locked_nodes.iter().position(|l| l.node_id == node_id) {
+            Some(i) => i,
+            None => {
+                self.locked_nodes.push(LockedNode { node_id, player, strategy: vec![], combo_strategies: vec![] });
+                self.locked_nodes.len() - 1
+            }
+        };
+        let lock = &mut self.locked_nodes[index];
+        match combo {
+            Some(combo) => {
+                lock.combo_strategies.retain(|(c, _)| *c != combo);
+                lock.combo_strategies.push((combo, freqs));
+            }
+            None => lock.strategy = freqs,
+        }
+        Ok(())
+    }
+}
+
+/// Action frequencies from a lock strategy like "check:0.5|bet:0.5" for a
+/// node whose actions are `labels`.
+fn parse_lock_strategy(spec: &str, labels: &[String]) -> Result<Vec<f64>, String> {
+    let key = |s: &str| s.to_lowercase().replace(' ', "");
+    let mut freqs = vec![0.0; labels.len()];
+    for entry in spec.split('|') {
+        let (action, freq) = entry
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Invalid lock strategy '{}': expected action:frequency", entry))?;
+        let freq = freq
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|f| (0.0..=1.0).contains(f))
+            .ok_or_else(|| format!("Invalid lock frequency '{}'", freq.trim()))?;
+        let action = key(action);
+        let matches: Vec<usize> = match labels.iter().position(|l| key(l) == action) {
+            Some(a) => vec![a],
+            None => (0..labels.len()).filter(|&a| key(&labels[a]).starts_with(&action)).collect(),
+        };
+        let [a] = matches[..] else {
+            let problem = if matches.is_empty() { "isn't available" } else { "is ambiguous" };
+            return Err(format!("Action '{}' {} here. Actions: {}", action, problem, labels.join(" | ")));
+        };
+        freqs[a] += freq;
+    }
+    let total: f64 = freqs.iter().sum();
+    if (total - 1.0).abs() > 1e-3 {
+        return Err(format!("Lock frequencies add up to {}, not 1", total));
+    }
+    Ok(freqs.iter().map(|f| f / total).collect())
+}
+
+/// Locked strategies by node: one entry per combo of the node's player,
+/// `None` for combos solved as usual.
+type Locks = HashMap<u16, Vec<Option<Vec<f64>>>>;
+
+/// `config.locked_nodes` resolved against the tree and combos. Locks that
+/// don't fit the tree (checked by `add_lock`) are ignored.
+fn resolve_locks(config: &RiverSolverConfig, tree: &TreeNode, showdown: &ShowdownTable) -> Locks {
+    let mut locks = Locks::new();
+    for lock in &config.locked_nodes {
+        let Some(TreeNode::Action { player, actions, .. }) = tree.find_action(lock.node_id) else {
+            continue;
+        };
+        if *player != lock.player {
+            continue;
+        }
+        let fits = |s: &Vec<f64>| s.len() == actions.len();
+        let overrides: Vec<(String, &Vec<f64>)> = lock
+            .combo_strategies
+            .iter()
+            .filter(|(_, s)| fits(s))
+            .filter_map(|(c, s)| Some((canonical_combo_string(c)?, s)))
+            .collect();
+        let combos = match player {
+            Player::OOP => &showdown.oop_combos,
+            Player::IP => &showdown.ip_combos,
+        };
+        let per_combo = combos
+            .iter()
+            .map(|combo| {
+                let name = combo.to_string();
+                overrides
+                    .iter()
+                    .find(|(c, _)| *c == name)
+                    .map(|(_, s)| *s)
+                    .or(Some(&lock.strategy).filter(|s| fits(s)))
+                    .cloned()
+            })
+            .collect();
+        locks.insert(lock.node_id, per_combo);
+    }
+    locks
+}
+
+/// The locked strategy of combo `h` at `node_id`, if any.
+fn locked_strategy(locks: &Locks, node_id: u16, h: usize) -> Option<&Vec<f64>> {
+    locks.get(&node_id).and_then(|per_combo| per_combo[h].as_ref())
 }
 
 /// Per-node strategy: action frequencies for each combo.
@@ -198,10 +797,13 @@ pub struct NodeStrategy {
     pub player: String,
     pub actions: Vec<String>,
     pub frequencies: Vec<Vec<f64>>, // [combo_idx][action_idx]
+    /// Set when some or all combos' frequencies here were locked, not solved.
+    #[serde(default)]
+    pub locked: bool,
 }
 
 /// Full solution from the river solver.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiverSolution {
     pub board: String,
     pub oop_range: Vec<String>,
@@ -219,6 +821,82 @@ pub struct RiverSolution {
     /// IP position label (e.g. "BTN") — used in cache key.
     #[serde(default)]
     pub ip_pos: String,
+    /// Combo ordering used by `oop_combos`/`ip_combos` (see `COMBO_ORDER_VERSION`).
+    #[serde(default)]
+    pub combo_order_version: u32,
+    /// Set when `strategies` were post-processed; `exploitability` is then
+    /// that of the processed strategy.
+    #[serde(default)]
+    pub postprocess: Option<PostProcessInfo>,
+    /// Set on a copy whose strategies were rounded for display (see
+    /// `simplified`); such copies aren't cached.
+    #[serde(default)]
+    pub simplified: Option<SimplifyInfo>,
+    /// Solve quality grade (see `quality`), set when the solve finishes.
+    #[serde(default)]
+    pub quality: Option<SolveQuality>,
+    /// Per-combo showdown outcomes from the root, indexed like `oop_combos`.
+    #[serde(default)]
+    pub oop_showdown_outcomes: Vec<ShowdownOutcome>,
+    /// Per-combo showdown outcomes from the root, indexed like `ip_combos`.
+    #[serde(default)]
+    pub ip_showdown_outcomes: Vec<ShowdownOutcome>,
+    /// (iterations, exploitability) snapshots when solved with a
+    /// `progress_interval`, ending with the final value.
+    #[serde(default)]
+    pub convergence_history: Vec<(usize, f64)>,
+    /// Frequency of each hand in `oop_range`; empty for solves from before
+    /// weighted ranges, where every hand is in full.
+    #[serde(default)]
+    pub oop_weights: Vec<f64>,
+    /// Frequency of each hand in `ip_range`, like `oop_weights`.
+    #[serde(default)]
+    pub ip_weights: Vec<f64>,
+    /// Initial reach of each combo in `oop_combos` for solves given
+    /// per-combo weights (`RiverSolverConfig::oop_combo_weights`); empty
+    /// when the range's hand frequencies were used.
+    #[serde(default)]
+    pub oop_combo_weights: Vec<f64>,
+    /// Initial reach of each combo in `ip_combos`, like `oop_combo_weights`.
+    #[serde(default)]
+    pub ip_combo_weights: Vec<f64>,
+    /// Bet sizes the tree was built with, as fractions of the pot.
+    #[serde(default = "default_river_bet_sizes")]
+    pub bet_sizes: Vec<f64>,
+    /// Whether the tree offered an all-in alongside `bet_sizes`.
+    #[serde(default = "default_true")]
+    pub add_allin: bool,
+    /// Raise sizes the tree was built with, as fractions of the pot after
+    /// calling.
+    #[serde(default = "default_river_raise_sizes")]
+    pub raise_sizes: Vec<f64>,
+    /// Most raises in the betting round.
+    #[serde(default = "default_river_max_raises")]
+    pub max_raises: usize,
+    /// Stopped by the config's `time_budget` instead of an iteration count.
+    /// Such a solve meets the quality floor whatever its iterations, since
+    /// solving again on the same machine would stop in the same place.
+    #[serde(default)]
+    pub time_limited: bool,
+}
+
+/// River bet sizes when none are configured, as fractions of the pot.
+pub const DEFAULT_RIVER_BET_SIZES: [f64; 3] = [0.33, 0.67, 1.0];
+
+fn default_river_bet_sizes() -> Vec<f64> {
+    DEFAULT_RIVER_BET_SIZES.to_vec()
+}
+
+fn default_river_raise_sizes() -> Vec<f64> {
+    TreeConfig::default_river(0.0, 0.0).raise_sizes
+}
+
+fn default_river_max_raises() -> usize {
+    TreeConfig::default_river(0.0, 0.0).max_raises
+}
+
+fn default_true() -> bool {
+    true
 }
 
 // ---------------------------------------------------------------------------
@@ -226,34 +904,75 @@ pub struct RiverSolution {
 // ---------------------------------------------------------------------------
 
 /// Solve a river spot.
+///
+/// Panics when the tree is over the config's node cap or the per-combo
+/// weights don't fit the ranges; `try_solve_river` returns those as errors.
 pub fn solve_river(config: &RiverSolverConfig) -> RiverSolution {
-    let tree_config = TreeConfig {
-        bet_sizes: config.bet_sizes.clone(),
-        raise_sizes: config.raise_sizes.clone(),
-        max_raises: config.max_raises,
-        starting_pot: config.starting_pot,
-        effective_stack: config.effective_stack,
-        add_allin: true,
-    };
+    solve_river_timed(config).0
+}
 
-    let (tree, _num_nodes) = build_tree(&tree_config);
+/// `solve_river`, also returning where the time went.
+pub fn solve_river_timed(config: &RiverSolverConfig) -> (RiverSolution, SolveStats) {
+    try_solve_river_timed(config).unwrap_or_else(|e| panic!("{}", e))
+}
 
-    let oop_combos = expand_range_to_combos(&config.oop_range, &config.board);
-    let ip_combos = expand_range_to_combos(&config.ip_range, &config.board);
+/// `solve_river`, or an error when the config fails `validate_tree_size`
+/// or `validate_combo_weights`.
+pub fn try_solve_river(config: &RiverSolverConfig) -> SolverResult<RiverSolution> {
+    Ok(try_solve_river_timed(config)?.0)
+}
+
+/// `try_solve_river`, also returning where the time went.
+pub fn try_solve_river_timed(config: &RiverSolverConfig) -> SolverResult<(RiverSolution, SolveStats)> {
+    config.validate_tree_size()?;
+    config.validate_combo_weights()?;
+    Ok(solve_river_unchecked(config))
+}
+
+fn solve_river_unchecked(config: &RiverSolverConfig) -> (RiverSolution, SolveStats) {
+    let mut timer = PhaseTimer::start();
+    let mut stats = SolveStats::default();
+    let (tree, _num_nodes) = build_tree(&config.tree_config());
+
+    let (oop_combos, oop_weights) = config.initial_reach(Player::OOP);
+    let (ip_combos, ip_weights) = config.initial_reach(Player::IP);
 
     if oop_combos.is_empty() || ip_combos.is_empty() {
-        return empty_solution(config);
+        stats.setup_ms = timer.lap();
+        return (empty_solution(config), stats);
+    }
+    stats.setup_ms = timer.lap();
+
+    let showdown = ShowdownTable::new(oop_combos, ip_combos, &config.board).with_weights(oop_weights, ip_weights);
+    let mut trainer = CfrTrainer::with_variant(config.cfr_variant);
+
+    // Locked info sets never update, so their average strategy stays as locked
+    let locks = resolve_locks(config, &tree, &showdown);
+    for (&node_id, per_combo) in &locks {
+        for (h, strategy) in per_combo.iter().enumerate() {
+            if let Some(strategy) = strategy {
+                trainer.set_average_strategy(&InfoSetKey { hand_bucket: h as u16, node_id }, strategy);
+            }
+        }
     }
 
-    let showdown = ShowdownTable::new(oop_combos, ip_combos, &config.board);
-    let mut trainer = CfrTrainer::new();
+    stats.precompute_ms = timer.lap();
 
     // Run alternating CFR+ iterations
+    let mut progress = ProgressTracker::new(config.progress_interval, config.on_progress, config.iterations)
+        .with_target(config.target_exploitability, config.starting_pot)
+        .with_time_budget(config.time_budget);
     for iter in 0..config.iterations {
+        if progress.out_of_time(iter) {
+            break;
+        }
+        if progress.due(iter) && progress.measure(iter, || locked_exploitability(&tree, &trainer, &showdown, &locks)) {
+            break;
+        }
         let traverser = if iter % 2 == 0 { Player::OOP } else { Player::IP };
 
         // Snapshot opponent strategies
-        let opp_snapshot = snapshot_strategies(&trainer, &tree, traverser.opponent(), &showdown);
+        let opp_snapshot = snapshot_strategies(&trainer, &tree, traverser.opponent(), &showdown, &locks);
 
         let num_combos = match traverser {
             Player::OOP => showdown.num_oop(),
@@ -261,25 +980,7 @@ pub fn solve_river(config: &RiverSolverConfig) -> RiverSolution {
         };
 
         for h in 0..num_combos {
-            // Initialize opp_reach: 1.0 for non-conflicting, 0.0 for blocked
-            let opp_reach = match traverser {
-                Player::OOP => {
-                    let valid = &showdown.valid_ip_for_oop[h];
-                    let mut reach = vec![0.0f64; showdown.num_ip()];
-                    for &j in valid {
-                        reach[j as usize] = 1.0;
-                    }
-                    reach
-                }
-                Player::IP => {
-                    let valid = &showdown.valid_oop_for_ip[h];
-                    let mut reach = vec![0.0f64; showdown.num_oop()];
-                    for &i in valid {
-                        reach[i as usize] = 1.0;
-                    }
-                    reach
-                }
-            };
+            let opp_reach = showdown.root_opp_reach(traverser, h);
 
             cfr_traverse(
                 &tree,
@@ -288,13 +989,72 @@ pub fn solve_river(config: &RiverSolverConfig) -> RiverSolution {
                 &opp_reach,
                 &showdown,
                 &opp_snapshot,
+                &locks,
                 &mut trainer,
+                iter,
             );
         }
     }
 
+    progress.end_iterations();
+    stats.iterate_ms = timer.lap();
+
     // Extract solution
-    extract_solution(config, &tree, &trainer, &showdown)
+    let Some(pp) = config.postprocess.filter(|p| p.is_active()) else {
+        let mut solution = extract_solution(config, progress.iterations_run(), &tree, &trainer, &showdown, &locks);
+        solution.convergence_history = progress.finish(solution.exploitability);
+        stats.extract_ms = timer.lap();
+        return (solution, stats);
+    };
+    let raw_exploitability = locked_exploitability(&tree, &trainer, &showdown, &locks);
+    postprocess_strategies(&pp, &tree, &mut trainer, &showdown, &config.board, &locks);
+    let mut solution = extract_solution(config, progress.iterations_run(), &tree, &trainer, &showdown, &locks);
+    solution.postprocess = Some(pp.applied(raw_exploitability));
+    solution.convergence_history = progress.finish(raw_exploitability);
+    stats.extract_ms = timer.lap();
+    (solution, stats)
+}
+
+/// Apply `pp` to every action node's average strategy in place.
+fn postprocess_strategies(
+    pp: &PostProcess,
+    tree: &TreeNode,
+    trainer: &mut CfrTrainer,
+    showdown: &ShowdownTable,
+    board: &[u8],
+    locks: &Locks,
+) {
+    let classes = |combos: &[Combo]| combos.iter().map(hand_class).collect::<Vec<u16>>();
+    let oop = (
+        classes(&showdown.oop_combos),
+        combo_equities(&showdown.oop_combos, &showdown.ip_combos, board, 1),
+    );
+    let ip = (
+        classes(&showdown.ip_combos),
+        combo_equities(&showdown.ip_combos, &showdown.oop_combos, board, 1),
+    );
+
+    let mut stack = vec![tree];
+    while let Some(node) = stack.pop() {
+        let TreeNode::Action { node_id, player, children, actions, .. } = node else {
+            continue;
+        };
+        let (classes, equities) = match player {
+            Player::OOP => &oop,
+            Player::IP => &ip,
+        };
+        let key = |h: usize| InfoSetKey { hand_bucket: h as u16, node_id: *node_id };
+        let mut freqs: Vec<Vec<f64>> = (0..classes.len())
+            .map(|h| trainer.get_average_strategy(&key(h), actions.len()))
+            .collect();
+        pp.apply(&mut freqs, classes, equities);
+        for (h, f) in freqs.iter().enumerate() {
+            if locked_strategy(locks, *node_id, h).is_none() {
+                trainer.set_average_strategy(&key(h), f);
+            }
+        }
+        stack.extend(children.iter());
+    }
 }
 
 /// Snapshot all opponent strategies for the given player to avoid borrow conflicts.
@@ -303,13 +1063,14 @@ fn snapshot_strategies(
     tree: &TreeNode,
     player: Player,
     showdown: &ShowdownTable,
+    locks: &Locks,
 ) -> HashMap<u16, Vec<Vec<f64>>> {
     let mut snapshot = HashMap::new();
     let num_combos = match player {
         Player::OOP => showdown.num_oop(),
         Player::IP => showdown.num_ip(),
     };
-    collect_strategies(tree, player, num_combos, trainer, &mut snapshot);
+    collect_strategies(tree, player, num_combos, trainer, locks, &mut snapshot);
     snapshot
 }
 
@@ -318,6 +1079,7 @@ fn collect_strategies(
     player: Player,
     num_combos: usize,
     trainer: &CfrTrainer,
+    locks: &Locks,
     snapshot: &mut HashMap<u16, Vec<Vec<f64>>>,
 ) {
     match node {
@@ -332,6 +1094,9 @@ fn collect_strategies(
                 let num_actions = actions.len();
                 let strats: Vec<Vec<f64>> = (0..num_combos)
                     .map(|h| {
+                        if let Some(strategy) = locked_strategy(locks, *node_id, h) {
+                            return strategy.clone();
+                        }
                         let key = InfoSetKey {
                             hand_bucket: h as u16,
                             node_id: *node_id,
@@ -342,7 +1107,7 @@ fn collect_strategies(
                 snapshot.insert(*node_id, strats);
             }
             for child in children {
-                collect_strategies(child, player, num_combos, trainer, snapshot);
+                collect_strategies(child, player, num_combos, trainer, locks, snapshot);
             }
         }
         TreeNode::Terminal { .. } | TreeNode::Chance { .. } => {}
@@ -358,7 +1123,9 @@ fn cfr_traverse(
     opp_reach: &[f64],
     showdown: &ShowdownTable,
     opp_snapshot: &HashMap<u16, Vec<Vec<f64>>>,
+    locks: &Locks,
     trainer: &mut CfrTrainer,
+    iter: usize,
 ) -> f64 {
     match node {
         TreeNode::Terminal {
@@ -383,7 +1150,11 @@ fn cfr_traverse(
                     hand_bucket: hand_idx as u16,
                     node_id: *node_id,
                 };
-                let strategy = trainer.get_strategy(&key, num_actions);
+                let locked = locked_strategy(locks, *node_id, hand_idx);
+                let strategy = match locked {
+                    Some(strategy) => strategy.clone(),
+                    None => trainer.get_strategy(&key, num_actions),
+                };
 
                 let mut action_values = vec![0.0f64; num_actions];
                 let mut node_value = 0.0;
@@ -391,17 +1162,21 @@ fn cfr_traverse(
                 for a in 0..num_actions {
                     action_values[a] = cfr_traverse(
                         &children[a], traverser, hand_idx, opp_reach,
-                        showdown, opp_snapshot, trainer,
+                        showdown, opp_snapshot, locks, trainer, iter,
                     );
                     node_value += strategy[a] * action_values[a];
                 }
 
+                // A locked strategy is played as given: no regrets to update
+                if locked.is_some() {
+                    return node_value;
+                }
+
                 // Compute reach probability (sum of opponent reach)
                 let reach_sum: f64 = opp_reach.iter().sum();
                 let reach_prob = if reach_sum > 0.0 { 1.0 } else { 0.0 };
 
-                let data = trainer.get_or_create(&key, num_actions);
-                data.update(&action_values, node_value, reach_prob);
+                trainer.update(&key, &action_values, node_value, reach_prob, iter);
 
                 node_value
             } else {
@@ -426,7 +1201,7 @@ fn cfr_traverse(
 
                     node_value += cfr_traverse(
                         &children[a], traverser, hand_idx, &new_opp_reach,
-                        showdown, opp_snapshot, trainer,
+                        showdown, opp_snapshot, locks, trainer, iter,
                     );
                 }
 
@@ -469,51 +1244,9 @@ fn compute_terminal_value(
             }
         }
         TerminalType::Showdown => {
-            let win_payoff = pot - my_invested;
-            let lose_payoff = -my_invested;
-            let tie_payoff = pot / 2.0 - my_invested;
-            let mut value = 0.0;
-
-            match traverser {
-                Player::OOP => {
-                    let my_score = showdown.oop_scores[hand_idx];
-                    for &j in &showdown.valid_ip_for_oop[hand_idx] {
-                        let j = j as usize;
-                        if opp_reach[j] < 1e-10 {
-                            continue;
-                        }
-                        let opp_score = showdown.ip_scores[j];
-                        let payoff = if my_score > opp_score {
-                            win_payoff
-                        } else if my_score < opp_score {
-                            lose_payoff
-                        } else {
-                            tie_payoff
-                        };
-                        value += opp_reach[j] * payoff;
-                    }
-                }
-                Player::IP => {
-                    let my_score = showdown.ip_scores[hand_idx];
-                    for &i in &showdown.valid_oop_for_ip[hand_idx] {
-                        let i = i as usize;
-                        if opp_reach[i] < 1e-10 {
-                            continue;
-                        }
-                        let opp_score = showdown.oop_scores[i];
-                        let payoff = if my_score > opp_score {
-                            win_payoff
-                        } else if my_score < opp_score {
-                            lose_payoff
-                        } else {
-                            tie_payoff
-                        };
-                        value += opp_reach[i] * payoff;
-                    }
-                }
-            }
-
-            value
+            let (my_score, opp_scores, valid) = showdown.matchups(traverser, hand_idx);
+            let [win, tie, lose] = showdown_outcome_mass(my_score, opp_scores, valid, opp_reach);
+            win * (pot - my_invested) + tie * (pot / 2.0 - my_invested) - lose * my_invested
         }
     }
 }
@@ -523,13 +1256,20 @@ fn compute_terminal_value(
 // ---------------------------------------------------------------------------
 
 /// Compute exploitability via best-response traversal.
-pub fn compute_exploitability(
+pub fn compute_exploitability(tree: &TreeNode, trainer: &CfrTrainer, showdown: &ShowdownTable) -> f64 {
+    locked_exploitability(tree, trainer, showdown, &Locks::new())
+}
+
+/// [`compute_exploitability`] where a best response can't deviate from a
+/// locked strategy.
+fn locked_exploitability(
     tree: &TreeNode,
     trainer: &CfrTrainer,
     showdown: &ShowdownTable,
+    locks: &Locks,
 ) -> f64 {
-    let oop_gain = best_response_value(tree, Player::OOP, trainer, showdown);
-    let ip_gain = best_response_value(tree, Player::IP, trainer, showdown);
+    let oop_gain = best_response_value(tree, Player::OOP, trainer, showdown, locks);
+    let ip_gain = best_response_value(tree, Player::IP, trainer, showdown, locks);
     (oop_gain + ip_gain) / 2.0
 }
 
@@ -540,48 +1280,31 @@ fn best_response_value(
     br_player: Player,
     trainer: &CfrTrainer,
     showdown: &ShowdownTable,
+    locks: &Locks,
 ) -> f64 {
     let num_br = match br_player {
         Player::OOP => showdown.num_oop(),
         Player::IP => showdown.num_ip(),
     };
-    let num_opp = match br_player {
-        Player::OOP => showdown.num_ip(),
-        Player::IP => showdown.num_oop(),
-    };
 
+    // Each combo's gain counts at its range frequency
     let mut total_gain = 0.0;
+    let mut total_weight = 0.0;
 
     for h in 0..num_br {
-        // Initialize opp reach
-        let opp_reach = match br_player {
-            Player::OOP => {
-                let valid = &showdown.valid_ip_for_oop[h];
-                let mut reach = vec![0.0f64; num_opp];
-                for &j in valid {
-                    reach[j as usize] = 1.0;
-                }
-                reach
-            }
-            Player::IP => {
-                let valid = &showdown.valid_oop_for_ip[h];
-                let mut reach = vec![0.0f64; num_opp];
-                for &i in valid {
-                    reach[i as usize] = 1.0;
-                }
-                reach
-            }
-        };
+        let opp_reach = showdown.root_opp_reach(br_player, h);
 
-        let br_value = br_traverse(tree, br_player, h, &opp_reach, showdown, trainer);
+        let br_value = br_traverse(tree, br_player, h, &opp_reach, showdown, trainer, locks);
 
         // Also compute the value using the actual average strategy
         let avg_value = avg_strategy_traverse(tree, br_player, h, &opp_reach, showdown, trainer);
 
-        total_gain += br_value - avg_value;
+        let weight = showdown.weight(br_player, h);
+        total_gain += weight * (br_value - avg_value);
+        total_weight += weight;
     }
 
-    total_gain / num_br as f64
+    if total_weight > 0.0 { total_gain / total_weight } else { 0.0 }
 }
 
 /// Best-response traversal: for the BR player, pick the best action at each node.
@@ -592,6 +1315,7 @@ fn br_traverse(
     opp_reach: &[f64],
     showdown: &ShowdownTable,
     trainer: &CfrTrainer,
+    locks: &Locks,
 ) -> f64 {
     match node {
         TreeNode::Terminal { terminal_type, pot, invested, .. } => {
@@ -603,17 +1327,15 @@ fn br_traverse(
             let num_actions = actions.len();
 
             if *player == br_player {
-                // Best response: pick the max-value action
-                let mut best = f64::NEG_INFINITY;
-                for a in 0..num_actions {
-                    let v = br_traverse(
-                        &children[a], br_player, hand_idx, opp_reach, showdown, trainer,
-                    );
-                    if v > best {
-                        best = v;
-                    }
+                let values = (0..num_actions).map(|a| {
+                    br_traverse(&children[a], br_player, hand_idx, opp_reach, showdown, trainer, locks)
+                });
+                match locked_strategy(locks, *node_id, hand_idx) {
+                    // Locked: play the locked strategy
+                    Some(strategy) => values.zip(strategy).map(|(v, p)| p * v).sum(),
+                    // Best response: pick the max-value action
+                    None => values.fold(f64::NEG_INFINITY, f64::max),
                 }
-                best
             } else {
                 // Opponent plays average strategy
                 let num_opp = opp_reach.len();
@@ -632,7 +1354,7 @@ fn br_traverse(
                         }
                     }
                     node_value += br_traverse(
-                        &children[a], br_player, hand_idx, &new_opp_reach, showdown, trainer,
+                        &children[a], br_player, hand_idx, &new_opp_reach, showdown, trainer, locks,
                     );
                 }
                 node_value
@@ -704,20 +1426,145 @@ fn avg_strategy_traverse(
     }
 }
 
+/// The action node `node_id`, with the opponent reach of `player`'s combo
+/// there: `opp_reach` at `node` times the opponent's average strategy at each
+/// of its decisions on the way.
+fn reach_node<'t>(
+    node: &'t TreeNode,
+    node_id: u16,
+    player: Player,
+    opp_reach: Vec<f64>,
+    trainer: &CfrTrainer,
+) -> Option<(&'t TreeNode, Vec<f64>)> {
+    let TreeNode::Action { node_id: id, player: to_act, children, actions, .. } = node else {
+        return None;
+    };
+    if *id == node_id {
+        return Some((node, opp_reach));
+    }
+    children.iter().enumerate().find_map(|(a, child)| {
+        let reach = if *to_act == player {
+            opp_reach.clone()
+        } else {
+            opp_reach
+                .iter()
+                .enumerate()
+                .map(|(j, &r)| {
+                    if r > 0.0 {
+                        let key = InfoSetKey { hand_bucket: j as u16, node_id: *id };
+                        r * trainer.get_average_strategy(&key, actions.len())[a]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+        reach_node(child, node_id, player, reach, trainer)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Showdown outcomes
+// ---------------------------------------------------------------------------
+
+/// Win/tie/lose shares at showdown for every combo of `player`, with both
+/// players on their average strategies.
+pub(crate) fn compute_showdown_outcomes(
+    tree: &TreeNode,
+    player: Player,
+    trainer: &CfrTrainer,
+    showdown: &ShowdownTable,
+) -> Vec<ShowdownOutcome> {
+    let num_combos = match player {
+        Player::OOP => showdown.num_oop(),
+        Player::IP => showdown.num_ip(),
+    };
+    (0..num_combos)
+        .map(|h| {
+            let opp_reach = showdown.root_opp_reach(player, h);
+            outcome_shares(outcome_traverse(tree, player, h, &opp_reach, showdown, trainer))
+        })
+        .collect()
+}
+
+/// `avg_strategy_traverse` with the showdown outcome mass in place of chips.
+fn outcome_traverse(
+    node: &TreeNode,
+    perspective: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    showdown: &ShowdownTable,
+    trainer: &CfrTrainer,
+) -> [f64; 3] {
+    match node {
+        TreeNode::Terminal { terminal_type: TerminalType::Showdown, .. } => {
+            let (my_score, opp_scores, valid) = showdown.matchups(perspective, hand_idx);
+            showdown_outcome_mass(my_score, opp_scores, valid, opp_reach)
+        }
+        TreeNode::Terminal { .. } => [0.0; 3],
+        TreeNode::Action { node_id, player, children, actions, .. } => {
+            let num_actions = actions.len();
+            let mut mass = [0.0f64; 3];
+
+            if *player == perspective {
+                let key = InfoSetKey {
+                    hand_bucket: hand_idx as u16,
+                    node_id: *node_id,
+                };
+                let avg = trainer.get_average_strategy(&key, num_actions);
+                for a in 0..num_actions {
+                    if avg[a] <= 0.0 {
+                        continue;
+                    }
+                    let child = outcome_traverse(&children[a], perspective, hand_idx, opp_reach, showdown, trainer);
+                    for k in 0..3 {
+                        mass[k] += avg[a] * child[k];
+                    }
+                }
+            } else {
+                let num_opp = opp_reach.len();
+                for a in 0..num_actions {
+                    let mut new_opp_reach = vec![0.0f64; num_opp];
+                    for j in 0..num_opp {
+                        if opp_reach[j] > 0.0 {
+                            let key = InfoSetKey {
+                                hand_bucket: j as u16,
+                                node_id: *node_id,
+                            };
+                            let avg = trainer.get_average_strategy(&key, num_actions);
+                            new_opp_reach[j] = opp_reach[j] * avg[a];
+                        }
+                    }
+                    let child = outcome_traverse(&children[a], perspective, hand_idx, &new_opp_reach, showdown, trainer);
+                    for k in 0..3 {
+                        mass[k] += child[k];
+                    }
+                }
+            }
+            mass
+        }
+        TreeNode::Chance { .. } => unreachable!("River solver does not use chance nodes"),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Strategy extraction
 // ---------------------------------------------------------------------------
 
 fn extract_solution(
     config: &RiverSolverConfig,
+    iterations: usize,
     tree: &TreeNode,
     trainer: &CfrTrainer,
     showdown: &ShowdownTable,
+    locks: &Locks,
 ) -> RiverSolution {
-    let exploitability = compute_exploitability(tree, trainer, showdown);
+    let exploitability = locked_exploitability(tree, trainer, showdown, locks);
 
     let mut strategies = Vec::new();
-    extract_node_strategies(tree, trainer, showdown, &mut strategies);
+    extract_node_strategies(tree, trainer, showdown, locks, &mut strategies);
+    let oop_showdown_outcomes = compute_showdown_outcomes(tree, Player::OOP, trainer, showdown);
+    let ip_showdown_outcomes = compute_showdown_outcomes(tree, Player::IP, trainer, showdown);
 
     let board_str = config
         .board
@@ -728,25 +1575,8 @@ fn extract_solution(
         })
         .collect::<String>();
 
-    let oop_combo_strs: Vec<String> = showdown
-        .oop_combos
-        .iter()
-        .map(|c| {
-            let c1 = crate::card_encoding::index_to_card(c.0);
-            let c2 = crate::card_encoding::index_to_card(c.1);
-            format!("{}{}", c1, c2)
-        })
-        .collect();
-
-    let ip_combo_strs: Vec<String> = showdown
-        .ip_combos
-        .iter()
-        .map(|c| {
-            let c1 = crate::card_encoding::index_to_card(c.0);
-            let c2 = crate::card_encoding::index_to_card(c.1);
-            format!("{}{}", c1, c2)
-        })
-        .collect();
+    let oop_combo_strs: Vec<String> = showdown.oop_combos.iter().map(|c| c.to_string()).collect();
+    let ip_combo_strs: Vec<String> = showdown.ip_combos.iter().map(|c| c.to_string()).collect();
 
     RiverSolution {
         board: board_str,
@@ -754,13 +1584,36 @@ fn extract_solution(
         ip_range: config.ip_range.clone(),
         starting_pot: config.starting_pot,
         effective_stack: config.effective_stack,
-        iterations: config.iterations,
+        iterations,
         exploitability,
         oop_combos: oop_combo_strs,
         ip_combos: ip_combo_strs,
         strategies,
         oop_pos: String::new(),
         ip_pos: String::new(),
+        combo_order_version: COMBO_ORDER_VERSION,
+        postprocess: None,
+        simplified: None,
+        quality: Some(SolveQuality::grade(
+            exploitability,
+            config.starting_pot,
+            iterations,
+            RECOMMENDED_RIVER_ITERATIONS,
+            None,
+            showdown.oop_combos.len().max(showdown.ip_combos.len()),
+        )),
+        oop_showdown_outcomes,
+        ip_showdown_outcomes,
+        convergence_history: vec![],
+        oop_weights: config.oop_weights.clone(),
+        ip_weights: config.ip_weights.clone(),
+        oop_combo_weights: config.oop_combo_weights.clone().unwrap_or_default(),
+        ip_combo_weights: config.ip_combo_weights.clone().unwrap_or_default(),
+        bet_sizes: config.bet_sizes.clone(),
+        add_allin: config.add_allin,
+        raise_sizes: config.raise_sizes.clone(),
+        max_raises: config.max_raises,
+        time_limited: config.time_budget.is_some(),
     }
 }
 
@@ -768,6 +1621,7 @@ fn extract_node_strategies(
     node: &TreeNode,
     trainer: &CfrTrainer,
     showdown: &ShowdownTable,
+    locks: &Locks,
     strategies: &mut Vec<NodeStrategy>,
 ) {
     match node {
@@ -804,10 +1658,11 @@ fn extract_node_strategies(
                 },
                 actions: action_labels,
                 frequencies,
+                locked: locks.get(node_id).is_some_and(|per_combo| per_combo.iter().any(Option::is_some)),
             });
 
             for child in children {
-                extract_node_strategies(child, trainer, showdown, strategies);
+                extract_node_strategies(child, trainer, showdown, locks, strategies);
             }
         }
         TreeNode::Terminal { .. } | TreeNode::Chance { .. } => {}
@@ -837,6 +1692,22 @@ fn empty_solution(config: &RiverSolverConfig) -> RiverSolution {
         strategies: vec![],
         oop_pos: String::new(),
         ip_pos: String::new(),
+        combo_order_version: COMBO_ORDER_VERSION,
+        postprocess: None,
+        simplified: None,
+        quality: None,
+        oop_showdown_outcomes: vec![],
+        ip_showdown_outcomes: vec![],
+        convergence_history: vec![],
+        oop_weights: config.oop_weights.clone(),
+        ip_weights: config.ip_weights.clone(),
+        oop_combo_weights: config.oop_combo_weights.clone().unwrap_or_default(),
+        ip_combo_weights: config.ip_combo_weights.clone().unwrap_or_default(),
+        bet_sizes: config.bet_sizes.clone(),
+        add_allin: config.add_allin,
+        raise_sizes: config.raise_sizes.clone(),
+        max_raises: config.max_raises,
+        time_limited: false,
     }
 }
 
@@ -846,6 +1717,65 @@ fn empty_solution(config: &RiverSolverConfig) -> RiverSolution {
 
 impl RiverSolution {
     pub fn display(&self) {
+        self.display_with(false);
+    }
+
+    /// Initial reach of each combo in `oop_combos` and `ip_combos`: the
+    /// per-combo weights the solve was given, else the hand frequencies.
+    fn reach_weights(&self) -> (Vec<f64>, Vec<f64>) {
+        let side = |range: &[String], weights: &[f64], per_combo: &[f64], combos: &[String]| {
+            if per_combo.len() == combos.len() && !combos.is_empty() {
+                per_combo.to_vec()
+            } else {
+                combo_weights(range, weights, &self.board, combos)
+            }
+        };
+        (
+            side(&self.oop_range, &self.oop_weights, &self.oop_combo_weights, &self.oop_combos),
+            side(&self.ip_range, &self.ip_weights, &self.ip_combo_weights, &self.ip_combos),
+        )
+    }
+
+    /// How each range splits at the root and answers each root action
+    /// (see `range_summary`).
+    pub fn summary(&self) -> RangeSummary {
+        let (tree, _) = build_tree(&self.tree_config());
+        let (oop_weights, ip_weights) = self.reach_weights();
+        summarize(
+            &tree,
+            |id| {
+                self.strategies
+                    .iter()
+                    .find(|s| s.node_id == id)
+                    .map(|s| (s.actions.as_slice(), s.frequencies.as_slice()))
+            },
+            WeightedCombos { combos: &self.oop_combos, weights: &oop_weights },
+            WeightedCombos { combos: &self.ip_combos, weights: &ip_weights },
+        )
+    }
+
+    /// Hand-class composition of each action the summary covers (see
+    /// `breakdown`).
+    pub fn breakdown(&self) -> Breakdown {
+        let (tree, _) = build_tree(&self.tree_config());
+        let (oop_weights, ip_weights) = self.reach_weights();
+        breakdown(
+            &tree,
+            |id| {
+                self.strategies
+                    .iter()
+                    .find(|s| s.node_id == id)
+                    .map(|s| (s.actions.as_slice(), s.frequencies.as_slice()))
+            },
+            WeightedCombos { combos: &self.oop_combos, weights: &oop_weights },
+            WeightedCombos { combos: &self.ip_combos, weights: &ip_weights },
+            &self.board,
+        )
+    }
+
+    /// The summary; `expanded` lists the root combos one by one instead of
+    /// grouping those that play alike (see `display::group_combos`).
+    pub fn display_with(&self, expanded: bool) {
         use colored::Colorize;
 
         println!();
@@ -861,52 +1791,55 @@ impl RiverSolution {
             "  Exploitability: {:.4}",
             self.exploitability,
         );
+        if let Some(pp) = &self.postprocess {
+            println!("  {}", pp.describe(self.exploitability).dimmed());
+        }
+        if let Some(simplified) = &self.simplified {
+            println!("  {}", format!("Strategies {}", simplified.describe()).yellow());
+        }
+        let locked: Vec<String> = self
+            .strategies
+            .iter()
+            .filter(|s| s.locked)
+            .map(|s| format!("{} ({})", s.node_id, s.player))
+            .collect();
+        if !locked.is_empty() {
+            println!(
+                "  {}",
+                format!("Locked nodes: {}; frequencies there are fixed, not solved", locked.join(", ")).yellow()
+            );
+        }
         println!(
             "  OOP range: {} ({} combos)  |  IP range: {} ({} combos)",
-            self.oop_range.join(","),
+            format_weighted_range(&self.oop_range, &self.oop_weights),
             self.oop_combos.len(),
-            self.ip_range.join(","),
+            format_weighted_range(&self.ip_range, &self.ip_weights),
             self.ip_combos.len(),
         );
 
+        let summary = self.summary();
+        if !summary.decisions.is_empty() {
+            println!();
+            println!("{}", range_summary_table(&summary));
+        }
+
         // Display root node strategy (OOP's first decision)
         if let Some(root_strat) = self.strategies.first() {
             println!();
             println!(
-                "  {} at root (node {}):",
+                "  {} at root (node {}){}:",
                 root_strat.player.bold(),
-                root_strat.node_id
+                root_strat.node_id,
+                if root_strat.locked { " [locked]".yellow().to_string() } else { String::new() },
             );
             println!("  Actions: {}", root_strat.actions.join(" | "));
 
-            let num_to_show = root_strat.frequencies.len().min(20);
             let combos = if root_strat.player == "OOP" {
                 &self.oop_combos
             } else {
                 &self.ip_combos
             };
-
-            for i in 0..num_to_show {
-                let freq_str: String = root_strat.frequencies[i]
-                    .iter()
-                    .zip(&root_strat.actions)
-                    .map(|(f, a)| {
-                        let pct = (f * 100.0).round() as u32;
-                        if pct > 70 {
-                            format!("{}:{}", a, format!("{}%", pct).green())
-                        } else if pct > 30 {
-                            format!("{}:{}", a, format!("{}%", pct).yellow())
-                        } else {
-                            format!("{}:{}%", a, pct)
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join("  ");
-                println!("    {}  {}", combos[i].bold(), freq_str);
-            }
-            if root_strat.frequencies.len() > num_to_show {
-                println!("    ... and {} more combos", root_strat.frequencies.len() - num_to_show);
-            }
+            print_combo_strategies(combos, &root_strat.frequencies, &root_strat.actions, expanded);
         }
 
         println!();
@@ -917,31 +1850,242 @@ impl RiverSolution {
 // Cache
 // ---------------------------------------------------------------------------
 
+fn river_file_stem(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64) -> String {
+    format!("river_{}_{}_{}_{:.0}_{:.0}", board, oop_pos, ip_pos, pot, stack)
+}
+
 impl RiverSolution {
+    /// Cache file name for a spot and its ranges' `range_key`, relative to
+    /// the solver cache directory. Suit-isomorphic boards share a file (see
+    /// `canonical_board`).
+    pub fn cache_file_name(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64, ranges: &str) -> String {
+        let board = canonical_board(board).unwrap_or_else(|_| board.to_string());
+        format!("{}_{}.bin", river_file_stem(&board, oop_pos, ip_pos, pot, stack), ranges)
+    }
+
+    /// `range_key` of the solve's ranges.
+    pub fn range_key(&self) -> String {
+        range_key(&self.oop_range, &self.oop_weights, &self.ip_range, &self.ip_weights)
+    }
+
     pub fn cache_path(&self) -> std::path::PathBuf {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let dir = std::path::Path::new(&home).join(".gto-cli").join("solver");
+        let dir = solver_cache_dir();
         std::fs::create_dir_all(&dir).ok();
+        // Locked solves aren't equilibria, and `gto query` assumes the default
+        // sizes: keep both out of its way
+        let mut suffix = sizing_suffix(&self.bet_sizes, self.add_allin, &DEFAULT_RIVER_BET_SIZES, true);
+        suffix.push_str(&raise_suffix(
+            "raise",
+            &self.raise_sizes,
+            self.max_raises,
+            &default_river_raise_sizes(),
+            default_river_max_raises(),
+        ));
+        if self.strategies.iter().any(|s| s.locked) {
+            suffix.push_str("_locked");
+        }
+        suffix.push_str(&combo_weights_key(&self.oop_combo_weights, &self.ip_combo_weights));
+        let board = canonical_board(&self.board).unwrap_or_else(|_| self.board.clone());
         dir.join(format!(
-            "river_{}_{}_{}_{:.0}_{:.0}.bin",
-            self.board, self.oop_pos, self.ip_pos, self.starting_pot, self.effective_stack,
+            "{}_{}{}.bin",
+            river_file_stem(&board, &self.oop_pos, &self.ip_pos, self.starting_pot, self.effective_stack),
+            self.range_key(),
+            suffix
         ))
     }
 
+    /// The tree the solution was solved on.
+    pub fn tree_config(&self) -> TreeConfig {
+        TreeConfig {
+            bet_sizes: self.bet_sizes.clone(),
+            raise_sizes: self.raise_sizes.clone(),
+            max_raises: self.max_raises,
+            add_allin: self.add_allin,
+            ..TreeConfig::default_river(self.starting_pot, self.effective_stack)
+        }
+    }
+
+    /// A copy with every solved strategy rounded by `simplify`. Its EV loss
+    /// is how much more exploitable the copy is than this solution, each
+    /// measured by best response on the rebuilt tree. Locked nodes keep their
+    /// frequencies.
+    pub fn simplified(&self, simplify: Simplify) -> RiverSolution {
+        let mut simplified = self.clone();
+        for node in simplified.strategies.iter_mut().filter(|s| !s.locked) {
+            simplify.apply_all(&mut node.frequencies);
+        }
+        let loss = self
+            .profile_exploitability()
+            .zip(simplified.profile_exploitability())
+            .map(|(before, after)| pct_of_pot((after - before).max(0.0), self.starting_pot));
+        simplified.simplified = Some(simplify.info(loss));
+        simplified
+    }
+
+    /// Exploitability of the stored strategies by best response; `None` if a
+    /// combo or the board doesn't parse. Locks aren't stored, so here a best
+    /// response may leave a locked node's frequencies.
+    fn profile_exploitability(&self) -> Option<f64> {
+        let (tree, trainer, showdown) = self.profile()?;
+        Some(compute_exploitability(&tree, &trainer, &showdown))
+    }
+
+    /// EV of each action at `node_id` for each of `player`'s combos
+    /// `combo_idxs`, in starting pots. Both sides play the stored strategies
+    /// after the action, and the opponent's range is narrowed by its own
+    /// actions on the way to the node. `None` if the node isn't `player`'s or
+    /// no opponent combo reaches it with one of the combos.
+    pub fn action_evs(&self, node_id: u16, player: Player, combo_idxs: &[usize]) -> Option<Vec<Vec<f64>>> {
+        let (tree, trainer, showdown) = self.profile()?;
+        let num_combos = match player {
+            Player::OOP => showdown.num_oop(),
+            Player::IP => showdown.num_ip(),
+        };
+        combo_idxs
+            .iter()
+            .map(|&combo_idx| {
+                if combo_idx >= num_combos {
+                    return None;
+                }
+                let root_reach = showdown.root_opp_reach(player, combo_idx);
+                let (node, opp_reach) = reach_node(&tree, node_id, player, root_reach, &trainer)?;
+                let TreeNode::Action { player: to_act, children, .. } = node else {
+                    return None;
+                };
+                let total: f64 = opp_reach.iter().sum();
+                if *to_act != player || total < 1e-10 {
+                    return None;
+                }
+                Some(
+                    children
+                        .iter()
+                        .map(|child| {
+                            avg_strategy_traverse(child, player, combo_idx, &opp_reach, &showdown, &trainer)
+                                / total
+                                / self.starting_pot
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// The rebuilt tree, the stored strategies as average strategies and the
+    /// showdown table; `None` if a combo or the board doesn't parse.
+    fn profile(&self) -> Option<(TreeNode, CfrTrainer, ShowdownTable)> {
+        let board: Vec<u8> = parse_board(&self.board).ok()?.iter().map(card_to_index).collect();
+        let (oop_weights, ip_weights) = self.reach_weights();
+        let showdown =
+            ShowdownTable::new(parse_combo_strings(&self.oop_combos)?, parse_combo_strings(&self.ip_combos)?, &board)
+            .with_weights(oop_weights, ip_weights);
+        if showdown.num_oop() == 0 || showdown.num_ip() == 0 {
+            return None;
+        }
+
+        let mut trainer = CfrTrainer::new();
+        for node in &self.strategies {
+            for (h, freqs) in node.frequencies.iter().enumerate() {
+                trainer.set_average_strategy(&InfoSetKey { hand_bucket: h as u16, node_id: node.node_id }, freqs);
+            }
+        }
+        let (tree, _) = build_tree(&self.tree_config());
+        Some((tree, trainer, showdown))
+    }
+
     pub fn save_cache(&self) {
         if let Ok(data) = bincode::serialize(self) {
             let path = self.cache_path();
-            std::fs::write(path, data).ok();
+            crate::status::cache_written(&path, write_atomic(&path, &data));
         }
     }
 
-    pub fn load_cache(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64) -> Option<RiverSolution> {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let path = std::path::Path::new(&home)
-            .join(".gto-cli")
-            .join("solver")
-            .join(format!("river_{}_{}_{}_{:.0}_{:.0}.bin", board, oop_pos, ip_pos, pot, stack));
-        let data = std::fs::read(path).ok()?;
-        bincode::deserialize(&data).ok()
+    /// The cached solve for a spot with the ranges of `ranges` (a
+    /// `range_key`), or for a suit-isomorphic board, in the suits of `board`.
+    /// `None` takes the spot's latest solve whatever its ranges (see
+    /// `read_spot_cache`).
+    pub fn load_cache(
+        board: &str,
+        oop_pos: &str,
+        ip_pos: &str,
+        pot: f64,
+        stack: f64,
+        ranges: Option<&str>,
+    ) -> Option<RiverSolution> {
+        let canonical = canonical_board(board).unwrap_or_else(|_| board.to_string());
+        // Solves cached before file names were canonicalized are under their own board
+        let stems = [
+            river_file_stem(&canonical, oop_pos, ip_pos, pot, stack),
+            river_file_stem(board, oop_pos, ip_pos, pot, stack),
+        ];
+        let read = |path: &std::path::Path| {
+            let solution: RiverSolution = bincode::deserialize(&std::fs::read(path).ok()?).ok()?;
+            (solution.combo_order_version == COMBO_ORDER_VERSION).then_some(solution)
+        };
+        let mut solution = read_spot_cache(&stems, ranges, read, RiverSolution::range_key)?;
+        solution.relabel_to(board).ok()?;
+        Some(solution)
+    }
+
+    /// Express the solution in the suits of `board`, a suit-isomorphic board
+    /// (a Ks9d4c7hQc solve read for Kh9c4d7sQd): board, combos and every
+    /// per-combo array are relabeled.
+    pub fn relabel_to(&mut self, board: &str) -> Result<(), String> {
+        let map = suit_permutation(&self.board, board)?
+            .ok_or_else(|| format!("Board {} is not a suit relabeling of {}", board, self.board))?;
+        if map == [0, 1, 2, 3] {
+            return Ok(());
+        }
+        let (oop_combos, oop_order) = relabel_combos(&self.oop_combos, &map);
+        let (ip_combos, ip_order) = relabel_combos(&self.ip_combos, &map);
+        for strategy in &mut self.strategies {
+            let order = if strategy.player == "OOP" { &oop_order } else { &ip_order };
+            strategy.frequencies = reorder_rows(&strategy.frequencies, order);
+        }
+        self.oop_showdown_outcomes = reorder_rows(&self.oop_showdown_outcomes, &oop_order);
+        self.ip_showdown_outcomes = reorder_rows(&self.ip_showdown_outcomes, &ip_order);
+        self.oop_combo_weights = reorder_rows(&self.oop_combo_weights, &oop_order);
+        self.ip_combo_weights = reorder_rows(&self.ip_combo_weights, &ip_order);
+        self.board = relabel_suits(&self.board, &map);
+        self.oop_combos = oop_combos;
+        self.ip_combos = ip_combos;
+        Ok(())
+    }
+
+    /// False for solves run below the iteration floor (via `--force`); those
+    /// aren't served to `gto query`. Time-limited solves always meet it.
+    pub fn meets_quality_floor(&self) -> bool {
+        self.time_limited || crate::limits::RIVER_ITERATIONS.meets_floor(self.iterations)
+    }
+
+    /// The stored quality grade, or one computed from the headline numbers
+    /// for solutions built without it.
+    pub fn solve_quality(&self) -> SolveQuality {
+        self.quality.clone().unwrap_or_else(|| {
+            SolveQuality::grade(
+                self.exploitability,
+                self.starting_pot,
+                self.iterations,
+                RECOMMENDED_RIVER_ITERATIONS,
+                None,
+                self.oop_combos.len().max(self.ip_combos.len()),
+            )
+        })
+    }
+
+    /// Showdown outcome of `player`'s ("OOP"/"IP") combo, or `None` for
+    /// solutions saved without outcomes and combos that never reach showdown.
+    pub fn showdown_outcome(&self, player: &str, combo_idx: usize) -> Option<ShowdownOutcome> {
+        let outcomes = if player == "OOP" { &self.oop_showdown_outcomes } else { &self.ip_showdown_outcomes };
+        outcomes.get(combo_idx).copied().filter(|(w, t, l)| w + t + l > 0.0)
+    }
+
+    /// Index of a combo (either card order) in `oop_combos`.
+    pub fn oop_combo_index(&self, combo: &str) -> Option<usize> {
+        find_combo_index(combo, &self.oop_combos)
+    }
+
+    /// Index of a combo (either card order) in `ip_combos`.
+    pub fn ip_combo_index(&self, combo: &str) -> Option<usize> {
+        find_combo_index(combo, &self.ip_combos)
     }
 }
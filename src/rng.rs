@@ -0,0 +1,14 @@
+//! Seeded random streams shared by the solvers and sampled equity.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// RNG for one stream of a solve. With a seed every stream is reproducible on
+/// its own, so parallel work and resumed solves draw the same numbers as a
+/// single sequential run would.
+pub(crate) fn stream_rng(seed: Option<u64>, stream: u64) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        None => StdRng::from_rng(rand::thread_rng()).expect("thread RNG never fails"),
+    }
+}
@@ -0,0 +1,262 @@
+//! River buckets and hand scores per flop runout, for the flop solver.
+//!
+//! A flop leaves `n * (n - 1)` turn/river runouts (2,256 with 48 cards left)
+//! and MCCFR samples one per iteration. Building every runout up front costs
+//! `runout_bytes` each, which wide ranges push into gigabytes. Past the
+//! budget (`FlopSolverConfig::max_precompute_bytes`) runouts are built on
+//! first use instead and the last `LAZY_CACHE_RUNOUTS` kept: less memory, at
+//! the cost of rebuilding a runout each time it's sampled after eviction.
+//! Both modes give the same values, since river buckets don't sample.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rayon::prelude::*;
+
+use crate::bucketing::assign_buckets;
+use crate::lookup_eval::evaluate_many;
+
+/// Runouts the lazy mode keeps built.
+pub const LAZY_CACHE_RUNOUTS: usize = 512;
+
+/// Default budget for the full tables: 2 GiB.
+pub const DEFAULT_MAX_PRECOMPUTE_BYTES: u64 = 2 << 30;
+
+/// River buckets and hand scores of both ranges on one runout, indexed like
+/// the solver's combos.
+pub struct RunoutData {
+    pub oop_buckets: Vec<u16>,
+    pub ip_buckets: Vec<u16>,
+    pub oop_scores: Vec<u32>,
+    pub ip_scores: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecomputeMode {
+    /// Every runout built before the first iteration.
+    Full,
+    /// Runouts built on first use, the most recent `cache_runouts` kept.
+    Lazy { cache_runouts: usize },
+}
+
+impl PrecomputeMode {
+    /// Full when `runouts` runouts for these range sizes fit in `budget` bytes.
+    pub fn choose(runouts: usize, num_oop: usize, num_ip: usize, budget: u64) -> Self {
+        if runouts as u64 * runout_bytes(num_oop, num_ip) <= budget {
+            PrecomputeMode::Full
+        } else {
+            PrecomputeMode::Lazy { cache_runouts: LAZY_CACHE_RUNOUTS }
+        }
+    }
+}
+
+impl std::fmt::Display for PrecomputeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrecomputeMode::Full => write!(f, "full"),
+            PrecomputeMode::Lazy { cache_runouts } => write!(f, "lazy, cache {} runouts", cache_runouts),
+        }
+    }
+}
+
+/// Bytes one runout's `RunoutData` takes for ranges of these sizes.
+pub fn runout_bytes(num_oop: usize, num_ip: usize) -> u64 {
+    let per_combo = (std::mem::size_of::<u16>() + std::mem::size_of::<u32>()) as u64;
+    (num_oop + num_ip) as u64 * per_combo + std::mem::size_of::<RunoutData>() as u64
+}
+
+/// Parse a memory size like "2G", "512M", "64k" or a plain byte count.
+pub fn parse_mem_size(s: &str) -> Result<u64, String> {
+    let t = s.trim();
+    let (digits, unit) = match t.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) => t.split_at(i),
+        None => (t, ""),
+    };
+    let shift = match unit.to_ascii_uppercase().trim_end_matches(['B', 'I']) {
+        "" => 0,
+        "K" => 10,
+        "M" => 20,
+        "G" => 30,
+        "T" => 40,
+        _ => return Err(format!("Invalid memory size '{}' (e.g. 512M, 2G)", s)),
+    };
+    match digits.trim().parse::<f64>() {
+        Ok(v) if v >= 0.0 && v.is_finite() => Ok((v * (1u64 << shift) as f64) as u64),
+        _ => Err(format!("Invalid memory size '{}' (e.g. 512M, 2G)", s)),
+    }
+}
+
+/// Per-runout river data for one flop, full or lazy. Owns its deck and
+/// combos, so it can be kept and shared between solves of the spot.
+pub struct RunoutTable {
+    board: [u8; 3],
+    remaining: Vec<u8>,
+    oop_pairs: Vec<(u8, u8)>,
+    ip_pairs: Vec<(u8, u8)>,
+    num_buckets: usize,
+    store: Store,
+}
+
+enum Store {
+    Full(Vec<Arc<RunoutData>>),
+    Lazy(Mutex<Lru>),
+}
+
+/// Built runouts with the clock tick each was last used at.
+struct Lru {
+    entries: HashMap<usize, (Arc<RunoutData>, u64)>,
+    clock: u64,
+    capacity: usize,
+}
+
+impl RunoutTable {
+    /// `remaining` is the deck left after `board`; in `Full` mode every
+    /// runout is built here.
+    pub fn new(
+        board: &[u8],
+        remaining: &[u8],
+        oop_pairs: &[(u8, u8)],
+        ip_pairs: &[(u8, u8)],
+        num_buckets: usize,
+        mode: PrecomputeMode,
+    ) -> Self {
+        let mut table = RunoutTable {
+            board: [board[0], board[1], board[2]],
+            remaining: remaining.to_vec(),
+            oop_pairs: oop_pairs.to_vec(),
+            ip_pairs: ip_pairs.to_vec(),
+            num_buckets,
+            store: Store::Full(Vec::new()),
+        };
+        table.store = match mode {
+            PrecomputeMode::Full => {
+                Store::Full((0..table.len()).into_par_iter().map(|idx| Arc::new(table.build(idx))).collect())
+            }
+            PrecomputeMode::Lazy { cache_runouts } => Store::Lazy(Mutex::new(Lru {
+                entries: HashMap::new(),
+                clock: 0,
+                capacity: cache_runouts.max(1),
+            })),
+        };
+        table
+    }
+
+    /// The deck left after the board, which runout indices refer to.
+    pub fn remaining(&self) -> &[u8] {
+        &self.remaining
+    }
+
+    pub fn mode(&self) -> PrecomputeMode {
+        match &self.store {
+            Store::Full(_) => PrecomputeMode::Full,
+            Store::Lazy(lru) => PrecomputeMode::Lazy { cache_runouts: lru.lock().unwrap().capacity },
+        }
+    }
+
+    /// Number of runouts.
+    pub fn len(&self) -> usize {
+        let n = self.remaining.len();
+        n * n.saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Index of the runout with these positions in `remaining` (which differ).
+    pub fn runout_idx(&self, turn_idx: usize, river_idx: usize) -> usize {
+        turn_idx * (self.remaining.len() - 1) + if river_idx > turn_idx { river_idx - 1 } else { river_idx }
+    }
+
+    /// Positions in `remaining` of a runout's turn and river cards.
+    pub fn cards(&self, runout_idx: usize) -> (usize, usize) {
+        let turn_idx = runout_idx / (self.remaining.len() - 1);
+        let river_adj = runout_idx % (self.remaining.len() - 1);
+        (turn_idx, if river_adj >= turn_idx { river_adj + 1 } else { river_adj })
+    }
+
+    pub fn get(&self, runout_idx: usize) -> Arc<RunoutData> {
+        let lru = match &self.store {
+            Store::Full(all) => return all[runout_idx].clone(),
+            Store::Lazy(lru) => lru,
+        };
+        {
+            let mut lru = lru.lock().unwrap();
+            lru.clock += 1;
+            let clock = lru.clock;
+            if let Some((data, used)) = lru.entries.get_mut(&runout_idx) {
+                *used = clock;
+                return data.clone();
+            }
+        }
+        // Built outside the lock, so parallel readers don't wait on each other
+        let data = Arc::new(self.build(runout_idx));
+        let mut lru = lru.lock().unwrap();
+        if lru.entries.len() >= lru.capacity {
+            if let Some(oldest) = lru.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(&idx, _)| idx) {
+                lru.entries.remove(&oldest);
+            }
+        }
+        let clock = lru.clock;
+        lru.entries.insert(runout_idx, (data.clone(), clock));
+        data
+    }
+
+    fn build(&self, runout_idx: usize) -> RunoutData {
+        let (turn_idx, river_idx) = self.cards(runout_idx);
+        let river_board =
+            [self.board[0], self.board[1], self.board[2], self.remaining[turn_idx], self.remaining[river_idx]];
+        let scores = |pairs: &[(u8, u8)]| -> Vec<u32> {
+            let mut scores = vec![0u32; pairs.len()];
+            evaluate_many(pairs, &river_board, &mut scores);
+            scores
+        };
+        RunoutData {
+            oop_buckets: assign_buckets(&self.oop_pairs, &river_board, self.num_buckets, 0),
+            ip_buckets: assign_buckets(&self.ip_pairs, &river_board, self.num_buckets, 0),
+            oop_scores: scores(&self.oop_pairs),
+            ip_scores: scores(&self.ip_pairs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_encoding::card_to_index;
+    use crate::cards::parse_board;
+
+    #[test]
+    fn test_parse_mem_size() {
+        assert_eq!(parse_mem_size("2G"), Ok(2 << 30));
+        assert_eq!(parse_mem_size("512m"), Ok(512 << 20));
+        assert_eq!(parse_mem_size("1.5GiB"), Ok(3 << 29));
+        assert_eq!(parse_mem_size("4096"), Ok(4096));
+        assert!(parse_mem_size("2X").is_err());
+        assert!(parse_mem_size("-1G").is_err());
+    }
+
+    #[test]
+    fn test_lazy_table_matches_full_and_evicts() {
+        let board: Vec<u8> = parse_board("Ks9d4c").unwrap().iter().map(card_to_index).collect();
+        let remaining: Vec<u8> = (0..52u8).filter(|c| !board.contains(c)).collect();
+        let oop: Vec<(u8, u8)> = vec![(0, 1), (12, 25), (30, 44)];
+        let ip: Vec<(u8, u8)> = vec![(2, 3), (40, 50)];
+        let full = RunoutTable::new(&board, &remaining, &oop, &ip, 10, PrecomputeMode::Full);
+        let lazy = RunoutTable::new(&board, &remaining, &oop, &ip, 10, PrecomputeMode::Lazy { cache_runouts: 4 });
+        assert_eq!(full.len(), 49 * 48);
+        for idx in (0..full.len()).step_by(97).chain([5, 5, 0]) {
+            let (a, b) = (full.get(idx), lazy.get(idx));
+            assert_eq!((&a.oop_buckets, &a.ip_buckets), (&b.oop_buckets, &b.ip_buckets));
+            assert_eq!((&a.oop_scores, &a.ip_scores), (&b.oop_scores, &b.ip_scores));
+            let (t, r) = full.cards(idx);
+            assert_eq!(full.runout_idx(t, r), idx);
+        }
+        let Store::Lazy(lru) = &lazy.store else { unreachable!() };
+        assert_eq!(lru.lock().unwrap().entries.len(), 4);
+
+        assert_eq!(PrecomputeMode::choose(2256, 1000, 1000, 1 << 20), PrecomputeMode::Lazy { cache_runouts: 512 });
+        assert_eq!(PrecomputeMode::choose(2256, 10, 10, DEFAULT_MAX_PRECOMPUTE_BYTES), PrecomputeMode::Full);
+        assert_eq!(PrecomputeMode::Lazy { cache_runouts: 512 }.to_string(), "lazy, cache 512 runouts");
+    }
+}
@@ -0,0 +1,96 @@
+//! JSON export of postflop solutions (`gto solve flop|turn|river --json`).
+//!
+//! The JSON is the solution struct with every field, as cached, plus a
+//! top-level `schema_version` and, for heads-up solutions, a `summary` (see
+//! `range_summary`) and a `breakdown` (see `breakdown`). Per-combo arrays are indexed exactly like
+//! `oop_combos` / `ip_combos`. Bump `SCHEMA_VERSION` whenever a field is
+//! renamed, removed or changes meaning; new fields alone don't need a bump.
+//! Non-finite numbers are written as `null`.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::breakdown::Breakdown;
+use crate::flop_solver::FlopSolution;
+use crate::multiway_solver::MultiwaySolution;
+use crate::range_summary::RangeSummary;
+use crate::river_solver::RiverSolution;
+use crate::turn_solver::TurnSolution;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A solution `to_json` exports.
+pub trait JsonSolution: Serialize {
+    /// Written as the top-level `summary`, when there is one.
+    fn range_summary(&self) -> Option<RangeSummary> {
+        None
+    }
+
+    /// Written as the top-level `breakdown`, when there is one.
+    fn breakdown(&self) -> Option<Breakdown> {
+        None
+    }
+}
+
+impl JsonSolution for FlopSolution {
+    fn range_summary(&self) -> Option<RangeSummary> {
+        Some(self.summary())
+    }
+
+    fn breakdown(&self) -> Option<Breakdown> {
+        Some(Self::breakdown(self))
+    }
+}
+
+impl JsonSolution for TurnSolution {
+    fn range_summary(&self) -> Option<RangeSummary> {
+        Some(self.summary())
+    }
+
+    fn breakdown(&self) -> Option<Breakdown> {
+        Some(Self::breakdown(self))
+    }
+}
+
+impl JsonSolution for RiverSolution {
+    fn range_summary(&self) -> Option<RangeSummary> {
+        Some(self.summary())
+    }
+
+    fn breakdown(&self) -> Option<Breakdown> {
+        Some(Self::breakdown(self))
+    }
+}
+
+impl JsonSolution for MultiwaySolution {}
+
+/// `solution` as a JSON object with its `schema_version`, summary and breakdown.
+pub fn to_json<T: JsonSolution>(solution: &T) -> Result<String, String> {
+    let fields = match serde_json::to_value(solution).map_err(|e| e.to_string())? {
+        serde_json::Value::Object(fields) => fields,
+        other => return Err(format!("Expected a solution object, got {}", other)),
+    };
+    let mut json = serde_json::Map::new();
+    json.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+    json.extend(fields);
+    if let Some(summary) = solution.range_summary() {
+        json.insert("summary".to_string(), serde_json::to_value(summary).map_err(|e| e.to_string())?);
+    }
+    if let Some(breakdown) = JsonSolution::breakdown(solution) {
+        json.insert("breakdown".to_string(), serde_json::to_value(breakdown).map_err(|e| e.to_string())?);
+    }
+    serde_json::to_string(&json).map_err(|e| e.to_string())
+}
+
+/// Write `solution` as JSON to `output`, or to stdout.
+pub fn write_json<T: JsonSolution>(solution: &T, output: Option<&Path>) -> Result<(), String> {
+    let json = to_json(solution)?;
+    match output {
+        Some(path) => std::fs::write(path, json + "\n").map_err(|e| format!("Cannot write {}: {}", path.display(), e)),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    }
+}
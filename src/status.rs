@@ -0,0 +1,121 @@
+//! Exit codes and the `--status-json` line, for scripts driving `gto`.
+//!
+//! | Code | Meaning                                                           |
+//! |------|-------------------------------------------------------------------|
+//! | 0    | Success                                                           |
+//! | 2    | Invalid input: bad arguments, board, range, seat, ...             |
+//! | 3    | Cache or file I/O error, e.g. a solve that couldn't be saved      |
+//! | 4    | Solve failed, or its quality is below the requested floor         |
+//! | 5    | Interrupted with a partial result: a prefetch or `--time` ran out |
+//!
+//! Commands keep printing their own errors; the first failure of a run is
+//! recorded here and the dispatcher turns it into the exit code. Each call
+//! site picks the code: `print_error` records invalid input, `print_failure`
+//! any other code, and `io_error` marks a failed read or write on its way to
+//! being printed. With `--status-json` every run ends with one
+//! JSON line on stderr:
+//!
+//! ```json
+//! {"command":"solve river","ok":true,"exit_code":0,"duration_ms":412,"cache_path":"/home/me/.gto-cli/solver/river_....bin"}
+//! ```
+
+use std::path::Path;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    InvalidInput = 2,
+    CacheIo = 3,
+    SolveFailed = 4,
+    Interrupted = 5,
+}
+
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+/// What a run has reported so far.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Status {
+    pub code: ExitCode,
+    pub error: Option<String>,
+    /// Last cache file the run wrote (or tried to).
+    pub cache_path: Option<String>,
+}
+
+static STATUS: Mutex<Status> = Mutex::new(Status { code: ExitCode::Success, error: None, cache_path: None });
+
+/// Record a failure. The first one wins: later errors are usually fallout.
+pub fn fail(code: ExitCode, error: &str) {
+    let mut status = STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    if status.code == ExitCode::Success {
+        status.code = code;
+        status.error = Some(error.to_string());
+    }
+}
+
+/// Record `error` as a cache or file I/O failure and pass it on, for the
+/// `map_err` of a command's reads and writes.
+pub fn io_error(error: String) -> String {
+    fail(ExitCode::CacheIo, &error);
+    error
+}
+
+/// Record the outcome of writing a cache file, printing an error if it failed.
+pub fn cache_written(path: &Path, result: std::io::Result<()>) {
+    STATUS.lock().unwrap_or_else(|e| e.into_inner()).cache_path = Some(path.display().to_string());
+    if let Err(e) = result {
+        let msg = format!("Cannot write cache {}: {}", path.display(), e);
+        fail(ExitCode::CacheIo, &msg);
+        crate::display::print_error(&msg);
+    }
+}
+
+/// The run's status so far.
+pub fn current() -> Status {
+    STATUS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// The `--status-json` line.
+pub fn status_line(command: &str, status: &Status, duration_ms: u128) -> String {
+    let mut line = serde_json::json!({
+        "command": command,
+        "ok": status.code == ExitCode::Success,
+        "exit_code": status.code.code(),
+        "duration_ms": duration_ms as u64,
+    });
+    if let Some(path) = &status.cache_path {
+        line["cache_path"] = path.clone().into();
+    }
+    if let Some(error) = &status.error {
+        line["error"] = error.clone().into();
+    }
+    line.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_line_omits_absent_fields() {
+        let ok = Status { code: ExitCode::Success, error: None, cache_path: None };
+        let line: serde_json::Value = serde_json::from_str(&status_line("odds", &ok, 12)).unwrap();
+        assert_eq!(line["ok"], true);
+        assert_eq!(line["exit_code"], 0);
+        assert!(line.get("error").is_none() && line.get("cache_path").is_none());
+
+        let failed = Status {
+            code: ExitCode::CacheIo,
+            error: Some("Cannot write cache".to_string()),
+            cache_path: Some("/x/river.bin".to_string()),
+        };
+        let line: serde_json::Value = serde_json::from_str(&status_line("solve river", &failed, 5)).unwrap();
+        assert_eq!(line["ok"], false);
+        assert_eq!(line["exit_code"], 3);
+        assert_eq!(line["cache_path"], "/x/river.bin");
+    }
+}
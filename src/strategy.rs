@@ -1,38 +1,160 @@
 //! Strategy lookup engine — queries solver output to answer:
 //! "Given this hand + position + board, what are the GTO action frequencies?"
 
-use crate::bucketing::assign_buckets;
-use crate::card_encoding::card_to_index;
-use crate::cards::parse_board;
-use crate::flop_solver::{FlopSolverConfig, FlopSolution, TemplateBucketStrategy, TreeEdge, solve_flop};
-use crate::preflop_solver::{Position, PreflopSolution, PreflopSpotResult};
-use crate::river_solver::{RiverSolverConfig, RiverSolution, solve_river};
-use crate::turn_solver::{TurnSolverConfig, TurnSolution, solve_turn};
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::cache_key::{drill_key, range_key_of};
+use crate::card_encoding::{card_to_index, index_to_card};
+use crate::cards::{check_dead_cards, check_distinct, parse_board, suit_style, Card, Suit, SuitStyle};
+use crate::display::ev_bar;
+use crate::flop_solver::{
+    solve_flop, turn_template_config, FlopSolverConfig, FlopSolution,
+    TemplateBucketStrategy, VillainView,
+};
+use crate::limits::{Bounds, FLOP_ITERATIONS, RIVER_ITERATIONS, TURN_ITERATIONS};
+use crate::postflop_tree::{
+    action_index, build_tree, build_turn_tree, legal_actions, player_label, walk_line, walk_line_to_river, Action, Player, TreeConfig,
+    TreeNode,
+};
+use crate::postprocess::{Simplify, SimplifyInfo};
+use crate::preflop_solver::{evs_over_fold, ForcedBets, Position, PreflopSizing, PreflopSolution, PreflopSpotResult};
+use crate::quality::{flop_node_reach, format_count, AnswerQuality, Grade};
+use crate::ranges::{parse_range, weighted_range_string};
+use crate::river_solver::{
+    Combo, RiverSolverConfig, RiverSolution, ShowdownOutcome, expand_range_to_combos, hand_combo_indices, try_solve_river,
+};
+use crate::turn_solver::{TurnNodeStrategy, TurnSolverConfig, TurnSolution, try_solve_turn};
 
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
 
+/// Wall time for an on-demand postflop solve when a query sets neither
+/// `--time` nor `--iterations`.
+pub const DEFAULT_SOLVE_TIME: Duration = Duration::from_secs(20);
+
 pub struct StrategyEngine {
     preflop: Option<PreflopSolution>,
     pub stack_bb: f64,
+    /// Overrides the derived villain range for postflop queries (exploitative solve).
+    villain_range: Option<String>,
+    /// Overrides the derived hero range the same way.
+    hero_range: Option<String>,
+    /// Allow on-demand solves outside the iteration bounds (see `limits`).
+    force: bool,
+    /// Minimum grade for postflop answers; cached answers below it are re-solved.
+    min_grade: Option<Grade>,
+    /// Re-solve low-fidelity flop solutions at full fidelity (`--refine`)
+    /// instead of answering from them.
+    refine: bool,
+    /// Answer postflop queries from the cache only: a miss is an error
+    /// instead of an on-demand solve.
+    cache_only: bool,
+    /// Round postflop answers for `--simplify`/`--purify`.
+    simplify: Option<Simplify>,
+    /// Cards known to be out of the deck (`--dead`). Cached solves were dealt
+    /// from the full deck, so postflop queries with dead cards solve fresh
+    /// and don't cache the result.
+    dead_cards: Vec<Card>,
+    /// Answer turn queries on this river (`--river-card`) from the river
+    /// strategies the turn solve keeps.
+    river_card: Option<Card>,
+    /// Answer turn queries from a drill-down solve (`--drill`): the flop
+    /// line that led to the turn, played by the cached flop solve.
+    drill: Option<Vec<String>>,
+    /// Add villain's range and responses to flop answers (`--show-villain`).
+    show_villain: bool,
+    /// Wall time for each on-demand solve (`--time`); the iterations passed
+    /// to `query_postflop` are then ignored, and the street's most
+    /// iterations are the cap.
+    time_budget: Option<Duration>,
+    /// Quick solves being written to the cache in the background.
+    pending_saves: Vec<std::thread::JoinHandle<()>>,
+}
+
+/// A postflop spot to look hero's hand up in (`query_postflop`).
+#[derive(Debug, Clone, Copy)]
+pub struct PostflopSpot<'a> {
+    pub hero: Position,
+    pub villain: Position,
+    /// Picks the preflop ranges and keys the cache.
+    pub pot_type: PotType,
+    pub board: &'a str,
+    /// Taken as given, not derived from `pot_type`.
+    pub pot: f64,
+    pub stack: f64,
+    /// Actions taken so far on this street (e.g. ["check", "bet33"], see
+    /// `walk_line`), ending on hero's decision. Empty means hero's first node.
+    pub line: &'a [String],
+}
+
+impl<'a> PostflopSpot<'a> {
+    /// A single raised pot at hero's first decision on the street.
+    pub fn new(hero: Position, villain: Position, board: &'a str, pot: f64, stack: f64) -> Self {
+        PostflopSpot { hero, villain, pot_type: PotType::Srp, board, pot, stack, line: &[] }
+    }
+
+    pub fn with_pot_type(mut self, pot_type: PotType) -> Self {
+        self.pot_type = pot_type;
+        self
+    }
+
+    pub fn with_line(mut self, line: &'a [String]) -> Self {
+        self.line = line;
+        self
+    }
 }
 
 pub struct StrategyResult {
     pub actions: Vec<String>,
     pub frequencies: Vec<f64>,
     pub source: StrategySource,
+    /// Confidence in a solver answer; `None` for preflop and not-in-range results.
+    pub quality: Option<AnswerQuality>,
+    /// Hero's win/tie/lose shares at showdown from this node, when the
+    /// solution stores them (turn and river solves).
+    pub showdown: Option<ShowdownOutcome>,
+    /// Hero's EV per action, in starting pots: stored by flop solves,
+    /// evaluated for river solves. Preflop, in bb over folding (see
+    /// `format_preflop_evs`), when the cached solve stores them.
+    pub evs: Option<Vec<f64>>,
+    /// Set when `frequencies` were rounded for `--simplify`/`--purify`.
+    pub simplified: Option<SimplifyInfo>,
+    /// Villain's range and how it answers hero's options, for flop answers
+    /// with `--show-villain`.
+    pub villain: Option<VillainView>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum StrategySource {
     Cached,
     SolvedOnDemand,
+    /// A low-fidelity flop solve (`FlopSolverConfig::quick`), fresh or cached.
+    QuickSolve,
+    /// A flop solve's bucketed turn/river template strategy.
+    DerivedFromFlop,
+    /// A turn solve from the flop solve's reach into the turn (`--drill`),
+    /// fresh or cached.
+    Drilled,
     NotInRange,
 }
 
+impl StrategySource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StrategySource::Cached => "cached",
+            StrategySource::SolvedOnDemand => "solved on demand",
+            StrategySource::QuickSolve => "quick solve (approximate)",
+            StrategySource::DerivedFromFlop => "derived from flop solve (bucketed)",
+            StrategySource::Drilled => "drill-down solve from flop",
+            StrategySource::NotInRange => "not in range",
+        }
+    }
+}
+
 /// Standard pot type for postflop solving.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PotType {
     /// Single raised pot: 2.5bb open + BB call + blinds = 6bb, 97bb effective
     Srp,
@@ -58,6 +180,45 @@ impl PotType {
             PotType::FourBet => "4BP",
         }
     }
+
+    /// Parse a `--pot-type` value: "srp", "3bet"/"3bp" or "4bet"/"4bp", any case.
+    pub fn parse(s: &str) -> Option<PotType> {
+        match s.trim().to_lowercase().as_str() {
+            "srp" => Some(PotType::Srp),
+            "3bet" | "3bp" => Some(PotType::ThreeBet),
+            "4bet" | "4bp" => Some(PotType::FourBet),
+            _ => None,
+        }
+    }
+
+    /// Starting pot and effective stack with `stack` bb behind preflop:
+    /// `pot_and_stack` scaled from 100bb.
+    pub fn pot_and_stack_at(&self, stack: f64) -> (f64, f64) {
+        let (pot, eff_stack) = self.pot_and_stack();
+        let scale = stack / 100.0;
+        (pot * scale, eff_stack * scale)
+    }
+
+    /// `pot_and_stack_at` for a pot between `seats` in a game with antes or
+    /// a straddle: raises scale with the straddle, the antes are dead money
+    /// that also come out of the stacks, and so is the straddle unless one
+    /// of the seats posted it.
+    pub fn pot_and_stack_with(&self, stack: f64, forced: &ForcedBets, seats: (Position, Position)) -> (f64, f64) {
+        let (pot, eff_stack) = self.pot_and_stack_at(stack);
+        let behind = stack - forced.ante_bb;
+        let committed = ((stack - eff_stack) * forced.biggest_blind()).min(behind);
+        let pot = pot + (committed - (stack - eff_stack)) * 2.0 + forced.extra_dead_money_between(seats.0, seats.1);
+        (pot, behind - committed)
+    }
+
+    /// Cache key for a seat in this pot type. Single raised pots use the bare
+    /// seat, so solves cached before pot types were keyed still match.
+    pub fn seat_key(&self, pos: &str) -> String {
+        match self {
+            PotType::Srp => pos.to_string(),
+            _ => format!("{}-{}", pos, self.as_str().to_lowercase()),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -76,6 +237,110 @@ pub fn default_villain(hero: Position) -> Position {
     }
 }
 
+/// Parse seats given for a manual solve and check `oop` acts first postflop.
+pub fn postflop_seats(oop: &str, ip: &str) -> Result<(Position, Position), String> {
+    let (oop_pos, ip_pos) = (parse_seat(oop)?, parse_seat(ip)?);
+    if oop_pos == ip_pos {
+        return Err(format!("OOP and IP seats are both {}", oop_pos));
+    }
+    if !ip_pos.is_ip_vs(&oop_pos) {
+        return Err(format!(
+            "{} acts before {} postflop; swap --oop-pos and --ip-pos",
+            ip_pos, oop_pos
+        ));
+    }
+    Ok((oop_pos, ip_pos))
+}
+
+fn parse_seat(s: &str) -> Result<Position, String> {
+    Position::from_str(s).ok_or_else(|| format!("Invalid position '{}'. Valid: UTG, HJ, CO, BTN, SB, BB", s))
+}
+
+/// The (OOP, IP) seats for hero at `hero` against villain at `villain`,
+/// and whether hero is OOP. `seats`, an (OOP, IP) pair also given, must
+/// name the same two seats the same way round.
+pub fn hero_villain_seats(
+    hero: &str,
+    villain: &str,
+    seats: Option<(&str, &str)>,
+) -> Result<((Position, Position), bool), String> {
+    let (hero_pos, villain_pos) = (parse_seat(hero)?, parse_seat(villain)?);
+    if hero_pos == villain_pos {
+        return Err(format!("Hero and villain are both {}", hero_pos));
+    }
+    let hero_is_oop = villain_pos.is_ip_vs(&hero_pos);
+    let derived = if hero_is_oop { (hero_pos, villain_pos) } else { (villain_pos, hero_pos) };
+    let Some((oop, ip)) = seats else {
+        return Ok((derived, hero_is_oop));
+    };
+    let given = (parse_seat(oop)?, parse_seat(ip)?);
+    if given == derived {
+        Ok((derived, hero_is_oop))
+    } else if given == (derived.1, derived.0) {
+        Err(format!(
+            "--oop-pos {} and --ip-pos {} have the seats the wrong way round: {} acts first postflop, so {} is OOP \
+             and {} is IP",
+            oop, ip, derived.0, derived.0, derived.1
+        ))
+    } else {
+        Err(format!(
+            "--oop-pos {} and --ip-pos {} aren't the --hero-pos/--villain-pos seats ({} and {})",
+            oop, ip, hero_pos, villain_pos
+        ))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hero/villain range overrides (exploitative solves)
+// ---------------------------------------------------------------------------
+
+/// Replace one side of a derived `(oop, ip)` range pair with an override,
+/// which must keep at least one combo once board cards are removed; `who`
+/// ("Hero"/"Villain") names it in the error when none survives.
+pub fn apply_range_override(
+    oop_range: &str,
+    ip_range: &str,
+    override_is_ip: bool,
+    range: &str,
+    board: &str,
+    who: &str,
+) -> Result<(String, String), String> {
+    if override_combos(range, board)?.is_empty() {
+        return Err(format!("{} range '{}' has no combos left on board {}", who, range, board));
+    }
+    if override_is_ip {
+        Ok((oop_range.to_string(), range.to_string()))
+    } else {
+        Ok((range.to_string(), ip_range.to_string()))
+    }
+}
+
+/// Cache position key for an exploitative solve: the overridden side's position
+/// tagged with a hash of the override's combos, so it never shares a cache entry
+/// with the GTO solve.
+pub fn exploit_position_key(pos: &str, range: &str, board: &str) -> String {
+    let combos = override_combos(range, board).unwrap_or_default();
+    // FNV-1a over the canonical (sorted) combo list — stable across runs
+    let mut hash: u32 = 0x811c_9dc5;
+    for combo in &combos {
+        for byte in combo.to_string().bytes().chain(std::iter::once(b',')) {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+    }
+    format!("{}-x{:08x}", pos, hash)
+}
+
+fn override_combos(range: &str, board: &str) -> Result<Vec<Combo>, String> {
+    let board_cards: Vec<u8> = parse_board(board)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(card_to_index)
+        .collect();
+    let hands = parse_range(range).map_err(|e| e.to_string())?;
+    Ok(expand_range_to_combos(&hands, &board_cards))
+}
+
 // ---------------------------------------------------------------------------
 // Range derivation from preflop solver
 // ---------------------------------------------------------------------------
@@ -106,24 +371,136 @@ pub fn derive_defending_range(spot: &PreflopSpotResult, threshold: f64) -> Vec<S
     hands
 }
 
-// ---------------------------------------------------------------------------
-// Combo lookup
-// ---------------------------------------------------------------------------
+/// Opener and responder ranges that see a flop in `pot_type`: the opening
+/// vs defending range in a single raised pot, the opener's 3-bet calls vs the
+/// responder's 3-bets in a 3-bet pot, and the opener's 4-bets vs the
+/// responder's 4-bet calls in a 4-bet pot.
+pub fn derive_pot_ranges(spot: &PreflopSpotResult, pot_type: PotType, threshold: f64) -> (Vec<String>, Vec<String>) {
+    let hands = |freq: &dyn Fn(usize) -> f64| -> Vec<String> {
+        (0..spot.open_strategy.len())
+            .filter(|&i| freq(i) > threshold)
+            .map(crate::game_tree::bucket_to_hand)
+            .collect()
+    };
+    match pot_type {
+        PotType::Srp => (derive_opening_range(spot, threshold), derive_defending_range(spot, threshold)),
+        PotType::ThreeBet => (
+            hands(&|i| spot.open_strategy[i] * spot.vs_3bet_call[i]),
+            hands(&|i| spot.vs_open_3bet[i]),
+        ),
+        PotType::FourBet => (
+            hands(&|i| spot.open_strategy[i] * spot.vs_3bet_4bet[i]),
+            hands(&|i| spot.vs_open_3bet[i] * spot.vs_4bet_call[i]),
+        ),
+    }
+}
 
-/// Find the index of a specific hand combo (e.g., "AhQd") in a combo list.
-/// Checks both orderings (AhQd and QdAh).
-pub fn find_combo_index(hand_str: &str, combo_list: &[String]) -> Option<usize> {
-    if hand_str.len() != 4 {
-        return None;
+/// Weighted opener and responder ranges that see a flop in `pot_type`, as
+/// range text: each hand at the frequency it reaches the flop (rounded to
+/// 0.01), hands below `threshold` left out. Unlike `derive_pot_ranges`, a
+/// single raised pot's responder only keeps its calls.
+pub fn derive_weighted_pot_ranges(spot: &PreflopSpotResult, pot_type: PotType, threshold: f64) -> (String, String) {
+    let range = |freq: &dyn Fn(usize) -> f64| -> String {
+        let hands: Vec<(String, f64)> = (0..spot.open_strategy.len())
+            .map(|i| (crate::game_tree::bucket_to_hand(i), (freq(i) * 100.0).round() / 100.0))
+            .filter(|&(_, w)| w > threshold)
+            .collect();
+        weighted_range_string(&hands)
+    };
+    match pot_type {
+        PotType::Srp => (range(&|i| spot.open_strategy[i]), range(&|i| spot.vs_open_call[i])),
+        PotType::ThreeBet => (
+            range(&|i| spot.open_strategy[i] * spot.vs_3bet_call[i]),
+            range(&|i| spot.vs_open_3bet[i]),
+        ),
+        PotType::FourBet => (
+            range(&|i| spot.open_strategy[i] * spot.vs_3bet_4bet[i]),
+            range(&|i| spot.vs_open_3bet[i] * spot.vs_4bet_call[i]),
+        ),
     }
-    let card1 = &hand_str[..2];
-    let card2 = &hand_str[2..];
-    let forward = format!("{}{}", card1, card2);
-    let reverse = format!("{}{}", card2, card1);
+}
 
-    combo_list
-        .iter()
-        .position(|c| c == &forward || c == &reverse)
+/// Opener and responder ranges that see a flop in `pot_type` by the static
+/// 6-max charts (`preflop`): opens vs calls, or 3-bet calls vs 3-bets. The
+/// charts have no 4-bet calls.
+pub fn chart_pot_ranges(opener: Position, responder: Position, pot_type: PotType) -> Result<(String, String), String> {
+    use crate::preflop::{get_rfi_range, get_vs_3bet_range, get_vs_rfi_range};
+
+    let (o, r) = (opener.as_str(), responder.as_str());
+    let vs_open = get_vs_rfi_range(r, o, "6max");
+    let (opener_range, responder_range) = match pot_type {
+        PotType::Srp => (get_rfi_range(o, "6max"), vs_open.call),
+        PotType::ThreeBet => (get_vs_3bet_range(o, r, "6max").call, vs_open.three_bet),
+        PotType::FourBet => return Err("The preflop charts have no 4-bet pot ranges".to_string()),
+    };
+    if opener_range.is_empty() || responder_range.is_empty() {
+        return Err(format!("The preflop charts have no {} ranges for {} vs {}", pot_type.as_str(), o, r));
+    }
+    Ok((opener_range.join(","), responder_range.join(",")))
+}
+
+/// A preflop matchup like "BTNvsBB" as (opener, responder); the seats may
+/// come in either order.
+pub fn parse_matchup(s: &str) -> Result<(Position, Position), String> {
+    let lower = s.to_lowercase();
+    let (a, b) = lower
+        .split_once("vs")
+        .ok_or_else(|| format!("Invalid matchup '{}'. Expected OPENERvsRESPONDER, e.g. BTNvsBB", s))?;
+    let parse = |p: &str| {
+        Position::from_str(p.trim_matches(|c: char| c == '-' || c == '_' || c.is_whitespace()))
+            .ok_or_else(|| format!("Invalid position '{}' in '{}'. Valid: UTG, HJ, CO, BTN, SB, BB", p, s))
+    };
+    let (a, b) = (parse(a)?, parse(b)?);
+    if a == b {
+        return Err(format!("Matchup '{}' has the same seat twice", s));
+    }
+    Ok(if preflop_open_order(a) < preflop_open_order(b) { (a, b) } else { (b, a) })
+}
+
+/// Ranges and seats for a postflop solve of a preflop matchup.
+#[derive(Debug, Clone)]
+pub struct MatchupRanges {
+    pub oop_pos: Position,
+    pub ip_pos: Position,
+    pub oop_range: String,
+    pub ip_range: String,
+    /// True when no usable preflop solve was cached at the stack and the
+    /// ranges come from the static charts.
+    pub from_charts: bool,
+}
+
+/// Ranges for `matchup` ("BTNvsBB") in `pot_type` from the cached 6-max
+/// preflop solve at `stack_bb`, falling back to the static charts when there
+/// is none (or it's below the iteration floor).
+pub fn matchup_ranges(matchup: &str, pot_type: PotType, stack_bb: f64) -> Result<MatchupRanges, String> {
+    let (opener, responder) = parse_matchup(matchup)?;
+    let solved = PreflopSolution::load("6max", stack_bb, 0.0, &PreflopSizing::default())
+        .ok()
+        .filter(|s| s.meets_quality_floor());
+    let (opener_range, responder_range, from_charts) = match solved.as_ref().and_then(|s| s.find_spot(opener, responder)) {
+        Some(spot) => {
+            let (opener_range, responder_range) = derive_weighted_pot_ranges(spot, pot_type, RANGE_THRESHOLD);
+            if opener_range.is_empty() || responder_range.is_empty() {
+                return Err(format!("Derived {} ranges for {} vs {} are empty", pot_type.as_str(), opener, responder));
+            }
+            (opener_range, responder_range, false)
+        }
+        None => {
+            let (opener_range, responder_range) = chart_pot_ranges(opener, responder, pot_type)?;
+            (opener_range, responder_range, true)
+        }
+    };
+    Ok(if opener.is_ip_vs(&responder) {
+        MatchupRanges { oop_pos: responder, ip_pos: opener, oop_range: responder_range, ip_range: opener_range, from_charts }
+    } else {
+        MatchupRanges { oop_pos: opener, ip_pos: responder, oop_range: opener_range, ip_range: responder_range, from_charts }
+    })
+}
+
+/// `range_key` of a query's ranges, or `None` (the seats' latest solve,
+/// whatever its ranges) when they couldn't be derived.
+fn ranges_key(ranges: &Result<(String, String), String>) -> Option<String> {
+    ranges.as_ref().ok().map(|(oop, ip)| range_key_of(oop, ip))
 }
 
 // ---------------------------------------------------------------------------
@@ -133,13 +510,141 @@ pub fn find_combo_index(hand_str: &str, combo_list: &[String]) -> Option<usize>
 impl StrategyEngine {
     pub fn new(stack_bb: f64) -> Self {
         // Try loading preflop solution
-        let preflop = PreflopSolution::load("6max", stack_bb, 0.0).ok();
+        let preflop = PreflopSolution::load("6max", stack_bb, 0.0, &PreflopSizing::default())
+            .ok()
+            .filter(|s| s.meets_quality_floor());
         StrategyEngine {
             preflop,
             stack_bb,
+            villain_range: None,
+            hero_range: None,
+            force: false,
+            min_grade: None,
+            refine: false,
+            cache_only: false,
+            simplify: None,
+            dead_cards: Vec::new(),
+            river_card: None,
+            drill: None,
+            show_villain: false,
+            time_budget: None,
+            pending_saves: Vec::new(),
         }
     }
 
+    /// Take `cards` out of the deck for postflop queries (`--dead`).
+    pub fn with_dead_cards(mut self, cards: Vec<Card>) -> Self {
+        self.dead_cards = cards;
+        self
+    }
+
+    /// Answer turn queries on the river `card` deals (`--river-card`): the
+    /// action path closes the turn and carries on into the river.
+    pub fn with_river_card(mut self, card: Option<Card>) -> Self {
+        self.river_card = card;
+        self
+    }
+
+    /// Answer turn queries from a combo-level turn and river solve whose
+    /// ranges are the cached flop solve's, played along `flop_line`
+    /// (`--drill`).
+    pub fn with_drill(mut self, flop_line: Option<Vec<String>>) -> Self {
+        self.drill = flop_line;
+        self
+    }
+
+    /// Add villain's range and its responses to hero's options to flop
+    /// answers (`--show-villain`).
+    pub fn with_show_villain(mut self, show: bool) -> Self {
+        self.show_villain = show;
+        self
+    }
+
+    /// Give on-demand solves a wall-time budget instead of an iteration
+    /// count (`--time`).
+    pub fn with_time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.time_budget = budget;
+        self
+    }
+
+    /// Iterations for an on-demand solve: `iterations`, or with a time
+    /// budget the most `bounds` allow (a cap).
+    fn solve_iterations(&self, iterations: usize, bounds: Bounds) -> usize {
+        if self.time_budget.is_some() { bounds.max } else { iterations }
+    }
+
+    /// How long an on-demand solve takes, for its "Solving ..." line:
+    /// `typical` ("1-5s"), or the time budget.
+    fn solve_length(&self, typical: &str) -> String {
+        match self.time_budget {
+            Some(budget) => format!("up to {:.0}s", budget.as_secs_f64()),
+            None => format!("this may take {}", typical),
+        }
+    }
+
+    /// Answer postflop queries from solutions rounded by `simplify`.
+    pub fn with_simplify(mut self, simplify: Option<Simplify>) -> Self {
+        self.simplify = simplify;
+        self
+    }
+
+    /// Re-solve quick flop solutions at full fidelity (`--refine`).
+    pub fn with_refine(mut self, refine: bool) -> Self {
+        self.refine = refine;
+        self
+    }
+
+    /// Refuse postflop answers graded below `min` (`--min-grade`).
+    pub fn with_min_grade(mut self, min: Option<Grade>) -> Self {
+        self.min_grade = min;
+        self
+    }
+
+    /// Answer preflop queries from the solve at `sizing` in a game with
+    /// `forced` antes and straddle (`--open-size`, `--ante`, `--straddle`).
+    pub fn with_preflop_game(mut self, sizing: &PreflopSizing, forced: ForcedBets) -> Self {
+        if *sizing != PreflopSizing::default() || forced != ForcedBets::default() {
+            self.preflop = PreflopSolution::load_with("6max", self.stack_bb, 0.0, sizing, &forced)
+                .ok()
+                .filter(|s| s.meets_quality_floor());
+        }
+        self
+    }
+
+    /// Let on-demand solves run outside the iteration bounds (`--force`).
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Never solve on a cache miss; the query fails instead.
+    pub fn with_cache_only(mut self, cache_only: bool) -> Self {
+        self.cache_only = cache_only;
+        self
+    }
+
+    /// Use `range` instead of the preflop-derived villain range for postflop queries.
+    pub fn with_villain_range(mut self, range: Option<String>) -> Self {
+        self.villain_range = range;
+        self
+    }
+
+    /// Use `range` instead of the preflop-derived hero range for postflop queries.
+    pub fn with_hero_range(mut self, range: Option<String>) -> Self {
+        self.hero_range = range;
+        self
+    }
+
+    /// True when postflop queries solve with an overridden hero or villain range.
+    pub fn is_exploitative(&self) -> bool {
+        self.villain_range.is_some() || self.hero_range.is_some()
+    }
+
+    /// True when cache misses fail instead of solving (`with_cache_only`).
+    pub fn is_cache_only(&self) -> bool {
+        self.cache_only
+    }
+
     pub fn has_preflop(&self) -> bool {
         self.preflop.is_some()
     }
@@ -162,18 +667,20 @@ impl StrategyEngine {
                 let open_freq = spot.open_strategy[bucket];
                 let fold_freq = 1.0 - open_freq;
                 Some(StrategyResult {
-                    actions: vec!["RAISE 2.5bb".to_string(), "FOLD".to_string()],
+                    actions: vec![format!("RAISE {}bb", solution.sizing.open_size_bb), "FOLD".to_string()],
                     frequencies: vec![open_freq, fold_freq],
                     source: StrategySource::Cached,
+                    quality: None,
+                    showdown: None,
+                    evs: spot.evs.as_ref().map(|evs| evs_over_fold(evs.open[bucket]).to_vec()),
+                    simplified: None,
+                    villain: None,
                 })
             }
             Some(villain_pos) => {
-                let hero_order = preflop_open_order(hero);
-                let villain_order = preflop_open_order(villain_pos);
-
-                if hero_order > villain_order {
+                // Who opens depends on the solve: a straddling UTG acts last
+                if let Some(spot) = solution.find_spot(villain_pos, hero) {
                     // Villain opened, hero responds
-                    let spot = solution.find_spot(villain_pos, hero)?;
                     let threebet = spot.vs_open_3bet[bucket];
                     let call = spot.vs_open_call[bucket];
                     let fold = (1.0 - threebet - call).max(0.0);
@@ -185,6 +692,11 @@ impl StrategyEngine {
                         ],
                         frequencies: vec![threebet, call, fold],
                         source: StrategySource::Cached,
+                        quality: None,
+                        showdown: None,
+                        evs: spot.evs.as_ref().map(|evs| evs_over_fold(evs.vs_open[bucket]).to_vec()),
+                        simplified: None,
+                        villain: None,
                     })
                 } else {
                     // Hero opened, villain 3-bet
@@ -200,28 +712,35 @@ impl StrategyEngine {
                         ],
                         frequencies: vec![fourbet, call, fold],
                         source: StrategySource::Cached,
+                        quality: None,
+                        showdown: None,
+                        evs: spot.evs.as_ref().map(|evs| evs_over_fold(evs.vs_3bet[bucket]).to_vec()),
+                        simplified: None,
+                        villain: None,
                     })
                 }
             }
         }
     }
 
-    /// Query postflop strategy for a specific hand on a given board.
+    /// Query postflop strategy for a specific hand in `spot`.
     /// Will solve on-demand if no cached solution exists.
-    /// `action_path` is an optional sequence of actions taken so far on this street
-    /// (e.g., &["x", "b"] for "OOP checked, IP bet"). Empty means root node.
-    pub fn query_postflop(
-        &mut self,
-        hand: &str,
-        hero: Position,
-        villain: Position,
-        board: &str,
-        pot: f64,
-        stack: f64,
-        iterations: usize,
-        action_path: &[String],
-    ) -> Result<StrategyResult, String> {
+    pub fn query_postflop(&mut self, hand: &str, spot: &PostflopSpot, iterations: usize) -> Result<StrategyResult, String> {
+        let PostflopSpot { hero, villain, pot_type, board, pot, stack, line: action_path } = *spot;
+        // The river card is dealt like a board card
         let board_len = board.len();
+        let dealt = match &self.river_card {
+            Some(_) if board_len != 8 => return Err("--river-card needs a turn board (4 cards)".to_string()),
+            Some(card) => format!("{}{}", board, card),
+            None => board.to_string(),
+        };
+        if self.drill.is_some() && board_len != 8 {
+            return Err("--drill needs a turn board (4 cards)".to_string());
+        }
+        check_dealt_cards(hand, &dealt)?;
+        let hole = parse_board(hand).unwrap_or_default();
+        let board_cards = parse_board(&dealt).map_err(|e| e.to_string())?;
+        check_dead_cards(&self.dead_cards, &[&hole], &board_cards).map_err(|e| e.to_string())?;
         let hero_side = if hero.is_ip_vs(&villain) { "IP" } else { "OOP" };
 
         // Determine OOP/IP positions for cache key
@@ -231,24 +750,52 @@ impl StrategyEngine {
             (hero, villain)
         };
 
-        // Derive ranges from preflop solution
-        let (oop_range, ip_range) = self.derive_postflop_ranges(hero, villain)?;
-        let oop_str = oop_range.join(",");
-        let ip_str = ip_range.join(",");
+        // Derive ranges from preflop solution. They key the cache and are needed
+        // to solve on a miss; without them the seats' cached solve (e.g. a manual
+        // `gto solve --oop-pos/--ip-pos`) is still usable, and overriding both
+        // sides needs none.
+        let mut ranges = if self.hero_range.is_some() && self.villain_range.is_some() {
+            Ok((String::new(), String::new()))
+        } else {
+            self.derive_pot_type_ranges(hero, villain, pot_type)
+                .map(|(oop_range, ip_range)| (oop_range.join(","), ip_range.join(",")))
+        };
+        let mut oop_key = pot_type.seat_key(oop_pos.as_str());
+        let mut ip_key = pot_type.seat_key(ip_pos.as_str());
+
+        // Range overrides: swap in the given range and key the cache separately
+        let overrides = [(&self.hero_range, hero_side == "IP", "Hero"), (&self.villain_range, hero_side == "OOP", "Villain")];
+        for (range, is_ip, who) in overrides {
+            let Some(range) = range else { continue };
+            let (oop_str, ip_str) = ranges?;
+            ranges = Ok(apply_range_override(&oop_str, &ip_str, is_ip, range, board, who)?);
+            let key = if is_ip { &mut ip_key } else { &mut oop_key };
+            *key = exploit_position_key(key, range, board);
+        }
 
         match board_len {
-            6 => self.query_flop(hand, hero_side, board, &oop_str, &ip_str, pot, stack, iterations, oop_pos.as_str(), ip_pos.as_str(), action_path),
-            8 => self.query_turn(hand, hero_side, board, &oop_str, &ip_str, pot, stack, iterations, oop_pos.as_str(), ip_pos.as_str(), action_path),
-            10 => self.query_river(hand, hero_side, board, &oop_str, &ip_str, pot, stack, iterations, oop_pos.as_str(), ip_pos.as_str(), action_path),
+            6 => self.query_flop(hand, hero_side, board, &ranges, pot, stack, iterations, &oop_key, &ip_key, action_path),
+            8 => self.query_turn(hand, hero_side, board, &ranges, pot, stack, iterations, &oop_key, &ip_key, action_path),
+            10 => self.query_river(hand, hero_side, board, &ranges, pot, stack, iterations, &oop_key, &ip_key, action_path),
             _ => Err(format!("Invalid board length: {} chars (expected 6, 8, or 10)", board_len)),
         }
     }
 
-    /// Derive OOP and IP ranges for a postflop spot from preflop solution.
-    fn derive_postflop_ranges(
+    /// Derive OOP and IP ranges for a single raised pot from preflop solution.
+    pub fn derive_postflop_ranges(
+        &self,
+        hero: Position,
+        villain: Position,
+    ) -> Result<(Vec<String>, Vec<String>), String> {
+        self.derive_pot_type_ranges(hero, villain, PotType::Srp)
+    }
+
+    /// Derive OOP and IP ranges for a postflop spot in `pot_type` from preflop solution.
+    pub fn derive_pot_type_ranges(
         &self,
         hero: Position,
         villain: Position,
+        pot_type: PotType,
     ) -> Result<(Vec<String>, Vec<String>), String> {
         let solution = self.preflop.as_ref().ok_or_else(|| {
             "No preflop solution loaded. Run `gto solve preflop` first.".to_string()
@@ -268,11 +815,10 @@ impl StrategyEngine {
             .find_spot(opener, responder)
             .ok_or_else(|| format!("No preflop spot found for {} vs {}", opener, responder))?;
 
-        let opener_range = derive_opening_range(spot, RANGE_THRESHOLD);
-        let responder_range = derive_defending_range(spot, RANGE_THRESHOLD);
+        let (opener_range, responder_range) = derive_pot_ranges(spot, pot_type, RANGE_THRESHOLD);
 
         if opener_range.is_empty() || responder_range.is_empty() {
-            return Err("Derived ranges are empty".to_string());
+            return Err(format!("Derived {} ranges are empty", pot_type.as_str()));
         }
 
         // OOP = whoever acts first postflop
@@ -284,13 +830,107 @@ impl StrategyEngine {
         }
     }
 
-    fn query_flop(
+    /// True when `result` meets `--min-grade`; ungraded results always do.
+    fn accepts(&self, result: &StrategyResult) -> bool {
+        match (self.min_grade, &result.quality) {
+            (Some(min), Some(q)) => q.grade.meets(min),
+            _ => true,
+        }
+    }
+
+    /// Whether cached solves can answer: not with dead cards, which they
+    /// didn't deal around.
+    fn uses_cache(&self) -> bool {
+        self.dead_cards.is_empty()
+    }
+
+    fn dead_card_indices(&self) -> Vec<u8> {
+        self.dead_cards.iter().map(card_to_index).collect()
+    }
+
+    /// Error out of a cache miss when on-demand solves are off.
+    fn check_may_solve(&self, street: &str, board: &str) -> Result<(), String> {
+        if self.cache_only {
+            return Err(format!("No cached {} solve for {}", street, board));
+        }
+        Ok(())
+    }
+
+    /// `solution` as `--simplify`/`--purify` shows it.
+    fn shown<'a, S: Clone>(&self, solution: &'a S, simplified: impl Fn(&S, Simplify) -> S) -> Cow<'a, S> {
+        match self.simplify {
+            Some(simplify) => Cow::Owned(simplified(solution, simplify)),
+            None => Cow::Borrowed(solution),
+        }
+    }
+
+    /// A template answer rounded for `--simplify`/`--purify`. Templates are
+    /// bucketed, so their EV loss isn't estimated.
+    fn simplify_template(&self, mut result: StrategyResult) -> StrategyResult {
+        if let (Some(simplify), false) = (self.simplify, result.frequencies.is_empty()) {
+            simplify.apply(&mut result.frequencies);
+            result.simplified = Some(simplify.info(None));
+        }
+        result
+    }
+
+    /// Note that a cached answer is being re-solved for `--min-grade`.
+    fn report_resolve(&self, result: &StrategyResult) {
+        if let (Some(min), Some(q)) = (self.min_grade, &result.quality) {
+            eprintln!("  Cached answer graded {}, below --min-grade {}; re-solving", q.grade, min);
+        }
+    }
+
+    /// `result` from a fresh solve, or an error if even that misses `--min-grade`.
+    fn require_grade(&self, result: StrategyResult) -> Result<StrategyResult, String> {
+        match (self.min_grade, &result.quality) {
+            (Some(min), Some(q)) if !q.grade.meets(min) => {
+                let msg = format!(
+                    "Fresh solve graded {}, below --min-grade {}; raise --time or --iterations",
+                    q.describe(),
+                    min
+                );
+                crate::status::fail(crate::status::ExitCode::SolveFailed, &msg);
+                Err(msg)
+            }
+            _ => Ok(result),
+        }
+    }
+
+    /// `lookup_in_flop_solution`, with villain's view of hero's decision
+    /// for `--show-villain`. Only a specific hand (e.g. AhKs) blocks
+    /// villain's combos.
+    fn flop_answer(
         &self,
+        solution: &FlopSolution,
+        hand: &str,
+        hero_side: &str,
+        action_path: &[String],
+    ) -> Result<StrategyResult, String> {
+        let mut result = lookup_in_flop_solution(solution, hand, hero_side, action_path)?;
+        if !self.show_villain || result.source == StrategySource::NotInRange {
+            return Ok(result);
+        }
+        let node_id = match hero_line_node(action_path, hero_side, || build_tree(&solution.tree_config()).0)? {
+            Some(id) => id,
+            None => solution
+                .strategies
+                .iter()
+                .find(|s| s.player == hero_side)
+                .map(|s| s.node_id)
+                .ok_or("No strategy found for hero's side at root node")?,
+        };
+        let hero_cards = if hand.len() == 4 { parse_board(hand).unwrap_or_default() } else { Vec::new() };
+        result.villain = Some(solution.villain_view(node_id, &hero_cards)?);
+        Ok(result)
+    }
+
+    fn query_flop(
+        &mut self,
         hand: &str,
         hero_side: &str,
         board: &str,
-        oop_range: &str,
-        ip_range: &str,
+        ranges: &Result<(String, String), String>,
         pot: f64,
         stack: f64,
         iterations: usize,
@@ -299,19 +939,54 @@ impl StrategyEngine {
         action_path: &[String],
     ) -> Result<StrategyResult, String> {
         // Try cache first (with position info in key)
-        if let Some(solution) = FlopSolution::load_cache(board, oop_pos, ip_pos, pot, stack) {
-            return lookup_in_flop_solution(&solution, hand, hero_side, action_path);
+        let key = ranges_key(ranges);
+        let cached = self.uses_cache().then(|| FlopSolution::load_cache(board, oop_pos, ip_pos, pot, stack, key.as_deref()));
+        let cached = cached.flatten().filter(|s| s.meets_quality_floor() && !(self.refine && s.low_fidelity));
+        let missed = cached.is_none();
+        if let Some(solution) = cached {
+            let result = self.flop_answer(&self.shown(&solution, FlopSolution::simplified), hand, hero_side, action_path)?;
+            if self.accepts(&result) {
+                return Ok(result);
+            }
+            self.report_resolve(&result);
+        }
+
+        self.check_may_solve("flop", board)?;
+
+        // Nothing cached: answer from a quick solve, saved in the background
+        let (oop_range, ip_range) = ranges.as_ref().map_err(Clone::clone)?;
+        if missed && !self.refine {
+            eprintln!("  Quick-solving flop {} (approximate; --refine for a full solve)...", board);
+            let mut config = FlopSolverConfig::quick(board, oop_range, ip_range, pot, stack).map_err(|e| e.to_string())?;
+            config.dead_cards = self.dead_card_indices();
+            let mut solution = solve_flop(&config);
+            solution.oop_pos = oop_pos.to_string();
+            solution.ip_pos = ip_pos.to_string();
+            let result = self.flop_answer(&self.shown(&solution, FlopSolution::simplified), hand, hero_side, action_path)?;
+            if self.uses_cache() {
+                self.pending_saves.push(std::thread::spawn(move || solution.save_cache()));
+            }
+            if self.accepts(&result) {
+                return Ok(result);
+            }
+            self.report_resolve(&result);
         }
 
         // Solve on-demand
-        eprintln!("  Solving flop {} (this may take 1-4 min)...", board);
-        let config = FlopSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations)?;
+        let iterations = self.solve_iterations(iterations, FLOP_ITERATIONS);
+        FLOP_ITERATIONS.check(iterations, self.force)?;
+        eprintln!("  Solving flop {} ({})...", board, self.solve_length("1-4 min"));
+        let mut config = FlopSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations).map_err(|e| e.to_string())?;
+        config.dead_cards = self.dead_card_indices();
+        config.time_budget = self.time_budget;
         let mut solution = solve_flop(&config);
         solution.oop_pos = oop_pos.to_string();
         solution.ip_pos = ip_pos.to_string();
-        solution.save_cache();
+        if self.uses_cache() {
+            solution.save_cache();
+        }
 
-        lookup_in_flop_solution(&solution, hand, hero_side, action_path)
+        self.require_grade(solved_on_demand(self.flop_answer(&self.shown(&solution, FlopSolution::simplified), hand, hero_side, action_path)?))
     }
 
     fn query_turn(
@@ -319,8 +994,7 @@ impl StrategyEngine {
         hand: &str,
         hero_side: &str,
         board: &str,
-        oop_range: &str,
-        ip_range: &str,
+        ranges: &Result<(String, String), String>,
         pot: f64,
         stack: f64,
         iterations: usize,
@@ -328,33 +1002,155 @@ impl StrategyEngine {
         ip_pos: &str,
         action_path: &[String],
     ) -> Result<StrategyResult, String> {
+        if let Some(flop_line) = &self.drill {
+            return self.query_drill(hand, hero_side, board, ranges, pot, stack, iterations, oop_pos, ip_pos, flop_line, action_path);
+        }
+
         // 1. Check dedicated turn cache
-        if let Some(solution) = TurnSolution::load_cache(board, oop_pos, ip_pos, pot, stack) {
-            return lookup_in_turn_solution(&solution, hand, hero_side, action_path);
+        let key = ranges_key(ranges);
+        let cached = self.uses_cache().then(|| TurnSolution::load_cache(board, oop_pos, ip_pos, pot, stack, key.as_deref()));
+        let river_card = self.river_card.as_ref().map(card_to_index);
+        // A solve without river strategies can't answer for a river card
+        let usable = |s: &TurnSolution| s.meets_quality_floor() && (river_card.is_none() || !s.river_strategies.is_empty());
+        if let Some(solution) = cached.flatten().filter(usable) {
+            let result =
+                lookup_in_turn_solution(&self.shown(&solution, TurnSolution::simplified), hand, hero_side, action_path, river_card)?;
+            if self.accepts(&result) {
+                return Ok(result);
+            }
+            self.report_resolve(&result);
         }
 
         // 2. Check flop solution for embedded turn template strategies
         let flop_board = &board[..6];
-        if let Some(flop_sol) = FlopSolution::load_cache(flop_board, oop_pos, ip_pos, pot, stack) {
+        let flop_cached = (self.uses_cache() && river_card.is_none()).then(|| FlopSolution::load_cache(flop_board, oop_pos, ip_pos, pot, stack, key.as_deref()));
+        if let Some(flop_sol) = flop_cached.flatten().filter(|s| s.meets_quality_floor() && !(self.refine && s.low_fidelity)) {
             if !flop_sol.turn_strategies.is_empty() {
+                // Without a flop line the turn is reached checked through,
+                // at the flop's pot and stack
                 if let Ok(result) = lookup_in_template_strategy(
                     &flop_sol, hand, hero_side, board, &flop_sol.turn_strategies,
-                    &flop_sol.turn_tree_edges, action_path,
-                ) {
-                    return Ok(result);
+                    &turn_template_config(), flop_sol.starting_pot, flop_sol.effective_stack, action_path,
+                ).map(|r| self.simplify_template(r)) {
+                    if self.accepts(&result) {
+                        return Ok(result);
+                    }
+                    self.report_resolve(&result);
                 }
             }
         }
 
         // 3. Solve on-demand
-        eprintln!("  Solving turn {} (this may take 15-45s)...", board);
-        let config = TurnSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations)?;
-        let mut solution = solve_turn(&config);
+        self.check_may_solve("turn", board)?;
+        let (oop_range, ip_range) = ranges.as_ref().map_err(Clone::clone)?;
+        let iterations = self.solve_iterations(iterations, TURN_ITERATIONS);
+        TURN_ITERATIONS.check(iterations, self.force)?;
+        eprintln!("  Solving turn {} ({})...", board, self.solve_length("15-45s"));
+        let mut config = TurnSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations).map_err(|e| e.to_string())?;
+        config.dead_cards = self.dead_card_indices();
+        config.time_budget = self.time_budget;
+        let mut solution = try_solve_turn(&config).map_err(|e| e.to_string())?;
         solution.oop_pos = oop_pos.to_string();
         solution.ip_pos = ip_pos.to_string();
-        solution.save_cache();
+        if self.uses_cache() {
+            solution.save_cache();
+        }
 
-        lookup_in_turn_solution(&solution, hand, hero_side, action_path)
+        self.require_grade(solved_on_demand(lookup_in_turn_solution(
+            &self.shown(&solution, TurnSolution::simplified),
+            hand,
+            hero_side,
+            action_path,
+            river_card,
+        )?))
+    }
+
+    /// Answer a turn query from a drill-down solve: the cached flop solve
+    /// plays both ranges along `flop_line`, and the turn and river are solved
+    /// combo by combo from the reach that leaves (cached per flop solve, line
+    /// and turn).
+    #[allow(clippy::too_many_arguments)]
+    fn query_drill(
+        &self,
+        hand: &str,
+        hero_side: &str,
+        board: &str,
+        ranges: &Result<(String, String), String>,
+        pot: f64,
+        stack: f64,
+        iterations: usize,
+        oop_pos: &str,
+        ip_pos: &str,
+        flop_line: &[String],
+        action_path: &[String],
+    ) -> Result<StrategyResult, String> {
+        if !self.uses_cache() {
+            return Err("--drill starts from the cached flop solve, which can't deal around --dead cards".to_string());
+        }
+        let flop_board = &board[..6];
+        let flop = FlopSolution::load_cache(flop_board, oop_pos, ip_pos, pot, stack, ranges_key(ranges).as_deref())
+            .filter(|s| s.meets_quality_floor())
+            .ok_or_else(|| format!("No cached flop solve for {}; query or solve the flop first", flop_board))?;
+        let reach = flop.line_reach(flop_line)?;
+        let flop_file = flop.cache_path().file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let key = drill_key(&flop_file, flop.iterations, flop.exploitability, &reach.line);
+        let path = TurnSolution::drill_cache_path(board, oop_pos, ip_pos, &key);
+        let river_card = self.river_card.as_ref().map(card_to_index);
+
+        let cached = TurnSolution::load_drill(&path);
+        let fresh = cached.is_none();
+        let solution = match cached {
+            Some(solution) => solution,
+            None => {
+                // Without a drill-down solve, the flop solve's turn template
+                // answers at the line's pot
+                if self.cache_only && river_card.is_none() && !flop.turn_strategies.is_empty() {
+                    if let Ok(result) = lookup_in_template_strategy(
+                        &flop, hand, hero_side, board, &flop.turn_strategies,
+                        &turn_template_config(), reach.pot, reach.effective_stack, action_path,
+                    ) {
+                        return Ok(self.simplify_template(result));
+                    }
+                }
+                let iterations = self.solve_iterations(iterations, TURN_ITERATIONS);
+                TURN_ITERATIONS.check(iterations, self.force)?;
+                let weighted = |combos: &[String], reach: &[f64]| -> Vec<(String, f64)> {
+                    combos.iter().cloned().zip(reach.iter().copied()).collect()
+                };
+                let mut config = TurnSolverConfig::from_combo_reach(
+                    board,
+                    &weighted(&flop.oop_combos, &reach.oop),
+                    &weighted(&flop.ip_combos, &reach.ip),
+                    reach.pot,
+                    reach.effective_stack,
+                    iterations,
+                )
+                .map_err(|e| e.to_string())?;
+                config.time_budget = self.time_budget;
+                eprintln!(
+                    "  Drilling into turn {} after [{}] ({})...",
+                    board,
+                    reach.line.join(", "),
+                    self.solve_length("15-45s")
+                );
+                let mut solution = try_solve_turn(&config).map_err(|e| e.to_string())?;
+                solution.oop_pos = oop_pos.to_string();
+                solution.ip_pos = ip_pos.to_string();
+                solution.save_drill(&path);
+                solution
+            }
+        };
+
+        let mut result =
+            lookup_in_turn_solution(&self.shown(&solution, TurnSolution::simplified), hand, hero_side, action_path, river_card)?;
+        if result.source == StrategySource::Cached {
+            result.source = StrategySource::Drilled;
+        }
+        if fresh {
+            self.require_grade(result)
+        } else {
+            Ok(result)
+        }
     }
 
     fn query_river(
@@ -362,8 +1158,7 @@ impl StrategyEngine {
         hand: &str,
         hero_side: &str,
         board: &str,
-        oop_range: &str,
-        ip_range: &str,
+        ranges: &Result<(String, String), String>,
         pot: f64,
         stack: f64,
         iterations: usize,
@@ -372,32 +1167,60 @@ impl StrategyEngine {
         action_path: &[String],
     ) -> Result<StrategyResult, String> {
         // 1. Check dedicated river cache
-        if let Some(solution) = RiverSolution::load_cache(board, oop_pos, ip_pos, pot, stack) {
-            return lookup_in_river_solution(&solution, hand, hero_side, action_path);
+        let key = ranges_key(ranges);
+        let cached = self.uses_cache().then(|| RiverSolution::load_cache(board, oop_pos, ip_pos, pot, stack, key.as_deref()));
+        if let Some(solution) = cached.flatten().filter(|s| s.meets_quality_floor()) {
+            let result = lookup_in_river_solution(&self.shown(&solution, RiverSolution::simplified), hand, hero_side, action_path)?;
+            if self.accepts(&result) {
+                return Ok(result);
+            }
+            self.report_resolve(&result);
         }
 
         // 2. Check flop solution for embedded river template strategies
         let flop_board = &board[..6];
-        if let Some(flop_sol) = FlopSolution::load_cache(flop_board, oop_pos, ip_pos, pot, stack) {
+        let flop_cached = self.uses_cache().then(|| FlopSolution::load_cache(flop_board, oop_pos, ip_pos, pot, stack, key.as_deref()));
+        if let Some(flop_sol) = flop_cached.flatten().filter(|s| s.meets_quality_floor() && !(self.refine && s.low_fidelity)) {
             if !flop_sol.river_strategies.is_empty() {
+                // The river is reached checked through on the flop and turn
                 if let Ok(result) = lookup_in_template_strategy(
                     &flop_sol, hand, hero_side, board, &flop_sol.river_strategies,
-                    &flop_sol.river_tree_edges, action_path,
-                ) {
-                    return Ok(result);
+                    &flop_sol.river_template_config(), flop_sol.starting_pot, flop_sol.effective_stack, action_path,
+                ).map(|r| self.simplify_template(r)) {
+                    if self.accepts(&result) {
+                        return Ok(result);
+                    }
+                    self.report_resolve(&result);
                 }
             }
         }
 
         // 3. Solve on-demand
-        eprintln!("  Solving river {} (this may take 1-5s)...", board);
-        let config = RiverSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations)?;
-        let mut solution = solve_river(&config);
+        self.check_may_solve("river", board)?;
+        let (oop_range, ip_range) = ranges.as_ref().map_err(Clone::clone)?;
+        let iterations = self.solve_iterations(iterations, RIVER_ITERATIONS);
+        RIVER_ITERATIONS.check(iterations, self.force)?;
+        eprintln!("  Solving river {} ({})...", board, self.solve_length("1-5s"));
+        let mut config = RiverSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations).map_err(|e| e.to_string())?;
+        config.dead_cards = self.dead_card_indices();
+        config.time_budget = self.time_budget;
+        let mut solution = try_solve_river(&config).map_err(|e| e.to_string())?;
         solution.oop_pos = oop_pos.to_string();
         solution.ip_pos = ip_pos.to_string();
-        solution.save_cache();
+        if self.uses_cache() {
+            solution.save_cache();
+        }
 
-        lookup_in_river_solution(&solution, hand, hero_side, action_path)
+        self.require_grade(solved_on_demand(lookup_in_river_solution(&self.shown(&solution, RiverSolution::simplified), hand, hero_side, action_path)?))
+    }
+}
+
+impl Drop for StrategyEngine {
+    /// Finish writing background quick solves so the process doesn't exit mid-write.
+    fn drop(&mut self) {
+        for handle in self.pending_saves.drain(..) {
+            handle.join().ok();
+        }
     }
 }
 
@@ -405,58 +1228,108 @@ impl StrategyEngine {
 // Action path navigation
 // ---------------------------------------------------------------------------
 
-/// Navigate tree edges following an action path. Returns the target node_id.
-///
-/// Action codes: "x"/"check" → Check, "b"/"bet" → Bet, "c"/"call" → Call,
-/// "r"/"raise" → Raise, "f"/"fold" → Fold.
-/// Can also match exact action labels like "Bet 2.0" or "Check".
-fn navigate_to_node(edges: &[TreeEdge], action_path: &[String]) -> Option<u16> {
-    if action_path.is_empty() {
-        return None; // no navigation needed, use root
+/// The node `line` leads to, which must be hero's decision; `None` for an
+/// empty line, where lookups use hero's first node. `tree` builds the
+/// solved tree, and is only called for a non-empty line.
+fn hero_line_node(
+    line: &[String],
+    hero_side: &str,
+    tree: impl FnOnce() -> TreeNode,
+) -> Result<Option<u16>, String> {
+    if line.is_empty() {
+        return Ok(None);
     }
+    let root = tree();
+    hero_decision(walk_line(&root, line)?, line, hero_side).map(Some)
+}
 
-    // Find root node (smallest from_id that doesn't appear as any to_id)
-    let to_ids: std::collections::HashSet<u16> = edges.iter().map(|e| e.to).collect();
-    let root_id = edges
-        .iter()
-        .map(|e| e.from)
-        .find(|id| !to_ids.contains(id))?;
-
-    let mut current = root_id;
-    for action_code in action_path {
-        let matching_edge = edges.iter().find(|e| {
-            e.from == current && action_matches(&e.action, action_code)
-        });
-        match matching_edge {
-            Some(edge) => current = edge.to,
-            None => return None, // action path leads to terminal or invalid
-        }
+/// The id of `node`, where `line` ends, if it's hero's decision.
+fn hero_decision(node: &TreeNode, line: &[String], hero_side: &str) -> Result<u16, String> {
+    let TreeNode::Action { node_id, player, .. } = node else {
+        return Err(format!("[{}] doesn't end at a decision", line.join(", ")));
+    };
+    let to_act = player_label(*player);
+    if to_act != hero_side {
+        return Err(format!(
+            "[{}] ends on villain's turn ({} to act; legal: {}). Add villain's action to reach {}'s decision",
+            line.join(", "),
+            to_act,
+            legal_actions(node),
+            hero_side
+        ));
     }
-    Some(current)
+    Ok(*node_id)
 }
 
-/// Check if a tree action label matches a user action code.
-fn action_matches(action_label: &str, code: &str) -> bool {
-    let lower = code.to_lowercase();
-    let label_lower = action_label.to_lowercase();
+/// Range-wide action frequencies at a flop node.
+#[derive(Debug, Clone)]
+pub struct LineFrequencies {
+    /// Tree action labels actually followed to reach the node.
+    pub line: Vec<String>,
+    /// "OOP" or "IP".
+    pub player: String,
+    pub actions: Vec<String>,
+    /// Acting range's frequencies, each combo weighted by how often it takes `line`.
+    pub frequencies: Vec<f64>,
+}
 
-    // Exact match
-    if label_lower == lower {
-        return true;
+/// Follow `line` from the flop root and aggregate the acting range's strategy.
+///
+/// Steps name actions the way `walk_line` takes them (e.g. ["check",
+/// "bet33", "raise"]); the acting range at each step is weighted by how
+/// often it takes the step.
+pub fn flop_line_frequencies(solution: &FlopSolution, line: &[String]) -> Result<LineFrequencies, String> {
+    let (tree, _) = build_tree(&solution.tree_config());
+    let mut oop_reach = vec![1.0; solution.oop_combos.len()];
+    let mut ip_reach = vec![1.0; solution.ip_combos.len()];
+    let mut followed: Vec<String> = Vec::new();
+
+    let node_strategy = |node: u16| {
+        solution
+            .strategies
+            .iter()
+            .find(|s| s.node_id == node)
+            .ok_or_else(|| format!("No strategy stored for node {}", node))
+    };
+
+    let mut node = &tree;
+    for step in line {
+        let TreeNode::Action { node_id, player, actions, children, .. } = node else {
+            return Err(format!("[{}] ends the flop action", followed.join(", ")));
+        };
+        let a = action_index(node, step).map_err(|why| {
+            format!("{} for {} after [{}]", why, player_label(*player), followed.join(", "))
+        })?;
+        let strat = node_strategy(*node_id)?;
+        let reach = if *player == Player::OOP { &mut oop_reach } else { &mut ip_reach };
+        for (r, freqs) in reach.iter_mut().zip(&strat.frequencies) {
+            *r *= freqs[a];
+        }
+        followed.push(actions[a].label());
+        node = &children[a];
     }
 
-    match lower.as_str() {
-        "x" | "check" => label_lower.starts_with("check"),
-        "f" | "fold" => label_lower.starts_with("fold"),
-        "c" | "call" => label_lower.starts_with("call"),
-        "b" | "bet" => label_lower.starts_with("bet"),
-        "r" | "raise" => label_lower.starts_with("raise"),
-        _ => {
-            // Try prefix match for specific sizes: "b33" matches "Bet 2.0" (33% pot)
-            // or partial label match: "bet 2" matches "Bet 2.0"
-            label_lower.starts_with(&lower)
+    let TreeNode::Action { node_id, player, .. } = node else {
+        return Err(format!("[{}] ends the flop action", followed.join(", ")));
+    };
+    let strat = node_strategy(*node_id)?;
+    let reach = if *player == Player::OOP { &oop_reach } else { &ip_reach };
+    let total: f64 = reach.iter().sum();
+    if total <= 1e-9 {
+        return Err(format!("The solver never reaches [{}]", followed.join(", ")));
+    }
+    let mut frequencies = vec![0.0; strat.actions.len()];
+    for (r, freqs) in reach.iter().zip(&strat.frequencies) {
+        for (f, p) in frequencies.iter_mut().zip(freqs) {
+            *f += r * p / total;
         }
     }
+    Ok(LineFrequencies {
+        line: followed,
+        player: strat.player.clone(),
+        actions: strat.actions.clone(),
+        frequencies,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -469,193 +1342,317 @@ fn lookup_in_flop_solution(
     hero_side: &str,
     action_path: &[String],
 ) -> Result<StrategyResult, String> {
-    let combos = if hero_side == "OOP" {
-        &solution.oop_combos
-    } else {
-        &solution.ip_combos
-    };
-
-    let combo_idx = match find_combo_index(hand, combos) {
-        Some(idx) => idx,
-        None => {
-            return Ok(StrategyResult {
-                actions: vec![],
-                frequencies: vec![],
-                source: StrategySource::NotInRange,
-            });
-        }
-    };
-
-    // Navigate to target node if action path provided
-    let target_node = if action_path.is_empty() {
-        None
-    } else {
-        navigate_to_node(&solution.flop_tree_edges, action_path)
-    };
+    let target_node = hero_line_node(action_path, hero_side, || {
+        build_tree(&solution.tree_config()).0
+    })?;
+    let combos = if hero_side == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+    let combo_idxs = hand_combo_indices(combos, hand);
+    if combo_idxs.is_empty() {
+        return Ok(StrategyResult {
+            actions: vec![],
+            frequencies: vec![],
+            source: StrategySource::NotInRange,
+            quality: None,
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
+        });
+    }
 
     for strat in &solution.strategies {
         let node_match = match target_node {
             Some(nid) => strat.node_id == nid,
             None => strat.player == hero_side, // root: first node matching hero's side
         };
-        if node_match && combo_idx < strat.frequencies.len() {
+        if node_match && combo_idxs.iter().all(|&i| i < strat.frequencies.len()) {
+            let visits = solution.iterations as f64 * flop_node_reach(solution, strat.node_id);
             return Ok(StrategyResult {
                 actions: strat.actions.clone(),
-                frequencies: strat.frequencies[combo_idx].clone(),
-                source: StrategySource::Cached,
+                frequencies: average_rows(&strat.frequencies, &combo_idxs),
+                source: flop_source(solution),
+                quality: Some(AnswerQuality::at_node(&solution.solve_quality(), visits)),
+                showdown: None,
+                evs: combo_idxs
+                    .iter()
+                    .all(|&i| i < strat.evs.len())
+                    .then(|| average_rows(&strat.evs, &combo_idxs)),
+                simplified: solution.simplified,
+                villain: None,
             });
         }
     }
 
-    Err(if action_path.is_empty() {
-        "No strategy found for hero's side at root node".to_string()
-    } else {
-        format!("Could not navigate action path: {:?}", action_path)
+    Err(match target_node {
+        None => "No strategy found for hero's side at root node".to_string(),
+        Some(nid) => format!("No strategy stored for node {}", nid),
     })
 }
 
+/// With `river_card`, `action_path` closes the turn and the answer comes from
+/// the river subtree the card deals (see `walk_line_to_river`).
 fn lookup_in_turn_solution(
     solution: &TurnSolution,
     hand: &str,
     hero_side: &str,
-    _action_path: &[String],
+    action_path: &[String],
+    river_card: Option<u8>,
 ) -> Result<StrategyResult, String> {
-    let combos = if hero_side == "OOP" {
-        &solution.oop_combos
-    } else {
-        &solution.ip_combos
-    };
-
-    let combo_idx = match find_combo_index(hand, combos) {
-        Some(idx) => idx,
-        None => {
-            return Ok(StrategyResult {
-                actions: vec![],
-                frequencies: vec![],
-                source: StrategySource::NotInRange,
-            });
+    let tree = || build_turn_tree(&solution.tree_config()).0;
+    let (target_node, strategies, river) = match river_card {
+        None => (hero_line_node(action_path, hero_side, tree)?, solution.strategies.iter().collect(), None),
+        Some(card) => {
+            let root = tree();
+            let node = hero_decision(walk_line_to_river(&root, action_path, card)?, action_path, hero_side)?;
+            let card = index_to_card(card).to_string();
+            let strategies: Vec<&TurnNodeStrategy> =
+                solution.river_subtrees(&card).flat_map(|r| &r.strategies).collect();
+            (Some(node), strategies, Some(card.to_lowercase()))
         }
     };
+    let combos = if hero_side == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+    let mut combo_idxs = hand_combo_indices(combos, hand);
+    // Combos holding the river card never reach its subtree
+    if let Some(card) = &river {
+        combo_idxs.retain(|&i| !combos[i].to_lowercase().contains(card.as_str()));
+    }
+    if combo_idxs.is_empty() {
+        return Ok(StrategyResult {
+            actions: vec![],
+            frequencies: vec![],
+            source: StrategySource::NotInRange,
+            quality: None,
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
+        });
+    }
 
-    // TurnSolution doesn't store tree edges yet — return root node
-    for strat in &solution.strategies {
-        if strat.player == hero_side && combo_idx < strat.frequencies.len() {
+    for strat in strategies {
+        let node_match = match target_node {
+            Some(nid) => strat.node_id == nid,
+            None => strat.player == hero_side,
+        };
+        if node_match && combo_idxs.iter().all(|&i| i < strat.frequencies.len()) {
+            // Reach below the root isn't stored; count every iteration as a visit
+            let visits = solution.iterations as f64;
             return Ok(StrategyResult {
                 actions: strat.actions.clone(),
-                frequencies: strat.frequencies[combo_idx].clone(),
+                frequencies: average_rows(&strat.frequencies, &combo_idxs),
                 source: StrategySource::Cached,
+                quality: Some(AnswerQuality::at_node(&solution.solve_quality(), visits)),
+                // Outcomes are stored per combo, from the root only
+                showdown: match combo_idxs[..] {
+                    [i] if target_node.is_none() => solution.showdown_outcome(hero_side, i),
+                    _ => None,
+                },
+                evs: mean_action_evs(solution.action_evs(strat.node_id, hero_player(hero_side), &combo_idxs)),
+                simplified: solution.simplified,
+                villain: None,
             });
         }
     }
 
-    Err("No strategy found for hero's side at root node".to_string())
+    Err(match target_node {
+        None => "No strategy found for hero's side at root node".to_string(),
+        Some(nid) => format!("No strategy stored for node {}", nid),
+    })
 }
 
 fn lookup_in_river_solution(
     solution: &RiverSolution,
     hand: &str,
     hero_side: &str,
-    _action_path: &[String],
+    action_path: &[String],
 ) -> Result<StrategyResult, String> {
-    let combos = if hero_side == "OOP" {
-        &solution.oop_combos
-    } else {
-        &solution.ip_combos
-    };
-
-    let combo_idx = match find_combo_index(hand, combos) {
-        Some(idx) => idx,
-        None => {
-            return Ok(StrategyResult {
-                actions: vec![],
-                frequencies: vec![],
-                source: StrategySource::NotInRange,
-            });
-        }
-    };
+    let target_node = hero_line_node(action_path, hero_side, || {
+        build_tree(&solution.tree_config()).0
+    })?;
+    let combos = if hero_side == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+    let combo_idxs = hand_combo_indices(combos, hand);
+    if combo_idxs.is_empty() {
+        return Ok(StrategyResult {
+            actions: vec![],
+            frequencies: vec![],
+            source: StrategySource::NotInRange,
+            quality: None,
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
+        });
+    }
 
-    // RiverSolution doesn't store tree edges yet — return root node
     for strat in &solution.strategies {
-        if strat.player == hero_side && combo_idx < strat.frequencies.len() {
+        let node_match = match target_node {
+            Some(nid) => strat.node_id == nid,
+            None => strat.player == hero_side,
+        };
+        if node_match && combo_idxs.iter().all(|&i| i < strat.frequencies.len()) {
+            // Reach below the root isn't stored; count every iteration as a visit
+            let visits = solution.iterations as f64;
             return Ok(StrategyResult {
                 actions: strat.actions.clone(),
-                frequencies: strat.frequencies[combo_idx].clone(),
+                frequencies: average_rows(&strat.frequencies, &combo_idxs),
                 source: StrategySource::Cached,
+                quality: Some(AnswerQuality::at_node(&solution.solve_quality(), visits)),
+                // Outcomes are stored per combo, from the root only
+                showdown: match combo_idxs[..] {
+                    [i] if target_node.is_none() => solution.showdown_outcome(hero_side, i),
+                    _ => None,
+                },
+                evs: mean_action_evs(solution.action_evs(strat.node_id, hero_player(hero_side), &combo_idxs)),
+                simplified: solution.simplified,
+                villain: None,
             });
         }
     }
 
-    Err("No strategy found for hero's side at root node".to_string())
+    Err(match target_node {
+        None => "No strategy found for hero's side at root node".to_string(),
+        Some(nid) => format!("No strategy stored for node {}", nid),
+    })
+}
+
+/// Hero's EV per action, averaged over hero's combos: per-combo rows from an
+/// `action_evs` evaluation of the stored strategies, which river and turn
+/// solves don't store EVs for.
+fn mean_action_evs(rows: Option<Vec<Vec<f64>>>) -> Option<Vec<f64>> {
+    let rows = rows.filter(|rows| !rows.is_empty())?;
+    Some(average_rows(&rows, &(0..rows.len()).collect::<Vec<_>>()))
+}
+
+/// The solver's side for `hero_side` ("OOP"/"IP").
+fn hero_player(hero_side: &str) -> Player {
+    if hero_side == "OOP" {
+        Player::OOP
+    } else {
+        Player::IP
+    }
+}
+
+/// Where an answer from `solution` comes from.
+fn flop_source(solution: &FlopSolution) -> StrategySource {
+    if solution.low_fidelity {
+        StrategySource::QuickSolve
+    } else {
+        StrategySource::Cached
+    }
+}
+
+/// `result`, looked up in a solution solved for this query.
+fn solved_on_demand(mut result: StrategyResult) -> StrategyResult {
+    if result.source == StrategySource::Cached {
+        result.source = StrategySource::SolvedOnDemand;
+    }
+    result
+}
+
+/// Mean of `rows` at `indices`: a canonical hand's average over its combos,
+/// or a single combo's row as is.
+fn average_rows(rows: &[Vec<f64>], indices: &[usize]) -> Vec<f64> {
+    let mut mean = vec![0.0; rows[indices[0]].len()];
+    for &i in indices {
+        for (m, v) in mean.iter_mut().zip(&rows[i]) {
+            *m += v / indices.len() as f64;
+        }
+    }
+    mean
 }
 
 /// Look up a hand's strategy from template bucket strategies embedded in a flop solution.
+/// `pot` and `stack` are the pot and stack behind when the template's street
+/// began, after the flop line that led there; the unit-pot sizes are shown
+/// scaled to them.
 ///
 /// 1. Parse the full board to card indices
 /// 2. Find the hand combo in the flop solution's combo list
 /// 3. Compute the hand's bucket on this specific board
 /// 4. Look up the bucket's strategy in the template strategies
+#[allow(clippy::too_many_arguments)]
 fn lookup_in_template_strategy(
     flop_sol: &FlopSolution,
     hand: &str,
     hero_side: &str,
     board: &str,
     template_strategies: &[TemplateBucketStrategy],
-    tree_edges: &[TreeEdge],
+    template: &TreeConfig,
+    pot: f64,
+    stack: f64,
     action_path: &[String],
 ) -> Result<StrategyResult, String> {
+    let target_node = hero_line_node(action_path, hero_side, || build_tree(template).0)?;
     if flop_sol.num_buckets == 0 {
         return Err("No bucket info in flop solution".to_string());
     }
 
-    // Parse hand to card indices
-    let hand_cards = parse_board(hand).map_err(|e| format!("{}", e))?;
-    if hand_cards.len() != 2 {
-        return Err("Hand must be exactly 2 cards".to_string());
-    }
-    let h0 = card_to_index(&hand_cards[0]);
-    let h1 = card_to_index(&hand_cards[1]);
+    // Parse board to card indices
+    let board_cards = parse_board(board).map_err(|e| format!("{}", e))?;
+    let board_indices: Vec<u8> = board_cards.iter().map(|c| card_to_index(c)).collect();
 
-    // Check the hand is in the flop solution's range
+    // The hand's combos in the flop solution's range, less any the later cards block
     let combos = if hero_side == "OOP" {
         &flop_sol.oop_combos
     } else {
         &flop_sol.ip_combos
     };
-    if find_combo_index(hand, combos).is_none() {
+    let hand_cards: Vec<(u8, u8)> = hand_combo_indices(combos, hand)
+        .into_iter()
+        .filter_map(|i| {
+            let cards = parse_board(&combos[i]).ok()?;
+            let (c0, c1) = (card_to_index(&cards[0]), card_to_index(&cards[1]));
+            (!board_indices.contains(&c0) && !board_indices.contains(&c1)).then_some((c0, c1))
+        })
+        .collect();
+    if hand_cards.is_empty() {
         return Ok(StrategyResult {
             actions: vec![],
             frequencies: vec![],
             source: StrategySource::NotInRange,
+            quality: None,
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
         });
     }
 
-    // Parse board to card indices
-    let board_cards = parse_board(board).map_err(|e| format!("{}", e))?;
-    let board_indices: Vec<u8> = board_cards.iter().map(|c| card_to_index(c)).collect();
-
-    // Compute bucket for this hand on this board
+    // Compute each combo's bucket on this board
     // num_samples: 200 for turn (4 cards), 0 for river (5 cards — exact equity)
     let num_samples = if board_indices.len() == 4 { 200 } else { 0 };
-    let buckets = assign_buckets(&[(h0, h1)], &board_indices, flop_sol.num_buckets, num_samples);
-    let bucket = buckets[0] as usize;
-
-    // Navigate to target node if action path provided
-    let target_node = if action_path.is_empty() {
-        None
-    } else {
-        navigate_to_node(tree_edges, action_path)
-    };
+    let buckets: Vec<usize> = flop_sol
+        .bucketing
+        .assign(&hand_cards, &board_indices, flop_sol.num_buckets, num_samples, &mut rand::thread_rng())
+        .into_iter()
+        .map(|b| b as usize)
+        .collect();
 
     for strat in template_strategies {
         let node_match = match target_node {
             Some(nid) => strat.node_id == nid,
             None => strat.player == hero_side,
         };
-        if node_match && bucket < strat.frequencies.len() {
+        if node_match {
+            // Buckets from a different count would index the wrong rows
+            if strat.frequencies.len() != flop_sol.num_buckets {
+                return Err(format!(
+                    "Template strategy has {} buckets, flop solution {}",
+                    strat.frequencies.len(),
+                    flop_sol.num_buckets
+                ));
+            }
+            // Template reach isn't stored; count every iteration as a visit
+            let visits = flop_sol.iterations as f64;
             return Ok(StrategyResult {
-                actions: strat.actions.clone(),
-                frequencies: strat.frequencies[bucket].clone(),
-                source: StrategySource::Cached,
+                actions: scaled_template_actions(template, strat, pot, stack),
+                frequencies: average_rows(&strat.frequencies, &buckets),
+                source: StrategySource::DerivedFromFlop,
+                quality: Some(AnswerQuality::at_node(&flop_sol.solve_quality(), visits)),
+                showdown: None,
+                evs: None,
+                simplified: None,
+                villain: None,
             });
         }
     }
@@ -663,6 +1660,28 @@ fn lookup_in_template_strategy(
     Err("No template strategy found for hero's side".to_string())
 }
 
+/// `strat`'s action labels with the template's unit-pot sizes scaled to
+/// `pot` (capped at `stack`), or the stored labels if the template node
+/// can't be found.
+fn scaled_template_actions(template: &TreeConfig, strat: &TemplateBucketStrategy, pot: f64, stack: f64) -> Vec<String> {
+    let (tree, _) = build_tree(template);
+    match tree.find_action(strat.node_id) {
+        Some(TreeNode::Action { actions, .. }) if actions.len() == strat.actions.len() => actions
+            .iter()
+            .map(|action| {
+                let scaled = match *action {
+                    Action::Bet(amt) => Action::Bet((amt * pot).min(stack)),
+                    Action::Call(amt) => Action::Call((amt * pot).min(stack)),
+                    Action::Raise(amt) => Action::Raise((amt * pot).min(stack)),
+                    other => other,
+                };
+                scaled.label()
+            })
+            .collect(),
+        _ => strat.actions.clone(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -678,43 +1697,55 @@ fn preflop_open_order(pos: Position) -> usize {
     }
 }
 
-/// Format a hand string with unicode suit symbols for display.
+/// Format a hand string with suit symbols for display (see `SuitStyle`).
 /// "AhQd" -> "A♥Q♦"
 pub fn pretty_hand(hand: &str) -> String {
+    pretty_hand_styled(hand, suit_style())
+}
+
+pub fn pretty_hand_styled(hand: &str, style: SuitStyle) -> String {
     if hand.len() != 4 {
         return hand.to_string();
     }
     let chars: Vec<char> = hand.chars().collect();
     let r1 = chars[0];
-    let s1 = suit_symbol(chars[1]);
+    let s1 = suit_symbol(chars[1], style);
     let r2 = chars[2];
-    let s2 = suit_symbol(chars[3]);
+    let s2 = suit_symbol(chars[3], style);
     format!("{}{}{}{}", r1, s1, r2, s2)
 }
 
-/// Format a board string with unicode suit symbols for display.
+/// Format a board string with suit symbols for display (see `SuitStyle`).
 /// "Ks9d4c" -> "K♠ 9♦ 4♣"
 pub fn pretty_board(board: &str) -> String {
+    pretty_board_styled(board, suit_style())
+}
+
+pub fn pretty_board_styled(board: &str, style: SuitStyle) -> String {
     let chars: Vec<char> = board.chars().collect();
     let mut result = Vec::new();
     let mut i = 0;
     while i + 1 < chars.len() {
         let rank = chars[i];
-        let suit = suit_symbol(chars[i + 1]);
+        let suit = suit_symbol(chars[i + 1], style);
         result.push(format!("{}{}", rank, suit));
         i += 2;
     }
     result.join(" ")
 }
 
-fn suit_symbol(c: char) -> &'static str {
-    match c {
-        's' => "\u{2660}",
-        'h' => "\u{2665}",
-        'd' => "\u{2666}",
-        'c' => "\u{2663}",
-        _ => "?",
+fn suit_symbol(c: char, style: SuitStyle) -> &'static str {
+    Suit::from_char(c).map(|s| s.render(style)).unwrap_or("?")
+}
+
+/// Error naming any card the hand and board share, or the board repeats. A
+/// canonical hand ("AKs") has no specific cards to collide.
+pub fn check_dealt_cards(hand: &str, board: &str) -> Result<(), String> {
+    let mut cards = parse_board(board).map_err(|e| e.to_string())?;
+    if let Ok(hole) = parse_board(hand) {
+        cards.extend(hole);
     }
+    check_distinct(&cards).map_err(|e| e.to_string())
 }
 
 /// Detect the street from board string length.
@@ -752,13 +1783,129 @@ pub fn format_strategy(result: &StrategyResult) -> String {
         .map(|(action, freq)| format!("{} ({:.0}%)", action, freq * 100.0))
         .collect();
 
-    if parts.is_empty() {
+    let strategy = if parts.is_empty() {
         "No significant actions".to_string()
     } else {
         format!("\u{2192} {}", parts.join(", "))
+    };
+    match &result.quality {
+        Some(q) => format!(
+            "{}  [{}, {} iterations]",
+            strategy,
+            result.source.label(),
+            format_count(q.solve.iterations as f64)
+        ),
+        None => format!("{}  [{}]", strategy, result.source.label()),
     }
 }
 
+/// "EV (starting pots): Check +0.41, Bet 3.3 +0.47 | overall +0.45"
+pub fn format_evs(result: &StrategyResult) -> Option<String> {
+    let evs = result.evs.as_ref()?;
+    let parts: Vec<String> = result
+        .actions
+        .iter()
+        .zip(evs)
+        .map(|(action, ev)| format!("{} {:+.2}", action, ev))
+        .collect();
+    let overall: f64 = result.frequencies.iter().zip(evs).map(|(f, ev)| f * ev).sum();
+    Some(format!("EV (starting pots): {} | overall {:+.2}", parts.join(", "), overall))
+}
+
+/// "Raise +0.32bb | Call +0.31bb | Fold 0.00bb": preflop EVs over folding,
+/// each action named by its first word ("3-BET" reads "3-Bet").
+pub fn format_preflop_evs(actions: &[(&str, f64)]) -> String {
+    actions
+        .iter()
+        .map(|(action, ev)| {
+            let word = action.split_whitespace().next().unwrap_or_default();
+            let name = word
+                .split('-')
+                .map(|part| format!("{}{}", part.get(..1).unwrap_or_default(), part.get(1..).unwrap_or_default().to_lowercase()))
+                .collect::<Vec<_>>()
+                .join("-");
+            // Fold is exactly 0 and reads better unsigned
+            if ev.abs() < 0.005 {
+                format!("{} 0.00bb", name)
+            } else {
+                format!("{} {:+.2}bb", name, ev)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// EV gap, in starting pots, within which actions count as equally good: an
+/// average strategy isn't solved closer to equilibrium than that.
+pub const INDIFFERENT_EV: f64 = 0.02;
+
+/// Share of the tied actions' frequency one of them needs to be recommended
+/// over mixing.
+const PURE_FREQUENCY: f64 = 0.9;
+
+/// The one action to take when a mix isn't practical.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recommendation {
+    /// Index into `actions`.
+    Action(usize),
+    /// Actions within `INDIFFERENT_EV` of each other that the solver mixes,
+    /// most frequent first.
+    Mix(Vec<usize>),
+}
+
+/// The highest-EV action, with actions within `INDIFFERENT_EV` of it tied:
+/// a tie goes to the action the solver nearly always takes, otherwise it's a
+/// mix. `None` without EVs.
+pub fn recommend(result: &StrategyResult) -> Option<Recommendation> {
+    let evs = result.evs.as_ref().filter(|evs| !evs.is_empty())?;
+    let freq = |a: usize| result.frequencies.get(a).copied().unwrap_or(0.0);
+    let best = evs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut tied: Vec<usize> = (0..evs.len()).filter(|&a| evs[a] >= best - INDIFFERENT_EV).collect();
+    tied.sort_by(|&a, &b| freq(b).total_cmp(&freq(a)).then(evs[b].total_cmp(&evs[a])));
+    let tied_freq: f64 = tied.iter().map(|&a| freq(a)).sum();
+    if tied.len() == 1 || freq(tied[0]) >= PURE_FREQUENCY * tied_freq {
+        Some(Recommendation::Action(tied[0]))
+    } else {
+        Some(Recommendation::Mix(tied))
+    }
+}
+
+/// "Best: Bet 3.3 (+0.47)" or "Best: indifferent — mix Check / Bet 3.3"
+pub fn format_recommendation(result: &StrategyResult) -> Option<String> {
+    let evs = result.evs.as_ref()?;
+    Some(match recommend(result)? {
+        Recommendation::Action(a) => format!("Best: {} ({:+.2})", result.actions[a], evs[a]),
+        Recommendation::Mix(tied) => {
+            let actions: Vec<&str> = tied.iter().map(|&a| result.actions[a].as_str()).collect();
+            format!("Best: indifferent \u{2014} mix {}", actions.join(" / "))
+        }
+    })
+}
+
+/// One `ev_bar` per action, labels aligned.
+pub fn format_ev_bars(result: &StrategyResult) -> Vec<String> {
+    let Some(evs) = &result.evs else {
+        return vec![];
+    };
+    let width = result.actions.iter().map(|a| a.len()).max().unwrap_or(0);
+    result
+        .actions
+        .iter()
+        .zip(evs)
+        .map(|(action, &ev)| format!("{:<width$}  {}", action, ev_bar(ev, 20)))
+        .collect()
+}
+
+/// "at showdown after this line: win 34% / chop 41% / lose 25%"
+pub fn format_showdown((win, tie, lose): ShowdownOutcome) -> String {
+    format!(
+        "at showdown after this line: win {:.0}% / chop {:.0}% / lose {:.0}%",
+        win * 100.0,
+        tie * 100.0,
+        lose * 100.0
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -766,6 +1913,8 @@ pub fn format_strategy(result: &StrategyResult) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::flop_solver::{FlopNodeStrategy, DEFAULT_FLOP_BET_SIZES, DEFAULT_TEMPLATE_RIVER_BET_SIZES};
+    use crate::river_solver::find_combo_index;
 
     #[test]
     fn test_find_combo_index() {
@@ -814,17 +1963,163 @@ mod tests {
         assert!((stack - 97.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_pot_type_parse_depth_and_seat_key() {
+        assert_eq!(PotType::parse("SRP"), Some(PotType::Srp));
+        assert_eq!(PotType::parse("3bet"), Some(PotType::ThreeBet));
+        assert_eq!(PotType::parse("4bp"), Some(PotType::FourBet));
+        assert_eq!(PotType::parse("limp"), None);
+
+        let (pot, stack) = PotType::ThreeBet.pot_and_stack_at(50.0);
+        assert!((pot - 10.0).abs() < 1e-9 && (stack - 40.0).abs() < 1e-9);
+
+        // Antes are dead money that also come out of the stacks; a straddle
+        // doubles the raises and is dead unless the straddler is in the pot
+        let btn_bb = (Position::BTN, Position::BB);
+        let ante = ForcedBets::new(0.125, 0.0, 100.0).unwrap();
+        assert_eq!(PotType::Srp.pot_and_stack_with(100.0, &ante, btn_bb), (6.75, 96.875));
+        let straddle = ForcedBets::new(0.0, 2.0, 100.0).unwrap();
+        assert_eq!(PotType::Srp.pot_and_stack_with(100.0, &straddle, btn_bb), (14.0, 94.0));
+        assert_eq!(PotType::Srp.pot_and_stack_with(100.0, &straddle, (Position::BB, Position::UTG)), (12.0, 94.0));
+        let both = ForcedBets::new(0.125, 2.0, 100.0).unwrap();
+        assert_eq!(PotType::Srp.pot_and_stack_with(100.0, &both, btn_bb), (14.75, 93.875));
+        assert_eq!(PotType::Srp.pot_and_stack_with(100.0, &both, (Position::BB, Position::UTG)), (12.75, 93.875));
+        assert_eq!(
+            PotType::Srp.pot_and_stack_with(100.0, &ForcedBets::default(), btn_bb),
+            PotType::Srp.pot_and_stack()
+        );
+
+        assert_eq!(PotType::Srp.seat_key("BB"), "BB");
+        assert_eq!(PotType::ThreeBet.seat_key("BB"), "BB-3bp");
+        assert_eq!(PotType::FourBet.seat_key("BTN"), "BTN-4bp");
+    }
+
+    #[test]
+    fn test_derive_pot_ranges_follow_the_betting() {
+        // Hand 0 opens and calls a 3-bet; hand 1 opens and 4-bets; hand 2 is
+        // 3-bet and calls a 4-bet; hand 3 is 3-bet and folds to one.
+        let [mut open, mut three_bet, mut call_3bet, mut four_bet, mut call_4bet] = [(); 5].map(|_| vec![0.0; 169]);
+        open[0] = 1.0;
+        open[1] = 1.0;
+        call_3bet[0] = 1.0;
+        four_bet[1] = 1.0;
+        three_bet[2] = 1.0;
+        three_bet[3] = 1.0;
+        call_4bet[2] = 1.0;
+        let spot = PreflopSpotResult {
+            opener: Position::BTN,
+            responder: Position::BB,
+            open_strategy: open,
+            vs_open_3bet: three_bet,
+            vs_open_call: vec![0.0; 169],
+            vs_3bet_4bet: four_bet,
+            vs_3bet_call: call_3bet,
+            vs_4bet_allin: vec![0.0; 169],
+            vs_4bet_call: call_4bet,
+            vs_5bet_call: vec![0.0; 169],
+            exploitability: 0.0,
+            iterations: 0,
+            evs: None,
+        };
+        let hand = crate::game_tree::bucket_to_hand;
+
+        let (opener, responder) = derive_pot_ranges(&spot, PotType::Srp, RANGE_THRESHOLD);
+        assert_eq!((opener, responder), (vec![hand(0), hand(1)], vec![hand(2), hand(3)]));
+        let (opener, responder) = derive_pot_ranges(&spot, PotType::ThreeBet, RANGE_THRESHOLD);
+        assert_eq!((opener, responder), (vec![hand(0)], vec![hand(2), hand(3)]));
+        let (opener, responder) = derive_pot_ranges(&spot, PotType::FourBet, RANGE_THRESHOLD);
+        assert_eq!((opener, responder), (vec![hand(1)], vec![hand(2)]));
+    }
+
+    #[test]
+    fn test_derive_weighted_pot_ranges_carry_frequencies() {
+        let [mut open, mut three_bet, mut call, mut call_3bet] = [(); 4].map(|_| vec![0.0; 169]);
+        open[0] = 1.0;
+        open[1] = 0.5;
+        open[2] = 0.03;
+        call_3bet[0] = 0.4;
+        call[3] = 0.6;
+        three_bet[4] = 1.0;
+        let spot = PreflopSpotResult {
+            opener: Position::BTN,
+            responder: Position::BB,
+            open_strategy: open,
+            vs_open_3bet: three_bet,
+            vs_open_call: call,
+            vs_3bet_4bet: vec![0.0; 169],
+            vs_3bet_call: call_3bet,
+            vs_4bet_allin: vec![0.0; 169],
+            vs_4bet_call: vec![0.0; 169],
+            vs_5bet_call: vec![0.0; 169],
+            exploitability: 0.0,
+            iterations: 0,
+            evs: None,
+        };
+        let hand = crate::game_tree::bucket_to_hand;
+
+        // Below the threshold drops out; a single raised pot's responder
+        // only keeps its calls
+        let (opener, responder) = derive_weighted_pot_ranges(&spot, PotType::Srp, RANGE_THRESHOLD);
+        assert_eq!(opener, format!("{},{}:0.5", hand(0), hand(1)));
+        assert_eq!(responder, format!("{}:0.6", hand(3)));
+        let (opener, responder) = derive_weighted_pot_ranges(&spot, PotType::ThreeBet, RANGE_THRESHOLD);
+        assert_eq!((opener, responder), (format!("{}:0.4", hand(0)), hand(4)));
+    }
+
+    #[test]
+    fn test_parse_matchup_orders_opener_first() {
+        assert_eq!(parse_matchup("BTNvsBB").unwrap(), (Position::BTN, Position::BB));
+        assert_eq!(parse_matchup("bb-vs-btn").unwrap(), (Position::BTN, Position::BB));
+        assert_eq!(parse_matchup("SBvsBB").unwrap(), (Position::SB, Position::BB));
+        assert!(parse_matchup("BTNBB").unwrap_err().contains("OPENERvsRESPONDER"));
+        assert!(parse_matchup("BTNvsMP").unwrap_err().contains("MP"));
+        assert!(parse_matchup("COvsCO").is_err());
+    }
+
+    #[test]
+    fn test_chart_pot_ranges_use_opens_and_calls() {
+        use crate::preflop::{get_rfi_range, get_vs_rfi_range};
+
+        let (opener, responder) = chart_pot_ranges(Position::BTN, Position::BB, PotType::Srp).unwrap();
+        assert_eq!(opener, get_rfi_range("BTN", "6max").join(","));
+        assert_eq!(responder, get_vs_rfi_range("BB", "BTN", "6max").call.join(","));
+        assert!(chart_pot_ranges(Position::BTN, Position::BB, PotType::FourBet).is_err());
+    }
+
     #[test]
     fn test_format_strategy() {
         let result = StrategyResult {
             actions: vec!["CHECK".to_string(), "BET 33%".to_string(), "BET 75%".to_string()],
             frequencies: vec![0.45, 0.30, 0.25],
             source: StrategySource::Cached,
+            quality: None,
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
         };
         let formatted = format_strategy(&result);
         assert!(formatted.contains("CHECK"));
         assert!(formatted.contains("45%"));
         assert!(formatted.contains("BET 33%"));
+        assert!(formatted.ends_with("[cached]"), "{}", formatted);
+    }
+
+    #[test]
+    fn test_format_strategy_tags_quick_solves() {
+        let solve = crate::quality::SolveQuality::grade(0.1, 6.0, 50_000, 500_000, Some(50), 100);
+        let result = StrategyResult {
+            actions: vec!["Check".to_string(), "Bet 3.0".to_string()],
+            frequencies: vec![0.6, 0.4],
+            source: StrategySource::QuickSolve,
+            quality: Some(AnswerQuality::at_node(&solve, 50_000.0)),
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
+        };
+        let formatted = format_strategy(&result);
+        assert!(formatted.ends_with("[quick solve (approximate), 50K iterations]"), "{}", formatted);
     }
 
     #[test]
@@ -833,10 +2128,67 @@ mod tests {
             actions: vec![],
             frequencies: vec![],
             source: StrategySource::NotInRange,
+            quality: None,
+            showdown: None,
+            evs: None,
+            simplified: None,
+            villain: None,
         };
         assert!(format_strategy(&result).contains("not in range"));
     }
 
+    fn ev_result(frequencies: Vec<f64>, evs: Vec<f64>) -> StrategyResult {
+        StrategyResult {
+            actions: vec!["Check".to_string(), "Bet 3.3".to_string(), "Bet 6.6".to_string()],
+            frequencies,
+            source: StrategySource::Cached,
+            quality: None,
+            showdown: None,
+            evs: Some(evs),
+            simplified: None,
+            villain: None,
+        }
+    }
+
+    #[test]
+    fn test_recommend_takes_the_highest_ev() {
+        let result = ev_result(vec![0.5, 0.3, 0.2], vec![0.41, 0.47, 0.30]);
+        assert_eq!(recommend(&result), Some(Recommendation::Action(1)));
+        assert_eq!(format_recommendation(&result).unwrap(), "Best: Bet 3.3 (+0.47)");
+        assert_eq!(format_ev_bars(&result).len(), 3);
+    }
+
+    #[test]
+    fn test_recommend_breaks_near_ties_by_frequency() {
+        // Within noise, but the solver nearly always checks
+        let result = ev_result(vec![0.95, 0.05, 0.0], vec![0.40, 0.41, 0.20]);
+        assert_eq!(recommend(&result), Some(Recommendation::Action(0)));
+    }
+
+    #[test]
+    fn test_recommend_mixes_real_mixes() {
+        let result = ev_result(vec![0.45, 0.55, 0.0], vec![0.40, 0.41, 0.20]);
+        assert_eq!(recommend(&result), Some(Recommendation::Mix(vec![1, 0])));
+        assert_eq!(
+            format_recommendation(&result).unwrap(),
+            "Best: indifferent \u{2014} mix Bet 3.3 / Check"
+        );
+    }
+
+    #[test]
+    fn test_recommend_needs_evs() {
+        let mut result = ev_result(vec![1.0, 0.0, 0.0], vec![]);
+        result.evs = None;
+        assert_eq!(recommend(&result), None);
+        assert!(format_ev_bars(&result).is_empty());
+    }
+
+    #[test]
+    fn test_format_preflop_evs() {
+        let evs = [("RAISE 2.5bb", 0.324), ("3-BET", -1.5), ("FOLD", 0.0)];
+        assert_eq!(format_preflop_evs(&evs), "Raise +0.32bb | 3-Bet -1.50bb | Fold 0.00bb");
+    }
+
     fn test_flop_sol() -> FlopSolution {
         FlopSolution {
             board: "Ks9d4c".to_string(),
@@ -846,6 +2198,9 @@ mod tests {
             effective_stack: 97.0,
             iterations: 100,
             exploitability: 0.0,
+            oop_equity: 0.0,
+            oop_ev_share: 0.0,
+            ip_ev_share: 0.0,
             oop_combos: vec!["AhKh".to_string(), "AdKd".to_string()],
             ip_combos: vec!["QhQc".to_string()],
             strategies: vec![],
@@ -862,6 +2217,22 @@ mod tests {
             flop_tree_edges: vec![],
             turn_tree_edges: vec![],
             river_tree_edges: vec![],
+            combo_order_version: crate::river_solver::COMBO_ORDER_VERSION,
+            postprocess: None,
+            simplified: None,
+            quality: None,
+            convergence_history: vec![],
+            oop_weights: vec![],
+            ip_weights: vec![],
+            exploitability_pct_pot: 0.0,
+            exploitability_std_err: 0.0,
+            low_fidelity: false,
+            river_bet_sizes: DEFAULT_TEMPLATE_RIVER_BET_SIZES.to_vec(),
+            bucketing: Default::default(),
+            combos_refined: false,
+            flop_bet_sizes: DEFAULT_FLOP_BET_SIZES.to_vec(),
+            oop_ev_vs_best_response: 0.0,
+            time_limited: false,
         }
     }
 
@@ -870,7 +2241,7 @@ mod tests {
         let flop_sol = test_flop_sol();
         let result = lookup_in_template_strategy(
             &flop_sol, "2h3c", "OOP", "Ks9d4c7h",
-            &flop_sol.turn_strategies, &flop_sol.turn_tree_edges, &[],
+            &flop_sol.turn_strategies, &turn_template_config(), 6.0, 100.0, &[],
         );
         assert!(result.is_ok());
         assert_eq!(result.unwrap().source, StrategySource::NotInRange);
@@ -881,48 +2252,236 @@ mod tests {
         let flop_sol = test_flop_sol();
         let result = lookup_in_template_strategy(
             &flop_sol, "AhKh", "OOP", "Ks9d4c7h",
-            &flop_sol.turn_strategies, &flop_sol.turn_tree_edges, &[],
+            &flop_sol.turn_strategies, &turn_template_config(), 6.0, 100.0, &[],
         );
         assert!(result.is_ok());
         let r = result.unwrap();
-        assert_eq!(r.source, StrategySource::Cached);
-        assert_eq!(r.actions.len(), 2);
+        assert_eq!(r.source, StrategySource::DerivedFromFlop);
+        // The template's 0.66-pot bet, scaled to the 6.0 pot
+        assert_eq!(r.actions, vec!["Check".to_string(), "Bet 4.0".to_string()]);
         assert_eq!(r.frequencies.len(), 2);
+        assert!(format_strategy(&r).contains("[derived from flop solve (bucketed), 100 iterations]"));
+
+        // After a flop bet and call the turn pot is bigger and the stack shorter
+        let sizes = |pot, stack| {
+            lookup_in_template_strategy(
+                &flop_sol, "AhKh", "OOP", "Ks9d4c7h",
+                &flop_sol.turn_strategies, &turn_template_config(), pot, stack, &[],
+            )
+            .unwrap()
+            .actions
+        };
+        assert_eq!(sizes(18.0, 94.0)[1], "Bet 11.9");
+        assert_eq!(sizes(18.0, 5.0)[1], "Bet 5.0");
+    }
+
+    #[test]
+    fn test_lookup_in_template_strategy_rejects_bucket_mismatch() {
+        let mut flop_sol = test_flop_sol();
+        flop_sol.num_buckets = 100;
+        let result = lookup_in_template_strategy(
+            &flop_sol, "AhKh", "OOP", "Ks9d4c7h",
+            &flop_sol.turn_strategies, &turn_template_config(), 6.0, 100.0, &[],
+        );
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_action_matches() {
-        assert!(action_matches("Check", "x"));
-        assert!(action_matches("Check", "check"));
-        assert!(action_matches("Bet 2.0", "b"));
-        assert!(action_matches("Bet 2.0", "bet"));
-        assert!(action_matches("Call 2.0", "c"));
-        assert!(action_matches("Raise 6.0", "r"));
-        assert!(action_matches("Fold", "f"));
-        assert!(!action_matches("Check", "b"));
-        assert!(!action_matches("Bet 2.0", "x"));
+    fn test_canonical_hand_averages_its_combos() {
+        let mut sol = test_flop_sol();
+        sol.oop_combos.push("AhQd".to_string());
+        sol.strategies = vec![FlopNodeStrategy {
+            node_id: 0,
+            player: "OOP".to_string(),
+            actions: vec!["Check".to_string(), "Bet 2.0".to_string()],
+            frequencies: vec![vec![1.0, 0.0], vec![0.5, 0.5], vec![0.0, 1.0]],
+            evs: vec![],
+        }];
+
+        let by_hand = sol.strategy_by_canonical(0);
+        assert_eq!(by_hand.len(), 2);
+        assert_eq!(by_hand["AKs"], vec![0.75, 0.25]);
+        assert_eq!(by_hand["AQo"], vec![0.0, 1.0]);
+        assert!(sol.strategy_by_canonical(7).is_empty());
+        assert!(sol.display_grid(0).contains("OOP bet/raise % by hand"));
+
+        let r = lookup_in_flop_solution(&sol, "AKs", "OOP", &[]).unwrap();
+        assert_eq!(r.frequencies, vec![0.75, 0.25]);
+        let r = lookup_in_flop_solution(&sol, "AKo", "OOP", &[]).unwrap();
+        assert_eq!(r.source, StrategySource::NotInRange);
+
+        // Template lookups average the combos' buckets the same way
+        let r = lookup_in_template_strategy(
+            &sol, "AKs", "OOP", "Ks9d4c7h", &sol.turn_strategies, &turn_template_config(), 6.0, 100.0, &[],
+        )
+        .unwrap();
+        assert_eq!(r.frequencies.len(), 2);
     }
 
     #[test]
-    fn test_navigate_to_node() {
-        use crate::flop_solver::TreeEdge;
-        // Tree: root(0,OOP) --Check--> 1(IP) --Bet--> 2(OOP)
-        //                    --Bet--> 3(IP)
-        let edges = vec![
-            TreeEdge { from: 0, action: "Check".to_string(), to: 1 },
-            TreeEdge { from: 0, action: "Bet 2.0".to_string(), to: 3 },
-            TreeEdge { from: 1, action: "Check".to_string(), to: 4 },
-            TreeEdge { from: 1, action: "Bet 2.0".to_string(), to: 2 },
+    fn test_flop_line_frequencies_weights_by_reach() {
+        let mut sol = test_flop_sol();
+        sol.ip_combos = vec!["QhQc".to_string(), "JhJc".to_string()];
+        let (tree, _) = build_tree(&sol.tree_config());
+        let line = |steps: &[&str]| steps.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        // The node `steps` reach, with each combo taking `step` at its
+        // frequency and the first other action otherwise
+        let node = |steps: &[&str], step: &str, freqs: &[f64]| {
+            let node = walk_line(&tree, &line(steps)).unwrap();
+            let TreeNode::Action { node_id, player, actions, .. } = node else { unreachable!() };
+            let a = action_index(node, step).unwrap();
+            let other = if a == 0 { 1 } else { 0 };
+            FlopNodeStrategy {
+                node_id: *node_id,
+                player: player_label(*player).to_string(),
+                actions: actions.iter().map(|a| a.label()).collect(),
+                frequencies: freqs
+                    .iter()
+                    .map(|&f| {
+                        let mut row = vec![0.0; actions.len()];
+                        row[a] = f;
+                        row[other] = 1.0 - f;
+                        row
+                    })
+                    .collect(),
+                evs: vec![],
+            }
+        };
+        sol.strategies = vec![
+            node(&[], "check", &[1.0, 0.5]),
+            node(&["check"], "bet33", &[1.0, 0.25]),
+            node(&["check", "bet33"], "allin", &[1.0, 1.0]),
+            node(&["check", "bet33", "allin"], "call", &[1.0, 0.0]),
         ];
-        // Empty path → None (use root)
-        assert_eq!(navigate_to_node(&edges, &[]), None);
-        // "x" → node 1
-        assert_eq!(navigate_to_node(&edges, &["x".to_string()]), Some(1));
-        // "x", "b" → node 2
-        assert_eq!(navigate_to_node(&edges, &["x".to_string(), "b".to_string()]), Some(2));
-        // "b" → node 3
-        assert_eq!(navigate_to_node(&edges, &["b".to_string()]), Some(3));
-        // invalid path → None
-        assert_eq!(navigate_to_node(&edges, &["x".to_string(), "f".to_string()]), None);
+
+        let r = flop_line_frequencies(&sol, &line(&["x", "bet33", "allin"])).unwrap();
+        assert_eq!(r.line, vec!["Check", "Bet 2.0", "Raise 97.0"]);
+        assert_eq!(r.player, "IP");
+        // IP reach is [1.0, 0.25]: only the second combo folds
+        assert_eq!(r.actions, vec!["Fold", "Call 95.0"]);
+        assert!((r.frequencies[0] - 0.2).abs() < 1e-9, "{:?}", r.frequencies);
+        assert!((r.frequencies[1] - 0.8).abs() < 1e-9);
+
+        // Steps the tree doesn't have, or that close the flop, are errors
+        let err = flop_line_frequencies(&sol, &line(&["x", "bet50"])).unwrap_err();
+        assert!(err.contains("legal: Check, Bet 2.0 (bet33)"), "{}", err);
+        assert!(flop_line_frequencies(&sol, &line(&["x", "x"])).unwrap_err().contains("ends the flop action"));
+    }
+
+    #[test]
+    fn test_flop_lookup_grades_by_node_reach() {
+        let edge = |from, action: &str, to| crate::flop_solver::TreeEdge { from, action: action.to_string(), to };
+        let node = |node_id, player: &str, actions: &[&str], frequencies: Vec<Vec<f64>>| FlopNodeStrategy {
+            node_id,
+            player: player.to_string(),
+            actions: actions.iter().map(|a| a.to_string()).collect(),
+            frequencies,
+            evs: vec![],
+        };
+        let mut sol = test_flop_sol();
+        sol.iterations = 500_000;
+        sol.flop_tree_edges = vec![edge(0, "Check", 1), edge(1, "Bet 2.0", 2)];
+        sol.strategies = vec![
+            node(0, "OOP", &["Check", "Bet 2.0"], vec![vec![1.0, 0.0], vec![0.5, 0.5]]),
+            node(1, "IP", &["Check", "Bet 2.0"], vec![vec![0.9, 0.1]]),
+            node(2, "OOP", &["Fold", "Call 2.0"], vec![vec![0.0, 1.0]; 2]),
+        ];
+
+        let root = lookup_in_flop_solution(&sol, "AhKh", "OOP", &[]).unwrap();
+        let q = root.quality.as_ref().expect("graded");
+        assert_eq!(q.grade, Grade::A);
+        assert!((q.node_visits - 500_000.0).abs() < 1e-6);
+
+        // Reached 75% x 10% of the time: 37.5K visits grades B
+        let path = ["x".to_string(), "bet33".to_string()];
+        let deep = lookup_in_flop_solution(&sol, "AhKh", "OOP", &path).unwrap();
+        let q = deep.quality.as_ref().expect("graded");
+        assert!((q.node_visits - 37_500.0).abs() < 1e-6, "{}", q.node_visits);
+        assert_eq!(q.grade, Grade::B);
+
+        let engine = StrategyEngine::new(100.0).with_min_grade(Some(Grade::A));
+        assert!(engine.accepts(&root));
+        assert!(!engine.accepts(&deep));
+        let err = engine.require_grade(deep).err().expect("below --min-grade");
+        assert!(err.contains("below --min-grade A"), "{}", err);
+    }
+
+    #[test]
+    fn test_postflop_seats_validates_order() {
+        assert_eq!(postflop_seats("bb", "BTN").unwrap(), (Position::BB, Position::BTN));
+        assert_eq!(postflop_seats("SB", "BB").unwrap(), (Position::SB, Position::BB));
+        assert!(postflop_seats("BTN", "BB").unwrap_err().contains("swap"));
+        assert!(postflop_seats("CO", "CO").is_err());
+        assert!(postflop_seats("MP", "BTN").is_err());
+    }
+
+    #[test]
+    fn test_hero_villain_seats_finds_who_is_oop() {
+        assert_eq!(hero_villain_seats("BB", "BTN", None).unwrap(), ((Position::BB, Position::BTN), true));
+        assert_eq!(hero_villain_seats("btn", "bb", None).unwrap(), ((Position::BB, Position::BTN), false));
+        assert_eq!(hero_villain_seats("CO", "SB", Some(("SB", "CO"))).unwrap(), ((Position::SB, Position::CO), false));
+        assert!(hero_villain_seats("BTN", "BB", Some(("BTN", "BB"))).unwrap_err().contains("wrong way round"));
+        assert!(hero_villain_seats("BTN", "BB", Some(("SB", "BTN"))).unwrap_err().contains("aren't"));
+        assert!(hero_villain_seats("CO", "CO", None).is_err());
+    }
+
+    #[test]
+    fn test_hero_line_node_must_end_on_hero() {
+        let tree = || build_tree(&TreeConfig::default_river(10.0, 100.0)).0;
+        let line = |steps: &[&str]| steps.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(hero_line_node(&[], "IP", || unreachable!()), Ok(None));
+        assert_eq!(hero_line_node(&line(&["check"]), "IP", tree), Ok(Some(1)));
+
+        let err = hero_line_node(&line(&["check"]), "OOP", tree).unwrap_err();
+        assert!(err.contains("ends on villain's turn (IP to act; legal: Check, Bet 3.3 (bet33)"), "{}", err);
+        assert!(hero_line_node(&line(&["check", "bet33"]), "OOP", tree).is_ok());
+    }
+
+    #[test]
+    fn test_apply_range_override_replaces_one_side() {
+        let (oop, ip) = apply_range_override("QQ", "AA", true, "JJ,TT", "Ks9d4c7h2s", "Villain").unwrap();
+        assert_eq!((oop.as_str(), ip.as_str()), ("QQ", "JJ,TT"));
+        let (oop, ip) = apply_range_override("QQ", "AA", false, "JJ", "Ks9d4c7h2s", "Villain").unwrap();
+        assert_eq!((oop.as_str(), ip.as_str()), ("JJ", "AA"));
+    }
+
+    #[test]
+    fn test_apply_range_override_rejects_empty_after_board() {
+        // Only one ace left in the deck — no AA combos survive
+        assert!(apply_range_override("QQ", "KK", true, "AA", "AsAhAd7h2s", "Villain").is_err());
+        assert!(apply_range_override("QQ", "KK", true, "", "Ks9d4c7h2s", "Villain").is_err());
+    }
+
+    #[test]
+    fn test_exploit_position_key_is_separate_and_stable() {
+        let key = exploit_position_key("BB", "JJ,TT", "Ks9d4c7h2s");
+        assert!(key.starts_with("BB-x"));
+        assert_ne!(key, "BB");
+        assert_eq!(key, exploit_position_key("BB", "TT,JJ", "Ks9d4c7h2s"));
+        assert_ne!(key, exploit_position_key("BB", "JJ", "Ks9d4c7h2s"));
+    }
+
+    #[test]
+    fn test_villain_range_override_changes_strategy() {
+        // QQ OOP on a dry river: never bets into AA, value bets vs JJ/TT
+        let board = "Ks9d4c7h2s";
+        let bet_freq = |ip_range: &str| {
+            let (oop, ip) = apply_range_override("QQ", "AA", true, ip_range, board, "Villain").unwrap();
+            let config = RiverSolverConfig::new(board, &oop, &ip, 10.0, 20.0, 2000).unwrap();
+            let solution = try_solve_river(&config).unwrap();
+            let result = lookup_in_river_solution(&solution, "QhQd", "OOP", &[]).unwrap();
+            result
+                .actions
+                .iter()
+                .zip(&result.frequencies)
+                .filter(|(a, _)| a.starts_with("Bet"))
+                .map(|(_, f)| f)
+                .sum::<f64>()
+        };
+        let vs_default = bet_freq("AA");
+        let vs_override = bet_freq("JJ,TT");
+        assert!(vs_default < 0.2, "QQ should check into AA, bet {:.2}", vs_default);
+        assert!(vs_override > 0.5, "QQ should value bet vs JJ/TT, bet {:.2}", vs_override);
     }
 }
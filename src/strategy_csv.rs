@@ -0,0 +1,236 @@
+//! CSV export of postflop strategies (`gto solve flop|turn|river
+//! --export-csv`), for reviewing a solve in a spreadsheet.
+//!
+//! One row per (node, combo, action) of the acting player, under a header:
+//!
+//! ```text
+//! board,node_id,player,acting_line,combo,action_label,frequency,ev
+//! Ks9d4c,0,OOP,root,AhAd,Check,0.412000,
+//! Ks9d4c,1,IP,Check,KhKd,Bet 3.3,0.871000,4.120000
+//! ```
+//!
+//! `acting_line` is the actions leading to the node joined by " > " ("root"
+//! at the root). `ev` is the action's EV in chips for solutions that store
+//! EVs (flop solves) and empty otherwise. A turn solution's rows cover the
+//! turn street; a 3-way river's `player` is its seat (OOP, MP or IP).
+//! Fields are quoted when they hold a comma, quote or newline.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::flop_solver::FlopSolution;
+use crate::multiway_solver::MultiwaySolution;
+use crate::postflop_tree::{action_lines, build_tree, build_turn_tree, TreeNode, MULTIWAY_SEATS};
+use crate::river_solver::RiverSolution;
+use crate::turn_solver::TurnSolution;
+
+pub const HEADER: &str = "board,node_id,player,acting_line,combo,action_label,frequency,ev";
+
+/// One action node's strategy, as the CSV lays it out.
+pub struct CsvNode<'a> {
+    pub node_id: u16,
+    pub player: &'a str,
+    /// Action labels from the root to the node.
+    pub line: Vec<String>,
+    pub actions: &'a [String],
+    /// The acting player's combos; `frequencies` rows are indexed like them.
+    pub combos: &'a [String],
+    pub frequencies: &'a [Vec<f64>],
+    /// Per-combo action EVs in starting pots; empty when not stored.
+    pub evs: &'a [Vec<f64>],
+}
+
+/// A solution `to_csv` exports.
+pub trait CsvSolution {
+    fn board(&self) -> &str;
+
+    fn starting_pot(&self) -> f64;
+
+    /// Every stored action node, in tree order.
+    fn csv_nodes(&self) -> Vec<CsvNode<'_>>;
+}
+
+/// Action lines by node id for the street `root` starts.
+fn lines_by_node(root: &TreeNode) -> HashMap<u16, Vec<String>> {
+    action_lines(root).into_iter().collect()
+}
+
+/// The side's combos for a solution's "OOP"/"IP" player label.
+fn side_combos<'a>(player: &str, oop: &'a [String], ip: &'a [String]) -> &'a [String] {
+    if player == "OOP" {
+        oop
+    } else {
+        ip
+    }
+}
+
+impl CsvSolution for FlopSolution {
+    fn board(&self) -> &str {
+        &self.board
+    }
+
+    fn starting_pot(&self) -> f64 {
+        self.starting_pot
+    }
+
+    fn csv_nodes(&self) -> Vec<CsvNode<'_>> {
+        let (tree, _) = build_tree(&self.tree_config());
+        let mut lines = lines_by_node(&tree);
+        self.strategies
+            .iter()
+            .map(|s| CsvNode {
+                node_id: s.node_id,
+                player: &s.player,
+                line: lines.remove(&s.node_id).unwrap_or_default(),
+                actions: &s.actions,
+                combos: side_combos(&s.player, &self.oop_combos, &self.ip_combos),
+                frequencies: &s.frequencies,
+                evs: &s.evs,
+            })
+            .collect()
+    }
+}
+
+impl CsvSolution for TurnSolution {
+    fn board(&self) -> &str {
+        &self.board
+    }
+
+    fn starting_pot(&self) -> f64 {
+        self.starting_pot
+    }
+
+    fn csv_nodes(&self) -> Vec<CsvNode<'_>> {
+        let (tree, _) = build_turn_tree(&self.tree_config());
+        let mut lines = lines_by_node(&tree);
+        self.strategies
+            .iter()
+            .map(|s| CsvNode {
+                node_id: s.node_id,
+                player: &s.player,
+                line: lines.remove(&s.node_id).unwrap_or_default(),
+                actions: &s.actions,
+                combos: side_combos(&s.player, &self.oop_combos, &self.ip_combos),
+                frequencies: &s.frequencies,
+                evs: &[],
+            })
+            .collect()
+    }
+}
+
+impl CsvSolution for RiverSolution {
+    fn board(&self) -> &str {
+        &self.board
+    }
+
+    fn starting_pot(&self) -> f64 {
+        self.starting_pot
+    }
+
+    fn csv_nodes(&self) -> Vec<CsvNode<'_>> {
+        let (tree, _) = build_tree(&self.tree_config());
+        let mut lines = lines_by_node(&tree);
+        self.strategies
+            .iter()
+            .map(|s| CsvNode {
+                node_id: s.node_id,
+                player: &s.player,
+                line: lines.remove(&s.node_id).unwrap_or_default(),
+                actions: &s.actions,
+                combos: side_combos(&s.player, &self.oop_combos, &self.ip_combos),
+                frequencies: &s.frequencies,
+                evs: &[],
+            })
+            .collect()
+    }
+}
+
+impl CsvSolution for MultiwaySolution {
+    fn board(&self) -> &str {
+        &self.board
+    }
+
+    fn starting_pot(&self) -> f64 {
+        self.starting_pot
+    }
+
+    fn csv_nodes(&self) -> Vec<CsvNode<'_>> {
+        self.strategies
+            .iter()
+            .map(|s| {
+                let seat = MULTIWAY_SEATS.iter().position(|&name| name == s.seat).unwrap_or(0);
+                CsvNode {
+                    node_id: s.node_id,
+                    player: &s.seat,
+                    line: s.line.clone(),
+                    actions: &s.actions,
+                    combos: &self.combos[seat],
+                    frequencies: &s.frequencies,
+                    evs: &[],
+                }
+            })
+            .collect()
+    }
+}
+
+/// `field` as a CSV field: quoted, with quotes doubled, when it holds a
+/// separator, quote or line break.
+pub fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// `solution`'s strategies as CSV, header first.
+pub fn to_csv<T: CsvSolution>(solution: &T) -> String {
+    let mut out = String::from(HEADER);
+    out.push('\n');
+    let board = csv_field(solution.board());
+    for node in solution.csv_nodes() {
+        let line = if node.line.is_empty() { "root".to_string() } else { node.line.join(" > ") };
+        let line = csv_field(&line);
+        let player = csv_field(node.player);
+        for (c, combo) in node.combos.iter().enumerate() {
+            let combo = csv_field(combo);
+            for (a, action) in node.actions.iter().enumerate() {
+                let frequency = node.frequencies.get(c).and_then(|row| row.get(a)).copied().unwrap_or(0.0);
+                let ev = match node.evs.get(c).and_then(|row| row.get(a)) {
+                    Some(ev) => format!("{:.6}", ev * solution.starting_pot()),
+                    None => String::new(),
+                };
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{:.6},{}\n",
+                    board,
+                    node.node_id,
+                    player,
+                    line,
+                    combo,
+                    csv_field(action),
+                    frequency,
+                    ev
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// Write `solution`'s strategies to `path` as CSV.
+pub fn write_csv<T: CsvSolution>(solution: &T, path: &Path) -> Result<(), String> {
+    std::fs::write(path, to_csv(solution)).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("AhKh"), "AhKh");
+        assert_eq!(csv_field("Ah,Kh"), "\"Ah,Kh\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,309 @@
+//! Effective-stack sweep: solve one flop spot at several stack depths and show
+//! how the strategy changes with SPR.
+//!
+//! Every depth uses the same ranges, so the solutions share one canonical combo
+//! order; combos are still matched by name in case a depth came from an older
+//! cache. Root actions are folded into `features::ACTION_TAXONOMY` so depths
+//! with different bet sizes (or an all-in replacing a bet) line up, and combos
+//! are grouped by `breakdown::HandClass`.
+
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::batch::load_or_solve_all;
+use crate::breakdown::{classify, HandClass};
+use crate::cache_key::range_key_of;
+use crate::cards::parse_board;
+use crate::features::{canonical_strategy, ACTION_TAXONOMY};
+use crate::flop_solver::{FlopSolution, FlopSolverConfig};
+use crate::postflop_tree::{build_tree, TreeNode};
+use crate::river_solver::find_combo_index;
+
+/// One depth of a sweep: root frequencies over `ACTION_TAXONOMY`, overall and
+/// per hand class.
+pub struct SweepPoint {
+    pub stack: f64,
+    pub spr: f64,
+    pub root: [f64; 6],
+    /// (class, combos, frequencies), most valuable class first.
+    pub categories: Vec<(HandClass, usize, [f64; 6])>,
+}
+
+/// Parse a `--stacks` list like "20,35,50".
+pub fn parse_stacks(s: &str) -> Result<Vec<f64>, String> {
+    let mut stacks = s
+        .split(',')
+        .map(|t| {
+            let t = t.trim();
+            match t.parse::<f64>() {
+                Ok(v) if v > 0.0 => Ok(v),
+                _ => Err(format!("Invalid stack depth '{}'", t)),
+            }
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+    stacks.sort_by(|a, b| a.total_cmp(b));
+    stacks.dedup();
+    if stacks.len() < 2 {
+        return Err("Give at least two stack depths".to_string());
+    }
+    Ok(stacks)
+}
+
+/// Aggregate the root decision of `solution` over the combos in `reference`.
+///
+/// Combos missing from `solution` are skipped; each remaining combo counts once.
+pub fn sweep_point(solution: &FlopSolution, reference: &[String]) -> Result<SweepPoint, String> {
+    let board = parse_board(&solution.board).map_err(|e| e.to_string())?;
+    let (tree, _) = build_tree(&solution.tree_config());
+    let TreeNode::Action { node_id, actions, .. } = &tree else {
+        return Err("Flop tree has no root decision".to_string());
+    };
+    let strat = solution
+        .strategies
+        .iter()
+        .find(|s| s.node_id == *node_id && s.actions.len() == actions.len())
+        .ok_or_else(|| format!("Solution for {} has no root strategy", solution.board))?;
+
+    let mut root = [0.0; 6];
+    let mut by_category: Vec<(HandClass, usize, [f64; 6])> = Vec::new();
+    let mut count = 0usize;
+    for combo in reference {
+        let Some(freqs) = find_combo_index(combo, &solution.oop_combos).and_then(|i| strat.frequencies.get(i)) else {
+            continue;
+        };
+        let slots = canonical_strategy(actions, freqs, solution.starting_pot, solution.effective_stack);
+        let Ok(hole) = parse_board(combo) else { continue };
+        let category = classify(&hole, &board);
+
+        let entry = match by_category.iter().position(|(c, _, _)| *c == category) {
+            Some(i) => &mut by_category[i],
+            None => {
+                by_category.push((category, 0, [0.0; 6]));
+                by_category.last_mut().unwrap()
+            }
+        };
+        entry.1 += 1;
+        for s in 0..6 {
+            entry.2[s] += slots[s];
+            root[s] += slots[s];
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return Err(format!("No combos in common with the {}bb solution", solution.effective_stack));
+    }
+
+    for r in root.iter_mut() {
+        *r /= count as f64;
+    }
+    for (_, n, freqs) in by_category.iter_mut() {
+        for f in freqs.iter_mut() {
+            *f /= *n as f64;
+        }
+    }
+    by_category.sort_by_key(|(c, _, _)| *c);
+
+    Ok(SweepPoint {
+        stack: solution.effective_stack,
+        spr: solution.effective_stack / solution.starting_pot,
+        root,
+        categories: by_category,
+    })
+}
+
+/// Index and frequency of the most frequent slot.
+fn dominant(freqs: &[f64; 6]) -> (usize, f64) {
+    freqs
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0))
+}
+
+/// One-line summary of how a class's dominant action changes with SPR,
+/// e.g. "bet_small at SPR 8.3-25.0, all_in at SPR 1.0-3.3".
+pub fn morph_summary(points: &[SweepPoint], category: HandClass) -> String {
+    // Highest SPR first, grouping consecutive depths with the same dominant slot
+    let mut segments: Vec<(usize, f64, f64)> = Vec::new();
+    for p in points.iter().rev() {
+        let Some((_, _, freqs)) = p.categories.iter().find(|(c, _, _)| *c == category) else {
+            continue;
+        };
+        let (slot, _) = dominant(freqs);
+        match segments.last_mut() {
+            Some((s, lo, _)) if *s == slot => *lo = p.spr,
+            _ => segments.push((slot, p.spr, p.spr)),
+        }
+    }
+    segments
+        .iter()
+        .map(|(slot, lo, hi)| {
+            if (hi - lo).abs() < 1e-9 {
+                format!("{} at SPR {:.1}", ACTION_TAXONOMY[*slot], lo)
+            } else {
+                format!("{} at SPR {:.1}-{:.1}", ACTION_TAXONOMY[*slot], lo, hi)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Long-format CSV: one row per (depth, scope, action).
+pub fn write_csv(points: &[SweepPoint], path: &Path) -> Result<(), String> {
+    let mut out = String::from("stack,spr,scope,combos,action,frequency\n");
+    for p in points {
+        let total: usize = p.categories.iter().map(|(_, n, _)| n).sum();
+        let scopes = std::iter::once(("All".to_string(), total, &p.root))
+            .chain(p.categories.iter().map(|(c, n, f)| (c.label(false).to_string(), *n, f)));
+        for (scope, n, freqs) in scopes {
+            for (action, f) in ACTION_TAXONOMY.iter().zip(freqs.iter()) {
+                out.push_str(&format!("{},{:.3},{},{},{},{:.6}\n", p.stack, p.spr, scope, n, action, f));
+            }
+        }
+    }
+    std::fs::write(path, out).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// `gto sweep stacks`: solve (or load) the spot at each depth and print the
+/// comparison tables.
+#[allow(clippy::too_many_arguments)]
+pub fn run_stack_sweep(
+    board: &str,
+    oop_pos: &str,
+    ip_pos: &str,
+    oop_range: &str,
+    ip_range: &str,
+    pot: f64,
+    stacks: &[f64],
+    iterations: usize,
+    csv: Option<&Path>,
+) -> Result<(), String> {
+    let configs = stacks
+        .iter()
+        .map(|&stack| FlopSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    println!();
+    println!(
+        "  {} Stack sweep  |  Board: {}  |  {} (OOP) vs {} (IP)  |  Pot: {}  |  Stacks: {}",
+        "GTO".bold(),
+        board,
+        oop_pos,
+        ip_pos,
+        pot,
+        stacks.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", "),
+    );
+
+    let spots: Vec<(String, &str, FlopSolverConfig)> = configs
+        .into_iter()
+        .map(|config| (format!("{}bb", config.effective_stack), board, config))
+        .collect();
+    let solutions = load_or_solve_all(&spots, oop_pos, ip_pos, &range_key_of(oop_range, ip_range));
+
+    let reference = &solutions[0].oop_combos;
+    let points = solutions
+        .iter()
+        .map(|s| sweep_point(s, reference))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    print_tables(&points);
+    if let Some(path) = csv {
+        write_csv(&points, path).map_err(crate::status::io_error)?;
+        println!("  Wrote {}", path.display());
+        println!();
+    }
+    Ok(())
+}
+
+fn print_tables(points: &[SweepPoint]) {
+    let header: String = points
+        .iter()
+        .map(|p| format!("{:>16}", format!("{}bb/SPR {:.1}", p.stack, p.spr)))
+        .collect();
+
+    println!();
+    println!("  {} root (OOP), all combos:", "Strategy".bold());
+    println!("  {:<12}{}", "", header);
+    for (slot, action) in ACTION_TAXONOMY.iter().enumerate() {
+        if points.iter().all(|p| p.root[slot] < 0.0005) {
+            continue;
+        }
+        let row: String = points
+            .iter()
+            .map(|p| format!("{:>15.1}%", p.root[slot] * 100.0))
+            .collect();
+        println!("  {:<12}{}", action, row);
+    }
+
+    let mut categories: Vec<HandClass> = Vec::new();
+    for p in points {
+        for (c, _, _) in &p.categories {
+            if !categories.contains(c) {
+                categories.push(*c);
+            }
+        }
+    }
+    categories.sort();
+
+    println!();
+    println!("  {} by hand class (dominant action):", "Strategy".bold());
+    println!("  {:<18}{}", "", header);
+    for &category in &categories {
+        let row: String = points
+            .iter()
+            .map(|p| match p.categories.iter().find(|(c, _, _)| *c == category) {
+                Some((_, _, freqs)) => {
+                    let (slot, f) = dominant(freqs);
+                    format!("{:>16}", format!("{} {:.0}%", short_slot(slot), f * 100.0))
+                }
+                None => format!("{:>16}", "-"),
+            })
+            .collect();
+        println!("  {:<18}{}", category.label(false), row);
+    }
+
+    println!();
+    for &category in &categories {
+        println!("  {}: {}", category.label(false).bold(), morph_summary(points, category));
+    }
+    println!();
+}
+
+/// Compact slot name for table cells.
+fn short_slot(slot: usize) -> &'static str {
+    ["x/c", "b-small", "b-med", "b-large", "jam", "fold"][slot]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(stack: f64, spr: f64, category: HandClass, freqs: [f64; 6]) -> SweepPoint {
+        SweepPoint { stack, spr, root: freqs, categories: vec![(category, 1, freqs)] }
+    }
+
+    #[test]
+    fn test_parse_stacks_sorts_and_validates() {
+        assert_eq!(parse_stacks("100, 20,50").unwrap(), vec![20.0, 50.0, 100.0]);
+        assert!(parse_stacks("20").is_err());
+        assert!(parse_stacks("20,abc").is_err());
+        assert!(parse_stacks("20,-5").is_err());
+    }
+
+    #[test]
+    fn test_morph_summary_groups_spr_ranges() {
+        let set = HandClass::Set;
+        let points = vec![
+            point(10.0, 1.7, set, [0.1, 0.0, 0.0, 0.1, 0.8, 0.0]),
+            point(35.0, 5.8, set, [0.2, 0.0, 0.0, 0.7, 0.1, 0.0]),
+            point(50.0, 8.3, set, [0.2, 0.1, 0.0, 0.6, 0.1, 0.0]),
+            point(150.0, 25.0, set, [0.3, 0.6, 0.0, 0.1, 0.0, 0.0]),
+        ];
+        assert_eq!(
+            morph_summary(&points, set),
+            "bet_small at SPR 25.0, bet_large at SPR 5.8-8.3, all_in at SPR 1.7"
+        );
+    }
+}
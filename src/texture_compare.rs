@@ -0,0 +1,294 @@
+//! Suit-texture comparison: solve the rainbow, two-tone and monotone versions
+//! of one rank pattern and show how the strategy changes with the suits.
+//!
+//! Each texture has its own board, so card removal gives each solution a
+//! slightly different combo set; every texture is aggregated over its own
+//! combos with `sweep::sweep_point` and compared on the shared
+//! `features::ACTION_TAXONOMY` slots.
+
+use colored::Colorize;
+
+use crate::batch::load_or_solve_all;
+use crate::breakdown::HandClass;
+use crate::cache_key::range_key_of;
+use crate::features::ACTION_TAXONOMY;
+use crate::flop_enumerator::{texture_boards, SuitTexture};
+use crate::flop_solver::{FlopSolution, FlopSolverConfig};
+use crate::sweep::{sweep_point, SweepPoint};
+
+/// Number of texture-driven differences listed under the tables.
+const TOP_DIFFERENCES: usize = 8;
+
+/// Frequencies of one scope, per texture that has it.
+type ScopeFreqs<'a> = Vec<(SuitTexture, &'a [f64; 6])>;
+
+/// One texture's solve, aggregated at the root.
+pub struct TexturePoint {
+    pub texture: SuitTexture,
+    pub board: String,
+    pub point: SweepPoint,
+    pub oop_equity: f64,
+    pub oop_ev_share: f64,
+    pub ip_ev_share: f64,
+}
+
+impl TexturePoint {
+    pub fn from_solution(texture: SuitTexture, solution: &FlopSolution) -> Result<TexturePoint, String> {
+        Ok(TexturePoint {
+            texture,
+            board: solution.board.clone(),
+            point: sweep_point(solution, &solution.oop_combos)?,
+            oop_equity: solution.oop_equity,
+            oop_ev_share: solution.oop_ev_share,
+            ip_ev_share: solution.ip_ev_share,
+        })
+    }
+
+    fn category(&self, category: HandClass) -> Option<&[f64; 6]> {
+        self.point
+            .categories
+            .iter()
+            .find(|(c, _, _)| *c == category)
+            .map(|(_, _, f)| f)
+    }
+}
+
+/// A frequency that moves with the suits: scope ("All" or a hand class),
+/// action slot, and the textures with the lowest and highest
+/// frequency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureDifference {
+    pub scope: String,
+    pub slot: usize,
+    pub low: (SuitTexture, f64),
+    pub high: (SuitTexture, f64),
+}
+
+impl TextureDifference {
+    pub fn spread(&self) -> f64 {
+        self.high.1 - self.low.1
+    }
+}
+
+/// The `limit` largest frequency spreads across textures, over the root and
+/// every hand class present in at least two textures.
+pub fn largest_differences(points: &[TexturePoint], limit: usize) -> Vec<TextureDifference> {
+    let mut scopes: Vec<(String, ScopeFreqs)> = vec![(
+        "All".to_string(),
+        points.iter().map(|p| (p.texture, &p.point.root)).collect(),
+    )];
+    for category in categories(points) {
+        let present: Vec<_> = points
+            .iter()
+            .filter_map(|p| p.category(category).map(|f| (p.texture, f)))
+            .collect();
+        if present.len() >= 2 {
+            scopes.push((category.label(false).to_string(), present));
+        }
+    }
+
+    let mut diffs = Vec::new();
+    for (scope, freqs) in &scopes {
+        for slot in 0..ACTION_TAXONOMY.len() {
+            let by_texture = freqs.iter().map(|(t, f)| (*t, f[slot]));
+            let low = by_texture.clone().min_by(|a, b| a.1.total_cmp(&b.1));
+            let high = by_texture.max_by(|a, b| a.1.total_cmp(&b.1));
+            if let (Some(low), Some(high)) = (low, high) {
+                if high.1 - low.1 >= 0.0005 {
+                    diffs.push(TextureDifference { scope: scope.clone(), slot, low, high });
+                }
+            }
+        }
+    }
+    diffs.sort_by(|a, b| b.spread().total_cmp(&a.spread()));
+    diffs.truncate(limit);
+    diffs
+}
+
+/// Hand classes seen in any texture, most valuable first.
+fn categories(points: &[TexturePoint]) -> Vec<HandClass> {
+    let mut categories: Vec<HandClass> = Vec::new();
+    for p in points {
+        for (c, _, _) in &p.point.categories {
+            if !categories.contains(c) {
+                categories.push(*c);
+            }
+        }
+    }
+    categories.sort();
+    categories
+}
+
+/// `gto compare-textures`: solve (or load) each texture of `ranks` and print
+/// the comparison tables.
+#[allow(clippy::too_many_arguments)]
+pub fn run_texture_compare(
+    ranks: &str,
+    oop_pos: &str,
+    ip_pos: &str,
+    oop_range: &str,
+    ip_range: &str,
+    pot: f64,
+    stack: f64,
+    iterations: usize,
+) -> Result<(), String> {
+    let boards = texture_boards(ranks)?;
+    let configs = boards
+        .iter()
+        .map(|(_, board)| FlopSolverConfig::new(board, oop_range, ip_range, pot, stack, iterations).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    println!();
+    println!(
+        "  {} Texture comparison  |  Ranks: {}  |  {} (OOP) vs {} (IP)  |  Pot: {}  |  Stack: {}",
+        "GTO".bold(),
+        ranks.to_uppercase(),
+        oop_pos,
+        ip_pos,
+        pot,
+        stack,
+    );
+    if boards.len() < 3 {
+        println!(
+            "  {}",
+            format!("Paired ranks: only {} texture(s) possible", boards.len()).dimmed()
+        );
+    }
+
+    let spots: Vec<(String, &str, FlopSolverConfig)> = boards
+        .iter()
+        .zip(configs)
+        .map(|((_, board), config)| (board.clone(), board.as_str(), config))
+        .collect();
+    let solutions = load_or_solve_all(&spots, oop_pos, ip_pos, &range_key_of(oop_range, ip_range));
+
+    let points = boards
+        .iter()
+        .zip(&solutions)
+        .map(|((texture, _), s)| TexturePoint::from_solution(*texture, s))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    print_tables(&points);
+    Ok(())
+}
+
+fn print_tables(points: &[TexturePoint]) {
+    let header: String = points
+        .iter()
+        .map(|p| format!("{:>18}", format!("{} {}", p.texture.as_str(), p.board)))
+        .collect();
+
+    println!();
+    println!("  {} root (OOP), all combos:", "Strategy".bold());
+    println!("  {:<12}{}", "", header);
+    for (slot, action) in ACTION_TAXONOMY.iter().enumerate() {
+        if points.iter().all(|p| p.point.root[slot] < 0.0005) {
+            continue;
+        }
+        let row: String = points
+            .iter()
+            .map(|p| format!("{:>17.1}%", p.point.root[slot] * 100.0))
+            .collect();
+        println!("  {:<12}{}", action, row);
+    }
+
+    println!();
+    println!("  {}:", "Value".bold());
+    println!("  {:<12}{}", "", header);
+    print_value_row(points, "OOP equity", |p| p.oop_equity);
+    print_value_row(points, "OOP EV", |p| p.oop_ev_share);
+    print_value_row(points, "IP EV", |p| p.ip_ev_share);
+
+    println!();
+    println!("  {} by hand class (combos, dominant action):", "Strategy".bold());
+    println!("  {:<18}{}", "", header);
+    for category in categories(points) {
+        let row: String = points
+            .iter()
+            .map(|p| match p.point.categories.iter().find(|(c, _, _)| *c == category) {
+                Some((_, n, freqs)) => {
+                    let (slot, f) = freqs
+                        .iter()
+                        .copied()
+                        .enumerate()
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .unwrap_or((0, 0.0));
+                    format!("{:>18}", format!("{} {} {:.0}%", n, ACTION_TAXONOMY[slot], f * 100.0))
+                }
+                None => format!("{:>18}", "-"),
+            })
+            .collect();
+        println!("  {:<18}{}", category.label(false), row);
+    }
+
+    let diffs = largest_differences(points, TOP_DIFFERENCES);
+    if !diffs.is_empty() {
+        println!();
+        println!("  {} texture-driven differences:", "Largest".bold());
+        for d in &diffs {
+            println!(
+                "  {:<18}{:<12}{:>6}  {} {:.0}% -> {} {:.0}%",
+                d.scope,
+                ACTION_TAXONOMY[d.slot],
+                format!("{:+.0}pp", d.spread() * 100.0).yellow(),
+                d.low.0.as_str(),
+                d.low.1 * 100.0,
+                d.high.0.as_str(),
+                d.high.1 * 100.0,
+            );
+        }
+    }
+    println!();
+}
+
+fn print_value_row(points: &[TexturePoint], label: &str, value: impl Fn(&TexturePoint) -> f64) {
+    let row: String = points
+        .iter()
+        .map(|p| format!("{:>17.1}%", value(p) * 100.0))
+        .collect();
+    println!("  {:<12}{}", label, row);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(texture: SuitTexture, root: [f64; 6], categories: Vec<(HandClass, [f64; 6])>) -> TexturePoint {
+        TexturePoint {
+            texture,
+            board: String::new(),
+            point: SweepPoint {
+                stack: 100.0,
+                spr: 16.7,
+                root,
+                categories: categories.into_iter().map(|(c, f)| (c, 1, f)).collect(),
+            },
+            oop_equity: 0.5,
+            oop_ev_share: 0.5,
+            ip_ev_share: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_largest_differences_ranks_by_spread() {
+        let flush = HandClass::Flush;
+        let pair = HandClass::TopPair;
+        let points = vec![
+            point(SuitTexture::Rainbow, [0.6, 0.4, 0.0, 0.0, 0.0, 0.0], vec![(pair, [0.5, 0.5, 0.0, 0.0, 0.0, 0.0])]),
+            point(SuitTexture::TwoTone, [0.7, 0.3, 0.0, 0.0, 0.0, 0.0], vec![(pair, [0.8, 0.2, 0.0, 0.0, 0.0, 0.0])]),
+            point(
+                SuitTexture::Monotone,
+                [0.9, 0.1, 0.0, 0.0, 0.0, 0.0],
+                vec![(pair, [0.9, 0.1, 0.0, 0.0, 0.0, 0.0]), (flush, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0])],
+            ),
+        ];
+        let diffs = largest_differences(&points, 3);
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(diffs[0].scope, pair.label(false));
+        assert_eq!(diffs[0].low, (SuitTexture::Rainbow, 0.5));
+        assert_eq!(diffs[0].high, (SuitTexture::Monotone, 0.9));
+        assert!((diffs[2].spread() - 0.3).abs() < 1e-9);
+        // Flush only exists on the monotone board, so it has nothing to compare
+        assert!(largest_differences(&points, 100).iter().all(|d| d.scope != flush.label(false)));
+    }
+}
@@ -0,0 +1,304 @@
+//! Per-texture aggregation of cached flop solutions (`gto report flops`).
+//!
+//! Every cached flop solution at the requested depth is summarized through the
+//! cache index (root frequencies weighted by each combo's range frequency),
+//! mapped onto the `features::ACTION_TAXONOMY` slots, and averaged per board
+//! texture class. Each board lands in one class per dimension (suits, pairing,
+//! wetness, high card), with every spot counted once. Files that can't be read
+//! are skipped with a warning.
+
+use colored::Colorize;
+
+use crate::cache_index::{solver_cache_dir, CacheIndex, IndexRecord};
+use crate::cards::parse_board;
+use crate::features::canonical_strategy;
+use crate::flop_solver::flop_tree_config;
+use crate::postflop::{analyze_board, BoardTexture, Wetness};
+use crate::postflop_tree::{build_tree, Action, TreeNode};
+use crate::strategy::PotType;
+
+const POT_TYPES: [PotType; 3] = [PotType::Srp, PotType::ThreeBet, PotType::FourBet];
+
+/// Texture classes, in display order.
+pub const TEXTURE_CLASSES: [&str; 12] = [
+    "Monotone",
+    "Two-tone",
+    "Rainbow",
+    "Paired",
+    "Unpaired",
+    "Dry",
+    "Medium",
+    "Wet",
+    "Ace-high",
+    "King-high",
+    "Queen-high",
+    "Jack-high or lower",
+];
+
+/// The classes a board belongs to: one per dimension.
+pub fn texture_classes(texture: &BoardTexture) -> [&'static str; 4] {
+    let suits = if texture.is_monotone {
+        "Monotone"
+    } else if texture.is_two_tone {
+        "Two-tone"
+    } else {
+        "Rainbow"
+    };
+    let pairing = if texture.is_paired { "Paired" } else { "Unpaired" };
+    let wetness = match texture.wetness {
+        Wetness::Dry => "Dry",
+        Wetness::Medium => "Medium",
+        Wetness::Wet => "Wet",
+    };
+    let high = match texture.high_card {
+        'A' => "Ace-high",
+        'K' => "King-high",
+        'Q' => "Queen-high",
+        _ => "Jack-high or lower",
+    };
+    [suits, pairing, wetness, high]
+}
+
+/// One solution's root: taxonomy slots, total bet frequency, and the bet
+/// frequency with its size in pot fractions summed over bet actions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootSummary {
+    pub slots: [f64; 6],
+    pub bet_freq: f64,
+    pub sized_bets: f64,
+}
+
+impl RootSummary {
+    /// Summarize an indexed solution's root. Errors when the root no longer
+    /// matches the flop tree (e.g. a solve from an older tree template).
+    pub fn from_record(record: &IndexRecord) -> Result<RootSummary, String> {
+        let (tree, _) = build_tree(&flop_tree_config(record.pot, record.stack));
+        let TreeNode::Action { actions, .. } = &tree else {
+            return Err("Flop tree has no root decision".to_string());
+        };
+        if actions.len() != record.root_frequencies.len() {
+            return Err(format!(
+                "{} has {} root actions, the flop tree has {}",
+                record.file,
+                record.root_frequencies.len(),
+                actions.len()
+            ));
+        }
+        let mut bet_freq = 0.0;
+        let mut sized_bets = 0.0;
+        for (action, &f) in actions.iter().zip(&record.root_frequencies) {
+            if let Action::Bet(amount) = action {
+                bet_freq += f;
+                sized_bets += f * amount / record.pot.max(1e-9);
+            }
+        }
+        Ok(RootSummary {
+            slots: canonical_strategy(actions, &record.root_frequencies, record.pot, record.stack),
+            bet_freq,
+            sized_bets,
+        })
+    }
+}
+
+/// Root frequencies summed over the spots of one texture class.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextureAggregate {
+    pub spots: usize,
+    slots: [f64; 6],
+    bet_freq: f64,
+    sized_bets: f64,
+}
+
+impl TextureAggregate {
+    pub fn add(&mut self, root: &RootSummary) {
+        self.spots += 1;
+        for (s, f) in self.slots.iter_mut().zip(&root.slots) {
+            *s += f;
+        }
+        self.bet_freq += root.bet_freq;
+        self.sized_bets += root.sized_bets;
+    }
+
+    /// Average frequency of each `ACTION_TAXONOMY` slot.
+    pub fn frequencies(&self) -> [f64; 6] {
+        let n = self.spots.max(1) as f64;
+        self.slots.map(|s| s / n)
+    }
+
+    /// Average bet frequency, all-ins included.
+    pub fn bet_frequency(&self) -> f64 {
+        self.bet_freq / self.spots.max(1) as f64
+    }
+
+    /// Average bet size as a fraction of the pot, weighted by how often each
+    /// size is used; `None` when no spot bets.
+    pub fn average_bet_size(&self) -> Option<f64> {
+        (self.bet_freq > 1e-12).then(|| self.sized_bets / self.bet_freq)
+    }
+}
+
+/// True when a cached spot is one of the batch pot types at `stack` bb.
+pub fn matches_depth(record: &IndexRecord, stack: f64) -> bool {
+    POT_TYPES.iter().any(|p| {
+        let (pot, eff_stack) = p.pot_and_stack_at(stack);
+        (pot - record.pot).abs() < 0.01 && (eff_stack - record.stack).abs() < 0.01
+    })
+}
+
+/// Aggregates per class in `TEXTURE_CLASSES` order, and the number of spots.
+pub type TextureReport = (Vec<(&'static str, TextureAggregate)>, usize);
+
+/// Aggregate the given flop cache files in `index` at `stack` bb. Files that
+/// can't be read or summarized are returned with the reason instead.
+pub fn aggregate_textures(index: &mut CacheIndex, files: &[String], stack: f64) -> (TextureReport, Vec<String>) {
+    let mut classes: Vec<(&'static str, TextureAggregate)> =
+        TEXTURE_CLASSES.iter().map(|&c| (c, TextureAggregate::default())).collect();
+    let mut spots = 0usize;
+    let mut skipped = Vec::new();
+    for file in files {
+        let record = match index.load_summary(file) {
+            Ok(r) => r,
+            Err(e) => {
+                skipped.push(format!("{}: {}", file, e));
+                continue;
+            }
+        };
+        if !matches_depth(&record, stack) {
+            continue;
+        }
+        let summary = parse_board(&record.board)
+            .map_err(|e| e.to_string())
+            .and_then(|cards| analyze_board(&cards).map_err(|e| e.to_string()))
+            .and_then(|texture| Ok((texture, RootSummary::from_record(&record)?)));
+        let (texture, root) = match summary {
+            Ok(s) => s,
+            Err(e) => {
+                skipped.push(format!("{}: {}", file, e));
+                continue;
+            }
+        };
+        spots += 1;
+        for class in texture_classes(&texture) {
+            if let Some((_, agg)) = classes.iter_mut().find(|(c, _)| *c == class) {
+                agg.add(&root);
+            }
+        }
+    }
+    ((classes, spots), skipped)
+}
+
+/// `gto report flops`: root frequencies per board texture over every cached
+/// flop solution at `stack` bb.
+pub fn run_flop_texture_report(stack: f64) {
+    use comfy_table::{Cell, CellAlignment, ContentArrangement, Table};
+
+    let dir = solver_cache_dir();
+    let mut index = CacheIndex::load(&dir);
+    let mut files: Vec<String> = std::fs::read_dir(&dir)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok()?.file_name().into_string().ok())
+                .filter(|n| n.starts_with("flop_") && n.ends_with(".bin"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    let ((classes, spots), skipped) = aggregate_textures(&mut index, &files, stack);
+    println!();
+    for reason in &skipped {
+        println!("  {} skipping {}", "Warning:".yellow().bold(), reason);
+    }
+    println!(
+        "  {} Flop textures  |  {}bb  |  {} cached solutions",
+        "GTO".bold(),
+        stack,
+        spots.to_string().bold()
+    );
+    if spots == 0 {
+        println!();
+        println!("  No cached flops at this depth. Run `gto solve batch` first.");
+        println!();
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Texture", "Spots", "Check", "Bet", "Small", "Medium", "Large", "All-in", "Avg bet"]);
+    let pct = |f: f64| Cell::new(format!("{:.1}%", f * 100.0)).set_alignment(CellAlignment::Right);
+    for (class, agg) in classes.iter().filter(|(_, a)| a.spots > 0) {
+        let f = agg.frequencies();
+        let avg = agg.average_bet_size().map_or("-".to_string(), |s| format!("{:.0}% pot", s * 100.0));
+        table.add_row(vec![
+            Cell::new(class),
+            Cell::new(agg.spots).set_alignment(CellAlignment::Right),
+            pct(f[0]),
+            pct(agg.bet_frequency()),
+            pct(f[1]),
+            pct(f[2]),
+            pct(f[3]),
+            pct(f[4]),
+            Cell::new(avg).set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!();
+    for line in table.to_string().lines() {
+        println!("  {}", line);
+    }
+    println!(
+        "  {}",
+        "OOP root frequencies averaged per spot; Bet includes all-ins, Avg bet weights each size by how often it is used.".dimmed()
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_averages_spots_and_weights_bet_size_by_frequency() {
+        let mut agg = TextureAggregate::default();
+        assert_eq!(agg.average_bet_size(), None);
+        agg.add(&RootSummary { slots: [0.5, 0.5, 0.0, 0.0, 0.0, 0.0], bet_freq: 0.5, sized_bets: 0.5 * 0.33 });
+        agg.add(&RootSummary { slots: [0.9, 0.0, 0.1, 0.0, 0.0, 0.0], bet_freq: 0.1, sized_bets: 0.1 * 0.75 });
+        assert_eq!(agg.spots, 2);
+        assert!((agg.frequencies()[0] - 0.7).abs() < 1e-12);
+        assert!((agg.bet_frequency() - 0.3).abs() < 1e-12);
+        let expected = (0.5 * 0.33 + 0.1 * 0.75) / 0.6;
+        assert!((agg.average_bet_size().unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_corrupt_files_are_skipped_and_other_depths_ignored() {
+        use crate::flop_solver::{solve_flop, FlopSolverConfig};
+
+        let dir = std::env::temp_dir().join(format!("gto-texture-report-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, pot, stack) in [("flop_a.bin", 6.0, 97.0), ("flop_b.bin", 10.0, 50.0)] {
+            let config = FlopSolverConfig::new("Ks9d4c", "AA,KK", "QQ,JJ", pot, stack, 200).unwrap();
+            std::fs::write(dir.join(file), bincode::serialize(&solve_flop(&config)).unwrap()).unwrap();
+        }
+        std::fs::write(dir.join("flop_c.bin"), b"not a solution").unwrap();
+
+        let files: Vec<String> = ["flop_a.bin", "flop_b.bin", "flop_c.bin"].iter().map(|f| f.to_string()).collect();
+        let ((classes, spots), skipped) = aggregate_textures(&mut CacheIndex::load(&dir), &files, 100.0);
+        assert_eq!(spots, 1);
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].starts_with("flop_c.bin"));
+        let rainbow = &classes.iter().find(|(c, _)| *c == "Rainbow").unwrap().1;
+        assert_eq!(rainbow.spots, 1);
+        assert!((rainbow.frequencies().iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert_eq!(classes.iter().find(|(c, _)| *c == "Monotone").unwrap().1.spots, 0);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_texture_classes_one_per_dimension() {
+        let texture = |b: &str| analyze_board(&parse_board(b).unwrap()).unwrap();
+        assert_eq!(texture_classes(&texture("AsKs2s")), ["Monotone", "Unpaired", "Wet", "Ace-high"]);
+        assert_eq!(texture_classes(&texture("Ks9d4c"))[..2], ["Rainbow", "Unpaired"]);
+        assert_eq!(texture_classes(&texture("8h8d3h"))[..2], ["Two-tone", "Paired"]);
+        assert_eq!(texture_classes(&texture("9h8d3c"))[3], "Jack-high or lower");
+    }
+}
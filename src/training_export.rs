@@ -0,0 +1,290 @@
+//! Training-data export: samples (state, strategy) pairs from cached flop
+//! solutions for imitation learning, one JSON object per line (NDJSON).
+//!
+//! Each line has:
+//!   `board`      flop cards, e.g. "Ks9d4c"
+//!   `line`       action labels from the flop root to the sampled node
+//!   `pot`        pot at the node (bb)
+//!   `stack`      acting player's remaining stack at the node (bb)
+//!   `hero`       "OOP" or "IP"
+//!   `hero_pos`   table position of the acting player ("" if the solve had none)
+//!   `hole`       acting player's hole cards, e.g. "AhKd"
+//!   `features`   `features::FEATURE_LEN` floats (layout in `features`)
+//!   `strategy`   solver frequencies over `features::ACTION_TAXONOMY`
+//!
+//! (node, combo) pairs are drawn proportionally to reach: the combo's own
+//! probability of taking the line times the opponent range's average
+//! probability of taking it. Output is deterministic for a given seed and spot list.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::cache_index::solver_cache_dir;
+use crate::card_encoding::card_to_index;
+use crate::cards::parse_board;
+use crate::display::print_failure;
+use crate::features::{canonical_strategy, encode_state, ACTION_TAXONOMY};
+use crate::flop_solver::FlopSolution;
+use crate::postflop_tree::{build_tree, Action, Player, TreeNode};
+use crate::status::ExitCode;
+
+/// One spot in a `--spots` manifest file (a JSON array of these).
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotKey {
+    pub board: String,
+    pub oop_pos: String,
+    pub ip_pos: String,
+    pub pot: f64,
+    pub stack: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrainingSample {
+    pub board: String,
+    pub line: Vec<String>,
+    pub pot: f64,
+    pub stack: f64,
+    pub hero: String,
+    pub hero_pos: String,
+    pub hole: String,
+    pub features: Vec<f32>,
+    pub strategy: [f64; 6],
+}
+
+/// A flop decision node with each player's per-combo reach.
+struct ReachNode<'a> {
+    player: Player,
+    line: Vec<String>,
+    pot: f64,
+    stack: f64,
+    actions: &'a [Action],
+    node_id: u16,
+    /// Acting player's per-combo probability of taking `line`.
+    own_reach: Vec<f64>,
+    /// Opponent range's average probability of taking `line`.
+    opp_reach: f64,
+}
+
+/// Sample `samples` (state, strategy) pairs from one solution.
+pub fn sample_solution(solution: &FlopSolution, samples: usize, rng: &mut StdRng) -> Vec<TrainingSample> {
+    let board: Vec<u8> = match parse_board(&solution.board) {
+        Ok(cards) => cards.iter().map(card_to_index).collect(),
+        Err(_) => return vec![],
+    };
+    let (tree, _) = build_tree(&solution.tree_config());
+
+    let mut nodes = Vec::new();
+    let oop_reach = vec![1.0; solution.oop_combos.len()];
+    let ip_reach = vec![1.0; solution.ip_combos.len()];
+    collect_reach_nodes(&tree, solution, Vec::new(), oop_reach, ip_reach, &mut nodes);
+
+    // Flatten to (node, combo) candidates weighted by reach
+    let mut candidates = Vec::new();
+    let mut weights = Vec::new();
+    for (n, node) in nodes.iter().enumerate() {
+        for (h, &r) in node.own_reach.iter().enumerate() {
+            let w = r * node.opp_reach;
+            if w > 1e-9 {
+                candidates.push((n, h));
+                weights.push(w);
+            }
+        }
+    }
+    let dist = match WeightedIndex::new(&weights) {
+        Ok(d) => d,
+        Err(_) => return vec![],
+    };
+
+    (0..samples)
+        .filter_map(|_| {
+            let (n, h) = candidates[dist.sample(rng)];
+            let node = &nodes[n];
+            let strat = solution.strategies.iter().find(|s| s.node_id == node.node_id)?;
+            let (hero, hero_pos, combos) = match node.player {
+                Player::OOP => ("OOP", &solution.oop_pos, &solution.oop_combos),
+                Player::IP => ("IP", &solution.ip_pos, &solution.ip_combos),
+            };
+            let hole_cards = parse_board(&combos[h]).ok()?;
+            let hole = [card_to_index(&hole_cards[0]), card_to_index(&hole_cards[1])];
+            Some(TrainingSample {
+                board: solution.board.clone(),
+                line: node.line.clone(),
+                pot: node.pot,
+                stack: node.stack,
+                hero: hero.to_string(),
+                hero_pos: hero_pos.clone(),
+                hole: combos[h].clone(),
+                features: encode_state(&board, hole, node.pot, node.stack, node.player == Player::IP),
+                strategy: canonical_strategy(node.actions, &strat.frequencies[h], node.pot, node.stack),
+            })
+        })
+        .collect()
+}
+
+fn collect_reach_nodes<'a>(
+    node: &'a TreeNode,
+    solution: &FlopSolution,
+    line: Vec<String>,
+    oop_reach: Vec<f64>,
+    ip_reach: Vec<f64>,
+    out: &mut Vec<ReachNode<'a>>,
+) {
+    let TreeNode::Action { node_id, player, pot, stacks, actions, children } = node else {
+        return;
+    };
+    let strat = match solution.strategies.iter().find(|s| s.node_id == *node_id) {
+        Some(s) if s.actions.len() == actions.len() => s,
+        _ => return,
+    };
+    let (own, opp) = match player {
+        Player::OOP => (&oop_reach, &ip_reach),
+        Player::IP => (&ip_reach, &oop_reach),
+    };
+    out.push(ReachNode {
+        player: *player,
+        line: line.clone(),
+        pot: *pot,
+        stack: stacks[player.index()],
+        actions,
+        node_id: *node_id,
+        own_reach: own.clone(),
+        opp_reach: opp.iter().sum::<f64>() / opp.len().max(1) as f64,
+    });
+
+    for (a, (action, child)) in actions.iter().zip(children).enumerate() {
+        let next: Vec<f64> = own
+            .iter()
+            .zip(&strat.frequencies)
+            .map(|(r, freqs)| r * freqs[a])
+            .collect();
+        let (next_oop, next_ip) = match player {
+            Player::OOP => (next, ip_reach.clone()),
+            Player::IP => (oop_reach.clone(), next),
+        };
+        let mut next_line = line.clone();
+        next_line.push(action.label());
+        collect_reach_nodes(child, solution, next_line, next_oop, next_ip, out);
+    }
+}
+
+/// Cached flop solutions for a manifest, or every cached flop solution when
+/// `manifest` is `None` (sorted by file name so the order is stable).
+pub fn load_spots(manifest: Option<&Path>) -> Result<Vec<FlopSolution>, String> {
+    match manifest {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+            let keys: Vec<SpotKey> = serde_json::from_str(&text)
+                .map_err(|e| format!("Invalid spot manifest {}: {}", path.display(), e))?;
+            Ok(keys
+                .iter()
+                .filter_map(|k| FlopSolution::load_cache(&k.board, &k.oop_pos, &k.ip_pos, k.pot, k.stack, None))
+                .collect())
+        }
+        None => {
+            let dir = solver_cache_dir();
+            let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+                .map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("flop_") && n.ends_with(".bin"))
+                })
+                .collect();
+            files.sort();
+            Ok(files.iter().filter_map(|p| FlopSolution::load_file(p).ok()).collect())
+        }
+    }
+}
+
+/// `gto export-training`: write sampled (state, strategy) pairs as NDJSON.
+pub fn run_export(spots: Option<String>, out: String, samples_per_spot: usize, seed: u64) {
+    let solutions = match load_spots(spots.as_deref().map(Path::new)) {
+        Ok(s) => s,
+        Err(e) => {
+            print_failure(ExitCode::CacheIo, &e);
+            return;
+        }
+    };
+    if solutions.is_empty() {
+        print_failure(ExitCode::CacheIo, "No cached flop solutions found for these spots.");
+        return;
+    }
+
+    let file = match std::fs::File::create(&out) {
+        Ok(f) => f,
+        Err(e) => {
+            print_failure(ExitCode::CacheIo, &format!("Cannot create {}: {}", out, e));
+            return;
+        }
+    };
+    let mut writer = std::io::BufWriter::new(file);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut written = 0usize;
+    for solution in &solutions {
+        for sample in sample_solution(solution, samples_per_spot, &mut rng) {
+            if let Ok(line) = serde_json::to_string(&sample) {
+                if writeln!(writer, "{}", line).is_err() {
+                    print_failure(ExitCode::CacheIo, &format!("Failed writing {}", out));
+                    return;
+                }
+                written += 1;
+            }
+        }
+    }
+    writer.flush().ok();
+
+    println!();
+    println!(
+        "  {} Exported {} samples from {} spots to {}",
+        "GTO".bold(),
+        written.to_string().bold(),
+        solutions.len(),
+        out
+    );
+    println!("  Strategy slots: {}", ACTION_TAXONOMY.join(", "));
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::FEATURE_LEN;
+    use crate::flop_solver::{solve_flop, FlopSolverConfig};
+
+    fn small_solution() -> FlopSolution {
+        let config = FlopSolverConfig::new("Ks9d4c", "AA,KK", "QQ,JJ", 10.0, 50.0, 300).unwrap();
+        solve_flop(&config)
+    }
+
+    #[test]
+    fn test_sampling_is_deterministic_for_seed() {
+        let solution = small_solution();
+        let a = sample_solution(&solution, 200, &mut StdRng::seed_from_u64(7));
+        let b = sample_solution(&solution, 200, &mut StdRng::seed_from_u64(7));
+        let json = |s: &[TrainingSample]| serde_json::to_string(s).unwrap();
+        assert_eq!(a.len(), 200);
+        assert_eq!(json(&a), json(&b));
+    }
+
+    #[test]
+    fn test_samples_are_well_formed() {
+        let solution = small_solution();
+        let samples = sample_solution(&solution, 300, &mut StdRng::seed_from_u64(1));
+        assert!(samples.iter().any(|s| s.line.is_empty()), "root is reachable");
+        assert!(samples.iter().any(|s| !s.line.is_empty()), "later nodes are sampled");
+        for s in &samples {
+            assert_eq!(s.features.len(), FEATURE_LEN);
+            assert!((s.strategy.iter().sum::<f64>() - 1.0).abs() < 1e-3, "{:?}", s.strategy);
+            let combos = if s.hero == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+            assert!(combos.contains(&s.hole));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Navigation state for walking a solved street's action tree, as `gto
+//! browse` does: the path of actions taken from the root, the action
+//! selected at the current decision, and moving down into it or back up.
+//!
+//! Only action nodes are stops: an action that folds or closes the street
+//! can be selected but not entered.
+
+use crate::postflop_tree::{Player, TerminalType, TreeNode};
+
+/// A position in an action tree.
+#[derive(Debug)]
+pub struct TreeNav {
+    root: TreeNode,
+    /// Index of the action taken at each decision from the root.
+    path: Vec<usize>,
+    /// Action selected at the current decision.
+    selected: usize,
+}
+
+impl TreeNav {
+    /// Start at `root`, which must be an action node.
+    pub fn new(root: TreeNode) -> Result<Self, String> {
+        match root {
+            TreeNode::Action { .. } => Ok(TreeNav { root, path: Vec::new(), selected: 0 }),
+            _ => Err("The tree has no decisions".to_string()),
+        }
+    }
+
+    /// The decision at the end of the path.
+    pub fn node(&self) -> &TreeNode {
+        self.path.iter().fold(&self.root, |node, &a| match node {
+            TreeNode::Action { children, .. } => &children[a],
+            _ => unreachable!("the path only passes through action nodes"),
+        })
+    }
+
+    pub fn node_id(&self) -> u16 {
+        match self.node() {
+            TreeNode::Action { node_id, .. } => *node_id,
+            _ => unreachable!("the path ends on an action node"),
+        }
+    }
+
+    pub fn player(&self) -> Player {
+        match self.node() {
+            TreeNode::Action { player, .. } => *player,
+            _ => unreachable!("the path ends on an action node"),
+        }
+    }
+
+    /// Labels of the legal actions at the current decision ("Bet 3.3").
+    pub fn actions(&self) -> Vec<String> {
+        match self.node() {
+            TreeNode::Action { actions, .. } => actions.iter().map(|a| a.label()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the next action, stopping at the last.
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.actions().len() - 1);
+    }
+
+    /// Select the previous action, stopping at the first.
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Take the selected action. It fails, leaving the position as it was,
+    /// when the action folds or closes the street.
+    pub fn descend(&mut self) -> Result<(), String> {
+        let TreeNode::Action { actions, children, .. } = self.node() else {
+            unreachable!("the path ends on an action node");
+        };
+        let label = actions[self.selected].label();
+        match &children[self.selected] {
+            TreeNode::Action { .. } => {
+                self.path.push(self.selected);
+                self.selected = 0;
+                Ok(())
+            }
+            TreeNode::Terminal { terminal_type: TerminalType::Fold { .. }, .. } => {
+                Err(format!("{} ends the hand", label))
+            }
+            _ => Err(format!("{} closes the street", label)),
+        }
+    }
+
+    /// Go back to the previous decision with the action taken there
+    /// selected; false at the root.
+    pub fn ascend(&mut self) -> bool {
+        match self.path.pop() {
+            Some(a) => {
+                self.selected = a;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Labels of the actions taken from the root; empty at the root.
+    pub fn line(&self) -> Vec<String> {
+        let mut node = &self.root;
+        let mut line = Vec::with_capacity(self.path.len());
+        for &a in &self.path {
+            let TreeNode::Action { actions, children, .. } = node else {
+                unreachable!("the path only passes through action nodes");
+            };
+            line.push(actions[a].label());
+            node = &children[a];
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::postflop_tree::{build_tree, TreeConfig};
+
+    fn nav() -> TreeNav {
+        let config = TreeConfig {
+            bet_sizes: vec![0.5],
+            raise_sizes: vec![],
+            max_raises: 0,
+            starting_pot: 10.0,
+            effective_stack: 100.0,
+            add_allin: false,
+        };
+        TreeNav::new(build_tree(&config).0).unwrap()
+    }
+
+    #[test]
+    fn descends_into_decisions_and_back_up() {
+        let mut nav = nav();
+        assert_eq!((nav.node_id(), nav.player()), (0, Player::OOP));
+        assert_eq!(nav.actions(), vec!["Check", "Bet 5.0"]);
+
+        nav.select_next();
+        nav.select_next();
+        assert_eq!(nav.selected(), 1);
+        nav.descend().unwrap();
+        assert_eq!(nav.player(), Player::IP);
+        assert_eq!(nav.line(), vec!["Bet 5.0"]);
+        assert_eq!(nav.actions(), vec!["Fold", "Call 5.0"]);
+        assert_eq!(nav.selected(), 0);
+
+        // Folding and calling end the street
+        assert_eq!(nav.descend().unwrap_err(), "Fold ends the hand");
+        nav.select_next();
+        assert_eq!(nav.descend().unwrap_err(), "Call 5.0 closes the street");
+        assert_eq!(nav.line(), vec!["Bet 5.0"]);
+
+        assert!(nav.ascend());
+        assert_eq!((nav.node_id(), nav.selected()), (0, 1));
+        assert!(nav.line().is_empty());
+        assert!(!nav.ascend());
+        nav.select_prev();
+        nav.select_prev();
+        assert_eq!(nav.selected(), 0);
+    }
+}
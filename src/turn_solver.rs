@@ -1,23 +1,41 @@
 //! Turn CFR+ solver.
 //!
 //! Solves heads-up turn spots using CFR+ over a turn+river game tree.
-//! At chance nodes (river card dealt), hand strengths are re-evaluated
-//! and blocker-aware reach probabilities are updated.
+//! At chance nodes (river card dealt), hand strengths and blocker-aware
+//! validity come from per-river tables built once before the iterations,
+//! and reach probabilities are updated for the card.
 //!
 //! Uses `FlatCfr` for memory-efficient storage (~5x vs HashMap-based)
 //! and two separate instances (one per player) to avoid borrow conflicts.
 
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::cache_index::{solver_cache_dir, write_atomic};
+use crate::cache_key::{range_key, read_spot_cache};
 use crate::card_encoding::{card_to_index, index_to_card};
 use crate::cards::parse_board;
-use crate::flat_cfr::FlatCfr;
-use crate::lookup_eval::evaluate_fast;
+use crate::cfr::CfrVariant;
+use crate::error::{SolverError, SolverResult};
+use crate::display::{print_combo_strategies, range_summary_table};
+use crate::flat_cfr::{FlatCfr, RegretUpdate};
+use crate::flop_enumerator::{canonical_board, relabel_suits, suit_permutation};
+use crate::lookup_eval::evaluate_many;
 use crate::postflop_tree::{
-    build_turn_tree, collect_node_metadata, Player, TerminalType, TreeNode, TurnTreeConfig,
+    build_tree, build_turn_tree, collect_node_metadata, path_to_node, raise_suffix, turn_tree_size, Player, TerminalType, TreeConfig,
+    TreeNode, TurnTreeConfig, DEFAULT_MAX_TREE_NODES, MAX_NUMBERED_NODES,
+};
+use crate::postprocess::{combo_equities, hand_class, PostProcess, PostProcessInfo, Simplify, SimplifyInfo};
+use crate::progress::{PhaseTimer, ProgressCallback, ProgressTracker, SolveStats};
+use crate::quality::{pct_of_pot, SolveQuality, RECOMMENDED_TURN_ITERATIONS};
+use crate::breakdown::{breakdown, Breakdown};
+use crate::range_summary::{combo_weights, summarize, RangeSummary, WeightedCombos};
+use crate::ranges::format_weighted_range;
+use crate::river_solver::{
+    parse_spot, parse_spot_board, ParsedSpot,
+    find_combo_index, expand_range_to_combos, expand_weighted_range_to_combos, combo_blockers, outcome_shares, parse_combo_strings, relabel_combos, valid_opponents, reorder_rows, showdown_outcome_mass,
+    Combo, ShowdownOutcome, COMBO_ORDER_VERSION,
 };
-use crate::ranges::parse_range;
-use crate::river_solver::{expand_range_to_combos, Combo};
 
 // ---------------------------------------------------------------------------
 // Config & result
@@ -26,11 +44,45 @@ use crate::river_solver::{expand_range_to_combos, Combo};
 pub struct TurnSolverConfig {
     /// 4-card turn board as u8 indices.
     pub board: Vec<u8>,
+    /// Cards known to be out of the deck (`--dead`): combos holding one are
+    /// left out and no river deals one.
+    pub dead_cards: Vec<u8>,
     pub oop_range: Vec<String>,
     pub ip_range: Vec<String>,
+    /// Frequency (0 to 1) of each hand in `oop_range`, e.g. 0.5 for `AKo:0.5`.
+    pub oop_weights: Vec<f64>,
+    /// Frequency of each hand in `ip_range`.
+    pub ip_weights: Vec<f64>,
     pub starting_pot: f64,
     pub effective_stack: f64,
     pub iterations: usize,
+    /// Smoothing/purification applied to turn-level strategies before extraction.
+    pub postprocess: Option<PostProcess>,
+    /// Measure exploitability every this many iterations (see `progress`).
+    pub progress_interval: Option<usize>,
+    /// Called with each progress snapshot.
+    pub on_progress: Option<ProgressCallback>,
+    /// Stop once exploitability is at or below this percent of the starting
+    /// pot; `iterations` is then a cap.
+    pub target_exploitability: Option<f64>,
+    /// Stop once this much wall time has gone into iterating; `iterations`
+    /// is then a cap (see `progress`).
+    pub time_budget: Option<std::time::Duration>,
+    /// Regret-minimization variant (CFR+ by default).
+    pub cfr_variant: CfrVariant,
+    /// Keep the solved river strategies in `river_strategies` (off for
+    /// `--no-rivers`, which keeps cached solves small).
+    pub extract_rivers: bool,
+    /// Turn raise sizes as fractions of the pot after calling.
+    pub raise_sizes: Vec<f64>,
+    /// Most raises in a turn betting round.
+    pub max_raises: usize,
+    /// River raise sizes, like `raise_sizes`.
+    pub river_raise_sizes: Vec<f64>,
+    /// Most raises in a river betting round.
+    pub river_max_raises: usize,
+    /// Most decision nodes the tree may have (see `validate_tree_size`).
+    pub max_tree_nodes: usize,
 }
 
 impl TurnSolverConfig {
@@ -41,30 +93,97 @@ impl TurnSolverConfig {
         starting_pot: f64,
         effective_stack: f64,
         iterations: usize,
-    ) -> Result<Self, String> {
-        let board_cards = parse_board(board_str).map_err(|e| e.to_string())?;
-        if board_cards.len() != 4 {
-            return Err("Turn board must have exactly 4 cards".to_string());
-        }
-        let board: Vec<u8> = board_cards.iter().map(|c| card_to_index(c)).collect();
-        let oop_range = parse_range(oop_range_str);
-        let ip_range = parse_range(ip_range_str);
+    ) -> SolverResult<Self> {
+        crate::limits::TURN_ITERATIONS.check_config(iterations)?;
+        let spot = parse_spot(board_str, 4, oop_range_str, ip_range_str)?;
+        Ok(Self::for_spot(spot, starting_pot, effective_stack, iterations))
+    }
 
-        if oop_range.is_empty() {
-            return Err("OOP range is empty".to_string());
-        }
-        if ip_range.is_empty() {
-            return Err("IP range is empty".to_string());
-        }
+    /// A config for ranges given combo by combo with each combo's reach, as
+    /// a drill-down solve hands them over from the flop (see
+    /// `FlopSolution::line_reach`). Combos the board blocks or that never
+    /// reach the turn are left out; each side's reaches are scaled so its
+    /// most likely combo plays in full, which leaves the solve unchanged.
+    pub fn from_combo_reach(
+        board_str: &str,
+        oop: &[(String, f64)],
+        ip: &[(String, f64)],
+        starting_pot: f64,
+        effective_stack: f64,
+        iterations: usize,
+    ) -> SolverResult<Self> {
+        crate::limits::TURN_ITERATIONS.check_config(iterations)?;
+        let board = parse_spot_board(board_str, 4)?;
+        let side = |reach: &[(String, f64)], side: &'static str| -> SolverResult<(Vec<String>, Vec<f64>)> {
+            let kept: Vec<&(String, f64)> = reach
+                .iter()
+                .filter(|(combo, r)| *r > 0.0 && !expand_range_to_combos(std::slice::from_ref(combo), &board).is_empty())
+                .collect();
+            let top = kept.iter().map(|(_, r)| *r).fold(0.0, f64::max);
+            if top <= 0.0 {
+                return Err(SolverError::EmptyRange { side });
+            }
+            Ok(kept.into_iter().map(|(combo, r)| (combo.clone(), r / top)).unzip())
+        };
+        let (oop_range, oop_weights) = side(oop, "OOP")?;
+        let (ip_range, ip_weights) = side(ip, "IP")?;
+        let spot = ParsedSpot { board, oop_range, oop_weights, ip_range, ip_weights };
+        Ok(Self::for_spot(spot, starting_pot, effective_stack, iterations))
+    }
 
-        Ok(TurnSolverConfig {
+    fn for_spot(spot: ParsedSpot, starting_pot: f64, effective_stack: f64, iterations: usize) -> Self {
+        let ParsedSpot { board, oop_range, oop_weights, ip_range, ip_weights } = spot;
+        TurnSolverConfig {
             board,
+            dead_cards: Vec::new(),
             oop_range,
             ip_range,
+            oop_weights,
+            ip_weights,
             starting_pot,
             effective_stack,
             iterations,
-        })
+            postprocess: None,
+            progress_interval: None,
+            on_progress: None,
+            target_exploitability: None,
+            time_budget: None,
+            cfr_variant: CfrVariant::CfrPlus,
+            extract_rivers: true,
+            raise_sizes: default_raise_sizes(),
+            max_raises: default_turn_max_raises(),
+            river_raise_sizes: default_raise_sizes(),
+            river_max_raises: default_river_max_raises(),
+            max_tree_nodes: DEFAULT_MAX_TREE_NODES,
+        }
+    }
+
+    /// Cache file name suffix of the tree's raising (see
+    /// `TurnSolution::raise_suffix`).
+    pub fn raise_suffix(&self) -> String {
+        turn_raise_suffix(&self.raise_sizes, self.max_raises, &self.river_raise_sizes, self.river_max_raises)
+    }
+
+    /// The turn+river tree the solve runs on.
+    pub fn tree_config(&self) -> TurnTreeConfig {
+        let mut tree = TurnTreeConfig::new(self.board.clone(), self.starting_pot, self.effective_stack);
+        tree.turn.raise_sizes = self.raise_sizes.clone();
+        tree.turn.max_raises = self.max_raises;
+        tree.river_raise_sizes = self.river_raise_sizes.clone();
+        tree.river_max_raises = self.river_max_raises;
+        tree.dead_cards = self.dead_cards.clone();
+        tree
+    }
+
+    /// The tree's decision node count, or an error when it's over
+    /// `max_tree_nodes` or too large to number.
+    pub fn validate_tree_size(&self) -> SolverResult<usize> {
+        let nodes = turn_tree_size(&self.tree_config());
+        let limit = self.max_tree_nodes.min(MAX_NUMBERED_NODES);
+        if nodes > limit {
+            return Err(SolverError::TreeTooLarge { nodes, limit });
+        }
+        Ok(nodes)
     }
 }
 
@@ -78,8 +197,22 @@ pub struct TurnNodeStrategy {
     pub frequencies: Vec<Vec<f64>>,
 }
 
+/// River strategies in the subtree one river card deals after one turn line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiverCardStrategies {
+    /// The river card, e.g. "7s".
+    pub card: String,
+    /// Turn actions that close the street into this subtree, e.g.
+    /// ["Check", "Bet 5.0", "Call"].
+    pub turn_line: Vec<String>,
+    /// Every river action node in the subtree, its root first. Rows are
+    /// indexed like `oop_combos`/`ip_combos`; those of combos holding `card`
+    /// are never reached.
+    pub strategies: Vec<TurnNodeStrategy>,
+}
+
 /// Full solution from the turn solver.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TurnSolution {
     pub board: String,
     pub oop_range: Vec<String>,
@@ -98,26 +231,124 @@ pub struct TurnSolution {
     /// IP position label (e.g. "BTN") — used in cache key.
     #[serde(default)]
     pub ip_pos: String,
+    /// Combo ordering used by `oop_combos`/`ip_combos` (see `COMBO_ORDER_VERSION`).
+    #[serde(default)]
+    pub combo_order_version: u32,
+    /// Set when `strategies` were post-processed; `exploitability` is then
+    /// that of the processed strategy.
+    #[serde(default)]
+    pub postprocess: Option<PostProcessInfo>,
+    /// Set on a copy whose strategies were rounded for display (see
+    /// `simplified`); such copies aren't cached.
+    #[serde(default)]
+    pub simplified: Option<SimplifyInfo>,
+    /// Solve quality grade (see `quality`), set when the solve finishes.
+    #[serde(default)]
+    pub quality: Option<SolveQuality>,
+    /// Per-combo showdown outcomes from the root over all rivers, indexed
+    /// like `oop_combos`.
+    #[serde(default)]
+    pub oop_showdown_outcomes: Vec<ShowdownOutcome>,
+    /// Per-combo showdown outcomes from the root over all rivers, indexed
+    /// like `ip_combos`.
+    #[serde(default)]
+    pub ip_showdown_outcomes: Vec<ShowdownOutcome>,
+    /// (iterations, exploitability) snapshots when solved with a
+    /// `progress_interval`, ending with the final value.
+    #[serde(default)]
+    pub convergence_history: Vec<(usize, f64)>,
+    /// Frequency of each hand in `oop_range`; empty for solves from before
+    /// weighted ranges, where every hand is in full.
+    #[serde(default)]
+    pub oop_weights: Vec<f64>,
+    /// Frequency of each hand in `ip_range`, like `oop_weights`.
+    #[serde(default)]
+    pub ip_weights: Vec<f64>,
+    /// `exploitability` as a percentage of the starting pot, comparable
+    /// across spots.
+    #[serde(default)]
+    pub exploitability_pct_pot: f64,
+    /// Solved river play, one entry per turn line and river card; empty for
+    /// `--no-rivers` solves.
+    #[serde(default)]
+    pub river_strategies: Vec<RiverCardStrategies>,
+    /// Turn raise sizes the tree was built with, as fractions of the pot
+    /// after calling.
+    #[serde(default = "default_raise_sizes")]
+    pub raise_sizes: Vec<f64>,
+    /// Most raises in a turn betting round.
+    #[serde(default = "default_turn_max_raises")]
+    pub max_raises: usize,
+    /// River raise sizes the tree was built with.
+    #[serde(default = "default_raise_sizes")]
+    pub river_raise_sizes: Vec<f64>,
+    /// Most raises in a river betting round.
+    #[serde(default = "default_river_max_raises")]
+    pub river_max_raises: usize,
+    /// Stopped by the config's `time_budget` instead of an iteration count.
+    /// Such a solve meets the quality floor whatever its iterations, since
+    /// solving again on the same machine would stop in the same place.
+    #[serde(default)]
+    pub time_limited: bool,
+}
+
+fn default_raise_sizes() -> Vec<f64> {
+    TreeConfig::default_turn(0.0, 0.0).raise_sizes
+}
+
+fn default_turn_max_raises() -> usize {
+    TreeConfig::default_turn(0.0, 0.0).max_raises
+}
+
+fn default_river_max_raises() -> usize {
+    TreeConfig::default_river(0.0, 0.0).max_raises
 }
 
 // ---------------------------------------------------------------------------
 // Solver
 // ---------------------------------------------------------------------------
 
+/// Traversers with fewer combos than this run sequentially; larger ranges
+/// traverse their combos in parallel.
+const PARALLEL_MIN_COMBOS: usize = 20;
+
 /// Solve a turn spot. Returns the full solution including exploitability.
+///
+/// Panics when the tree is over the config's node cap; `try_solve_turn`
+/// returns that as an error.
 pub fn solve_turn(config: &TurnSolverConfig) -> TurnSolution {
-    let tree_config = TurnTreeConfig::new(
-        config.board.clone(),
-        config.starting_pot,
-        config.effective_stack,
-    );
-    let (tree, _num_nodes) = build_turn_tree(&tree_config);
+    solve_turn_timed(config).0
+}
+
+/// `solve_turn`, also returning where the time went.
+pub fn solve_turn_timed(config: &TurnSolverConfig) -> (TurnSolution, SolveStats) {
+    try_solve_turn_timed(config).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// `solve_turn`, or an error when the tree fails `validate_tree_size`.
+pub fn try_solve_turn(config: &TurnSolverConfig) -> SolverResult<TurnSolution> {
+    Ok(try_solve_turn_timed(config)?.0)
+}
+
+/// `try_solve_turn`, also returning where the time went.
+pub fn try_solve_turn_timed(config: &TurnSolverConfig) -> SolverResult<(TurnSolution, SolveStats)> {
+    config.validate_tree_size()?;
+    Ok(solve_turn_with(config, PARALLEL_MIN_COMBOS))
+}
+
+fn solve_turn_with(config: &TurnSolverConfig, parallel_min_combos: usize) -> (TurnSolution, SolveStats) {
+    let mut timer = PhaseTimer::start();
+    let mut stats = SolveStats::default();
+    let (tree, _num_nodes) = build_turn_tree(&config.tree_config());
 
-    let oop_combos = expand_range_to_combos(&config.oop_range, &config.board);
-    let ip_combos = expand_range_to_combos(&config.ip_range, &config.board);
+    let blocked = [config.board.as_slice(), &config.dead_cards].concat();
+    let (oop_combos, oop_weights) =
+        expand_weighted_range_to_combos(&config.oop_range, &config.oop_weights, &blocked);
+    let (ip_combos, ip_weights) = expand_weighted_range_to_combos(&config.ip_range, &config.ip_weights, &blocked);
 
     if oop_combos.is_empty() || ip_combos.is_empty() {
-        return empty_solution(config);
+        stats.setup_ms = timer.lap();
+        return (empty_solution(config), stats);
     }
 
     // Collect node metadata and build FlatCfr instances per player
@@ -140,64 +371,62 @@ pub fn solve_turn(config: &TurnSolverConfig) -> TurnSolution {
         })
         .collect();
 
-    let mut oop_cfr = FlatCfr::new(&oop_nodes);
-    let mut ip_cfr = FlatCfr::new(&ip_nodes);
+    let mut oop_cfr = FlatCfr::with_variant(&oop_nodes, config.cfr_variant);
+    let mut ip_cfr = FlatCfr::with_variant(&ip_nodes, config.cfr_variant);
+    stats.setup_ms = timer.lap();
 
     // Precompute: blocker sets for each combo
-    let oop_blockers: Vec<[bool; 52]> = oop_combos
-        .iter()
-        .map(|c| {
-            let mut b = [false; 52];
-            b[c.0 as usize] = true;
-            b[c.1 as usize] = true;
-            b
-        })
-        .collect();
-    let ip_blockers: Vec<[bool; 52]> = ip_combos
-        .iter()
-        .map(|c| {
-            let mut b = [false; 52];
-            b[c.0 as usize] = true;
-            b[c.1 as usize] = true;
-            b
-        })
-        .collect();
+    let oop_blockers = combo_blockers(&oop_combos);
+    let ip_blockers = combo_blockers(&ip_combos);
 
     // Precompute: validity tables (which OOP combos are valid for each IP combo and vice versa)
-    let valid_ip_for_oop: Vec<Vec<u16>> = oop_combos
-        .iter()
-        .map(|oop| {
-            ip_combos
-                .iter()
-                .enumerate()
-                .filter(|(_, ip)| {
-                    oop.0 != ip.0 && oop.0 != ip.1 && oop.1 != ip.0 && oop.1 != ip.1
-                })
-                .map(|(j, _)| j as u16)
-                .collect()
-        })
-        .collect();
-    let valid_oop_for_ip: Vec<Vec<u16>> = ip_combos
-        .iter()
-        .map(|ip| {
-            oop_combos
-                .iter()
-                .enumerate()
-                .filter(|(_, oop)| {
-                    ip.0 != oop.0 && ip.0 != oop.1 && ip.1 != oop.0 && ip.1 != oop.1
-                })
-                .map(|(i, _)| i as u16)
-                .collect()
-        })
-        .collect();
+    let valid_ip_for_oop = valid_opponents(&oop_combos, &ip_combos);
+    let valid_oop_for_ip = valid_opponents(&ip_combos, &oop_combos);
+
+    // Precompute: hand scores and validity on every river card
+    let rivers = RiverTables::new(&config.board, &oop_combos, &ip_combos, &valid_ip_for_oop, &valid_oop_for_ip);
+
+    // Opponent reach at the root: range frequency for non-conflicting combos,
+    // 0.0 for blocked
+    let initial_opp_reach = |traverser: Player, h: usize| -> Vec<f64> {
+        match traverser {
+            Player::OOP => {
+                let mut reach = vec![0.0f64; ip_combos.len()];
+                for &j in &valid_ip_for_oop[h] {
+                    reach[j as usize] = ip_weights[j as usize];
+                }
+                reach
+            }
+            Player::IP => {
+                let mut reach = vec![0.0f64; oop_combos.len()];
+                for &i in &valid_oop_for_ip[h] {
+                    reach[i as usize] = oop_weights[i as usize];
+                }
+                reach
+            }
+        }
+    };
 
-    // Reusable buffers
-    let max_actions = metas.iter().map(|m| m.num_actions).max().unwrap_or(1) as usize;
-    let mut strategy_buf = vec![0.0f32; max_actions];
-    let mut action_values = vec![0.0f32; max_actions];
+    stats.precompute_ms = timer.lap();
 
     // Run alternating CFR+ iterations
+    let mut progress = ProgressTracker::new(config.progress_interval, config.on_progress, config.iterations)
+        .with_target(config.target_exploitability, config.starting_pot)
+        .with_time_budget(config.time_budget);
     for iter in 0..config.iterations {
+        if progress.out_of_time(iter) {
+            break;
+        }
+        if progress.due(iter)
+            && progress.measure(iter, || {
+                compute_exploitability(
+                    &tree, &oop_cfr, &ip_cfr, &oop_combos, &ip_combos, &oop_weights, &ip_weights, &oop_blockers,
+                    &ip_blockers, &rivers,
+                )
+            })
+        {
+            break;
+        }
         let traverser = if iter % 2 == 0 { Player::OOP } else { Player::IP };
 
         let num_combos = match traverser {
@@ -205,62 +434,344 @@ pub fn solve_turn(config: &TurnSolverConfig) -> TurnSolution {
             Player::IP => ip_combos.len(),
         };
 
-        for h in 0..num_combos {
-            // Initialize opponent reach: 1.0 for non-conflicting, 0.0 for blocked
-            let opp_reach = match traverser {
-                Player::OOP => {
-                    let valid = &valid_ip_for_oop[h];
-                    let mut reach = vec![0.0f64; ip_combos.len()];
-                    for &j in valid {
-                        reach[j as usize] = 1.0;
-                    }
-                    reach
-                }
-                Player::IP => {
-                    let valid = &valid_oop_for_ip[h];
-                    let mut reach = vec![0.0f64; oop_combos.len()];
-                    for &i in valid {
-                        reach[i as usize] = 1.0;
-                    }
-                    reach
-                }
-            };
+        // Sequential path for small ranges
+        if num_combos < parallel_min_combos {
+            for h in 0..num_combos {
+                let opp_reach = initial_opp_reach(traverser, h);
+                cfr_traverse_turn(
+                    &tree,
+                    traverser,
+                    h,
+                    &opp_reach,
+                    &oop_combos,
+                    &ip_combos,
+                    &oop_blockers,
+                    &ip_blockers,
+                    &config.board,
+                    &rivers,
+                    &mut oop_cfr,
+                    &mut ip_cfr,
+                    iter,
+                );
+            }
+            continue;
+        }
 
-            cfr_traverse_turn(
-                &tree,
-                traverser,
-                h,
-                &opp_reach,
-                &oop_combos,
-                &ip_combos,
-                &oop_blockers,
-                &ip_blockers,
-                &config.board,
-                &mut oop_cfr,
-                &mut ip_cfr,
-                &mut strategy_buf,
-                &mut action_values,
-                iter,
-            );
+        // Parallel path: each combo only updates its own rows of the
+        // traverser's table, so traverse the tables read-only and apply the
+        // collected updates afterwards
+        let all_updates: Vec<Vec<RegretUpdate>> = (0..num_combos)
+            .into_par_iter()
+            .map(|h| {
+                let opp_reach = initial_opp_reach(traverser, h);
+                let mut updates = Vec::new();
+                cfr_traverse_turn_ro(
+                    &tree,
+                    traverser,
+                    h,
+                    &opp_reach,
+                    &oop_blockers,
+                    &ip_blockers,
+                    &config.board,
+                    &rivers,
+                    &oop_cfr,
+                    &ip_cfr,
+                    &mut updates,
+                    iter,
+                );
+                updates
+            })
+            .collect();
+
+        let cfr = match traverser {
+            Player::OOP => &mut oop_cfr,
+            Player::IP => &mut ip_cfr,
+        };
+        for upd in all_updates.into_iter().flatten() {
+            cfr.update(upd.node_id, upd.bucket, &upd.action_values, upd.node_value, upd.reach_prob, iter);
         }
     }
+    progress.end_iterations();
+    stats.iterate_ms = timer.lap();
 
     // Extract solution
-    extract_solution(
-        config,
+    let Some(pp) = config.postprocess.filter(|p| p.is_active()) else {
+        let mut solution = extract_solution(
+            config, progress.iterations_run(), &tree, &oop_cfr, &ip_cfr, &oop_combos, &ip_combos, &oop_weights,
+            &ip_weights, &rivers, &metas,
+        );
+        solution.convergence_history = progress.finish(solution.exploitability);
+        stats.extract_ms = timer.lap();
+        return (solution, stats);
+    };
+    let raw_exploitability = compute_exploitability(
         &tree,
         &oop_cfr,
         &ip_cfr,
         &oop_combos,
         &ip_combos,
-        &metas,
-    )
+        &oop_weights,
+        &ip_weights,
+        &oop_blockers,
+        &ip_blockers,
+        &rivers,
+    );
+    postprocess_turn_strategies(&pp, &tree, &mut oop_cfr, &mut ip_cfr, &oop_combos, &ip_combos, &config.board);
+    let mut solution = extract_solution(
+        config, progress.iterations_run(), &tree, &oop_cfr, &ip_cfr, &oop_combos, &ip_combos, &oop_weights,
+        &ip_weights, &rivers, &metas,
+    );
+    solution.postprocess = Some(pp.applied(raw_exploitability));
+    solution.convergence_history = progress.finish(raw_exploitability);
+    stats.extract_ms = timer.lap();
+    (solution, stats)
+}
+
+/// Apply `pp` to the turn-level action nodes' average strategies in place.
+/// River subtrees keep their raw strategies.
+fn postprocess_turn_strategies(
+    pp: &PostProcess,
+    tree: &TreeNode,
+    oop_cfr: &mut FlatCfr,
+    ip_cfr: &mut FlatCfr,
+    oop_combos: &[Combo],
+    ip_combos: &[Combo],
+    board: &[u8],
+) {
+    let oop_classes: Vec<u16> = oop_combos.iter().map(hand_class).collect();
+    let ip_classes: Vec<u16> = ip_combos.iter().map(hand_class).collect();
+    let oop_equities = combo_equities(oop_combos, ip_combos, board, 48);
+    let ip_equities = combo_equities(ip_combos, oop_combos, board, 48);
+
+    let mut stack = vec![tree];
+    while let Some(node) = stack.pop() {
+        let TreeNode::Action { node_id, player, children, actions, .. } = node else {
+            continue;
+        };
+        let nid = *node_id as usize;
+        let (cfr, classes, equities) = match player {
+            Player::OOP => (&mut *oop_cfr, &oop_classes, &oop_equities),
+            Player::IP => (&mut *ip_cfr, &ip_classes, &ip_equities),
+        };
+        let mut buf = vec![0.0f32; actions.len()];
+        let mut freqs: Vec<Vec<f64>> = (0..classes.len())
+            .map(|h| {
+                cfr.average_strategy(nid, h, &mut buf);
+                buf.iter().map(|&v| v as f64).collect()
+            })
+            .collect();
+        pp.apply(&mut freqs, classes, equities);
+        for (h, f) in freqs.iter().enumerate() {
+            let f32s: Vec<f32> = f.iter().map(|&v| v as f32).collect();
+            cfr.set_average_strategy(nid, h, &f32s);
+        }
+        stack.extend(children.iter());
+    }
 }
 
 // ---------------------------------------------------------------------------
 // CFR+ traversal
 // ---------------------------------------------------------------------------
 
+/// Traverser's value at a river terminal, summed over opponent reach.
+#[allow(clippy::too_many_arguments)]
+fn river_terminal_value(
+    terminal_type: &TerminalType,
+    pot: f64,
+    my_invested: f64,
+    traverser: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    oop_scores: &[u32],
+    ip_scores: &[u32],
+    valid_ip_for_oop_h: &[u16],
+    valid_oop_for_ip_h: &[u16],
+) -> f64 {
+    let opp_reach_sum: f64 = opp_reach.iter().sum();
+    if opp_reach_sum < 1e-10 {
+        return 0.0;
+    }
+
+    match terminal_type {
+        TerminalType::Fold { folder } => {
+            if *folder == traverser {
+                -my_invested * opp_reach_sum
+            } else {
+                (pot - my_invested) * opp_reach_sum
+            }
+        }
+        TerminalType::Showdown => {
+            let win_payoff = pot - my_invested;
+            let lose_payoff = -my_invested;
+            let tie_payoff = pot / 2.0 - my_invested;
+            let (my_score, opp_scores, valid) = match traverser {
+                Player::OOP => (oop_scores[hand_idx], ip_scores, valid_ip_for_oop_h),
+                Player::IP => (ip_scores[hand_idx], oop_scores, valid_oop_for_ip_h),
+            };
+            let mut value = 0.0;
+            for &j in valid {
+                let j = j as usize;
+                if opp_reach[j] < 1e-10 {
+                    continue;
+                }
+                let opp_score = opp_scores[j];
+                let payoff = if my_score > opp_score {
+                    win_payoff
+                } else if my_score < opp_score {
+                    lose_payoff
+                } else {
+                    tie_payoff
+                };
+                value += opp_reach[j] * payoff;
+            }
+            value
+        }
+    }
+}
+
+/// Hand scores and river-aware validity for every river card, built once
+/// before the iterations instead of at every chance node visit.
+pub(crate) struct RiverTables {
+    /// (OOP scores, IP scores) per river card; `None` for board cards.
+    scores: Vec<Option<(Vec<u32>, Vec<u32>)>>,
+    /// `[river_card][oop_idx]`: IP combos valid against that OOP combo once
+    /// the card is dealt (empty for board cards).
+    valid_ip_for_oop: Vec<Vec<Vec<u16>>>,
+    /// `[river_card][ip_idx]`: OOP combos valid against that IP combo.
+    valid_oop_for_ip: Vec<Vec<Vec<u16>>>,
+}
+
+impl RiverTables {
+    /// `valid_ip_for_oop` and `valid_oop_for_ip` are the turn-level validity
+    /// tables, narrowed here to the opponents each river card leaves.
+    pub(crate) fn new(
+        board: &[u8],
+        oop_combos: &[Combo],
+        ip_combos: &[Combo],
+        valid_ip_for_oop: &[Vec<u16>],
+        valid_oop_for_ip: &[Vec<u16>],
+    ) -> Self {
+        let pairs = |combos: &[Combo]| -> Vec<(u8, u8)> { combos.iter().map(|c| (c.0, c.1)).collect() };
+        let (oop_pairs, ip_pairs) = (pairs(oop_combos), pairs(ip_combos));
+        let scores = (0..52u8)
+            .into_par_iter()
+            .map(|river_card| {
+                if board.contains(&river_card) {
+                    return None;
+                }
+                let river_board = [board[0], board[1], board[2], board[3], river_card];
+                let scores = |pairs: &[(u8, u8)]| -> Vec<u32> {
+                    let mut scores = vec![0u32; pairs.len()];
+                    evaluate_many(pairs, &river_board, &mut scores);
+                    scores
+                };
+                Some((scores(&oop_pairs), scores(&ip_pairs)))
+            })
+            .collect();
+        let narrow = |valid: &[Vec<u16>], opp_combos: &[Combo]| -> Vec<Vec<Vec<u16>>> {
+            (0..52u8)
+                .map(|river_card| {
+                    if board.contains(&river_card) {
+                        return Vec::new();
+                    }
+                    valid
+                        .iter()
+                        .map(|opps| {
+                            opps.iter()
+                                .copied()
+                                .filter(|&j| {
+                                    let opp = &opp_combos[j as usize];
+                                    opp.0 != river_card && opp.1 != river_card
+                                })
+                                .collect()
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        RiverTables {
+            scores,
+            valid_ip_for_oop: narrow(valid_ip_for_oop, ip_combos),
+            valid_oop_for_ip: narrow(valid_oop_for_ip, oop_combos),
+        }
+    }
+
+    /// Both ranges' scores on `river_card`.
+    fn scores(&self, river_card: u8) -> (&[u32], &[u32]) {
+        let (oop, ip) = self.scores[river_card as usize].as_ref().expect("river card is on the board");
+        (oop, ip)
+    }
+
+    /// Opponents valid against `traverser`'s `hand_idx` on `river_card`, as
+    /// (IP valid for OOP, OOP valid for IP) with only the traverser's side set.
+    fn valid(&self, traverser: Player, hand_idx: usize, river_card: u8) -> (&[u16], &[u16]) {
+        match traverser {
+            Player::OOP => (&self.valid_ip_for_oop[river_card as usize][hand_idx], &[]),
+            Player::IP => (&[], &self.valid_oop_for_ip[river_card as usize][hand_idx]),
+        }
+    }
+}
+
+/// Everything a river subtree needs once a river card is dealt.
+struct RiverDeal<'a> {
+    /// Opponent reach with combos blocked by the river card zeroed.
+    opp_reach: Vec<f64>,
+    /// Full 5-card board (turn board + river card).
+    river_board: [u8; 5],
+    oop_scores: &'a [u32],
+    ip_scores: &'a [u32],
+    /// Opponents valid against the traverser's hand on this river (only the
+    /// traverser's side is filled).
+    valid_ip_h: &'a [u16],
+    valid_oop_h: &'a [u16],
+}
+
+/// Deal `river_card` for the traverser's `hand_idx`, or `None` when the
+/// traverser's hand blocks it.
+#[allow(clippy::too_many_arguments)]
+fn deal_river<'a>(
+    traverser: Player,
+    hand_idx: usize,
+    river_card: u8,
+    opp_reach: &[f64],
+    oop_blockers: &[[bool; 52]],
+    ip_blockers: &[[bool; 52]],
+    board: &[u8],
+    rivers: &'a RiverTables,
+) -> Option<RiverDeal<'a>> {
+    let (my_blockers, opp_blockers) = match traverser {
+        Player::OOP => (oop_blockers, ip_blockers),
+        Player::IP => (ip_blockers, oop_blockers),
+    };
+    if my_blockers[hand_idx][river_card as usize] {
+        return None;
+    }
+
+    // Zero out opponents blocked by the river card
+    let opp_reach: Vec<f64> = opp_reach
+        .iter()
+        .enumerate()
+        .map(|(j, &r)| {
+            if r > 0.0 && !opp_blockers[j][river_card as usize] {
+                r
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    let (oop_scores, ip_scores) = rivers.scores(river_card);
+    let (valid_ip_h, valid_oop_h) = rivers.valid(traverser, hand_idx, river_card);
+    Some(RiverDeal {
+        opp_reach,
+        river_board: [board[0], board[1], board[2], board[3], river_card],
+        oop_scores,
+        ip_scores,
+        valid_ip_h,
+        valid_oop_h,
+    })
+}
+
 /// Recursive CFR+ traversal for river subtrees (inside chance nodes).
 /// `river_board` is the full 5-card board (turn board + dealt river card).
 #[allow(clippy::too_many_arguments)]
@@ -278,8 +789,6 @@ fn cfr_traverse_river(
     valid_oop_for_ip_h: &[u16],
     oop_cfr: &mut FlatCfr,
     ip_cfr: &mut FlatCfr,
-    strategy_buf: &mut [f32],
-    action_values_buf: &mut [f32],
     iter: usize,
 ) -> f64 {
     match node {
@@ -288,70 +797,18 @@ fn cfr_traverse_river(
             pot,
             invested,
             ..
-        } => {
-            let opp_reach_sum: f64 = opp_reach.iter().sum();
-            if opp_reach_sum < 1e-10 {
-                return 0.0;
-            }
-            let my_invested = invested[traverser.index()];
-
-            match terminal_type {
-                TerminalType::Fold { folder } => {
-                    if *folder == traverser {
-                        -my_invested * opp_reach_sum
-                    } else {
-                        (*pot - my_invested) * opp_reach_sum
-                    }
-                }
-                TerminalType::Showdown => {
-                    let win_payoff = *pot - my_invested;
-                    let lose_payoff = -my_invested;
-                    let tie_payoff = *pot / 2.0 - my_invested;
-                    let mut value = 0.0;
-
-                    match traverser {
-                        Player::OOP => {
-                            let my_score = oop_scores[hand_idx];
-                            for &j in valid_ip_for_oop_h {
-                                let j = j as usize;
-                                if opp_reach[j] < 1e-10 {
-                                    continue;
-                                }
-                                let opp_score = ip_scores[j];
-                                let payoff = if my_score > opp_score {
-                                    win_payoff
-                                } else if my_score < opp_score {
-                                    lose_payoff
-                                } else {
-                                    tie_payoff
-                                };
-                                value += opp_reach[j] * payoff;
-                            }
-                        }
-                        Player::IP => {
-                            let my_score = ip_scores[hand_idx];
-                            for &i in valid_oop_for_ip_h {
-                                let i = i as usize;
-                                if opp_reach[i] < 1e-10 {
-                                    continue;
-                                }
-                                let opp_score = oop_scores[i];
-                                let payoff = if my_score > opp_score {
-                                    win_payoff
-                                } else if my_score < opp_score {
-                                    lose_payoff
-                                } else {
-                                    tie_payoff
-                                };
-                                value += opp_reach[i] * payoff;
-                            }
-                        }
-                    }
-
-                    value
-                }
-            }
-        }
+        } => river_terminal_value(
+            terminal_type,
+            *pot,
+            invested[traverser.index()],
+            traverser,
+            hand_idx,
+            opp_reach,
+            oop_scores,
+            ip_scores,
+            valid_ip_for_oop_h,
+            valid_oop_for_ip_h,
+        ),
         TreeNode::Action {
             node_id,
             player,
@@ -367,13 +824,15 @@ fn cfr_traverse_river(
                     Player::OOP => &*oop_cfr,
                     Player::IP => &*ip_cfr,
                 };
-                cfr.current_strategy(nid, hand_idx, strategy_buf);
+                // Local buffers: the children's traversals reuse none of them
+                let mut strategy = vec![0.0f32; num_actions];
+                cfr.current_strategy(nid, hand_idx, &mut strategy);
 
                 let mut node_value = 0.0f64;
+                let mut action_values = vec![0.0f32; num_actions];
                 for a in 0..num_actions {
                     // Regret pruning: skip near-zero-probability actions after warmup
-                    if strategy_buf[a] < 0.001 && iter > 1000 && iter % 1000 != 0 {
-                        action_values_buf[a] = 0.0;
+                    if strategy[a] < 0.001 && iter > 1000 && iter % 1000 != 0 {
                         continue;
                     }
                     let av = cfr_traverse_river(
@@ -390,12 +849,10 @@ fn cfr_traverse_river(
                         valid_oop_for_ip_h,
                         oop_cfr,
                         ip_cfr,
-                        strategy_buf,
-                        action_values_buf,
                         iter,
                     );
-                    action_values_buf[a] = av as f32;
-                    node_value += strategy_buf[a] as f64 * av;
+                    action_values[a] = av as f32;
+                    node_value += strategy[a] as f64 * av;
                 }
 
                 let reach_sum: f64 = opp_reach.iter().sum();
@@ -408,9 +865,10 @@ fn cfr_traverse_river(
                 cfr_mut.update(
                     nid,
                     hand_idx,
-                    &action_values_buf[..num_actions],
+                    &action_values,
                     node_value as f32,
                     reach_prob,
+                    iter,
                 );
 
                 node_value
@@ -457,8 +915,6 @@ fn cfr_traverse_river(
                         valid_oop_for_ip_h,
                         oop_cfr,
                         ip_cfr,
-                        strategy_buf,
-                        action_values_buf,
                         iter,
                     );
                 }
@@ -489,10 +945,9 @@ fn cfr_traverse_turn(
     oop_blockers: &[[bool; 52]],
     ip_blockers: &[[bool; 52]],
     board: &[u8],
+    rivers: &RiverTables,
     oop_cfr: &mut FlatCfr,
     ip_cfr: &mut FlatCfr,
-    strategy_buf: &mut [f32],
-    action_values_buf: &mut [f32],
     iter: usize,
 ) -> f64 {
     match node {
@@ -529,129 +984,34 @@ fn cfr_traverse_turn(
             let mut valid_count = 0usize;
 
             for (ci, &river_card) in cards.iter().enumerate() {
-                // Skip if traverser's hand blocks this river card
-                let traverser_blocked = match traverser {
-                    Player::OOP => oop_blockers[hand_idx][river_card as usize],
-                    Player::IP => ip_blockers[hand_idx][river_card as usize],
-                };
-                if traverser_blocked {
+                let Some(deal) = deal_river(
+                    traverser,
+                    hand_idx,
+                    river_card,
+                    opp_reach,
+                    oop_blockers,
+                    ip_blockers,
+                    board,
+                    rivers,
+                ) else {
                     continue;
-                }
-                valid_count += 1;
-
-                // Build new opp_reach: zero out opponents blocked by river card
-                let new_opp_reach: Vec<f64> = match traverser {
-                    Player::OOP => opp_reach
-                        .iter()
-                        .enumerate()
-                        .map(|(j, &r)| {
-                            if r > 0.0 && !ip_blockers[j][river_card as usize] {
-                                r
-                            } else {
-                                0.0
-                            }
-                        })
-                        .collect(),
-                    Player::IP => opp_reach
-                        .iter()
-                        .enumerate()
-                        .map(|(i, &r)| {
-                            if r > 0.0 && !oop_blockers[i][river_card as usize] {
-                                r
-                            } else {
-                                0.0
-                            }
-                        })
-                        .collect(),
-                };
-
-                // Build 5-card river board
-                let river_board: [u8; 5] = [board[0], board[1], board[2], board[3], river_card];
-
-                // Evaluate hand strengths for this river card
-                let oop_scores: Vec<u32> = oop_combos
-                    .iter()
-                    .map(|c| {
-                        evaluate_fast(&[
-                            c.0,
-                            c.1,
-                            river_board[0],
-                            river_board[1],
-                            river_board[2],
-                            river_board[3],
-                            river_board[4],
-                        ])
-                    })
-                    .collect();
-                let ip_scores: Vec<u32> = ip_combos
-                    .iter()
-                    .map(|c| {
-                        evaluate_fast(&[
-                            c.0,
-                            c.1,
-                            river_board[0],
-                            river_board[1],
-                            river_board[2],
-                            river_board[3],
-                            river_board[4],
-                        ])
-                    })
-                    .collect();
-
-                // Validity tables for this hand against opponents (blocker-aware)
-                let (valid_ip_h, valid_oop_h) = match traverser {
-                    Player::OOP => {
-                        let valid_ip: Vec<u16> = ip_combos
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, ip)| {
-                                let oop = &oop_combos[hand_idx];
-                                oop.0 != ip.0
-                                    && oop.0 != ip.1
-                                    && oop.1 != ip.0
-                                    && oop.1 != ip.1
-                                    && ip.0 != river_card
-                                    && ip.1 != river_card
-                            })
-                            .map(|(j, _)| j as u16)
-                            .collect();
-                        (valid_ip, Vec::new())
-                    }
-                    Player::IP => {
-                        let valid_oop: Vec<u16> = oop_combos
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, oop)| {
-                                let ip = &ip_combos[hand_idx];
-                                ip.0 != oop.0
-                                    && ip.0 != oop.1
-                                    && ip.1 != oop.0
-                                    && ip.1 != oop.1
-                                    && oop.0 != river_card
-                                    && oop.1 != river_card
-                            })
-                            .map(|(i, _)| i as u16)
-                            .collect();
-                        (Vec::new(), valid_oop)
-                    }
                 };
+                valid_count += 1;
 
                 let child_value = cfr_traverse_river(
                     &children[ci],
                     traverser,
                     hand_idx,
-                    &new_opp_reach,
+                    &deal.opp_reach,
                     oop_combos,
                     ip_combos,
-                    &river_board,
-                    &oop_scores,
-                    &ip_scores,
-                    &valid_ip_h,
-                    &valid_oop_h,
+                    &deal.river_board,
+                    deal.oop_scores,
+                    deal.ip_scores,
+                    deal.valid_ip_h,
+                    deal.valid_oop_h,
                     oop_cfr,
                     ip_cfr,
-                    strategy_buf,
-                    action_values_buf,
                     iter,
                 );
                 total_value += child_value;
@@ -678,13 +1038,15 @@ fn cfr_traverse_turn(
                     Player::OOP => &*oop_cfr,
                     Player::IP => &*ip_cfr,
                 };
-                cfr.current_strategy(nid, hand_idx, strategy_buf);
+                // Local buffers: the children's traversals reuse none of them
+                let mut strategy = vec![0.0f32; num_actions];
+                cfr.current_strategy(nid, hand_idx, &mut strategy);
 
                 let mut node_value = 0.0f64;
+                let mut action_values = vec![0.0f32; num_actions];
                 for a in 0..num_actions {
                     // Regret pruning: skip near-zero-probability actions after warmup
-                    if strategy_buf[a] < 0.001 && iter > 1000 && iter % 1000 != 0 {
-                        action_values_buf[a] = 0.0;
+                    if strategy[a] < 0.001 && iter > 1000 && iter % 1000 != 0 {
                         continue;
                     }
                     let av = cfr_traverse_turn(
@@ -697,14 +1059,13 @@ fn cfr_traverse_turn(
                         oop_blockers,
                         ip_blockers,
                         board,
+                        rivers,
                         oop_cfr,
                         ip_cfr,
-                        strategy_buf,
-                        action_values_buf,
                         iter,
                     );
-                    action_values_buf[a] = av as f32;
-                    node_value += strategy_buf[a] as f64 * av;
+                    action_values[a] = av as f32;
+                    node_value += strategy[a] as f64 * av;
                 }
 
                 let reach_sum: f64 = opp_reach.iter().sum();
@@ -717,9 +1078,10 @@ fn cfr_traverse_turn(
                 cfr_mut.update(
                     nid,
                     hand_idx,
-                    &action_values_buf[..num_actions],
+                    &action_values,
                     node_value as f32,
                     reach_prob,
+                    iter,
                 );
 
                 node_value
@@ -762,10 +1124,9 @@ fn cfr_traverse_turn(
                         oop_blockers,
                         ip_blockers,
                         board,
+                        rivers,
                         oop_cfr,
                         ip_cfr,
-                        strategy_buf,
-                        action_values_buf,
                         iter,
                     );
                 }
@@ -776,46 +1137,322 @@ fn cfr_traverse_turn(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Parallel traversal: readonly + collected updates
+// ---------------------------------------------------------------------------
+
+/// Readonly `cfr_traverse_turn` that collects RegretUpdates for the
+/// traverser instead of mutating its table.
+#[allow(clippy::too_many_arguments)]
+fn cfr_traverse_turn_ro(
+    node: &TreeNode,
+    traverser: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    oop_blockers: &[[bool; 52]],
+    ip_blockers: &[[bool; 52]],
+    board: &[u8],
+    rivers: &RiverTables,
+    oop_cfr: &FlatCfr,
+    ip_cfr: &FlatCfr,
+    updates: &mut Vec<RegretUpdate>,
+    iter: usize,
+) -> f64 {
+    match node {
+        TreeNode::Terminal {
+            terminal_type,
+            pot,
+            invested,
+            ..
+        } => {
+            let opp_reach_sum: f64 = opp_reach.iter().sum();
+            if opp_reach_sum < 1e-10 {
+                return 0.0;
+            }
+            let my_invested = invested[traverser.index()];
+            match terminal_type {
+                TerminalType::Fold { folder } => {
+                    if *folder == traverser {
+                        -my_invested * opp_reach_sum
+                    } else {
+                        (*pot - my_invested) * opp_reach_sum
+                    }
+                }
+                // Shouldn't happen at turn level (all converted to Chance)
+                TerminalType::Showdown => 0.0,
+            }
+        }
+        TreeNode::Chance {
+            cards, children, ..
+        } => {
+            let mut total_value = 0.0;
+            let mut valid_count = 0usize;
+            for (ci, &river_card) in cards.iter().enumerate() {
+                let Some(deal) = deal_river(
+                    traverser,
+                    hand_idx,
+                    river_card,
+                    opp_reach,
+                    oop_blockers,
+                    ip_blockers,
+                    board,
+                    rivers,
+                ) else {
+                    continue;
+                };
+                valid_count += 1;
+                total_value += cfr_traverse_river_ro(
+                    &children[ci],
+                    traverser,
+                    hand_idx,
+                    &deal.opp_reach,
+                    deal.oop_scores,
+                    deal.ip_scores,
+                    deal.valid_ip_h,
+                    deal.valid_oop_h,
+                    oop_cfr,
+                    ip_cfr,
+                    updates,
+                    iter,
+                );
+            }
+            if valid_count > 0 {
+                total_value / valid_count as f64
+            } else {
+                0.0
+            }
+        }
+        TreeNode::Action { .. } => traverse_action_ro(
+            node,
+            traverser,
+            hand_idx,
+            opp_reach,
+            oop_cfr,
+            ip_cfr,
+            updates,
+            iter,
+            &mut |child, opp_reach, updates| {
+                cfr_traverse_turn_ro(
+                    child,
+                    traverser,
+                    hand_idx,
+                    opp_reach,
+                    oop_blockers,
+                    ip_blockers,
+                    board,
+                    rivers,
+                    oop_cfr,
+                    ip_cfr,
+                    updates,
+                    iter,
+                )
+            },
+        ),
+    }
+}
+
+/// Readonly `cfr_traverse_river`.
+#[allow(clippy::too_many_arguments)]
+fn cfr_traverse_river_ro(
+    node: &TreeNode,
+    traverser: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    oop_scores: &[u32],
+    ip_scores: &[u32],
+    valid_ip_for_oop_h: &[u16],
+    valid_oop_for_ip_h: &[u16],
+    oop_cfr: &FlatCfr,
+    ip_cfr: &FlatCfr,
+    updates: &mut Vec<RegretUpdate>,
+    iter: usize,
+) -> f64 {
+    match node {
+        TreeNode::Terminal {
+            terminal_type,
+            pot,
+            invested,
+            ..
+        } => river_terminal_value(
+            terminal_type,
+            *pot,
+            invested[traverser.index()],
+            traverser,
+            hand_idx,
+            opp_reach,
+            oop_scores,
+            ip_scores,
+            valid_ip_for_oop_h,
+            valid_oop_for_ip_h,
+        ),
+        TreeNode::Action { .. } => traverse_action_ro(
+            node,
+            traverser,
+            hand_idx,
+            opp_reach,
+            oop_cfr,
+            ip_cfr,
+            updates,
+            iter,
+            &mut |child, opp_reach, updates| {
+                cfr_traverse_river_ro(
+                    child,
+                    traverser,
+                    hand_idx,
+                    opp_reach,
+                    oop_scores,
+                    ip_scores,
+                    valid_ip_for_oop_h,
+                    valid_oop_for_ip_h,
+                    oop_cfr,
+                    ip_cfr,
+                    updates,
+                    iter,
+                )
+            },
+        ),
+        TreeNode::Chance { .. } => {
+            unreachable!("River subtree should not contain chance nodes")
+        }
+    }
+}
+
+/// One action node of a readonly traversal: recurse into each child with
+/// `traverse_child` (pruning as the mutating traversals do), and for the
+/// traverser's nodes collect the regret update.
+#[allow(clippy::too_many_arguments)]
+fn traverse_action_ro(
+    node: &TreeNode,
+    traverser: Player,
+    hand_idx: usize,
+    opp_reach: &[f64],
+    oop_cfr: &FlatCfr,
+    ip_cfr: &FlatCfr,
+    updates: &mut Vec<RegretUpdate>,
+    iter: usize,
+    traverse_child: &mut dyn FnMut(&TreeNode, &[f64], &mut Vec<RegretUpdate>) -> f64,
+) -> f64 {
+    let TreeNode::Action {
+        node_id,
+        player,
+        children,
+        actions,
+        ..
+    } = node
+    else {
+        unreachable!("traverse_action_ro called on a non-action node")
+    };
+    let num_actions = actions.len();
+    let nid = *node_id as usize;
+
+    if *player == traverser {
+        let cfr = match traverser {
+            Player::OOP => oop_cfr,
+            Player::IP => ip_cfr,
+        };
+        let mut strategy = vec![0.0f32; num_actions];
+        cfr.current_strategy(nid, hand_idx, &mut strategy);
+
+        let mut node_value = 0.0f64;
+        let mut action_values = vec![0.0f32; num_actions];
+        for a in 0..num_actions {
+            // Regret pruning: skip near-zero-probability actions after warmup
+            if strategy[a] < 0.001 && iter > 1000 && iter % 1000 != 0 {
+                continue;
+            }
+            let av = traverse_child(&children[a], opp_reach, updates);
+            action_values[a] = av as f32;
+            node_value += strategy[a] as f64 * av;
+        }
+
+        let reach_sum: f64 = opp_reach.iter().sum();
+        let reach_prob = if reach_sum > 0.0 { 1.0f32 } else { 0.0f32 };
+        updates.push(RegretUpdate {
+            street: 1,
+            node_id: nid,
+            bucket: hand_idx,
+            action_values,
+            node_value: node_value as f32,
+            reach_prob,
+        });
+        node_value
+    } else {
+        let opp_cfr = match traverser {
+            Player::OOP => ip_cfr,
+            Player::IP => oop_cfr,
+        };
+        let num_opp = opp_reach.len();
+        let opp_num_actions = opp_cfr.node_num_actions(nid) as usize;
+
+        let mut opp_strats = vec![0.0f32; num_opp * opp_num_actions];
+        for j in 0..num_opp {
+            if opp_reach[j] > 0.0 {
+                opp_cfr.current_strategy(nid, j, &mut opp_strats[j * opp_num_actions..(j + 1) * opp_num_actions]);
+            }
+        }
+
+        let mut node_value = 0.0f64;
+        for a in 0..num_actions {
+            let mut new_opp_reach = vec![0.0f64; num_opp];
+            for j in 0..num_opp {
+                if opp_reach[j] > 0.0 {
+                    new_opp_reach[j] = opp_reach[j] * opp_strats[j * opp_num_actions + a] as f64;
+                }
+            }
+            node_value += traverse_child(&children[a], &new_opp_reach, updates);
+        }
+        node_value
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Exploitability
 // ---------------------------------------------------------------------------
 
 /// Compute exploitability via best-response traversal.
-pub fn compute_exploitability(
+pub(crate) fn compute_exploitability(
     tree: &TreeNode,
     oop_cfr: &FlatCfr,
     ip_cfr: &FlatCfr,
     oop_combos: &[Combo],
     ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
     oop_blockers: &[[bool; 52]],
     ip_blockers: &[[bool; 52]],
-    board: &[u8],
+    rivers: &RiverTables,
 ) -> f64 {
-    let oop_gain = best_response_value(
+    let (oop_gain, _) = best_response_value(
         tree,
         Player::OOP,
         oop_cfr,
         ip_cfr,
         oop_combos,
         ip_combos,
+        oop_weights,
+        ip_weights,
         oop_blockers,
         ip_blockers,
-        board,
+        rivers,
     );
-    let ip_gain = best_response_value(
+    let (ip_gain, _) = best_response_value(
         tree,
         Player::IP,
         oop_cfr,
         ip_cfr,
         oop_combos,
         ip_combos,
+        oop_weights,
+        ip_weights,
         oop_blockers,
         ip_blockers,
-        board,
+        rivers,
     );
     (oop_gain + ip_gain) / 2.0
 }
 
+/// `br_player`'s range-weighted gain from best responding over its average
+/// strategy, and its best-response value itself.
 #[allow(clippy::too_many_arguments)]
 fn best_response_value(
     tree: &TreeNode,
@@ -824,10 +1461,12 @@ fn best_response_value(
     ip_cfr: &FlatCfr,
     oop_combos: &[Combo],
     ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
     oop_blockers: &[[bool; 52]],
     ip_blockers: &[[bool; 52]],
-    board: &[u8],
-) -> f64 {
+    rivers: &RiverTables,
+) -> (f64, f64) {
     let num_br = match br_player {
         Player::OOP => oop_combos.len(),
         Player::IP => ip_combos.len(),
@@ -860,13 +1499,21 @@ fn best_response_value(
         })
         .collect();
 
+    let (my_weights, opp_weights) = match br_player {
+        Player::OOP => (oop_weights, ip_weights),
+        Player::IP => (ip_weights, oop_weights),
+    };
+
+    // Each combo's gain counts at its range frequency
     let mut total_gain = 0.0;
+    let mut total_value = 0.0;
+    let mut total_weight = 0.0;
     let mut strat_buf = vec![0.0f32; 16]; // max actions at any node
 
     for h in 0..num_br {
         let mut opp_reach = vec![0.0f64; num_opp];
         for &j in &valid_for[h] {
-            opp_reach[j as usize] = 1.0;
+            opp_reach[j as usize] = opp_weights[j as usize];
         }
 
         let br_value = br_traverse_turn(
@@ -878,7 +1525,7 @@ fn best_response_value(
             ip_combos,
             oop_blockers,
             ip_blockers,
-            board,
+            rivers,
             oop_cfr,
             ip_cfr,
             &mut strat_buf,
@@ -894,17 +1541,19 @@ fn best_response_value(
             ip_combos,
             oop_blockers,
             ip_blockers,
-            board,
+            rivers,
             oop_cfr,
             ip_cfr,
             &mut strat_buf,
             false, // average strategy
         );
 
-        total_gain += br_value - avg_value;
+        total_gain += my_weights[h] * (br_value - avg_value);
+        total_value += my_weights[h] * br_value;
+        total_weight += my_weights[h];
     }
 
-    total_gain / num_br as f64
+    if total_weight > 0.0 { (total_gain / total_weight, total_value / total_weight) } else { (0.0, 0.0) }
 }
 
 /// Best-response / average-strategy traversal for exploitability.
@@ -918,7 +1567,7 @@ fn br_traverse_turn(
     ip_combos: &[Combo],
     oop_blockers: &[[bool; 52]],
     ip_blockers: &[[bool; 52]],
-    board: &[u8],
+    rivers: &RiverTables,
     oop_cfr: &FlatCfr,
     ip_cfr: &FlatCfr,
     strat_buf: &mut [f32],
@@ -988,44 +1637,8 @@ fn br_traverse_turn(
                         .collect(),
                 };
 
-                let river_board = [board[0], board[1], board[2], board[3], river_card];
-                let oop_scores: Vec<u32> = oop_combos
-                    .iter()
-                    .map(|c| evaluate_fast(&[c.0, c.1, river_board[0], river_board[1], river_board[2], river_board[3], river_board[4]]))
-                    .collect();
-                let ip_scores: Vec<u32> = ip_combos
-                    .iter()
-                    .map(|c| evaluate_fast(&[c.0, c.1, river_board[0], river_board[1], river_board[2], river_board[3], river_board[4]]))
-                    .collect();
-
-                let (valid_ip_h, valid_oop_h) = match br_player {
-                    Player::OOP => {
-                        let v: Vec<u16> = ip_combos
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, ip)| {
-                                let oop = &oop_combos[hand_idx];
-                                oop.0 != ip.0 && oop.0 != ip.1 && oop.1 != ip.0 && oop.1 != ip.1
-                                    && ip.0 != river_card && ip.1 != river_card
-                            })
-                            .map(|(j, _)| j as u16)
-                            .collect();
-                        (v, Vec::new())
-                    }
-                    Player::IP => {
-                        let v: Vec<u16> = oop_combos
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, oop)| {
-                                let ip = &ip_combos[hand_idx];
-                                ip.0 != oop.0 && ip.0 != oop.1 && ip.1 != oop.0 && ip.1 != oop.1
-                                    && oop.0 != river_card && oop.1 != river_card
-                            })
-                            .map(|(i, _)| i as u16)
-                            .collect();
-                        (Vec::new(), v)
-                    }
-                };
+                let (oop_scores, ip_scores) = rivers.scores(river_card);
+                let (valid_ip_h, valid_oop_h) = rivers.valid(br_player, hand_idx, river_card);
 
                 total_value += br_traverse_river(
                     &children[ci],
@@ -1034,10 +1647,10 @@ fn br_traverse_turn(
                     &new_opp_reach,
                     oop_combos,
                     ip_combos,
-                    &oop_scores,
-                    &ip_scores,
-                    &valid_ip_h,
-                    &valid_oop_h,
+                    oop_scores,
+                    ip_scores,
+                    valid_ip_h,
+                    valid_oop_h,
                     oop_cfr,
                     ip_cfr,
                     strat_buf,
@@ -1069,7 +1682,7 @@ fn br_traverse_turn(
                         let v = br_traverse_turn(
                             &children[a], br_player, hand_idx, opp_reach,
                             oop_combos, ip_combos, oop_blockers, ip_blockers,
-                            board, oop_cfr, ip_cfr, strat_buf, is_br,
+                            rivers, oop_cfr, ip_cfr, strat_buf, is_br,
                         );
                         if v > best {
                             best = v;
@@ -1083,14 +1696,16 @@ fn br_traverse_turn(
                         Player::IP => ip_cfr,
                     };
                     cfr.average_strategy(nid, hand_idx, strat_buf);
+                    // Children overwrite strat_buf
+                    let strategy: Vec<f64> = strat_buf[..num_actions].iter().map(|&p| p as f64).collect();
                     let mut node_value = 0.0;
                     for a in 0..num_actions {
                         let v = br_traverse_turn(
                             &children[a], br_player, hand_idx, opp_reach,
                             oop_combos, ip_combos, oop_blockers, ip_blockers,
-                            board, oop_cfr, ip_cfr, strat_buf, is_br,
+                            rivers, oop_cfr, ip_cfr, strat_buf, is_br,
                         );
-                        node_value += strat_buf[a] as f64 * v;
+                        node_value += strategy[a] * v;
                     }
                     node_value
                 }
@@ -1114,7 +1729,7 @@ fn br_traverse_turn(
                     node_value += br_traverse_turn(
                         &children[a], br_player, hand_idx, &new_opp_reach,
                         oop_combos, ip_combos, oop_blockers, ip_blockers,
-                        board, oop_cfr, ip_cfr, strat_buf, is_br,
+                        rivers, oop_cfr, ip_cfr, strat_buf, is_br,
                     );
                 }
                 node_value
@@ -1226,6 +1841,8 @@ fn br_traverse_river(
                         Player::IP => ip_cfr,
                     };
                     cfr.average_strategy(nid, hand_idx, strat_buf);
+                    // Children overwrite strat_buf
+                    let strategy: Vec<f64> = strat_buf[..num_actions].iter().map(|&p| p as f64).collect();
                     let mut node_value = 0.0;
                     for a in 0..num_actions {
                         let v = br_traverse_river(
@@ -1234,7 +1851,7 @@ fn br_traverse_river(
                             valid_ip_for_oop_h, valid_oop_for_ip_h,
                             oop_cfr, ip_cfr, strat_buf, is_br,
                         );
-                        node_value += strat_buf[a] as f64 * v;
+                        node_value += strategy[a] * v;
                     }
                     node_value
                 }
@@ -1268,38 +1885,289 @@ fn br_traverse_river(
     }
 }
 
+/// A solution's stored strategies loaded back into CFR tables over the
+/// rebuilt tree, with everything a traversal of it reads.
+struct TurnProfile {
+    tree: TreeNode,
+    oop_cfr: FlatCfr,
+    ip_cfr: FlatCfr,
+    oop_combos: Vec<Combo>,
+    ip_combos: Vec<Combo>,
+    oop_weights: Vec<f64>,
+    ip_weights: Vec<f64>,
+    oop_blockers: Vec<[bool; 52]>,
+    ip_blockers: Vec<[bool; 52]>,
+    rivers: RiverTables,
+}
+
+impl TurnProfile {
+    /// What a best response wins against each side's strategy: `[vs OOP,
+    /// vs IP]`, in the units of `compute_exploitability`.
+    fn best_response_values(&self) -> [f64; 2] {
+        [Player::IP, Player::OOP].map(|br_player| {
+            best_response_value(
+                &self.tree, br_player, &self.oop_cfr, &self.ip_cfr, &self.oop_combos, &self.ip_combos,
+                &self.oop_weights, &self.ip_weights, &self.oop_blockers, &self.ip_blockers, &self.rivers,
+            )
+            .1
+        })
+    }
+
+    /// `player`'s combo `hand_idx` at the action node `node_id`: its value
+    /// for each action there, summed over opponent reach, and that reach's
+    /// total. Opponent reach is narrowed by the opponent's own actions and
+    /// by the river card on the way. `None` if the node isn't `player`'s or
+    /// the combo holds the river card it's under.
+    fn action_values(&self, node_id: u16, player: Player, hand_idx: usize) -> Option<(Vec<f64>, f64)> {
+        let (node, path) = path_to_node(&self.tree, node_id)?;
+        let TreeNode::Action { player: to_act, children, .. } = node else {
+            return None;
+        };
+        if *to_act != player {
+            return None;
+        }
+        let (my_blockers, opp_blockers, opp_cfr) = match player {
+            Player::OOP => (&self.oop_blockers, &self.ip_blockers, &self.ip_cfr),
+            Player::IP => (&self.ip_blockers, &self.oop_blockers, &self.oop_cfr),
+        };
+        let (valid, opp_weights) = match player {
+            Player::OOP => (valid_opponents(&self.oop_combos[hand_idx..=hand_idx], &self.ip_combos), &self.ip_weights),
+            Player::IP => (valid_opponents(&self.ip_combos[hand_idx..=hand_idx], &self.oop_combos), &self.oop_weights),
+        };
+        let mut opp_reach = vec![0.0f64; opp_weights.len()];
+        for &j in &valid[0] {
+            opp_reach[j as usize] = opp_weights[j as usize];
+        }
+
+        let mut strat_buf = vec![0.0f32; 16];
+        let mut river_card = None;
+        for (step, a) in path {
+            match step {
+                TreeNode::Action { node_id: id, player: actor, .. } if *actor != player => {
+                    for (j, r) in opp_reach.iter_mut().enumerate() {
+                        if *r > 0.0 {
+                            opp_cfr.average_strategy(*id as usize, j, &mut strat_buf);
+                            *r *= strat_buf[a] as f64;
+                        }
+                    }
+                }
+                TreeNode::Chance { cards, .. } => {
+                    let card = cards[a];
+                    if my_blockers[hand_idx][card as usize] {
+                        return None;
+                    }
+                    for (j, r) in opp_reach.iter_mut().enumerate() {
+                        if opp_blockers[j][card as usize] {
+                            *r = 0.0;
+                        }
+                    }
+                    river_card = Some(card);
+                }
+                _ => {}
+            }
+        }
+
+        let values = children
+            .iter()
+            .map(|child| match river_card {
+                None => br_traverse_turn(
+                    child, player, hand_idx, &opp_reach, &self.oop_combos, &self.ip_combos, &self.oop_blockers,
+                    &self.ip_blockers, &self.rivers, &self.oop_cfr, &self.ip_cfr, &mut strat_buf, false,
+                ),
+                Some(card) => {
+                    let (oop_scores, ip_scores) = self.rivers.scores(card);
+                    let (valid_ip_h, valid_oop_h) = self.rivers.valid(player, hand_idx, card);
+                    br_traverse_river(
+                        child, player, hand_idx, &opp_reach, &self.oop_combos, &self.ip_combos, oop_scores,
+                        ip_scores, valid_ip_h, valid_oop_h, &self.oop_cfr, &self.ip_cfr, &mut strat_buf, false,
+                    )
+                }
+            })
+            .collect();
+        Some((values, opp_reach.iter().sum()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Showdown outcomes
+// ---------------------------------------------------------------------------
+
+/// What the outcome traversal reads for one combo besides the node and reach.
+struct OutcomeContext<'a> {
+    player: Player,
+    hand_idx: usize,
+    /// Opponent combos that don't conflict with `hand_idx`.
+    valid: &'a [u16],
+    oop_cfr: &'a FlatCfr,
+    ip_cfr: &'a FlatCfr,
+    oop_blockers: &'a [[bool; 52]],
+    ip_blockers: &'a [[bool; 52]],
+    /// (OOP scores, IP scores) per river card; `None` for board cards.
+    river_scores: &'a [Option<(Vec<u32>, Vec<u32>)>],
+}
+
+/// Win/tie/lose shares at showdown for every combo of `player`, with both
+/// players on their average strategies and every river card dealt.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_showdown_outcomes(
+    tree: &TreeNode,
+    player: Player,
+    oop_cfr: &FlatCfr,
+    ip_cfr: &FlatCfr,
+    oop_combos: &[Combo],
+    ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
+    oop_blockers: &[[bool; 52]],
+    ip_blockers: &[[bool; 52]],
+    rivers: &RiverTables,
+) -> Vec<ShowdownOutcome> {
+    let (my_combos, opp_combos, opp_weights) = match player {
+        Player::OOP => (oop_combos, ip_combos, ip_weights),
+        Player::IP => (ip_combos, oop_combos, oop_weights),
+    };
+    (0..my_combos.len())
+        .map(|h| {
+            let mine = &my_combos[h];
+            let valid: Vec<u16> = opp_combos
+                .iter()
+                .enumerate()
+                .filter(|(_, o)| mine.0 != o.0 && mine.0 != o.1 && mine.1 != o.0 && mine.1 != o.1)
+                .map(|(j, _)| j as u16)
+                .collect();
+            let mut opp_reach = vec![0.0f64; opp_combos.len()];
+            for &j in &valid {
+                opp_reach[j as usize] = opp_weights[j as usize];
+            }
+            let ctx = OutcomeContext {
+                player,
+                hand_idx: h,
+                valid: &valid,
+                oop_cfr,
+                ip_cfr,
+                oop_blockers,
+                ip_blockers,
+                river_scores: &rivers.scores,
+            };
+            outcome_shares(outcome_traverse(tree, &ctx, &opp_reach, None))
+        })
+        .collect()
+}
+
+/// `br_traverse_turn` in average-strategy mode, accumulating the showdown
+/// outcome mass instead of chips. `river_card` is set below the chance node.
+fn outcome_traverse(
+    node: &TreeNode,
+    ctx: &OutcomeContext,
+    opp_reach: &[f64],
+    river_card: Option<u8>,
+) -> [f64; 3] {
+    match node {
+        TreeNode::Terminal { terminal_type: TerminalType::Showdown, .. } => {
+            let Some((oop_scores, ip_scores)) = river_card.and_then(|c| ctx.river_scores[c as usize].as_ref()) else {
+                return [0.0; 3]; // Turn-level showdown shouldn't exist
+            };
+            let (my_score, opp_scores) = match ctx.player {
+                Player::OOP => (oop_scores[ctx.hand_idx], ip_scores),
+                Player::IP => (ip_scores[ctx.hand_idx], oop_scores),
+            };
+            showdown_outcome_mass(my_score, opp_scores, ctx.valid, opp_reach)
+        }
+        TreeNode::Terminal { .. } => [0.0; 3],
+        TreeNode::Chance { cards, children, .. } => {
+            let (my_blockers, opp_blockers) = match ctx.player {
+                Player::OOP => (ctx.oop_blockers, ctx.ip_blockers),
+                Player::IP => (ctx.ip_blockers, ctx.oop_blockers),
+            };
+            let mut mass = [0.0f64; 3];
+            let mut valid_count = 0usize;
+            for (ci, &card) in cards.iter().enumerate() {
+                if my_blockers[ctx.hand_idx][card as usize] {
+                    continue;
+                }
+                valid_count += 1;
+                let new_opp_reach: Vec<f64> = opp_reach
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &r)| if opp_blockers[j][card as usize] { 0.0 } else { r })
+                    .collect();
+                let child = outcome_traverse(&children[ci], ctx, &new_opp_reach, Some(card));
+                for k in 0..3 {
+                    mass[k] += child[k];
+                }
+            }
+            if valid_count > 0 {
+                mass.map(|m| m / valid_count as f64)
+            } else {
+                mass
+            }
+        }
+        TreeNode::Action { node_id, player, children, actions, .. } => {
+            let num_actions = actions.len();
+            let nid = *node_id as usize;
+            let mut strat = vec![0.0f32; num_actions];
+            let mut mass = [0.0f64; 3];
+
+            if *player == ctx.player {
+                let cfr = match ctx.player {
+                    Player::OOP => ctx.oop_cfr,
+                    Player::IP => ctx.ip_cfr,
+                };
+                cfr.average_strategy(nid, ctx.hand_idx, &mut strat);
+                for a in 0..num_actions {
+                    if strat[a] <= 0.0 {
+                        continue;
+                    }
+                    let child = outcome_traverse(&children[a], ctx, opp_reach, river_card);
+                    for k in 0..3 {
+                        mass[k] += strat[a] as f64 * child[k];
+                    }
+                }
+            } else {
+                let opp_cfr = match ctx.player {
+                    Player::OOP => ctx.ip_cfr,
+                    Player::IP => ctx.oop_cfr,
+                };
+                for a in 0..num_actions {
+                    let mut new_opp_reach = vec![0.0f64; opp_reach.len()];
+                    for j in 0..opp_reach.len() {
+                        if opp_reach[j] > 0.0 {
+                            opp_cfr.average_strategy(nid, j, &mut strat);
+                            new_opp_reach[j] = opp_reach[j] * strat[a] as f64;
+                        }
+                    }
+                    let child = outcome_traverse(&children[a], ctx, &new_opp_reach, river_card);
+                    for k in 0..3 {
+                        mass[k] += child[k];
+                    }
+                }
+            }
+            mass
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Solution extraction
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 fn extract_solution(
     config: &TurnSolverConfig,
+    iterations: usize,
     tree: &TreeNode,
     oop_cfr: &FlatCfr,
     ip_cfr: &FlatCfr,
     oop_combos: &[Combo],
     ip_combos: &[Combo],
+    oop_weights: &[f64],
+    ip_weights: &[f64],
+    rivers: &RiverTables,
     _metas: &[crate::postflop_tree::NodeMeta],
 ) -> TurnSolution {
     // Compute exploitability
-    let oop_blockers: Vec<[bool; 52]> = oop_combos
-        .iter()
-        .map(|c| {
-            let mut b = [false; 52];
-            b[c.0 as usize] = true;
-            b[c.1 as usize] = true;
-            b
-        })
-        .collect();
-    let ip_blockers: Vec<[bool; 52]> = ip_combos
-        .iter()
-        .map(|c| {
-            let mut b = [false; 52];
-            b[c.0 as usize] = true;
-            b[c.1 as usize] = true;
-            b
-        })
-        .collect();
+    let oop_blockers = combo_blockers(oop_combos);
+    let ip_blockers = combo_blockers(ip_combos);
 
     let exploitability = compute_exploitability(
         tree,
@@ -1307,14 +2175,35 @@ fn extract_solution(
         ip_cfr,
         oop_combos,
         ip_combos,
+        oop_weights,
+        ip_weights,
         &oop_blockers,
         &ip_blockers,
-        &config.board,
+        rivers,
     );
 
-    // Extract turn-level strategies (first few action nodes before chance)
+    let outcomes = |player| {
+        compute_showdown_outcomes(
+            tree, player, oop_cfr, ip_cfr, oop_combos, ip_combos, oop_weights, ip_weights, &oop_blockers,
+            &ip_blockers, rivers,
+        )
+    };
+    let oop_showdown_outcomes = outcomes(Player::OOP);
+    let ip_showdown_outcomes = outcomes(Player::IP);
+
+    // Extract turn-level strategies, and the river subtrees' unless skipped
     let mut strategies = Vec::new();
-    extract_turn_strategies(tree, oop_cfr, ip_cfr, oop_combos, ip_combos, &mut strategies);
+    let mut river_strategies = Vec::new();
+    extract_turn_strategies(
+        tree,
+        oop_cfr,
+        ip_cfr,
+        oop_combos,
+        ip_combos,
+        &mut strategies,
+        &mut Vec::new(),
+        config.extract_rivers.then_some(&mut river_strategies),
+    );
 
     let board_str = config
         .board
@@ -1322,14 +2211,8 @@ fn extract_solution(
         .map(|&b| format!("{}", index_to_card(b)))
         .collect::<String>();
 
-    let oop_combo_strs: Vec<String> = oop_combos
-        .iter()
-        .map(|c| format!("{}{}", index_to_card(c.0), index_to_card(c.1)))
-        .collect();
-    let ip_combo_strs: Vec<String> = ip_combos
-        .iter()
-        .map(|c| format!("{}{}", index_to_card(c.0), index_to_card(c.1)))
-        .collect();
+    let oop_combo_strs: Vec<String> = oop_combos.iter().map(|c| c.to_string()).collect();
+    let ip_combo_strs: Vec<String> = ip_combos.iter().map(|c| c.to_string()).collect();
 
     TurnSolution {
         board: board_str,
@@ -1337,16 +2220,44 @@ fn extract_solution(
         ip_range: config.ip_range.clone(),
         starting_pot: config.starting_pot,
         effective_stack: config.effective_stack,
-        iterations: config.iterations,
+        iterations,
         exploitability,
         oop_combos: oop_combo_strs,
         ip_combos: ip_combo_strs,
         strategies,
         oop_pos: String::new(),
         ip_pos: String::new(),
+        combo_order_version: COMBO_ORDER_VERSION,
+        postprocess: None,
+        simplified: None,
+        quality: Some(SolveQuality::grade(
+            exploitability,
+            config.starting_pot,
+            iterations,
+            RECOMMENDED_TURN_ITERATIONS,
+            None,
+            oop_combos.len().max(ip_combos.len()),
+        )),
+        oop_showdown_outcomes,
+        ip_showdown_outcomes,
+        convergence_history: vec![],
+        oop_weights: config.oop_weights.clone(),
+        ip_weights: config.ip_weights.clone(),
+        exploitability_pct_pot: pct_of_pot(exploitability, config.starting_pot),
+        river_strategies,
+        raise_sizes: config.raise_sizes.clone(),
+        max_raises: config.max_raises,
+        river_raise_sizes: config.river_raise_sizes.clone(),
+        river_max_raises: config.river_max_raises,
+        time_limited: config.time_budget.is_some(),
     }
 }
 
+/// Collect the strategies of the action nodes under `node` into
+/// `strategies`, stopping at chance nodes. With `rivers`, each river subtree
+/// a chance node deals is collected there instead, under the turn `line`
+/// that leads to it.
+#[allow(clippy::too_many_arguments)]
 fn extract_turn_strategies(
     node: &TreeNode,
     oop_cfr: &FlatCfr,
@@ -1354,6 +2265,8 @@ fn extract_turn_strategies(
     oop_combos: &[Combo],
     ip_combos: &[Combo],
     strategies: &mut Vec<TurnNodeStrategy>,
+    line: &mut Vec<String>,
+    mut rivers: Option<&mut Vec<RiverCardStrategies>>,
 ) {
     match node {
         TreeNode::Action {
@@ -1388,12 +2301,25 @@ fn extract_turn_strategies(
                 frequencies,
             });
 
-            for child in children {
-                extract_turn_strategies(child, oop_cfr, ip_cfr, oop_combos, ip_combos, strategies);
+            for (action, child) in actions.iter().zip(children) {
+                line.push(action.label());
+                extract_turn_strategies(
+                    child, oop_cfr, ip_cfr, oop_combos, ip_combos, strategies, line, rivers.as_deref_mut(),
+                );
+                line.pop();
             }
         }
-        TreeNode::Chance { .. } => {
-            // Don't recurse into river subtrees for turn-level strategy extraction
+        TreeNode::Chance { cards, children, .. } => {
+            let Some(rivers) = rivers else { return };
+            for (&card, child) in cards.iter().zip(children) {
+                let mut river = Vec::new();
+                extract_turn_strategies(child, oop_cfr, ip_cfr, oop_combos, ip_combos, &mut river, line, None);
+                rivers.push(RiverCardStrategies {
+                    card: index_to_card(card).to_string(),
+                    turn_line: line.clone(),
+                    strategies: river,
+                });
+            }
         }
         TreeNode::Terminal { .. } => {}
     }
@@ -1419,6 +2345,22 @@ fn empty_solution(config: &TurnSolverConfig) -> TurnSolution {
         strategies: vec![],
         oop_pos: String::new(),
         ip_pos: String::new(),
+        combo_order_version: COMBO_ORDER_VERSION,
+        postprocess: None,
+        simplified: None,
+        quality: None,
+        oop_showdown_outcomes: vec![],
+        ip_showdown_outcomes: vec![],
+        convergence_history: vec![],
+        oop_weights: config.oop_weights.clone(),
+        ip_weights: config.ip_weights.clone(),
+        exploitability_pct_pot: 0.0,
+        river_strategies: vec![],
+        raise_sizes: config.raise_sizes.clone(),
+        max_raises: config.max_raises,
+        river_raise_sizes: config.river_raise_sizes.clone(),
+        river_max_raises: config.river_max_raises,
+        time_limited: false,
     }
 }
 
@@ -1428,6 +2370,51 @@ fn empty_solution(config: &TurnSolverConfig) -> TurnSolution {
 
 impl TurnSolution {
     pub fn display(&self) {
+        self.display_with(false);
+    }
+
+    /// How each range splits at the root and answers each root action
+    /// (see `range_summary`).
+    pub fn summary(&self) -> RangeSummary {
+        let (tree, _) = build_tree(&self.tree_config().turn);
+        let oop_weights = combo_weights(&self.oop_range, &self.oop_weights, &self.board, &self.oop_combos);
+        let ip_weights = combo_weights(&self.ip_range, &self.ip_weights, &self.board, &self.ip_combos);
+        summarize(
+            &tree,
+            |id| {
+                self.strategies
+                    .iter()
+                    .find(|s| s.node_id == id)
+                    .map(|s| (s.actions.as_slice(), s.frequencies.as_slice()))
+            },
+            WeightedCombos { combos: &self.oop_combos, weights: &oop_weights },
+            WeightedCombos { combos: &self.ip_combos, weights: &ip_weights },
+        )
+    }
+
+    /// Hand-class composition of each action the summary covers (see
+    /// `breakdown`).
+    pub fn breakdown(&self) -> Breakdown {
+        let (tree, _) = build_tree(&self.tree_config().turn);
+        let oop_weights = combo_weights(&self.oop_range, &self.oop_weights, &self.board, &self.oop_combos);
+        let ip_weights = combo_weights(&self.ip_range, &self.ip_weights, &self.board, &self.ip_combos);
+        breakdown(
+            &tree,
+            |id| {
+                self.strategies
+                    .iter()
+                    .find(|s| s.node_id == id)
+                    .map(|s| (s.actions.as_slice(), s.frequencies.as_slice()))
+            },
+            WeightedCombos { combos: &self.oop_combos, weights: &oop_weights },
+            WeightedCombos { combos: &self.ip_combos, weights: &ip_weights },
+            &self.board,
+        )
+    }
+
+    /// The summary; `expanded` lists the root combos one by one instead of
+    /// grouping those that play alike (see `display::group_combos`).
+    pub fn display_with(&self, expanded: bool) {
         use colored::Colorize;
 
         println!();
@@ -1439,15 +2426,30 @@ impl TurnSolution {
             self.effective_stack,
             self.iterations,
         );
-        println!("  Exploitability: {:.4}", self.exploitability);
+        println!(
+            "  Exploitability: {:.4}  ({:.1}% of pot)",
+            self.exploitability, self.exploitability_pct_pot
+        );
+        if let Some(pp) = &self.postprocess {
+            println!("  {}", pp.describe(self.exploitability).dimmed());
+        }
+        if let Some(simplified) = &self.simplified {
+            println!("  {}", format!("Strategies {}", simplified.describe()).yellow());
+        }
         println!(
             "  OOP range: {} ({} combos)  |  IP range: {} ({} combos)",
-            self.oop_range.join(","),
+            format_weighted_range(&self.oop_range, &self.oop_weights),
             self.oop_combos.len(),
-            self.ip_range.join(","),
+            format_weighted_range(&self.ip_range, &self.ip_weights),
             self.ip_combos.len(),
         );
 
+        let summary = self.summary();
+        if !summary.decisions.is_empty() {
+            println!();
+            println!("{}", range_summary_table(&summary));
+        }
+
         if let Some(root_strat) = self.strategies.first() {
             println!();
             println!(
@@ -1457,72 +2459,454 @@ impl TurnSolution {
             );
             println!("  Actions: {}", root_strat.actions.join(" | "));
 
-            let num_to_show = root_strat.frequencies.len().min(20);
             let combos = if root_strat.player == "OOP" {
                 &self.oop_combos
             } else {
                 &self.ip_combos
             };
+            print_combo_strategies(combos, &root_strat.frequencies, &root_strat.actions, expanded);
+        }
 
-            for i in 0..num_to_show {
-                let freq_str: String = root_strat.frequencies[i]
-                    .iter()
-                    .zip(&root_strat.actions)
-                    .map(|(f, a)| {
-                        let pct = (f * 100.0).round() as u32;
-                        if pct > 70 {
-                            format!("{}:{}", a, format!("{}%", pct).green())
-                        } else if pct > 30 {
-                            format!("{}:{}", a, format!("{}%", pct).yellow())
-                        } else {
-                            format!("{}:{}%", a, pct)
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join("  ");
-                println!("    {}  {}", combos[i].bold(), freq_str);
-            }
-            if root_strat.frequencies.len() > num_to_show {
-                println!(
-                    "    ... and {} more combos",
-                    root_strat.frequencies.len() - num_to_show
-                );
-            }
+        if let Some(river) = self.river_strategies.first() {
+            let cards = self.river_strategies.iter().filter(|r| r.turn_line == river.turn_line).count();
+            println!();
+            println!(
+                "  {}",
+                format!(
+                    "River strategies: {} subtrees over {} cards; --river-card {} to show one",
+                    self.river_strategies.len(),
+                    cards,
+                    river.card
+                )
+                .dimmed()
+            );
         }
 
         println!();
     }
+
+    /// The river subtrees `card` deals, one per turn line that reaches it.
+    pub fn river_subtrees<'a>(&'a self, card: &str) -> impl Iterator<Item = &'a RiverCardStrategies> + 'a {
+        let card = card.to_string();
+        self.river_strategies.iter().filter(move |r| r.card.eq_ignore_ascii_case(&card))
+    }
+
+    /// The strategy at the root of each river subtree `card` deals, for the
+    /// combos that don't hold the card.
+    pub fn display_river(&self, card: &str, expanded: bool) {
+        use colored::Colorize;
+
+        for river in self.river_subtrees(card) {
+            let Some(root_strat) = river.strategies.first() else { continue };
+            let combos = if root_strat.player == "OOP" {
+                &self.oop_combos
+            } else {
+                &self.ip_combos
+            };
+            let (combos, frequencies): (Vec<String>, Vec<Vec<f64>>) = combos
+                .iter()
+                .zip(&root_strat.frequencies)
+                .filter(|(combo, _)| !combo.to_lowercase().contains(&river.card.to_lowercase()))
+                .map(|(combo, freqs)| (combo.clone(), freqs.clone()))
+                .unzip();
+
+            println!();
+            println!(
+                "  River {} after {}: {} to act (node {})",
+                river.card.bold(),
+                river.turn_line.join(", "),
+                root_strat.player.bold(),
+                root_strat.node_id
+            );
+            println!("  Actions: {}", root_strat.actions.join(" | "));
+            print_combo_strategies(&combos, &frequencies, &root_strat.actions, expanded);
+        }
+        println!();
+    }
+
+    /// A copy that keeps only the river subtrees `card` deals, for output
+    /// filtered with `--river-card`.
+    pub fn with_river_card(&self, card: &str) -> TurnSolution {
+        let mut filtered = self.clone();
+        filtered.river_strategies.retain(|r| r.card.eq_ignore_ascii_case(card));
+        filtered
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Cache
 // ---------------------------------------------------------------------------
 
+/// `raising` is the tree's `raise_suffix`, empty for the default raising.
+fn turn_file_stem(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64, raising: &str) -> String {
+    format!("turn_{}_{}_{}_{:.0}_{:.0}{}", board, oop_pos, ip_pos, pot, stack, raising)
+}
+
+/// Cache file name suffix for turn and river raising other than the default
+/// (see `raise_suffix`).
+fn turn_raise_suffix(raise_sizes: &[f64], max_raises: usize, river_raise_sizes: &[f64], river_max_raises: usize) -> String {
+    let default = TurnTreeConfig::new(Vec::new(), 0.0, 0.0);
+    format!(
+        "{}{}",
+        raise_suffix("raise", raise_sizes, max_raises, &default.turn.raise_sizes, default.turn.max_raises),
+        raise_suffix("rvraise", river_raise_sizes, river_max_raises, &default.river_raise_sizes, default.river_max_raises),
+    )
+}
+
 impl TurnSolution {
+    /// Cache file name for a spot, its tree's `raise_suffix` and its ranges'
+    /// `range_key`, relative to the solver cache directory. Suit-isomorphic
+    /// boards share a file (see `canonical_board`).
+    pub fn cache_file_name(
+        board: &str,
+        oop_pos: &str,
+        ip_pos: &str,
+        pot: f64,
+        stack: f64,
+        raising: &str,
+        ranges: &str,
+    ) -> String {
+        let board = canonical_board(board).unwrap_or_else(|_| board.to_string());
+        format!("{}_{}.bin", turn_file_stem(&board, oop_pos, ip_pos, pot, stack, raising), ranges)
+    }
+
+    /// `range_key` of the solve's ranges.
+    pub fn range_key(&self) -> String {
+        range_key(&self.oop_range, &self.oop_weights, &self.ip_range, &self.ip_weights)
+    }
+
     pub fn cache_path(&self) -> std::path::PathBuf {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let dir = std::path::Path::new(&home).join(".gto-cli").join("solver");
+        let dir = solver_cache_dir();
         std::fs::create_dir_all(&dir).ok();
-        dir.join(format!(
-            "turn_{}_{}_{}_{:.0}_{:.0}.bin",
-            self.board, self.oop_pos, self.ip_pos, self.starting_pot, self.effective_stack,
+        // Other raising is kept apart from the default trees `gto query` reads
+        dir.join(Self::cache_file_name(
+            &self.board,
+            &self.oop_pos,
+            &self.ip_pos,
+            self.starting_pot,
+            self.effective_stack,
+            &self.raise_suffix(),
+            &self.range_key(),
         ))
     }
 
+    /// Cache file name suffix for raising other than the default (see
+    /// `raise_suffix`).
+    pub fn raise_suffix(&self) -> String {
+        turn_raise_suffix(&self.raise_sizes, self.max_raises, &self.river_raise_sizes, self.river_max_raises)
+    }
+
+    /// The turn+river tree the solution was solved on.
+    pub fn tree_config(&self) -> TurnTreeConfig {
+        let board = crate::cards::parse_board(&self.board)
+            .unwrap_or_default()
+            .iter()
+            .map(crate::card_encoding::card_to_index)
+            .collect();
+        let mut tree = TurnTreeConfig::new(board, self.starting_pot, self.effective_stack);
+        tree.turn.raise_sizes = self.raise_sizes.clone();
+        tree.turn.max_raises = self.max_raises;
+        tree.river_raise_sizes = self.river_raise_sizes.clone();
+        tree.river_max_raises = self.river_max_raises;
+        tree
+    }
+
     pub fn save_cache(&self) {
         if let Ok(data) = bincode::serialize(self) {
             let path = self.cache_path();
-            std::fs::write(path, data).ok();
+            crate::status::cache_written(&path, write_atomic(&path, &data));
+        }
+    }
+
+    /// Cache file for a drill-down solve of `board` from a flop solve (see
+    /// `drill_key`). Drills aren't relabeled, so the board is kept as dealt.
+    pub fn drill_cache_path(board: &str, oop_pos: &str, ip_pos: &str, drill_key: &str) -> std::path::PathBuf {
+        let dir = solver_cache_dir();
+        std::fs::create_dir_all(&dir).ok();
+        dir.join(format!("turn_drill_{}_{}_{}_{}.bin", board, oop_pos, ip_pos, drill_key))
+    }
+
+    /// The drill-down solve cached at `path`, if any.
+    pub fn load_drill(path: &std::path::Path) -> Option<TurnSolution> {
+        Self::read_file(path)
+    }
+
+    /// The solve cached at `path`, if it's one in the current combo order.
+    fn read_file(path: &std::path::Path) -> Option<TurnSolution> {
+        let mut solution: TurnSolution = bincode::deserialize(&std::fs::read(path).ok()?).ok()?;
+        solution.fill_exploitability_pct();
+        (solution.combo_order_version == COMBO_ORDER_VERSION).then_some(solution)
+    }
+
+    /// Fill in `exploitability_pct_pot` for solves cached before it was
+    /// recorded, which read it back as 0.
+    pub fn fill_exploitability_pct(&mut self) {
+        if self.exploitability_pct_pot == 0.0 {
+            self.exploitability_pct_pot = pct_of_pot(self.exploitability, self.starting_pot);
+        }
+    }
+
+    pub fn save_drill(&self, path: &std::path::Path) {
+        if let Ok(data) = bincode::serialize(self) {
+            crate::status::cache_written(path, write_atomic(path, &data));
+        }
+    }
+
+    /// The cached solve for a spot with the ranges of `ranges` (a
+    /// `range_key`), or for a suit-isomorphic board, in the suits of `board`.
+    /// `None` takes the spot's latest solve whatever its ranges (see
+    /// `read_spot_cache`). Only solves with the default raising are read.
+    pub fn load_cache(
+        board: &str,
+        oop_pos: &str,
+        ip_pos: &str,
+        pot: f64,
+        stack: f64,
+        ranges: Option<&str>,
+    ) -> Option<TurnSolution> {
+        Self::load_cache_raised(board, oop_pos, ip_pos, pot, stack, "", ranges)
+    }
+
+    /// `load_cache` for a tree with the raising of `raising` (a
+    /// `raise_suffix`).
+    pub fn load_cache_raised(
+        board: &str,
+        oop_pos: &str,
+        ip_pos: &str,
+        pot: f64,
+        stack: f64,
+        raising: &str,
+        ranges: Option<&str>,
+    ) -> Option<TurnSolution> {
+        let canonical = canonical_board(board).unwrap_or_else(|_| board.to_string());
+        // Solves cached before file names were canonicalized are under their own board
+        let stems = [
+            turn_file_stem(&canonical, oop_pos, ip_pos, pot, stack, raising),
+            turn_file_stem(board, oop_pos, ip_pos, pot, stack, raising),
+        ];
+        let mut solution = read_spot_cache(&stems, ranges, Self::read_file, TurnSolution::range_key)?;
+        solution.relabel_to(board).ok()?;
+        Some(solution)
+    }
+
+    /// Express the solution in the suits of `board`, a suit-isomorphic board:
+    /// board, combos and every per-combo array are relabeled.
+    pub fn relabel_to(&mut self, board: &str) -> Result<(), String> {
+        let map = suit_permutation(&self.board, board)?
+            .ok_or_else(|| format!("Board {} is not a suit relabeling of {}", board, self.board))?;
+        if map == [0, 1, 2, 3] {
+            return Ok(());
+        }
+        let (oop_combos, oop_order) = relabel_combos(&self.oop_combos, &map);
+        let (ip_combos, ip_order) = relabel_combos(&self.ip_combos, &map);
+        let river_nodes = self.river_strategies.iter_mut().flat_map(|r| &mut r.strategies);
+        for strategy in self.strategies.iter_mut().chain(river_nodes) {
+            let order = if strategy.player == "OOP" { &oop_order } else { &ip_order };
+            strategy.frequencies = reorder_rows(&strategy.frequencies, order);
+        }
+        for river in &mut self.river_strategies {
+            river.card = relabel_suits(&river.card, &map);
+        }
+        self.oop_showdown_outcomes = reorder_rows(&self.oop_showdown_outcomes, &oop_order);
+        self.ip_showdown_outcomes = reorder_rows(&self.ip_showdown_outcomes, &ip_order);
+        self.board = relabel_suits(&self.board, &map);
+        self.oop_combos = oop_combos;
+        self.ip_combos = ip_combos;
+        Ok(())
+    }
+
+    /// False for solves run below the iteration floor (via `--force`); those
+    /// aren't served to `gto query`. Time-limited solves always meet it.
+    pub fn meets_quality_floor(&self) -> bool {
+        self.time_limited || crate::limits::TURN_ITERATIONS.meets_floor(self.iterations)
+    }
+
+    /// The stored quality grade, or one computed from the headline numbers
+    /// for solutions built without it.
+    pub fn solve_quality(&self) -> SolveQuality {
+        self.quality.clone().unwrap_or_else(|| {
+            SolveQuality::grade(
+                self.exploitability,
+                self.starting_pot,
+                self.iterations,
+                RECOMMENDED_TURN_ITERATIONS,
+                None,
+                self.oop_combos.len().max(self.ip_combos.len()),
+            )
+        })
+    }
+
+    /// Showdown outcome of `player`'s ("OOP"/"IP") combo, or `None` for
+    /// solutions saved without outcomes and combos that never reach showdown.
+    pub fn showdown_outcome(&self, player: &str, combo_idx: usize) -> Option<ShowdownOutcome> {
+        let outcomes = if player == "OOP" { &self.oop_showdown_outcomes } else { &self.ip_showdown_outcomes };
+        outcomes.get(combo_idx).copied().filter(|(w, t, l)| w + t + l > 0.0)
+    }
+
+    /// EV of each action at `node_id`, a turn or river node, for each of
+    /// `player`'s combos `combo_idxs`, in starting pots: both sides play the
+    /// stored strategies after the action, as in
+    /// `RiverSolution::action_evs`. `None` for `--no-rivers` solves, which
+    /// keep no river play to price the turn actions with, if the node isn't
+    /// `player`'s, or if no opponent combo reaches it with one of the combos.
+    pub fn action_evs(&self, node_id: u16, player: Player, combo_idxs: &[usize]) -> Option<Vec<Vec<f64>>> {
+        let profile = self.profile()?;
+        let num_combos = match player {
+            Player::OOP => profile.oop_combos.len(),
+            Player::IP => profile.ip_combos.len(),
+        };
+        combo_idxs
+            .iter()
+            .map(|&h| {
+                if h >= num_combos {
+                    return None;
+                }
+                let (values, total) = profile.action_values(node_id, player, h)?;
+                (total >= 1e-10).then(|| values.iter().map(|v| v / total / self.starting_pot).collect())
+            })
+            .collect()
+    }
+
+    /// The stored turn and river strategies loaded back into CFR tables over
+    /// the rebuilt tree; `None` for `--no-rivers` solves or if a combo or the
+    /// board doesn't parse.
+    fn profile(&self) -> Option<TurnProfile> {
+        if self.river_strategies.is_empty() {
+            return None;
+        }
+        let board: Vec<u8> = parse_board(&self.board).ok()?.iter().map(card_to_index).collect();
+        let oop_combos = parse_combo_strings(&self.oop_combos)?;
+        let ip_combos = parse_combo_strings(&self.ip_combos)?;
+        if board.len() != 4 || oop_combos.is_empty() || ip_combos.is_empty() {
+            return None;
+        }
+
+        let (tree, _) = build_turn_tree(&self.tree_config());
+        let metas = collect_node_metadata(&tree);
+        let table = |side: Player, num_combos: usize| -> Vec<(u8, u16)> {
+            metas
+                .iter()
+                .map(|m| (m.num_actions, if m.player == side { num_combos as u16 } else { 0 }))
+                .collect()
+        };
+        let mut oop_cfr = FlatCfr::new(&table(Player::OOP, oop_combos.len()));
+        let mut ip_cfr = FlatCfr::new(&table(Player::IP, ip_combos.len()));
+        let river_nodes = self.river_strategies.iter().flat_map(|r| &r.strategies);
+        for node in self.strategies.iter().chain(river_nodes) {
+            let (cfr, num_combos) = match node.player.as_str() {
+                "OOP" => (&mut oop_cfr, oop_combos.len()),
+                _ => (&mut ip_cfr, ip_combos.len()),
+            };
+            let nid = node.node_id as usize;
+            if nid >= cfr.num_nodes()
+                || cfr.node_num_hands(nid) as usize != num_combos
+                || node.frequencies.len() != num_combos
+                || node.frequencies.iter().any(|f| f.len() != cfr.node_num_actions(nid) as usize)
+            {
+                return None;
+            }
+            for (h, freqs) in node.frequencies.iter().enumerate() {
+                let freqs: Vec<f32> = freqs.iter().map(|&f| f as f32).collect();
+                cfr.set_average_strategy(nid, h, &freqs);
+            }
+        }
+
+        let valid_ip_for_oop = valid_opponents(&oop_combos, &ip_combos);
+        let valid_oop_for_ip = valid_opponents(&ip_combos, &oop_combos);
+        let rivers = RiverTables::new(&board, &oop_combos, &ip_combos, &valid_ip_for_oop, &valid_oop_for_ip);
+        Some(TurnProfile {
+            tree,
+            oop_cfr,
+            ip_cfr,
+            oop_weights: combo_weights(&self.oop_range, &self.oop_weights, &self.board, &self.oop_combos),
+            ip_weights: combo_weights(&self.ip_range, &self.ip_weights, &self.board, &self.ip_combos),
+            oop_blockers: combo_blockers(&oop_combos),
+            ip_blockers: combo_blockers(&ip_combos),
+            oop_combos,
+            ip_combos,
+            rivers,
+        })
+    }
+
+    /// A copy with every turn and river strategy rounded by `simplify`. Its
+    /// EV loss is how much more a best response wins against each side's
+    /// rounded strategy than against its original, on the rebuilt tree and
+    /// averaged over the two sides; there's none to measure without river
+    /// strategies (`--no-rivers`).
+    pub fn simplified(&self, simplify: Simplify) -> TurnSolution {
+        let mut simplified = self.clone();
+        let river_nodes = simplified.river_strategies.iter_mut().flat_map(|r| &mut r.strategies);
+        for node in simplified.strategies.iter_mut().chain(river_nodes) {
+            simplify.apply_all(&mut node.frequencies);
         }
+        let loss = self
+            .profile()
+            .zip(simplified.profile())
+            .map(|(before, after)| {
+                let (before, after) = (before.best_response_values(), after.best_response_values());
+                let conceded: f64 = (0..2).map(|p| (after[p] - before[p]).max(0.0)).sum();
+                pct_of_pot(conceded / 2.0, self.starting_pot)
+            });
+        simplified.simplified = Some(simplify.info(loss));
+        simplified
+    }
+
+    /// Index of a combo (either card order) in `oop_combos`.
+    pub fn oop_combo_index(&self, combo: &str) -> Option<usize> {
+        find_combo_index(combo, &self.oop_combos)
     }
 
-    pub fn load_cache(board: &str, oop_pos: &str, ip_pos: &str, pot: f64, stack: f64) -> Option<TurnSolution> {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let path = std::path::Path::new(&home)
-            .join(".gto-cli")
-            .join("solver")
-            .join(format!("turn_{}_{}_{}_{:.0}_{:.0}.bin", board, oop_pos, ip_pos, pot, stack));
-        let data = std::fs::read(path).ok()?;
-        bincode::deserialize(&data).ok()
+    /// Index of a combo (either card order) in `ip_combos`.
+    pub fn ip_combo_index(&self, combo: &str) -> Option<usize> {
+        find_combo_index(combo, &self.ip_combos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_and_sequential_paths_agree() {
+        // Each combo only touches its own rows, so deferring the updates
+        // changes nothing
+        let config = TurnSolverConfig::new("Ks9d4c7h", "AA,KK,99,AQs,JTs", "QQ,TT,AJo,T8s", 10.0, 50.0, 60).unwrap();
+        let sequential = solve_turn_with(&config, usize::MAX).0;
+        let parallel = solve_turn_with(&config, 0).0;
+        let root = |s: &TurnSolution| s.strategies.iter().find(|n| n.node_id == 0).unwrap().frequencies.clone();
+        let (seq, par) = (root(&sequential), root(&parallel));
+        assert_eq!(seq.len(), par.len());
+        let max_diff = seq
+            .iter()
+            .zip(&par)
+            .flat_map(|(a, b)| a.iter().zip(b).map(|(x, y)| (x - y).abs()))
+            .fold(0.0f64, f64::max);
+        assert!(max_diff < 1e-6, "root strategies differ by {}", max_diff);
+    }
+
+    #[test]
+    fn test_precomputed_river_tables_keep_outputs() {
+        // Root strategies recorded before the river scores and validity were
+        // hoisted out of the chance nodes; exploitability since the average
+        // strategy's own nodes stopped reading a buffer their children reuse
+        let config = TurnSolverConfig::new("Ks9d4c7h", "AA,KK,99,AQs,JTs", "QQ,TT,AJo,T8s", 10.0, 50.0, 40).unwrap();
+        let expected: [(&str, [f64; 4]); 5] = [
+            ("AcAd", [0.21777616441249847, 0.3128436505794525, 0.30224642157554626, 0.16713379323482513]),
+            ("AcQc", [0.05692719295620918, 0.38321653008461, 0.41639944911003113, 0.14345683157444]),
+            ("KcKd", [0.24679802358150482, 0.31794270873069763, 0.3004501760005951, 0.13480906188488007]),
+            ("JcTc", [0.15179219841957092, 0.2199452519416809, 0.23557095229625702, 0.39269164204597473]),
+            ("9c9h", [0.22395312786102295, 0.3233954906463623, 0.31177595257759094, 0.1408754140138626]),
+        ];
+        for parallel_min_combos in [usize::MAX, 0] {
+            let solution = solve_turn_with(&config, parallel_min_combos).0;
+            assert!((solution.exploitability - 44.3468454238967).abs() < 1e-9);
+            let root = solution.strategies.iter().find(|n| n.node_id == 0).unwrap();
+            for (combo, freqs) in &expected {
+                let idx = solution.oop_combo_index(combo).unwrap();
+                for (got, want) in root.frequencies[idx].iter().zip(freqs) {
+                    assert!((got - want).abs() < 1e-9, "{}: {:?} vs {:?}", combo, root.frequencies[idx], freqs);
+                }
+            }
+        }
     }
 }
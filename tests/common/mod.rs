@@ -0,0 +1,32 @@
+//! Helpers shared by the integration tests.
+
+use std::path::{Path, PathBuf};
+
+use gto_cli::cache_index::CACHE_DIR_ENV;
+
+/// A fresh solver cache directory that `GTO_CACHE_DIR` points at until it
+/// is dropped, when it is removed. The variable is process-wide, so only
+/// one test in each test binary may own a scratch cache.
+pub struct ScratchCache {
+    dir: PathBuf,
+}
+
+impl ScratchCache {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("gto-{}-{}", name, std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        std::env::set_var(CACHE_DIR_ENV, &dir);
+        ScratchCache { dir }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for ScratchCache {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
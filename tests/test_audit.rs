@@ -951,7 +951,7 @@ fn audit_pipeline_value_hand() {
     let input = b"6max\n1/2\n200\nBTN\n2\nAhKs\nn\ny\nKd7c2h\nbet\n50\ny\nJh\nbet\n75\ny\n3c\nbet\n100\nn\n";
     let mut reader = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
 
     assert!(out.contains("RAISE"), "AKs BTN should RAISE preflop");
@@ -965,7 +965,7 @@ fn audit_pipeline_drawing_hand() {
     let input = b"6max\n1/2\n200\nCO\n2\nTs9s\nn\ny\n8s7d2s\nbet\n50\ny\nAc\ncheck\ny\nKs\nbet\n100\nn\n";
     let mut reader = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
 
     assert!(out.contains("RAISE") || out.contains("CALL"), "T9s CO should open");
@@ -982,7 +982,7 @@ fn audit_pipeline_missed_hand_fold() {
     let input = b"6max\n1/2\n200\nBTN\n2\n7h2c\nn\nn\n";
     let mut reader = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
 
     assert!(out.contains("FOLD"), "72o from BTN should FOLD");
@@ -994,7 +994,7 @@ fn audit_pipeline_facing_raise() {
     let input = b"6max\n1/2\n200\nCO\n2\nQsQh\ny\nUTG\nn\ny\nAs7d2c\nbet\n50\nn\nn\n";
     let mut reader = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
 
     assert!(
@@ -1010,7 +1010,7 @@ fn audit_pipeline_bb_defense() {
     let input = b"6max\n1/2\n200\nBB\n2\n8s7s\ny\nBTN\nn\nn\n";
     let mut reader = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
 
     // Should get a valid preflop action
@@ -1027,7 +1027,7 @@ fn audit_pipeline_no_panics_full_hand() {
     let input = b"6max\n1/2\n200\nBTN\n2\nAsKh\nn\ny\nQd9c3s\ncheck\ny\n5d\ncheck\ny\n2h\ncheck\nn\n";
     let mut reader = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
 
     // Verify the session completed without errors
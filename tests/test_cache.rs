@@ -0,0 +1,205 @@
+//! Tests for the solver cache directory: listing, loading and pruning.
+//!
+//! The cache is pointed at a scratch directory with `GTO_CACHE_DIR`, so one
+//! test owns the directory; the others don't touch it.
+
+use std::time::Duration;
+
+use gto_cli::cache::{load, parse_age, prune, scan, CacheKind, CachedSolution};
+use gto_cli::cache_index::{solver_cache_dir, CACHE_DIR_ENV};
+use gto_cli::cache_key::range_key_of;
+use gto_cli::game_tree::{CallNode, MultiwayPushFoldResult};
+use gto_cli::preflop_solver::{
+    ForcedBets, Position, PreflopSizing, PreflopSolution, PreflopSpotResult, PREFLOP_CACHE_VERSION,
+};
+use gto_cli::strategy::StrategyEngine;
+use gto_cli::river_solver::{solve_river, RiverSolution, RiverSolverConfig};
+
+#[test]
+fn test_cache_files_are_listed_loaded_and_pruned() {
+    let dir = std::env::temp_dir().join(format!("gto-cache-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_var(CACHE_DIR_ENV, &dir);
+    assert_eq!(solver_cache_dir(), dir);
+
+    let config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK", "QQ,JJ", 6.0, 97.0, 300).unwrap();
+    let mut river = solve_river(&config);
+    river.oop_pos = "BB".to_string();
+    river.ip_pos = "BTN".to_string();
+    assert!(river.cache_path().starts_with(&dir));
+    river.save_cache();
+
+    // The file is keyed by the ranges: another matchup on the same seats
+    // misses, and a lookup that knows only the seats takes what's there
+    let key = river.range_key();
+    assert_eq!(key, range_key_of("KK,AA", "QQ,JJ"));
+    let other = range_key_of("AA", "QQ");
+    let lookup = |ranges: Option<&str>| RiverSolution::load_cache("Ks9d4c7hQc", "BB", "BTN", 6.0, 97.0, ranges);
+    assert_eq!(lookup(Some(&key)).unwrap().oop_range, river.oop_range);
+    assert!(lookup(Some(&other)).is_none());
+    assert!(lookup(None).is_some());
+
+    // A file named before range keys is read, and renamed, for its own ranges only
+    let legacy = dir.join("river_Ks9d4c7hQc_BB_BTN_6_97.bin");
+    std::fs::rename(river.cache_path(), &legacy).unwrap();
+    assert!(lookup(Some(&other)).is_none());
+    assert!(legacy.exists());
+    assert!(lookup(Some(&key)).is_some());
+    assert!(!legacy.exists() && river.cache_path().exists());
+
+    let preflop = PreflopSolution {
+        version: PREFLOP_CACHE_VERSION,
+        table_size: "6max".to_string(),
+        stack_bb: 100.0,
+        rake_pct: 0.0,
+        sizing: PreflopSizing::default(),
+        forced_bets: ForcedBets::default(),
+        iterations: 1000,
+        spots: vec![],
+    };
+    assert!(preflop.cache_path().starts_with(&dir));
+    preflop.save().unwrap();
+
+    // Files from before the format was versioned still load; ones from a
+    // newer version don't
+    let json = std::fs::read_to_string(preflop.cache_path()).unwrap();
+    let unversioned = json.replacen(&format!("\"version\":{},", PREFLOP_CACHE_VERSION), "", 1);
+    assert_ne!(unversioned, json);
+    std::fs::write(preflop.cache_path(), unversioned).unwrap();
+    assert_eq!(PreflopSolution::load("6max", 100.0, 0.0, &PreflopSizing::default()).unwrap().version, 0);
+    let newer = PreflopSolution { version: PREFLOP_CACHE_VERSION + 1, ..preflop.clone() };
+    newer.save().unwrap();
+    let err = PreflopSolution::load("6max", 100.0, 0.0, &PreflopSizing::default()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    preflop.save().unwrap();
+
+    // Other sizings get their own file, and one whose contents were solved
+    // at another sizing is an error rather than a match
+    let sizing = PreflopSizing::new(2.2, 3.0, 2.5, 100.0).unwrap();
+    let resized = PreflopSolution { sizing, ..preflop.clone() };
+    assert_ne!(resized.cache_path(), preflop.cache_path());
+    std::fs::copy(preflop.cache_path(), resized.cache_path()).unwrap();
+    let err = PreflopSolution::load("6max", 100.0, 0.0, &sizing).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    resized.save().unwrap();
+    assert_eq!(PreflopSolution::load("6max", 100.0, 0.0, &sizing).unwrap().sizing, sizing);
+    std::fs::remove_file(resized.cache_path()).unwrap();
+
+    // So do antes and a straddle
+    let forced = ForcedBets::new(0.125, 2.0, 100.0).unwrap();
+    let straddled = PreflopSolution { forced_bets: forced, ..preflop.clone() };
+    assert_ne!(straddled.cache_path(), preflop.cache_path());
+    straddled.save().unwrap();
+    let sizing = PreflopSizing::default();
+    assert_eq!(PreflopSolution::load_with("6max", 100.0, 0.0, &sizing, &forced).unwrap().forced_bets, forced);
+    std::fs::remove_file(straddled.cache_path()).unwrap();
+
+    // Queries find a straddled solve at a sizing of its own too
+    let sizing = PreflopSizing::new(6.0, 3.0, 2.5, 100.0).unwrap();
+    let spot = PreflopSpotResult {
+        opener: Position::HJ,
+        responder: Position::UTG,
+        open_strategy: vec![1.0; 169],
+        vs_open_3bet: vec![0.0; 169],
+        vs_open_call: vec![0.0; 169],
+        vs_3bet_4bet: vec![0.0; 169],
+        vs_3bet_call: vec![0.0; 169],
+        vs_4bet_allin: vec![0.0; 169],
+        vs_4bet_call: vec![0.0; 169],
+        vs_5bet_call: vec![0.0; 169],
+        exploitability: 0.0,
+        iterations: 1000,
+        evs: None,
+    };
+    let resized = PreflopSolution { sizing, spots: vec![spot], ..straddled.clone() };
+    resized.save().unwrap();
+    let engine = StrategyEngine::new(100.0).with_preflop_game(&sizing, forced);
+    let rfi = engine.query_preflop("AKs", Position::HJ, None).expect("answers from the resized solve");
+    assert_eq!(rfi.actions[0], "RAISE 6bb");
+    std::fs::remove_file(resized.cache_path()).unwrap();
+
+    let pushfold = MultiwayPushFoldResult {
+        shover: Position::CO,
+        stack_bb: 12.0,
+        ante_bb: 0.125,
+        rake_pct: 0.0,
+        iterations: 1000,
+        shove_strategy: vec![1.0; 169],
+        calls: vec![CallNode { position: Position::BB, callers_ahead: vec![], call_strategy: vec![0.0; 169] }],
+    };
+    assert_eq!(pushfold.cache_path(), dir.join("pushfold_CO_12bb_0.125ante_0pct.json"));
+    pushfold.save().unwrap();
+    let reloaded = MultiwayPushFoldResult::load(Position::CO, 12.0, 0.125, 0.0).unwrap();
+    assert_eq!(reloaded.calls[0].position, Position::BB);
+
+    // Locks, the flop index and stray files aren't solutions
+    std::fs::write(dir.join("flop_x.bin.lock"), "1").unwrap();
+    std::fs::write(dir.join("index.bin"), []).unwrap();
+    std::fs::write(dir.join("notes.txt"), "x").unwrap();
+
+    let entries = scan(&dir);
+    let kinds: Vec<CacheKind> = entries.iter().map(|e| e.kind).collect();
+    assert_eq!(kinds, [CacheKind::Preflop, CacheKind::PushFold, CacheKind::River]);
+    assert_eq!(entries[0].file, "preflop_6max_100bb_0pct.json");
+    assert!(entries.iter().all(|e| e.len > 0));
+
+    // JSON and bincode payloads both load
+    let header = load(&dir.join(&entries[0].file)).unwrap().header();
+    assert_eq!((header.seats.as_str(), header.iterations, header.grade), ("6max", 1000, None));
+    let loaded = load(&dir.join(&entries[1].file)).unwrap();
+    assert_eq!(loaded.header().seats, "CO");
+    assert_eq!(loaded.root_summary(), ["CO shove 100.0%", "BB call 0.0%"]);
+    let river_file = dir.join(&entries[2].file);
+    let loaded = load(&river_file).unwrap();
+    assert!(matches!(loaded, CachedSolution::River(_)));
+    let header = loaded.header();
+    assert_eq!(header.board, "Ks9d4c7hQc");
+    assert_eq!(header.seats, "BBvBTN");
+    assert_eq!((header.pot, header.stack, header.iterations), (Some(6.0), 97.0, 300));
+    assert!(header.grade.is_some());
+    assert_eq!(loaded.root_summary().len(), 1);
+
+    // Garbage under a solution name is an error, not a panic
+    std::fs::write(dir.join("turn_bad.bin"), [1, 2, 3]).unwrap();
+    let err = load(&dir.join("turn_bad.bin")).err().unwrap();
+    assert!(err.contains("is not a turn solution"), "{}", err);
+    assert!(load(&dir.join("notes.txt")).is_err());
+
+    // Nothing is a day old; by type only the matching kind goes
+    assert!(prune(&dir, Some(Duration::from_secs(86_400)), None).unwrap().is_empty());
+    let removed = prune(&dir, None, Some(CacheKind::River)).unwrap();
+    assert_eq!(removed.len(), 1);
+    assert!(!river_file.exists());
+    let removed = prune(&dir, Some(Duration::ZERO), None).unwrap();
+    assert_eq!(removed.len(), 3);
+    assert!(scan(&dir).is_empty());
+    assert!(dir.join("index.bin").exists() && dir.join("notes.txt").exists());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_parse_age_units() {
+    assert_eq!(parse_age("30d").unwrap(), Duration::from_secs(30 * 86_400));
+    assert_eq!(parse_age("12h").unwrap(), Duration::from_secs(12 * 3600));
+    assert_eq!(parse_age("90m").unwrap(), Duration::from_secs(90 * 60));
+    assert_eq!(parse_age("2w").unwrap(), Duration::from_secs(14 * 86_400));
+    assert_eq!(parse_age("45s").unwrap(), Duration::from_secs(45));
+    for bad in ["30", "d", "30y", "-1d", "1.5d"] {
+        assert!(parse_age(bad).is_err(), "{}", bad);
+    }
+    assert!(parse_age("18446744073709551615w").unwrap_err().contains("too large"));
+}
+
+#[test]
+fn test_cache_kind_of_file() {
+    assert_eq!(CacheKind::of_file("flop_Ks9d4c_BB_BTN_6_97.bin"), Some(CacheKind::Flop));
+    assert_eq!(CacheKind::of_file("river_Ks9d4c7hQc_BB_BTN_6_97_locked.bin"), Some(CacheKind::River));
+    assert_eq!(CacheKind::of_file("preflop_6max_100bb_0pct.json"), Some(CacheKind::Preflop));
+    assert_eq!(CacheKind::of_file("pushfold_CO_12bb_0.125ante_0pct.json"), Some(CacheKind::PushFold));
+    assert_eq!(CacheKind::of_file("flop_Ks9d4c_BB_BTN_6_97.ckpt"), None);
+    assert_eq!(CacheKind::of_file("preflop_6max_100bb_0pct.bin"), None);
+    assert_eq!(CacheKind::of_file("index.bin"), None);
+    assert_eq!(CacheKind::parse("Turn"), Some(CacheKind::Turn));
+}
@@ -101,6 +101,14 @@ fn test_parse_board_river() {
     assert_eq!(board.len(), 5);
 }
 
+#[test]
+fn test_parse_board_rejects_repeats_and_long_boards() {
+    assert!(matches!(parse_board("AsKdAs"), Err(GtoError::ConflictingCards(ref c)) if c == "As"));
+    assert!(matches!(parse_board("AsKdQh5c2s3d"), Err(GtoError::BoardTooLong(6))));
+    // Plain card lists keep repeats for the caller to report
+    assert_eq!(parse_cards("AsKdAs").unwrap().len(), 3);
+}
+
 #[test]
 fn test_deck_full() {
     let d = Deck::new(None);
@@ -168,6 +176,25 @@ fn test_simplify_offsuit() {
     assert_eq!(simplify_hand(&cards).unwrap(), "AKo");
 }
 
+#[test]
+fn test_hand_to_canonical() {
+    assert_eq!(hand_to_canonical("9h9d"), "99");
+    assert_eq!(hand_to_canonical("KsAs"), "AKs");
+    assert_eq!(hand_to_canonical("Td9c"), "T9o");
+    assert_eq!(hand_to_canonical("AKo"), "AKo");
+}
+
+#[test]
+fn test_canonical_hand_class() {
+    assert_eq!(canonical_hand_class("qq").as_deref(), Some("QQ"));
+    assert_eq!(canonical_hand_class("kas").as_deref(), Some("AKs"));
+    assert_eq!(canonical_hand_class("T9O").as_deref(), Some("T9o"));
+    assert_eq!(canonical_hand_class("AK"), None);
+    assert_eq!(canonical_hand_class("AAs"), None);
+    assert_eq!(canonical_hand_class("AKx"), None);
+    assert_eq!(canonical_hand_class("AhKs"), None);
+}
+
 #[test]
 fn test_simplify_ordering() {
     let cards1 = vec![
@@ -214,3 +241,54 @@ fn test_hand_combos_specific() {
     assert_eq!(combos[0].0, Card::new(Rank::Ace, Suit::Spades));
     assert_eq!(combos[0].1, Card::new(Rank::King, Suit::Hearts));
 }
+
+#[test]
+fn test_pretty_output_round_trips_through_parser() {
+    use gto_cli::strategy::{pretty_board_styled, pretty_hand_styled};
+    for style in [SuitStyle::Unicode, SuitStyle::Ascii] {
+        for &rank in &ALL_RANKS {
+            for &suit in &ALL_SUITS {
+                let card = Card::new(rank, suit);
+                let rendered = format!("{}{}", rank.to_char(), suit.render(style));
+                assert_eq!(parse_card(&rendered).unwrap(), card, "{}", rendered);
+                let board = pretty_board_styled(&card.to_string(), style);
+                assert_eq!(parse_board(&board).unwrap(), vec![card], "{}", board);
+            }
+        }
+        let hand = pretty_hand_styled("AhTd", style);
+        assert_eq!(normalize_cards(&hand).unwrap(), "AhTd");
+        let board = pretty_board_styled("Ks9d4c7h2s", style);
+        assert_eq!(normalize_cards(&board).unwrap(), "Ks9d4c7h2s");
+    }
+}
+
+#[test]
+fn test_parse_accepts_outline_and_emoji_suits_and_ten() {
+    let expected = parse_board("AsKhQdJc").unwrap();
+    assert_eq!(parse_board("A\u{2664}K\u{2661}Q\u{2662}J\u{2667}").unwrap(), expected);
+    assert_eq!(parse_board("A\u{2660}\u{FE0F} K\u{2665}\u{FE0F} Q\u{2666} J\u{2663}").unwrap(), expected);
+    assert_eq!(parse_card("10h").unwrap(), Card::new(Rank::Ten, Suit::Hearts));
+    assert_eq!(normalize_cards("10s 9\u{2666} 4c").unwrap(), "Ts9d4c");
+    assert!(parse_board("K\u{2660}9").is_err());
+}
+
+#[test]
+fn test_ascii_board_table_alignment() {
+    use comfy_table::Table;
+    use gto_cli::strategy::pretty_board_styled;
+
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::ASCII_FULL);
+    table.set_header(vec!["Board", "Texture"]);
+    table.add_row(vec![pretty_board_styled("Ks9d4c", SuitStyle::Ascii), "dry".to_string()]);
+    table.add_row(vec![pretty_board_styled("JhTh9c", SuitStyle::Ascii), "wet".to_string()]);
+    let expected = "\
++----------+---------+
+| Board    | Texture |
++====================+
+| Ks 9d 4c | dry     |
+|----------+---------|
+| Jh Th 9c | wet     |
++----------+---------+";
+    assert_eq!(table.to_string(), expected);
+}
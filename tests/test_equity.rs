@@ -1,5 +1,6 @@
 use gto_cli::cards::*;
 use gto_cli::equity::*;
+use gto_cli::error::GtoError;
 
 fn c(notation: &str) -> Card {
     parse_card(notation).unwrap()
@@ -93,3 +94,212 @@ fn test_no_valid_combos() {
     );
     assert!(result.is_err());
 }
+
+fn weighted(range: &str) -> Vec<(String, f64)> {
+    gto_cli::ranges::parse_weighted_range(range).unwrap()
+}
+
+#[test]
+fn test_weighted_range_on_river_is_hand_computable() {
+    // AhAd vs AA:1.0, KK:0.5 on a blank river: the one live AA combo (AsAc)
+    // chops, the six KK combos at half weight (3 combos) all lose.
+    let hero = [c("Ah"), c("Ad")];
+    let board = parse_board("Qs7c4d2h9c").unwrap();
+    let result = equity_vs_weighted_range(&hero, &weighted("AA:1.0, KK:0.5"), Some(&board), 1000).unwrap();
+    assert!((result.villain_combos - 4.0).abs() < 1e-12);
+    assert!((result.equity() - (0.5 + 3.0) / 4.0).abs() < 1e-12);
+
+    // Unweighted: 1 AA + 6 KK
+    let unweighted = equity_vs_range(&hero, &["AA".to_string(), "KK".to_string()], Some(&board), 1000).unwrap();
+    assert!((unweighted.villain_combos - 7.0).abs() < 1e-12);
+    assert!((unweighted.equity() - (0.5 + 6.0) / 7.0).abs() < 1e-12);
+
+    // Frequencies can also ride on range terms
+    let terms = equity_vs_range(&hero, &["AA:1.0".to_string(), "KK:0.5".to_string()], Some(&board), 1000).unwrap();
+    assert!((terms.equity() - result.equity()).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_range_from_the_flop_is_enumerated() {
+    let hero = [c("Ah"), c("Ad")];
+    let board = parse_board("Kc8h4s").unwrap();
+    let range = weighted("AA:1.0, KK:0.5");
+    let exact = exact_equity_vs_weighted_range(&hero, &range, &board, &[]).unwrap();
+    let result = equity_vs_weighted_range(&hero, &range, Some(&board), 100).unwrap();
+    assert!(exact.exact && result.exact);
+    assert_eq!(result.equity(), exact.equity());
+    // Hero's aces block five of AA's six combos; KK keeps 3 live combos at half weight
+    assert!((exact.villain_combos - (1.0 + 1.5)).abs() < 1e-12);
+    // Four live combos, each against all 990 turn and river pairs
+    assert_eq!(exact.simulations, 4 * 990);
+
+    let unweighted = exact_equity_vs_weighted_range(&hero, &weighted("AA, KK"), &board, &[]).unwrap();
+    assert!((unweighted.equity() - exact.equity()).abs() > 0.01);
+
+    // Preflop stays sampled unless enumeration is asked for
+    let sampled = equity_vs_weighted_range(&hero, &weighted("KK"), None, 1000).unwrap();
+    assert!(!sampled.exact);
+}
+
+#[test]
+fn test_hand_vs_hand_enumerates_runouts() {
+    let aa = [c("As"), c("Ah")];
+    let kk = [c("Ks"), c("Kh")];
+
+    // One river card to come: all 44 are dealt, whatever `simulations` says
+    let board = parse_board("2s5d8cJh").unwrap();
+    let turn = equity_vs_hand(&aa, &kk, Some(&board), 10).unwrap();
+    assert!(turn.exact);
+    assert_eq!(turn.simulations, 44);
+    // Kings need one of the two kings left
+    assert!((turn.lose - 2.0 / 44.0).abs() < 1e-12);
+
+    let river = equity_vs_hand(&aa, &kk, Some(&parse_board("2s5d8cJh3c").unwrap()), 10).unwrap();
+    assert_eq!((river.simulations, river.win), (1, 1.0));
+
+    // Preflop enumeration deals all C(48, 5) boards and agrees with sampling
+    let exact = exact_equity_vs_hand(&aa, &kk, &[], &[]).unwrap();
+    assert!(exact.exact);
+    assert_eq!(exact.simulations, 1_712_304);
+    let sampled = equity_vs_hand(&aa, &kk, None, 100_000).unwrap();
+    assert!(!sampled.exact);
+    assert!((exact.equity() - sampled.equity()).abs() < 0.01);
+    assert!((exact.win + exact.tie + exact.lose - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_range_vs_range_on_river_is_hand_computable() {
+    // AA vs KK,QQ with a king on board: KK's three live combos make a set
+    // and beat all six AA combos; the six QQ combos lose to them
+    let board = parse_board("Kd7c4s2h9c").unwrap();
+    let result = equity_range_vs_range(&weighted("AA"), &weighted("KK, QQ"), Some(&board), 1000).unwrap();
+    assert!(result.exact);
+    assert_eq!(result.matchups, 6 * 9);
+    assert!((result.equity - 36.0 / 54.0).abs() < 1e-12);
+
+    let kk = result.classes2.iter().find(|c| c.hand == "KK").unwrap();
+    let qq = result.classes2.iter().find(|c| c.hand == "QQ").unwrap();
+    assert_eq!((kk.combos, qq.combos), (3, 6));
+    assert_eq!(kk.equity, Some(1.0));
+    assert_eq!(qq.equity, Some(0.0));
+}
+
+#[test]
+fn test_range_vs_range_removes_card_conflicts() {
+    // AKs vs AA: each AKs combo blocks three of the six AA combos
+    let result = equity_range_vs_range(&weighted("AKs"), &weighted("AA"), None, 200_000).unwrap();
+    assert!(!result.exact);
+    assert_eq!(result.matchups, 4 * 3);
+
+    // Preflop sampling lands near the known AA vs KK equity (~82%)
+    let result = equity_range_vs_range(&weighted("AA"), &weighted("KK"), None, 200_000).unwrap();
+    assert!((result.equity - 0.82).abs() < 0.015, "{}", result.equity);
+    assert!(equity_range_vs_range(&weighted("AhAs"), &weighted("AsAh"), None, 1000).is_err());
+}
+
+#[test]
+fn test_hand_vs_hand_rejects_shared_cards() {
+    let result = equity_vs_hand(&[c("Ah"), c("As")], &[c("Ah"), c("Kd")], None, 1000);
+    assert!(matches!(result, Err(GtoError::ConflictingCards(ref cards)) if cards == "Ah"));
+
+    let board = parse_board("Kd9s4c").unwrap();
+    assert!(equity_vs_hand(&[c("Ah"), c("As")], &[c("Kd"), c("Kh")], Some(&board), 1000).is_err());
+    assert!(exact_equity_vs_hand(&[c("Ah"), c("As")], &[c("Kd"), c("Kh")], &board, &[]).is_err());
+}
+
+#[test]
+fn test_hand_vs_range_rejects_hero_on_board_but_drops_blocked_villain_combos() {
+    let board = parse_board("Kd9s4c").unwrap();
+    let result = equity_vs_range(&[c("Kd"), c("Ks")], &["AA".to_string()], Some(&board), 1000);
+    assert!(matches!(result, Err(GtoError::ConflictingCards(ref cards)) if cards == "Kd"));
+
+    // KdKx combos share the board's Kd and just drop out: 3 of 6 KK combos remain
+    let result = equity_vs_range(&[c("Ah"), c("As")], &["KK".to_string()], Some(&board), 1000).unwrap();
+    assert_eq!(result.villain_combos, 3.0);
+}
+
+#[test]
+fn test_precision_target_stops_sampling_early() {
+    // AA vs KK: ±1% needs ~6,500 runouts, so a 1M cap stops after two batches
+    let sampling = Sampling { precision: Some(0.01), seed: Some(7) };
+    let result = equity_vs_hand_sampled(&[c("As"), c("Ah")], &[c("Ks"), c("Kh")], None, &[], 1_000_000, sampling).unwrap();
+    assert!(result.converged);
+    assert!(result.simulations < 20_000, "{}", result.simulations);
+    assert!(result.ci_half_width() <= 0.01);
+    assert!((result.equity() - 0.82).abs() < 0.03, "{}", result.equity());
+
+    // A cap too small for the target runs out first
+    let result = equity_vs_weighted_range_sampled(
+        &[c("As"), c("Ah")],
+        &weighted("KK, QQ, AKs"),
+        None,
+        &[],
+        2_000,
+        Sampling { precision: Some(0.001), seed: Some(7) },
+    )
+    .unwrap();
+    assert!(!result.converged);
+    assert!(result.std_error > 0.0);
+
+    let bad = Sampling { precision: Some(0.0), seed: None };
+    assert!(equity_vs_hand_sampled(&[c("As"), c("Ah")], &[c("Ks"), c("Kh")], None, &[], 1000, bad).is_err());
+}
+
+#[test]
+fn test_seeded_sampling_is_reproducible() {
+    let hero = [c("Ah"), c("Kh")];
+    let sampling = Sampling { precision: None, seed: Some(42) };
+    let a = equity_vs_weighted_range_sampled(&hero, &weighted("TT+, AQs+"), None, &[], 20_000, sampling).unwrap();
+    let b = equity_vs_weighted_range_sampled(&hero, &weighted("TT+, AQs+"), None, &[], 20_000, sampling).unwrap();
+    assert_eq!((a.win, a.tie, a.simulations), (b.win, b.tie, b.simulations));
+
+    let a = equity_vs_hand_sampled(&hero, &[c("Qs"), c("Qd")], None, &[], 12_345, sampling).unwrap();
+    let b = equity_vs_hand_sampled(&hero, &[c("Qs"), c("Qd")], None, &[], 12_345, sampling).unwrap();
+    assert_eq!((a.win, a.tie, a.simulations), (b.win, b.tie, 12_345));
+    assert!(!a.converged && !a.exact);
+
+    // Enumerated results carry no sampling error
+    let exact = equity_vs_hand(&hero, &[c("Qs"), c("Qd")], Some(&parse_board("2c7d9s").unwrap()), 100).unwrap();
+    assert!(exact.converged && exact.std_error == 0.0);
+}
+
+#[test]
+fn test_dead_cards_leave_the_deck() {
+    // AA vs a set of kings on the turn: only the two remaining aces save hero,
+    // and with both dead there's nothing left to hit
+    let aa = [c("Ah"), c("Ad")];
+    let kk = [c("Ks"), c("Kd")];
+    let board = parse_board("Kc7h4d2s").unwrap();
+    let live = equity_vs_hand(&aa, &kk, Some(&board), 1000).unwrap();
+    assert!((live.equity() - 2.0 / 44.0).abs() < 1e-12);
+    let dead = [c("As"), c("Ac")];
+    let result = equity_vs_hand_sampled(&aa, &kk, Some(&board), &dead, 1000, Sampling::default()).unwrap();
+    assert_eq!((result.equity(), result.simulations), (0.0, 42));
+
+    // Dead cards also take villain combos out of a range: KsKh and KhKd are gone
+    let range = weighted("KK");
+    let result = exact_equity_vs_weighted_range(&aa, &range, &board, &[c("Kh")]).unwrap();
+    assert_eq!(result.villain_combos, 1.0);
+
+    let err = equity_vs_hand_sampled(&aa, &kk, None, &[c("Ad")], 1000, Sampling::default());
+    assert!(matches!(err, Err(GtoError::DeadCardDealt { place: "in your hand", .. })));
+    let err = exact_equity_vs_hand(&aa, &kk, &board, &[c("7h")]);
+    assert!(matches!(err, Err(GtoError::DeadCardDealt { place: "on the board", .. })));
+}
+
+#[test]
+fn test_equity_distribution_averages_to_turn_equity() {
+    // Hand against hand every river is equally likely, so the per-card
+    // equities average to the turn equity
+    let board = parse_board("2h7h9cKs").unwrap();
+    let hero = [c("Ah"), c("Qh")];
+    let distribution = equity_distribution(&hero, &weighted("JsJd"), &board, &[]).unwrap();
+    assert_eq!(distribution.len(), 44);
+    let mean = distribution.iter().map(|(_, e)| e).sum::<f64>() / 44.0;
+    let turn = exact_equity_vs_hand(&hero, &[c("Js"), c("Jd")], &board, &[]).unwrap();
+    assert!((mean - turn.equity()).abs() < 1e-9);
+    // Any heart makes the nut flush
+    assert!(distribution.iter().filter(|(card, _)| card.suit == Suit::Hearts).all(|(_, e)| *e == 1.0));
+
+    assert!(equity_distribution(&hero, &weighted("JsJd"), &board[..2], &[]).is_err());
+}
@@ -0,0 +1,162 @@
+//! Tests for hand history parsing and spot analysis.
+//!
+//! The analysis test reads solver caches, so it points `GTO_CACHE_DIR` at a
+//! scratch directory; the parser tests don't touch it.
+
+use gto_cli::cache_index::CACHE_DIR_ENV;
+use gto_cli::hand_history::{analyze_hand, parse_hand_histories, replay, HandAction, Spot, Street};
+use gto_cli::preflop_solver::Position;
+use gto_cli::river_solver::{solve_river, RiverSolverConfig};
+use gto_cli::strategy::{postflop_seats, PotType, StrategyEngine};
+
+const FIXTURES: &str = include_str!("fixtures/hand_histories.txt");
+
+fn parse_err(text: &str) -> String {
+    parse_hand_histories(text).expect_err("malformed hand parses")
+}
+
+#[test]
+fn test_fixture_hands_parse() {
+    let hands = parse_hand_histories(FIXTURES).unwrap();
+    let ids: Vec<&str> = hands.iter().map(|h| h.id.as_str()).collect();
+    assert_eq!(ids, ["srp-river", "3bet-pot", "multiway", "limped"]);
+
+    let srp = &hands[0];
+    assert_eq!(srp.hero, Position::BTN);
+    assert_eq!(srp.hole_cards, "QhQd");
+    assert_eq!(srp.seats.len(), 6);
+    assert_eq!(srp.streets.len(), 4);
+    assert_eq!(srp.streets[3].street, Street::River);
+    assert_eq!(srp.streets[3].cards, "Qc");
+    assert_eq!(srp.streets[3].line, 9);
+
+    // Suit symbols and a "bb" suffix are accepted
+    assert_eq!(hands[1].hole_cards, "AhAd");
+    assert_eq!(hands[1].streets[1].actions[1].action, HandAction::Raise(20.0));
+    assert_eq!(hands[3].effective_stack(), 40.0);
+}
+
+#[test]
+fn test_replay_derives_pots_stacks_and_lines() {
+    let hands = parse_hand_histories(FIXTURES).unwrap();
+
+    // BTN opens 2.5, BB calls, SB's blind is dead: 5.5bb pot, 97.5bb behind
+    let decisions = replay(&hands[0]).unwrap();
+    let streets: Vec<Street> = decisions.iter().map(|d| d.street).collect();
+    assert_eq!(streets, [Street::Preflop, Street::Flop, Street::Turn, Street::River]);
+    assert_eq!(decisions[0].spot, Spot::Preflop { villain: None });
+    let river = &decisions[3];
+    assert_eq!(river.board, "Ks9d4c7hQc");
+    assert!((river.pot - 5.5).abs() < 1e-9);
+    assert!((river.stack - 97.5).abs() < 1e-9);
+    assert_eq!(river.action, HandAction::Bet(4.0));
+    assert_eq!(
+        river.spot,
+        Spot::Postflop { villain: Position::BB, pot_type: PotType::Srp, line: vec!["check".to_string()] }
+    );
+
+    // A 3-bet pot: bets and raises become pot-percentage steps, a shove "allin"
+    let decisions = replay(&hands[1]).unwrap();
+    assert_eq!(decisions[0].spot, Spot::Preflop { villain: Some(Position::CO) });
+    assert!((decisions[1].pot - 22.5).abs() < 1e-9);
+    assert_eq!(decisions[1].spot, Spot::Postflop { villain: Position::CO, pot_type: PotType::ThreeBet, line: vec![] });
+    let Spot::Postflop { line, .. } = &decisions[2].spot else {
+        panic!("heads-up flop");
+    };
+    assert_eq!(line, &["bet31", "raise36"]);
+    assert!((decisions[2].chips - 82.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_replay_marks_spots_no_solution_covers() {
+    let hands = parse_hand_histories(FIXTURES).unwrap();
+
+    let decisions = replay(&hands[2]).unwrap();
+    assert_eq!(decisions[0].spot, Spot::Preflop { villain: Some(Position::HJ) });
+    assert!(matches!(&decisions[1].spot, Spot::Uncovered(why) if why.contains("multiway")));
+
+    let decisions = replay(&hands[3]).unwrap();
+    assert_eq!(decisions.len(), 2);
+    assert!(decisions.iter().all(|d| matches!(&d.spot, Spot::Uncovered(why) if why.contains("limped"))));
+}
+
+#[test]
+fn test_parse_errors_name_the_line_and_what_was_expected() {
+    let header = "Hand: 1\nSeats: BTN 100, BB 100\nHero: BTN AhKd\n";
+    let cases = [
+        ("Seats: BTN 100, BB 100\n", "line 1: expected 'Hand:' to start a hand"),
+        ("Hand: 1\nHero: BTN AhKd\n", "line 2: expected a Seats line, got Hero"),
+        ("Hand: 1\nSeats: BTN 100, XX 100\n", "line 2: expected a position"),
+        ("Hand: 1\nSeats: BTN 100, BB deep\n", "line 2: expected an amount in bb, got 'deep'"),
+        ("Hand: 1\nSeats: BTN 100, BB 100\nHero: CO AhKd\n", "line 3: hero's position CO has no seat"),
+        ("Hand: 1\nSeats: BTN 100, BB 100\nHero: BTN Ah\n", "line 3: expected two hole cards"),
+        ("Hand: 1\nSeats: BTN 100, BB 100\nHero: BTN AhKd\n", "line 1: hand 1 ends without a Preflop line"),
+    ];
+    for (text, expected) in cases {
+        let err = parse_err(text);
+        assert!(err.contains(expected), "{:?}: {}", text, err);
+    }
+
+    let cases = [
+        ("Preflop: BB call\n", "line 4: expected BTN to act, got BB"),
+        ("Preflop: BTN jam\n", "line 4: expected fold, check, call, bet or raise after BTN"),
+        ("Preflop: BTN\n", "line 4: expected fold, check, call, bet or raise after BTN, got 'BTN'"),
+        ("Preflop: BTN raise\n", "line 4: expected 'BTN raise AMOUNT'"),
+        ("Preflop: BTN bet 3\n", "line 4: BTN faces 1; raise instead of bet"),
+        ("Preflop: BTN raise 2.5\nFlop: Ks9d4c\n", "line 4: the preflop action isn't finished; BB is still to act"),
+        ("Preflop: BTN raise 2.5, BB call\nFlop: Ks9d\n", "line 5: expected 3 cards for the flop, got 'Ks9d'"),
+        ("Preflop: BTN raise 2.5, BB call\nFlop: Ks9dAh\n", "line 5: card Ah is dealt twice"),
+        ("Preflop: BTN raise 2.5, BB call\nFlop: Ks9d4c | BB bet 2, BTN check\n", "line 5: BTN cannot check facing 2"),
+        ("Preflop: BTN raise 2.5, BB call\nFlop: Ks9d4c | BB check, BTN bet 200\n", "line 5: BTN puts in 200 with 97.5 behind"),
+        ("Preflop: BTN raise 2.5, BB fold\nFlop: Ks9d4c\n", "line 5: the hand is over after the preflop"),
+        ("Preflop: BTN raise 2.5, BB call\nTurn: 7h\n", "line 5: expected a Flop line, got Turn"),
+    ];
+    for (lines, expected) in cases {
+        let err = parse_err(&format!("{}{}", header, lines));
+        assert!(err.contains(expected), "{:?}: {}", lines, err);
+    }
+}
+
+#[test]
+fn test_analysis_compares_hero_with_cached_solve() {
+    let dir = std::env::temp_dir().join(format!("gto-hand-history-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_var(CACHE_DIR_ENV, &dir);
+
+    // The river of the first fixture hand, solved at the pot and stack its action gives
+    let hands = parse_hand_histories(FIXTURES).unwrap();
+    let (oop_pos, ip_pos) = postflop_seats("BB", "BTN").unwrap();
+    let config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK,K9s", "QQ,JJ,AK", 5.5, 97.5, 1000).unwrap();
+    let mut solution = solve_river(&config);
+    solution.oop_pos = oop_pos.as_str().to_string();
+    solution.ip_pos = ip_pos.as_str().to_string();
+    solution.save_cache();
+
+    let mut engine = StrategyEngine::new(100.0);
+    let analyses = analyze_hand(&hands[0], &mut engine, 300).unwrap();
+    assert_eq!(analyses.len(), 4);
+
+    // No preflop solution here: preflop and the uncached flop and turn say why
+    let err = analyses[0].result.as_ref().err().unwrap();
+    assert!(err.contains("gto solve preflop"), "{}", err);
+    assert!(analyses[1].result.as_ref().err().unwrap().contains("preflop"));
+
+    let river = &analyses[3];
+    let result = river.result.as_ref().expect("cached river solve is found");
+    let chosen = river.chosen.expect("hero's bet maps to a solver action");
+    assert!(result.actions[chosen].starts_with("Bet"), "{:?}", result.actions);
+    assert!((result.frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+    // River solves store no per-action EVs; they're priced against the
+    // stored strategies. Hero's 3.7bb bet gives up most of what the jam wins
+    let evs = result.evs.as_ref().expect("river EVs are evaluated on lookup");
+    assert_eq!(evs.len(), result.actions.len());
+    let best = evs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    assert_eq!(result.actions.last().map(String::as_str), Some("Bet 97.5"));
+    assert_eq!(evs.last().copied(), Some(best), "{:?}", evs);
+    let loss = river.ev_loss.expect("hero's action is priced");
+    assert!((loss - (best - evs[chosen]) * river.decision.pot).abs() < 1e-9, "{} vs {:?}", loss, evs);
+    assert!((loss - 9.15).abs() < 0.1, "lost {:.2}bb", loss);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
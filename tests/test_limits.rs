@@ -0,0 +1,104 @@
+//! Tests for iteration/simulation bounds and the `--force` override.
+
+use gto_cli::cards::parse_card;
+use gto_cli::equity::{equity_vs_hand, equity_vs_range};
+use gto_cli::error::SolverError;
+use gto_cli::flop_solver::FlopSolverConfig;
+use gto_cli::limits::*;
+use gto_cli::river_solver::RiverSolverConfig;
+use gto_cli::turn_solver::TurnSolverConfig;
+
+const ALL: [Bounds; 6] = [
+    PUSHFOLD_ITERATIONS,
+    PREFLOP_ITERATIONS,
+    RIVER_ITERATIONS,
+    TURN_ITERATIONS,
+    FLOP_ITERATIONS,
+    EQUITY_SIMS,
+];
+
+#[test]
+fn zero_is_rejected_even_with_force() {
+    for b in ALL {
+        assert!(b.check(0, false).is_err(), "{}", b.name);
+        assert!(b.check(0, true).is_err(), "{}", b.name);
+    }
+}
+
+#[test]
+fn minimum_is_inclusive() {
+    for b in ALL {
+        let err = b.check(b.min - 1, false).unwrap_err();
+        assert!(err.contains(&b.min.to_string()), "{}", err);
+        assert!(err.contains("--force"), "{}", err);
+        assert!(b.check(b.min, false).is_ok(), "{}", b.name);
+    }
+}
+
+#[test]
+fn maximum_is_inclusive() {
+    for b in ALL {
+        assert!(b.check(b.max, false).is_ok(), "{}", b.name);
+        let err = b.check(b.max + 1, false).unwrap_err();
+        assert!(err.contains(&b.max.to_string()), "{}", err);
+        assert!(err.contains("--force"), "{}", err);
+    }
+}
+
+#[test]
+fn force_lifts_both_bounds() {
+    for b in ALL {
+        assert!(b.check(1, true).is_ok(), "{}", b.name);
+        assert!(b.check(b.max + 1, true).is_ok(), "{}", b.name);
+    }
+}
+
+#[test]
+fn force_stops_at_the_hard_maximum() {
+    for b in ALL {
+        assert!(b.check(b.hard_max(), true).is_ok(), "{}", b.name);
+        let err = b.check(b.hard_max() + 1, true).unwrap_err();
+        assert!(err.contains(&b.hard_max().to_string()), "{}", err);
+    }
+}
+
+/// Build a config with `iterations` for the street `b` bounds.
+fn new_config(b: Bounds, iterations: usize) -> Result<(), SolverError> {
+    match b.name {
+        "Flop iterations" => FlopSolverConfig::new("Ks9d4c", "AA", "QQ", 10.0, 50.0, iterations).map(|_| ()),
+        "Turn iterations" => TurnSolverConfig::new("Ks9d4c2h", "AA", "QQ", 10.0, 50.0, iterations).map(|_| ()),
+        _ => RiverSolverConfig::new("Ks9d4c2h7s", "AA", "QQ", 10.0, 50.0, iterations).map(|_| ()),
+    }
+}
+
+#[test]
+fn solver_configs_reject_zero_and_absurd_iterations() {
+    for b in [FLOP_ITERATIONS, TURN_ITERATIONS, RIVER_ITERATIONS] {
+        for n in [0, b.hard_max() + 1, usize::MAX] {
+            let err = new_config(b, n).unwrap_err();
+            assert!(matches!(err, SolverError::InvalidConfig { field: "iterations", .. }), "{}: {}", b.name, err);
+        }
+        // Library callers aren't held to the CLI's bounds, only to these
+        assert!(new_config(b, 1).is_ok(), "{}", b.name);
+        assert!(new_config(b, b.hard_max()).is_ok(), "{}", b.name);
+    }
+}
+
+#[test]
+fn quality_floor_matches_minimum() {
+    for b in ALL {
+        assert!(!b.meets_floor(b.min - 1), "{}", b.name);
+        assert!(b.meets_floor(b.min), "{}", b.name);
+    }
+    assert_eq!(RIVER_ITERATIONS.min, 1_000);
+    assert_eq!(FLOP_ITERATIONS.min, 10_000);
+    assert_eq!(EQUITY_SIMS.min, 100);
+}
+
+#[test]
+fn equity_rejects_zero_sims() {
+    let aa = [parse_card("As").unwrap(), parse_card("Ah").unwrap()];
+    let kk = [parse_card("Ks").unwrap(), parse_card("Kh").unwrap()];
+    assert!(equity_vs_hand(&aa, &kk, None, 0).is_err());
+    assert!(equity_vs_range(&aa, &["KK".to_string()], None, 0).is_err());
+}
@@ -37,6 +37,25 @@ fn test_implied_odds_zero_future() {
     assert!((imp - po).abs() < 0.001);
 }
 
+#[test]
+fn test_parse_equity_reads_fractions_and_percentages() {
+    assert_eq!(parse_equity("0.35").unwrap(), 0.35);
+    assert_eq!(parse_equity("35").unwrap(), 0.35);
+    assert_eq!(parse_equity("35%").unwrap(), 0.35);
+    assert_eq!(parse_equity("1").unwrap(), 1.0);
+    assert_eq!(parse_equity("1%").unwrap(), 0.01);
+    assert!(parse_equity("135").is_err());
+    assert!(parse_equity("-0.2").is_err());
+    assert!(parse_equity("lots").is_err());
+}
+
+#[test]
+fn test_next_card_equity_counts_unseen_cards() {
+    assert!((next_card_equity(DrawType::Flush.outs(), 3).unwrap() - 9.0 / 47.0).abs() < 1e-12);
+    assert!((next_card_equity(DrawType::OpenEnded.outs(), 4).unwrap() - 8.0 / 46.0).abs() < 1e-12);
+    assert!(next_card_equity(9, 5).is_err());
+}
+
 #[test]
 fn test_reverse_implied_odds_basic() {
     let result = reverse_implied_odds(100.0, 50.0, 100.0).unwrap();
@@ -81,6 +100,30 @@ fn test_mdf_overbet() {
     assert!((result - 1.0 / 3.0).abs() < 0.001);
 }
 
+#[test]
+fn test_mdf_vs_raise_check_raise() {
+    // Bet 3.3 into 10, raised to 11: call 7.7 into 24.3; raiser risks 11 to win 13.3
+    let r = mdf_vs_raise(10.0, 3.3, 11.0).unwrap();
+    assert!((r.to_call - 7.7).abs() < 0.001);
+    assert!((r.pot_odds - 7.7 / 32.0).abs() < 0.001);
+    assert!((r.mdf - 13.3 / 24.3).abs() < 0.001);
+}
+
+#[test]
+fn test_mdf_vs_raise_pot_sized() {
+    // Half-pot bet, raised to 150: call 100 to win 300, raiser risks 150 to win 150
+    let r = mdf_vs_raise(100.0, 50.0, 150.0).unwrap();
+    assert!((r.to_call - 100.0).abs() < 0.001);
+    assert!((r.pot_odds - 0.25).abs() < 0.001);
+    assert!((r.mdf - 0.5).abs() < 0.001);
+}
+
+#[test]
+fn test_mdf_vs_raise_rejects_non_raise() {
+    assert!(mdf_vs_raise(10.0, 5.0, 5.0).is_err());
+    assert!(mdf_vs_raise(0.0, 5.0, 15.0).is_err());
+}
+
 #[test]
 fn test_fold_equity_profitable() {
     let result = fold_equity(0.6, 100.0, 75.0);
@@ -151,3 +194,41 @@ fn test_effective_stack_three() {
 fn test_effective_stack_invalid() {
     assert!(effective_stack(&[100.0]).is_err());
 }
+
+#[test]
+fn test_geometric_sizing_three_pot_sized_bets() {
+    // Pot 1 with 13 behind: pot-sized bets of 1, 3 and 9 (pots 1, 3, 9, 27)
+    let plan = geometric_sizing(1.0, 13.0, 3).unwrap();
+    assert!((plan.fraction - 1.0).abs() < 1e-9);
+    let bets: Vec<f64> = plan.streets.iter().map(|s| s.bet).collect();
+    let pots: Vec<f64> = plan.streets.iter().map(|s| s.pot).collect();
+    for (got, want) in bets.iter().zip([1.0, 3.0, 9.0]).chain(pots.iter().zip([1.0, 3.0, 9.0])) {
+        assert!((got - want).abs() < 1e-9, "{} vs {}", got, want);
+    }
+    assert_eq!(plan.streets[2].stack_after, 0.0);
+    for street in &plan.streets {
+        assert!((street.mdf - 0.5).abs() < 1e-9);
+        assert!((street.break_even_fold - 0.5).abs() < 1e-9);
+    }
+    // Villain continues half the time on each street: 1/8 reaches showdown
+    assert!((plan.cumulative_fold - 0.875).abs() < 1e-9);
+    assert_eq!(plan.total_risk, 13.0);
+}
+
+#[test]
+fn test_geometric_sizing_one_street_is_a_shove() {
+    let plan = geometric_sizing(6.0, 94.0, 1).unwrap();
+    assert_eq!(plan.streets.len(), 1);
+    assert!((plan.streets[0].bet - 94.0).abs() < 1e-9);
+    assert!((plan.fraction - 94.0 / 6.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_geometric_sizing_rejects_shallow_stacks_and_bad_streets() {
+    // 2bb behind a 6bb pot over 3 streets: a first bet of about 0.56bb
+    let err = geometric_sizing(6.0, 2.0, 3).unwrap_err().to_string();
+    assert!(err.contains("too shallow"), "{}", err);
+    assert!(geometric_sizing(6.0, 94.0, 0).is_err());
+    assert!(geometric_sizing(6.0, 94.0, 4).is_err());
+    assert!(geometric_sizing(0.0, 94.0, 3).is_err());
+}
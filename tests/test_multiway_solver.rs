@@ -0,0 +1,128 @@
+//! Tests for the three-handed river tree and solver.
+
+use gto_cli::multiway_solver::{solve_river_multiway, MultiwaySolverConfig};
+use gto_cli::postflop_tree::{build_multiway_tree, Action, MultiwayNode, TreeConfig};
+
+fn small_config() -> TreeConfig {
+    TreeConfig {
+        bet_sizes: vec![0.5],
+        raise_sizes: vec![1.0],
+        max_raises: 1,
+        starting_pot: 10.0,
+        effective_stack: 20.0,
+        add_allin: false,
+    }
+}
+
+fn child<'a>(node: &'a MultiwayNode, action: &str) -> &'a MultiwayNode {
+    let MultiwayNode::Action { actions, children, .. } = node else {
+        panic!("expected an action node");
+    };
+    let a = actions.iter().position(|a| a.label() == action).expect("action exists");
+    &children[a]
+}
+
+fn seat(node: &MultiwayNode) -> usize {
+    match node {
+        MultiwayNode::Action { seat, .. } => *seat,
+        MultiwayNode::Terminal { .. } => panic!("expected an action node"),
+    }
+}
+
+#[test]
+fn test_multiway_check_around_reaches_showdown() {
+    let (tree, _) = build_multiway_tree(&small_config());
+    assert_eq!(seat(&tree), 0);
+    let mp = child(&tree, "Check");
+    assert_eq!(seat(mp), 1);
+    let ip = child(mp, "Check");
+    assert_eq!(seat(ip), 2);
+    match child(ip, "Check") {
+        MultiwayNode::Terminal { active, invested, pot } => {
+            assert_eq!(*active, [true; 3]);
+            assert_eq!(*invested, [0.0; 3]);
+            assert_eq!(*pot, 10.0);
+        }
+        _ => panic!("three checks end the street"),
+    }
+}
+
+#[test]
+fn test_multiway_fold_removes_one_player() {
+    let (tree, _) = build_multiway_tree(&small_config());
+    let mp = child(&tree, "Bet 5.0");
+    let MultiwayNode::Action { actions, .. } = mp else {
+        panic!("MP faces the bet");
+    };
+    assert_eq!(actions[0], Action::Fold);
+    assert_eq!(actions[1], Action::Call(5.0));
+
+    // MP folds: the hand goes on with IP to act
+    let ip = child(mp, "Fold");
+    assert_eq!(seat(ip), 2);
+    match child(ip, "Call 5.0") {
+        MultiwayNode::Terminal { active, invested, pot } => {
+            assert_eq!(*active, [true, false, true]);
+            assert_eq!(*invested, [5.0, 0.0, 5.0]);
+            assert_eq!(*pot, 20.0);
+        }
+        _ => panic!("IP's call closes the action"),
+    }
+    match child(ip, "Fold") {
+        MultiwayNode::Terminal { active, .. } => assert_eq!(*active, [true, false, false]),
+        _ => panic!("two folds end the hand"),
+    }
+}
+
+#[test]
+fn test_multiway_raise_reopens_action() {
+    let (tree, counts) = build_multiway_tree(&small_config());
+    // OOP bets 5, MP raises to 5 + (20 + 5) = 20 (all-in); OOP and IP must act again
+    let ip = child(child(&tree, "Bet 5.0"), "Raise 20.0");
+    assert_eq!(seat(ip), 2);
+    let oop = child(ip, "Fold");
+    assert_eq!(seat(oop), 0);
+    let MultiwayNode::Action { actions, .. } = oop else {
+        panic!("OOP faces the raise");
+    };
+    assert_eq!(actions, &vec![Action::Fold, Action::Call(15.0)]);
+    assert!(counts.iter().all(|&n| n > 0));
+}
+
+#[test]
+fn test_multiway_solve_nuts_never_fold() {
+    // MP's top set beats everything; OOP bets a smaller set and bluffs
+    let mut config = MultiwaySolverConfig::new("AhKd7c4s9h", ["77,QJo", "AA,KJo,T8s", "KQo,65s"], 10.0, 20.0, 1000).unwrap();
+    config.seed = 7;
+    let solution = solve_river_multiway(&config);
+    assert_eq!(solution.combos[1].iter().filter(|c| c.starts_with('A')).count(), 3);
+
+    for strat in &solution.strategies {
+        for freqs in &strat.frequencies {
+            let total: f64 = freqs.iter().sum();
+            assert!((total - 1.0).abs() < 1e-3, "frequencies sum to {}", total);
+        }
+    }
+    // Facing each lead some OOP combo really makes (strategies at unreached nodes stay near uniform)
+    let root = solution.strategy_after(&[]).unwrap();
+    let mut checked = 0;
+    for strat in solution.strategies.iter().filter(|s| s.seat == "MP" && s.line.len() == 1) {
+        let lead = root.actions.iter().position(|a| *a == strat.line[0]).unwrap();
+        if strat.actions[0] != "Fold" || root.frequencies.iter().all(|f| f[lead] < 0.05) {
+            continue;
+        }
+        checked += 1;
+        let sets = solution.combos[1].iter().zip(&strat.frequencies).filter(|(c, _)| c.starts_with('A'));
+        for (_, freqs) in sets {
+            assert!(freqs[0] < 0.05, "top set folds {:.0}% after {:?}", freqs[0] * 100.0, strat.line);
+        }
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn test_multiway_config_rejects_bad_input() {
+    assert!(MultiwaySolverConfig::new("AhKd7c4s", ["AA", "KK", "QQ"], 10.0, 20.0, 100).is_err());
+    let err = MultiwaySolverConfig::new("AhKd7c4s9h", ["AA", "", "QQ"], 10.0, 20.0, 100).unwrap_err();
+    assert!(err.contains("MP"), "{}", err);
+}
@@ -314,7 +314,7 @@ fn test_session_quit_at_table_size() {
     let input = b"q\n";
     let mut reader: &[u8] = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
     assert!(out.contains("Welcome to GTO Play!"));
 }
@@ -325,7 +325,7 @@ fn test_session_preflop_raise_aks_btn() {
     let input = b"6max\n1/2\n200\nBTN\n2\nAhKs\nn\nn\n";
     let mut reader: &[u8] = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
     assert!(out.contains("RAISE"), "Expected RAISE for AKs on BTN, got:\n{}", out);
 }
@@ -336,7 +336,7 @@ fn test_session_preflop_fold_72o_utg() {
     let input = b"6max\n1/2\n200\nUTG\n2\n7h2c\nn\nn\n";
     let mut reader: &[u8] = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
     assert!(out.contains("FOLD"), "Expected FOLD for 72o on UTG");
     assert!(out.contains("fold preflop"));
@@ -348,7 +348,7 @@ fn test_session_invalid_cards_reprompt() {
     let input = b"6max\n1/2\n200\nBTN\n2\nZZ\nAhKs\nn\nn\n";
     let mut reader: &[u8] = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
     assert!(out.contains("Invalid"));
     assert!(out.contains("RAISE"));
@@ -360,9 +360,103 @@ fn test_session_through_flop() {
     let input = b"6max\n1/2\n200\nBTN\n2\nAhKs\nn\ny\nKd7c2h\nbet\n5\nn\nn\n";
     let mut reader: &[u8] = &input[..];
     let mut output = Vec::new();
-    run_interactive_session(&mut reader, &mut output);
+    run_interactive_session(&mut reader, &mut output, None);
     let out = String::from_utf8(output).unwrap();
     assert!(out.contains("Flop"));
     assert!(out.contains("Texture:"));
     assert!(out.contains("Equity vs villain:"));
 }
+
+#[test]
+fn test_session_logs_each_completed_hand() {
+    let dir = std::env::temp_dir().join(format!("gto-play-log-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+
+    // AhKs on the BTN opens, checks the flop, and the hand ends before the turn
+    let input = b"\nn\nAhKs\nBTN\nKd7c2h\n2\n\nn\n";
+    let mut reader: &[u8] = &input[..];
+    run_interactive_session(&mut reader, &mut Vec::new(), Some(&dir));
+
+    let logs: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().path()).collect();
+    assert_eq!(logs.len(), 1);
+    let hands = load_hands(&logs[0]).unwrap();
+    assert_eq!(hands.len(), 1);
+    let hand = &hands[0];
+    assert_eq!(logs[0], session_log_path(&dir, &utc_date(hand.timestamp)));
+    assert_eq!((hand.hero.as_str(), hand.hole_cards.as_str(), hand.board.as_str()), ("BTN", "AhKs", "Kd7c2h"));
+    let played: Vec<(&str, &str)> = hand.decisions.iter().map(|d| (d.street.as_str(), d.action.as_str())).collect();
+    assert_eq!(played, vec![("preflop", "Raise"), ("flop", "Check")]);
+    assert!(!hand.decisions[1].advice.actions.is_empty());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_utc_date_and_action_kinds() {
+    assert_eq!(utc_date(0), "1970-01-01");
+    assert_eq!(utc_date(1_714_521_600), "2024-05-01");
+    assert_eq!(utc_date(951_782_400), "2000-02-29");
+    assert!(same_action_kind("Bet/Raise", "Bet 33%"));
+    assert!(same_action_kind("All-in", "RAISE 2.5bb"));
+    assert!(same_action_kind("Check", "Check"));
+    assert!(!same_action_kind("Call", "Fold"));
+}
+
+#[test]
+fn review_scores_postflop_decisions_against_the_cache() {
+    use gto_cli::cache_index::CACHE_DIR_ENV;
+    use gto_cli::river_solver::{solve_river, RiverSolverConfig};
+    use gto_cli::strategy::postflop_seats;
+
+    let scratch = std::env::temp_dir().join(format!("gto-play-review-{}", std::process::id()));
+    std::fs::remove_dir_all(&scratch).ok();
+    std::env::set_var(CACHE_DIR_ENV, scratch.join("solver"));
+    std::env::set_var(SESSIONS_DIR_ENV, scratch.join("sessions"));
+
+    // The river the hand reaches is solved; its flop isn't
+    let (oop_pos, ip_pos) = postflop_seats("BB", "BTN").unwrap();
+    let config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK,K9s", "QQ,JJ,AK", 6.0, 97.0, 1000).unwrap();
+    let mut solution = solve_river(&config);
+    solution.oop_pos = oop_pos.as_str().to_string();
+    solution.ip_pos = ip_pos.as_str().to_string();
+    solution.save_cache();
+
+    let decision = |street: &str, board: &str, action: &str| DecisionRecord {
+        street: street.to_string(),
+        board: board.to_string(),
+        pot: if street == "preflop" { 1.5 } else { 6.0 },
+        stack: 97.0,
+        advice: Advice { actions: vec![action.to_string()], frequencies: vec![1.0], solver: false },
+        action: action.to_string(),
+        amount: None,
+    };
+    let hand = HandRecord {
+        timestamp: 1_714_521_600,
+        stack_bb: 100.0,
+        hero: "BTN".to_string(),
+        villain: "BB".to_string(),
+        hole_cards: "QhQd".to_string(),
+        board: "Ks9d4c7hQc".to_string(),
+        pot_type: "SRP".to_string(),
+        decisions: vec![
+            decision("preflop", "", "Raise"),
+            decision("flop", "Ks9d4c", "Check"),
+            decision("river", "Ks9d4c7hQc", "Bet/Raise"),
+        ],
+    };
+
+    let review = review_hands(std::slice::from_ref(&hand));
+    let [flop, turn, river] = review.by_street;
+    assert_eq!((flop.decisions, flop.covered), (1, 0), "the unsolved flop counts but isn't scored");
+    assert_eq!(turn, Agreement::default());
+    assert_eq!((river.decisions, river.covered), (1, 1));
+    assert_eq!(river.agreed + review.misses.len(), 1);
+    assert_eq!(review.postflop().decisions, 2, "preflop is left out");
+
+    append_hand(&session_log_path(&sessions_dir(), "2024-05-01"), &hand).unwrap();
+    assert_eq!(run_review(Some("2024-05-01")), Ok(()));
+    assert!(run_review(Some("2024-05-02")).unwrap_err().starts_with("No hands logged on 2024-05-02"));
+    assert!(run_review(Some("May 1")).unwrap_err().starts_with("Invalid date"));
+
+    std::fs::remove_dir_all(&scratch).ok();
+}
@@ -147,3 +147,153 @@ fn test_street_strategy_weak_fold() {
     let result = street_strategy("weak", &texture, 100.0, 500.0, "OOP", "flop");
     assert!(result.action.contains("FOLD") || result.action.contains("CHECK"));
 }
+
+#[test]
+fn test_wet_score_orders_textures() {
+    let dry = analyze_board(&parse_board("Ks7d2c").unwrap()).unwrap();
+    let medium = analyze_board(&parse_board("AsKs7d").unwrap()).unwrap();
+    let wet = analyze_board(&parse_board("Ts9s8s").unwrap()).unwrap();
+    assert!(dry.wet_score < medium.wet_score);
+    assert!(medium.wet_score < wet.wet_score);
+}
+
+#[test]
+fn test_geometric_fraction() {
+    // SPR 4 over one street = pot-sized overbet x4 => 400% pot
+    assert!((geometric_fraction(4.0, 1) - 4.0).abs() < 1e-9);
+    // SPR 4 over two streets: (9^0.5 - 1) / 2 = 1.0
+    assert!((geometric_fraction(4.0, 2) - 1.0).abs() < 1e-9);
+    assert_eq!(geometric_fraction(0.0, 2), 0.0);
+}
+
+#[test]
+fn test_recommend_size_wetter_is_larger() {
+    let boards = ["Ks7d2c", "AsKs7d", "Ts9s8d", "Ts9s8s"];
+    let mut last = 0.0;
+    for board in boards {
+        let texture = analyze_board(&parse_board(board).unwrap()).unwrap();
+        let rec = recommend_bet_size(&texture, 8.0, "flop", 0.8, 10.0, 80.0).unwrap();
+        assert!(rec.fraction >= last, "{}: {} < {}", board, rec.fraction, last);
+        last = rec.fraction;
+    }
+}
+
+#[test]
+fn test_recommend_size_nuts_higher_spr_is_larger() {
+    let texture = analyze_board(&parse_board("Ks7d2c").unwrap()).unwrap();
+    let mut last = 0.0;
+    for spr in [2.0, 4.0, 8.0, 12.0, 20.0] {
+        let rec = recommend_bet_size(&texture, spr, "flop", 1.0, 10.0, spr * 10.0).unwrap();
+        assert!(rec.fraction > last, "SPR {}: {} <= {}", spr, rec.fraction, last);
+        assert!(rec.plan.contains("geometric"));
+        last = rec.fraction;
+    }
+}
+
+#[test]
+fn test_recommend_size_stronger_value_is_larger() {
+    let texture = analyze_board(&parse_board("Ts9s8d").unwrap()).unwrap();
+    let mut last = 0.0;
+    for pct in [0.65, 0.75, 0.85, 0.95] {
+        let rec = recommend_bet_size(&texture, 10.0, "turn", pct, 10.0, 100.0).unwrap();
+        assert!(rec.fraction >= last, "pct {}: {} < {}", pct, rec.fraction, last);
+        last = rec.fraction;
+    }
+}
+
+#[test]
+fn test_recommend_size_marginal_checks() {
+    let texture = analyze_board(&parse_board("Ts9s8d").unwrap()).unwrap();
+    for pct in [0.4, 0.5, 0.6] {
+        assert!(recommend_bet_size(&texture, 8.0, "flop", pct, 10.0, 80.0).is_none());
+    }
+}
+
+#[test]
+fn test_recommend_size_amount_capped_at_stack() {
+    let texture = analyze_board(&parse_board("Ts9s8s").unwrap()).unwrap();
+    let rec = recommend_bet_size(&texture, 0.5, "river", 1.0, 10.0, 5.0).unwrap();
+    assert!(rec.amount <= 5.0 + 1e-9);
+    assert!((rec.amount - (rec.fraction * 10.0).min(5.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_street_strategy_sized_fallback_matches_static() {
+    let texture = analyze_board(&parse_board("Ks7d2c").unwrap()).unwrap();
+    let old = street_strategy("strong", &texture, 10.0, 80.0, "IP", "flop");
+    let new = street_strategy_sized("strong", &texture, 10.0, 80.0, "IP", "flop", None);
+    assert_eq!(old.sizing, new.sizing);
+    assert!(new.size.is_none());
+}
+
+#[test]
+fn test_street_strategy_sized_uses_percentile() {
+    let texture = analyze_board(&parse_board("Ks7d2c").unwrap()).unwrap();
+    let value = street_strategy_sized("strong", &texture, 10.0, 80.0, "IP", "flop", Some(0.9));
+    assert_eq!(value.action, "BET");
+    assert!(value.size.is_some());
+    assert!(value.sizing.contains("% pot"));
+
+    let marginal = street_strategy_sized("strong", &texture, 10.0, 80.0, "IP", "flop", Some(0.5));
+    assert_eq!(marginal.action, "CHECK");
+}
+
+// ---------------------------------------------------------------------------
+// Turn/river texture transitions
+// ---------------------------------------------------------------------------
+
+fn transition(board: &str) -> TextureTransition {
+    let cards = parse_board(board).unwrap();
+    texture_transition(&cards[..cards.len() - 1], cards[cards.len() - 1])
+}
+
+#[test]
+fn test_transition_brick_turn() {
+    let t = transition("Ks9d4c2h");
+    assert_eq!(t.kinds, [TransitionKind::Brick]);
+    assert_eq!(t.street, "turn");
+    assert_eq!(t.to_string(), "brick turn");
+}
+
+#[test]
+fn test_transition_pairs_and_completes_flush() {
+    // Two spades on the flop; the 9s both pairs the nine and brings the third spade
+    let t = transition("Ks9d4s9s");
+    assert!(t.has(TransitionKind::BoardPairing));
+    assert!(t.has(TransitionKind::FlushCompleting));
+    assert!(!t.is_brick());
+    assert_eq!(t.kinds[0], TransitionKind::FlushCompleting);
+}
+
+#[test]
+fn test_transition_flush_draw_and_four_flush() {
+    let t = transition("Ks9d4c7d");
+    assert_eq!(t.kinds, [TransitionKind::FlushDrawAdded]);
+    // A second suit pairing up on a two-tone flop is still a new draw
+    assert!(transition("Ks9s4c7c").has(TransitionKind::FlushDrawAdded));
+    // No draws left to add on the river
+    assert!(!transition("Ks9d4c2h7h").has(TransitionKind::FlushDrawAdded));
+    assert_eq!(transition("Ks9s4s2h7s").kinds, [TransitionKind::FourFlush]);
+}
+
+#[test]
+fn test_transition_straight_completing() {
+    // 9-8 makes a straight once the 7 joins J-T
+    assert!(transition("JsTd2c7h").has(TransitionKind::StraightCompleting));
+    // The wheel: ace plays low
+    assert!(transition("As4d9c3h").has(TransitionKind::StraightCompleting));
+    // Already possible on the flop
+    assert!(!transition("9s8d7c2h").has(TransitionKind::StraightCompleting));
+}
+
+#[test]
+fn test_transition_overcard_and_river_street() {
+    let t = transition("Ts7d2c4hAs");
+    assert_eq!(t.street, "river");
+    assert!(t.has(TransitionKind::Overcard));
+    let board = parse_board("Ks9d4c7h2s").unwrap();
+    let transitions = board_transitions(&board);
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0].card, board[3]);
+    assert_eq!(transitions[1].street, "river");
+}
@@ -9,10 +9,16 @@
 //! effects emerge from spots with different structure (e.g., SB vs BB
 //! where SB is OOP, or BTN vs SB with higher dead money).
 
+mod common;
+
+use common::ScratchCache;
 use gto_cli::game_tree::{
     bucket_to_hand, hand_to_bucket, precompute_equity_table, NUM_HANDS,
 };
-use gto_cli::preflop_solver::{solve_preflop_spot, Position};
+use gto_cli::preflop_solver::{
+    evs_over_fold, solve_preflop_spot, solve_preflop_spot_sized, solve_preflop_spot_to, ForcedBets, Position,
+    PreflopSizing, PREFLOP_CACHE_VERSION,
+};
 
 // ---------------------------------------------------------------------------
 // Shared equity table (expensive to compute, reused across tests)
@@ -73,6 +79,21 @@ fn convergence_sb_vs_bb() {
     );
 }
 
+#[test]
+fn target_stops_a_spot_once_it_is_reached() {
+    let (sizing, forced) = (PreflopSizing::default(), ForcedBets::default());
+    let to = |target| {
+        solve_preflop_spot_to(Position::CO, Position::BB, 100.0, 3000, target, 0.0, &sizing, &forced, equity_table())
+    };
+
+    let loose = to(Some(1.0));
+    assert_eq!(loose.iterations, 1000);
+    assert!(loose.exploitability <= 1.0);
+    // An unreachable target runs to the cap, like no target
+    assert_eq!(to(Some(1e-12)).iterations, 3000);
+    assert_eq!(to(None).iterations, 3000);
+}
+
 // ---------------------------------------------------------------------------
 // Strategy validity
 // ---------------------------------------------------------------------------
@@ -144,6 +165,24 @@ fn aa_3bets_more_than_calls_vs_open() {
     );
 }
 
+#[test]
+fn action_evs_are_stored_and_track_the_strategy() {
+    let result = solve_with(Position::BTN, Position::BB, 100.0, 5000, 0.0);
+    let evs = result.evs.as_ref().expect("solves store action EVs");
+    let aa = hand_to_bucket("AA").unwrap();
+    let trash = hand_to_bucket("72o").unwrap();
+
+    // The button posts nothing, so its fold is worth 0; the big blind's
+    // fold loses its blind
+    assert_eq!(evs.open[aa][1], 0.0);
+    assert!((evs.vs_open[trash][2] + 1.0).abs() < 1e-9);
+    assert_eq!(evs_over_fold(evs.vs_open[trash]), [evs.vs_open[trash][0] + 1.0, evs.vs_open[trash][1] + 1.0, 0.0]);
+    // AA opens for profit; 72o, which folds, would lose by opening
+    assert!(evs.open[aa][0] > 1.0, "AA open EV {}", evs.open[aa][0]);
+    assert!(evs.open[trash][0] < 0.0, "72o open EV {}", evs.open[trash][0]);
+    assert!(evs.vs_open[aa][0] > evs.vs_open[aa][2]);
+}
+
 #[test]
 fn premium_hands_always_open() {
     let premiums = ["AA", "KK", "QQ", "AKs"];
@@ -276,6 +315,37 @@ fn bb_defends_wider_vs_more_dead_money() {
     );
 }
 
+#[test]
+fn bb_defends_narrower_vs_a_bigger_open() {
+    let vs_open = |open: f64| {
+        let sizing = PreflopSizing::new(open, 3.0, 2.5, 100.0).unwrap();
+        let spot = solve_preflop_spot_sized(Position::BTN, Position::BB, 100.0, 30000, 0.0, &sizing, equity_table());
+        spot.three_bet_pct() + spot.flat_call_pct()
+    };
+    let (min_raise, big) = (vs_open(2.0), vs_open(3.5));
+    assert!(
+        big < min_raise,
+        "BB should defend less vs a 3.5bb open ({:.1}%) than vs 2bb ({:.1}%)",
+        big, min_raise,
+    );
+}
+
+#[test]
+fn a_big_ante_widens_the_open() {
+    let open_pct = |ante: f64| {
+        let forced = ForcedBets::new(ante, 0.0, 100.0).unwrap();
+        let sizing = PreflopSizing::default();
+        solve_preflop_spot_to(Position::CO, Position::BB, 100.0, 30000, None, 0.0, &sizing, &forced, equity_table())
+            .open_pct()
+    };
+    let (no_ante, big_ante) = (open_pct(0.0), open_pct(0.5));
+    assert!(
+        big_ante > no_ante + 10.0,
+        "CO should open much wider with a 0.5bb ante ({:.1}%) than without ({:.1}%)",
+        big_ante, no_ante,
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Range size sanity checks
 // ---------------------------------------------------------------------------
@@ -301,3 +371,65 @@ fn sb_open_range_reasonable() {
         "SB open range {:.1}% should be 10-60%", pct,
     );
 }
+
+// ---------------------------------------------------------------------------
+// Portable JSON export / import
+// ---------------------------------------------------------------------------
+
+fn quick_solution() -> gto_cli::preflop_solver::PreflopSolution {
+    let spots = gto_cli::preflop_solver::all_6max_spots()
+        .into_iter()
+        .map(|(opener, responder)| solve_with(opener, responder, 100.0, 1000, 0.0))
+        .collect();
+    gto_cli::preflop_solver::PreflopSolution {
+        version: PREFLOP_CACHE_VERSION,
+        table_size: "6max".to_string(),
+        stack_bb: 100.0,
+        rake_pct: 0.0,
+        sizing: PreflopSizing::default(),
+        forced_bets: ForcedBets::default(),
+        iterations: 1000,
+        spots,
+    }
+}
+
+#[test]
+fn portable_json_round_trips_losslessly_through_the_cache() {
+    use gto_cli::preflop_json::{export, import};
+    use gto_cli::preflop_solver::PreflopSolution;
+
+    let cache = ScratchCache::new("preflop-json");
+
+    let exported = serde_json::to_string_pretty(&export(&quick_solution())).unwrap();
+    let imported = import(&exported, 100.0, 0.0).unwrap();
+    imported.save().unwrap();
+    assert!(imported.cache_path().starts_with(cache.path()));
+    let loaded = PreflopSolution::load("6max", 100.0, 0.0, &PreflopSizing::default()).unwrap();
+    let reexported = serde_json::to_string_pretty(&export(&loaded)).unwrap();
+
+    assert_eq!(exported, reexported);
+    assert_eq!(loaded.spots.len(), 15);
+    assert!(loaded.find_spot(Position::BTN, Position::BB).is_some());
+}
+
+#[test]
+fn import_rejects_malformed_ranges() {
+    use gto_cli::preflop_json::{export, import};
+
+    let good = export(&quick_solution());
+    let check = |edit: &dyn Fn(&mut gto_cli::preflop_json::PortablePreflop), stack: f64, expected: &str| {
+        let mut file = good.clone();
+        edit(&mut file);
+        let err = import(&serde_json::to_string(&file).unwrap(), stack, 0.0).unwrap_err();
+        assert!(err.contains(expected), "expected '{}' in '{}'", expected, err);
+    };
+
+    assert!(import(&serde_json::to_string(&good).unwrap(), 100.0, 0.0).is_ok());
+    check(&|f| f.spots[0].open.pop().map(|_| ()).unwrap(), 100.0, "has 168 entries");
+    check(&|f| f.spots[3].three_bet[7] = 1.5, 100.0, "outside [0, 1]");
+    check(&|f| { f.spots[2].three_bet[0] = 0.8; f.spots[2].call_vs_open[0] = 0.8; }, 100.0, "more than 1");
+    check(&|f| { f.spots.pop(); }, 100.0, "Missing spot SB vs BB");
+    check(&|f| { let dup = f.spots[0].clone(); f.spots.push(dup); }, 100.0, "more than once");
+    check(&|_| {}, 50.0, "File is for 100bb");
+    check(&|f| f.format = "other".to_string(), 100.0, "Expected format");
+}
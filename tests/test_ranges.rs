@@ -1,5 +1,9 @@
 use gto_cli::cards::*;
+use gto_cli::error::GtoError;
+use gto_cli::flop_solver::FlopSolverConfig;
 use gto_cli::ranges::*;
+use gto_cli::river_solver::RiverSolverConfig;
+use gto_cli::turn_solver::TurnSolverConfig;
 
 #[test]
 fn test_combo_count_pair() {
@@ -18,7 +22,7 @@ fn test_combo_count_offsuit() {
 
 #[test]
 fn test_parse_range_simple() {
-    let result = parse_range("AA,KK,QQ");
+    let result = parse_range("AA,KK,QQ").unwrap();
     assert!(result.contains(&"AA".to_string()));
     assert!(result.contains(&"KK".to_string()));
     assert!(result.contains(&"QQ".to_string()));
@@ -26,7 +30,7 @@ fn test_parse_range_simple() {
 
 #[test]
 fn test_parse_range_plus_pairs() {
-    let result = parse_range("TT+");
+    let result = parse_range("TT+").unwrap();
     assert!(result.contains(&"TT".to_string()));
     assert!(result.contains(&"JJ".to_string()));
     assert!(result.contains(&"QQ".to_string()));
@@ -37,7 +41,7 @@ fn test_parse_range_plus_pairs() {
 
 #[test]
 fn test_parse_range_plus_suited() {
-    let result = parse_range("ATs+");
+    let result = parse_range("ATs+").unwrap();
     assert!(result.contains(&"ATs".to_string()));
     assert!(result.contains(&"AJs".to_string()));
     assert!(result.contains(&"AQs".to_string()));
@@ -47,7 +51,7 @@ fn test_parse_range_plus_suited() {
 
 #[test]
 fn test_parse_range_dash_pairs() {
-    let result = parse_range("77-TT");
+    let result = parse_range("77-TT").unwrap();
     assert!(result.contains(&"77".to_string()));
     assert!(result.contains(&"88".to_string()));
     assert!(result.contains(&"99".to_string()));
@@ -58,7 +62,7 @@ fn test_parse_range_dash_pairs() {
 
 #[test]
 fn test_parse_range_dash_suited() {
-    let result = parse_range("KTs-KQs");
+    let result = parse_range("KTs-KQs").unwrap();
     assert!(result.contains(&"KTs".to_string()));
     assert!(result.contains(&"KJs".to_string()));
     assert!(result.contains(&"KQs".to_string()));
@@ -67,11 +71,80 @@ fn test_parse_range_dash_suited() {
 
 #[test]
 fn test_parse_range_mixed() {
-    let result = parse_range("AA, KK, AKs, AQs+");
+    let result = parse_range("AA, KK, AKs, AQs+").unwrap();
     assert!(result.contains(&"AA".to_string()));
     assert!(result.contains(&"AKs".to_string()));
 }
 
+#[test]
+fn test_parse_range_accepts_every_term_form() {
+    let cases: &[(&str, &[&str])] = &[
+        ("AA,KK", &["AA", "KK"]),
+        (" qq+ ", &["AA", "KK", "QQ"]),
+        ("Tt+", &["AA", "KK", "QQ", "JJ", "TT"]),
+        ("kqs", &["KQs"]),
+        ("QKo", &["KQo"]),
+        ("AK", &["AKs", "AKo"]),
+        ("AQ+", &["AKs", "AQs", "AKo", "AQo"]),
+        ("ATo+", &["AKo", "AQo", "AJo", "ATo"]),
+        ("KQo-KTo", &["KQo", "KJo", "KTo"]),
+        ("KTs-KQs", &["KQs", "KJs", "KTs"]),
+        ("76s-54s", &["76s", "65s", "54s"]),
+        ("T9o-87o", &["T9o", "98o", "87o"]),
+        ("44-22", &["44", "33", "22"]),
+        ("AhKs", &["AhKs"]),
+        ("ahks", &["AhKs"]),
+        ("AA:0.5,,", &["AA"]),
+        ("top 1%", &["AA", "KK"]),
+    ];
+    for (range, expected) in cases {
+        let mut got = parse_range(range).unwrap_or_else(|e| panic!("{}: {}", range, e));
+        let mut expected: Vec<String> = expected.iter().map(|h| h.to_string()).collect();
+        got.sort();
+        expected.sort();
+        assert_eq!(got, expected, "{}", range);
+    }
+    assert_eq!(parse_range("top 15%").unwrap(), range_from_top_pct(15.0).unwrap());
+    assert_eq!(parse_range("15%").unwrap(), range_from_top_pct(15.0).unwrap());
+    assert!(parse_range("").unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_range_rejects_malformed_terms() {
+    let cases: &[(&str, &[&str])] = &[
+        ("ATo+,KQo-KTo,22+,KQx", &["'KQx'"]),
+        ("A", &["'A'"]),
+        ("AKQ", &["'AKQ'"]),
+        ("1A", &["'1A'"]),
+        ("AAs", &["'AAs'"]),
+        ("KQo-JTs", &["'KQo-JTs'"]),
+        ("KQo-T8o", &["'KQo-T8o'"]),
+        ("22-AKs", &["'22-AKs'"]),
+        ("AhAh", &["'AhAh'"]),
+        ("AA:2,KK:x", &["'AA:2'", "'KK:x'"]),
+        ("top x%", &["'topx%'"]),
+        ("top 0%", &["'top0%'"]),
+        ("AA,bogus,KK,7", &["'bogus'", "'7'"]),
+    ];
+    for (range, rejected) in cases {
+        let Err(GtoError::InvalidRange(terms)) = parse_range(range) else {
+            panic!("{} should be rejected", range);
+        };
+        assert_eq!(terms.len(), rejected.len(), "{}: {:?}", range, terms);
+        for (term, want) in terms.iter().zip(rejected.iter()) {
+            assert!(term.starts_with(want), "{}: {} should name {}", range, term, want);
+        }
+    }
+    // The weighted parse and the solver configs report the same terms
+    assert!(parse_weighted_range("AA,Tx+").unwrap_err().to_string().contains("'Tx+'"));
+    let flop = FlopSolverConfig::new("Ks9d4c", "AA,Tx+", "QQ", 10.0, 50.0, 100).err().unwrap();
+    let turn = TurnSolverConfig::new("Ks9d4c2h", "AA", "QQ,Jj-Tx", 10.0, 50.0, 100).err().unwrap();
+    let river = RiverSolverConfig::new("Ks9d4c2h3s", "AA,99:1.5", "QQ", 10.0, 50.0, 100).err().unwrap();
+    assert!(flop.to_string().contains("'Tx+'"), "{}", flop);
+    assert!(turn.to_string().contains("'Jj-Tx'"), "{}", turn);
+    assert!(river.to_string().contains("'99:1.5'"), "{}", river);
+}
+
 #[test]
 fn test_range_from_top_pct_1() {
     let result = range_from_top_pct(1.0).unwrap();
@@ -145,3 +218,68 @@ fn test_blocked_combos_count() {
     let count = blocked_combos("AA", &hero).unwrap();
     assert_eq!(count, 5); // holding 2 aces blocks 5 of 6 combos
 }
+
+#[test]
+fn test_apply_range_edit_adds_and_removes() {
+    let base = parse_range("AA,KK,AKs,K7s").unwrap();
+    let edited = apply_range_edit(&base, "+A5o,-K7s").unwrap();
+    assert!(edited.contains(&"A5o".to_string()));
+    assert!(!edited.contains(&"K7s".to_string()));
+    assert_eq!(edited.len(), base.len());
+}
+
+#[test]
+fn test_apply_range_edit_expands_range_syntax() {
+    let base = parse_range("AA").unwrap();
+    let edited = apply_range_edit(&base, "+22-44, -AA").unwrap();
+    assert_eq!(edited.len(), 3);
+    assert!(!edited.contains(&"AA".to_string()));
+}
+
+#[test]
+fn test_apply_range_edit_rejects_unsigned_terms() {
+    let base = parse_range("AA").unwrap();
+    assert!(apply_range_edit(&base, "KK").is_err());
+    assert!(apply_range_edit(&base, "+").is_err());
+}
+
+#[test]
+fn test_parse_weighted_range() {
+    let range = parse_weighted_range("AA:1.0, KK:0.5, QQ+:0.25, AKs").unwrap();
+    let weight = |hand: &str| range.iter().find(|(h, _)| h == hand).map(|(_, w)| *w);
+    // A later term overrides an earlier one
+    assert_eq!(weight("AA"), Some(0.25));
+    assert_eq!(weight("KK"), Some(0.25));
+    assert_eq!(weight("QQ"), Some(0.25));
+    assert_eq!(weight("AKs"), Some(1.0));
+    assert_eq!(range.len(), 4);
+    assert!(parse_weighted_range("KK:1.5").is_err());
+    assert!(parse_weighted_range("KK:half").is_err());
+}
+
+#[test]
+fn test_weighted_range_helpers() {
+    // parse_range keeps the hands of weighted terms
+    assert_eq!(parse_range("AA,AKo:0.5").unwrap(), parse_range("AA,AKo").unwrap());
+
+    let range = parse_weighted_range("TT+,AKo:0.5,QQ:0.25").unwrap();
+    assert!((weighted_total_combos(&range) - (4.0 * 6.0 + 1.5 + 6.0)).abs() < 1e-12);
+    assert_eq!(weighted_range_string(&range), "AA,KK,QQ:0.25,JJ,TT,AKo:0.5");
+
+    let (hands, weights) = parse_range_weights("AA,KK:0,AKo:0.5").unwrap();
+    assert_eq!(hands, vec!["AA".to_string(), "AKo".to_string()]);
+    assert_eq!(format_weighted_range(&hands, &weights), "AA,AKo (0.5)");
+    // Missing weights count in full
+    assert_eq!(format_weighted_range(&hands, &[]), "AA,AKo");
+}
+
+#[test]
+fn test_apply_weighted_range_edit_keeps_frequencies() {
+    let base = vec!["AA".to_string(), "AKo".to_string()];
+    let edited = apply_weighted_range_edit(&base, &[1.0, 0.5], "+KK:0.25,+QQ,-AA").unwrap();
+    assert_eq!(
+        edited,
+        vec![("KK".to_string(), 0.25), ("QQ".to_string(), 1.0), ("AKo".to_string(), 0.5)]
+    );
+    assert!(apply_weighted_range_edit(&base, &[1.0, 0.5], "+KK:2").is_err());
+}
@@ -1,11 +1,15 @@
 //! Tests for the river solver.
 
 use gto_cli::card_encoding::card_to_index;
+use gto_cli::cfr::CfrVariant;
+use gto_cli::error::SolverError;
 use gto_cli::cards::parse_card;
 use gto_cli::lookup_eval::evaluate_fast;
 use gto_cli::postflop_tree::{build_tree, Player, TerminalType, TreeConfig, TreeNode};
+use gto_cli::postprocess::{PostProcess, Simplify};
 use gto_cli::river_solver::{
-    expand_range_to_combos, solve_river, Combo, RiverSolverConfig, ShowdownTable,
+    canonical_combo_string, expand_range_to_combos, expand_weighted_range_to_combos, parse_combo_weights_csv,
+    solve_river, try_solve_river, Combo, RiverSolution, RiverSolverConfig, ShowdownTable, COMBO_ORDER_VERSION,
 };
 
 // ---------------------------------------------------------------------------
@@ -54,7 +58,7 @@ fn tree_node_ids_sequential() {
     collect_ids(&root, &mut ids);
     ids.sort();
     ids.dedup();
-    assert_eq!(ids.len(), num_nodes as usize);
+    assert_eq!(ids.len(), num_nodes);
     for (i, &id) in ids.iter().enumerate() {
         assert_eq!(id, i as u16, "Node IDs should be sequential");
     }
@@ -204,10 +208,64 @@ fn showdown_non_conflicting_valid() {
     );
 }
 
+#[test]
+fn config_rejects_bad_boards_with_typed_errors() {
+    let short = RiverSolverConfig::new("Ks9d4c7h", "AA", "QQ", 10.0, 20.0, 100);
+    assert_eq!(short.err(), Some(SolverError::WrongBoardSize { expected: 5, got: 4 }));
+
+    let repeated = RiverSolverConfig::new("Ks9d4cKs2h", "AA", "QQ", 10.0, 20.0, 100);
+    assert!(matches!(repeated, Err(SolverError::ConflictingCards { .. })));
+
+    let garbled = RiverSolverConfig::new("Ks9d4c7hXx", "AA", "QQ", 10.0, 20.0, 100);
+    assert!(matches!(garbled, Err(SolverError::InvalidBoard { .. })));
+}
+
+#[test]
+fn every_street_config_rejects_malformed_boards_without_panicking() {
+    use gto_cli::flop_solver::FlopSolverConfig;
+    use gto_cli::turn_solver::TurnSolverConfig;
+
+    let boards = [
+        "", "K", "Ks9", "Ks9d", "KsKs4c", "Ks9d4cKs", "Ks9d4c7hKs", "Ks9d4c7h2s3d", "Ks 9d 4c 7h 2s 3d 5h", "XxYyZz",
+        "1s2s3s", "Ks9d4c\u{2660}",
+    ];
+    for board in boards {
+        let results = [
+            std::panic::catch_unwind(|| FlopSolverConfig::new(board, "AA", "QQ", 10.0, 20.0, 10).is_err()),
+            std::panic::catch_unwind(|| TurnSolverConfig::new(board, "AA", "QQ", 10.0, 20.0, 10).is_err()),
+            std::panic::catch_unwind(|| RiverSolverConfig::new(board, "AA", "QQ", 10.0, 20.0, 10).is_err()),
+        ];
+        for (street, result) in ["flop", "turn", "river"].iter().zip(results) {
+            assert_eq!(result.ok(), Some(true), "{} config with board '{}' should error, not panic", street, board);
+        }
+    }
+}
+
+#[test]
+fn config_rejects_a_range_the_board_blocks_entirely() {
+    // Both of IP's combos hold the K of spades
+    let blocked = RiverSolverConfig::new("Ks9d4c7h2s", "AA", "KsQs,KsJs", 10.0, 20.0, 100);
+    assert_eq!(blocked.err(), Some(SolverError::EmptyRange { side: "IP" }));
+
+    // A partly blocked range keeps its live combos
+    assert!(RiverSolverConfig::new("Ks9d4c7h2s", "AA", "KsQs,KhJh", 10.0, 20.0, 100).is_ok());
+}
+
 // ---------------------------------------------------------------------------
 // Solver convergence tests
 // ---------------------------------------------------------------------------
 
+#[test]
+fn dead_cards_remove_combos_like_board_blockers() {
+    // KK loses the Ks to the board; the dead Ah takes three AA combos with it
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK", "QQ", 10.0, 20.0, 200).unwrap();
+    config.dead_cards = vec![card("Ah"), card("Qd")];
+    let result = solve_river(&config);
+    assert_eq!(result.oop_combos.len(), 3 + 3);
+    assert_eq!(result.ip_combos.len(), 3);
+    assert!(result.oop_combos.iter().chain(&result.ip_combos).all(|c| !c.contains("Ah") && !c.contains("Qd")));
+}
+
 #[test]
 fn solver_nuts_vs_air() {
     // AA vs 72o on a dry board — AA always has the nuts
@@ -243,6 +301,35 @@ fn solver_nuts_vs_air() {
     }
 }
 
+#[test]
+fn action_evs_price_each_action_against_the_solved_range() {
+    // AA vs 72o: every AA line wins at least the pot, and 72o facing a bet
+    // loses exactly what it calls
+    let config = RiverSolverConfig::new("2s3h4d5c8s", "AA", "72o", 10.0, 20.0, 2000).unwrap();
+    let result = solve_river(&config);
+
+    let root = &result.strategies[0];
+    let rows = result.action_evs(root.node_id, Player::OOP, &[0, 1]).unwrap();
+    assert_eq!(rows.len(), 2);
+    for evs in &rows {
+        assert_eq!(evs.len(), root.actions.len());
+        assert!(evs.iter().all(|&ev| ev >= 1.0 - 1e-9), "{:?}", evs);
+    }
+
+    // IP facing OOP's first bet size
+    let (tree, _) = build_tree(&result.tree_config());
+    let TreeNode::Action { children, .. } = &tree else { panic!("root is an action node") };
+    let TreeNode::Action { node_id, .. } = &children[1] else { panic!("a bet leads to IP's decision") };
+    let facing_bet = result.strategies.iter().find(|s| s.node_id == *node_id).unwrap();
+    let evs = &result.action_evs(facing_bet.node_id, Player::IP, &[0]).unwrap()[0];
+    let call: f64 = facing_bet.actions[1].trim_start_matches("Call ").parse().unwrap();
+    assert!(evs[0].abs() < 1e-9);
+    assert!((evs[1] + call / 10.0).abs() < 1e-9, "{:?}", evs);
+
+    // Not IP's node
+    assert!(result.action_evs(root.node_id, Player::IP, &[0]).is_none());
+}
+
 #[test]
 fn solver_strategies_valid_probabilities() {
     let config = RiverSolverConfig::new(
@@ -310,6 +397,99 @@ fn solver_exploitability_decreases() {
     );
 }
 
+#[test]
+fn postprocessed_solution_reports_both_exploitabilities() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK,K9s,T8s", "QQ,JJ,AK", 10.0, 20.0, 1000).unwrap();
+    let raw = solve_river(&config);
+    assert!(raw.postprocess.is_none());
+
+    config.postprocess = Some(PostProcess::new(0.10, 0.5, 0.03).unwrap());
+    let processed = solve_river(&config);
+    let info = processed.postprocess.as_ref().expect("post-processing recorded");
+    assert!(
+        (info.raw_exploitability - raw.exploitability).abs() < 1e-9,
+        "raw exploitability is kept: {} vs {}",
+        info.raw_exploitability,
+        raw.exploitability
+    );
+    for strat in &processed.strategies {
+        for freq in &strat.frequencies {
+            assert!((freq.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+            assert!(freq.iter().all(|&f| f == 0.0 || f >= 0.10 - 1e-9), "{:?}", freq);
+        }
+    }
+}
+
+#[test]
+fn simplified_copy_rounds_strategies_and_estimates_ev_loss() {
+    let config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK,K9s,T8s", "QQ,JJ,AK", 10.0, 20.0, 1000).unwrap();
+    let solution = solve_river(&config);
+
+    let simplified = solution.simplified(Simplify::new(0.10, false).unwrap());
+    assert!(solution.simplified.is_none(), "the solution itself is left alone");
+    for strat in &simplified.strategies {
+        for freq in &strat.frequencies {
+            assert!((freq.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+            assert!(freq.iter().all(|&f| f == 0.0 || f == 1.0 || (0.10..=0.90).contains(&f)), "{:?}", freq);
+        }
+    }
+    let info = simplified.simplified.expect("simplification recorded");
+    assert!(info.ev_loss_pct_pot.is_some_and(|loss| loss >= 0.0), "{:?}", info);
+
+    let purified = solution.simplified(Simplify::new(0.0, true).unwrap());
+    assert!(purified.strategies.iter().flat_map(|s| &s.frequencies).all(|f| f.iter().all(|&p| p == 0.0 || p == 1.0)));
+
+    // Rounding nothing costs nothing
+    let unchanged = solution.simplified(Simplify::new(0.0, false).unwrap());
+    assert!(unchanged.simplified.unwrap().ev_loss_pct_pot.unwrap() < 1e-9);
+}
+
+#[test]
+fn locked_node_plays_the_locked_strategy() {
+    // AA always bets for value against 72o; lock it into checking instead
+    let mut config = RiverSolverConfig::new("2s3h4d5c8s", "AA", "72o", 10.0, 20.0, 1000).unwrap();
+    config.add_lock("node=0,player=OOP,strategy=check:1.0").unwrap();
+    let result = solve_river(&config);
+
+    let root = &result.strategies[0];
+    assert!(root.locked);
+    assert!(root.frequencies.iter().all(|f| f[0] == 1.0), "{:?}", root.frequencies);
+    assert!(result.strategies[1..].iter().all(|s| !s.locked));
+    assert!(result.exploitability.is_finite() && result.exploitability >= -1e-9);
+
+    // One combo locked; the others still find the bet
+    let mut config = RiverSolverConfig::new("2s3h4d5c8s", "AA", "72o", 10.0, 20.0, 2000).unwrap();
+    config.add_lock("node=0,player=OOP,combo=AhAs,strategy=check:1.0").unwrap();
+    let result = solve_river(&config);
+    let root = &result.strategies[0];
+    assert!(root.locked);
+    let locked = result.oop_combo_index("AsAh").unwrap();
+    for (i, freq) in root.frequencies.iter().enumerate() {
+        if i == locked {
+            assert_eq!(freq[0], 1.0);
+        } else {
+            assert!(freq[0] < 0.5, "combo {} checks {:.2}", result.oop_combos[i], freq[0]);
+        }
+    }
+}
+
+#[test]
+fn invalid_locks_are_rejected() {
+    let mut config = RiverSolverConfig::new("2s3h4d5c8s", "AA", "72o", 10.0, 20.0, 1000).unwrap();
+    // Root is OOP's decision
+    assert!(config.add_lock("node=0,player=IP,strategy=check:1.0").unwrap_err().contains("OOP"));
+    assert!(config.add_lock("node=999,player=OOP,strategy=check:1.0").unwrap_err().contains("No decision node"));
+    // Three bet sizes plus all-in
+    assert!(config.add_lock("node=0,player=OOP,strategy=bet:1.0").unwrap_err().contains("ambiguous"));
+    assert!(config.add_lock("node=0,player=OOP,strategy=check:0.5").unwrap_err().contains("add up"));
+    assert!(config.add_lock("node=0,player=OOP").is_err());
+    assert!(config.locked_nodes.is_empty());
+
+    config.add_lock("node=0,player=oop,strategy=check:0.25|bet 3.3:0.75").unwrap();
+    assert_eq!(config.locked_nodes[0].strategy[0], 0.25);
+    assert_eq!(config.locked_nodes[0].strategy[1], 0.75);
+}
+
 #[test]
 fn solver_check_only_ev_is_showdown_equity() {
     // With no bet sizes, the only option is check-check -> showdown
@@ -368,6 +548,69 @@ fn solver_symmetric_ranges_balanced() {
     );
 }
 
+#[test]
+fn solver_low_spr_plays_jam_or_check() {
+    // SPR 0.3 / 0.5 / 1.0: every label is distinct, and with the nuts OOP
+    // shoves while air folds to the shove
+    for stack in [3.0, 5.0, 10.0] {
+        let config = RiverSolverConfig::new("2s3h4d5c8s", "AA", "72o", 10.0, stack, 2000).unwrap();
+        let result = solve_river(&config);
+
+        for strat in &result.strategies {
+            for (i, label) in strat.actions.iter().enumerate() {
+                assert!(!strat.actions[..i].contains(label), "SPR {}: duplicate {:?}", stack / 10.0, strat.actions);
+            }
+        }
+
+        let root = &result.strategies[0];
+        let jam = format!("Bet {:.1}", stack);
+        assert!(root.actions.contains(&jam), "SPR {}: no all-in in {:?}", stack / 10.0, root.actions);
+        if stack < 3.3 {
+            // Even the smallest (33%) size is past the stack
+            assert_eq!(root.actions, vec!["Check".to_string(), jam.clone()]);
+        }
+        for freq in &root.frequencies {
+            assert!(freq[0] < 0.5, "SPR {}: AA should not check, got {:.2}", stack / 10.0, freq[0]);
+        }
+
+        let facing_jam = result
+            .strategies
+            .iter()
+            .find(|s| s.player == "IP" && s.actions.first().map(String::as_str) == Some("Fold"))
+            .expect("IP faces a bet");
+        for freq in &facing_jam.frequencies {
+            assert!(freq[0] > 0.9, "SPR {}: 72o should fold, got {:.2}", stack / 10.0, freq[0]);
+        }
+    }
+}
+
+#[test]
+fn solver_showdown_outcomes_show_chops_with_shared_nut_straight() {
+    // Both ranges are mostly tens for the broadway straight; OOP's sixes and
+    // IP's air give the tens something to beat
+    let config = RiverSolverConfig::new("AsKdQhJc3h", "T9s,66", "T8s,T7s,72s", 10.0, 20.0, 1000).unwrap();
+    let result = solve_river(&config);
+    assert_eq!(result.oop_showdown_outcomes.len(), result.oop_combos.len());
+    assert_eq!(result.ip_showdown_outcomes.len(), result.ip_combos.len());
+
+    for (i, combo) in result.oop_combos.iter().enumerate() {
+        let (win, tie, lose) = result.showdown_outcome("OOP", i).expect("every combo reaches showdown");
+        assert!((win + tie + lose - 1.0).abs() < 1e-9);
+        if combo.starts_with('T') {
+            assert!(tie > win && tie > lose, "{}: chops should dominate, got {:?}", combo, (win, tie, lose));
+            assert_eq!(lose, 0.0, "{} can't lose at showdown", combo);
+        }
+    }
+    for (i, combo) in result.ip_combos.iter().enumerate() {
+        if let Some((win, tie, lose)) = result.showdown_outcome("IP", i) {
+            if combo.starts_with('T') {
+                assert!(tie > 0.0 && win > 0.0, "{}: got {:?}", combo, (win, tie, lose));
+                assert_eq!(lose, 0.0, "{} can't lose at showdown", combo);
+            }
+        }
+    }
+}
+
 #[test]
 fn combo_expansion_correct_count() {
     let b = board("2s3h4d5c8s");
@@ -390,6 +633,43 @@ fn combo_expansion_correct_count() {
     assert_eq!(combos.len(), 3); // 3 combos without As
 }
 
+#[test]
+fn weighted_combo_expansion_carries_hand_frequencies() {
+    let b = board("2s3h4d5c8s");
+    let range = vec!["AA".to_string(), "AKs".to_string()];
+    let (combos, weights) = expand_weighted_range_to_combos(&range, &[0.5, 1.0], &b);
+    assert_eq!(combos, expand_range_to_combos(&range, &b));
+    for (combo, w) in combos.iter().zip(&weights) {
+        let pair = combo.to_string().chars().nth(2) == Some('A');
+        assert_eq!(*w, if pair { 0.5 } else { 1.0 }, "{}", combo);
+    }
+    assert_eq!(weights.iter().sum::<f64>(), 6.0 * 0.5 + 4.0);
+}
+
+#[test]
+fn weighted_range_starts_combos_at_their_frequency() {
+    // AA is value and 76s air against IP's KK bluff catcher; at 10% the air
+    // is a much smaller share of the showdowns KK reaches
+    let full = solve_river(&RiverSolverConfig::new("QsJd8c3h2s", "AA,76s", "KK", 10.0, 20.0, 1000).unwrap());
+    let config = RiverSolverConfig::new("QsJd8c3h2s", "AA,76s:0.1", "KK", 10.0, 20.0, 1000).unwrap();
+    assert_eq!(config.oop_weights, vec![1.0, 0.1]);
+    let weighted = solve_river(&config);
+    assert_eq!(weighted.oop_weights, vec![1.0, 0.1]);
+    assert_eq!(weighted.ip_weights, vec![1.0]);
+    assert_eq!(weighted.oop_combos, full.oop_combos);
+
+    let kk_wins = |result: &RiverSolution| {
+        (0..result.ip_combos.len()).filter_map(|i| result.showdown_outcome("IP", i)).map(|(win, _, _)| win).sum::<f64>()
+    };
+    assert!(kk_wins(&weighted) < kk_wins(&full) * 0.5, "{} vs {}", kk_wins(&weighted), kk_wins(&full));
+    assert!(weighted.exploitability < 0.05 * 10.0);
+
+    // Frequency 0 drops the hand
+    let dropped = RiverSolverConfig::new("QsJd8c3h2s", "AA,76s:0", "KK", 10.0, 20.0, 100).unwrap();
+    assert_eq!(dropped.oop_range, vec!["AA".to_string()]);
+    assert!(RiverSolverConfig::new("QsJd8c3h2s", "AA:2", "KK", 10.0, 20.0, 100).is_err());
+}
+
 #[test]
 fn river_solver_config_validates_board() {
     // Too few cards
@@ -411,3 +691,442 @@ fn empty_range_after_blockers() {
     // As and Ah on board, so only AdAc survives
     assert_eq!(combos.len(), 1, "Only AdAc should survive");
 }
+
+// ---------------------------------------------------------------------------
+// Canonical combo ordering
+// ---------------------------------------------------------------------------
+
+#[test]
+fn combo_order_independent_of_range_order() {
+    let b = board("2s3h4d5c8s");
+    let forward: Vec<String> = ["AA", "KK", "AKs", "QJo"].iter().map(|s| s.to_string()).collect();
+    let mut reversed = forward.clone();
+    reversed.reverse();
+
+    let a = expand_range_to_combos(&forward, &b);
+    let r = expand_range_to_combos(&reversed, &b);
+    assert_eq!(a, r);
+
+    // Higher card index first, sorted descending, no duplicates
+    for c in &a {
+        assert!(c.0 > c.1);
+    }
+    for w in a.windows(2) {
+        assert!(w[0] > w[1]);
+    }
+}
+
+#[test]
+fn permuted_ranges_produce_identical_solutions() {
+    let config_a = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,AKs", "QQ,JJ,AQs", 10.0, 20.0, 200).unwrap();
+    let config_b = RiverSolverConfig::new("Ks9d4c7h2s", "AKs,KK,AA", "AQs,JJ,QQ", 10.0, 20.0, 200).unwrap();
+    let a = solve_river(&config_a);
+    let b = solve_river(&config_b);
+
+    assert_eq!(a.cache_path(), b.cache_path());
+    assert_eq!(a.oop_combos, b.oop_combos);
+    assert_eq!(a.ip_combos, b.ip_combos);
+    assert_eq!(a.strategies.len(), b.strategies.len());
+    for (sa, sb) in a.strategies.iter().zip(&b.strategies) {
+        assert_eq!(sa.node_id, sb.node_id);
+        assert_eq!(sa.frequencies, sb.frequencies);
+    }
+    assert_eq!(a.combo_order_version, COMBO_ORDER_VERSION);
+}
+
+#[test]
+fn combo_order_survives_serialization() {
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,AKs", "QQ", 10.0, 20.0, 50).unwrap();
+    let solution = solve_river(&config);
+    let bytes = bincode::serialize(&solution).unwrap();
+    let restored: RiverSolution = bincode::deserialize(&bytes).unwrap();
+
+    assert_eq!(restored.oop_combos, solution.oop_combos);
+    assert_eq!(restored.ip_combos, solution.ip_combos);
+    assert_eq!(restored.combo_order_version, solution.combo_order_version);
+}
+
+#[test]
+fn combo_index_accepts_either_card_order() {
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,AKs", "QQ", 10.0, 20.0, 10).unwrap();
+    let solution = solve_river(&config);
+
+    let idx = solution.oop_combo_index("AhKh").expect("AhKh in range");
+    assert_eq!(solution.oop_combo_index("KhAh"), Some(idx));
+    assert_eq!(solution.oop_combos[idx], "AhKh");
+    assert!(solution.ip_combo_index("QdQc").is_some());
+    assert_eq!(solution.oop_combo_index("QdQc"), None);
+    assert_eq!(canonical_combo_string("KhAh").as_deref(), Some("AhKh"));
+    assert_eq!(canonical_combo_string("AhAh"), None);
+}
+
+#[test]
+fn json_export_is_versioned_and_combo_indexed() {
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,AKs", "QQ", 10.0, 20.0, 50).unwrap();
+    let solution = solve_river(&config);
+    let json: serde_json::Value = serde_json::from_str(&gto_cli::solution_json::to_json(&solution).unwrap()).unwrap();
+
+    assert_eq!(json["schema_version"], gto_cli::solution_json::SCHEMA_VERSION);
+    assert_eq!(json["oop_combos"].as_array().unwrap().len(), solution.oop_combos.len());
+    let root = &json["strategies"][0];
+    let acting = if root["player"] == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+    assert_eq!(root["frequencies"].as_array().unwrap().len(), acting.len());
+    let summary = json["summary"]["decisions"].as_array().unwrap();
+    assert_eq!(summary.len(), solution.summary().decisions.len());
+    assert_eq!(summary[0]["actions"], root["actions"]);
+
+    // A solution without combos still exports as valid, versioned JSON. The
+    // config rejects fully blocked ranges, so empty the solved one directly.
+    let mut solution = solution;
+    solution.oop_combos.clear();
+    solution.ip_combos.clear();
+    solution.strategies.clear();
+    let empty: serde_json::Value =
+        serde_json::from_str(&gto_cli::solution_json::to_json(&solution).unwrap()).unwrap();
+    assert_eq!(empty["oop_combos"], serde_json::json!([]));
+    assert_eq!(empty["strategies"], serde_json::json!([]));
+    assert_eq!(empty["schema_version"], gto_cli::solution_json::SCHEMA_VERSION);
+}
+
+#[test]
+fn breakdown_accounts_for_every_combo_of_the_root_range() {
+    use gto_cli::breakdown::{HandClass, HandGroup};
+
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,K9s,QhJh,T8s", "QQ,JJ", 10.0, 20.0, 200).unwrap();
+    let solution = solve_river(&config);
+    let breakdown = solution.breakdown();
+    let root = &breakdown.decisions[0];
+    assert_eq!(root.actions.len(), solution.strategies[0].actions.len());
+
+    // Every OOP combo takes some root action in full
+    let total: f64 = root.actions.iter().map(|a| a.combos(None)).sum();
+    assert!((total - solution.oop_combos.len() as f64).abs() < 1e-6, "{} combos", total);
+    let classes = |class: HandClass| -> f64 {
+        root.actions.iter().flat_map(|a| &a.classes).filter(|c| c.class == class).map(|c| c.combos).sum()
+    };
+    assert!((classes(HandClass::TwoPair) - 2.0).abs() < 1e-6);
+    assert!((classes(HandClass::Overpair) - 6.0).abs() < 1e-6);
+    assert!(root.actions.iter().all(|a| a.classes.iter().all(|c| c.group == c.class.group())));
+
+    // Bets carry the bluff:value ratio their size supports
+    for (action, tree_action) in root.actions.iter().zip(&solution.strategies[0].actions) {
+        assert_eq!(action.balanced_bluff_to_value.is_some(), tree_action.starts_with("Bet"), "{}", tree_action);
+    }
+    assert!(root.actions.iter().flat_map(|a| &a.classes).any(|c| c.group == HandGroup::Bluff));
+
+    let json: serde_json::Value = serde_json::from_str(&gto_cli::solution_json::to_json(&solution).unwrap()).unwrap();
+    assert_eq!(json["breakdown"]["decisions"].as_array().unwrap().len(), breakdown.decisions.len());
+}
+
+#[test]
+fn summary_splits_the_root_and_weights_responses_by_the_line() {
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,AKs,T8s,65s:0.5", "QQ,JJ,AQs", 10.0, 20.0, 300).unwrap();
+    let solution = solve_river(&config);
+    let summary = solution.summary();
+
+    let root = &summary.decisions[0];
+    assert_eq!((root.player.as_str(), root.line.len()), ("OOP", 0));
+    assert_eq!(root.actions, solution.strategies[0].actions);
+    // One IP decision for each OOP root action
+    assert_eq!(summary.decisions.len(), 1 + root.actions.len());
+    for decision in &summary.decisions {
+        let total: f64 = decision.frequencies.iter().sum();
+        assert!((total - 1.0).abs() < 1e-6, "{:?} sums to {}", decision.line, total);
+    }
+    let vs_check = &summary.decisions[1];
+    assert_eq!((vs_check.player.as_str(), vs_check.line.clone()), ("IP", vec!["Check".to_string()]));
+
+    // IP's answer to the check averages its combos evenly: OOP checks with
+    // hands that block IP's about equally
+    let node = solution.strategies.iter().find(|s| s.node_id == vs_check.node_id).unwrap();
+    let raw = node.frequencies.iter().map(|f| f[0]).sum::<f64>() / node.frequencies.len() as f64;
+    assert!((vs_check.frequencies[0] - raw).abs() < 0.1, "{} vs {}", vs_check.frequencies[0], raw);
+}
+
+#[test]
+fn progress_interval_records_convergence_history() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,AKs", "QQ,JJ,AQs", 10.0, 20.0, 50).unwrap();
+    config.progress_interval = Some(20);
+    let solution = solve_river(&config);
+
+    let iterations: Vec<usize> = solution.convergence_history.iter().map(|&(i, _)| i).collect();
+    assert_eq!(iterations, vec![20, 40, 50]);
+    assert_eq!(solution.convergence_history.last().unwrap().1, solution.exploitability);
+    assert!(solution.convergence_history[0].1 >= solution.exploitability);
+
+    config.progress_interval = None;
+    assert!(solve_river(&config).convergence_history.is_empty());
+}
+
+#[test]
+fn target_exploitability_stops_before_the_cap() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,AKs", "QQ,JJ,AQs", 10.0, 20.0, 5000).unwrap();
+    config.target_exploitability = Some(2.0);
+    let solution = solve_river(&config);
+
+    assert!(solution.iterations < 5000, "ran {} iterations", solution.iterations);
+    assert!(solution.exploitability <= 0.02 * 10.0);
+    assert_eq!(solution.convergence_history.last(), Some(&(solution.iterations, solution.exploitability)));
+    assert_eq!(solution.quality.as_ref().unwrap().iterations, solution.iterations);
+}
+
+#[test]
+fn relabeled_solution_matches_solving_the_isomorphic_board() {
+    let ranges = ("AA,KK,AKs,K9s", "QQ,JJ,AQs,T9s");
+    let mut solution = solve_river(&RiverSolverConfig::new("Ks9d4c7h2s", ranges.0, ranges.1, 10.0, 20.0, 300).unwrap());
+    let direct = solve_river(&RiverSolverConfig::new("Kh9c4d7s2h", ranges.0, ranges.1, 10.0, 20.0, 300).unwrap());
+
+    solution.relabel_to("Kh9c4d7s2h").unwrap();
+    assert_eq!(solution.board, direct.board);
+    assert_eq!(solution.oop_combos, direct.oop_combos);
+    assert_eq!(solution.ip_combos, direct.ip_combos);
+    for (relabeled, solved) in solution.strategies.iter().zip(&direct.strategies) {
+        for (a, b) in relabeled.frequencies.iter().flatten().zip(solved.frequencies.iter().flatten()) {
+            assert!((a - b).abs() < 1e-3, "node {}: {} vs {}", solved.node_id, a, b);
+        }
+    }
+    assert_eq!(solution.oop_showdown_outcomes.len(), direct.oop_showdown_outcomes.len());
+
+    assert!(solution.relabel_to("Kh9h4d7s2h").is_err());
+}
+
+#[test]
+fn dcfr_reaches_cfr_plus_equilibrium_in_fewer_iterations() {
+    let config = |variant, iterations| {
+        let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,T8s,65s", "QQ,JJ,AQs", 10.0, 20.0, iterations).unwrap();
+        config.bet_sizes = vec![0.75];
+        config.max_raises = 1;
+        config.cfr_variant = variant;
+        config
+    };
+    let reference = solve_river(&config(CfrVariant::CfrPlus, 3000));
+    let cfr_plus = solve_river(&config(CfrVariant::CfrPlus, 200));
+    let dcfr = solve_river(&config(CfrVariant::DCFR, 200));
+
+    // OOP's opening strategy: value-bet the overpairs, mix the bluffs
+    for (a, b) in reference.strategies[0].frequencies.iter().flatten().zip(dcfr.strategies[0].frequencies.iter().flatten()) {
+        assert!((a - b).abs() < 0.02, "CFR+ {:.3} vs DCFR {:.3}", a, b);
+    }
+    assert!(
+        dcfr.exploitability * 5.0 < cfr_plus.exploitability,
+        "DCFR {:.3} vs CFR+ {:.3} after 200 iterations",
+        dcfr.exploitability,
+        cfr_plus.exploitability
+    );
+}
+
+#[test]
+fn custom_sizes_cap_at_the_stack_and_key_the_cache() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,T8s", "QQ,JJ,AQs", 10.0, 8.0, 200).unwrap();
+    let default_path = solve_river(&config).cache_path();
+    config.bet_sizes = vec![0.5, 2.0];
+    config.add_allin = false;
+    let solution = solve_river(&config);
+
+    // A 2x pot bet doesn't fit behind 8: it's the all-in
+    assert_eq!(solution.strategies[0].actions, ["Check", "Bet 5.0", "Bet 8.0"]);
+
+    let path = solution.cache_path();
+    assert_ne!(path, default_path);
+    assert!(path.to_string_lossy().ends_with("_s50-200.bin"), "{}", path.display());
+}
+
+#[test]
+fn custom_raising_is_kept_on_the_solution_and_keys_the_cache() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,T8s", "QQ,JJ,AQs", 10.0, 100.0, 200).unwrap();
+    let default = solve_river(&config);
+    config.raise_sizes = vec![0.5, 1.0];
+    config.max_raises = 1;
+    let solution = solve_river(&config);
+    assert_eq!(solution.tree_config(), config.tree_config());
+
+    // Facing the first bet: the sizes plus the all-in, and no re-raise
+    let raises = |actions: &[String]| actions.iter().filter(|a| a.starts_with("Raise")).count();
+    let facing = |s: &RiverSolution| s.strategies.iter().find(|n| n.actions[0] == "Fold").unwrap().actions.clone();
+    assert_eq!(raises(&facing(&default)), 2);
+    assert_eq!(raises(&facing(&solution)), 3);
+    assert!(solution.strategies.iter().filter(|n| n.actions[0] == "Fold").all(|n| raises(&n.actions) != 1));
+
+    let path = solution.cache_path();
+    assert_ne!(path, default.cache_path());
+    assert!(path.to_string_lossy().ends_with("_raise50-100x1.bin"), "{}", path.display());
+
+    let nodes = config.validate_tree_size().unwrap();
+    config.max_tree_nodes = nodes - 1;
+    let too_large = SolverError::TreeTooLarge { nodes, limit: nodes - 1 };
+    assert_eq!(config.validate_tree_size(), Err(too_large.clone()));
+    assert_eq!(try_solve_river(&config).unwrap_err(), too_large);
+}
+
+#[test]
+fn builder_requires_the_spot_and_reports_typed_errors() {
+    let config = RiverSolverConfig::builder()
+        .board("Ks9d4c7h2s")
+        .oop("AA,KK")
+        .ip("QQ,JJ")
+        .pot(6.0)
+        .stack(20.0)
+        .build()
+        .unwrap();
+    assert_eq!(config.starting_pot, 6.0);
+    assert_eq!(config.iterations, 10_000);
+
+    let missing = RiverSolverConfig::builder().board("Ks9d4c7h2s").oop("AA").build();
+    assert_eq!(missing.err(), Some(SolverError::MissingField { field: "ip range" }));
+    let bad_pot = RiverSolverConfig::builder().board("Ks9d4c7h2s").oop("AA").ip("KK").pot(0.0).build();
+    assert!(matches!(bad_pot, Err(SolverError::InvalidConfig { field: "pot", .. })));
+}
+
+// ---------------------------------------------------------------------------
+// CSV export
+// ---------------------------------------------------------------------------
+
+/// Split one CSV record, honoring quoted fields with doubled quotes.
+fn parse_csv_record(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+#[test]
+fn csv_export_has_a_row_per_node_combo_and_action() {
+    use gto_cli::postflop_tree::{build_tree, walk_line};
+    use gto_cli::strategy_csv::{to_csv, HEADER};
+
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,AK", "QQ,JJ", 10.0, 20.0, 200).unwrap();
+    let solution = solve_river(&config);
+    let csv = to_csv(&solution);
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(HEADER));
+    let rows: Vec<Vec<String>> = lines.map(parse_csv_record).collect();
+
+    let expected: usize = solution
+        .strategies
+        .iter()
+        .map(|s| {
+            let combos = if s.player == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+            combos.len() * s.actions.len()
+        })
+        .sum();
+    assert_eq!(rows.len(), expected);
+    assert!(rows.iter().all(|r| r.len() == 8 && r[0] == "Ks9d4c7h2s" && r[7].is_empty()));
+
+    // Each row's line walks the tree to its node, and frequencies match
+    let (tree, _) = build_tree(&solution.tree_config());
+    for row in &rows {
+        let node_id: u16 = row[1].parse().unwrap();
+        let steps: Vec<String> = if row[3] == "root" { vec![] } else { row[3].split(" > ").map(String::from).collect() };
+        match walk_line(&tree, &steps).unwrap() {
+            gto_cli::postflop_tree::TreeNode::Action { node_id: id, .. } => assert_eq!(*id, node_id, "{}", row[3]),
+            _ => panic!("line {} doesn't end on a decision", row[3]),
+        }
+        let node = solution.strategies.iter().find(|s| s.node_id == node_id).unwrap();
+        let combos = if node.player == "OOP" { &solution.oop_combos } else { &solution.ip_combos };
+        let c = combos.iter().position(|combo| *combo == row[4]).unwrap();
+        let a = node.actions.iter().position(|action| *action == row[5]).unwrap();
+        assert!((row[6].parse::<f64>().unwrap() - node.frequencies[c][a]).abs() < 1e-6);
+    }
+    assert_eq!(parse_csv_record("a,\"Ah,Kh\",\"x\"\"y\""), ["a", "Ah,Kh", "x\"y"]);
+}
+
+#[test]
+fn combo_weights_replace_hand_frequencies() {
+    // Per-combo weights of 1 for AA and 0.5 for KK solve as "AA,KK:0.5" does
+    let hand_weighted = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK:0.5,T8s", "QQ,JJ,AQs", 10.0, 20.0, 300).unwrap();
+    let mut combo_weighted = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,T8s", "QQ,JJ,AQs", 10.0, 20.0, 300).unwrap();
+    let kk = ["Kh", "Kd", "Kc"];
+    let weights: Vec<f64> = combo_weighted
+        .combos(Player::OOP)
+        .iter()
+        .map(|c| if kk.iter().any(|k| c.to_string().contains(k)) { 0.5 } else { 1.0 })
+        .collect();
+    combo_weighted.oop_combo_weights = Some(weights.clone());
+    combo_weighted.validate_combo_weights().unwrap();
+
+    let expected = solve_river(&hand_weighted);
+    let solution = solve_river(&combo_weighted);
+    assert_eq!(solution.oop_combo_weights, weights);
+    assert_eq!(solution.oop_combos, expected.oop_combos);
+    for (got, want) in solution.strategies.iter().zip(&expected.strategies) {
+        for (g, w) in got.frequencies.iter().flatten().zip(want.frequencies.iter().flatten()) {
+            assert!((g - w).abs() < 1e-9, "node {}: {} vs {}", got.node_id, g, w);
+        }
+    }
+    // Range statistics count each combo at its weight
+    let (got, want) = (solution.summary(), expected.summary());
+    for (g, w) in got.decisions.iter().zip(&want.decisions) {
+        for (a, b) in g.frequencies.iter().zip(&w.frequencies) {
+            assert!((a - b).abs() < 1e-9, "{:?}: {} vs {}", g.line, a, b);
+        }
+    }
+    assert_ne!(solution.cache_path(), expected.cache_path());
+}
+
+#[test]
+fn combo_weights_must_match_the_expanded_combos() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK", "QQ,JJ", 10.0, 20.0, 100).unwrap();
+    // KK loses three combos to the K on board: 6 + 3
+    assert_eq!(config.combos(Player::OOP).len(), 9);
+    config.oop_combo_weights = Some(vec![1.0; 12]);
+    assert!(matches!(config.validate_combo_weights(), Err(SolverError::InvalidConfig { .. })));
+    assert_eq!(try_solve_river(&config).err(), config.validate_combo_weights().err());
+    config.oop_combo_weights = Some(vec![0.0; 9]);
+    assert_eq!(config.validate_combo_weights(), Err(SolverError::EmptyRange { side: "OOP" }));
+    config.oop_combo_weights = None;
+    config.ip_combo_weights = Some(vec![-1.0; 12]);
+    assert!(config.validate_combo_weights().is_err());
+}
+
+#[test]
+fn combo_weights_csv_fills_unlisted_combos_with_zero() {
+    let config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK", "QQ,JJ", 10.0, 20.0, 100).unwrap();
+    let combos = config.combos(Player::OOP);
+    let weights = parse_combo_weights_csv("combo,weight\n# reach at the river\nAdAh,0.8\n\nKdKh, 0.25\n", &combos).unwrap();
+    assert_eq!(weights.len(), combos.len());
+    let weight_of = |combo: &str| {
+        let canonical = canonical_combo_string(combo).unwrap();
+        weights[combos.iter().position(|c| c.to_string() == canonical).unwrap()]
+    };
+    assert_eq!(weight_of("AhAd"), 0.8);
+    assert_eq!(weight_of("KhKd"), 0.25);
+    assert_eq!(weights.iter().filter(|&&w| w == 0.0).count(), combos.len() - 2);
+
+    // The board's Ks blocks KsKh; QQ isn't OOP's
+    assert!(parse_combo_weights_csv("KsKh,1", &combos).unwrap_err().contains("isn't in the range"));
+    assert!(parse_combo_weights_csv("QhQd,1", &combos).is_err());
+    assert!(parse_combo_weights_csv("AhAd,1\nAdAh,1", &combos).unwrap_err().contains("twice"));
+    assert!(parse_combo_weights_csv("AhAd,lots", &combos).unwrap_err().contains("Line 1"));
+}
+
+#[test]
+fn time_budget_stops_the_solve_and_records_the_iterations_run() {
+    let mut config = RiverSolverConfig::new("Ks9d4c7h2s", "AA,KK,AKs", "QQ,JJ,AQs", 10.0, 20.0, 10_000_000).unwrap();
+    config.time_budget = Some(std::time::Duration::from_millis(200));
+    let start = std::time::Instant::now();
+    let solution = solve_river(&config);
+    assert!(start.elapsed() < std::time::Duration::from_secs(5), "{:?}", start.elapsed());
+    assert!(solution.iterations > 0 && solution.iterations < config.iterations, "{}", solution.iterations);
+    assert!(solution.convergence_history.is_empty());
+    // However few iterations fit, the timed solve isn't re-run for the floor
+    assert!(solution.time_limited && solution.meets_quality_floor());
+
+    // With a progress callback and no interval, snapshots come by the clock
+    fn ignore(_: &gto_cli::progress::SolveProgress) {}
+    config.on_progress = Some(ignore);
+    let solution = solve_river(&config);
+    let history = &solution.convergence_history;
+    assert!(history.len() >= 2, "{:?}", history);
+    assert_eq!(history.last().unwrap().0, solution.iterations);
+}
@@ -0,0 +1,114 @@
+//! Tests for exit codes and the `--status-json` line.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use gto_cli::cache_index::CACHE_DIR_ENV;
+
+/// Run `gto` with `--status-json` and its solver cache in `cache`; returns
+/// the exit code and the parsed status line (the last line of stderr).
+fn run(cache: &Path, args: &[&str]) -> (i32, serde_json::Value) {
+    let output = Command::new(env!("CARGO_BIN_EXE_gto"))
+        .args(args)
+        .arg("--status-json")
+        .env(CACHE_DIR_ENV, cache)
+        .output()
+        .expect("run gto");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = stderr.lines().last().unwrap_or_default();
+    let status = serde_json::from_str(line).unwrap_or_else(|e| panic!("bad status line {:?}: {}", line, e));
+    (output.status.code().unwrap_or(-1), status)
+}
+
+fn temp_cache(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gto-status-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn success_exits_zero_with_ok_status() {
+    let dir = temp_cache("ok");
+    let (code, status) = run(&dir, &["odds", "10", "5"]);
+    assert_eq!(code, 0);
+    assert_eq!(status["command"], "odds");
+    assert_eq!(status["ok"], true);
+    assert_eq!(status["exit_code"], 0);
+    assert!(status["duration_ms"].is_u64());
+    assert!(status.get("error").is_none());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn invalid_board_exits_two() {
+    let dir = temp_cache("board");
+    let (code, status) = run(&dir, &["equity", "AhAd", "KK", "--board", "XxYyZz"]);
+    assert_eq!(code, 2);
+    assert_eq!(status["ok"], false);
+    assert_eq!(status["exit_code"], 2);
+    assert!(status["error"].as_str().is_some_and(|e| !e.is_empty()));
+
+    // Usage errors caught by the argument parser use the same code
+    let (code, status) = run(&dir, &["equity"]);
+    assert_eq!(code, 2);
+    assert_eq!(status["command"], "equity");
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn hero_seat_without_villain_range_exits_two() {
+    let dir = temp_cache("villain");
+    let (code, status) = run(
+        &dir,
+        &["solve", "river", "--board", "Ks9d4c7hQc", "--hero-pos", "BB", "--villain-pos", "BTN", "--hero", "AA"],
+    );
+    assert_eq!(code, 2);
+    assert_eq!(status["error"], "--villain range required");
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn unwritable_cache_exits_three() {
+    // A file in the cache dir's path makes it uncreatable, even as root
+    let dir = temp_cache("cache");
+    std::fs::write(dir.join("file"), "").unwrap();
+    let (code, status) = run(
+        &dir.join("file").join("solver"),
+        &["solve", "river", "--board", "Ks9d4c7hQc", "--oop", "AA", "--ip", "KK", "-i", "1000"],
+    );
+    assert_eq!(code, 3);
+    assert_eq!(status["command"], "solve river");
+    assert_eq!(status["exit_code"], 3);
+    // Cached under the canonical suits shared by every isomorphic board
+    assert!(status["cache_path"].as_str().is_some_and(|p| p.contains("river_Ks9h4d7cQd")));
+    assert!(status["error"].as_str().is_some_and(|e| e.contains("Cannot write cache")));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn missing_cache_file_exits_three() {
+    let dir = temp_cache("info");
+    let (code, status) = run(&dir, &["cache", "info", "river_nothing.bin"]);
+    assert_eq!(code, 3);
+    assert_eq!(status["exit_code"], 3);
+    assert!(status["error"].as_str().is_some_and(|e| e.contains("river_nothing.bin")));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn timed_solve_short_of_its_target_exits_five() {
+    let dir = temp_cache("timed");
+    let (code, status) = run(
+        &dir,
+        &[
+            "solve", "river", "--board", "Ks9d4c7hQc", "--oop", "AA,KK,QQ,AK", "--ip", "KK,QQ,JJ,AQ",
+            "--time", "1s", "--target-exploitability", "0.0000001",
+        ],
+    );
+    assert_eq!(code, 5);
+    assert_eq!(status["exit_code"], 5);
+    assert!(status["error"].as_str().is_some_and(|e| e.contains("not reached")));
+    // The partial solve is still saved
+    assert!(status["cache_path"].as_str().is_some_and(|p| Path::new(p).exists()));
+    std::fs::remove_dir_all(&dir).ok();
+}
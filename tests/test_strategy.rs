@@ -0,0 +1,101 @@
+//! Tests for strategy lookups against cached solves.
+//!
+//! Solver caches live under `$HOME/.gto-cli`, so this binary points HOME at a
+//! scratch directory. Keep it to tests that can share that directory.
+
+use gto_cli::preflop_solver::Position;
+use gto_cli::river_solver::{solve_river, RiverSolverConfig};
+use gto_cli::strategy::{postflop_seats, PostflopSpot, PotType, StrategyEngine, StrategySource};
+
+#[test]
+fn manual_solve_with_seats_is_used_by_query() {
+    let home = std::env::temp_dir().join(format!("gto-strategy-{}", std::process::id()));
+    std::fs::remove_dir_all(&home).ok();
+    std::fs::create_dir_all(&home).unwrap();
+    std::env::set_var("HOME", &home);
+
+    // `gto solve river ... --oop-pos BB --ip-pos BTN`
+    let (oop_pos, ip_pos) = postflop_seats("BB", "BTN").unwrap();
+    let config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK,K9s", "QQ,JJ,AK", 6.0, 97.0, 1000).unwrap();
+    let mut solution = solve_river(&config);
+    solution.oop_pos = oop_pos.as_str().to_string();
+    solution.ip_pos = ip_pos.as_str().to_string();
+    solution.save_cache();
+
+    // No preflop solution exists here, so a cache miss would fail instead of re-solving
+    let spot = PostflopSpot::new(Position::BTN, Position::BB, "Ks9d4c7hQc", 6.0, 97.0);
+    let mut engine = StrategyEngine::new(97.0);
+    assert!(!engine.has_preflop());
+    let result = engine
+        .query_postflop("QhQd", &spot, 300)
+        .expect("cached manual solve is found");
+    assert_eq!(result.source, StrategySource::Cached);
+    assert!((result.frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+
+    // A suit relabeling of the board (s->h, h->s, d->c, c->d) reads the same
+    // solve, with the hand relabeled the same way
+    let relabeled = engine
+        .query_postflop("QsQc", &PostflopSpot::new(Position::BTN, Position::BB, "Kh9c4d7sQd", 6.0, 97.0), 300)
+        .expect("isomorphic board hits the cache");
+    assert_eq!(relabeled.source, StrategySource::Cached);
+    assert_eq!(relabeled.actions, result.actions);
+    assert_eq!(relabeled.frequencies, result.frequencies);
+
+    // The same seats in a 3-bet pot are cached under their own key
+    let err = engine
+        .query_postflop("QhQd", &spot.with_pot_type(PotType::ThreeBet), 300)
+        .err()
+        .unwrap();
+    assert!(err.contains("preflop"), "{}", err);
+
+    // A different matchup misses the cache and needs preflop ranges to solve
+    let err = engine
+        .query_postflop("QhQd", &PostflopSpot { hero: Position::CO, ..spot }, 300)
+        .err()
+        .unwrap();
+    assert!(err.contains("preflop"), "{}", err);
+
+    // A forced solve below the quality floor is cached but never served
+    let config = RiverSolverConfig::new("Ks9d4c7hQc", "AA,KK,K9s", "QQ,JJ,AK", 6.0, 97.0, 300).unwrap();
+    let mut solution = solve_river(&config);
+    solution.oop_pos = oop_pos.as_str().to_string();
+    solution.ip_pos = ip_pos.as_str().to_string();
+    assert!(!solution.meets_quality_floor());
+    solution.save_cache();
+    let err = engine
+        .query_postflop("QhQd", &spot, 300)
+        .err()
+        .unwrap();
+    assert!(err.contains("preflop"), "{}", err);
+
+    // Overriding both ranges needs no preflop solution and never reads the
+    // position-keyed solve
+    let mut exploit = StrategyEngine::new(97.0)
+        .with_hero_range(Some("QQ,AK".to_string()))
+        .with_villain_range(Some("AA,KK,K9s".to_string()))
+        .with_force(true);
+    assert!(exploit.is_exploitative());
+    let result = exploit
+        .query_postflop("QhQd", &spot, 300)
+        .expect("explicit ranges solve without a preflop solution");
+    assert_eq!(result.source, StrategySource::SolvedOnDemand);
+
+    // An override with nothing left on the board fails before solving
+    let err = StrategyEngine::new(97.0)
+        .with_hero_range(Some("KsQs".to_string()))
+        .with_villain_range(Some("AA".to_string()))
+        .query_postflop("QhQd", &spot, 300)
+        .err()
+        .unwrap();
+    assert!(err.contains("Hero range 'KsQs'"), "{}", err);
+
+    std::fs::remove_dir_all(&home).ok();
+}
+
+#[test]
+fn query_rejects_a_hand_that_shares_a_board_card() {
+    let mut engine = StrategyEngine::new(100.0);
+    let spot = PostflopSpot::new(Position::BTN, Position::BB, "KsQd4c", 6.0, 97.0);
+    let result = engine.query_postflop("AhKs", &spot, 300);
+    assert!(matches!(result, Err(ref e) if e.contains("Ks")));
+}
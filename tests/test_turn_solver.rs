@@ -1,6 +1,15 @@
 //! Tests for the turn solver.
 
-use gto_cli::turn_solver::{solve_turn, TurnSolverConfig};
+mod common;
+
+use common::ScratchCache;
+use gto_cli::error::SolverError;
+use gto_cli::flop_solver::{solve_flop, FlopSolverConfig};
+use gto_cli::postflop_tree::{build_tree, Player, TreeNode};
+use gto_cli::postprocess::Simplify;
+use gto_cli::preflop_solver::Position;
+use gto_cli::strategy::{PostflopSpot, StrategyEngine, StrategySource};
+use gto_cli::turn_solver::{solve_turn, try_solve_turn, TurnNodeStrategy, TurnSolution, TurnSolverConfig};
 
 // ---------------------------------------------------------------------------
 // Config validation
@@ -125,6 +134,16 @@ fn solver_exploitability_reasonable() {
         "Exploitability should be reasonable, got {:.4}",
         result.exploitability
     );
+    assert!((result.exploitability_pct_pot - result.exploitability * 10.0).abs() < 1e-9);
+
+    // Solves cached before the percentage was recorded read it back derived
+    let path = std::env::temp_dir().join(format!("gto-turn-pct-{}.bin", std::process::id()));
+    let mut older = result.clone();
+    older.exploitability_pct_pot = 0.0;
+    older.save_drill(&path);
+    let read = TurnSolution::load_drill(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert!((read.exploitability_pct_pot - result.exploitability_pct_pot).abs() < 1e-9);
 }
 
 #[test]
@@ -200,3 +219,216 @@ fn solver_multiple_actions_available() {
         root.actions.len()
     );
 }
+
+#[test]
+fn solver_showdown_outcomes_chop_identical_straights() {
+    // Every river leaves both tens with the broadway straight: no flush is
+    // possible and a paired river gives no one a full house
+    let config = TurnSolverConfig::new("AsKdQhJc", "T9s", "T8s", 10.0, 20.0, 100).unwrap();
+    let result = solve_turn(&config);
+    assert_eq!(result.oop_showdown_outcomes.len(), result.oop_combos.len());
+
+    for (i, combo) in result.oop_combos.iter().enumerate() {
+        if let Some((win, tie, lose)) = result.showdown_outcome("OOP", i) {
+            assert!(tie > 0.99, "{}: expected a chop, got {:?}", combo, (win, tie, lose));
+        }
+    }
+    assert!((0..result.ip_combos.len()).any(|j| result.showdown_outcome("IP", j).is_some()));
+}
+
+#[test]
+fn solver_weighted_range_starts_combos_at_their_frequency() {
+    // QQ wins far fewer showdowns once OOP's air is down to 10%
+    let solve = |oop: &str| solve_turn(&TurnSolverConfig::new("Ks9d4c2h", oop, "QQ", 10.0, 20.0, 200).unwrap());
+    let full = solve("AA,76s");
+    let weighted = solve("AA,76s:0.1");
+    assert_eq!(weighted.oop_weights, vec![1.0, 0.1]);
+    assert_eq!(weighted.ip_weights, vec![1.0]);
+
+    let qq_wins = |outcomes: &[(f64, f64, f64)]| outcomes.iter().map(|o| o.0).sum::<f64>();
+    assert!(
+        qq_wins(&weighted.ip_showdown_outcomes) < qq_wins(&full.ip_showdown_outcomes) * 0.5,
+        "{} vs {}",
+        qq_wins(&weighted.ip_showdown_outcomes),
+        qq_wins(&full.ip_showdown_outcomes)
+    );
+}
+
+#[test]
+fn river_strategies_cover_every_card_after_each_closing_line() {
+    let mut config = TurnSolverConfig::new("Ks9d4c7h", "AA,KK,AQs", "QQ,TT,AJo", 10.0, 50.0, 20).unwrap();
+    let solution = solve_turn(&config);
+    let check_check: Vec<_> = solution.river_strategies.iter().filter(|r| r.turn_line == ["Check", "Check"]).collect();
+    assert_eq!(check_check.len(), 48);
+    assert!(check_check.iter().all(|r| !solution.board.contains(&r.card)));
+    // The river opens with OOP to act, one row per combo
+    let root = &check_check[0].strategies[0];
+    assert_eq!(root.player, "OOP");
+    assert_eq!(root.frequencies.len(), solution.oop_combos.len());
+    assert_eq!(solution.river_subtrees("2C").count(), solution.river_strategies.len() / 48);
+
+    config.extract_rivers = false;
+    assert!(solve_turn(&config).river_strategies.is_empty());
+}
+
+#[test]
+fn action_evs_price_turn_and_river_actions_against_the_solved_ranges() {
+    // AA vs 72o: only a seven or a deuce saves 72o, so AA keeps most of
+    // the pot on the turn and all of it on a blank river
+    let mut config = TurnSolverConfig::new("Ks9d4c2h", "AA", "72o", 10.0, 20.0, 300).unwrap();
+    let solution = solve_turn(&config);
+    let aces: Vec<usize> = (0..solution.oop_combos.len()).collect();
+
+    let root = &solution.strategies[0];
+    let rows = solution.action_evs(root.node_id, Player::OOP, &aces).unwrap();
+    assert_eq!(rows.len(), aces.len());
+    for evs in &rows {
+        assert_eq!(evs.len(), root.actions.len());
+        assert!(evs.iter().all(|&ev| ev > 0.7), "{:?}", evs);
+    }
+    // Not IP's node
+    assert!(solution.action_evs(root.node_id, Player::IP, &[0]).is_none());
+
+    // IP facing OOP's first bet size: folding costs nothing, calling loses
+    let (tree, _) = build_tree(&solution.tree_config().turn);
+    let TreeNode::Action { children, .. } = &tree else { panic!("root is an action node") };
+    let TreeNode::Action { node_id, .. } = &children[1] else { panic!("a bet leads to IP's decision") };
+    let evs = &solution.action_evs(*node_id, Player::IP, &[0]).unwrap()[0];
+    assert!(evs[0].abs() < 1e-9, "{:?}", evs);
+    assert!(evs[1] < 0.0, "{:?}", evs);
+
+    // On the 3s after check-check AA is never behind
+    let river = solution.river_subtrees("3s").find(|r| r.turn_line == ["Check", "Check"]).unwrap();
+    let rows = solution.action_evs(river.strategies[0].node_id, Player::OOP, &aces).unwrap();
+    assert!(rows.iter().flatten().all(|&ev| ev >= 1.0 - 1e-9), "{:?}", rows);
+
+    // Without river play the turn actions can't be priced
+    config.extract_rivers = false;
+    let turn_only = solve_turn(&config);
+    assert!(turn_only.action_evs(turn_only.strategies[0].node_id, Player::OOP, &[0]).is_none());
+}
+
+#[test]
+fn simplification_loss_is_measured_and_grows_with_coarser_rounding() {
+    let config = TurnSolverConfig::new("Ks9d4c2h", "AA,KK,65s,76s", "KQs,KJs,QQ", 10.0, 20.0, 3000).unwrap();
+    let solution = solve_turn(&config);
+    let loss = |simplify: Simplify| solution.simplified(simplify).simplified.unwrap().ev_loss_pct_pot.unwrap();
+
+    assert!(loss(Simplify::new(0.0, false).unwrap()) < 1e-9, "rounding nothing costs nothing");
+    let light = loss(Simplify::new(0.05, false).unwrap());
+    let heavy = loss(Simplify::new(0.30, false).unwrap());
+    let purified = loss(Simplify::new(0.0, true).unwrap());
+    assert!(light >= 0.0);
+    assert!(light <= heavy && heavy <= purified, "{} {} {}", light, heavy, purified);
+    assert!(purified > 0.0);
+}
+
+#[test]
+fn raise_settings_shape_both_streets_and_key_the_cache() {
+    let mut config = TurnSolverConfig::new("Ks9d4c7h", "AA,KK", "QQ,JJ", 10.0, 50.0, 20).unwrap();
+    let default_nodes = config.validate_tree_size().unwrap();
+    let default = solve_turn(&config);
+    config.raise_sizes = vec![0.5, 1.0];
+    config.max_raises = 1;
+    config.river_raise_sizes = vec![0.5, 1.0];
+    config.river_max_raises = 1;
+    let nodes = config.validate_tree_size().unwrap();
+    assert_ne!(nodes, default_nodes);
+    let solution = solve_turn(&config);
+    assert_eq!(solution.tree_config().turn, config.tree_config().turn);
+
+    // Both sizes and the all-in on each street
+    let most_raises = |nodes: &[TurnNodeStrategy]| {
+        nodes.iter().map(|n| n.actions.iter().filter(|a| a.starts_with("Raise")).count()).max().unwrap()
+    };
+    assert_eq!(most_raises(&solution.strategies), 3);
+    assert_eq!(most_raises(&solution.river_strategies[0].strategies), 3);
+
+    // The raising is part of the stem `load_cache_raised` reads back
+    let path = solution.cache_path();
+    assert_ne!(path, default.cache_path());
+    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(name.contains("_raise50-100x1_rvraise50-100x1_r"), "{}", name);
+    let raising = config.raise_suffix();
+    assert_eq!(name, TurnSolution::cache_file_name("Ks9d4c7h", "", "", 10.0, 50.0, &raising, &solution.range_key()));
+
+    config.max_tree_nodes = nodes - 1;
+    let too_large = SolverError::TreeTooLarge { nodes, limit: nodes - 1 };
+    assert_eq!(config.validate_tree_size(), Err(too_large.clone()));
+    assert_eq!(try_solve_turn(&config).unwrap_err(), too_large);
+}
+
+// ---------------------------------------------------------------------------
+// Drill-down from a flop solve
+// ---------------------------------------------------------------------------
+
+#[test]
+fn drill_solves_the_turn_from_the_flop_solves_reach() {
+    let cache = ScratchCache::new("drill");
+
+    let config = FlopSolverConfig::new("Ks9d4c", "AA,KK,AK", "QQ,JJ,KQs", 10.0, 50.0, 10_000).unwrap();
+    let mut flop = solve_flop(&config);
+    flop.oop_pos = "BB".to_string();
+    flop.ip_pos = "BTN".to_string();
+    flop.save_cache();
+
+    // Each combo's reach is its frequency for its own flop action
+    let line = |steps: &[&str]| steps.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let reach = flop.line_reach(&line(&["check", "check"])).unwrap();
+    assert_eq!(reach.line, ["Check", "Check"]);
+    assert_eq!((reach.pot, reach.effective_stack), (10.0, 50.0));
+    let root = &flop.strategies[0];
+    for (r, freqs) in reach.oop.iter().zip(&root.frequencies) {
+        assert!((r - freqs[0]).abs() < 1e-9);
+    }
+    assert!(flop.line_reach(&line(&["check"])).unwrap_err().contains("doesn't close the flop"));
+    assert!(flop.line_reach(&line(&["raise"])).is_err());
+
+    // Only combos that reach the turn and dodge the turn card are solved
+    let weighted = |combos: &[String], reach: &[f64]| combos.iter().cloned().zip(reach.iter().copied()).collect::<Vec<_>>();
+    let turn = TurnSolverConfig::from_combo_reach(
+        "Ks9d4c7h",
+        &weighted(&flop.oop_combos, &reach.oop),
+        &weighted(&flop.ip_combos, &reach.ip),
+        reach.pot,
+        reach.effective_stack,
+        100,
+    )
+    .unwrap();
+    let reached = reach.oop.iter().filter(|&&r| r > 0.0).count();
+    assert!(turn.oop_range.len() <= reached);
+    assert_eq!(turn.oop_weights.iter().copied().fold(0.0, f64::max), 1.0);
+
+    let spot = |board| PostflopSpot::new(Position::BTN, Position::BB, board, 10.0, 50.0);
+    let mut engine = StrategyEngine::new(50.0).with_drill(Some(line(&["check", "check"])));
+    let mut query = || engine.query_postflop("QhQd", &spot("Ks9d4c7h"), 1000);
+    let drilled = query().expect("drills from the cached flop solve");
+    assert_eq!(drilled.source, StrategySource::Drilled);
+    assert!((drilled.frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+    // The drill is cached and read back
+    let drills = std::fs::read_dir(cache.path())
+        .unwrap()
+        .filter_map(|e| e.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("turn_drill_Ks9d4c7h_BB_BTN_"))
+        .count();
+    assert_eq!(drills, 1);
+    assert_eq!(query().unwrap().frequencies, drilled.frequencies);
+
+    // Cache-only, an undrilled turn falls back to the flop solve's turn
+    // template, with its sizes scaled to the pot the flop line built
+    let bet_call = flop.line_reach(&line(&["bet33", "call"])).unwrap();
+    assert!(bet_call.pot > 10.0);
+    let mut cache_only = StrategyEngine::new(50.0).with_drill(Some(line(&["bet33", "call"]))).with_cache_only(true);
+    let templated = cache_only
+        .query_postflop("QhQd", &spot("Ks9d4c2s"), 1000)
+        .expect("answers from the turn template");
+    assert_eq!(templated.source, StrategySource::DerivedFromFlop);
+    assert_eq!(templated.actions[1], format!("Bet {:.1}", (0.66 * bet_call.pot).min(bet_call.effective_stack)));
+
+    let mut flop_board = StrategyEngine::new(50.0).with_drill(Some(line(&["check", "check"])));
+    let err = flop_board
+        .query_postflop("QhQd", &spot("Ks9d4c"), 1000)
+        .err()
+        .unwrap();
+    assert!(err.contains("turn board"), "{}", err);
+}